@@ -0,0 +1,107 @@
+//! # Description
+//!
+//! Derive macro `NcRecord` of the crate [netcdf3](https://docs.rs/netcdf3), re-exported by this one with the feature `derive`.
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, LitStr, Type};
+
+/// Implements the trait `netcdf3::NcRecord` for a struct with named fields, each field being stored in a record variable.
+///
+/// The variable of a field has the name of the field, or the name given by the attribute `#[nc(rename = "var_name")]`.
+#[proc_macro_derive(NcRecord, attributes(nc))]
+pub fn derive_nc_record(input: TokenStream) -> TokenStream {
+    let input: DeriveInput = parse_macro_input!(input as DeriveInput);
+    match impl_nc_record(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Field of the struct and the name of its record variable
+struct RecordField<'a> {
+    ident: &'a Ident,
+    ty: &'a Type,
+    var_name: LitStr,
+}
+
+fn impl_nc_record(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let fields: Vec<RecordField> = parse_fields(input)?;
+    let struct_name: &Ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let add_vars = fields.iter().map(|field: &RecordField| {
+        let ty: &Type = field.ty;
+        let var_name: &LitStr = &field.var_name;
+        quote! {
+            data_set.add_var(#var_name, &[unlim_dim_name], <#ty as ::netcdf3::NcValue>::DATA_TYPE)?;
+        }
+    });
+    let write_fields = fields.iter().map(|field: &RecordField| {
+        let (ident, ty, var_name): (&Ident, &Type, &LitStr) = (field.ident, field.ty, &field.var_name);
+        quote! {
+            <#ty as ::netcdf3::NcValue>::write_record(&self.#ident, file_writer, #var_name, record_index)?;
+        }
+    });
+    let read_fields = fields.iter().map(|field: &RecordField| {
+        let (ident, ty, var_name): (&Ident, &Type, &LitStr) = (field.ident, field.ty, &field.var_name);
+        quote! {
+            #ident: <#ty as ::netcdf3::NcValue>::read_record(file_reader, #var_name, record_index)?,
+        }
+    });
+
+    Ok(quote! {
+        impl #impl_generics ::netcdf3::NcRecord for #struct_name #ty_generics #where_clause {
+            fn add_vars(data_set: &mut ::netcdf3::DataSet, unlim_dim_name: &str) -> ::std::result::Result<(), ::netcdf3::InvalidDataSet> {
+                #(#add_vars)*
+                return ::std::result::Result::Ok(());
+            }
+
+            fn write_record<W: ::std::io::Write + ::std::io::Seek>(&self, file_writer: &mut ::netcdf3::FileWriter<'_, W>, record_index: usize) -> ::std::result::Result<(), ::netcdf3::WriteError> {
+                #(#write_fields)*
+                return ::std::result::Result::Ok(());
+            }
+
+            fn read_record<R: ::std::io::Read + ::std::io::Seek>(file_reader: &mut ::netcdf3::FileReader<R>, record_index: usize) -> ::std::result::Result<Self, ::netcdf3::ReadError> {
+                return ::std::result::Result::Ok(Self {
+                    #(#read_fields)*
+                });
+            }
+        }
+    })
+}
+
+/// Returns the named fields of the struct, and the names of their variables.
+fn parse_fields(input: &DeriveInput) -> Result<Vec<RecordField<'_>>, Error> {
+    let named_fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new(Span::call_site(), "`NcRecord` can only be derived for the structs with named fields")),
+        },
+        _ => return Err(Error::new(Span::call_site(), "`NcRecord` can only be derived for the structs")),
+    };
+    let mut fields: Vec<RecordField> = Vec::with_capacity(named_fields.len());
+    for field in named_fields.iter() {
+        // the field is named
+        let ident: &Ident = field.ident.as_ref().ok_or_else(|| Error::new(Span::call_site(), "unnamed field"))?;
+        let mut var_name: LitStr = LitStr::new(&ident.unraw().to_string(), ident.span());
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("nc")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    var_name = meta.value()?.parse()?;
+                    return Ok(());
+                }
+                Err(meta.error("unsupported `nc` attribute, expected `rename = \"...\"`"))
+            })?;
+        }
+        fields.push(RecordField {
+            ident,
+            ty: &field.ty,
+            var_name,
+        });
+    }
+    Ok(fields)
+}
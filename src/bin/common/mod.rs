@@ -0,0 +1,81 @@
+//! Parsing of the command lines and handling of the errors shared by the command line tools.
+// each tool uses only a part of these items
+#![allow(dead_code)]
+
+use std::process::exit;
+
+/// Exit code of the invalid command lines.
+pub const USAGE_EXIT_CODE: i32 = 2;
+
+/// Returns the arguments of the command line parsed by `parse_args`.
+///
+/// Prints `usage` and exits if the option `-h` (or `--help`) is given, or if the arguments are not valid.
+pub fn parse_command_line<A>(program_name: &str, usage: &str, parse_args: fn(&[String]) -> Result<A, String>) -> A {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg: &String| arg == "-h" || arg == "--help") {
+        print!("{}", usage);
+        exit(0);
+    }
+    return match parse_args(&args) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}: {}\n\n{}", program_name, message, usage);
+            exit(USAGE_EXIT_CODE);
+        }
+    };
+}
+
+/// Returns the value of `result`, or prints the error message and exits with the code `1`.
+pub fn exit_on_error<T>(program_name: &str, result: Result<T, String>) -> T {
+    return match result {
+        Ok(value) => value,
+        Err(message) => {
+            eprintln!("{}: {}", program_name, message);
+            exit(1);
+        }
+    };
+}
+
+/// Splits the arguments into the options and the positional arguments, the latter being returned.
+///
+/// `handle_option` is called with the flag and the value of each option. The value follows the flag (`-o out.nc`)
+/// or is attached to a long flag (`--output=out.nc`), except for the `switches` which have an empty value.
+pub fn parse_options<F>(args: &[String], switches: &[&str], mut handle_option: F) -> Result<Vec<String>, String>
+    where F: FnMut(&str, &str) -> Result<(), String>
+{
+    let mut positional_args: Vec<String> = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let (flag, inline_value): (&str, Option<&str>) = match arg.split_once('=') {
+            Some((flag, value)) if arg.starts_with("--") => (flag, Some(value)),
+            _ => (arg.as_str(), None),
+        };
+        if !flag.starts_with('-') || flag == "-" {
+            positional_args.push(arg.clone());
+            continue;
+        }
+        if switches.contains(&flag) {
+            if inline_value.is_some() {
+                return Err(format!("the option `{}` has no value", flag));
+            }
+            handle_option(flag, "")?;
+            continue;
+        }
+        let value: &str = match inline_value.or_else(|| args.next().map(|value: &String| value.as_str())) {
+            Some(value) => value,
+            None => return Err(format!("missing value of the option `{}`", flag)),
+        };
+        handle_option(flag, value)?;
+    }
+    return Ok(positional_args);
+}
+
+/// Parses a comma-separated list of names, the empty names are ignored.
+pub fn parse_names(value: &str) -> Vec<String> {
+    return value.split(',').map(|name: &str| name.trim().to_string()).filter(|name: &String| !name.is_empty()).collect();
+}
+
+#[cfg(test)]
+pub fn to_args(args: &[&str]) -> Vec<String> {
+    return args.iter().map(|arg: &&str| arg.to_string()).collect();
+}
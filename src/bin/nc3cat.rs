@@ -0,0 +1,87 @@
+//! Concatenates NetCDF-3 files along their *unlimited-size* dimension (see the function `netcdf3::concat_nc3`).
+//!
+//! ```text
+//! nc3cat <INPUT_1> [<INPUT_2> ...] <OUTPUT>
+//! ```
+mod common;
+
+use std::fs::File;
+use std::io::BufWriter;
+
+use netcdf3::{concat_nc3, ConcatError, FileReader};
+
+const USAGE: &str = "\
+Usage: nc3cat <INPUT_1> [<INPUT_2> ...] <OUTPUT>
+
+Concatenates the records of the NetCDF-3 files INPUT_1, INPUT_2, etc. into OUTPUT.
+
+The input files must define the same dimensions and the same variables. The attributes and the fixed-size variables
+are copied from the first input file.
+
+Options:
+  -h, --help    Prints this message
+";
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    input_file_paths: Vec<String>,
+    output_file_path: String,
+}
+
+fn main() {
+    let args: Args = common::parse_command_line("nc3cat", USAGE, parse_args);
+    common::exit_on_error("nc3cat", run(&args));
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    let mut file_readers: Vec<FileReader> = vec![];
+    for input_file_path in args.input_file_paths.iter() {
+        let file_reader = FileReader::open(input_file_path).map_err(|err| format!("cannot read `{}`: {}", input_file_path, err))?;
+        file_readers.push(file_reader);
+    }
+    let output_file = File::create(&args.output_file_path)
+        .map_err(|err| format!("cannot create `{}`: {}", args.output_file_path, err))?;
+    let output: BufWriter<File> = concat_nc3(&mut file_readers, BufWriter::new(output_file))
+        .map_err(|err: ConcatError| match err {
+            ConcatError::DimensionMismatch{file_index, dim_name} => {
+                format!("the dimension `{}` of `{}` differs from the first file", dim_name, args.input_file_paths[file_index])
+            },
+            ConcatError::VariableMismatch{file_index, var_name} => {
+                format!("the variable `{}` of `{}` differs from the first file", var_name, args.input_file_paths[file_index])
+            },
+            err => format!("cannot concatenate the files into `{}`: {}", args.output_file_path, err),
+        })?;
+    output.into_inner().map_err(|err| format!("cannot write `{}`: {}", args.output_file_path, err.error()))?;
+    return Ok(());
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let positional_args: Vec<String> = common::parse_options(args, &[], |flag: &str, _value: &str| {
+        return Err(format!("unknown option `{}`", flag));
+    })?;
+    if positional_args.len() < 2 {
+        return Err(String::from("expected at least one input file and one output file"));
+    }
+    let (output_file_path, input_file_paths): (&String, &[String]) = positional_args.split_last().unwrap();
+    return Ok(Args{
+        input_file_paths: input_file_paths.to_vec(),
+        output_file_path: output_file_path.clone(),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{parse_args, Args};
+    use super::common::to_args;
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            Ok(Args{input_file_paths: to_args(&["in_1.nc", "in_2.nc"]), output_file_path: String::from("out.nc")}),
+            parse_args(&to_args(&["in_1.nc", "in_2.nc", "out.nc"]))
+        );
+        assert!(parse_args(&to_args(&["in.nc"])).is_err());
+        assert!(parse_args(&to_args(&["--vars", "in.nc", "out.nc"])).is_err());
+    }
+}
@@ -0,0 +1,131 @@
+//! Copies a NetCDF-3 file, possibly converting its version, reserving space for the header,
+//! selecting a subset of the variables and aligning the data part (see the function `netcdf3::copy_nc3`).
+//!
+//! ```text
+//! nc3copy [OPTIONS] <INPUT> <OUTPUT>
+//! ```
+mod common;
+
+use std::fs::File;
+use std::io::BufWriter;
+
+use netcdf3::{copy_nc3, CopyOptions, FileReader, Version};
+
+const USAGE: &str = "\
+Usage: nc3copy [OPTIONS] <INPUT> <OUTPUT>
+
+Copies the NetCDF-3 file INPUT into OUTPUT.
+
+Options:
+  -k, --kind <KIND>             Version of the output file: `classic` (or `1`) or `64-bit-offset` (or `2`),
+                                the version of the input file is kept by default
+  -r, --header-reserve <BYTES>  Minimum number of bytes reserved for the header of the output file
+  -v, --vars <VAR_1,VAR_2,...>  Names of the copied variables, all the variables are copied by default
+  -a, --align <BYTES>           Alignment of the beginning of the data part of the output file
+  -h, --help                    Prints this message
+";
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    input_file_path: String,
+    output_file_path: String,
+    options: CopyOptions,
+}
+
+fn main() {
+    let args: Args = common::parse_command_line("nc3copy", USAGE, parse_args);
+    common::exit_on_error("nc3copy", run(&args));
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    let mut file_reader = FileReader::open(&args.input_file_path)
+        .map_err(|err| format!("cannot read `{}`: {}", args.input_file_path, err))?;
+    let output_file = File::create(&args.output_file_path)
+        .map_err(|err| format!("cannot create `{}`: {}", args.output_file_path, err))?;
+    let output: BufWriter<File> = copy_nc3(&mut file_reader, BufWriter::new(output_file), &args.options)
+        .map_err(|err| format!("cannot copy `{}` into `{}`: {}", args.input_file_path, args.output_file_path, err))?;
+    output.into_inner().map_err(|err| format!("cannot write `{}`: {}", args.output_file_path, err.error()))?;
+    return Ok(());
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut options = CopyOptions::default();
+    let mut positional_args: Vec<String> = common::parse_options(args, &[], |flag: &str, value: &str| {
+        match flag {
+            "-k" | "--kind" => options.version = Some(parse_version(value)?),
+            "-r" | "--header-reserve" => options.header_min_size = parse_num_bytes(flag, value)?,
+            "-v" | "--vars" => {
+                options.var_names = Some(common::parse_names(value));
+            },
+            "-a" | "--align" => options.data_alignment = parse_num_bytes(flag, value)?,
+            _ => return Err(format!("unknown option `{}`", flag)),
+        }
+        return Ok(());
+    })?;
+    if positional_args.len() != 2 {
+        return Err(String::from("expected exactly one input file and one output file"));
+    }
+    let output_file_path: String = positional_args.pop().unwrap();
+    let input_file_path: String = positional_args.pop().unwrap();
+    return Ok(Args{
+        input_file_path: input_file_path,
+        output_file_path: output_file_path,
+        options: options,
+    });
+}
+
+fn parse_version(value: &str) -> Result<Version, String> {
+    return match value {
+        "classic" | "1" | "nc3" => Ok(Version::Classic),
+        "64-bit-offset" | "64-bit offset" | "2" | "nc6" => Ok(Version::Offset64Bit),
+        _ => Err(format!("invalid kind `{}`, expected `classic` or `64-bit-offset`", value)),
+    };
+}
+
+fn parse_num_bytes(flag: &str, value: &str) -> Result<usize, String> {
+    return value.parse::<usize>().map_err(|_| format!("invalid number of bytes `{}` for the option `{}`", value, flag));
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{parse_args, Args};
+    use super::common::to_args;
+    use netcdf3::{CopyOptions, Version};
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            Ok(Args{
+                input_file_path: String::from("in.nc"),
+                output_file_path: String::from("out.nc"),
+                options: CopyOptions::default(),
+            }),
+            parse_args(&to_args(&["in.nc", "out.nc"]))
+        );
+        assert_eq!(
+            Ok(Args{
+                input_file_path: String::from("in.nc"),
+                output_file_path: String::from("out.nc"),
+                options: CopyOptions{
+                    version: Some(Version::Offset64Bit),
+                    header_min_size: 1024,
+                    var_names: Some(vec![String::from("lat"), String::from("temp")]),
+                    data_alignment: 4096,
+                    dim_slices: vec![],
+                },
+            }),
+            parse_args(&to_args(&["-k", "64-bit-offset", "in.nc", "--header-reserve=1024", "-v", "lat,temp", "--align", "4096", "out.nc"]))
+        );
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(parse_args(&to_args(&["in.nc"])).is_err());
+        assert!(parse_args(&to_args(&["in.nc", "out.nc", "other.nc"])).is_err());
+        assert!(parse_args(&to_args(&["-k", "netcdf4", "in.nc", "out.nc"])).is_err());
+        assert!(parse_args(&to_args(&["--align", "-1", "in.nc", "out.nc"])).is_err());
+        assert!(parse_args(&to_args(&["in.nc", "out.nc", "--vars"])).is_err());
+        assert!(parse_args(&to_args(&["--unknown", "1", "in.nc", "out.nc"])).is_err());
+    }
+}
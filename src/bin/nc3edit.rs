@@ -0,0 +1,324 @@
+//! Renames the variables, the dimensions and the attributes, and adds, modifies or deletes the attributes
+//! of an existing NetCDF-3 file (see the function `netcdf3::edit_nc3`).
+//!
+//! ```text
+//! nc3edit [OPTIONS] <INPUT>
+//! ```
+mod common;
+
+
+use netcdf3::{edit_nc3_file, DataSet, DataVector, EditError, EditMode, InvalidDataSet};
+
+const USAGE: &str = "\
+Usage: nc3edit [OPTIONS] <INPUT>
+
+Edits the header of the NetCDF-3 file INPUT. The header is rewritten in place when the space reserved for it allows,
+otherwise the data are moved to enlarge it. The file is edited in place unless the option `--output` is used.
+
+Options:
+  -v, --rename-var <OLD,NEW>              Renames the variable OLD
+  -d, --rename-dim <OLD,NEW>              Renames the dimension OLD
+  -r, --rename-attr <VAR,OLD,NEW>         Renames the attribute OLD of the variable VAR
+  -a, --attr <ATTR,VAR,MODE[,TYPE,VALUE]> Edits the attribute ATTR of the variable VAR, with MODE:
+                                            o  overwrites the attribute, or creates it
+                                            c  creates the attribute, if it does not exist
+                                            m  modifies the attribute, if it exists
+                                            d  deletes the attribute, if it exists (TYPE and VALUE are omitted)
+                                          and TYPE: c (text), b (i8), s (i16), i (i32), f (f32) or d (f64),
+                                          the numeric values are separated by commas
+  -o, --output <OUTPUT>                   Writes the edited file to OUTPUT, INPUT is not modified
+  -h, --help                              Prints this message
+
+The variable name `global` refers to the global attributes. The options are applied in the order of the command line.
+";
+
+/// Variable name referring to the global attributes.
+const GLOBAL_ATTRS: &str = "global";
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    input_file_path: String,
+    output_file_path: Option<String>,
+    operations: Vec<Operation>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Operation {
+    RenameVar{old_name: String, new_name: String},
+    RenameDim{old_name: String, new_name: String},
+    /// Renames an attribute of a variable, or a global attribute if `var_name` is `None`.
+    RenameAttr{var_name: Option<String>, old_name: String, new_name: String},
+    /// Edits an attribute of a variable, or a global attribute if `var_name` is `None`.
+    EditAttr{attr_name: String, var_name: Option<String>, mode: AttrMode, value: Option<DataVector>},
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AttrMode {
+    Overwrite,
+    Create,
+    Modify,
+    Delete,
+}
+
+fn main() {
+    let args: Args = common::parse_command_line("nc3edit", USAGE, parse_args);
+    let report: String = common::exit_on_error("nc3edit", run(args));
+    print!("{}", report);
+}
+
+fn run(args: Args) -> Result<String, String> {
+    let file_path: &str = match &args.output_file_path {
+        None => &args.input_file_path,
+        Some(output_file_path) => {
+            std::fs::copy(&args.input_file_path, output_file_path)
+                .map_err(|err| format!("cannot copy `{}` into `{}`: {}", args.input_file_path, output_file_path, err))?;
+            output_file_path
+        },
+    };
+    let operations: Vec<Operation> = args.operations;
+    let edit_mode: EditMode = edit_nc3_file(file_path, |data_set: &mut DataSet| apply_operations(data_set, operations))
+        .map_err(|err: EditError| match err {
+            EditError::DataSet(err) => format!("cannot edit `{}`: {}", file_path, err),
+            EditError::DataLayoutChanged => format!("cannot edit `{}`: the layout of the data has changed", file_path),
+            err => format!("cannot edit `{}`: {}", file_path, err),
+        })?;
+    return Ok(match edit_mode {
+        EditMode::InPlace => format!("{}: header rewritten in place\n", file_path),
+        EditMode::Rewritten => format!("{}: header enlarged, data moved\n", file_path),
+    });
+}
+
+fn apply_operations(data_set: &mut DataSet, operations: Vec<Operation>) -> Result<(), InvalidDataSet> {
+    for operation in operations.into_iter() {
+        match operation {
+            Operation::RenameVar{old_name, new_name} => data_set.rename_var(&old_name, &new_name)?,
+            Operation::RenameDim{old_name, new_name} => data_set.rename_dim(&old_name, &new_name)?,
+            Operation::RenameAttr{var_name: None, old_name, new_name} => data_set.rename_global_attr(&old_name, &new_name)?,
+            Operation::RenameAttr{var_name: Some(var_name), old_name, new_name} => data_set.rename_var_attr(&var_name, &old_name, &new_name)?,
+            Operation::EditAttr{attr_name, var_name, mode, value} => edit_attr(data_set, &attr_name, var_name.as_deref(), mode, value)?,
+        }
+    }
+    return Ok(());
+}
+
+fn edit_attr(data_set: &mut DataSet, attr_name: &str, var_name: Option<&str>, mode: AttrMode, value: Option<DataVector>) -> Result<(), InvalidDataSet> {
+    let attr_exists: bool = match var_name {
+        None => data_set.has_global_attr(attr_name),
+        Some(var_name) => data_set.has_var_attr(var_name, attr_name).ok_or_else(|| InvalidDataSet::VariableNotDefined(var_name.to_string()))?,
+    };
+    let (remove_attr, add_attr): (bool, bool) = match mode {
+        AttrMode::Overwrite => (attr_exists, true),
+        AttrMode::Create => (false, !attr_exists),
+        AttrMode::Modify => (attr_exists, attr_exists),
+        AttrMode::Delete => (attr_exists, false),
+    };
+    if remove_attr {
+        match var_name {
+            None => data_set.remove_global_attr(attr_name)?,
+            Some(var_name) => data_set.remove_var_attr(var_name, attr_name)?,
+        };
+    }
+    if let (true, Some(value)) = (add_attr, value) {
+        match (var_name, value) {
+            (None, DataVector::I8(data)) => data_set.add_global_attr_i8(attr_name, data)?,
+            (None, DataVector::U8(data)) => data_set.add_global_attr_u8(attr_name, data)?,
+            (None, DataVector::I16(data)) => data_set.add_global_attr_i16(attr_name, data)?,
+            (None, DataVector::I32(data)) => data_set.add_global_attr_i32(attr_name, data)?,
+            (None, DataVector::F32(data)) => data_set.add_global_attr_f32(attr_name, data)?,
+            (None, DataVector::F64(data)) => data_set.add_global_attr_f64(attr_name, data)?,
+            (Some(var_name), DataVector::I8(data)) => data_set.add_var_attr_i8(var_name, attr_name, data)?,
+            (Some(var_name), DataVector::U8(data)) => data_set.add_var_attr_u8(var_name, attr_name, data)?,
+            (Some(var_name), DataVector::I16(data)) => data_set.add_var_attr_i16(var_name, attr_name, data)?,
+            (Some(var_name), DataVector::I32(data)) => data_set.add_var_attr_i32(var_name, attr_name, data)?,
+            (Some(var_name), DataVector::F32(data)) => data_set.add_var_attr_f32(var_name, attr_name, data)?,
+            (Some(var_name), DataVector::F64(data)) => data_set.add_var_attr_f64(var_name, attr_name, data)?,
+        }
+    }
+    return Ok(());
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut output_file_path: Option<String> = None;
+    let mut operations: Vec<Operation> = vec![];
+    let mut positional_args: Vec<String> = common::parse_options(args, &[], |flag: &str, value: &str| {
+        match flag {
+            "-v" | "--rename-var" => {
+                let (old_name, new_name): (String, String) = parse_renaming(value)?;
+                operations.push(Operation::RenameVar{old_name: old_name, new_name: new_name});
+            },
+            "-d" | "--rename-dim" => {
+                let (old_name, new_name): (String, String) = parse_renaming(value)?;
+                operations.push(Operation::RenameDim{old_name: old_name, new_name: new_name});
+            },
+            "-r" | "--rename-attr" => {
+                let (var_name, renaming): (&str, &str) = value.split_once(',')
+                    .ok_or_else(|| format!("invalid renaming `{}`, expected `VAR,OLD,NEW`", value))?;
+                let (old_name, new_name): (String, String) = parse_renaming(renaming)?;
+                operations.push(Operation::RenameAttr{var_name: parse_var_name(var_name), old_name: old_name, new_name: new_name});
+            },
+            "-a" | "--attr" => operations.push(parse_attr_edit(value)?),
+            "-o" | "--output" => output_file_path = Some(value.to_string()),
+            _ => return Err(format!("unknown option `{}`", flag)),
+        }
+        return Ok(());
+    })?;
+    if positional_args.len() != 1 {
+        return Err(String::from("expected exactly one input file"));
+    }
+    if operations.is_empty() {
+        return Err(String::from("no edit requested"));
+    }
+    return Ok(Args{
+        input_file_path: positional_args.pop().unwrap(),
+        output_file_path: output_file_path,
+        operations: operations,
+    });
+}
+
+/// Parses a renaming `OLD,NEW`.
+fn parse_renaming(value: &str) -> Result<(String, String), String> {
+    return match value.split_once(',') {
+        Some((old_name, new_name)) if !old_name.trim().is_empty() && !new_name.trim().is_empty() => {
+            Ok((old_name.trim().to_string(), new_name.trim().to_string()))
+        },
+        _ => Err(format!("invalid renaming `{}`, expected `OLD,NEW`", value)),
+    };
+}
+
+/// Returns `None` for the global attributes.
+fn parse_var_name(var_name: &str) -> Option<String> {
+    let var_name: &str = var_name.trim();
+    return match var_name {
+        GLOBAL_ATTRS => None,
+        _ => Some(var_name.to_string()),
+    };
+}
+
+/// Parses an attribute edit `ATTR,VAR,MODE[,TYPE,VALUE]`.
+fn parse_attr_edit(value: &str) -> Result<Operation, String> {
+    let invalid_value = || format!("invalid attribute edit `{}`, expected `ATTR,VAR,MODE[,TYPE,VALUE]`", value);
+    let items: Vec<&str> = value.splitn(5, ',').collect();
+    if items.len() < 3 || items[0].trim().is_empty() || items[1].trim().is_empty() {
+        return Err(invalid_value());
+    }
+    let mode: AttrMode = match items[2].trim() {
+        "o" => AttrMode::Overwrite,
+        "c" => AttrMode::Create,
+        "m" => AttrMode::Modify,
+        "d" => AttrMode::Delete,
+        mode => return Err(format!("invalid attribute edit mode `{}`, expected `o`, `c`, `m` or `d`", mode)),
+    };
+    let value: Option<DataVector> = match (mode, &items[3..]) {
+        (AttrMode::Delete, []) => None,
+        (AttrMode::Delete, _) => return Err(invalid_value()),
+        (_, [attr_type, attr_value]) => Some(parse_attr_value(attr_type.trim(), attr_value)?),
+        (_, _) => return Err(invalid_value()),
+    };
+    return Ok(Operation::EditAttr{
+        attr_name: items[0].trim().to_string(),
+        var_name: parse_var_name(items[1]),
+        mode: mode,
+        value: value,
+    });
+}
+
+/// Parses the value of an attribute, the text is kept as is and the numeric values are separated by commas.
+fn parse_attr_value(attr_type: &str, attr_value: &str) -> Result<DataVector, String> {
+    fn parse_numbers<T: std::str::FromStr>(attr_value: &str) -> Result<Vec<T>, String> {
+        return attr_value.split(',').map(|item: &str| {
+            item.trim().parse::<T>().map_err(|_err| format!("invalid numeric value `{}`", item.trim()))
+        }).collect();
+    }
+    return match attr_type {
+        "c" => Ok(DataVector::U8(attr_value.as_bytes().to_vec())),
+        "b" => parse_numbers::<i8>(attr_value).map(DataVector::I8),
+        "s" => parse_numbers::<i16>(attr_value).map(DataVector::I16),
+        "i" => parse_numbers::<i32>(attr_value).map(DataVector::I32),
+        "f" => parse_numbers::<f32>(attr_value).map(DataVector::F32),
+        "d" => parse_numbers::<f64>(attr_value).map(DataVector::F64),
+        _ => Err(format!("invalid attribute type `{}`, expected `c`, `b`, `s`, `i`, `f` or `d`", attr_type)),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{apply_operations, parse_args, parse_attr_edit, Args, AttrMode, Operation};
+    use super::common::to_args;
+    use netcdf3::{DataSet, DataVector, InvalidDataSet};
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            Ok(Args{
+                input_file_path: String::from("in.nc"),
+                output_file_path: Some(String::from("out.nc")),
+                operations: vec![
+                    Operation::RenameVar{old_name: String::from("temp"), new_name: String::from("tas")},
+                    Operation::RenameDim{old_name: String::from("lat"), new_name: String::from("latitude")},
+                    Operation::RenameAttr{var_name: None, old_name: String::from("title"), new_name: String::from("name")},
+                ],
+            }),
+            parse_args(&to_args(&["-v", "temp,tas", "in.nc", "--rename-dim=lat,latitude", "-r", "global,title,name", "-o", "out.nc"]))
+        );
+        assert!(parse_args(&to_args(&["in.nc"])).is_err());
+        assert!(parse_args(&to_args(&["-v", "temp,tas"])).is_err());
+        assert!(parse_args(&to_args(&["-v", "temp", "in.nc"])).is_err());
+        assert!(parse_args(&to_args(&["--vars", "temp", "in.nc"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_attr_edit() {
+        assert_eq!(
+            Ok(Operation::EditAttr{
+                attr_name: String::from("title"),
+                var_name: None,
+                mode: AttrMode::Overwrite,
+                value: Some(DataVector::U8(b"Example, edited".to_vec())),
+            }),
+            parse_attr_edit("title,global,o,c,Example, edited")
+        );
+        assert_eq!(
+            Ok(Operation::EditAttr{
+                attr_name: String::from("valid_range"),
+                var_name: Some(String::from("temp")),
+                mode: AttrMode::Create,
+                value: Some(DataVector::F32(vec![-50.0, 50.0])),
+            }),
+            parse_attr_edit("valid_range,temp,c,f,-50,50")
+        );
+        assert_eq!(
+            Ok(Operation::EditAttr{attr_name: String::from("units"), var_name: Some(String::from("temp")), mode: AttrMode::Delete, value: None}),
+            parse_attr_edit("units,temp,d")
+        );
+        assert!(parse_attr_edit("units,temp,o").is_err());
+        assert!(parse_attr_edit("units,temp,d,c,K").is_err());
+        assert!(parse_attr_edit("units,temp,x,c,K").is_err());
+        assert!(parse_attr_edit("units,temp,o,x,K").is_err());
+        assert!(parse_attr_edit("scale_factor,temp,o,s,0.5").is_err());
+    }
+
+    #[test]
+    fn test_apply_operations() {
+        let mut data_set = DataSet::new();
+        data_set.add_var_f32::<&str>("temp", &[]).unwrap();
+        data_set.add_var_attr_string("temp", "units", "K").unwrap();
+        let operations: Vec<Operation> = vec![
+            parse_attr_edit("units,temp,c,c,degC").unwrap(),
+            parse_attr_edit("long_name,temp,m,c,Temperature").unwrap(),
+            parse_attr_edit("scale_factor,temp,o,d,0.5").unwrap(),
+            parse_attr_edit("history,global,o,c,edited").unwrap(),
+            parse_attr_edit("comment,global,d").unwrap(),
+        ];
+        apply_operations(&mut data_set, operations).unwrap();
+        assert_eq!(Some(String::from("K")),         data_set.get_var_attr_as_string("temp", "units"));
+        assert_eq!(Some(false),                     data_set.has_var_attr("temp", "long_name"));
+        assert_eq!(Some(&[0.5][..]),                data_set.get_var_attr_f64("temp", "scale_factor"));
+        assert_eq!(Some(String::from("edited")),    data_set.get_global_attr_as_string("history"));
+
+        assert_eq!(
+            Err(InvalidDataSet::VariableNotDefined(String::from("undef_var"))),
+            apply_operations(&mut data_set, vec![parse_attr_edit("units,undef_var,o,c,K").unwrap()])
+        );
+    }
+}
@@ -0,0 +1,112 @@
+//! Repairs a NetCDF-3 file truncated in the middle of the record data (see the function `netcdf3::repair_nc3`).
+//!
+//! ```text
+//! nc3repair [OPTIONS] <INPUT>
+//! ```
+mod common;
+
+
+use netcdf3::{repair_nc3_file, RepairReport};
+
+const USAGE: &str = "\
+Usage: nc3repair [OPTIONS] <INPUT>
+
+Recomputes the number of records of the truncated NetCDF-3 file INPUT, completes its last record with fill values
+and rewrites its header. The file is repaired in place unless the option `--output` is used.
+
+Options:
+  -o, --output <OUTPUT>  Writes the repaired file to OUTPUT, INPUT is not modified
+  -h, --help             Prints this message
+";
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    input_file_path: String,
+    output_file_path: Option<String>,
+}
+
+fn main() {
+    let args: Args = common::parse_command_line("nc3repair", USAGE, parse_args);
+    let report: String = common::exit_on_error("nc3repair", run(&args));
+    print!("{}", report);
+}
+
+fn run(args: &Args) -> Result<String, String> {
+    let file_path: &str = match &args.output_file_path {
+        None => &args.input_file_path,
+        Some(output_file_path) => {
+            std::fs::copy(&args.input_file_path, output_file_path)
+                .map_err(|err| format!("cannot copy `{}` into `{}`: {}", args.input_file_path, output_file_path, err))?;
+            output_file_path
+        },
+    };
+    let report: RepairReport = repair_nc3_file(file_path).map_err(|err| format!("cannot repair `{}`: {}", file_path, err))?;
+    return Ok(format_report(file_path, &report));
+}
+
+fn format_report(file_path: &str, report: &RepairReport) -> String {
+    if !report.is_modified() {
+        return format!("{}: nothing to repair ({} records)\n", file_path, report.num_records);
+    }
+    let header_num_records: String = match report.header_num_records {
+        Some(header_num_records) => header_num_records.to_string(),
+        None => String::from("indeterminate"),
+    };
+    return format!(
+        "{}: number of records {} -> {}, {} bytes appended\n",
+        file_path, header_num_records, report.num_records, report.num_filled_bytes
+    );
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut output_file_path: Option<String> = None;
+    let mut positional_args: Vec<String> = common::parse_options(args, &[], |flag: &str, value: &str| {
+        match flag {
+            "-o" | "--output" => output_file_path = Some(value.to_string()),
+            _ => return Err(format!("unknown option `{}`", flag)),
+        }
+        return Ok(());
+    })?;
+    if positional_args.len() != 1 {
+        return Err(String::from("expected exactly one input file"));
+    }
+    return Ok(Args{
+        input_file_path: positional_args.pop().unwrap(),
+        output_file_path: output_file_path,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{format_report, parse_args, Args};
+    use super::common::to_args;
+    use netcdf3::RepairReport;
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            Ok(Args{input_file_path: String::from("in.nc"), output_file_path: None}),
+            parse_args(&to_args(&["in.nc"]))
+        );
+        assert_eq!(
+            Ok(Args{input_file_path: String::from("in.nc"), output_file_path: Some(String::from("out.nc"))}),
+            parse_args(&to_args(&["in.nc", "-o", "out.nc"]))
+        );
+        assert!(parse_args(&to_args(&[])).is_err());
+        assert!(parse_args(&to_args(&["in.nc", "out.nc"])).is_err());
+        assert!(parse_args(&to_args(&["in.nc", "--output"])).is_err());
+    }
+
+    #[test]
+    fn test_format_report() {
+        assert_eq!(
+            "in.nc: nothing to repair (2 records)\n",
+            format_report("in.nc", &RepairReport{header_num_records: Some(2), num_records: 2, num_filled_bytes: 0})
+        );
+        assert_eq!(
+            "in.nc: number of records indeterminate -> 2, 298 bytes appended\n",
+            format_report("in.nc", &RepairReport{header_num_records: None, num_records: 2, num_filled_bytes: 298})
+        );
+    }
+}
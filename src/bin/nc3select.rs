@@ -0,0 +1,124 @@
+//! Extracts variables and ranges of indices from a NetCDF-3 file into a new file (see the function `netcdf3::copy_nc3`).
+//!
+//! ```text
+//! nc3select [OPTIONS] <INPUT> <OUTPUT>
+//! ```
+mod common;
+
+use std::fs::File;
+use std::io::BufWriter;
+
+use netcdf3::{copy_nc3, CopyError, CopyOptions, DimSlice, FileReader};
+
+const USAGE: &str = "\
+Usage: nc3select [OPTIONS] <INPUT> <OUTPUT>
+
+Extracts the selected variables and ranges of indices of the NetCDF-3 file INPUT into OUTPUT.
+
+Options:
+  -v, --vars <VAR_1,VAR_2,...>   Names of the extracted variables, all the variables are extracted by default
+  -d, --dim <DIM,START,COUNT>    Extracts COUNT indices from START along the dimension DIM, can be repeated
+  -h, --help                     Prints this message
+";
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    input_file_path: String,
+    output_file_path: String,
+    options: CopyOptions,
+}
+
+fn main() {
+    let args: Args = common::parse_command_line("nc3select", USAGE, parse_args);
+    common::exit_on_error("nc3select", run(&args));
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    let mut file_reader = FileReader::open(&args.input_file_path)
+        .map_err(|err| format!("cannot read `{}`: {}", args.input_file_path, err))?;
+    let output_file = File::create(&args.output_file_path)
+        .map_err(|err| format!("cannot create `{}`: {}", args.output_file_path, err))?;
+    let output: BufWriter<File> = copy_nc3(&mut file_reader, BufWriter::new(output_file), &args.options)
+        .map_err(|err: CopyError| match err {
+            CopyError::VariableNotDefined(var_name) => format!("the variable `{}` is not defined in `{}`", var_name, args.input_file_path),
+            CopyError::DimensionNotDefined(dim_name) => format!("the dimension `{}` is not defined in `{}`", dim_name, args.input_file_path),
+            CopyError::DimSliceNotValid{dim_name, start, count, dim_size} => {
+                format!("invalid range `{},{},{}` of the dimension `{}` of size {}", dim_name, start, count, dim_name, dim_size)
+            },
+            err => format!("cannot copy `{}` into `{}`: {}", args.input_file_path, args.output_file_path, err),
+        })?;
+    output.into_inner().map_err(|err| format!("cannot write `{}`: {}", args.output_file_path, err.error()))?;
+    return Ok(());
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut options = CopyOptions::default();
+    let mut positional_args: Vec<String> = common::parse_options(args, &[], |flag: &str, value: &str| {
+        match flag {
+            "-v" | "--vars" => {
+                options.var_names = Some(common::parse_names(value));
+            },
+            "-d" | "--dim" => options.dim_slices.push(parse_dim_slice(value)?),
+            _ => return Err(format!("unknown option `{}`", flag)),
+        }
+        return Ok(());
+    })?;
+    if positional_args.len() != 2 {
+        return Err(String::from("expected exactly one input file and one output file"));
+    }
+    let output_file_path: String = positional_args.pop().unwrap();
+    let input_file_path: String = positional_args.pop().unwrap();
+    return Ok(Args{
+        input_file_path: input_file_path,
+        output_file_path: output_file_path,
+        options: options,
+    });
+}
+
+/// Parses a range of indices `DIM,START,COUNT`.
+fn parse_dim_slice(value: &str) -> Result<DimSlice, String> {
+    let invalid_value = || format!("invalid range `{}`, expected `DIM,START,COUNT`", value);
+    let items: Vec<&str> = value.split(',').map(|item: &str| item.trim()).collect();
+    if items.len() != 3 || items[0].is_empty() {
+        return Err(invalid_value());
+    }
+    let start: usize = items[1].parse().map_err(|_| invalid_value())?;
+    let count: usize = items[2].parse().map_err(|_| invalid_value())?;
+    return Ok(DimSlice::new(items[0], start, count));
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{parse_args, parse_dim_slice, Args};
+    use super::common::to_args;
+    use netcdf3::{CopyOptions, DimSlice};
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            Ok(Args{
+                input_file_path: String::from("in.nc"),
+                output_file_path: String::from("out.nc"),
+                options: CopyOptions{
+                    var_names: Some(vec![String::from("lat"), String::from("temp")]),
+                    dim_slices: vec![DimSlice::new("time", 10, 5), DimSlice::new("lat", 0, 2)],
+                    ..CopyOptions::default()
+                },
+            }),
+            parse_args(&to_args(&["-v", "lat,temp", "-d", "time,10,5", "in.nc", "--dim=lat,0,2", "out.nc"]))
+        );
+        assert!(parse_args(&to_args(&["in.nc"])).is_err());
+        assert!(parse_args(&to_args(&["-d", "time,10", "in.nc", "out.nc"])).is_err());
+        assert!(parse_args(&to_args(&["--kind", "classic", "in.nc", "out.nc"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_dim_slice() {
+        assert_eq!(Ok(DimSlice::new("time", 0, 1)),     parse_dim_slice("time,0,1"));
+        assert_eq!(Ok(DimSlice::new("time", 2, 3)),     parse_dim_slice(" time , 2 , 3 "));
+        assert!(parse_dim_slice(",0,1").is_err());
+        assert!(parse_dim_slice("time,-1,1").is_err());
+        assert!(parse_dim_slice("time,0,1,2").is_err());
+    }
+}
@@ -0,0 +1,179 @@
+//! Prints the layout of a NetCDF-3 file (header size and used size, record size, offsets of the variables)
+//! and the statistics of its variables (see the function `netcdf3::cf::var_stats`).
+//!
+//! ```text
+//! nc3stat [OPTIONS] <INPUT>
+//! ```
+mod common;
+
+use std::io::{Read, Seek};
+
+use netcdf3::{FileReader, Variable, Version, DataVector, Dimension};
+use netcdf3::cf::{var_stats, VarStats};
+
+const USAGE: &str = "\
+Usage: nc3stat [OPTIONS] <INPUT>
+
+Prints the layout of the NetCDF-3 file INPUT and the minimum, maximum, mean and number of missing values of its variables.
+
+Options:
+  -v, --vars <VAR_1,VAR_2,...>  Names of the described variables, all the variables are described by default
+  -h, --help                    Prints this message
+";
+
+/// Header of the table of the variables.
+const COLUMN_NAMES: [&str; 9] = ["variable", "type", "shape", "offset", "size", "min", "max", "mean", "missing"];
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    input_file_path: String,
+    var_names: Option<Vec<String>>,
+}
+
+fn main() {
+    let args: Args = common::parse_command_line("nc3stat", USAGE, parse_args);
+    let report: String = common::exit_on_error("nc3stat", run(&args));
+    print!("{}", report);
+}
+
+fn run(args: &Args) -> Result<String, String> {
+    let mut file_reader = FileReader::open(&args.input_file_path)
+        .map_err(|err| format!("cannot read `{}`: {}", args.input_file_path, err))?;
+    let var_names: Vec<String> = match &args.var_names {
+        None => file_reader.data_set().get_var_names(),
+        Some(var_names) => {
+            if let Some(var_name) = var_names.iter().find(|var_name: &&String| !file_reader.data_set().has_var(var_name)) {
+                return Err(format!("the variable `{}` is not defined in `{}`", var_name, args.input_file_path));
+            }
+            var_names.clone()
+        },
+    };
+    let mut report: String = layout_report(&file_reader, &args.input_file_path);
+    report.push('\n');
+    let mut rows: Vec<Vec<String>> = vec![COLUMN_NAMES.iter().map(|column_name: &&str| column_name.to_string()).collect()];
+    for var_name in var_names.iter() {
+        let stats: VarStats = read_var_stats(&mut file_reader, var_name)
+            .map_err(|err| format!("cannot read the variable `{}`: {}", var_name, err))?;
+        rows.push(var_row(&file_reader, var_name, &stats));
+    }
+    report.push_str(&format_table(&rows));
+    return Ok(report);
+}
+
+/// Returns the description of the layout of the file.
+fn layout_report<R: Read + Seek>(file_reader: &FileReader<R>, input_file_path: &str) -> String {
+    let version: &str = match file_reader.version() {
+        Version::Classic => "classic",
+        Version::Offset64Bit => "64-bit offset",
+    };
+    let mut report = String::new();
+    report.push_str(&format!("file: {}\n", input_file_path));
+    report.push_str(&format!("version: {}\n", version));
+    report.push_str(&format!("header size: {} bytes\n", file_reader.header_size()));
+    report.push_str(&format!("header used size: {} bytes\n", file_reader.header_used_size()));
+    match file_reader.data_set().record_size() {
+        Some(record_size) => report.push_str(&format!("record size: {} bytes\n", record_size)),
+        None => report.push_str("record size: -\n"),
+    }
+    report.push_str(&format!("number of records: {}\n", file_reader.data_set().num_records().unwrap_or(0)));
+    return report;
+}
+
+/// Computes the statistics of a variable, the record variables are read record by record.
+fn read_var_stats<R: Read + Seek>(file_reader: &mut FileReader<R>, var_name: &str) -> Result<VarStats, netcdf3::ReadError> {
+    let var: Variable = file_reader.data_set().get_var(var_name).unwrap().clone();
+    if !var.is_record_var() {
+        let data: DataVector = file_reader.read_var(var_name)?;
+        return Ok(var_stats(&var, &data));
+    }
+    let mut stats = VarStats::default();
+    for record_index in 0..file_reader.data_set().num_records().unwrap_or(0) {
+        let record: DataVector = file_reader.read_record(var_name, record_index)?;
+        stats = stats.merge(&var_stats(&var, &record));
+    }
+    return Ok(stats);
+}
+
+fn var_row<R: Read + Seek>(file_reader: &FileReader<R>, var_name: &str, stats: &VarStats) -> Vec<String> {
+    let var: &Variable = file_reader.data_set().get_var(var_name).unwrap();
+    let shape: Vec<String> = var.dims().iter().map(|dim: &std::rc::Rc<Dimension>| dim.size().to_string()).collect();
+    let format_value = |value: Option<f64>| -> String { value.map(|value: f64| format!("{}", value)).unwrap_or_else(|| String::from("-")) };
+    return vec![
+        var_name.to_string(),
+        var.data_type().c_api_name().to_string(),
+        format!("[{}]", shape.join(", ")),
+        file_reader.var_begin_offset(var_name).map(|offset: u64| offset.to_string()).unwrap_or_else(|| String::from("-")),
+        (var.len() * var.data_type().size_of()).to_string(),
+        format_value(stats.min),
+        format_value(stats.max),
+        format_value(stats.mean),
+        stats.num_missing.to_string(),
+    ];
+}
+
+/// Formats the rows as a table with left-aligned columns.
+fn format_table(rows: &[Vec<String>]) -> String {
+    let num_columns: usize = rows.iter().map(|row: &Vec<String>| row.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..num_columns).map(|column_index: usize| {
+        rows.iter().filter_map(|row: &Vec<String>| row.get(column_index)).map(|cell: &String| cell.chars().count()).max().unwrap_or(0)
+    }).collect();
+    let mut table = String::new();
+    for row in rows.iter() {
+        let cells: Vec<String> = row.iter().zip(widths.iter()).map(|(cell, width): (&String, &usize)| format!("{:<width$}", cell, width = width)).collect();
+        table.push_str(cells.join("  ").trim_end());
+        table.push('\n');
+    }
+    return table;
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut var_names: Option<Vec<String>> = None;
+    let mut positional_args: Vec<String> = common::parse_options(args, &[], |flag: &str, value: &str| {
+        match flag {
+            "-v" | "--vars" => {
+                var_names = Some(common::parse_names(value));
+            },
+            _ => return Err(format!("unknown option `{}`", flag)),
+        }
+        return Ok(());
+    })?;
+    if positional_args.len() != 1 {
+        return Err(String::from("expected exactly one input file"));
+    }
+    return Ok(Args{
+        input_file_path: positional_args.pop().unwrap(),
+        var_names: var_names,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{format_table, parse_args, Args};
+    use super::common::to_args;
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            Ok(Args{input_file_path: String::from("in.nc"), var_names: None}),
+            parse_args(&to_args(&["in.nc"]))
+        );
+        assert_eq!(
+            Ok(Args{input_file_path: String::from("in.nc"), var_names: Some(vec![String::from("lat"), String::from("temp")])}),
+            parse_args(&to_args(&["--vars=lat,temp", "in.nc"]))
+        );
+        assert!(parse_args(&to_args(&[])).is_err());
+        assert!(parse_args(&to_args(&["in.nc", "out.nc"])).is_err());
+        assert!(parse_args(&to_args(&["in.nc", "-v"])).is_err());
+        assert!(parse_args(&to_args(&["--kind", "classic", "in.nc"])).is_err());
+    }
+
+    #[test]
+    fn test_format_table() {
+        let rows: Vec<Vec<String>> = vec![
+            vec![String::from("variable"), String::from("min")],
+            vec![String::from("x"), String::from("-1.5")],
+        ];
+        assert_eq!("variable  min\nx         -1.5\n",      format_table(&rows));
+    }
+}
@@ -0,0 +1,138 @@
+//! Checks the header and the layout of the data of NetCDF-3 files (see the function `netcdf3::validate_nc3`).
+//!
+//! ```text
+//! nc3validate [OPTIONS] <INPUT_1> [<INPUT_2> ...]
+//! ```
+mod common;
+
+use std::fs::File;
+use std::io::BufReader;
+use std::process::exit;
+
+use netcdf3::{validate_nc3, LayoutViolation};
+
+const USAGE: &str = "\
+Usage: nc3validate [OPTIONS] <INPUT_1> [<INPUT_2> ...]
+
+Checks the NetCDF-3 files INPUT_1, INPUT_2, etc. : validity of the header, increasing and non-overlapping data offsets,
+chunk sizes matching the dimensions, file size matching the data size and zero padding of the header.
+
+Exits with the code 1 if one of the files is not valid.
+
+Options:
+  -q, --quiet   Prints only the violations
+  -h, --help    Prints this message
+";
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    input_file_paths: Vec<String>,
+    quiet: bool,
+}
+
+fn main() {
+    let args: Args = common::parse_command_line("nc3validate", USAGE, parse_args);
+    let mut all_valid: bool = true;
+    for input_file_path in args.input_file_paths.iter() {
+        let messages: Vec<String> = match validate_file(input_file_path) {
+            Ok(violations) => violations.iter().map(format_violation).collect(),
+            Err(message) => vec![message],
+        };
+        if messages.is_empty() && !args.quiet {
+            println!("{}: ok", input_file_path);
+        }
+        for message in messages.iter() {
+            println!("{}: {}", input_file_path, message);
+        }
+        all_valid &= messages.is_empty();
+    }
+    if !all_valid {
+        exit(1);
+    }
+}
+
+fn validate_file(input_file_path: &str) -> Result<Vec<LayoutViolation>, String> {
+    let input_file = File::open(input_file_path).map_err(|err| format!("cannot open the file: {}", err))?;
+    return validate_nc3(BufReader::new(input_file)).map_err(|err| format!("invalid header: {}", err));
+}
+
+fn format_violation(violation: &LayoutViolation) -> String {
+    return match violation {
+        LayoutViolation::DataOverlapsHeader{var_name, begin_offset, header_size} => {
+            format!("the data of `{}` begin at the offset {}, inside the header ({} bytes)", var_name, begin_offset, header_size)
+        },
+        LayoutViolation::OffsetsNotIncreasing{var_name, prev_var_name} => {
+            format!("the data of `{}` begin before the data of `{}`", var_name, prev_var_name)
+        },
+        LayoutViolation::DataOverlap{var_name, prev_var_name} => {
+            format!("the data of `{}` overlap the data of `{}`", var_name, prev_var_name)
+        },
+        LayoutViolation::RecordOverflow{var_name} => {
+            format!("the record data of `{}` exceed the record size", var_name)
+        },
+        LayoutViolation::ChunkSizeMismatch{var_name, header_chunk_size, chunk_size} => {
+            format!("the chunk size of `{}` is {} bytes in the header, {} bytes expected from its dimensions", var_name, header_chunk_size, chunk_size)
+        },
+        LayoutViolation::FileTooShort{required_size, file_size} => {
+            format!("the file is truncated, {} bytes instead of {} bytes", file_size, required_size)
+        },
+        LayoutViolation::TrailingBytes{data_end, file_size} => {
+            format!("{} unexpected bytes after the end of the data", file_size - data_end)
+        },
+        LayoutViolation::NonZeroPadding{offset} => {
+            format!("non-zero padding byte at the offset {} of the header", offset)
+        },
+    };
+}
+
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut quiet: bool = false;
+    let input_file_paths: Vec<String> = common::parse_options(args, &["-q", "--quiet"], |flag: &str, _value: &str| {
+        match flag {
+            "-q" | "--quiet" => quiet = true,
+            _ => return Err(format!("unknown option `{}`", flag)),
+        }
+        return Ok(());
+    })?;
+    if input_file_paths.is_empty() {
+        return Err(String::from("expected at least one input file"));
+    }
+    return Ok(Args{
+        input_file_paths: input_file_paths,
+        quiet: quiet,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{format_violation, parse_args, Args};
+    use super::common::to_args;
+    use netcdf3::LayoutViolation;
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            Ok(Args{input_file_paths: to_args(&["in_1.nc", "in_2.nc"]), quiet: true}),
+            parse_args(&to_args(&["in_1.nc", "-q", "in_2.nc"]))
+        );
+        assert_eq!(
+            Ok(Args{input_file_paths: to_args(&["in.nc"]), quiet: false}),
+            parse_args(&to_args(&["in.nc"]))
+        );
+        assert!(parse_args(&to_args(&[])).is_err());
+        assert!(parse_args(&to_args(&["--strict", "in.nc"])).is_err());
+    }
+
+    #[test]
+    fn test_format_violation() {
+        assert_eq!(
+            "the file is truncated, 2000 bytes instead of 2332 bytes",
+            format_violation(&LayoutViolation::FileTooShort{required_size: 2332, file_size: 2000})
+        );
+        assert_eq!(
+            "8 unexpected bytes after the end of the data",
+            format_violation(&LayoutViolation::TrailingBytes{data_end: 2332, file_size: 2340})
+        );
+    }
+}
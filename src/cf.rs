@@ -0,0 +1,29 @@
+//! Helpers implementing some of the [CF Conventions](http://cfconventions.org/) on top of the NetCDF-3 data sets.
+mod flags;
+pub use flags::{add_flag_attrs, decode_flags};
+
+mod masking;
+pub use masking::mask_var;
+
+mod packing;
+pub use packing::{pack, unpack, unpack_var};
+
+mod stats;
+pub use stats::{var_stats, VarStats};
+
+#[cfg(feature = "units")]
+pub mod units;
+
+#[cfg(feature = "chrono")]
+mod time;
+#[cfg(feature = "chrono")]
+pub use time::decode_time;
+#[cfg(all(feature = "chrono", feature = "read"))]
+pub use time::{read_records_by_time, select_records_by_time};
+
+use crate::Variable;
+
+/// Returns the numeric values of a variable attribute (`None` if the attribute is not defined or contains text).
+pub(crate) fn get_attr_values(var: &Variable, attr_name: &str) -> Option<Vec<f64>> {
+    return var.get_attr(attr_name)?.as_f64_vec();
+}
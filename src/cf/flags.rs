@@ -0,0 +1,152 @@
+use crate::{DataSet, DataType, DataVector, InvalidDataSet, Variable};
+use crate::cf::get_attr_values;
+
+const FLAG_VALUES_ATTR_NAME: &str = "flag_values";
+const FLAG_MEANINGS_ATTR_NAME: &str = "flag_meanings";
+
+/// Decodes the elements of a flag variable through its `flag_values` and `flag_meanings` attributes.
+///
+/// `None` is returned for the elements not matching any flag value. If the attributes are not defined,
+/// or if their numbers of values differ, then `None` is returned for all the elements.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{DataSet, DataVector, cf::{add_flag_attrs, decode_flags}};
+///
+/// const VAR_NAME: &str = "quality_flag";
+///
+/// let mut data_set = DataSet::new();
+/// data_set.add_var_i8::<&str>(VAR_NAME, &[]).unwrap();
+/// add_flag_attrs(&mut data_set, VAR_NAME, &[(0, "good"), (1, "suspect"), (2, "bad")]).unwrap();
+///
+/// assert_eq!(Some(&[0_i8, 1, 2][..]),                         data_set.get_var_attr_i8(VAR_NAME, "flag_values"));
+/// assert_eq!(Some(String::from("good suspect bad")),          data_set.get_var_attr_as_string(VAR_NAME, "flag_meanings"));
+///
+/// let data = DataVector::I8(vec![2, 0, 5]);
+/// assert_eq!(
+///     vec![Some(String::from("bad")), Some(String::from("good")), None],
+///     decode_flags(data_set.get_var(VAR_NAME).unwrap(), &data)
+/// );
+/// ```
+pub fn decode_flags(var: &Variable, data: &DataVector) -> Vec<Option<String>> {
+    let flag_values: Vec<f64> = get_attr_values(var, FLAG_VALUES_ATTR_NAME).unwrap_or_default();
+    let flag_meanings: String = var.get_attr_as_string(FLAG_MEANINGS_ATTR_NAME).unwrap_or_default();
+    let flag_meanings: Vec<&str> = flag_meanings.split_whitespace().collect();
+    if flag_values.len() != flag_meanings.len() {
+        return vec![None; data.len()];
+    }
+    return data.get_as_f64_vec().into_iter().map(|value: f64| {
+        flag_values.iter()
+            .position(|flag_value: &f64| *flag_value == value)
+            .map(|flag_index: usize| flag_meanings[flag_index].to_string())
+    }).collect();
+}
+
+/// Defines the attributes `flag_values` (stored with the data type of the variable) and `flag_meanings`.
+///
+/// An error is returned if :
+///
+/// - the variable is not defined,
+/// - a flag meaning is empty or contains whitespaces,
+/// - a flag value cannot be represented by the data type of the variable,
+/// - the attributes are already defined.
+///
+/// Nothing is added to the variable if an error occurs.
+pub fn add_flag_attrs(data_set: &mut DataSet, var_name: &str, flags: &[(i32, &str)]) -> Result<(), InvalidDataSet> {
+    let data_type: DataType = data_set.var_data_type(var_name).ok_or_else(|| InvalidDataSet::VariableNotDefined(var_name.to_string()))?;
+    for (flag_value, flag_meaning) in flags.iter() {
+        if flag_meaning.is_empty() || flag_meaning.contains(char::is_whitespace) {
+            return Err(InvalidDataSet::FlagMeaningNotValid{var_name: var_name.to_string(), flag_meaning: flag_meaning.to_string()});
+        }
+        let (min, max): (i64, i64) = match data_type {
+            DataType::I8 => (i8::MIN as i64, i8::MAX as i64),
+            DataType::U8 => (u8::MIN as i64, u8::MAX as i64),
+            DataType::I16 => (i16::MIN as i64, i16::MAX as i64),
+            DataType::I32 | DataType::F32 | DataType::F64 => (i32::MIN as i64, i32::MAX as i64),
+        };
+        if (*flag_value as i64) < min || (*flag_value as i64) > max {
+            return Err(InvalidDataSet::FlagValueOutOfRange{var_name: var_name.to_string(), flag_value: *flag_value, data_type: data_type});
+        }
+    }
+    for attr_name in [FLAG_VALUES_ATTR_NAME, FLAG_MEANINGS_ATTR_NAME].iter() {
+        if data_set.has_var_attr(var_name, attr_name) == Some(true) {
+            return Err(InvalidDataSet::VariableAttributeAlreadyExists{var_name: var_name.to_string(), attr_name: attr_name.to_string()});
+        }
+    }
+
+    let flag_values = flags.iter().map(|(flag_value, _flag_meaning): &(i32, &str)| *flag_value);
+    match data_type {
+        DataType::I8 => data_set.add_var_attr_i8(var_name, FLAG_VALUES_ATTR_NAME, flag_values.map(|value: i32| value as i8).collect())?,
+        DataType::U8 => data_set.add_var_attr_u8(var_name, FLAG_VALUES_ATTR_NAME, flag_values.map(|value: i32| value as u8).collect())?,
+        DataType::I16 => data_set.add_var_attr_i16(var_name, FLAG_VALUES_ATTR_NAME, flag_values.map(|value: i32| value as i16).collect())?,
+        DataType::I32 => data_set.add_var_attr_i32(var_name, FLAG_VALUES_ATTR_NAME, flag_values.collect())?,
+        DataType::F32 => data_set.add_var_attr_f32(var_name, FLAG_VALUES_ATTR_NAME, flag_values.map(|value: i32| value as f32).collect())?,
+        DataType::F64 => data_set.add_var_attr_f64(var_name, FLAG_VALUES_ATTR_NAME, flag_values.map(|value: i32| value as f64).collect())?,
+    }
+    let flag_meanings: Vec<&str> = flags.iter().map(|(_flag_value, flag_meaning): &(i32, &str)| *flag_meaning).collect();
+    data_set.add_var_attr_string(var_name, FLAG_MEANINGS_ATTR_NAME, flag_meanings.join(" "))?;
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{add_flag_attrs, decode_flags};
+    use crate::{DataSet, DataType, DataVector, InvalidDataSet};
+
+    const VAR_NAME: &str = "flag";
+
+    #[test]
+    fn test_decode_flags_without_attrs() {
+        let mut data_set = DataSet::new();
+        data_set.add_var_i16::<&str>(VAR_NAME, &[]).unwrap();
+        let data = DataVector::I16(vec![0, 1]);
+        assert_eq!(vec![None, None],    decode_flags(data_set.get_var(VAR_NAME).unwrap(), &data));
+
+        // inconsistent attributes
+        data_set.add_var_attr_i16(VAR_NAME, "flag_values", vec![0, 1]).unwrap();
+        data_set.add_var_attr_string(VAR_NAME, "flag_meanings", "off").unwrap();
+        assert_eq!(vec![None, None],    decode_flags(data_set.get_var(VAR_NAME).unwrap(), &data));
+    }
+
+    #[test]
+    fn test_add_flag_attrs() {
+        let mut data_set = DataSet::new();
+        data_set.add_var_f32::<&str>(VAR_NAME, &[]).unwrap();
+        add_flag_attrs(&mut data_set, VAR_NAME, &[(-1, "missing"), (1, "valid")]).unwrap();
+        assert_eq!(Some(&[-1.0_f32, 1.0][..]),      data_set.get_var_attr_f32(VAR_NAME, "flag_values"));
+
+        let data = DataVector::F32(vec![1.0, -1.0, 0.5]);
+        assert_eq!(
+            vec![Some(String::from("valid")), Some(String::from("missing")), None],
+            decode_flags(data_set.get_var(VAR_NAME).unwrap(), &data)
+        );
+
+        assert_eq!(
+            InvalidDataSet::VariableAttributeAlreadyExists{var_name: String::from(VAR_NAME), attr_name: String::from("flag_values")},
+            add_flag_attrs(&mut data_set, VAR_NAME, &[(0, "off")]).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_add_flag_attrs_errors() {
+        let mut data_set = DataSet::new();
+        data_set.add_var_i8::<&str>(VAR_NAME, &[]).unwrap();
+
+        assert_eq!(
+            InvalidDataSet::VariableNotDefined(String::from("undef_var")),
+            add_flag_attrs(&mut data_set, "undef_var", &[(0, "off")]).unwrap_err()
+        );
+        assert_eq!(
+            InvalidDataSet::FlagMeaningNotValid{var_name: String::from(VAR_NAME), flag_meaning: String::from("not ok")},
+            add_flag_attrs(&mut data_set, VAR_NAME, &[(0, "ok"), (1, "not ok")]).unwrap_err()
+        );
+        assert_eq!(
+            InvalidDataSet::FlagValueOutOfRange{var_name: String::from(VAR_NAME), flag_value: 128, data_type: DataType::I8},
+            add_flag_attrs(&mut data_set, VAR_NAME, &[(0, "off"), (128, "on")]).unwrap_err()
+        );
+        // nothing has been added
+        assert_eq!(0,       data_set.num_var_attrs(VAR_NAME).unwrap());
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{DataType, DataVector, Variable};
+use crate::cf::get_attr_values;
+use crate::{NC_FILL_I16, NC_FILL_I32, NC_FILL_F32, NC_FILL_F64};
+
+const FILL_VALUE_ATTR_NAME: &str = "_FillValue";
+const MISSING_VALUE_ATTR_NAME: &str = "missing_value";
+const VALID_RANGE_ATTR_NAME: &str = "valid_range";
+const VALID_MIN_ATTR_NAME: &str = "valid_min";
+const VALID_MAX_ATTR_NAME: &str = "valid_max";
+
+/// Masks the missing values of a variable, `None` is returned for each missing element.
+///
+/// An element is considered as missing if :
+///
+/// - it is equal to the `_FillValue` attribute, or to the default fill value of the variable data type
+///   when the attribute is not defined (except for the `i8` and `u8` variables, like the netCDF4-python library does),
+/// - it is equal to one of the values of the `missing_value` attribute,
+/// - it is outside of the `valid_range` attribute, or lower than `valid_min`, or greater than `valid_max`
+///   (`valid_range` takes precedence over `valid_min` and `valid_max`).
+///
+/// The values are compared before unpacking (see [`unpack_var`](fn.unpack_var.html)).
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{DataSet, DataVector, NC_FILL_I16, cf::mask_var};
+///
+/// const VAR_NAME: &str = "var_1";
+///
+/// let mut data_set = DataSet::new();
+/// data_set.add_var_i16::<&str>(VAR_NAME, &[]).unwrap();
+/// data_set.add_var_attr_i16(VAR_NAME, "missing_value", vec![-1]).unwrap();
+/// data_set.add_var_attr_i16(VAR_NAME, "valid_range", vec![0, 100]).unwrap();
+///
+/// let data = DataVector::I16(vec![NC_FILL_I16, -1, 0, 50, 101]);
+/// assert_eq!(
+///     vec![None, None, Some(0.0), Some(50.0), None],
+///     mask_var(data_set.get_var(VAR_NAME).unwrap(), &data)
+/// );
+/// ```
+pub fn mask_var(var: &Variable, data: &DataVector) -> Vec<Option<f64>> {
+    let fill_value: Option<f64> = match var.get_attr(FILL_VALUE_ATTR_NAME) {
+        Some(attr) => attr.data().get_as_f64_vec().first().copied(),
+        None => default_fill_value(var.data_type()),
+    };
+    let missing_values: Vec<f64> = get_attr_values(var, MISSING_VALUE_ATTR_NAME).unwrap_or_default();
+    let (valid_min, valid_max): (Option<f64>, Option<f64>) = match get_attr_values(var, VALID_RANGE_ATTR_NAME) {
+        Some(valid_range) if valid_range.len() == 2 => (Some(valid_range[0]), Some(valid_range[1])),
+        _ => (
+            get_attr_values(var, VALID_MIN_ATTR_NAME).and_then(|values: Vec<f64>| values.first().copied()),
+            get_attr_values(var, VALID_MAX_ATTR_NAME).and_then(|values: Vec<f64>| values.first().copied()),
+        ),
+    };
+
+    return data.get_as_f64_vec().into_iter().map(|value: f64| {
+        let is_missing: bool = Some(value) == fill_value
+            || missing_values.contains(&value)
+            || valid_min.map(|valid_min: f64| value < valid_min).unwrap_or(false)
+            || valid_max.map(|valid_max: f64| value > valid_max).unwrap_or(false);
+        if is_missing { None } else { Some(value) }
+    }).collect();
+}
+
+fn default_fill_value(data_type: DataType) -> Option<f64> {
+    return match data_type {
+        DataType::I8 => None,
+        DataType::U8 => None,
+        DataType::I16 => Some(NC_FILL_I16 as f64),
+        DataType::I32 => Some(NC_FILL_I32 as f64),
+        DataType::F32 => Some(NC_FILL_F32 as f64),
+        DataType::F64 => Some(NC_FILL_F64),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::mask_var;
+    use crate::{DataSet, DataVector};
+    use crate::{NC_FILL_I8, NC_FILL_F32};
+
+    const VAR_NAME: &str = "var_1";
+
+    #[test]
+    fn test_mask_var_fill_value() {
+        let mut data_set = DataSet::new();
+        data_set.add_var_f32::<&str>(VAR_NAME, &[]).unwrap();
+        let data = DataVector::F32(vec![NC_FILL_F32, 1.0, -999.0]);
+
+        // default fill value
+        assert_eq!(vec![None, Some(1.0), Some(-999.0)],     mask_var(data_set.get_var(VAR_NAME).unwrap(), &data));
+
+        // defined fill value
+        data_set.add_var_attr_f32(VAR_NAME, "_FillValue", vec![-999.0]).unwrap();
+        assert_eq!(vec![Some(NC_FILL_F32 as f64), Some(1.0), None],     mask_var(data_set.get_var(VAR_NAME).unwrap(), &data));
+
+        // the default fill value is not used for the `i8` variables
+        let mut data_set = DataSet::new();
+        data_set.add_var_i8::<&str>(VAR_NAME, &[]).unwrap();
+        let data = DataVector::I8(vec![NC_FILL_I8, 1]);
+        assert_eq!(vec![Some(NC_FILL_I8 as f64), Some(1.0)],    mask_var(data_set.get_var(VAR_NAME).unwrap(), &data));
+    }
+
+    #[test]
+    fn test_mask_var_valid_min_max() {
+        let mut data_set = DataSet::new();
+        data_set.add_var_i32::<&str>(VAR_NAME, &[]).unwrap();
+        let data = DataVector::I32(vec![-5, 0, 5, 10, 15]);
+
+        data_set.add_var_attr_i32(VAR_NAME, "valid_min", vec![0]).unwrap();
+        assert_eq!(vec![None, Some(0.0), Some(5.0), Some(10.0), Some(15.0)],    mask_var(data_set.get_var(VAR_NAME).unwrap(), &data));
+
+        data_set.add_var_attr_i32(VAR_NAME, "valid_max", vec![10]).unwrap();
+        assert_eq!(vec![None, Some(0.0), Some(5.0), Some(10.0), None],          mask_var(data_set.get_var(VAR_NAME).unwrap(), &data));
+
+        // `valid_range` takes precedence
+        data_set.add_var_attr_i32(VAR_NAME, "valid_range", vec![5, 15]).unwrap();
+        assert_eq!(vec![None, None, Some(5.0), Some(10.0), Some(15.0)],         mask_var(data_set.get_var(VAR_NAME).unwrap(), &data));
+    }
+}
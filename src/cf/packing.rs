@@ -0,0 +1,145 @@
+use crate::{DataType, DataVector, Variable};
+use crate::cf::get_attr_values;
+use crate::{NC_FILL_I8, NC_FILL_U8, NC_FILL_I16, NC_FILL_I32, NC_FILL_F32, NC_FILL_F64};
+
+const SCALE_FACTOR_ATTR_NAME: &str = "scale_factor";
+const ADD_OFFSET_ATTR_NAME: &str = "add_offset";
+
+/// Packs floating values into the data type `data_type` following the CF packing convention :
+///
+/// `packed = round((value - add_offset) / scale_factor)`
+///
+/// The integer packed values are clamped to the range of `data_type`,
+/// and the NaN values are replaced by the default fill value of `data_type` (see [`NC_FILL_I16`](constant.NC_FILL_I16.html), etc.).
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{DataType, DataVector, cf::{pack, unpack}};
+///
+/// let values: Vec<f64> = vec![273.15, 274.15, 300.0, std::f64::NAN];
+///
+/// let packed: DataVector = pack(&values, 0.01, 273.15, DataType::I16);
+/// assert_eq!(DataType::I16,                           packed.data_type());
+/// assert_eq!(Some(&[0_i16, 100, 2685, -32767][..]),   packed.get_i16());
+///
+/// let unpacked: Vec<f64> = unpack(&packed, 0.01, 273.15);
+/// assert!((unpacked[2] - 300.0).abs() < 0.01);
+/// ```
+pub fn pack(values: &[f64], scale_factor: f64, add_offset: f64, data_type: DataType) -> DataVector {
+    macro_rules! pack_into_int {
+        ($prim_type:ty, $nc_fill:expr) => {
+            values.iter().map(|value: &f64| {
+                let packed: f64 = ((value - add_offset) / scale_factor).round();
+                if packed.is_nan() {
+                    $nc_fill
+                } else {
+                    packed.max(<$prim_type>::MIN as f64).min(<$prim_type>::MAX as f64) as $prim_type
+                }
+            }).collect()
+        };
+    }
+    return match data_type {
+        DataType::I8 => DataVector::I8(pack_into_int!(i8, NC_FILL_I8)),
+        DataType::U8 => DataVector::U8(pack_into_int!(u8, NC_FILL_U8)),
+        DataType::I16 => DataVector::I16(pack_into_int!(i16, NC_FILL_I16)),
+        DataType::I32 => DataVector::I32(pack_into_int!(i32, NC_FILL_I32)),
+        DataType::F32 => DataVector::F32(values.iter().map(|value: &f64| {
+            if value.is_nan() { NC_FILL_F32 } else { ((value - add_offset) / scale_factor) as f32 }
+        }).collect()),
+        DataType::F64 => DataVector::F64(values.iter().map(|value: &f64| {
+            if value.is_nan() { NC_FILL_F64 } else { (value - add_offset) / scale_factor }
+        }).collect()),
+    };
+}
+
+/// Unpacks values following the CF packing convention :
+///
+/// `value = packed * scale_factor + add_offset`
+///
+/// Also see the function [`pack`](fn.pack.html).
+pub fn unpack(packed: &DataVector, scale_factor: f64, add_offset: f64) -> Vec<f64> {
+    return packed.get_as_f64_vec().into_iter().map(|packed: f64| {
+        packed * scale_factor + add_offset
+    }).collect();
+}
+
+/// Unpacks the data of a variable using its `scale_factor` and `add_offset` attributes.
+///
+/// Missing attributes default to `scale_factor = 1` and `add_offset = 0`.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{DataSet, DataVector, cf::unpack_var};
+///
+/// const VAR_NAME: &str = "temperature";
+///
+/// let mut data_set = DataSet::new();
+/// data_set.add_var_i16::<&str>(VAR_NAME, &[]).unwrap();
+/// data_set.add_var_attr_f32(VAR_NAME, "scale_factor", vec![0.5]).unwrap();
+/// data_set.add_var_attr_f32(VAR_NAME, "add_offset", vec![10.0]).unwrap();
+///
+/// let var = data_set.get_var(VAR_NAME).unwrap();
+/// assert_eq!(vec![15.0],      unpack_var(var, &DataVector::I16(vec![10])));
+/// ```
+pub fn unpack_var(var: &Variable, packed: &DataVector) -> Vec<f64> {
+    let scale_factor: f64 = get_attr_values(var, SCALE_FACTOR_ATTR_NAME).and_then(|values: Vec<f64>| values.first().copied()).unwrap_or(1.0);
+    let add_offset: f64 = get_attr_values(var, ADD_OFFSET_ATTR_NAME).and_then(|values: Vec<f64>| values.first().copied()).unwrap_or(0.0);
+    return unpack(packed, scale_factor, add_offset);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{pack, unpack, unpack_var};
+    use crate::{DataSet, DataType, DataVector};
+    use crate::{NC_FILL_I8, NC_FILL_F32};
+
+    #[test]
+    fn test_pack() {
+        let values: [f64; 5] = [-1000.0, -1.0, 0.0, 1.0, 1000.0];
+
+        assert_eq!(DataVector::I8(vec![-128, -10, 0, 10, 127]),     pack(&values, 0.1, 0.0, DataType::I8));
+        assert_eq!(DataVector::U8(vec![0, 0, 0, 1, 255]),           pack(&values, 1.0, 0.0, DataType::U8));
+        assert_eq!(DataVector::I16(vec![-1001, -2, -1, 0, 999]),    pack(&values, 1.0, 1.0, DataType::I16));
+        assert_eq!(DataVector::I32(vec![-500, -1, 0, 1, 500]),      pack(&values, 2.0, 0.0, DataType::I32));
+        assert_eq!(DataVector::F32(vec![-500.0, -0.5, 0.0, 0.5, 500.0]),    pack(&values, 2.0, 0.0, DataType::F32));
+        assert_eq!(DataVector::F64(vec![-500.0, -0.5, 0.0, 0.5, 500.0]),    pack(&values, 2.0, 0.0, DataType::F64));
+
+        // NaN values are replaced by the default fill values
+        assert_eq!(DataVector::I8(vec![NC_FILL_I8]),                pack(&[std::f64::NAN], 1.0, 0.0, DataType::I8));
+        assert_eq!(DataVector::F32(vec![NC_FILL_F32]),              pack(&[std::f64::NAN], 1.0, 0.0, DataType::F32));
+    }
+
+    #[test]
+    fn test_pack_unpack_round_trip() {
+        let values: Vec<f64> = (0..100).map(|i: i32| 250.0 + (i as f64) * 0.37).collect();
+        let scale_factor: f64 = 0.001;
+        let add_offset: f64 = 270.0;
+
+        let packed: DataVector = pack(&values, scale_factor, add_offset, DataType::I16);
+        let unpacked: Vec<f64> = unpack(&packed, scale_factor, add_offset);
+        assert_eq!(values.len(),    unpacked.len());
+        for (value, unpacked) in values.iter().zip(unpacked.iter()) {
+            assert!((value - unpacked).abs() <= scale_factor / 2.0 + 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_unpack_var() {
+        const VAR_NAME: &str = "var_1";
+        let mut data_set = DataSet::new();
+        data_set.add_var_i8::<&str>(VAR_NAME, &[]).unwrap();
+
+        // without packing attributes
+        let data: DataVector = DataVector::I8(vec![1, 2]);
+        assert_eq!(vec![1.0, 2.0],  unpack_var(data_set.get_var(VAR_NAME).unwrap(), &data));
+
+        data_set.add_var_attr_f64(VAR_NAME, "scale_factor", vec![2.0]).unwrap();
+        assert_eq!(vec![2.0, 4.0],  unpack_var(data_set.get_var(VAR_NAME).unwrap(), &data));
+
+        data_set.add_var_attr_f64(VAR_NAME, "add_offset", vec![-1.0]).unwrap();
+        assert_eq!(vec![1.0, 3.0],  unpack_var(data_set.get_var(VAR_NAME).unwrap(), &data));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{DataVector, Variable};
+use crate::cf::mask_var;
+
+/// Summary statistics of the values of a variable (see [`var_stats`](fn.var_stats.html)).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct VarStats {
+    /// Number of elements, including the missing ones.
+    pub num_values: usize,
+    /// Number of missing elements (fill values, missing values, values outside of the valid range and NaN values).
+    pub num_missing: usize,
+    /// Minimum of the valid values (`None` if there is no valid value).
+    pub min: Option<f64>,
+    /// Maximum of the valid values (`None` if there is no valid value).
+    pub max: Option<f64>,
+    /// Mean of the valid values (`None` if there is no valid value).
+    pub mean: Option<f64>,
+}
+
+impl VarStats {
+
+    /// Returns the number of valid elements.
+    pub fn num_valid(&self) -> usize {
+        return self.num_values - self.num_missing;
+    }
+
+    /// Returns the statistics of the union of the two sets of values (allows to compute the statistics record by record).
+    pub fn merge(&self, other: &VarStats) -> VarStats {
+        let num_valid: usize = self.num_valid() + other.num_valid();
+        let weighted_sum = |stats: &VarStats| -> f64 { stats.mean.map(|mean: f64| mean * stats.num_valid() as f64).unwrap_or(0.0) };
+        return VarStats{
+            num_values: self.num_values + other.num_values,
+            num_missing: self.num_missing + other.num_missing,
+            min: merge_option(self.min, other.min, f64::min),
+            max: merge_option(self.max, other.max, f64::max),
+            mean: if num_valid == 0 { None } else { Some((weighted_sum(self) + weighted_sum(other)) / num_valid as f64) },
+        };
+    }
+}
+
+/// Computes the minimum, the maximum and the mean of the valid values of a variable, and counts its missing values.
+///
+/// The missing values are masked by [`mask_var`](fn.mask_var.html), the NaN values are also considered as missing.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{DataSet, DataVector, NC_FILL_F32, cf::{var_stats, VarStats}};
+///
+/// const VAR_NAME: &str = "var_1";
+///
+/// let mut data_set = DataSet::new();
+/// data_set.add_fixed_dim("x", 5).unwrap();
+/// data_set.add_var_f32(VAR_NAME, &["x"]).unwrap();
+///
+/// let data = DataVector::F32(vec![1.0, NC_FILL_F32, 2.0, f32::NAN, 6.0]);
+/// assert_eq!(
+///     VarStats{num_values: 5, num_missing: 2, min: Some(1.0), max: Some(6.0), mean: Some(3.0)},
+///     var_stats(data_set.get_var(VAR_NAME).unwrap(), &data)
+/// );
+/// ```
+pub fn var_stats(var: &Variable, data: &DataVector) -> VarStats {
+    let mut stats = VarStats{num_values: data.len(), ..VarStats::default()};
+    let mut sum: f64 = 0.0;
+    for value in mask_var(var, data).into_iter() {
+        match value {
+            Some(value) if !value.is_nan() => {
+                stats.min = Some(stats.min.map_or(value, |min: f64| min.min(value)));
+                stats.max = Some(stats.max.map_or(value, |max: f64| max.max(value)));
+                sum += value;
+            },
+            _ => stats.num_missing += 1,
+        }
+    }
+    let num_valid: usize = stats.num_valid();
+    if num_valid > 0 {
+        stats.mean = Some(sum / num_valid as f64);
+    }
+    return stats;
+}
+
+fn merge_option(a: Option<f64>, b: Option<f64>, merge: fn(f64, f64) -> f64) -> Option<f64> {
+    return match (a, b) {
+        (Some(a), Some(b)) => Some(merge(a, b)),
+        (a, None) => a,
+        (None, b) => b,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{var_stats, VarStats};
+    use crate::{DataSet, DataVector};
+    use crate::NC_FILL_I16;
+
+    const VAR_NAME: &str = "var_1";
+
+    #[test]
+    fn test_var_stats_no_valid_value() {
+        let mut data_set = DataSet::new();
+        data_set.add_fixed_dim("x", 2).unwrap();
+        data_set.add_var_i16(VAR_NAME, &["x"]).unwrap();
+        let var = data_set.get_var(VAR_NAME).unwrap();
+
+        assert_eq!(
+            VarStats{num_values: 2, num_missing: 2, min: None, max: None, mean: None},
+            var_stats(var, &DataVector::I16(vec![NC_FILL_I16, NC_FILL_I16]))
+        );
+        assert_eq!(VarStats::default(),        var_stats(var, &DataVector::I16(vec![])));
+    }
+
+    #[test]
+    fn test_var_stats_merge() {
+        let mut data_set = DataSet::new();
+        data_set.add_fixed_dim("x", 3).unwrap();
+        data_set.add_var_i16(VAR_NAME, &["x"]).unwrap();
+        let var = data_set.get_var(VAR_NAME).unwrap();
+
+        let stats_1: VarStats = var_stats(var, &DataVector::I16(vec![1, 2, NC_FILL_I16]));
+        let stats_2: VarStats = var_stats(var, &DataVector::I16(vec![-3, NC_FILL_I16, NC_FILL_I16]));
+        assert_eq!(
+            VarStats{num_values: 6, num_missing: 3, min: Some(-3.0), max: Some(2.0), mean: Some(0.0)},
+            stats_1.merge(&stats_2)
+        );
+        assert_eq!(stats_1,                    stats_1.merge(&VarStats::default()));
+    }
+}
@@ -0,0 +1,596 @@
+#[cfg(feature = "read")]
+use std::collections::HashMap;
+#[cfg(feature = "read")]
+use std::io::{Read, Seek};
+#[cfg(feature = "read")]
+use std::ops::Range;
+#[cfg(feature = "read")]
+use std::rc::Rc;
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+
+use crate::{DataVector, Variable};
+#[cfg(feature = "read")]
+use crate::{DimRef, Dimension, FileReader, VarRef};
+use crate::cf::units::TimeUnits;
+use crate::error::DecodeTimeError;
+#[cfg(feature = "read")]
+use crate::error::{ReadError, SelectTimeError};
+
+const UNITS_ATTR_NAME: &str = "units";
+const CALENDAR_ATTR_NAME: &str = "calendar";
+
+/// Number of days between the Julian day 0 and the day before 0001-01-01 (proleptic Gregorian calendar).
+const JULIAN_DAY_NUMBER_OFFSET: i64 = 1_721_425;
+
+/// Calendars handled by [`decode_time`](fn.decode_time.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Calendar {
+    /// Mixed Julian/Gregorian calendar, the Gregorian calendar is used from 1582-10-15.
+    Standard,
+    ProlepticGregorian,
+    Julian,
+}
+
+impl Calendar {
+
+    fn parse(calendar: &str) -> Option<Calendar> {
+        return match calendar.trim().to_lowercase().as_str() {
+            "standard" | "gregorian" => Some(Calendar::Standard),
+            "proleptic_gregorian" => Some(Calendar::ProlepticGregorian),
+            "julian" => Some(Calendar::Julian),
+            _ => None,
+        };
+    }
+
+    /// Returns `true` if the date `(year, month, day)` does not exist in the calendar.
+    ///
+    /// The 10 days from 1582-10-05 to 1582-10-14 were skipped by the switch from the Julian to the Gregorian calendar.
+    fn is_skipped_date(&self, year: i32, month: u32, day: u32) -> bool {
+        return match self {
+            Calendar::Standard => (year, month, day) >= (1582, 10, 5) && (year, month, day) < (1582, 10, 15),
+            Calendar::ProlepticGregorian | Calendar::Julian => false,
+        };
+    }
+
+    /// Returns `true` if the date `(year, month, day)` is expressed in the Julian calendar.
+    fn is_julian_date(&self, year: i32, month: u32, day: u32) -> bool {
+        return match self {
+            Calendar::Standard => (year, month, day) < (1582, 10, 15),
+            Calendar::ProlepticGregorian => false,
+            Calendar::Julian => true,
+        };
+    }
+}
+
+/// Decodes the values of a CF time coordinate variable into UTC date-times.
+///
+/// The `units` attribute of the variable must have the form `"<time unit> since <reference date>"`
+/// (for example `"hours since 1970-01-01 00:00:00"`), and the optional `calendar` attribute must be one of
+/// `standard` (the default), `gregorian`, `proleptic_gregorian` or `julian`.
+///
+/// With the `standard` calendar, reference dates before 1582-10-15 are read in the Julian calendar
+/// and the dates from 1582-10-05 to 1582-10-14 (skipped by the switch to the Gregorian calendar) are rejected.
+/// Offsets are added to the reference point in time, so they correctly cross the switch in both directions.
+///
+/// The returned date-times are points in time expressed in the proleptic Gregorian calendar.
+/// Model calendars (`noleap`, `360_day`, ...) are not supported because their dates cannot be mapped to real points in time.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{DateTime, TimeZone, Utc};
+/// # let utc = |y, m, d, h, mi, s| Utc.with_ymd_and_hms(y, m, d, h, mi, s).unwrap();
+/// use netcdf3::{DataSet, DataVector, cf::decode_time};
+///
+/// const TIME_VAR_NAME: &str = "time";
+///
+/// let mut data_set = DataSet::new();
+/// data_set.set_unlimited_dim(TIME_VAR_NAME, 3).unwrap();
+/// data_set.add_var_f64(TIME_VAR_NAME, &[TIME_VAR_NAME]).unwrap();
+/// data_set.add_var_attr_string(TIME_VAR_NAME, "units", "hours since 2020-01-01 00:00:00").unwrap();
+///
+/// let time_data = DataVector::F64(vec![0.0, 1.5, 24.0]);
+/// let times: Vec<DateTime<Utc>> = decode_time(data_set.get_var(TIME_VAR_NAME).unwrap(), &time_data).unwrap();
+///
+/// assert_eq!(utc(2020, 1, 1, 0, 0, 0),     times[0]);
+/// assert_eq!(utc(2020, 1, 1, 1, 30, 0),    times[1]);
+/// assert_eq!(utc(2020, 1, 2, 0, 0, 0),     times[2]);
+/// ```
+pub fn decode_time(var: &Variable, data: &DataVector) -> Result<Vec<DateTime<Utc>>, DecodeTimeError> {
+    let var_name: &str = var.name();
+    let units: String = var.get_attr_as_string(UNITS_ATTR_NAME).ok_or_else(|| {
+        DecodeTimeError::UnitsNotDefined(var_name.to_string())
+    })?;
+    let calendar: Calendar = match var.get_attr_as_string(CALENDAR_ATTR_NAME) {
+        None => Calendar::Standard,
+        Some(calendar) => Calendar::parse(&calendar).ok_or_else(|| {
+            DecodeTimeError::CalendarNotSupported{var_name: var_name.to_string(), calendar: calendar.clone()}
+        })?,
+    };
+    let (unit_seconds, ref_date_time): (f64, DateTime<Utc>) = parse_time_units(&units, calendar).ok_or_else(|| {
+        DecodeTimeError::UnitsNotValid{var_name: var_name.to_string(), units: units.clone()}
+    })?;
+
+    return data.get_as_f64_vec().into_iter().enumerate().map(|(index, value): (usize, f64)| {
+        offset_date_time(&ref_date_time, value * unit_seconds).ok_or_else(|| {
+            DecodeTimeError::DateTimeOutOfRange{var_name: var_name.to_string(), index}
+        })
+    }).collect();
+}
+
+/// Returns the range of the records whose time coordinate falls in the interval `time_range`.
+///
+/// The time coordinate is the coordinate variable of the *unlimited-size* dimension, decoded by [`decode_time`](fn.decode_time.html).
+/// Its values are expected to be increasing, the returned range spans from the first to the last record in the interval.
+/// An empty range is returned if no record is in the interval.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use chrono::{TimeZone, Utc};
+/// use netcdf3::{DataSet, FileReader, FileWriter, Version, cf::select_records_by_time};
+///
+/// let mut data_set = DataSet::new();
+/// data_set.set_unlimited_dim("time", 4).unwrap();
+/// data_set.add_var_f64("time", &["time"]).unwrap();
+/// data_set.add_var_attr_string("time", "units", "days since 2020-01-01").unwrap();
+///
+/// let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+/// file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+/// file_writer.write_var_f64("time", &[0.0, 1.0, 2.0, 3.0]).unwrap();
+/// let mut file_reader = FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+///
+/// let start = Utc.with_ymd_and_hms(2020, 1, 2, 0, 0, 0).unwrap();
+/// let end = Utc.with_ymd_and_hms(2020, 1, 4, 0, 0, 0).unwrap();
+/// assert_eq!(1..3,        select_records_by_time(&mut file_reader, start..end).unwrap());
+/// ```
+#[cfg(feature = "read")]
+pub fn select_records_by_time<R: Read + Seek>(file_reader: &mut FileReader<R>, time_range: Range<DateTime<Utc>>) -> Result<Range<usize>, SelectTimeError> {
+    let unlim_dim_name: String = file_reader.unlimited_dim_name().ok_or(SelectTimeError::UnlimitedDimensionNotDefined)?;
+    let time_var_name: String = file_reader.data_set().dim(&unlim_dim_name)
+        .and_then(|dim: DimRef| dim.coord_var())
+        .map(|var: VarRef| var.name().to_string())
+        .ok_or_else(|| SelectTimeError::TimeCoordinateNotDefined(unlim_dim_name.clone()))?;
+    let time_data: DataVector = file_reader.read_var(&time_var_name)?;
+    let time_var: &Variable = file_reader.data_set().get_var(&time_var_name).ok_or(ReadError::Unexpected)?;
+    let times: Vec<DateTime<Utc>> = decode_time(time_var, &time_data)?;
+
+    let first: Option<usize> = times.iter().position(|time: &DateTime<Utc>| time_range.contains(time));
+    let last: Option<usize> = times.iter().rposition(|time: &DateTime<Utc>| time_range.contains(time));
+    return Ok(match (first, last) {
+        (Some(first), Some(last)) => first..(last + 1),
+        _ => {
+            let num_previous_records: usize = times.iter().take_while(|time: &&DateTime<Utc>| **time < time_range.start).count();
+            num_previous_records..num_previous_records
+        },
+    });
+}
+
+/// Reads the records whose time coordinate falls in the interval `time_range` (see [`select_records_by_time`](fn.select_records_by_time.html)).
+///
+/// Returns the range of the selected records and the data of the variables `var_names`,
+/// only the selected records of the record variables being read. The *fixed-size* variables are read entirely.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use std::io::Cursor;
+/// use chrono::{TimeZone, Utc};
+/// use netcdf3::{DataSet, DataVector, FileReader, FileWriter, Version, cf::read_records_by_time};
+///
+/// let mut data_set = DataSet::new();
+/// data_set.set_unlimited_dim("time", 3).unwrap();
+/// data_set.add_fixed_dim("station", 2).unwrap();
+/// data_set.add_var_f64("time", &["time"]).unwrap();
+/// data_set.add_var_attr_string("time", "units", "hours since 2020-01-01 00:00:00").unwrap();
+/// data_set.add_var_f32("t2m", &["time", "station"]).unwrap();
+///
+/// let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+/// file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+/// file_writer.write_var_f64("time", &[0.0, 6.0, 12.0]).unwrap();
+/// file_writer.write_var_f32("t2m", &[280.0, 281.0, 282.0, 283.0, 284.0, 285.0]).unwrap();
+/// let mut file_reader = FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+///
+/// let start = Utc.with_ymd_and_hms(2020, 1, 1, 3, 0, 0).unwrap();
+/// let end = Utc.with_ymd_and_hms(2020, 1, 2, 0, 0, 0).unwrap();
+/// let (records, vars_data): (_, HashMap<String, DataVector>) = read_records_by_time(&mut file_reader, start..end, &["t2m"]).unwrap();
+/// assert_eq!(1..3,                                                records);
+/// assert_eq!(Some(&DataVector::F32(vec![282.0, 283.0, 284.0, 285.0])),  vars_data.get("t2m"));
+/// ```
+#[cfg(feature = "read")]
+pub fn read_records_by_time<R: Read + Seek>(
+    file_reader: &mut FileReader<R>,
+    time_range: Range<DateTime<Utc>>,
+    var_names: &[&str],
+) -> Result<(Range<usize>, HashMap<String, DataVector>), SelectTimeError> {
+    let records: Range<usize> = select_records_by_time(file_reader, time_range)?;
+    let mut vars_data: HashMap<String, DataVector> = HashMap::with_capacity(var_names.len());
+    for var_name in var_names.iter() {
+        let var: &Variable = file_reader.data_set().get_var(var_name).ok_or_else(|| ReadError::VariableNotDefined(var_name.to_string()))?;
+        let data: DataVector = match var.is_record_var() {
+            false => file_reader.read_var(var_name)?,
+            true => {
+                let mut start: Vec<usize> = vec![0; var.num_dims()];
+                let mut count: Vec<usize> = var.dims().iter().map(|dim: &Rc<Dimension>| dim.size()).collect();
+                start[0] = records.start;
+                count[0] = records.len();
+                file_reader.read_var_slice(var_name, &start, &count)?
+            },
+        };
+        vars_data.insert(var_name.to_string(), data);
+    }
+    return Ok((records, vars_data));
+}
+
+/// Adds a floating number of seconds to a date-time, rounded to the nearest nanosecond.
+fn offset_date_time(date_time: &DateTime<Utc>, seconds: f64) -> Option<DateTime<Utc>> {
+    // Bounds of `chrono::Duration` (approximately `i64::MAX` milliseconds)
+    const MAX_ABS_SECONDS: f64 = 9.0e15;
+    if !seconds.is_finite() || seconds.abs() >= MAX_ABS_SECONDS {
+        return None;
+    }
+    let whole_seconds: f64 = seconds.floor();
+    let nanoseconds: f64 = ((seconds - whole_seconds) * 1.0e9).round();
+    let duration: Duration = Duration::seconds(whole_seconds as i64) + Duration::nanoseconds(nanoseconds as i64);
+    return date_time.checked_add_signed(duration);
+}
+
+/// Parses the CF time units `"<time unit> since <reference date>"`.
+///
+/// Returns the number of seconds of the time unit and the reference date-time.
+fn parse_time_units(units: &str, calendar: Calendar) -> Option<(f64, DateTime<Utc>)> {
+    let time_units: TimeUnits = TimeUnits::parse(units)?;
+    let ref_date_time: DateTime<Utc> = parse_reference_date_time(&time_units.reference.to_lowercase(), calendar)?;
+    return Some((time_units.unit.factor, ref_date_time));
+}
+
+/// Parses the reference date-time, for example `"1970-01-01"`, `"1970-1-1 00:00:00.5"`, `"1970-01-01T00:00:00Z"` or `"1970-01-01 00:00 -6:00"`.
+fn parse_reference_date_time(input: &str, calendar: Calendar) -> Option<DateTime<Utc>> {
+    let input: &str = input.trim();
+    let date_end: usize = input.find([' ', 't']).unwrap_or(input.len());
+    let date: NaiveDate = parse_date(&input[..date_end], calendar)?;
+    let input: &str = input[date_end..].trim_start_matches([' ', 't']);
+
+    let time_end: usize = input.find(|c: char| !(c.is_ascii_digit() || c == ':' || c == '.')).unwrap_or(input.len());
+    let time: NaiveTime = if time_end == 0 { NaiveTime::from_hms_opt(0, 0, 0)? } else { parse_time(&input[..time_end])? };
+    let tz_offset_seconds: i64 = parse_time_zone(input[time_end..].trim())?;
+
+    let date_time: DateTime<Utc> = Utc.from_utc_datetime(&NaiveDateTime::new(date, time));
+    return date_time.checked_add_signed(Duration::seconds(-tz_offset_seconds));
+}
+
+fn parse_date(input: &str, calendar: Calendar) -> Option<NaiveDate> {
+    let (sign, input): (i32, &str) = match input.strip_prefix('-') {
+        Some(input) => (-1, input),
+        None => (1, input),
+    };
+    let fields: Vec<&str> = input.split('-').collect();
+    if fields.len() != 3 || fields.iter().any(|field: &&str| field.is_empty() || !field.bytes().all(|b: u8| b.is_ascii_digit())) {
+        return None;
+    }
+    let year: i32 = sign * fields[0].parse::<i32>().ok()?;
+    let month: u32 = fields[1].parse().ok()?;
+    let day: u32 = fields[2].parse().ok()?;
+    if calendar.is_skipped_date(year, month, day) {
+        return None;
+    }
+    if calendar.is_julian_date(year, month, day) {
+        return julian_calendar_date(year, month, day);
+    }
+    return NaiveDate::from_ymd_opt(year, month, day);
+}
+
+/// Converts a Julian calendar date into a proleptic Gregorian date (through the Julian day number).
+fn julian_calendar_date(year: i32, month: u32, day: u32) -> Option<NaiveDate> {
+    let days_in_month: u32 = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 => if year.rem_euclid(4) == 0 { 29 } else { 28 },
+        _ => return None,
+    };
+    if day == 0 || day > days_in_month {
+        return None;
+    }
+    let a: i64 = (14 - month as i64) / 12;
+    let y: i64 = year as i64 + 4800 - a;
+    let m: i64 = month as i64 + 12 * a - 3;
+    let julian_day_number: i64 = day as i64 + (153 * m + 2) / 5 + 365 * y + y.div_euclid(4) - 32_083;
+    let num_days_from_ce: i64 = julian_day_number - JULIAN_DAY_NUMBER_OFFSET;
+    if num_days_from_ce < i32::MIN as i64 || num_days_from_ce > i32::MAX as i64 {
+        return None;
+    }
+    return NaiveDate::from_num_days_from_ce_opt(num_days_from_ce as i32);
+}
+
+fn parse_time(input: &str) -> Option<NaiveTime> {
+    let fields: Vec<&str> = input.split(':').collect();
+    if fields.len() > 3 || fields.iter().any(|field: &&str| field.is_empty()) {
+        return None;
+    }
+    let hour: u32 = fields[0].parse().ok()?;
+    let minute: u32 = match fields.get(1) {
+        Some(field) => field.parse().ok()?,
+        None => 0,
+    };
+    let (second, nanosecond): (u32, u32) = match fields.get(2) {
+        None => (0, 0),
+        Some(field) => {
+            let second: f64 = field.parse().ok()?;
+            let whole_second: f64 = second.floor();
+            (whole_second as u32, ((second - whole_second) * 1.0e9).round().min(999_999_999.0) as u32)
+        },
+    };
+    return NaiveTime::from_hms_nano_opt(hour, minute, second, nanosecond);
+}
+
+/// Returns the time zone offset in seconds (`"Z"`, `"UTC"`, `"+1"`, `"-6:00"`, `"+0530"`, ...).
+fn parse_time_zone(input: &str) -> Option<i64> {
+    if input.is_empty() || input == "z" || input == "utc" || input == "gmt" {
+        return Some(0);
+    }
+    let (sign, input): (i64, &str) = match input.as_bytes()[0] {
+        b'+' => (1, &input[1..]),
+        b'-' => (-1, &input[1..]),
+        _ => return None,
+    };
+    if input.is_empty() || !input.bytes().all(|b: u8| b.is_ascii_digit() || b == b':') {
+        return None;
+    }
+    let (hours, minutes): (i64, i64) = match input.find(':') {
+        Some(colon_pos) => (input[..colon_pos].parse().ok()?, input[colon_pos + 1..].parse().ok()?),
+        None if input.len() > 2 => (input[..input.len() - 2].parse().ok()?, input[input.len() - 2..].parse().ok()?),
+        None => (input.parse().ok()?, 0),
+    };
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    return Some(sign * (hours * 3_600 + minutes * 60));
+}
+
+#[cfg(all(test, feature = "read", feature = "write"))]
+mod tests {
+
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+
+    use std::io::Cursor;
+
+    use super::{decode_time, parse_time_units, read_records_by_time, select_records_by_time, Calendar};
+    use crate::{DataSet, DataVector, FileReader, FileWriter, Version};
+    use crate::error::{DecodeTimeError, ReadError, SelectTimeError};
+
+    const TIME_VAR_NAME: &str = "time";
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, min, sec).unwrap()
+    }
+
+    fn time_data_set(units: Option<&str>, calendar: Option<&str>) -> DataSet {
+        let mut data_set = DataSet::new();
+        data_set.set_unlimited_dim(TIME_VAR_NAME, 2).unwrap();
+        data_set.add_var_i32(TIME_VAR_NAME, &[TIME_VAR_NAME]).unwrap();
+        if let Some(units) = units {
+            data_set.add_var_attr_string(TIME_VAR_NAME, "units", units).unwrap();
+        }
+        if let Some(calendar) = calendar {
+            data_set.add_var_attr_string(TIME_VAR_NAME, "calendar", calendar).unwrap();
+        }
+        data_set
+    }
+
+    #[test]
+    fn test_parse_time_units() {
+        assert_eq!(
+            Some((86_400.0, utc(1970, 1, 1, 0, 0, 0))),
+            parse_time_units("days since 1970-01-01", Calendar::Standard)
+        );
+        assert_eq!(
+            Some((3_600.0, utc(1990, 5, 6, 12, 30, 0))),
+            parse_time_units("Hours since 1990-5-6 12:30", Calendar::Standard)
+        );
+        assert_eq!(
+            Some((1.0, utc(2000, 1, 1, 0, 0, 0) + Duration::milliseconds(500))),
+            parse_time_units("seconds since 2000-01-01T00:00:00.5Z", Calendar::Standard)
+        );
+        assert_eq!(
+            Some((60.0, utc(2000, 1, 1, 6, 0, 0))),
+            parse_time_units("minutes since 2000-01-01 00:00:00 -6:00", Calendar::Standard)
+        );
+        assert_eq!(
+            Some((60.0, utc(1999, 12, 31, 18, 30, 0))),
+            parse_time_units("minutes since 2000-01-01 00:00:00 +0530", Calendar::ProlepticGregorian)
+        );
+        // The reference date is expressed in the Julian calendar
+        assert_eq!(
+            Some((86_400.0, utc(0, 12, 30, 0, 0, 0))),
+            parse_time_units("days since 0001-01-01 00:00:00", Calendar::Standard)
+        );
+        assert_eq!(
+            Some((86_400.0, utc(1, 1, 1, 0, 0, 0))),
+            parse_time_units("days since 0001-01-01 00:00:00", Calendar::ProlepticGregorian)
+        );
+        assert_eq!(
+            Some((86_400.0, utc(2000, 1, 14, 0, 0, 0))),
+            parse_time_units("days since 2000-01-01", Calendar::Julian)
+        );
+
+        // The Julian/Gregorian switch of the standard calendar
+        assert_eq!(
+            Some((86_400.0, utc(1582, 10, 14, 0, 0, 0))),
+            parse_time_units("days since 1582-10-04", Calendar::Standard)
+        );
+        assert_eq!(
+            Some((86_400.0, utc(1582, 10, 15, 0, 0, 0))),
+            parse_time_units("days since 1582-10-15", Calendar::Standard)
+        );
+        assert_eq!(None,    parse_time_units("days since 1582-10-05", Calendar::Standard));
+        assert_eq!(None,    parse_time_units("days since 1582-10-14", Calendar::Standard));
+        assert_eq!(
+            Some((86_400.0, utc(1582, 10, 10, 0, 0, 0))),
+            parse_time_units("days since 1582-10-10", Calendar::ProlepticGregorian)
+        );
+
+        assert_eq!(None,    parse_time_units("days", Calendar::Standard));
+        assert_eq!(None,    parse_time_units("fortnights since 1970-01-01", Calendar::Standard));
+        assert_eq!(None,    parse_time_units("days since 1970-13-01", Calendar::Standard));
+        assert_eq!(None,    parse_time_units("days since 1970-01-01 25:00:00", Calendar::Standard));
+        assert_eq!(None,    parse_time_units("days since 1970-01-01 00:00:00 CEST", Calendar::Standard));
+    }
+
+    #[test]
+    fn test_decode_time() {
+        let data_set: DataSet = time_data_set(Some("days since 1970-01-01"), Some("gregorian"));
+        let times: Vec<DateTime<Utc>> = decode_time(
+            data_set.get_var(TIME_VAR_NAME).unwrap(),
+            &DataVector::I32(vec![0, 18_262]),
+        ).unwrap();
+        assert_eq!(vec![utc(1970, 1, 1, 0, 0, 0), utc(2020, 1, 1, 0, 0, 0)],   times);
+
+        let times: Vec<DateTime<Utc>> = decode_time(
+            data_set.get_var(TIME_VAR_NAME).unwrap(),
+            &DataVector::F32(vec![-0.5, 0.25]),
+        ).unwrap();
+        assert_eq!(vec![utc(1969, 12, 31, 12, 0, 0), utc(1970, 1, 1, 6, 0, 0)],    times);
+
+        // 1582-10-04 (Julian) is followed by 1582-10-15 (Gregorian) in the standard calendar
+        let data_set: DataSet = time_data_set(Some("days since 1582-10-04"), Some("standard"));
+        let times: Vec<DateTime<Utc>> = decode_time(
+            data_set.get_var(TIME_VAR_NAME).unwrap(),
+            &DataVector::I32(vec![0, 1]),
+        ).unwrap();
+        assert_eq!(vec![utc(1582, 10, 14, 0, 0, 0), utc(1582, 10, 15, 0, 0, 0)],   times);
+
+        let data_set: DataSet = time_data_set(Some("days since 1582-10-15"), Some("standard"));
+        let times: Vec<DateTime<Utc>> = decode_time(
+            data_set.get_var(TIME_VAR_NAME).unwrap(),
+            &DataVector::I32(vec![-1, 0]),
+        ).unwrap();
+        assert_eq!(vec![utc(1582, 10, 14, 0, 0, 0), utc(1582, 10, 15, 0, 0, 0)],   times);
+    }
+
+    #[test]
+    fn test_decode_time_errors() {
+        let data: DataVector = DataVector::F64(vec![0.0, f64::NAN]);
+
+        let data_set: DataSet = time_data_set(None, None);
+        assert_eq!(
+            DecodeTimeError::UnitsNotDefined(String::from(TIME_VAR_NAME)),
+            decode_time(data_set.get_var(TIME_VAR_NAME).unwrap(), &data).unwrap_err()
+        );
+
+        let data_set: DataSet = time_data_set(Some("days after 1970-01-01"), None);
+        assert_eq!(
+            DecodeTimeError::UnitsNotValid{var_name: String::from(TIME_VAR_NAME), units: String::from("days after 1970-01-01")},
+            decode_time(data_set.get_var(TIME_VAR_NAME).unwrap(), &data).unwrap_err()
+        );
+
+        let data_set: DataSet = time_data_set(Some("days since 1582-10-10"), Some("gregorian"));
+        assert_eq!(
+            DecodeTimeError::UnitsNotValid{var_name: String::from(TIME_VAR_NAME), units: String::from("days since 1582-10-10")},
+            decode_time(data_set.get_var(TIME_VAR_NAME).unwrap(), &data).unwrap_err()
+        );
+
+        let data_set: DataSet = time_data_set(Some("days since 1970-01-01"), Some("noleap"));
+        assert_eq!(
+            DecodeTimeError::CalendarNotSupported{var_name: String::from(TIME_VAR_NAME), calendar: String::from("noleap")},
+            decode_time(data_set.get_var(TIME_VAR_NAME).unwrap(), &data).unwrap_err()
+        );
+
+        let data_set: DataSet = time_data_set(Some("days since 1970-01-01"), None);
+        assert_eq!(
+            DecodeTimeError::DateTimeOutOfRange{var_name: String::from(TIME_VAR_NAME), index: 1},
+            decode_time(data_set.get_var(TIME_VAR_NAME).unwrap(), &data).unwrap_err()
+        );
+    }
+
+    fn time_file_reader(data_set: &DataSet, time_data: &[f64]) -> FileReader<Cursor<Vec<u8>>> {
+        let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+        file_writer.set_def(data_set, Version::Classic, 0).unwrap();
+        if data_set.has_var(TIME_VAR_NAME) {
+            file_writer.write_var_f64(TIME_VAR_NAME, time_data).unwrap();
+        }
+        return FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_select_records_by_time() {
+        let mut data_set = DataSet::new();
+        data_set.set_unlimited_dim(TIME_VAR_NAME, 4).unwrap();
+        data_set.add_var_f64(TIME_VAR_NAME, &[TIME_VAR_NAME]).unwrap();
+        data_set.add_var_attr_string(TIME_VAR_NAME, "units", "hours since 2020-01-01").unwrap();
+        let mut file_reader = time_file_reader(&data_set, &[0.0, 1.0, 2.0, 3.0]);
+
+        // the end is excluded
+        assert_eq!(1..3,    select_records_by_time(&mut file_reader, utc(2020, 1, 1, 1, 0, 0)..utc(2020, 1, 1, 3, 0, 0)).unwrap());
+        assert_eq!(0..4,    select_records_by_time(&mut file_reader, utc(2019, 1, 1, 0, 0, 0)..utc(2021, 1, 1, 0, 0, 0)).unwrap());
+        // no record in the interval
+        assert_eq!(2..2,    select_records_by_time(&mut file_reader, utc(2020, 1, 1, 1, 30, 0)..utc(2020, 1, 1, 1, 45, 0)).unwrap());
+        assert_eq!(4..4,    select_records_by_time(&mut file_reader, utc(2021, 1, 1, 0, 0, 0)..utc(2022, 1, 1, 0, 0, 0)).unwrap());
+        assert_eq!(0..0,    select_records_by_time(&mut file_reader, utc(2019, 1, 1, 0, 0, 0)..utc(2019, 1, 2, 0, 0, 0)).unwrap());
+    }
+
+    #[test]
+    fn test_select_records_by_time_error() {
+        let range = utc(2020, 1, 1, 0, 0, 0)..utc(2021, 1, 1, 0, 0, 0);
+
+        // no unlimited dimension
+        let mut file_reader = time_file_reader(&DataSet::new(), &[]);
+        assert_eq!(SelectTimeError::UnlimitedDimensionNotDefined,    select_records_by_time(&mut file_reader, range.clone()).unwrap_err());
+
+        // no time coordinate
+        let mut data_set = DataSet::new();
+        data_set.set_unlimited_dim(TIME_VAR_NAME, 2).unwrap();
+        let mut file_reader = time_file_reader(&data_set, &[]);
+        assert_eq!(
+            SelectTimeError::TimeCoordinateNotDefined(String::from(TIME_VAR_NAME)),
+            select_records_by_time(&mut file_reader, range.clone()).unwrap_err()
+        );
+
+        // no units
+        data_set.add_var_f64(TIME_VAR_NAME, &[TIME_VAR_NAME]).unwrap();
+        let mut file_reader = time_file_reader(&data_set, &[0.0, 1.0]);
+        assert_eq!(
+            SelectTimeError::DecodeTime(DecodeTimeError::UnitsNotDefined(String::from(TIME_VAR_NAME))),
+            select_records_by_time(&mut file_reader, range.clone()).unwrap_err()
+        );
+
+        // undefined variable
+        data_set.add_var_attr_string(TIME_VAR_NAME, "units", "days since 2020-01-01").unwrap();
+        let mut file_reader = time_file_reader(&data_set, &[0.0, 1.0]);
+        assert_eq!(
+            SelectTimeError::Read(ReadError::VariableNotDefined(String::from("undef_var"))),
+            read_records_by_time(&mut file_reader, range, &["undef_var"]).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_read_records_by_time() {
+        let mut data_set = DataSet::new();
+        data_set.set_unlimited_dim(TIME_VAR_NAME, 3).unwrap();
+        data_set.add_fixed_dim("station", 2).unwrap();
+        data_set.add_var_f64(TIME_VAR_NAME, &[TIME_VAR_NAME]).unwrap();
+        data_set.add_var_attr_string(TIME_VAR_NAME, "units", "days since 2020-01-01").unwrap();
+        data_set.add_var_i32("station", &["station"]).unwrap();
+        data_set.add_var_i16("count", &[TIME_VAR_NAME, "station"]).unwrap();
+        let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+        file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+        file_writer.write_var_f64(TIME_VAR_NAME, &[0.0, 1.0, 2.0]).unwrap();
+        file_writer.write_var_i32("station", &[10, 20]).unwrap();
+        file_writer.write_var_i16("count", &[1, 2, 3, 4, 5, 6]).unwrap();
+        let mut file_reader = FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+
+        let (records, vars_data) = read_records_by_time(&mut file_reader, utc(2020, 1, 1, 12, 0, 0)..utc(2020, 1, 10, 0, 0, 0), &[TIME_VAR_NAME, "station", "count"]).unwrap();
+        assert_eq!(1..3,                                    records);
+        assert_eq!(Some(&DataVector::F64(vec![1.0, 2.0])),  vars_data.get(TIME_VAR_NAME));
+        // the fixed-size variables are read entirely
+        assert_eq!(Some(&DataVector::I32(vec![10, 20])),    vars_data.get("station"));
+        assert_eq!(Some(&DataVector::I16(vec![3, 4, 5, 6])),    vars_data.get("count"));
+
+        // no selected record
+        let (records, vars_data) = read_records_by_time(&mut file_reader, utc(2021, 1, 1, 0, 0, 0)..utc(2022, 1, 1, 0, 0, 0), &["count"]).unwrap();
+        assert_eq!(3..3,                                    records);
+        assert_eq!(Some(&DataVector::I16(vec![])),          vars_data.get("count"));
+    }
+}
@@ -0,0 +1,259 @@
+//! Basic parsing and conversion of the CF unit strings.
+//!
+//! Only a small subset of the [UDUNITS](https://www.unidata.ucar.edu/software/udunits/) grammar is supported :
+//! an optional SI prefix followed by a base unit (`"km"`, `"hPa"`, `"milliseconds"`, `"degC"`, ...),
+//! and the time units `"<time unit> since <reference date>"`.
+//!
+//! # Example
+//!
+//! ```
+//! use netcdf3::cf::units::{convert, BaseUnit, TimeUnits, Unit};
+//!
+//! assert_eq!(Some(vec![0.0, 100.0]),         convert(&[273.15, 373.15], "K", "degC"));
+//! assert_eq!(Some(vec![1.5]),                convert(&[1500.0], "m", "km"));
+//! assert_eq!(None,                           convert(&[1.0], "m", "s"));
+//!
+//! let hpa: Unit = Unit::parse("hPa").unwrap();
+//! assert_eq!(BaseUnit::Pascal,               hpa.base);
+//! assert_eq!(100.0,                          hpa.factor);
+//!
+//! let time_units: TimeUnits = TimeUnits::parse("hours since 1970-01-01 00:00:00").unwrap();
+//! assert_eq!(3600.0,                         time_units.unit.factor);
+//! assert_eq!("1970-01-01 00:00:00",          time_units.reference);
+//! ```
+
+/// Base units (the SI units, or dimensionless).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaseUnit {
+    Dimensionless,
+    Meter,
+    Second,
+    Kilogram,
+    Kelvin,
+    Pascal,
+}
+
+/// Parsed unit, the values are converted into the base unit with `base_value = value * factor + offset`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unit {
+    pub base: BaseUnit,
+    pub factor: f64,
+    pub offset: f64,
+}
+
+struct UnitDef {
+    symbols: &'static [&'static str],
+    names: &'static [&'static str],
+    base: BaseUnit,
+    factor: f64,
+    offset: f64,
+    prefixable: bool,
+}
+
+const UNIT_DEFS: &[UnitDef] = &[
+    UnitDef{symbols: &["1", ""], names: &[], base: BaseUnit::Dimensionless, factor: 1.0, offset: 0.0, prefixable: false},
+    UnitDef{symbols: &["%"], names: &["percent"], base: BaseUnit::Dimensionless, factor: 0.01, offset: 0.0, prefixable: false},
+    UnitDef{symbols: &["m"], names: &["meter", "metre"], base: BaseUnit::Meter, factor: 1.0, offset: 0.0, prefixable: true},
+    UnitDef{symbols: &["s", "sec"], names: &["second", "sec"], base: BaseUnit::Second, factor: 1.0, offset: 0.0, prefixable: true},
+    UnitDef{symbols: &["msec"], names: &["msec"], base: BaseUnit::Second, factor: 1.0e-3, offset: 0.0, prefixable: false},
+    UnitDef{symbols: &["usec"], names: &["usec"], base: BaseUnit::Second, factor: 1.0e-6, offset: 0.0, prefixable: false},
+    UnitDef{symbols: &["min"], names: &["minute", "min"], base: BaseUnit::Second, factor: 60.0, offset: 0.0, prefixable: false},
+    UnitDef{symbols: &["h", "hr"], names: &["hour", "hr"], base: BaseUnit::Second, factor: 3_600.0, offset: 0.0, prefixable: false},
+    UnitDef{symbols: &["d"], names: &["day"], base: BaseUnit::Second, factor: 86_400.0, offset: 0.0, prefixable: false},
+    UnitDef{symbols: &["g"], names: &["gram"], base: BaseUnit::Kilogram, factor: 1.0e-3, offset: 0.0, prefixable: true},
+    UnitDef{symbols: &["K", "degK", "deg_K"], names: &["kelvin", "degree_kelvin"], base: BaseUnit::Kelvin, factor: 1.0, offset: 0.0, prefixable: true},
+    UnitDef{
+        symbols: &["degC", "deg_C", "°C"], names: &["celsius", "degree_celsius", "degree_c"],
+        base: BaseUnit::Kelvin, factor: 1.0, offset: 273.15, prefixable: false,
+    },
+    UnitDef{symbols: &["Pa"], names: &["pascal"], base: BaseUnit::Pascal, factor: 1.0, offset: 0.0, prefixable: true},
+    UnitDef{symbols: &["bar"], names: &["bar"], base: BaseUnit::Pascal, factor: 1.0e5, offset: 0.0, prefixable: true},
+];
+
+/// SI prefixes : `(symbol, name, factor)`
+const PREFIXES: &[(&str, &str, f64)] = &[
+    ("G", "giga", 1.0e9),
+    ("M", "mega", 1.0e6),
+    ("k", "kilo", 1.0e3),
+    ("h", "hecto", 1.0e2),
+    ("da", "deca", 1.0e1),
+    ("d", "deci", 1.0e-1),
+    ("c", "centi", 1.0e-2),
+    ("m", "milli", 1.0e-3),
+    ("u", "micro", 1.0e-6),
+    ("µ", "micro", 1.0e-6),
+    ("n", "nano", 1.0e-9),
+];
+
+impl Unit {
+
+    /// Parses a unit string, returns `None` if the unit is not supported.
+    ///
+    /// The symbols are case sensitive (`"Mm"` is not `"mm"`), the names are not (`"Hours"`, `"KILOMETERS"`).
+    pub fn parse(units: &str) -> Option<Unit> {
+        let units: &str = units.trim();
+        let names: [String; 2] = name_forms(units);
+        let has_name = |unit_def: &UnitDef, name_prefix: &str| -> bool {
+            names.iter().any(|name: &String| {
+                name.strip_prefix(name_prefix).map(|name: &str| unit_def.names.contains(&name)).unwrap_or(false)
+            })
+        };
+        let to_unit = |unit_def: &UnitDef, prefix_factor: f64| -> Unit {
+            Unit{base: unit_def.base, factor: prefix_factor * unit_def.factor, offset: unit_def.offset}
+        };
+        // without prefix
+        if let Some(unit_def) = UNIT_DEFS.iter().find(|unit_def: &&UnitDef| unit_def.symbols.contains(&units) || has_name(unit_def, "")) {
+            return Some(to_unit(unit_def, 1.0));
+        }
+        // with a prefix
+        for (prefix_symbol, prefix_name, prefix_factor) in PREFIXES.iter() {
+            let prefixable_defs = UNIT_DEFS.iter().filter(|unit_def: &&UnitDef| unit_def.prefixable);
+            for unit_def in prefixable_defs {
+                let has_symbol: bool = units.strip_prefix(prefix_symbol).map(|symbol: &str| unit_def.symbols.contains(&symbol)).unwrap_or(false);
+                if has_symbol || has_name(unit_def, prefix_name) {
+                    return Some(to_unit(unit_def, *prefix_factor));
+                }
+            }
+        }
+        return None;
+    }
+
+    /// Returns `true` if the values can be converted between both units.
+    pub fn is_convertible_to(&self, other: &Unit) -> bool {
+        return self.base == other.base;
+    }
+
+    /// Converts a value into the base unit.
+    pub fn to_base(&self, value: f64) -> f64 {
+        return value * self.factor + self.offset;
+    }
+
+    /// Converts a value from the base unit.
+    pub fn from_base(&self, base_value: f64) -> f64 {
+        return (base_value - self.offset) / self.factor;
+    }
+}
+
+/// Returns the lower case name, and the same name without the plural form (`"Meters"` -> `"meter"`, `"degrees_Celsius"` -> `"degree_celsius"`).
+fn name_forms(units: &str) -> [String; 2] {
+    let name: String = units.to_lowercase();
+    let singular: String = match name.strip_suffix('s') {
+        Some(singular) if singular.len() > 1 => singular.to_string(),
+        _ => name.clone(),
+    };
+    let singular: String = singular.replace("degrees", "degree");
+    return [name.replace("degrees", "degree"), singular];
+}
+
+/// Parsed CF time units `"<time unit> since <reference date>"`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeUnits {
+    /// Time unit, its base unit is `BaseUnit::Second`
+    pub unit: Unit,
+    /// Reference date, not parsed
+    pub reference: String,
+}
+
+impl TimeUnits {
+
+    /// Parses CF time units, returns `None` if the unit is not supported or is not a time unit.
+    pub fn parse(units: &str) -> Option<TimeUnits> {
+        const SINCE: &str = " since ";
+        let since_pos: usize = units.to_ascii_lowercase().find(SINCE)?;
+        let unit: Unit = Unit::parse(&units[..since_pos])?;
+        if unit.base != BaseUnit::Second {
+            return None;
+        }
+        let reference: &str = units[since_pos + SINCE.len()..].trim();
+        if reference.is_empty() {
+            return None;
+        }
+        return Some(TimeUnits{unit, reference: reference.to_string()});
+    }
+}
+
+/// Converts values between two units.
+///
+/// Returns `None` if one of the units is not supported or if the units are not convertible.
+pub fn convert(values: &[f64], from_units: &str, to_units: &str) -> Option<Vec<f64>> {
+    let from_unit: Unit = Unit::parse(from_units)?;
+    let to_unit: Unit = Unit::parse(to_units)?;
+    if !from_unit.is_convertible_to(&to_unit) {
+        return None;
+    }
+    return Some(values.iter().map(|value: &f64| to_unit.from_base(from_unit.to_base(*value))).collect());
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{BaseUnit, TimeUnits, Unit};
+
+    fn unit(base: BaseUnit, factor: f64, offset: f64) -> Option<Unit> {
+        Some(Unit{base, factor, offset})
+    }
+
+    #[test]
+    fn test_parse_unit() {
+        assert_eq!(unit(BaseUnit::Meter, 1.0, 0.0),             Unit::parse("m"));
+        assert_eq!(unit(BaseUnit::Meter, 1.0, 0.0),             Unit::parse("meters"));
+        assert_eq!(unit(BaseUnit::Meter, 1.0e3, 0.0),           Unit::parse("km"));
+        assert_eq!(unit(BaseUnit::Meter, 1.0e3, 0.0),           Unit::parse("Kilometres"));
+        assert_eq!(unit(BaseUnit::Meter, 1.0e-3, 0.0),          Unit::parse("mm"));
+        assert_eq!(unit(BaseUnit::Meter, 1.0e6, 0.0),           Unit::parse("Mm"));
+        assert_eq!(unit(BaseUnit::Second, 60.0, 0.0),           Unit::parse("min"));
+        assert_eq!(unit(BaseUnit::Second, 3_600.0, 0.0),        Unit::parse("Hours"));
+        assert_eq!(unit(BaseUnit::Second, 86_400.0, 0.0),       Unit::parse("days"));
+        assert_eq!(unit(BaseUnit::Second, 1.0e-3, 0.0),         Unit::parse("ms"));
+        assert_eq!(unit(BaseUnit::Second, 1.0e-6, 0.0),         Unit::parse("microseconds"));
+        assert_eq!(unit(BaseUnit::Second, 1.0e-3, 0.0),         Unit::parse("msec"));
+        assert_eq!(unit(BaseUnit::Second, 1.0e-3, 0.0),         Unit::parse("msecs"));
+        assert_eq!(unit(BaseUnit::Second, 1.0e-6, 0.0),         Unit::parse("usec"));
+        assert_eq!(unit(BaseUnit::Second, 1.0e-6, 0.0),         Unit::parse("usecs"));
+        assert_eq!(unit(BaseUnit::Kilogram, 1.0, 0.0),          Unit::parse("kg"));
+        assert_eq!(unit(BaseUnit::Kelvin, 1.0, 0.0),            Unit::parse("K"));
+        assert_eq!(unit(BaseUnit::Kelvin, 1.0, 273.15),         Unit::parse("degC"));
+        assert_eq!(unit(BaseUnit::Kelvin, 1.0, 273.15),         Unit::parse("degrees_Celsius"));
+        assert_eq!(unit(BaseUnit::Pascal, 1.0e2, 0.0),          Unit::parse("hPa"));
+        assert_eq!(unit(BaseUnit::Pascal, 1.0e2, 0.0),          Unit::parse("mbar"));
+        assert_eq!(unit(BaseUnit::Dimensionless, 1.0, 0.0),     Unit::parse("1"));
+        assert_eq!(unit(BaseUnit::Dimensionless, 0.01, 0.0),    Unit::parse("%"));
+
+        assert_eq!(None,        Unit::parse("furlong"));
+        assert_eq!(None,        Unit::parse("kdegC"));
+        assert_eq!(None,        Unit::parse("MM"));
+    }
+
+    #[test]
+    fn test_parse_time_units() {
+        assert_eq!(
+            Some(TimeUnits{unit: Unit{base: BaseUnit::Second, factor: 86_400.0, offset: 0.0}, reference: String::from("1970-01-01")}),
+            TimeUnits::parse("days since 1970-01-01")
+        );
+        assert_eq!(
+            Some(TimeUnits{unit: Unit{base: BaseUnit::Second, factor: 1.0, offset: 0.0}, reference: String::from("2000-1-1 0:0:0 -6:00")}),
+            TimeUnits::parse("Seconds SINCE 2000-1-1 0:0:0 -6:00")
+        );
+        assert_eq!(
+            Some(TimeUnits{unit: Unit{base: BaseUnit::Second, factor: 1.0e-3, offset: 0.0}, reference: String::from("1970-01-01")}),
+            TimeUnits::parse("msecs since 1970-01-01")
+        );
+        assert_eq!(
+            Some(TimeUnits{unit: Unit{base: BaseUnit::Second, factor: 1.0e-6, offset: 0.0}, reference: String::from("1970-01-01")}),
+            TimeUnits::parse("usec since 1970-01-01")
+        );
+        assert_eq!(None,        TimeUnits::parse("days"));
+        assert_eq!(None,        TimeUnits::parse("days since "));
+        assert_eq!(None,        TimeUnits::parse("meters since 1970-01-01"));
+    }
+
+    #[test]
+    fn test_convert() {
+        assert_eq!(Some(vec![-273.15, 0.0]),    super::convert(&[0.0, 273.15], "K", "degC"));
+        assert_eq!(Some(vec![1_000.0]),         super::convert(&[1.0], "km", "m"));
+        assert_eq!(Some(vec![1.0]),             super::convert(&[1_000.0], "hPa", "bar"));
+        assert_eq!(Some(vec![2.0]),             super::convert(&[120.0], "minutes", "hours"));
+        assert_eq!(None,                        super::convert(&[1.0], "km", "kg"));
+        assert_eq!(None,                        super::convert(&[1.0], "km", "furlong"));
+    }
+}
@@ -5,15 +5,40 @@ pub(crate) use dimension::DimensionSize;
 mod attribute;
 pub use attribute::Attribute;
 
+mod attr_value;
+pub use attr_value::AttrValue;
+
 mod variable;
 pub use variable::Variable;
+#[cfg(feature = "std")]
+pub(crate) use variable::SAME_TYPE_ATTR_NAMES;
+
+mod handle;
+pub use handle::{DimRef, VarRef};
+
+mod dim_id;
+pub use dim_id::{DimId, DimKey};
+
+mod name_index;
+use name_index::NameIndex;
+
+mod vars_layout;
+use vars_layout::VarsLayout;
 
 mod tests;
 
-use std::{cell::RefMut, ops::Deref, rc::Rc};
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use core::cell::RefMut;
+use alloc::rc::Rc;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
 
-use crate::{DataType, InvalidDataSet};
+use crate::{DataType, InvalidDataSet, NameError, NameValidation, Version, NC_MAX_NAME_SIZE};
+#[cfg(feature = "std")]
+use crate::data_vector::DataSlice;
 use crate::data_vector::DataVector;
+use crate::name_string::check_name;
 
 /// Default fill value for the `i8` elements (same value as `NC_FILL_BYTE` defined in the header file [netcdf.h](https://www.unidata.ucar.edu/software/netcdf/docs/netcdf_8h.html))
 ///
@@ -106,6 +131,7 @@ pub const NC_FILL_F64: f64 = 9.9692099683868690e+36;
 /// assert_eq!(
 ///     InvalidDataSet::MaximumFixedDimensionSizeExceeded{
 ///         dim_name: String::from(DIM_NAME),
+///         max: NC_MAX_DIM_SIZE,
 ///         get: NC_MAX_DIM_SIZE + 1,
 ///     },
 ///     data_set.add_fixed_dim(DIM_NAME, NC_MAX_DIM_SIZE + 1).unwrap_err()
@@ -117,7 +143,7 @@ pub const NC_FILL_F64: f64 = 9.9692099683868690e+36;
 /// assert_eq!(false,                   data_set.has_dim(DIM_NAME));
 /// assert_eq!(None,                    data_set.dim_size(DIM_NAME));
 /// ```
-pub const NC_MAX_DIM_SIZE: usize = (std::i32::MAX - 3) as usize;
+pub const NC_MAX_DIM_SIZE: usize = (core::i32::MAX - 3) as usize;
 
 /// Maximum number of dimensions per variable
 ///
@@ -185,6 +211,7 @@ pub const NC_MAX_DIM_SIZE: usize = (std::i32::MAX - 3) as usize;
 /// assert_eq!(
 ///     InvalidDataSet::MaximumDimensionsPerVariableExceeded{
 ///         var_name: String::from(VAR_NAME),
+///         max: NC_MAX_VAR_DIMS,
 ///         num_dims: NC_MAX_VAR_DIMS + 1
 ///     },
 ///     data_set.add_var_i32(VAR_NAME, &too_long_dim_list).unwrap_err(),
@@ -253,12 +280,75 @@ pub const NC_MAX_VAR_DIMS: usize = 1024;
 /// data_set.add_var_attr_u8("air_temperature", "units", String::from("Celsius").into_bytes()).unwrap();
 ///
 /// ```
-#[derive(Debug, PartialEq)]
 pub struct DataSet {
     pub(crate) unlimited_dim: Option<Rc<Dimension>>,
     pub(crate) dims: Vec<Rc<Dimension>>,
     pub(crate) attrs: Vec<Attribute>,
     pub(crate) vars: Vec<Variable>,
+    pub(crate) name_validation: NameValidation,
+    /// Accepts the names exceeding `NC_MAX_NAME_SIZE` bytes, only while reading a file (see `LongNamePolicy::Preserve`)
+    pub(crate) allow_long_names: bool,
+    /// Positions of the dimensions, the global attributes and the variables, indexed by name
+    dims_index: NameIndex,
+    attrs_index: NameIndex,
+    vars_index: NameIndex,
+    /// Incremented when a dimension is removed, invalidating the previously returned [`DimId`](struct.DimId.html)s
+    dims_generation: u32,
+    /// Sizes of the record variables
+    vars_layout: VarsLayout,
+}
+
+impl core::fmt::Debug for DataSet {
+    /// The indexes of the names are not displayed.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        return f.debug_struct("DataSet")
+            .field("unlimited_dim", &self.unlimited_dim)
+            .field("dims", &self.dims)
+            .field("attrs", &self.attrs)
+            .field("vars", &self.vars)
+            .field("name_validation", &self.name_validation)
+            .finish();
+    }
+}
+
+impl PartialEq for DataSet {
+    /// The rules of the name validation are not compared.
+    fn eq(&self, other: &Self) -> bool {
+        return self.unlimited_dim == other.unlimited_dim
+            && self.dims == other.dims
+            && self.attrs == other.attrs
+            && self.vars == other.vars;
+    }
+}
+
+/// Returns the variable, see [`get_var`](struct.DataSet.html#method.get_var) for the non-panicking lookup.
+///
+/// # Panics
+///
+/// Panics if the variable is not defined.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{DataSet, DataType};
+///
+/// let mut data_set = DataSet::new();
+/// data_set.add_fixed_dim("latitude", 3).unwrap();
+/// data_set.add_var_f32("t2m", &["latitude"]).unwrap();
+/// data_set.add_var_attr_string("t2m", "units", "K").unwrap();
+///
+/// assert_eq!(DataType::F32,                   data_set["t2m"].data_type());
+/// assert_eq!(Some(String::from("K")),         data_set["t2m"]["units"].as_string());
+/// ```
+impl core::ops::Index<&str> for DataSet {
+    type Output = Variable;
+
+    fn index(&self, var_name: &str) -> &Variable {
+        return match self.get_var(var_name) {
+            Some(var) => var,
+            None => panic!("the variable `{}` is not defined", var_name),
+        };
+    }
 }
 
 impl DataSet {
@@ -269,9 +359,77 @@ impl DataSet {
             dims: vec![],
             attrs: vec![],
             vars: vec![],
+            name_validation: NameValidation::default(),
+            allow_long_names: false,
+            dims_index: NameIndex::new(),
+            attrs_index: NameIndex::new(),
+            vars_index: NameIndex::new(),
+            dims_generation: 0,
+            vars_layout: VarsLayout::new(),
         }
     }
 
+    /// Returns a copy of the data set whose dimensions are not shared with the data set, the copy being modified
+    /// without modifying the data set.
+    #[cfg(feature = "std")]
+    pub(crate) fn deep_clone(&self) -> DataSet {
+        let dims: Vec<Rc<Dimension>> = self.dims.iter().map(|dim: &Rc<Dimension>| Rc::new(Dimension::clone(dim))).collect();
+        let clone_dim = |dim: &Rc<Dimension>| -> Rc<Dimension> {
+            return match self.dims.iter().position(|dim_2: &Rc<Dimension>| Rc::ptr_eq(dim, dim_2)) {
+                Some(dim_index) => Rc::clone(&dims[dim_index]),
+                None => Rc::new(Dimension::clone(dim)),
+            };
+        };
+        let vars: Vec<Variable> = self.vars.iter().map(|var: &Variable| {
+            let mut cloned_var: Variable = var.clone();
+            cloned_var.unlimited_dim = var.unlimited_dim.as_ref().map(clone_dim);
+            cloned_var.dims = var.dims.iter().map(clone_dim).collect();
+            cloned_var
+        }).collect();
+        return DataSet{
+            unlimited_dim: self.unlimited_dim.as_ref().map(clone_dim),
+            dims: dims,
+            attrs: self.attrs.clone(),
+            vars: vars,
+            name_validation: self.name_validation,
+            allow_long_names: self.allow_long_names,
+            dims_index: self.dims_index.clone(),
+            attrs_index: self.attrs_index.clone(),
+            vars_index: self.vars_index.clone(),
+            dims_generation: self.dims_generation,
+            vars_layout: self.vars_layout,
+        };
+    }
+
+    /// Sets the rules checking the names of the dimensions, the variables and the attributes added or renamed afterward
+    /// ([`NameValidation::Strict`](enum.NameValidation.html#variant.Strict) by default).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::{DataSet, InvalidDataSet, NameError, NameValidation};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// assert_eq!(NameValidation::Strict,      data_set.name_validation());
+    /// assert_eq!(
+    ///     InvalidDataSet::VariableNameNotValid{var_name: String::from(".hidden"), reason: NameError::FirstCharNotAllowed{chr: '.'}},
+    ///     data_set.add_var_i8::<&str>(".hidden", &[]).unwrap_err()
+    /// );
+    ///
+    /// data_set.set_name_validation(NameValidation::Lenient);
+    /// assert_eq!(NameValidation::Lenient,     data_set.name_validation());
+    /// data_set.add_var_i8::<&str>(".hidden", &[]).unwrap();
+    /// assert_eq!(true,                        data_set.has_var(".hidden"));
+    /// ```
+    pub fn set_name_validation(&mut self, name_validation: NameValidation) {
+        self.name_validation = name_validation;
+    }
+
+    /// Returns the rules checking the names (see [`set_name_validation`](struct.DataSet.html#method.set_name_validation)).
+    pub fn name_validation(&self) -> NameValidation {
+        return self.name_validation;
+    }
+
     // ----------------------------------------------------------------
     //
     //                          Dimensions
@@ -279,13 +437,24 @@ impl DataSet {
     // ----------------------------------------------------------------
     /// Appends a new *fixed size* dimension in the dataset.
     ///
-    /// Returns a error if an other dimension with the same name is already defined.
-    pub fn add_fixed_dim<T: std::convert::AsRef<str>>(&mut self, dim_name: T, dim_size: usize) -> Result<(), InvalidDataSet> {
+    /// Returns a error if an other dimension with the same name is already defined,
+    /// otherwise returns the [`DimId`](struct.DimId.html) of the dimension.
+    pub fn add_fixed_dim<T: core::convert::AsRef<str>>(&mut self, dim_name: T, dim_size: usize) -> Result<DimId, InvalidDataSet> {
         let dim_name: &str = dim_name.as_ref();
-        if self.dims.iter().position(|dim| *dim.name.borrow() == dim_name).is_some() {
+        if self.find_dim_from_name(dim_name).is_some() {
             return Err(InvalidDataSet::DimensionAlreadyExists(dim_name.to_string()));
         }
-        let new_fixed_size_dim = Rc::new(Dimension::new_fixed_size(dim_name, dim_size)?);
+        self.push_fixed_dim(dim_name, dim_size)?;
+        return Ok(DimId::new(self.dims.len() - 1, self.dims_generation));
+    }
+
+    /// Appends a new *fixed size* dimension without checking that its name is not already used.
+    ///
+    /// The dimensions sharing the same name are only distinguished by their ids, the methods using the dimension names
+    /// refer to the first one.
+    pub(crate) fn push_fixed_dim(&mut self, dim_name: &str, dim_size: usize) -> Result<(), InvalidDataSet> {
+        let new_fixed_size_dim = Rc::new(Dimension::new_fixed_size(dim_name, dim_size, self.name_validation, self.allow_long_names)?);
+        self.dims_index.push(dim_name, self.dims.len());
         self.dims.push(new_fixed_size_dim);
         return Ok(());
     }
@@ -295,21 +464,40 @@ impl DataSet {
     /// Returns a error if :
     ///  1. the *unlimited size* is already defined
     ///  2. if an other dimension with the same name is already defined
-    pub fn set_unlimited_dim<T: std::convert::AsRef<str>>(&mut self, dim_name: T, dim_size: usize) -> Result<(), InvalidDataSet> {
+    ///
+    /// Otherwise returns the [`DimId`](struct.DimId.html) of the dimension.
+    pub fn set_unlimited_dim<T: core::convert::AsRef<str>>(&mut self, dim_name: T, dim_size: usize) -> Result<DimId, InvalidDataSet> {
         let dim_name: &str = dim_name.as_ref();
         if let Some(unlimited_dim) = &self.unlimited_dim {
             return Err(InvalidDataSet::UnlimitedDimensionAlreadyExists(unlimited_dim.name()));
         }
-        if self.dims.iter().position(|dim| *dim.name.borrow() == dim_name).is_some() {
+        if self.find_dim_from_name(dim_name).is_some() {
             return Err(InvalidDataSet::DimensionAlreadyExists(dim_name.to_string()));
         }
-        let new_unlimited_dim = Rc::new(Dimension::new_unlimited_size(dim_name, dim_size)?);
+        self.push_unlimited_dim(dim_name, dim_size)?;
+        return Ok(DimId::new(self.dims.len() - 1, self.dims_generation));
+    }
+
+    /// Initializes the *unlimited size* dimension without checking that its name is not already used
+    /// (see [`push_fixed_dim`](struct.DataSet.html#method.push_fixed_dim)).
+    pub(crate) fn push_unlimited_dim(&mut self, dim_name: &str, dim_size: usize) -> Result<(), InvalidDataSet> {
+        if let Some(unlimited_dim) = &self.unlimited_dim {
+            return Err(InvalidDataSet::UnlimitedDimensionAlreadyExists(unlimited_dim.name()));
+        }
+        let new_unlimited_dim = Rc::new(Dimension::new_unlimited_size(dim_name, dim_size, self.name_validation, self.allow_long_names)?);
+        self.dims_index.push(dim_name, self.dims.len());
         self.dims.push(Rc::clone(&new_unlimited_dim));
         self.unlimited_dim = Some(new_unlimited_dim);
         return Ok(());
     }
 
+    /// Returns the [`DimId`](struct.DimId.html) of the dimension, or `None` if it is not defined.
+    pub fn dim_id(&self, dim_name: &str) -> Option<DimId> {
+        return self.dims_index.get(dim_name).map(|index: usize| DimId::new(index, self.dims_generation));
+    }
+
     /// Returns the number of dimensions defined in the data set.
+    #[inline]
     pub fn num_dims(&self) -> usize {
         return self.dims.len();
     }
@@ -330,11 +518,23 @@ impl DataSet {
             .map(|(_dim_index, dim): (usize, &Rc<Dimension>)| Rc::clone(dim))
     }
 
+    /// Returns a handle on the dimension (see [`DimRef`](struct.DimRef.html)), or `None`.
+    pub fn dim(&self, dim_name: &str) -> Option<DimRef<'_>> {
+        return self.find_dim_from_name(dim_name)
+            .map(|(_dim_index, dim): (usize, &Rc<Dimension>)| DimRef::new(self, dim));
+    }
+
     /// Returns the references of all the dimensions defined in the data set.
     pub fn get_dims(&self) -> Vec<Rc<Dimension>> {
         return self.dims.iter().map(|dim: &Rc<Dimension>| Rc::clone(dim)).collect();
     }
 
+    /// Returns all the dimensions defined in the data set, without cloning them (see [`get_dims`](#method.get_dims)).
+    #[inline]
+    pub fn dims(&self) -> &[Rc<Dimension>] {
+        return &self.dims;
+    }
+
     /// Returns the names all the dimensions defined in the data set.
     pub fn dim_names(&self) -> Vec<String>
     {
@@ -394,6 +594,8 @@ impl DataSet {
         }
 
         let removed_dim: Rc<Dimension> = self.dims.remove(removed_dim_index);
+        self.rebuild_dims_index();
+        self.dims_generation = self.dims_generation.wrapping_add(1);
 
         // Remove the *unlimited-size* dimension if necessary
         if removed_dim.is_unlimited() {
@@ -414,7 +616,7 @@ impl DataSet {
             return Ok(());
         }
 
-        let (_dim_position, renamed_dim): (usize, &Rc<Dimension>) = match self.find_dim_from_name(old_dim_name) {
+        let (dim_position, renamed_dim): (usize, &Rc<Dimension>) = match self.find_dim_from_name(old_dim_name) {
             None => {
                 return Err(InvalidDataSet::DimensionNotDefined(old_dim_name.to_string()));
             }
@@ -425,24 +627,28 @@ impl DataSet {
             return Err(InvalidDataSet::DimensionAlreadyExists(new_dim_name.to_string()));
         }
 
-        Dimension::check_dim_name(new_dim_name)?;
+        Dimension::check_dim_name(new_dim_name, self.name_validation, false)?;
 
-        let mut dim_name: RefMut<String> = renamed_dim.name.borrow_mut();
-        *dim_name = new_dim_name.to_string();
+        {
+            let mut dim_name: RefMut<String> = renamed_dim.name.borrow_mut();
+            *dim_name = new_dim_name.to_string();
+        }
+        let dim_names: Vec<String> = self.dim_names();
+        self.dims_index.rename(old_dim_name, new_dim_name, dim_position, dim_names.iter().map(String::as_str));
         return Ok(());
     }
 
     /// Find a dataset's dimension from is name.
     fn find_dim_from_name(&self, dim_name: &str) -> Option<(usize, &Rc<Dimension>)> {
-        return self
-            .dims
-            .iter()
-            .position(|dim| {
-                return dim.name.borrow().deref() == dim_name;
-            })
-            .map(|index| {
-                return (index, &self.dims[index]);
-            });
+        return self.dims_index.get(dim_name).map(|index: usize| {
+            return (index, &self.dims[index]);
+        });
+    }
+
+    /// Rebuilds the index of the dimension names, after a removal.
+    fn rebuild_dims_index(&mut self) {
+        let dim_names: Vec<String> = self.dim_names();
+        self.dims_index.rebuild(dim_names.iter().map(String::as_str));
     }
 
     pub fn get_dims_from_dim_ids(&self, dim_ids: &[usize]) -> Result<Vec<Rc<Dimension>>, InvalidDataSet> {
@@ -450,7 +656,7 @@ impl DataSet {
         let not_found_dim_ids: Vec<usize> = dim_ids
             .iter()
             .filter(|dim_id: &&usize| self.dims.get(**dim_id).is_none())
-            .map(|i| i.clone())
+            .map(|i| *i)
             .collect();
         if !not_found_dim_ids.is_empty() {
             return Err(InvalidDataSet::DimensionIdsNotFound{
@@ -481,6 +687,11 @@ impl DataSet {
 
     /// Add a new variable in the dataset defined over named dimensions.
     ///
+    /// The dimensions are given by their names or by their [`DimId`](struct.DimId.html)s (see the trait [`DimKey`](trait.DimKey.html)).
+    ///
+    /// The error [`InvalidDataSet::MaximumVariableSizeExceeded`](enum.InvalidDataSet.html#variant.MaximumVariableSizeExceeded)
+    /// is returned if the variables exceed the limits of the 64-bit offset version (see [`check_format_limits`](#method.check_format_limits)).
+    ///
     /// # Examples
     ///
     /// Add a 2D variable
@@ -515,19 +726,18 @@ impl DataSet {
     /// assert_eq!(1,                   data_set.num_vars());
     /// assert_eq!(Some(1),             data_set.var_len(SCALAR_VAR_NAME));
     /// ```
-    pub fn add_var<T: std::convert::AsRef<str>>(&mut self, var_name: &str, dims_name: &[T], data_type: DataType) -> Result<(), InvalidDataSet> {
+    pub fn add_var<T: DimKey>(&mut self, var_name: &str, dims_name: &[T], data_type: DataType) -> Result<(), InvalidDataSet> {
 
         let var_dims: Vec<&Rc<Dimension>> = {
             let mut var_dims: Vec<&Rc<Dimension>> = vec![];
             let mut undefined_dims: Vec<String> = vec![];
-            for dim_name in dims_name.iter() {
-                let dim_name: &str = dim_name.as_ref();
-                match self.find_dim_from_name(dim_name) {
-                    None => {
-                        undefined_dims.push(dim_name.to_string());
+            for dim_key in dims_name.iter() {
+                match dim_key.dim_position(self) {
+                    Err(undef_dim_name) => {
+                        undefined_dims.push(undef_dim_name);
                     }
-                    Some((_index, dim)) => {
-                        var_dims.push(dim);
+                    Ok(index) => {
+                        var_dims.push(&self.dims[index]);
                     }
                 }
             }
@@ -544,46 +754,68 @@ impl DataSet {
             return Err(InvalidDataSet::VariableAlreadyExists(var_name.to_string()));
         }
         let var_dims: Vec<Rc<Dimension>> = var_dims.into_iter().map(|ref dim| Rc::clone(dim)).collect();
-        self.add_var_using_dim_refs(var_name, var_dims, data_type.clone())?;
+        self.add_checked_var(var_name, var_dims, data_type)?;
         Ok(())
     }
 
+    /// Appends a new variable, and checks the limits of the 64-bit offset version (see
+    /// [`check_format_limits`](#method.check_format_limits)), the data set being unchanged if they are exceeded.
+    ///
+    /// The limits of the classic version are checked by the [`FileWriter`](struct.FileWriter.html).
+    fn add_checked_var(&mut self, var_name: &str, var_dims: Vec<Rc<Dimension>>, data_type: DataType) -> Result<&mut Variable, InvalidDataSet> {
+        let prev_vars_layout: VarsLayout = self.vars_layout;
+        let _ = self.add_var_using_dim_refs(var_name, var_dims, data_type)?;
+        if let Err(err) = self.check_added_var_limits(&prev_vars_layout, Version::Offset64Bit) {
+            let _ = self.vars.pop();
+            self.vars_index.rebuild(self.vars.iter().map(Variable::name));
+            self.vars_layout = prev_vars_layout;
+            return Err(err);
+        }
+        return Ok(self.vars.last_mut().unwrap());
+    }
+
     pub(crate) fn add_var_using_dim_refs(&mut self, var_name: &str, var_dims: Vec<Rc<Dimension>>, data_type: DataType) -> Result<&Variable, InvalidDataSet> {
-        let _ = self.vars.push(Variable::new(var_name, var_dims, data_type)?);
+        let var: Variable = Variable::new(var_name, var_dims, data_type, self.name_validation, self.allow_long_names)?;
+        // Check that the record size can still be represented
+        let vars_layout: VarsLayout = self.vars_layout.checked_push(&var, self.vars.len()).ok_or_else(|| InvalidDataSet::RecordSizeOverflow(var_name.to_string()))?;
+        self.vars_index.push(var_name, self.vars.len());
+        self.vars.push(var);
+        self.vars_layout = vars_layout;
         Ok(self.vars.last().unwrap())
     }
 
     /// Add a new `i8` type variable  defined over named dimensions (see the [add_var](struct.DataSet.html#method.add_var) method).
-    pub fn add_var_i8<T: std::convert::AsRef<str>>(&mut self, var_name: &str, dims_name: &[T]) -> Result<(), InvalidDataSet> {
+    pub fn add_var_i8<T: DimKey>(&mut self, var_name: &str, dims_name: &[T]) -> Result<(), InvalidDataSet> {
         self.add_var(var_name, dims_name, DataType::I8)
     }
 
     /// Add a new `u8` type variable  defined over named dimensions (see the [add_var](struct.DataSet.html#method.add_var) method).
-    pub fn add_var_u8<T: std::convert::AsRef<str>>(&mut self, var_name: &str, dims_name: &[T]) -> Result<(), InvalidDataSet> {
+    pub fn add_var_u8<T: DimKey>(&mut self, var_name: &str, dims_name: &[T]) -> Result<(), InvalidDataSet> {
         self.add_var(var_name, dims_name, DataType::U8)
     }
 
     /// Add a new `i16` type variable  defined over named dimensions (see the [add_var](struct.DataSet.html#method.add_var) method).
-    pub fn add_var_i16<T: std::convert::AsRef<str>>(&mut self, var_name: &str, dims_name: &[T]) -> Result<(), InvalidDataSet> {
+    pub fn add_var_i16<T: DimKey>(&mut self, var_name: &str, dims_name: &[T]) -> Result<(), InvalidDataSet> {
         self.add_var(var_name, dims_name, DataType::I16)
     }
 
     /// Add a new `i32` type variable  defined over named dimensions (see the [add_var](struct.DataSet.html#method.add_var) method).
-    pub fn add_var_i32<T: std::convert::AsRef<str>>(&mut self, var_name: &str, dims_name: &[T]) -> Result<(), InvalidDataSet> {
+    pub fn add_var_i32<T: DimKey>(&mut self, var_name: &str, dims_name: &[T]) -> Result<(), InvalidDataSet> {
         self.add_var(var_name, dims_name, DataType::I32)
     }
 
     /// Add a new `f32` type variable  defined over named dimensions (see the [add_var](struct.DataSet.html#method.add_var) method).
-    pub fn add_var_f32<T: std::convert::AsRef<str>>(&mut self, var_name: &str, dims_name: &[T]) -> Result<(), InvalidDataSet> {
+    pub fn add_var_f32<T: DimKey>(&mut self, var_name: &str, dims_name: &[T]) -> Result<(), InvalidDataSet> {
         self.add_var(var_name, dims_name, DataType::F32)
     }
 
     /// Add a new `f64` type variable  defined over named dimensions (see the [add_var](struct.DataSet.html#method.add_var) method).
-    pub fn add_var_f64<T: std::convert::AsRef<str>>(&mut self, var_name: &str, dims_name: &[T]) -> Result<(), InvalidDataSet> {
+    pub fn add_var_f64<T: DimKey>(&mut self, var_name: &str, dims_name: &[T]) -> Result<(), InvalidDataSet> {
         self.add_var(var_name, dims_name, DataType::F64)
     }
 
     /// Returns the number of defined variables.
+    #[inline]
     pub fn num_vars(&self) -> usize {
         self.vars.len()
     }
@@ -630,6 +862,12 @@ impl DataSet {
             .ok();
     }
 
+    /// Returns a handle on the variable (see [`VarRef`](struct.VarRef.html)), or `None`.
+    pub fn var(&self, var_name: &str) -> Option<VarRef<'_>> {
+        return self.get_var(var_name)
+            .map(|var: &Variable| VarRef::new(self, var));
+    }
+
     /// Returns a mutable reference to the variable
     pub fn get_var_mut(&mut self, var_name: &str) -> Option<&mut Variable> {
         return self.find_var_from_name(var_name)
@@ -645,6 +883,12 @@ impl DataSet {
         return self.vars.iter().collect();
     }
 
+    /// Returns all the variables defined in the dataset, without collecting them (see [`get_vars`](#method.get_vars)).
+    #[inline]
+    pub fn vars(&self) -> &[Variable] {
+        return &self.vars;
+    }
+
     /// Returns the names all the variables defined in the dataset.
     pub fn get_var_names(&self) -> Vec<String>
     {
@@ -674,10 +918,11 @@ impl DataSet {
             return Err(InvalidDataSet::VariableAlreadyExists(new_var_name.to_string()));
         }
         // Check the validity of the new name
-        let _ = Variable::check_var_name(new_var_name)?;
+        Variable::check_var_name(new_var_name, self.name_validation, false)?;
 
         // Then rename the variable
         self.vars[renamed_var_index].name = new_var_name.to_string();
+        self.vars_index.rename(old_var_name, new_var_name, renamed_var_index, self.vars.iter().map(Variable::name));
 
         return Ok(());
     }
@@ -686,20 +931,121 @@ impl DataSet {
     pub fn remove_var(&mut self, var_name: &str) -> Result<Variable, InvalidDataSet> {
         let var_index: usize = self.find_var_from_name(var_name)?.0;
         let removed_var: Variable = self.vars.remove(var_index);
+        self.vars_index.rebuild(self.vars.iter().map(Variable::name));
+        self.rebuild_vars_layout();
         return Ok(removed_var);
     }
 
+    /// Adds the variable `new_var_name` having the dimensions, the data type and the attributes of the variable `var_name`,
+    /// for example to define a variable derived from an other one.
+    ///
+    /// The new variable is appended after the other variables, its values are not loaded (see [`duplicate_var_with_data`](#method.duplicate_var_with_data)).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::{DataSet, DataType, InvalidDataSet};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.add_fixed_dim("latitude", 3).unwrap();
+    /// data_set.add_var_f32("t2m", &["latitude"]).unwrap();
+    /// data_set.add_var_attr_string("t2m", "units", "K").unwrap();
+    ///
+    /// data_set.duplicate_var("t2m", "t2m_anomaly").unwrap();
+    /// assert_eq!(vec!["t2m", "t2m_anomaly"],                  data_set.get_var_names());
+    /// assert_eq!(Some(DataType::F32),                         data_set.var_data_type("t2m_anomaly"));
+    /// assert_eq!(Some(String::from("K")),                     data_set.get_var_attr_as_string("t2m_anomaly", "units"));
+    ///
+    /// assert_eq!(
+    ///     Err(InvalidDataSet::VariableAlreadyExists(String::from("t2m_anomaly"))),
+    ///     data_set.duplicate_var("t2m", "t2m_anomaly")
+    /// );
+    /// ```
+    pub fn duplicate_var(&mut self, var_name: &str, new_var_name: &str) -> Result<(), InvalidDataSet> {
+        let var: &Variable = self.find_var_from_name(var_name)?.1;
+        let (var_dims, data_type, var_attrs): (Vec<Rc<Dimension>>, DataType, Vec<Attribute>) = (var.get_dims(), var.data_type(), var.attrs.clone());
+        if self.find_var_from_name(new_var_name).is_ok() {
+            return Err(InvalidDataSet::VariableAlreadyExists(new_var_name.to_string()));
+        }
+        let new_var: &mut Variable = self.add_checked_var(new_var_name, var_dims, data_type)?;
+        new_var.attrs = var_attrs;
+        new_var.rebuild_attrs_index();
+        return Ok(());
+    }
+
+    /// Duplicates the variable `var_name` (see [`duplicate_var`](#method.duplicate_var)) and copies its loaded values
+    /// (see [`Variable::data`](struct.Variable.html#method.data)).
+    ///
+    /// The variable is duplicated even if its values are not loaded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use netcdf3::{DataSet, DataVector};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.set_unlimited_dim("time", 0).unwrap();
+    /// data_set.add_var_f32("t2m", &["time"]).unwrap();
+    /// let mut record: HashMap<String, DataVector> = HashMap::new();
+    /// record.insert(String::from("t2m"), DataVector::F32(vec![273.15]));
+    /// data_set.append_record(record).unwrap();
+    ///
+    /// data_set.duplicate_var_with_data("t2m", "t2m_anomaly").unwrap();
+    /// assert_eq!(Some(&DataVector::F32(vec![273.15])),        data_set["t2m_anomaly"].data());
+    /// ```
+    pub fn duplicate_var_with_data(&mut self, var_name: &str, new_var_name: &str) -> Result<(), InvalidDataSet> {
+        self.duplicate_var(var_name, new_var_name)?;
+        let var_data: Option<DataVector> = self.find_var_from_name(var_name)?.1.data.clone();
+        let new_var_index: usize = self.find_var_from_name(new_var_name)?.0;
+        self.vars[new_var_index].data = var_data;
+        return Ok(());
+    }
+
+    /// Changes the data type of the variable, its attributes being unchanged and its loaded values dropped.
+    ///
+    /// An error is returned if the size of the variable or the record size overflows `usize`, or if the limits of the 64-bit offset
+    /// version are exceeded (see [`check_format_limits`](#method.check_format_limits)), the variable being then unchanged.
+    #[cfg(feature = "std")]
+    pub(crate) fn set_var_data_type(&mut self, var_name: &str, data_type: DataType) -> Result<(), InvalidDataSet> {
+        let var_index: usize = self.find_var_from_name(var_name)?.0;
+        let prev_data_type: DataType = core::mem::replace(&mut self.vars[var_index].data_type, data_type);
+        match self.check_changed_var(var_index) {
+            Err(err) => {
+                self.vars[var_index].data_type = prev_data_type;
+                return Err(err);
+            },
+            Ok(vars_layout) => self.vars_layout = vars_layout,
+        }
+        // the loaded values have the previous data type
+        if prev_data_type != data_type {
+            self.vars[var_index].data = None;
+        }
+        return Ok(());
+    }
+
+    /// Checks the variable `var_index` whose definition has just been changed, and returns the new layout of the variables.
+    ///
+    /// An error is returned if the size of the variable or the record size overflows `usize`, or if the limits of the 64-bit offset
+    /// version are exceeded (see [`check_format_limits`](#method.check_format_limits)).
+    fn check_changed_var(&self, var_index: usize) -> Result<VarsLayout, InvalidDataSet> {
+        let var_name: &str = self.vars[var_index].name();
+        if self.vars[var_index].checked_chunk_size().is_none() {
+            return Err(InvalidDataSet::VariableSizeOverflow(var_name.to_string()));
+        }
+        let vars_layout: VarsLayout = VarsLayout::compute(&self.vars).ok_or_else(|| InvalidDataSet::RecordSizeOverflow(var_name.to_string()))?;
+        self.check_format_limits(Version::Offset64Bit)?;
+        return Ok(vars_layout);
+    }
+
     /// Finds the dataset's variable from his name, and returns a tuple containing :
     ///
     /// - 0 : the index of the variable
     /// - 1 : a reference to the variable
     pub(crate) fn find_var_from_name(&self, var_name: &str) -> Result<(usize, &Variable), InvalidDataSet> {
-        return self
-            .vars
-            .iter()
-            .position(|var: &Variable| var.name == var_name)
-            .map(|var_index| (var_index, &self.vars[var_index]))
-            .ok_or(InvalidDataSet::VariableNotDefined(var_name.to_string()));
+        return self.vars_index.get(var_name)
+            .map(|var_index: usize| (var_index, &self.vars[var_index]))
+            .ok_or_else(|| InvalidDataSet::VariableNotDefined(var_name.to_string()));
     }
 
     // ----------------------------------------------------------------
@@ -712,8 +1058,9 @@ impl DataSet {
         // Check that the variable is defined
         let var_index: usize = self.find_var_from_name(var_name)?.0;
         // Append the new attribute
+        let name_validation: NameValidation = self.name_validation;
         let var: &mut Variable = &mut self.vars[var_index];
-        var.add_attr_i8(attr_name, var_attr_value)?;
+        var.add_attr_with(Attribute::from_data(attr_name.to_string(), DataVector::I8(var_attr_value)), name_validation)?;
         Ok(())
     }
 
@@ -722,8 +1069,9 @@ impl DataSet {
         // Check that the variable is defined
         let var_index: usize = self.find_var_from_name(var_name)?.0;
         // Append the new attribute
+        let name_validation: NameValidation = self.name_validation;
         let var: &mut Variable = &mut self.vars[var_index];
-        var.add_attr_u8(attr_name, var_attr_value)?;
+        var.add_attr_with(Attribute::from_data(attr_name.to_string(), DataVector::U8(var_attr_value)), name_validation)?;
         Ok(())
     }
 
@@ -737,8 +1085,9 @@ impl DataSet {
         // Check that the variable is defined
         let var_index: usize = self.find_var_from_name(var_name)?.0;
         // Append the new attribute
+        let name_validation: NameValidation = self.name_validation;
         let var: &mut Variable = &mut self.vars[var_index];
-        var.add_attr_i16(attr_name, var_attr_value)?;
+        var.add_attr_with(Attribute::from_data(attr_name.to_string(), DataVector::I16(var_attr_value)), name_validation)?;
         Ok(())
     }
 
@@ -747,8 +1096,9 @@ impl DataSet {
         // Check that the variable is defined
         let var_index: usize = self.find_var_from_name(var_name)?.0;
         // Append the new attribute
+        let name_validation: NameValidation = self.name_validation;
         let var: &mut Variable = &mut self.vars[var_index];
-        var.add_attr_i32(attr_name, var_attr_value)?;
+        var.add_attr_with(Attribute::from_data(attr_name.to_string(), DataVector::I32(var_attr_value)), name_validation)?;
         Ok(())
     }
 
@@ -758,8 +1108,9 @@ impl DataSet {
         // Check that the variable is defined
         let var_index: usize = self.find_var_from_name(var_name)?.0;
         // Append the new attribute
+        let name_validation: NameValidation = self.name_validation;
         let var: &mut Variable = &mut self.vars[var_index];
-        var.add_attr_f32(attr_name, var_attr_value)?;
+        var.add_attr_with(Attribute::from_data(attr_name.to_string(), DataVector::F32(var_attr_value)), name_validation)?;
         Ok(())
     }
 
@@ -768,11 +1119,100 @@ impl DataSet {
         // Check that the variable is defined
         let var_index: usize = self.find_var_from_name(var_name)?.0;
         // Append the new attribute
+        let name_validation: NameValidation = self.name_validation;
+        let var: &mut Variable = &mut self.vars[var_index];
+        var.add_attr_with(Attribute::from_data(attr_name.to_string(), DataVector::F64(var_attr_value)), name_validation)?;
+        Ok(())
+    }
+
+    /// Adds several attributes of different data types in the variable.
+    ///
+    /// The attributes are added in the given order. If one of them cannot be added, none of them is added and the error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::{AttrValue, DataSet, DataSlice, DataType, InvalidDataSet};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.add_fixed_dim("latitude", 3).unwrap();
+    /// data_set.add_var_f32("t2m", &["latitude"]).unwrap();
+    /// data_set.add_var_attrs("t2m", &[
+    ///     ("units", AttrValue::Str("K")),
+    ///     ("valid_range", AttrValue::Slice(DataSlice::F32(&[150.0, 350.0]))),
+    ///     ("_FillValue", AttrValue::F32(-999.0)),
+    /// ]).unwrap();
+    ///
+    /// assert_eq!(Some(3),                                 data_set.num_var_attrs("t2m"));
+    /// assert_eq!(Some(String::from("K")),                 data_set.get_var_attr_as_string("t2m", "units"));
+    /// assert_eq!(Some(&[150.0, 350.0][..]),               data_set.get_var_attr_f32("t2m", "valid_range"));
+    ///
+    /// // The attribute `units` is already defined, `long_name` is not added
+    /// assert_eq!(
+    ///     Err(InvalidDataSet::VariableAttributeAlreadyExists{var_name: String::from("t2m"), attr_name: String::from("units")}),
+    ///     data_set.add_var_attrs("t2m", &[("long_name", AttrValue::Str("2 metre temperature")), ("units", AttrValue::Str("K"))])
+    /// );
+    /// assert_eq!(Some(false),                             data_set.has_var_attr("t2m", "long_name"));
+    /// ```
+    pub fn add_var_attrs(&mut self, var_name: &str, attrs: &[(&str, AttrValue)]) -> Result<(), InvalidDataSet> {
+        // Check that the variable is defined
+        let var_index: usize = self.find_var_from_name(var_name)?.0;
+        let name_validation: NameValidation = self.name_validation;
+        let var: &mut Variable = &mut self.vars[var_index];
+        for (i, (attr_name, attr_value)) in attrs.iter().enumerate() {
+            let attr = Attribute::from_data(attr_name.to_string(), attr_value.to_data_vector());
+            if let Err(err) = var.add_attr_with(attr, name_validation) {
+                // Remove the attributes previously added
+                for (added_attr_name, _) in attrs[..i].iter() {
+                    var.remove_attr(added_attr_name)?;
+                }
+                return Err(err);
+            }
+        }
+        return Ok(());
+    }
+
+    /// Adds an attribute read from a file in the variable, its name being checked with the rules of the data set.
+    ///
+    /// Its data type is not checked (see [`DataSet::check_attr_data_types`](#method.check_attr_data_types)).
+    pub(crate) fn add_var_attr_from(&mut self, var_name: &str, attr: Attribute) -> Result<(), InvalidDataSet> {
+        // Check that the variable is defined
+        let var_index: usize = self.find_var_from_name(var_name)?.0;
+        // Append the new attribute
+        let (name_validation, allow_long_names): (NameValidation, bool) = (self.name_validation, self.allow_long_names);
         let var: &mut Variable = &mut self.vars[var_index];
-        var.add_attr_f64(attr_name, var_attr_value)?;
+        var.add_parsed_attr_with(attr, name_validation, allow_long_names)?;
         Ok(())
     }
 
+    /// Replaces a variable attribute read from a file at its position, and returns the replaced attribute.
+    ///
+    /// Its data type is not checked (see [`DataSet::check_attr_data_types`](#method.check_attr_data_types)).
+    pub(crate) fn replace_var_attr_from(&mut self, var_name: &str, attr: Attribute) -> Result<Attribute, InvalidDataSet> {
+        let var_index: usize = self.find_var_from_name(var_name)?.0;
+        let var: &mut Variable = &mut self.vars[var_index];
+        let attr_index: usize = var.find_attr_from_name(&attr.name)?.0;
+        return Ok(core::mem::replace(&mut var.attrs[attr_index], attr));
+    }
+
+    /// Replaces the variable attribute at its position, or adds it if it is not defined.
+    ///
+    /// The data type of the attributes `_FillValue` and `missing_value` is checked.
+    #[cfg(feature = "std")]
+    pub(crate) fn set_var_attr_from(&mut self, var_name: &str, attr: Attribute) -> Result<(), InvalidDataSet> {
+        let var_index: usize = self.find_var_from_name(var_name)?.0;
+        let name_validation: NameValidation = self.name_validation;
+        let var: &mut Variable = &mut self.vars[var_index];
+        return match var.find_attr_from_name(&attr.name).map(|(attr_index, _attr): (usize, &Attribute)| attr_index) {
+            Ok(attr_index) => {
+                var.check_attr_data_type(&attr.name, attr.data_type())?;
+                var.attrs[attr_index] = attr;
+                Ok(())
+            },
+            Err(_) => var.add_attr_with(attr, name_validation),
+        };
+    }
+
     /// Returns a reference of variable attribute.
     pub fn get_var_attr(&self, var_name: &str, attr_name: &str) -> Option<&Attribute> {
         return self.find_var_attr_from_name(var_name, attr_name).map(
@@ -834,8 +1274,9 @@ impl DataSet {
     /// Rename the variable attribute.
     pub fn rename_var_attr(&mut self, var_name: &str, old_attr_name: &str, new_attr_name: &str) -> Result<(), InvalidDataSet> {
         let var_index = self.find_var_from_name(var_name)?.0;
+        let name_validation: NameValidation = self.name_validation;
         let var: &mut Variable = &mut self.vars[var_index];
-        var.rename_attr(old_attr_name, new_attr_name)?;
+        var.rename_attr(old_attr_name, new_attr_name, name_validation)?;
         Ok(())
     }
 
@@ -915,11 +1356,43 @@ impl DataSet {
     //
     // ----------------------------------------------------------------
     fn find_global_attr_from_name(&self, attr_name: &str) -> Result<(usize, &Attribute), InvalidDataSet> {
-        self.attrs
-            .iter()
-            .position(|ref_attr: &Attribute| ref_attr.name == attr_name)
+        self.attrs_index.get(attr_name)
             .map(|attr_index: usize| (attr_index, &self.attrs[attr_index]))
-            .ok_or(InvalidDataSet::GlobalAttributeNotDefined(attr_name.to_string()))
+            .ok_or_else(|| InvalidDataSet::GlobalAttributeNotDefined(attr_name.to_string()))
+    }
+
+    /// Appends a global attribute, its name being already checked.
+    fn push_global_attr(&mut self, attr: Attribute) {
+        self.attrs_index.push(&attr.name, self.attrs.len());
+        self.attrs.push(attr);
+    }
+
+    /// Adds a global attribute, its name being checked with the rules of the data set.
+    pub(crate) fn add_global_attr_from(&mut self, attr: Attribute) -> Result<(), InvalidDataSet> {
+        if self.find_global_attr_from_name(&attr.name).is_ok() {
+            return Err(InvalidDataSet::GlobalAttributeAlreadyExists(attr.name));
+        }
+        check_name(&attr.name, self.name_validation, self.allow_long_names)
+            .map_err(|reason: NameError| InvalidDataSet::GlobalAttributeNameNotValid{attr_name: attr.name.to_string(), reason: reason})?;
+        self.push_global_attr(attr);
+        Ok(())
+    }
+
+    /// Replaces a global attribute read from a file at its position, and returns the replaced attribute.
+    pub(crate) fn replace_global_attr_from(&mut self, attr: Attribute) -> Result<Attribute, InvalidDataSet> {
+        let attr_index: usize = self.find_global_attr_from_name(&attr.name)?.0;
+        return Ok(core::mem::replace(&mut self.attrs[attr_index], attr));
+    }
+
+    /// Replaces the global attribute at its position, or adds it if it is not defined.
+    pub(crate) fn set_global_attr_from(&mut self, attr: Attribute) -> Result<(), InvalidDataSet> {
+        return match self.attrs_index.get(&attr.name) {
+            Some(attr_index) => {
+                self.attrs[attr_index] = attr;
+                Ok(())
+            },
+            None => self.add_global_attr_from(attr),
+        };
     }
 
     /// Returns a reference to the global attribute.
@@ -929,11 +1402,23 @@ impl DataSet {
             .map(|(_attr_index, ref_attr)| ref_attr)
     }
 
+    /// Returns a reference to the global attribute, or `None` (same as [`get_global_attr`](#method.get_global_attr)).
+    #[inline]
+    pub fn attr(&self, attr_name: &str) -> Option<&Attribute> {
+        return self.get_global_attr(attr_name);
+    }
+
     /// Returns a reference of all global attributes.
     pub fn get_global_attrs(&self) -> Vec<&Attribute> {
         self.attrs.iter().collect()
     }
 
+    /// Returns all the global attributes, without collecting them (see [`get_global_attrs`](#method.get_global_attrs)).
+    #[inline]
+    pub fn global_attrs(&self) -> &[Attribute] {
+        return &self.attrs;
+    }
+
     /// Returns the length (number of elements) of the global attribute.
     pub fn get_global_attr_len(&self, attr_name: &str) -> Option<usize> {
         self.find_global_attr_from_name(attr_name)
@@ -973,30 +1458,12 @@ impl DataSet {
 
     /// Adds a global `i8` type attribute in the data set.
     pub fn add_global_attr_i8(&mut self, attr_name: &str, attr_data: Vec<i8>) -> Result<(), InvalidDataSet> {
-        if self.find_global_attr_from_name(attr_name).is_ok() {
-            return Err(InvalidDataSet::GlobalAttributeAlreadyExists(attr_name.to_string()));
-        }
-        let _ = Attribute::check_attr_name(attr_name)
-            .map_err(|invalid_attr_name: String| InvalidDataSet::GlobalAttributeNameNotValid(invalid_attr_name))?;
-        self.attrs.push(Attribute {
-            name: attr_name.to_string(),
-            data: DataVector::I8(attr_data),
-        });
-        Ok(())
+        self.add_global_attr_from(Attribute::from_data(attr_name.to_string(), DataVector::I8(attr_data)))
     }
 
     /// Adds a global `u8` type attribute in the data set.
     pub fn add_global_attr_u8(&mut self, attr_name: &str, attr_data: Vec<u8>) -> Result<(), InvalidDataSet> {
-        if self.find_global_attr_from_name(attr_name).is_ok() {
-            return Err(InvalidDataSet::GlobalAttributeAlreadyExists(attr_name.to_string()));
-        }
-        let _ = Attribute::check_attr_name(attr_name)
-            .map_err(|invalid_attr_name: String| InvalidDataSet::GlobalAttributeNameNotValid(invalid_attr_name))?;
-        self.attrs.push(Attribute {
-            name: attr_name.to_string(),
-            data: DataVector::U8(attr_data),
-        });
-        Ok(())
+        self.add_global_attr_from(Attribute::from_data(attr_name.to_string(), DataVector::U8(attr_data)))
     }
 
     /// Adds a global `u8` type attribute in the data set.
@@ -1006,58 +1473,22 @@ impl DataSet {
 
     /// Adds a global `i16` type attribute in the data set.
     pub fn add_global_attr_i16(&mut self, attr_name: &str, attr_data: Vec<i16>) -> Result<(), InvalidDataSet> {
-        if self.find_global_attr_from_name(attr_name).is_ok() {
-            return Err(InvalidDataSet::GlobalAttributeAlreadyExists(attr_name.to_string()));
-        }
-        let _ = Attribute::check_attr_name(attr_name)
-            .map_err(|invalid_attr_name: String| InvalidDataSet::GlobalAttributeNameNotValid(invalid_attr_name))?;
-        self.attrs.push(Attribute {
-            name: attr_name.to_string(),
-            data: DataVector::I16(attr_data),
-        });
-        Ok(())
+        self.add_global_attr_from(Attribute::from_data(attr_name.to_string(), DataVector::I16(attr_data)))
     }
 
     /// Adds a global `i32` type attribute in the data set.
     pub fn add_global_attr_i32(&mut self, attr_name: &str, attr_data: Vec<i32>) -> Result<(), InvalidDataSet> {
-        if self.find_global_attr_from_name(attr_name).is_ok() {
-            return Err(InvalidDataSet::GlobalAttributeAlreadyExists(attr_name.to_string()));
-        }
-        let _ = Attribute::check_attr_name(attr_name)
-            .map_err(|invalid_attr_name: String| InvalidDataSet::GlobalAttributeNameNotValid(invalid_attr_name))?;
-        self.attrs.push(Attribute {
-            name: attr_name.to_string(),
-            data: DataVector::I32(attr_data),
-        });
-        Ok(())
+        self.add_global_attr_from(Attribute::from_data(attr_name.to_string(), DataVector::I32(attr_data)))
     }
 
     /// Adds a global `f32` type attribute in the data set.
     pub fn add_global_attr_f32(&mut self, attr_name: &str, attr_data: Vec<f32>) -> Result<(), InvalidDataSet> {
-        if self.find_global_attr_from_name(attr_name).is_ok() {
-            return Err(InvalidDataSet::GlobalAttributeAlreadyExists(attr_name.to_string()));
-        }
-        let _ = Attribute::check_attr_name(attr_name)
-            .map_err(|invalid_attr_name: String| InvalidDataSet::GlobalAttributeNameNotValid(invalid_attr_name))?;
-        self.attrs.push(Attribute {
-            name: attr_name.to_string(),
-            data: DataVector::F32(attr_data),
-        });
-        Ok(())
+        self.add_global_attr_from(Attribute::from_data(attr_name.to_string(), DataVector::F32(attr_data)))
     }
 
     /// Add a global `f64` type attribute in the data set.
     pub fn add_global_attr_f64(&mut self, attr_name: &str, attr_data: Vec<f64>) -> Result<(), InvalidDataSet> {
-        if self.find_global_attr_from_name(attr_name).is_ok() {
-            return Err(InvalidDataSet::GlobalAttributeAlreadyExists(attr_name.to_string()));
-        }
-        let _ = Attribute::check_attr_name(attr_name)
-            .map_err(|invalid_attr_name: String| InvalidDataSet::GlobalAttributeNameNotValid(invalid_attr_name))?;
-        self.attrs.push(Attribute {
-            name: attr_name.to_string(),
-            data: DataVector::F64(attr_data),
-        });
-        Ok(())
+        self.add_global_attr_from(Attribute::from_data(attr_name.to_string(), DataVector::F64(attr_data)))
     }
 
     pub fn rename_global_attr(&mut self, old_attr_name: &str, new_attr_name: &str) -> Result<(), InvalidDataSet> {
@@ -1075,11 +1506,12 @@ impl DataSet {
         }
 
         // Check that the new name is a NetCDF-3 valid name
-        let _ = Attribute::check_attr_name(new_attr_name)
-            .map_err(|invalid_attr_name: String| InvalidDataSet::GlobalAttributeNameNotValid(invalid_attr_name))?;
+        Attribute::check_attr_name(new_attr_name, self.name_validation)
+            .map_err(|reason: NameError| InvalidDataSet::GlobalAttributeNameNotValid{attr_name: new_attr_name.to_string(), reason: reason})?;
 
         // Update the attribute name
         self.attrs[renamed_attr_index].name = new_attr_name.to_string();
+        self.attrs_index.rename(old_attr_name, new_attr_name, renamed_attr_index, self.attrs.iter().map(Attribute::name));
 
         Ok(())
     }
@@ -1088,7 +1520,237 @@ impl DataSet {
         // Check that the `attr_name` attribute has been defined
         let removed_attr_index = self.find_global_attr_from_name(attr_name)?.0;
 
-        Ok(self.attrs.remove(removed_attr_index))
+        let removed_attr: Attribute = self.attrs.remove(removed_attr_index);
+        self.attrs_index.rebuild(self.attrs.iter().map(Attribute::name));
+        Ok(removed_attr)
+    }
+
+    /// Renames at once the dimensions, the variables, the global attributes and the variable attributes, each pair
+    /// `(old_name, new_name)` renaming all the items named `old_name`.
+    ///
+    /// All the new names are checked before renaming any item, nothing is renamed if an error is returned.
+    /// The pairs are applied simultaneously (the names can be swapped), the first pair renaming a name being used.
+    /// The names which are not used by the data set are ignored, so that the same pairs can normalize heterogeneous data sets.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::{DataSet, InvalidDataSet};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.add_fixed_dim("lat", 3).unwrap();
+    /// data_set.add_var_f32("lat", &["lat"]).unwrap();
+    /// data_set.add_var_attr_string("lat", "unit", "degrees_north").unwrap();
+    /// data_set.add_var_f32("t2m", &["lat"]).unwrap();
+    ///
+    /// let renames = [("lat", "latitude"), ("unit", "units"), ("t2m", "temperature"), ("lon", "longitude")];
+    /// data_set.rename_many(&renames).unwrap();
+    /// assert_eq!(vec!["latitude"],                        data_set.dim_names());
+    /// assert_eq!(vec!["latitude", "temperature"],         data_set.get_var_names());
+    /// assert_eq!(Some(String::from("degrees_north")),     data_set.get_var_attr_as_string("latitude", "units"));
+    ///
+    /// // `temperature` would name two variables
+    /// assert_eq!(
+    ///     Err(InvalidDataSet::VariableAlreadyExists(String::from("temperature"))),
+    ///     data_set.rename_many(&[("latitude", "temperature")])
+    /// );
+    /// assert_eq!(vec!["latitude"],                        data_set.dim_names());
+    /// assert_eq!(vec!["latitude", "temperature"],         data_set.get_var_names());
+    /// ```
+    pub fn rename_many(&mut self, renames: &[(&str, &str)]) -> Result<(), InvalidDataSet> {
+        let rename = |name: &str| -> Option<String> {
+            return renames.iter()
+                .find(|(old_name, new_name): &&(&str, &str)| *old_name == name && *new_name != name)
+                .map(|(_old_name, new_name): &(&str, &str)| new_name.to_string());
+        };
+        // Returns `true` if an other item has the name of the item `index`
+        let find_duplicate = |names: &[String], index: usize| -> bool {
+            return names.iter().enumerate().any(|(other_index, name): (usize, &String)| other_index != index && *name == names[index]);
+        };
+
+        // Compute and check the new names
+        let new_dim_names: Vec<Option<String>> = self.dims.iter().map(|dim: &Rc<Dimension>| rename(&dim.name())).collect();
+        let dim_names: Vec<String> = self.dims.iter().zip(new_dim_names.iter()).map(|(dim, new_name): (&Rc<Dimension>, &Option<String>)| {
+            new_name.clone().unwrap_or_else(|| dim.name())
+        }).collect();
+        for (dim_index, new_dim_name) in new_dim_names.iter().enumerate() {
+            if let Some(new_dim_name) = new_dim_name {
+                if find_duplicate(&dim_names, dim_index) {
+                    return Err(InvalidDataSet::DimensionAlreadyExists(new_dim_name.to_string()));
+                }
+                Dimension::check_dim_name(new_dim_name, self.name_validation, false)?;
+            }
+        }
+
+        let new_var_names: Vec<Option<String>> = self.vars.iter().map(|var: &Variable| rename(var.name())).collect();
+        let var_names: Vec<String> = self.vars.iter().zip(new_var_names.iter()).map(|(var, new_name): (&Variable, &Option<String>)| {
+            new_name.clone().unwrap_or_else(|| var.name().to_string())
+        }).collect();
+        for (var_index, new_var_name) in new_var_names.iter().enumerate() {
+            if let Some(new_var_name) = new_var_name {
+                if find_duplicate(&var_names, var_index) {
+                    return Err(InvalidDataSet::VariableAlreadyExists(new_var_name.to_string()));
+                }
+                Variable::check_var_name(new_var_name, self.name_validation, false)?;
+            }
+        }
+
+        let new_attr_names: Vec<Option<String>> = self.attrs.iter().map(|attr: &Attribute| rename(attr.name())).collect();
+        let attr_names: Vec<String> = self.attrs.iter().zip(new_attr_names.iter()).map(|(attr, new_name): (&Attribute, &Option<String>)| {
+            new_name.clone().unwrap_or_else(|| attr.name().to_string())
+        }).collect();
+        for (attr_index, new_attr_name) in new_attr_names.iter().enumerate() {
+            if let Some(new_attr_name) = new_attr_name {
+                if find_duplicate(&attr_names, attr_index) {
+                    return Err(InvalidDataSet::GlobalAttributeAlreadyExists(new_attr_name.to_string()));
+                }
+                Attribute::check_attr_name(new_attr_name, self.name_validation)
+                    .map_err(|reason: NameError| InvalidDataSet::GlobalAttributeNameNotValid{attr_name: new_attr_name.to_string(), reason: reason})?;
+            }
+        }
+
+        let mut var_attr_names: Vec<Vec<String>> = Vec::with_capacity(self.vars.len());
+        for var in self.vars.iter() {
+            let new_attr_names: Vec<Option<String>> = var.attrs.iter().map(|attr: &Attribute| rename(attr.name())).collect();
+            let attr_names: Vec<String> = var.attrs.iter().zip(new_attr_names.iter()).map(|(attr, new_name): (&Attribute, &Option<String>)| {
+                new_name.clone().unwrap_or_else(|| attr.name().to_string())
+            }).collect();
+            for (attr_index, new_attr_name) in new_attr_names.iter().enumerate() {
+                if let Some(new_attr_name) = new_attr_name {
+                    if find_duplicate(&attr_names, attr_index) {
+                        return Err(InvalidDataSet::VariableAttributeAlreadyExists{var_name: var.name().to_string(), attr_name: new_attr_name.to_string()});
+                    }
+                    Attribute::check_attr_name(new_attr_name, self.name_validation)
+                        .map_err(|reason: NameError| InvalidDataSet::VariableAttributeNameNotValid{
+                            var_name: var.name().to_string(),
+                            attr_name: new_attr_name.to_string(),
+                            reason: reason,
+                        })?;
+                    var.check_attr_data_type(new_attr_name, var.attrs[attr_index].data_type())?;
+                }
+            }
+            var_attr_names.push(attr_names);
+        }
+
+        // Then rename the items
+        for (dim, dim_name) in self.dims.iter().zip(dim_names) {
+            *dim.name.borrow_mut() = dim_name;
+        }
+        self.rebuild_dims_index();
+        for ((var, var_name), attr_names) in self.vars.iter_mut().zip(var_names).zip(var_attr_names) {
+            var.name = var_name;
+            for (attr, attr_name) in var.attrs.iter_mut().zip(attr_names) {
+                attr.name = attr_name;
+            }
+            var.rebuild_attrs_index();
+        }
+        self.vars_index.rebuild(self.vars.iter().map(Variable::name));
+        for (attr, attr_name) in self.attrs.iter_mut().zip(attr_names) {
+            attr.name = attr_name;
+        }
+        self.attrs_index.rebuild(self.attrs.iter().map(Attribute::name));
+        return Ok(());
+    }
+
+    /// Prepends a timestamped line to the global attribute `history`, the attribute is created if it is not defined.
+    ///
+    /// The line is formatted like the ones added by the NCO and CDO tools, the time being the current UTC time
+    /// (see [`DataSet::append_history_at`](#method.append_history_at)).
+    #[cfg(feature = "std")]
+    pub fn append_history(&mut self, entry: &str) -> Result<(), InvalidDataSet> {
+        return self.append_history_at(entry, std::time::SystemTime::now());
+    }
+
+    /// Prepends the line `"{time}: {entry}"` to the global attribute `history`, the attribute is created if it is not defined.
+    ///
+    /// The UTC `time` is formatted like `Sat Oct 17 09:30:00 2026`, the most recent line being the first one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use netcdf3::{DataSet, DataType, InvalidDataSet};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.append_history_at("ncks -v t2m in.nc out.nc", SystemTime::UNIX_EPOCH + Duration::from_secs(1_600_000_000)).unwrap();
+    /// data_set.append_history_at("regrid.py out.nc", SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000)).unwrap();
+    /// assert_eq!(
+    ///     Some(String::from("Tue Nov 14 22:13:20 2023: regrid.py out.nc\nSun Sep 13 12:26:40 2020: ncks -v t2m in.nc out.nc")),
+    ///     data_set.get_global_attr_as_string("history")
+    /// );
+    ///
+    /// // The attribute `history` must contain text
+    /// let mut data_set = DataSet::new();
+    /// data_set.add_global_attr_i32("history", vec![1]).unwrap();
+    /// assert_eq!(
+    ///     Err(InvalidDataSet::GlobalAttributeMismatchDataType{attr_name: String::from("history"), req: DataType::U8, get: DataType::I32}),
+    ///     data_set.append_history("edited")
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn append_history_at(&mut self, entry: &str, time: std::time::SystemTime) -> Result<(), InvalidDataSet> {
+        const HISTORY_ATTR_NAME: &str = "history";
+        let seconds: u64 = time.duration_since(std::time::UNIX_EPOCH).map(|duration: std::time::Duration| duration.as_secs()).unwrap_or(0);
+        let mut history: Vec<u8> = format!("{}: {}", format_history_time(seconds), entry).into_bytes();
+        if let Ok((_attr_index, previous_attr)) = self.find_global_attr_from_name(HISTORY_ATTR_NAME) {
+            let previous_history: &[u8] = previous_attr.get_u8().ok_or_else(|| InvalidDataSet::GlobalAttributeMismatchDataType{
+                attr_name: HISTORY_ATTR_NAME.to_string(),
+                req: DataType::U8,
+                get: previous_attr.data_type(),
+            })?;
+            if !previous_history.is_empty() {
+                history.push(b'\n');
+                history.extend_from_slice(previous_history);
+            }
+        }
+        return self.set_global_attr_from(Attribute::from_data(HISTORY_ATTR_NAME.to_string(), DataVector::U8(history)));
+    }
+
+    /// Sets the global attribute `Conventions` (for example `"CF-1.8"`), replacing its previous value.
+    ///
+    /// The attribute keeps its position if it is already defined.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::DataSet;
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.set_conventions("CF-1.7").unwrap();
+    /// data_set.set_title("Air temperature measurements").unwrap();
+    /// data_set.set_institution("Example institute").unwrap();
+    /// data_set.set_source("Surface observations").unwrap();
+    /// data_set.set_conventions("CF-1.8").unwrap();
+    ///
+    /// assert_eq!(vec!["Conventions", "title", "institution", "source"],    data_set.get_global_attr_names());
+    /// assert_eq!(Some(String::from("CF-1.8")),                            data_set.get_global_attr_as_string("Conventions"));
+    /// assert_eq!(Some(String::from("Air temperature measurements")),      data_set.get_global_attr_as_string("title"));
+    /// assert_eq!(Some(String::from("Example institute")),                 data_set.get_global_attr_as_string("institution"));
+    /// assert_eq!(Some(String::from("Surface observations")),              data_set.get_global_attr_as_string("source"));
+    /// ```
+    pub fn set_conventions(&mut self, conventions: &str) -> Result<(), InvalidDataSet> {
+        return self.set_global_attr_from(Attribute::from_data(String::from("Conventions"), DataVector::U8(conventions.as_bytes().to_vec())));
+    }
+
+    /// Sets the global attribute `title`, a short description of the data set, replacing its previous value.
+    ///
+    /// Also see the method [`DataSet::set_conventions`](#method.set_conventions).
+    pub fn set_title(&mut self, title: &str) -> Result<(), InvalidDataSet> {
+        return self.set_global_attr_from(Attribute::from_data(String::from("title"), DataVector::U8(title.as_bytes().to_vec())));
+    }
+
+    /// Sets the global attribute `institution`, where the data set was produced, replacing its previous value.
+    ///
+    /// Also see the method [`DataSet::set_conventions`](#method.set_conventions).
+    pub fn set_institution(&mut self, institution: &str) -> Result<(), InvalidDataSet> {
+        return self.set_global_attr_from(Attribute::from_data(String::from("institution"), DataVector::U8(institution.as_bytes().to_vec())));
+    }
+
+    /// Sets the global attribute `source`, the method of production of the original data, replacing its previous value.
+    ///
+    /// Also see the method [`DataSet::set_conventions`](#method.set_conventions).
+    pub fn set_source(&mut self, source: &str) -> Result<(), InvalidDataSet> {
+        return self.set_global_attr_from(Attribute::from_data(String::from("source"), DataVector::U8(source.as_bytes().to_vec())));
     }
 
     /// Returns the attribute value as a `&[i8]`.
@@ -1151,6 +1813,9 @@ impl DataSet {
     ///
     /// Returns `None` if the data set has not a *unlimited-size* dimension.
     ///
+    /// If only one record variable is defined, its records are packed without the zero padding bytes,
+    /// as specified by the NetCDF-3 format.
+    ///
     /// # Example
     ///
     /// ```
@@ -1177,8 +1842,9 @@ impl DataSet {
     /// // First : Add a 1D variable (a vector) over the *unlimited-size* dimension
     /// data_set.add_var_i8(VAR_1D_NAME, &[UNLIM_DIM_NAME]).unwrap();
     /// const VAR_1D_CHUNK_SIZE: usize = 4;  // 1 useful byte + 3 zero-padding bytes
+    /// // the only record variable is not padded
     /// assert_eq!(true,                                            data_set.has_unlimited_dim());
-    /// assert_eq!(Some(VAR_1D_CHUNK_SIZE),                         data_set.record_size());
+    /// assert_eq!(Some(1),                                         data_set.record_size());
     ///
     /// // Second : Add a 2D variable (a matrix) over the* unlimited-size* dimension
     /// data_set.add_fixed_dim(FIXED_DIM_NAME, FIXED_DIM_SIZE).unwrap();
@@ -1195,14 +1861,24 @@ impl DataSet {
             return None;
         }
         else {
-            let (record_vars, _fixed_size_vars): (Vec<&Variable>, Vec<&Variable>) = self.vars.iter().partition(|var: &&Variable| var.is_record_var());
-            let record_size: usize = record_vars.into_iter().fold(0, |sum:usize , var: &Variable| {
-                sum + var.chunk_size()
-            });
-            Some(record_size)
+            // The record size is checked when each record variable is added
+            return Some(self.vars_layout.record_size());
         }
     }
 
+    /// Returns `true` if the records are packed without the zero padding bytes, namely if only one record variable is defined.
+    pub(crate) fn records_are_packed(&self) -> bool
+    {
+        return self.vars_layout.num_record_vars() == 1;
+    }
+
+    /// Recomputes the sizes of the record variables, after a removal or a change of their dimensions.
+    ///
+    /// The record size of the remaining variables has already been checked.
+    fn rebuild_vars_layout(&mut self) {
+        self.vars_layout = VarsLayout::compute(&self.vars).expect("Shouldn't have occurred! The record size has been checked when the variables were added.");
+    }
+
     /// Returns the number of records stored in data file.
     ///
     /// Returns `None` if the data set has not an *unlimited-size* dimension.
@@ -1231,4 +1907,535 @@ impl DataSet {
             Some(dim) => Some(dim.size())
         }
     }
-}
\ No newline at end of file
+
+    /// Appends a record to the loaded values of the record variables (see [`Variable::data`](struct.Variable.html#method.data)),
+    /// and increments the size of the *unlimited-size* dimension.
+    ///
+    /// The record `values_by_var` must contain the values of each record variable, whose values of all the previous records must be loaded
+    /// (the values of the first record being loaded by its append). Nothing is modified if an error is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use netcdf3::{DataSet, DataVector, InvalidDataSet};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.set_unlimited_dim("time", 0).unwrap();
+    /// data_set.add_fixed_dim("station", 2).unwrap();
+    /// data_set.add_var_f64("time", &["time"]).unwrap();
+    /// data_set.add_var_f32("temperature", &["time", "station"]).unwrap();
+    ///
+    /// for (time, temperatures) in [(0.0, [14.5, 12.0]), (1.0, [15.0, 12.5])].iter() {
+    ///     let mut record: HashMap<String, DataVector> = HashMap::new();
+    ///     record.insert(String::from("time"), DataVector::F64(vec![*time]));
+    ///     record.insert(String::from("temperature"), DataVector::F32(temperatures.to_vec()));
+    ///     data_set.append_record(record).unwrap();
+    /// }
+    /// assert_eq!(Some(2),                                                 data_set.num_records());
+    /// assert_eq!(Some(&DataVector::F32(vec![14.5, 12.0, 15.0, 12.5])),    data_set["temperature"].data());
+    ///
+    /// // The values of `temperature` are missing
+    /// let mut record: HashMap<String, DataVector> = HashMap::new();
+    /// record.insert(String::from("time"), DataVector::F64(vec![2.0]));
+    /// assert_eq!(
+    ///     Err(InvalidDataSet::VariableMismatchDataLength{var_name: String::from("temperature"), req: 2, get: 0}),
+    ///     data_set.append_record(record)
+    /// );
+    /// assert_eq!(Some(2),                                                 data_set.num_records());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn append_record(&mut self, values_by_var: HashMap<String, DataVector>) -> Result<(), InvalidDataSet> {
+        let num_records: usize = self.num_records().ok_or(InvalidDataSet::UnlimitedDimensionNotDefined)?;
+        // Check the record and the loaded values before modifying them
+        for var_name in values_by_var.keys() {
+            let var: &Variable = self.find_var_from_name(var_name)?.1;
+            if !var.is_record_var() {
+                return Err(InvalidDataSet::VariableNotRecord(var_name.to_string()));
+            }
+        }
+        for var in self.vars.iter().filter(|var: &&Variable| var.is_record_var()) {
+            let chunk_len: usize = var.chunk_len();
+            let record_values: Option<&DataVector> = values_by_var.get(var.name());
+            if let Some(record_values) = record_values {
+                if record_values.data_type() != var.data_type() {
+                    return Err(InvalidDataSet::VariableMismatchDataType{var_name: var.name().to_string(), req: var.data_type(), get: record_values.data_type()});
+                }
+            }
+            let record_len: usize = record_values.map(DataVector::len).unwrap_or(0);
+            if record_len != chunk_len {
+                return Err(InvalidDataSet::VariableMismatchDataLength{var_name: var.name().to_string(), req: chunk_len, get: record_len});
+            }
+            match &var.data {
+                None => if num_records * chunk_len > 0 {
+                    return Err(InvalidDataSet::VariableDataNotLoaded(var.name().to_string()));
+                },
+                Some(values) => {
+                    if values.data_type() != var.data_type() {
+                        return Err(InvalidDataSet::VariableMismatchDataType{var_name: var.name().to_string(), req: var.data_type(), get: values.data_type()});
+                    }
+                    if values.len() != num_records * chunk_len {
+                        return Err(InvalidDataSet::VariableMismatchDataLength{var_name: var.name().to_string(), req: num_records * chunk_len, get: values.len()});
+                    }
+                },
+            }
+        }
+
+        for (var_name, record_values) in values_by_var.into_iter() {
+            let var_index: usize = self.find_var_from_name(&var_name)?.0;
+            let values: &mut DataVector = self.vars[var_index].data.get_or_insert_with(|| DataVector::new(record_values.data_type(), 0));
+            match (values, record_values) {
+                (DataVector::I8(values), DataVector::I8(mut record_values)) => values.append(&mut record_values),
+                (DataVector::U8(values), DataVector::U8(mut record_values)) => values.append(&mut record_values),
+                (DataVector::I16(values), DataVector::I16(mut record_values)) => values.append(&mut record_values),
+                (DataVector::I32(values), DataVector::I32(mut record_values)) => values.append(&mut record_values),
+                (DataVector::F32(values), DataVector::F32(mut record_values)) => values.append(&mut record_values),
+                (DataVector::F64(values), DataVector::F64(mut record_values)) => values.append(&mut record_values),
+                _ => {},  // previously checked
+            }
+        }
+        if let Some(unlimited_dim) = &self.unlimited_dim {
+            if let DimensionSize::Unlimited(dim_size) = &unlimited_dim.size {
+                dim_size.replace(num_records + 1);
+            }
+        }
+        return Ok(());
+    }
+
+    /// Returns the values of the record `record_index` of each record variable, borrowed from their loaded values
+    /// (see [`Variable::data`](struct.Variable.html#method.data)).
+    ///
+    /// An error is returned if the values of a record variable are not loaded. The returned slices can be written back
+    /// by [`FileWriter::write_records`](struct.FileWriter.html#method.write_records).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use netcdf3::{DataSet, DataSlice, DataVector, InvalidDataSet};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.set_unlimited_dim("time", 0).unwrap();
+    /// data_set.add_fixed_dim("station", 2).unwrap();
+    /// data_set.add_var_f64("time", &["time"]).unwrap();
+    /// data_set.add_var_f32("temperature", &["time", "station"]).unwrap();
+    /// data_set.add_var_i32("station_id", &["station"]).unwrap();
+    ///
+    /// for (time, temperatures) in [(0.0, [14.5, 12.0]), (1.0, [15.0, 12.5])].iter() {
+    ///     let mut record: HashMap<String, DataVector> = HashMap::new();
+    ///     record.insert(String::from("time"), DataVector::F64(vec![*time]));
+    ///     record.insert(String::from("temperature"), DataVector::F32(temperatures.to_vec()));
+    ///     data_set.append_record(record).unwrap();
+    /// }
+    ///
+    /// let record: HashMap<String, DataSlice> = data_set.get_record(1).unwrap();
+    /// assert_eq!(2,                                       record.len());
+    /// assert_eq!(Some(&DataSlice::F64(&[1.0])),           record.get("time"));
+    /// assert_eq!(Some(&DataSlice::F32(&[15.0, 12.5])),    record.get("temperature"));
+    ///
+    /// assert_eq!(
+    ///     Err(InvalidDataSet::RecordIndexExceeded{index: 2, num_records: 2}),
+    ///     data_set.get_record(2)
+    /// );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn get_record(&self, record_index: usize) -> Result<HashMap<String, DataSlice<'_>>, InvalidDataSet> {
+        let num_records: usize = self.num_records().ok_or(InvalidDataSet::UnlimitedDimensionNotDefined)?;
+        if record_index >= num_records {
+            return Err(InvalidDataSet::RecordIndexExceeded{index: record_index, num_records: num_records});
+        }
+        let mut record: HashMap<String, DataSlice<'_>> = HashMap::new();
+        for var in self.vars.iter().filter(|var: &&Variable| var.is_record_var()) {
+            let chunk_len: usize = var.chunk_len();
+            let values: DataSlice<'_> = match &var.data {
+                None => return Err(InvalidDataSet::VariableDataNotLoaded(var.name().to_string())),
+                Some(values) => values.as_slice(),
+            };
+            if values.len() != num_records * chunk_len {
+                return Err(InvalidDataSet::VariableMismatchDataLength{var_name: var.name().to_string(), req: num_records * chunk_len, get: values.len()});
+            }
+            record.insert(var.name().to_string(), values.sub_slice(record_index * chunk_len, (record_index + 1) * chunk_len));
+        }
+        return Ok(record);
+    }
+
+    /// Makes the *fixed-size* variable a record variable, its first dimension being replaced by the *unlimited-size* dimension.
+    ///
+    /// The size of the first dimension must be the number of records. The values of the variable keep their order,
+    /// the data read before the conversion are the data of the record variable. Nothing is done if the variable is already
+    /// a record variable.
+    ///
+    /// The variable is unchanged if the limits of the 64-bit offset version would be exceeded (see [`check_format_limits`](#method.check_format_limits)).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::{DataSet, InvalidDataSet};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.set_unlimited_dim("time", 3).unwrap();
+    /// data_set.add_fixed_dim("time_fixed", 3).unwrap();
+    /// data_set.add_fixed_dim("station", 2).unwrap();
+    /// data_set.add_var_f32("temperature", &["time_fixed", "station"]).unwrap();
+    /// data_set.add_var_i32("station_id", &["station"]).unwrap();
+    ///
+    /// data_set.make_record_var("temperature").unwrap();
+    /// assert_eq!(Some(true),                          data_set.is_record_var("temperature"));
+    /// assert_eq!(vec!["time", "station"],             data_set.get_var("temperature").unwrap().dim_names());
+    ///
+    /// data_set.make_fixed_var("temperature", "time_fixed").unwrap();
+    /// assert_eq!(Some(false),                         data_set.is_record_var("temperature"));
+    /// assert_eq!(vec!["time_fixed", "station"],       data_set.get_var("temperature").unwrap().dim_names());
+    ///
+    /// assert_eq!(
+    ///     Err(InvalidDataSet::NumRecordsMismatch{var_name: String::from("station_id"), num_records: 3, dim_size: Some(2)}),
+    ///     data_set.make_record_var("station_id")
+    /// );
+    /// ```
+    pub fn make_record_var(&mut self, var_name: &str) -> Result<(), InvalidDataSet> {
+        let unlimited_dim: Rc<Dimension> = self.unlimited_dim.clone().ok_or(InvalidDataSet::UnlimitedDimensionNotDefined)?;
+        let var_index: usize = self.find_var_from_name(var_name)?.0;
+        let var: &Variable = &self.vars[var_index];
+        if var.is_record_var() {
+            return Ok(());
+        }
+        let dim_size: Option<usize> = var.dims.first().map(|dim: &Rc<Dimension>| dim.size());
+        if dim_size != Some(unlimited_dim.size()) {
+            return Err(InvalidDataSet::NumRecordsMismatch{var_name: var_name.to_string(), num_records: unlimited_dim.size(), dim_size: dim_size});
+        }
+        let fixed_dim: Rc<Dimension> = self.exchange_first_dim(var_index, unlimited_dim);
+        // Check that the record size can still be represented and that the limits are still kept
+        match self.check_changed_var(var_index) {
+            Err(err) => {
+                let _ = self.exchange_first_dim(var_index, fixed_dim);
+                return Err(err);
+            },
+            Ok(vars_layout) => self.vars_layout = vars_layout,
+        }
+        return Ok(());
+    }
+
+    /// Makes the record variable a *fixed-size* variable, its *unlimited-size* dimension being replaced by the *fixed-size*
+    /// dimension `dim_name` (see [`make_record_var`](#method.make_record_var)).
+    ///
+    /// The dimension is defined with the number of records as size if it does not exist yet, otherwise its size must be
+    /// the number of records. Nothing is done if the variable is already a *fixed-size* variable.
+    ///
+    /// The variable is unchanged and the dimension is not defined if the limits of the 64-bit offset version would be exceeded
+    /// (see [`check_format_limits`](#method.check_format_limits)).
+    pub fn make_fixed_var(&mut self, var_name: &str, dim_name: &str) -> Result<(), InvalidDataSet> {
+        let num_records: usize = self.num_records().ok_or(InvalidDataSet::UnlimitedDimensionNotDefined)?;
+        let var_index: usize = self.find_var_from_name(var_name)?.0;
+        if !self.vars[var_index].is_record_var() {
+            return Ok(());
+        }
+        // the new dimension is defined once the variable has been checked
+        let (fixed_dim, new_dim): (Rc<Dimension>, bool) = match self.get_dim(dim_name) {
+            None => (Rc::new(Dimension::new_fixed_size(dim_name, num_records, self.name_validation, self.allow_long_names)?), true),
+            Some(dim) if dim.is_unlimited() => return Err(InvalidDataSet::UnlimitedDimensionAlreadyExists(dim_name.to_string())),
+            Some(dim) if dim.size() != num_records => {
+                return Err(InvalidDataSet::NumRecordsMismatch{var_name: var_name.to_string(), num_records: num_records, dim_size: Some(dim.size())});
+            },
+            Some(dim) if self.vars[var_index].dims.iter().any(|var_dim: &Rc<Dimension>| Rc::ptr_eq(var_dim, &dim)) => {
+                let mut dim_names: Vec<String> = self.vars[var_index].dim_names();
+                dim_names[0] = dim_name.to_string();
+                return Err(InvalidDataSet::DimensionsUsedMultipleTimes{var_name: var_name.to_string(), get_dim_names: dim_names});
+            },
+            Some(dim) => (dim, false),
+        };
+        let unlimited_dim: Rc<Dimension> = self.exchange_first_dim(var_index, Rc::clone(&fixed_dim));
+        // Check that the size of the variable can be represented and that the limits are still kept
+        match self.check_changed_var(var_index) {
+            Err(err) => {
+                let _ = self.exchange_first_dim(var_index, unlimited_dim);
+                return Err(err);
+            },
+            Ok(vars_layout) => self.vars_layout = vars_layout,
+        }
+        if new_dim {
+            self.dims_index.push(dim_name, self.dims.len());
+            self.dims.push(fixed_dim);
+        }
+        return Ok(());
+    }
+
+    /// Replaces the first dimension of the variable by `dim`, and returns the replaced dimension.
+    fn exchange_first_dim(&mut self, var_index: usize, dim: Rc<Dimension>) -> Rc<Dimension> {
+        let var: &mut Variable = &mut self.vars[var_index];
+        var.unlimited_dim = if dim.is_unlimited() { Some(Rc::clone(&dim)) } else { None };
+        return core::mem::replace(&mut var.dims[0], dim);
+    }
+
+    /// Checks that the variables can be stored in a file of the NetCDF-3 `version`.
+    ///
+    /// The chunk size of the variables cannot exceed [`Version::max_chunk_size`](enum.Version.html#method.max_chunk_size)
+    /// (`2^31 - 4` bytes for the classic version and `2^32 - 4` bytes for the 64-bit offset version), except for:
+    ///
+    /// - the last *fixed-size* variable when no record variable is defined,
+    /// - the last record variable.
+    ///
+    /// These chunk sizes are not used to compute the begin offsets of the other variables. The limits of the 64-bit offset
+    /// version are checked when the variables are added, the ones of the classic version are checked by the
+    /// [`FileWriter`](struct.FileWriter.html) before writing the header.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::{DataSet, InvalidDataSet, Version};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.add_fixed_dim("dim_1", 50_000).unwrap();
+    /// data_set.add_fixed_dim("dim_2", 15_000).unwrap();
+    /// // 50_000 * 15_000 * 4 bytes (~2.8 GiB)
+    /// data_set.add_var_f32("large_var", &["dim_1", "dim_2"]).unwrap();
+    /// assert_eq!(Ok(()),      data_set.check_format_limits(Version::Classic));
+    ///
+    /// // The large variable is not the last one anymore
+    /// data_set.add_var_f32("small_var", &["dim_1"]).unwrap();
+    /// assert_eq!(
+    ///     Err(InvalidDataSet::MaximumVariableSizeExceeded{
+    ///         var_name: String::from("large_var"),
+    ///         version: Version::Classic,
+    ///         max: 2_147_483_644,
+    ///         get: 3_000_000_000,
+    ///     }),
+    ///     data_set.check_format_limits(Version::Classic)
+    /// );
+    /// assert_eq!(Ok(()),      data_set.check_format_limits(Version::Offset64Bit));
+    ///
+    /// // 50_000 * 50_000 * 4 bytes (~9.3 GiB), exceeds the limits of both versions
+    /// data_set.add_fixed_dim("dim_3", 50_000).unwrap();
+    /// data_set.add_var_f32("very_large_var", &["dim_1", "dim_3"]).unwrap();
+    /// assert_eq!(
+    ///     Err(InvalidDataSet::MaximumVariableSizeExceeded{
+    ///         var_name: String::from("very_large_var"),
+    ///         version: Version::Offset64Bit,
+    ///         max: 4_294_967_292,
+    ///         get: 10_000_000_000,
+    ///     }),
+    ///     data_set.add_var_f32("other_var", &["dim_1"])
+    /// );
+    /// assert_eq!(false,       data_set.has_var("other_var"));
+    /// ```
+    pub fn check_format_limits(&self, version: Version) -> Result<(), InvalidDataSet> {
+        let (record_vars, fixed_size_vars): (Vec<&Variable>, Vec<&Variable>) = self.vars.iter().partition(|var: &&Variable| var.is_record_var());
+        let num_unchecked_fixed_size_vars: usize = match record_vars.is_empty() {
+            true => 1,
+            false => 0,
+        };
+        let checked_vars = fixed_size_vars.iter().take(fixed_size_vars.len().saturating_sub(num_unchecked_fixed_size_vars))
+            .chain(record_vars.iter().take(record_vars.len().saturating_sub(1)));
+        for var in checked_vars {
+            DataSet::check_var_limits(var, version)?;
+        }
+        return Ok(());
+    }
+
+    /// Checks the limits of the version `version` like [`check_format_limits`](#method.check_format_limits), for the last variable
+    /// just added to a data set respecting them, `prev_vars_layout` being the layout before its addition.
+    ///
+    /// Only the added variable and the variable which was the last of its kind can break the limits.
+    fn check_added_var_limits(&self, prev_vars_layout: &VarsLayout, version: Version) -> Result<(), InvalidDataSet> {
+        let added_var_index: usize = self.vars.len() - 1;
+        let checked_var_indices: [Option<usize>; 2] = match self.vars[added_var_index].is_record_var() {
+            // the previous last record variable, and the last *fixed-size* variable if no record variable was defined
+            true => [prev_vars_layout.last_record_var(), prev_vars_layout.last_fixed_var().filter(|_| prev_vars_layout.num_record_vars() == 0)],
+            // the added variable if a record variable is defined, otherwise the previous last *fixed-size* variable
+            false if prev_vars_layout.num_record_vars() > 0 => [Some(added_var_index), None],
+            false => [prev_vars_layout.last_fixed_var(), None],
+        };
+        for var_index in checked_var_indices.iter().flatten() {
+            DataSet::check_var_limits(&self.vars[*var_index], version)?;
+        }
+        return Ok(());
+    }
+
+    /// Checks that the chunk size of the variable does not exceed the maximum size allowed by the version `version`.
+    fn check_var_limits(var: &Variable, version: Version) -> Result<(), InvalidDataSet> {
+        let max_chunk_size: usize = version.max_chunk_size();
+        let chunk_size: usize = var.chunk_size();
+        if chunk_size > max_chunk_size {
+            return Err(InvalidDataSet::MaximumVariableSizeExceeded{
+                var_name: var.name().to_string(),
+                version: version,
+                max: max_chunk_size,
+                get: chunk_size,
+            });
+        }
+        return Ok(());
+    }
+
+    /// Checks that the attributes `_FillValue` and `missing_value` of each variable have the data type of the variable,
+    /// as required by the NetCDF C library.
+    ///
+    /// These attributes are checked when they are added, but not when they are read from a file (the mismatches are reported by
+    /// [`FileReader::diagnostics`](struct.FileReader.html#method.diagnostics)). This check is done by the
+    /// [`FileWriter`](struct.FileWriter.html) before writing the header.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::{DataSet, DataType, InvalidDataSet};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.add_fixed_dim("dim_1", 3).unwrap();
+    /// data_set.add_var_i16("var_1", &["dim_1"]).unwrap();
+    /// data_set.add_var_attr_i16("var_1", "_FillValue", vec![-1]).unwrap();
+    /// assert_eq!(Ok(()),      data_set.check_attr_data_types());
+    ///
+    /// assert_eq!(
+    ///     Err(InvalidDataSet::VariableAttributeMismatchDataType{
+    ///         var_name: String::from("var_1"),
+    ///         attr_name: String::from("missing_value"),
+    ///         req: DataType::I16,
+    ///         get: DataType::F32,
+    ///     }),
+    ///     data_set.add_var_attr_f32("var_1", "missing_value", vec![-1.0])
+    /// );
+    /// ```
+    pub fn check_attr_data_types(&self) -> Result<(), InvalidDataSet> {
+        for var in self.vars.iter() {
+            for attr in var.attrs.iter() {
+                var.check_attr_data_type(&attr.name, attr.data_type())?;
+            }
+        }
+        return Ok(());
+    }
+
+    /// Checks that the names of the dimensions, the variables and the attributes do not exceed
+    /// [`NC_MAX_NAME_SIZE`](constant.NC_MAX_NAME_SIZE.html) bytes.
+    ///
+    /// These sizes are checked when the names are defined, but not when they are read with the policy
+    /// [`LongNamePolicy::Preserve`](enum.LongNamePolicy.html#variant.Preserve). This check is done by the
+    /// [`FileWriter`](struct.FileWriter.html) before writing the header.
+    pub fn check_name_sizes(&self) -> Result<(), InvalidDataSet> {
+        let too_long = |name: &str| -> Option<NameError> {
+            return match name.len() > NC_MAX_NAME_SIZE {
+                true => Some(NameError::TooLong{max: NC_MAX_NAME_SIZE, get: name.len()}),
+                false => None,
+            };
+        };
+        for dim in self.dims.iter() {
+            if let Some(reason) = too_long(&dim.name()) {
+                return Err(InvalidDataSet::DimensionNameNotValid{dim_name: dim.name(), reason: reason});
+            }
+        }
+        for attr in self.attrs.iter() {
+            if let Some(reason) = too_long(&attr.name) {
+                return Err(InvalidDataSet::GlobalAttributeNameNotValid{attr_name: attr.name.to_string(), reason: reason});
+            }
+        }
+        for var in self.vars.iter() {
+            if let Some(reason) = too_long(&var.name) {
+                return Err(InvalidDataSet::VariableNameNotValid{var_name: var.name.to_string(), reason: reason});
+            }
+            for attr in var.attrs.iter() {
+                if let Some(reason) = too_long(&attr.name) {
+                    return Err(InvalidDataSet::VariableAttributeNameNotValid{
+                        var_name: var.name.to_string(),
+                        attr_name: attr.name.to_string(),
+                        reason: reason,
+                    });
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    /// Returns a compact multi-line overview of the data set: the numbers of dimensions, variables and global attributes,
+    /// the dimensions with their sizes, the variables with their data types and shapes, and a few key global attributes
+    /// (`title`, `institution`, `source`, `Conventions` and `history`, truncated to 80 characters).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::DataSet;
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.set_unlimited_dim("time", 2).unwrap();
+    /// data_set.add_fixed_dim("latitude", 3).unwrap();
+    /// data_set.add_var_f64("time", &["time"]).unwrap();
+    /// data_set.add_var_f32("t2m", &["time", "latitude"]).unwrap();
+    /// data_set.add_global_attr_string("title", "Example").unwrap();
+    /// data_set.add_global_attr_i32("version", vec![2]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     "2 dimensions, 2 variables, 2 global attributes\n\
+    ///      dimensions:\n\
+    ///      \x20   time = UNLIMITED (2 currently)\n\
+    ///      \x20   latitude = 3\n\
+    ///      variables:\n\
+    ///      \x20   double time(time)  // shape [2]\n\
+    ///      \x20   float t2m(time, latitude)  // shape [2, 3]\n\
+    ///      global attributes:\n\
+    ///      \x20   title = \"Example\"\n",
+    ///     data_set.summary()
+    /// );
+    /// ```
+    pub fn summary(&self) -> String {
+        use core::fmt::Write;
+        const KEY_GLOBAL_ATTR_NAMES: [&str; 5] = ["title", "institution", "source", "Conventions", "history"];
+        const MAX_ATTR_LEN: usize = 80;
+
+        // Writing into a `String` cannot fail
+        let mut summary = String::new();
+        let _ = writeln!(summary, "{} dimensions, {} variables, {} global attributes", self.num_dims(), self.num_vars(), self.num_global_attrs());
+        if !self.dims.is_empty() {
+            summary.push_str("dimensions:\n");
+            for dim in self.dims.iter() {
+                let _ = writeln!(summary, "    {}", dim);
+            }
+        }
+        if !self.vars.is_empty() {
+            summary.push_str("variables:\n");
+            for var in self.vars.iter() {
+                let _ = write!(summary, "    {} {}", var.data_type(), var.name());
+                if var.num_dims() > 0 {
+                    let _ = write!(summary, "({})", var.dim_names().join(", "));
+                }
+                let shape: Vec<String> = var.dims().iter().map(|dim: &Rc<Dimension>| dim.size().to_string()).collect();
+                let _ = writeln!(summary, "  // shape [{}]", shape.join(", "));
+            }
+        }
+        let key_attrs: Vec<&Attribute> = KEY_GLOBAL_ATTR_NAMES.iter().filter_map(|attr_name: &&str| self.get_global_attr(attr_name)).collect();
+        if !key_attrs.is_empty() {
+            summary.push_str("global attributes:\n");
+            for attr in key_attrs.into_iter() {
+                let mut attr_line = String::new();
+                let _ = write!(attr_line, "{:#}", attr);
+                if attr_line.chars().count() > MAX_ATTR_LEN {
+                    attr_line = attr_line.chars().take(MAX_ATTR_LEN - 3).collect::<String>() + "...";
+                }
+                let _ = writeln!(summary, "    {}", attr_line);
+            }
+        }
+        return summary;
+    }
+}
+
+/// Formats the number of seconds since the Unix epoch like the C function `asctime` (`Sat Oct 17 09:30:00 2026`), in UTC.
+#[cfg(feature = "std")]
+fn format_history_time(seconds: u64) -> String {
+    const WEEKDAY_NAMES: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+    const MONTH_NAMES: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+    let days: u64 = seconds / 86_400;
+    let seconds_of_day: u64 = seconds % 86_400;
+    // 1970-01-01 is a thursday
+    let weekday: usize = ((days + 4) % 7) as usize;
+    // civil date from the number of days (proleptic Gregorian calendar), the years starting on March 1st
+    let shifted_days: u64 = days + 719_468;
+    let era: u64 = shifted_days / 146_097;
+    let day_of_era: u64 = shifted_days % 146_097;
+    let year_of_era: u64 = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year: u64 = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month: u64 = (5 * day_of_year + 2) / 153;
+    let day: u64 = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month: u64 = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 };
+    let year: u64 = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+    return format!(
+        "{} {} {:2} {:02}:{:02}:{:02} {}",
+        WEEKDAY_NAMES[weekday], MONTH_NAMES[(month - 1) as usize], day,
+        seconds_of_day / 3_600, (seconds_of_day % 3_600) / 60, seconds_of_day % 60,
+        year,
+    );
+}
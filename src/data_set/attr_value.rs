@@ -0,0 +1,65 @@
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+
+use crate::{DataSlice, DataType, DataVector};
+
+/// Value of an attribute, allowing to add several attributes of different data types at once
+/// (see [`DataSet::add_var_attrs`](struct.DataSet.html#method.add_var_attrs)).
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{AttrValue, DataSlice, DataType};
+///
+/// assert_eq!(DataType::F32,           AttrValue::F32(0.0).data_type());
+/// assert_eq!(DataType::U8,            AttrValue::Str("K").data_type());
+/// assert_eq!(DataType::I16,           AttrValue::Slice(DataSlice::I16(&[0, 100])).data_type());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AttrValue<'a> {
+    I8(i8),
+    U8(u8),
+    I16(i16),
+    I32(i32),
+    F32(f32),
+    F64(f64),
+    /// Text, stored as `u8` values.
+    Str(&'a str),
+    /// Several values (for example the attribute `valid_range`).
+    Slice(DataSlice<'a>),
+}
+
+impl<'a> AttrValue<'a> {
+
+    /// Returns the NetCDF-3 data type of the attribute.
+    pub fn data_type(&self) -> DataType {
+        return match self {
+            AttrValue::I8(_) => DataType::I8,
+            AttrValue::U8(_) | AttrValue::Str(_) => DataType::U8,
+            AttrValue::I16(_) => DataType::I16,
+            AttrValue::I32(_) => DataType::I32,
+            AttrValue::F32(_) => DataType::F32,
+            AttrValue::F64(_) => DataType::F64,
+            AttrValue::Slice(data) => data.data_type(),
+        };
+    }
+
+    /// Copies the values into a `DataVector`.
+    pub(crate) fn to_data_vector(self) -> DataVector {
+        return match self {
+            AttrValue::I8(value) => DataVector::I8(vec![value]),
+            AttrValue::U8(value) => DataVector::U8(vec![value]),
+            AttrValue::I16(value) => DataVector::I16(vec![value]),
+            AttrValue::I32(value) => DataVector::I32(vec![value]),
+            AttrValue::F32(value) => DataVector::F32(vec![value]),
+            AttrValue::F64(value) => DataVector::F64(vec![value]),
+            AttrValue::Str(text) => DataVector::U8(text.as_bytes().to_vec()),
+            AttrValue::Slice(DataSlice::I8(data)) => DataVector::I8(data.to_vec()),
+            AttrValue::Slice(DataSlice::U8(data)) => DataVector::U8(data.to_vec()),
+            AttrValue::Slice(DataSlice::I16(data)) => DataVector::I16(data.to_vec()),
+            AttrValue::Slice(DataSlice::I32(data)) => DataVector::I32(data.to_vec()),
+            AttrValue::Slice(DataSlice::F32(data)) => DataVector::F32(data.to_vec()),
+            AttrValue::Slice(DataSlice::F64(data)) => DataVector::F64(data.to_vec()),
+        };
+    }
+}
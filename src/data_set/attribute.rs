@@ -1,4 +1,8 @@
-use crate::name_string::is_valid_name;
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use core::cell::{Cell, OnceCell};
+
+use crate::name_string::{validate_name_with, NameError, NameValidation};
 use crate::data_vector::DataVector;
 use crate::DataType;
 
@@ -253,69 +257,172 @@ use crate::DataType;
 /// ```
 ///
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub struct Attribute {
     pub(crate) name: String,
-    pub(crate) data: DataVector,
+    data: AttributeData,
+}
+
+/// Values of an attribute, decoded or kept as big-endian bytes until their first access.
+enum AttributeData {
+    Decoded(DataVector),
+    /// The raw bytes are released once the values are decoded into `decoded`.
+    Raw{data_type: DataType, len: usize, bytes: Cell<Vec<u8>>, decoded: OnceCell<DataVector>},
+}
+
+impl AttributeData {
+    fn get(&self) -> &DataVector {
+        return match self {
+            AttributeData::Decoded(data) => data,
+            AttributeData::Raw{data_type, len: _, bytes, decoded} => decoded.get_or_init(|| {
+                DataVector::from_be_bytes(&bytes.take(), *data_type)
+            }),
+        };
+    }
+}
+
+impl Clone for AttributeData {
+    fn clone(&self) -> Self {
+        return match self {
+            AttributeData::Decoded(data) => AttributeData::Decoded(data.clone()),
+            AttributeData::Raw{data_type, len, bytes, decoded} => {
+                // the bytes are moved out of the cell while being copied
+                let raw_bytes: Vec<u8> = bytes.take();
+                let cloned_bytes: Vec<u8> = raw_bytes.clone();
+                bytes.set(raw_bytes);
+                AttributeData::Raw{data_type: *data_type, len: *len, bytes: Cell::new(cloned_bytes), decoded: decoded.clone()}
+            },
+        };
+    }
+}
+
+impl core::fmt::Debug for AttributeData {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        return match self {
+            AttributeData::Decoded(data) => f.debug_tuple("Decoded").field(data).finish(),
+            AttributeData::Raw{data_type, len, bytes: _, decoded} => f.debug_struct("Raw")
+                .field("data_type", data_type)
+                .field("len", len)
+                .field("decoded", decoded)
+                .finish(),
+        };
+    }
+}
+
+impl PartialEq for Attribute {
+    fn eq(&self, other: &Self) -> bool {
+        return self.name == other.name && self.data() == other.data();
+    }
 }
 
 impl Attribute {
     /// Creates a new attribute from a `DataVector`.
-    pub(crate) fn new(name: &str, data: DataVector) -> Result<Attribute, String> {
-        Attribute::check_attr_name(name)?;
-        Ok(Attribute {
-            name: name.to_string(),
+    pub(crate) fn new(name: &str, data: DataVector) -> Result<Attribute, NameError> {
+        Attribute::check_attr_name(name, NameValidation::Strict)?;
+        Ok(Attribute::from_data(name.to_string(), data))
+    }
+
+    /// Creates a new attribute from a `DataVector`, its name being checked by the caller.
+    pub(crate) fn from_data(name: String, data: DataVector) -> Attribute {
+        return Attribute {
+            name: name,
+            data: AttributeData::Decoded(data),
+        };
+    }
+
+    /// Creates a new attribute from the big-endian bytes of its values, decoded at the first access.
+    ///
+    /// The name of the attribute is checked by the caller, and the number of bytes is a multiple of the size of the data type.
+    /// The `u8` values are the raw bytes, they are stored at once.
+    pub(crate) fn from_be_bytes(name: String, data_type: DataType, bytes: Vec<u8>) -> Attribute {
+        let data: AttributeData = match data_type {
+            DataType::U8 => AttributeData::Decoded(DataVector::U8(bytes)),
+            _ => AttributeData::Raw{
+                data_type: data_type,
+                len: bytes.len() / data_type.size_of(),
+                bytes: Cell::new(bytes),
+                decoded: OnceCell::new(),
+            },
+        };
+        return Attribute {
+            name: name,
             data: data,
-        })
+        };
+    }
+
+    /// Returns the values of the attribute, decoding them if necessary.
+    pub(crate) fn data(&self) -> &DataVector {
+        return self.data.get();
+    }
+
+    /// Returns `true` if the values of the attribute have been decoded.
+    ///
+    /// The values are kept as raw bytes while reading the header with the option
+    /// [`ReadOptions::lazy_attrs`](struct.ReadOptions.html#structfield.lazy_attrs), and decoded at the first access.
+    /// The `u8` values do not require any decoding, they are always decoded.
+    pub fn is_decoded(&self) -> bool {
+        return match &self.data {
+            AttributeData::Decoded(_) => true,
+            AttributeData::Raw{decoded, ..} => decoded.get().is_some(),
+        };
     }
     /// Creates a new attribute containing i8 data.
-    pub(in crate::data_set) fn new_i8(name: &str, data: Vec<i8>) -> Result<Attribute, String> {
+    pub(in crate::data_set) fn new_i8(name: &str, data: Vec<i8>) -> Result<Attribute, NameError> {
         let data = DataVector::I8(data);
         Attribute::new(name, data)
     }
 
     /// Creates a new attribute containing *u8* data.
-    pub(in crate::data_set) fn new_u8(name: &str, data: Vec<u8>) -> Result<Attribute, String> {
+    pub(in crate::data_set) fn new_u8(name: &str, data: Vec<u8>) -> Result<Attribute, NameError> {
         let data = DataVector::U8(data);
         Attribute::new(name, data)
     }
 
     /// Create a new attribute containing *i16* data.
-    pub(in crate::data_set) fn new_i16(name: &str, data: Vec<i16>) -> Result<Attribute, String> {
+    pub(in crate::data_set) fn new_i16(name: &str, data: Vec<i16>) -> Result<Attribute, NameError> {
         let data = DataVector::I16(data);
         Attribute::new(name, data)
     }
 
     /// Creates a new attribute containing *i32* data.
-    pub(crate) fn new_i32(name: &str, data: Vec<i32>) -> Result<Attribute, String> {
+    pub(crate) fn new_i32(name: &str, data: Vec<i32>) -> Result<Attribute, NameError> {
         let data = DataVector::I32(data);
         Attribute::new(name, data)
     }
 
     /// Creates a new attribute containing *f32* data.
-    pub(crate) fn new_f32(name: &str, data: Vec<f32>) -> Result<Attribute, String> {
+    pub(crate) fn new_f32(name: &str, data: Vec<f32>) -> Result<Attribute, NameError> {
         let data = DataVector::F32(data);
         Attribute::new(name, data)
     }
 
     /// Creates a new attribute containing *f64* data.
-    pub(crate) fn new_f64(name: &str, data: Vec<f64>) -> Result<Attribute, String> {
+    pub(crate) fn new_f64(name: &str, data: Vec<f64>) -> Result<Attribute, NameError> {
         let data = DataVector::F64(data);
         Attribute::new(name, data)
     }
 
     /// Returns the name of the attribute.
+    #[inline]
     pub fn name(&self) -> &str {
         &self.name
     }
     /// Returns the NetCDF-3 data type of the attribute : *i8*, *u8*, ...
+    #[inline]
     pub fn data_type(&self) -> DataType {
-        self.data.data_type()
+        match &self.data {
+            AttributeData::Decoded(data) => data.data_type(),
+            AttributeData::Raw{data_type, ..} => *data_type,
+        }
     }
 
     /// Returns the number of elements (the length) of the attribute.
+    #[inline]
     pub fn len(&self) -> usize {
-        self.data.len()
+        match &self.data {
+            AttributeData::Decoded(data) => data.len(),
+            AttributeData::Raw{len, ..} => *len,
+        }
     }
 
     /// Returns a reference of the `i8` data or `None` of the attribute has not `i8` data.
@@ -359,12 +466,15 @@ impl Attribute {
     /// assert_eq!(None,                        global_attr.get_f64());
     /// ```
     pub fn get_i8(&self) -> Option<&[i8]> {
-        self.data.get_i8()
+        self.data().get_i8()
     }
 
     /// Returns a reference of the `u8` data or `None` if the attribute has not `u8` data (also see the method [get_i8](struct.Attribute.html#method.get_i8)).
     pub fn get_u8(&self) -> Option<&[u8]> {
-        self.data.get_u8()
+        match &self.data {
+            AttributeData::Decoded(data) => data.get_u8(),
+            AttributeData::Raw{..} => None,
+        }
     }
 
     /// Returns the attribute data as a `String`.
@@ -411,39 +521,145 @@ impl Attribute {
     ///
     /// ```
     pub fn get_as_string(&self) -> Option<String> {
-        self.data.get_as_string()
+        self.get_u8().and_then(|bytes: &[u8]| String::from_utf8(bytes.to_vec()).ok())
     }
 
     /// Returns a reference of the `i16` data or `None` if the attribute has not `i16` data (also see the method [get_i8](struct.Attribute.html#method.get_i8)).
     pub fn get_i16(&self) -> Option<&[i16]> {
-        self.data.get_i16()
+        self.data().get_i16()
     }
 
     /// Returns a reference of the `i32` data or `None` if the attribute has not `i32` data (also see the method [get_i8](struct.Attribute.html#method.get_i8)).
     pub fn get_i32(&self) -> Option<&[i32]> {
-        self.data.get_i32()
+        self.data().get_i32()
     }
 
     /// Returns a reference of the `f32` data or `None` if the attribute has not `f32` data (also see the method [get_i8](struct.Attribute.html#method.get_i8)).
     pub fn get_f32(&self) -> Option<&[f32]> {
-        self.data.get_f32()
+        self.data().get_f32()
     }
 
     /// Returns a reference of the `f64` data or `None` if the attribute has not `f64` data (also see the method [get_i8](struct.Attribute.html#method.get_i8)).
     pub fn get_f64(&self) -> Option<&[f64]> {
-        self.data.get_f64()
+        self.data().get_f64()
+    }
+
+    /// Returns the text of a `u8` attribute, or `None` if the attribute contains numeric values.
+    ///
+    /// Unlike [get_as_string](struct.Attribute.html#method.get_as_string), the invalid UTF-8 sequences are replaced
+    /// by the character `U+FFFD` and the trailing null characters are removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::{DataSet, Attribute};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.add_global_attr_u8("title", b"Example\0".to_vec()).unwrap();
+    /// data_set.add_global_attr_f32("scale_factor", vec![0.5]).unwrap();
+    ///
+    /// let title: &Attribute = data_set.get_global_attr("title").unwrap();
+    /// assert_eq!(Some(String::from("Example")),           title.as_string());
+    /// assert_eq!(None,                                    title.as_f64_vec());
+    ///
+    /// let scale_factor: &Attribute = data_set.get_global_attr("scale_factor").unwrap();
+    /// assert_eq!(None,                                    scale_factor.as_string());
+    /// assert_eq!(Some(vec![0.5]),                         scale_factor.as_f64_vec());
+    /// assert_eq!(Some(0.5),                               scale_factor.first_as_f64());
+    /// ```
+    pub fn as_string(&self) -> Option<String> {
+        let bytes: &[u8] = self.get_u8()?;
+        let text_len: usize = bytes.iter().rposition(|byte: &u8| *byte != 0_u8).map(|index: usize| index + 1).unwrap_or(0);
+        return Some(String::from_utf8_lossy(&bytes[0..text_len]).into_owned());
     }
 
-    pub(crate) fn check_attr_name(attr_name: &str) -> Result<(), String> {
-        match is_valid_name(attr_name) {
-            true => Ok(()),
-            false => Err(attr_name.to_string()),
+    /// Returns a copy of the numeric values converted to `f64`, or `None` if the attribute is a `u8` (text) attribute
+    /// (also see the method [as_string](struct.Attribute.html#method.as_string)).
+    pub fn as_f64_vec(&self) -> Option<Vec<f64>> {
+        if self.data_type() == DataType::U8 {
+            return None;
+        }
+        return Some(self.data().get_as_f64_vec());
+    }
+
+    /// Returns the first numeric value converted to `f64`, or `None` if the attribute is empty or is a `u8` (text) attribute
+    /// (also see the method [as_string](struct.Attribute.html#method.as_string)).
+    pub fn first_as_f64(&self) -> Option<f64> {
+        return match self.data() {
+            DataVector::I8(data) => data.first().map(|value: &i8| *value as f64),
+            DataVector::U8(_) => None,
+            DataVector::I16(data) => data.first().map(|value: &i16| *value as f64),
+            DataVector::I32(data) => data.first().map(|value: &i32| *value as f64),
+            DataVector::F32(data) => data.first().map(|value: &f32| *value as f64),
+            DataVector::F64(data) => data.first().copied(),
+        };
+    }
+
+    pub(crate) fn check_attr_name(attr_name: &str, name_validation: NameValidation) -> Result<(), NameError> {
+        return validate_name_with(attr_name, name_validation);
+    }
+}
+
+/// Formats the text of a `u8` attribute (see [as_string](struct.Attribute.html#method.as_string)),
+/// or the numeric values separated by commas.
+///
+/// The alternate form (`{:#}`) prefixes the values with the attribute name as in CDL, the text being quoted.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::DataSet;
+///
+/// let mut data_set = DataSet::new();
+/// data_set.add_global_attr_string("units", "degree_north").unwrap();
+/// data_set.add_global_attr_i16("valid_range", vec![-90, 90]).unwrap();
+///
+/// assert_eq!("degree_north",      data_set.get_global_attr("units").unwrap().to_string());
+/// assert_eq!("-90, 90",           data_set.get_global_attr("valid_range").unwrap().to_string());
+/// assert_eq!(
+///     "units = \"degree_north\"",
+///     format!("{:#}", data_set.get_global_attr("units").unwrap())
+/// );
+/// assert_eq!(
+///     "valid_range = -90, 90",
+///     format!("{:#}", data_set.get_global_attr("valid_range").unwrap())
+/// );
+/// ```
+impl core::fmt::Display for Attribute {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        fn write_values<T: core::fmt::Display>(f: &mut core::fmt::Formatter, values: &[T]) -> core::fmt::Result {
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", value)?;
+            }
+            return Ok(());
+        }
+        if f.alternate() {
+            write!(f, "{} = ", self.name())?;
         }
+        if let Some(text) = self.as_string() {
+            return match f.alternate() {
+                true => write!(f, "{:?}", text),
+                false => write!(f, "{}", text),
+            };
+        }
+        return match self.data() {
+            DataVector::I8(data) => write_values(f, data),
+            DataVector::U8(data) => write_values(f, data),
+            DataVector::I16(data) => write_values(f, data),
+            DataVector::I32(data) => write_values(f, data),
+            DataVector::F32(data) => write_values(f, data),
+            DataVector::F64(data) => write_values(f, data),
+        };
     }
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(not(feature = "std"))]
+    use crate::alloc_prelude::*;
     use super::{Attribute, DataType};
     #[test]
     fn test_new_i8() {
@@ -459,4 +675,37 @@ mod tests {
         assert_eq!(None, attr.get_f32());
         assert_eq!(None, attr.get_f64());
     }
+
+    #[test]
+    fn test_value_accessors() {
+        // text attribute
+        let attr = Attribute::new_u8("units", b"m s-1\0\0".to_vec()).unwrap();
+        assert_eq!(Some(String::from("m s-1")),         attr.as_string());
+        assert_eq!(None,                                attr.as_f64_vec());
+        assert_eq!(None,                                attr.first_as_f64());
+        assert_eq!("m s-1",                             attr.to_string());
+        assert_eq!("units = \"m s-1\"",                 format!("{:#}", attr));
+
+        // not valid UTF-8 text
+        let attr = Attribute::new_u8("comment", vec![b'c', b'a', b'f', b'\xe9']).unwrap();
+        assert_eq!(None,                                attr.get_as_string());
+        assert_eq!(Some(String::from("caf\u{FFFD}")),   attr.as_string());
+
+        // numeric attributes
+        let attr = Attribute::new_i16("valid_range", vec![-10, 10]).unwrap();
+        assert_eq!(None,                                attr.as_string());
+        assert_eq!(Some(vec![-10.0, 10.0]),             attr.as_f64_vec());
+        assert_eq!(Some(-10.0),                         attr.first_as_f64());
+        assert_eq!("-10, 10",                           attr.to_string());
+        assert_eq!("valid_range = -10, 10",             format!("{:#}", attr));
+
+        let attr = Attribute::new_f64("scale_factor", vec![0.25]).unwrap();
+        assert_eq!(Some(0.25),                          attr.first_as_f64());
+        assert_eq!("0.25",                              attr.to_string());
+
+        let attr = Attribute::new_i32("empty", vec![]).unwrap();
+        assert_eq!(Some(vec![]),                        attr.as_f64_vec());
+        assert_eq!(None,                                attr.first_as_f64());
+        assert_eq!("",                                  attr.to_string());
+    }
 }
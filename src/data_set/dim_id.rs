@@ -0,0 +1,79 @@
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+
+use crate::DataSet;
+
+/// Handle on a dimension of a data set, returned by [`DataSet::add_fixed_dim`](struct.DataSet.html#method.add_fixed_dim),
+/// [`DataSet::set_unlimited_dim`](struct.DataSet.html#method.set_unlimited_dim) and [`DataSet::dim_id`](struct.DataSet.html#method.dim_id).
+///
+/// The handle can be given to [`DataSet::add_var`](struct.DataSet.html#method.add_var) instead of the dimension name,
+/// the dimension is then resolved from its position without any name lookup.
+/// The handle stays valid when the dimension is renamed, and becomes invalid when a dimension is removed from the data set.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{DataSet, DataType, InvalidDataSet};
+///
+/// let mut data_set = DataSet::new();
+/// let time = data_set.set_unlimited_dim("time", 2).unwrap();
+/// let latitude = data_set.add_fixed_dim("latitude", 3).unwrap();
+/// let longitude = data_set.add_fixed_dim("longitude", 4).unwrap();
+/// assert_eq!(Some(latitude),                  data_set.dim_id("latitude"));
+///
+/// data_set.add_var("t2m", &[time, latitude, longitude], DataType::F32).unwrap();
+/// assert_eq!(vec![2, 3, 4],                   data_set.var("t2m").unwrap().shape());
+///
+/// // the handles are invalidated by the removal of a dimension
+/// let unused = data_set.add_fixed_dim("unused", 1).unwrap();
+/// data_set.remove_dim("unused").unwrap();
+/// assert_eq!(
+///     InvalidDataSet::DimensionsNotDefined{
+///         var_name: String::from("u10"),
+///         undef_dim_names: vec![String::from("<dimension #0>"), String::from("<dimension #3>")],
+///     },
+///     data_set.add_var("u10", &[time, unused], DataType::F32).unwrap_err()
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DimId {
+    index: usize,
+    generation: u32,
+}
+
+impl DimId {
+    pub(in crate::data_set) fn new(index: usize, generation: u32) -> DimId {
+        return DimId {
+            index: index,
+            generation: generation,
+        };
+    }
+
+    /// Returns the position of the dimension in the data set.
+    pub fn index(&self) -> usize {
+        return self.index;
+    }
+}
+
+/// Reference to a dimension given to [`DataSet::add_var`](struct.DataSet.html#method.add_var): its name or its [`DimId`](struct.DimId.html).
+pub trait DimKey {
+    /// Returns the position of the dimension in the data set,
+    /// or the name reported by the error [`DimensionsNotDefined`](enum.InvalidDataSet.html#variant.DimensionsNotDefined).
+    fn dim_position(&self, data_set: &DataSet) -> Result<usize, String>;
+}
+
+impl<T: AsRef<str> + ?Sized> DimKey for T {
+    fn dim_position(&self, data_set: &DataSet) -> Result<usize, String> {
+        let dim_name: &str = self.as_ref();
+        return data_set.dims_index.get(dim_name).ok_or_else(|| String::from(dim_name));
+    }
+}
+
+impl DimKey for DimId {
+    fn dim_position(&self, data_set: &DataSet) -> Result<usize, String> {
+        if self.generation != data_set.dims_generation || self.index >= data_set.dims.len() {
+            return Err(String::from("<dimension #") + &self.index.to_string() + ">");
+        }
+        return Ok(self.index);
+    }
+}
@@ -1,8 +1,10 @@
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
 use crate::InvalidDataSet;
 use crate::NC_MAX_DIM_SIZE;
-use crate::name_string::is_valid_name;
+use crate::name_string::{check_name, NameError, NameValidation};
 
-use std::cell::RefCell;
+use core::cell::{Ref, RefCell};
 
 /// NetCDF-3 dimension
 ///
@@ -160,8 +162,8 @@ impl DimensionSize {
     /// Return the size of the dimension.
     pub(in crate::data_set) fn size(&self) -> usize {
         return match self {
-            DimensionSize::Unlimited(size) => size.borrow().clone(),
-            DimensionSize::Fixed(size) => size.clone(),
+            DimensionSize::Unlimited(size) => *size.borrow(),
+            DimensionSize::Fixed(size) => *size,
         };
     }
 
@@ -178,13 +180,13 @@ impl DimensionSize {
 impl Dimension {
 
     /// Creates a new *fixed size* NetCDF-3 dimension.
-    pub(crate) fn new_fixed_size(name: &str, size: usize) -> Result<Dimension, InvalidDataSet> {
-        Dimension::check_dim_name(name)?;
+    pub(crate) fn new_fixed_size(name: &str, size: usize, name_validation: NameValidation, allow_long_names: bool) -> Result<Dimension, InvalidDataSet> {
+        Dimension::check_dim_name(name, name_validation, allow_long_names)?;
         if size == 0 {
             return Err(InvalidDataSet::FixedDimensionWithZeroSize(name.to_string()));
         }
         if size > NC_MAX_DIM_SIZE {
-            return Err(InvalidDataSet::MaximumFixedDimensionSizeExceeded{dim_name: name.to_string(), get: size});
+            return Err(InvalidDataSet::MaximumFixedDimensionSizeExceeded{dim_name: name.to_string(), max: NC_MAX_DIM_SIZE, get: size});
         }
         return Ok(Dimension {
             name: RefCell::new(name.to_string()),
@@ -193,8 +195,8 @@ impl Dimension {
     }
 
     /// Creates a new *unlimited size* NetCDF-3 dimension.
-    pub(crate) fn new_unlimited_size(name: &str, size: usize) -> Result<Dimension, InvalidDataSet> {
-        Dimension::check_dim_name(name)?;
+    pub(crate) fn new_unlimited_size(name: &str, size: usize, name_validation: NameValidation, allow_long_names: bool) -> Result<Dimension, InvalidDataSet> {
+        Dimension::check_dim_name(name, name_validation, allow_long_names)?;
         return Ok(Dimension {
             name: RefCell::new(name.to_string()),
             size: DimensionSize::new(size, DimensionType::UnlimitedSize),
@@ -206,7 +208,15 @@ impl Dimension {
         return self.name.borrow().clone();
     }
 
+    /// Returns the name of the NetCDF-3 dimension without cloning it (see [`name`](#method.name)).
+    ///
+    /// The name is shared with the data set, which can rename the dimension once the returned `Ref` is dropped.
+    pub fn borrow_name(&self) -> Ref<'_, str> {
+        return Ref::map(self.name.borrow(), |name: &String| name.as_str());
+    }
+
     /// Returns the size of the NetCDF-3 dimension.
+    #[inline]
     pub fn size(&self) -> usize {
         return self.size.size();
     }
@@ -226,10 +236,31 @@ impl Dimension {
         return self.dim_type() == DimensionType::FixedSize;
     }
 
-    pub(in crate::data_set) fn check_dim_name(dim_name: &str) -> Result<(), InvalidDataSet> {
-        return match is_valid_name(dim_name) {
-            true => Ok(()),
-            false => Err(InvalidDataSet::DimensionNameNotValid(dim_name.to_string())),
+    pub(in crate::data_set) fn check_dim_name(dim_name: &str, name_validation: NameValidation, allow_long_names: bool) -> Result<(), InvalidDataSet> {
+        return check_name(dim_name, name_validation, allow_long_names)
+            .map_err(|reason: NameError| InvalidDataSet::DimensionNameNotValid{dim_name: dim_name.to_string(), reason: reason});
+    }
+}
+
+/// Formats the dimension as in CDL, the current size of the *unlimited-size* dimension being added.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::DataSet;
+///
+/// let mut data_set = DataSet::new();
+/// data_set.set_unlimited_dim("time", 24).unwrap();
+/// data_set.add_fixed_dim("latitude", 181).unwrap();
+///
+/// assert_eq!("time = UNLIMITED (24 currently)",   data_set.get_dim("time").unwrap().to_string());
+/// assert_eq!("latitude = 181",                    data_set.get_dim("latitude").unwrap().to_string());
+/// ```
+impl core::fmt::Display for Dimension {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        return match &self.size {
+            DimensionSize::Unlimited(size) => write!(f, "{} = UNLIMITED ({} currently)", self.borrow_name(), size.borrow()),
+            DimensionSize::Fixed(size) => write!(f, "{} = {}", self.borrow_name(), size),
         };
     }
 }
@@ -238,21 +269,22 @@ impl Dimension {
 #[cfg(test)]
 mod tests {
 
-    use std::rc::Rc;
-    use crate::{Dimension, DimensionType};
+    use alloc::rc::Rc;
+    use crate::{Dimension, DimensionType, NameValidation};
 
     #[test]
     fn test_dim_new_fixed_size() {
         const DIM_NAME: &str = "dim_1";
         const DIM_SIZE: usize = 10;
 
-        let dim = Dimension::new_fixed_size(DIM_NAME, DIM_SIZE).unwrap();
+        let dim = Dimension::new_fixed_size(DIM_NAME, DIM_SIZE, NameValidation::Strict, false).unwrap();
 
         assert_eq!(DIM_NAME, dim.name());
         assert_eq!(DIM_SIZE, dim.size());
         assert_eq!(DimensionType::FixedSize, dim.dim_type());
         assert_eq!(true, dim.is_fixed());
-        assert_eq!(false, dim.is_unlimited())
+        assert_eq!(false, dim.is_unlimited());
+        assert_eq!("dim_1 = 10", dim.to_string());
     }
 
     #[test]
@@ -260,13 +292,14 @@ mod tests {
         const DIM_NAME: &str = "dim_1";
         const DIM_SIZE: usize = 10;
 
-        let dim = Dimension::new_unlimited_size(DIM_NAME, DIM_SIZE).unwrap();
+        let dim = Dimension::new_unlimited_size(DIM_NAME, DIM_SIZE, NameValidation::Strict, false).unwrap();
 
         assert_eq!(DIM_NAME, dim.name());
         assert_eq!(DIM_SIZE, dim.size());
         assert_eq!(DimensionType::UnlimitedSize, dim.dim_type());
         assert_eq!(false, dim.is_fixed());
         assert_eq!(true, dim.is_unlimited());
+        assert_eq!("dim_1 = UNLIMITED (10 currently)", dim.to_string());
     }
 
     #[test]
@@ -274,50 +307,50 @@ mod tests {
 
         // test equality between 2 fixed-size dimension
         {
-            let dim_a: Dimension = Dimension::new_fixed_size("name_1", 180).unwrap();
-            let dim_b: Dimension = Dimension::new_fixed_size("name_1", 180).unwrap();
+            let dim_a: Dimension = Dimension::new_fixed_size("name_1", 180, NameValidation::Strict, false).unwrap();
+            let dim_b: Dimension = Dimension::new_fixed_size("name_1", 180, NameValidation::Strict, false).unwrap();
             assert_eq!(dim_a, dim_b);
         }
 
         // test equality between 2 fixed-size dimension with different sizes
         {
-            let dim_a: Dimension = Dimension::new_fixed_size("name_1", 90).unwrap();
-            let dim_b: Dimension = Dimension::new_fixed_size("name_1", 180).unwrap();
+            let dim_a: Dimension = Dimension::new_fixed_size("name_1", 90, NameValidation::Strict, false).unwrap();
+            let dim_b: Dimension = Dimension::new_fixed_size("name_1", 180, NameValidation::Strict, false).unwrap();
             assert_ne!(dim_a, dim_b);
         }
 
         // test equality between 2 fixed-size dimension with different names
         {
-            let dim_a: Dimension = Dimension::new_fixed_size("name_1", 180).unwrap();
-            let dim_b: Dimension = Dimension::new_fixed_size("name_2", 180).unwrap();
+            let dim_a: Dimension = Dimension::new_fixed_size("name_1", 180, NameValidation::Strict, false).unwrap();
+            let dim_b: Dimension = Dimension::new_fixed_size("name_2", 180, NameValidation::Strict, false).unwrap();
             assert_ne!(dim_a, dim_b);
         }
 
         // test equality between 2 unlimited-size dimension
         {
-            let dim_a: Dimension = Dimension::new_unlimited_size("name_1", 180).unwrap();
-            let dim_b: Dimension = Dimension::new_unlimited_size("name_1", 180).unwrap();
+            let dim_a: Dimension = Dimension::new_unlimited_size("name_1", 180, NameValidation::Strict, false).unwrap();
+            let dim_b: Dimension = Dimension::new_unlimited_size("name_1", 180, NameValidation::Strict, false).unwrap();
             assert_eq!(dim_a, dim_b);
         }
 
         // test equality between 2 unlimited-size dimension with different sizes
         {
-            let dim_a: Dimension = Dimension::new_unlimited_size("name_1", 90).unwrap();
-            let dim_b: Dimension = Dimension::new_unlimited_size("name_1", 180).unwrap();
+            let dim_a: Dimension = Dimension::new_unlimited_size("name_1", 90, NameValidation::Strict, false).unwrap();
+            let dim_b: Dimension = Dimension::new_unlimited_size("name_1", 180, NameValidation::Strict, false).unwrap();
             assert_ne!(dim_a, dim_b);
         }
 
         // test equality between 2 unlimited-size dimension with different names
         {
-            let dim_a: Dimension = Dimension::new_unlimited_size("name_1", 180).unwrap();
-            let dim_b: Dimension = Dimension::new_unlimited_size("name_2", 180).unwrap();
+            let dim_a: Dimension = Dimension::new_unlimited_size("name_1", 180, NameValidation::Strict, false).unwrap();
+            let dim_b: Dimension = Dimension::new_unlimited_size("name_2", 180, NameValidation::Strict, false).unwrap();
             assert_ne!(dim_a, dim_b);
         }
 
         // test equality between 1 unlimited-size dimension and 1 fixed-size dimension
         {
-            let dim_a: Dimension = Dimension::new_fixed_size("name_1", 180).unwrap();
-            let dim_b: Dimension = Dimension::new_unlimited_size("name_1", 180).unwrap();
+            let dim_a: Dimension = Dimension::new_fixed_size("name_1", 180, NameValidation::Strict, false).unwrap();
+            let dim_b: Dimension = Dimension::new_unlimited_size("name_1", 180, NameValidation::Strict, false).unwrap();
             assert_ne!(dim_a, dim_b);
         }
     }
@@ -326,8 +359,8 @@ mod tests {
     fn test_rc_dim_equality() {
         // test equality between 2 fixed-size dimensions
         {
-            let dim_a: Rc<Dimension> = Rc::new(Dimension::new_fixed_size("name_1", 180).unwrap());
-            let dim_b: Rc<Dimension> = Rc::new(Dimension::new_fixed_size("name_1", 180).unwrap());
+            let dim_a: Rc<Dimension> = Rc::new(Dimension::new_fixed_size("name_1", 180, NameValidation::Strict, false).unwrap());
+            let dim_b: Rc<Dimension> = Rc::new(Dimension::new_fixed_size("name_1", 180, NameValidation::Strict, false).unwrap());
 
             assert_eq!(dim_a, dim_b);
             assert!(!Rc::ptr_eq(&dim_a, &dim_b));
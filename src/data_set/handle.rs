@@ -0,0 +1,170 @@
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use core::ops::Deref;
+use alloc::rc::Rc;
+
+use crate::{Attribute, DataSet, Dimension, Variable};
+
+/// Handle on a variable of a data set, returned by [`DataSet::var`](struct.DataSet.html#method.var).
+///
+/// The handle dereferences to the [`Variable`](struct.Variable.html), and keeps a reference to the data set
+/// to navigate to the dimensions of the variable. The references returned by its methods live as long as the data set,
+/// so the lookups can be chained.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::DataSet;
+///
+/// let mut data_set = DataSet::new();
+/// data_set.set_unlimited_dim("time", 2).unwrap();
+/// data_set.add_fixed_dim("latitude", 3).unwrap();
+/// data_set.add_var_f32("t2m", &["time", "latitude"]).unwrap();
+/// data_set.add_var_attr_string("t2m", "units", "K").unwrap();
+///
+/// fn units(data_set: &DataSet, var_name: &str) -> Option<String> {
+///     return data_set.var(var_name)?.attr("units")?.as_string();
+/// }
+/// assert_eq!(Some(String::from("K")),         units(&data_set, "t2m"));
+/// assert_eq!(None,                            units(&data_set, "undefined_var"));
+///
+/// let t2m = data_set.var("t2m").unwrap();
+/// assert_eq!(vec![2, 3],                      t2m.shape());
+/// assert_eq!(Some(3),                         t2m.dim("latitude").map(|dim| dim.size()));
+/// assert_eq!(None,                            t2m.attr("scale_factor"));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct VarRef<'a> {
+    data_set: &'a DataSet,
+    var: &'a Variable,
+}
+
+impl<'a> VarRef<'a> {
+    pub(in crate::data_set) fn new(data_set: &'a DataSet, var: &'a Variable) -> VarRef<'a> {
+        return VarRef {
+            data_set: data_set,
+            var: var,
+        };
+    }
+
+    /// Returns the data set containing the variable.
+    pub fn data_set(&self) -> &'a DataSet {
+        return self.data_set;
+    }
+
+    /// Returns the variable.
+    pub fn variable(&self) -> &'a Variable {
+        return self.var;
+    }
+
+    /// Returns the name of the variable.
+    pub fn name(&self) -> &'a str {
+        return self.var.name();
+    }
+
+    /// Returns the attribute of the variable, or `None`.
+    pub fn attr(&self, attr_name: &str) -> Option<&'a Attribute> {
+        return self.var.get_attr(attr_name);
+    }
+
+    /// Returns a handle on a dimension of the variable, or `None` if the variable is not defined over this dimension.
+    pub fn dim(&self, dim_name: &str) -> Option<DimRef<'a>> {
+        return self.var.dims().iter()
+            .find(|dim: &&Rc<Dimension>| *dim.borrow_name() == *dim_name)
+            .map(|dim: &'a Rc<Dimension>| DimRef::new(self.data_set, dim));
+    }
+
+    /// Returns the handles on the dimensions of the variable.
+    pub fn dim_refs(&self) -> Vec<DimRef<'a>> {
+        return self.var.dims().iter()
+            .map(|dim: &'a Rc<Dimension>| DimRef::new(self.data_set, dim))
+            .collect();
+    }
+
+    /// Returns the sizes of the dimensions of the variable.
+    pub fn shape(&self) -> Vec<usize> {
+        return self.var.dims().iter()
+            .map(|dim: &Rc<Dimension>| dim.size())
+            .collect();
+    }
+}
+
+impl<'a> Deref for VarRef<'a> {
+    type Target = Variable;
+
+    fn deref(&self) -> &Variable {
+        return self.var;
+    }
+}
+
+/// Handle on a dimension of a data set, returned by [`DataSet::dim`](struct.DataSet.html#method.dim)
+/// and [`VarRef::dim`](struct.VarRef.html#method.dim).
+///
+/// The handle dereferences to the [`Dimension`](struct.Dimension.html), and keeps a reference to the data set
+/// to navigate to the variables defined over the dimension.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::DataSet;
+///
+/// let mut data_set = DataSet::new();
+/// data_set.set_unlimited_dim("time", 2).unwrap();
+/// data_set.add_var_f64("time", &["time"]).unwrap();
+/// data_set.add_var_f32("t2m", &["time"]).unwrap();
+///
+/// let time = data_set.dim("time").unwrap();
+/// assert_eq!(2,                               time.size());
+/// assert_eq!(true,                            time.is_unlimited());
+/// assert_eq!(Some("time"),                    time.coord_var().map(|var| var.name()));
+/// assert_eq!(vec!["time", "t2m"],             time.vars().iter().map(|var| var.name()).collect::<Vec<&str>>());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct DimRef<'a> {
+    data_set: &'a DataSet,
+    dim: &'a Rc<Dimension>,
+}
+
+impl<'a> DimRef<'a> {
+    pub(in crate::data_set) fn new(data_set: &'a DataSet, dim: &'a Rc<Dimension>) -> DimRef<'a> {
+        return DimRef {
+            data_set: data_set,
+            dim: dim,
+        };
+    }
+
+    /// Returns the data set containing the dimension.
+    pub fn data_set(&self) -> &'a DataSet {
+        return self.data_set;
+    }
+
+    /// Returns the dimension.
+    pub fn dimension(&self) -> &'a Rc<Dimension> {
+        return self.dim;
+    }
+
+    /// Returns the handles on the variables defined over the dimension.
+    pub fn vars(&self) -> Vec<VarRef<'a>> {
+        return self.data_set.vars().iter()
+            .filter(|var: &&Variable| var.dims().iter().any(|dim: &Rc<Dimension>| Rc::ptr_eq(dim, self.dim)))
+            .map(|var: &'a Variable| VarRef::new(self.data_set, var))
+            .collect();
+    }
+
+    /// Returns the handle on the coordinate variable (the 1D variable having the same name as the dimension), or `None`.
+    pub fn coord_var(&self) -> Option<VarRef<'a>> {
+        let var: &'a Variable = self.data_set.get_var(&self.dim.borrow_name())?;
+        if var.num_dims() != 1 || !Rc::ptr_eq(&var.dims()[0], self.dim) {
+            return None;
+        }
+        return Some(VarRef::new(self.data_set, var));
+    }
+}
+
+impl<'a> Deref for DimRef<'a> {
+    type Target = Dimension;
+
+    fn deref(&self) -> &Dimension {
+        return self.dim;
+    }
+}
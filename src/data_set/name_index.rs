@@ -0,0 +1,93 @@
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+#[cfg(not(feature = "std"))]
+use alloc::collections::BTreeMap as Map;
+#[cfg(feature = "std")]
+use std::collections::HashMap as Map;
+
+/// Index of the positions of named items (dimensions, variables or attributes) stored in a `Vec`.
+///
+/// The `Vec` keeps the insertion order used to write the header, the index only speeds up the lookups by name.
+/// If several items share the same name, the first one is indexed.
+#[derive(Clone, Default)]
+pub(crate) struct NameIndex {
+    positions: Map<String, usize>,
+    has_duplicates: bool,
+}
+
+impl NameIndex {
+
+    /// Creates an empty index.
+    pub(crate) fn new() -> NameIndex {
+        return NameIndex::default();
+    }
+
+    /// Returns the position of the first item named `name`.
+    pub(crate) fn get(&self, name: &str) -> Option<usize> {
+        return self.positions.get(name).copied();
+    }
+
+    /// Registers the item appended at `position`, a name already indexed keeps its first position.
+    pub(crate) fn push(&mut self, name: &str, position: usize) {
+        if self.positions.contains_key(name) {
+            self.has_duplicates = true;
+        }
+        else {
+            self.positions.insert(name.to_string(), position);
+        }
+    }
+
+    /// Registers the renaming of the item at `position`.
+    ///
+    /// All the names of the items are required to rebuild the index when duplicate names exist.
+    pub(crate) fn rename<'a, I: Iterator<Item = &'a str>>(&mut self, old_name: &str, new_name: &str, position: usize, names: I) {
+        if self.has_duplicates {
+            self.rebuild(names);
+        }
+        else {
+            self.positions.remove(old_name);
+            self.positions.insert(new_name.to_string(), position);
+        }
+    }
+
+    /// Rebuilds the index from the names of all the items, after a removal for example.
+    pub(crate) fn rebuild<'a, I: Iterator<Item = &'a str>>(&mut self, names: I) {
+        self.positions.clear();
+        self.has_duplicates = false;
+        for (position, name) in names.enumerate() {
+            self.push(name, position);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::NameIndex;
+
+    #[test]
+    fn test_name_index() {
+        let mut index = NameIndex::new();
+        index.push("a", 0);
+        index.push("b", 1);
+        assert_eq!(Some(0),                     index.get("a"));
+        assert_eq!(Some(1),                     index.get("b"));
+        assert_eq!(None,                        index.get("c"));
+
+        index.rename("b", "c", 1, ["a", "c"].iter().copied());
+        assert_eq!(None,                        index.get("b"));
+        assert_eq!(Some(1),                     index.get("c"));
+
+        // The first item wins, the duplicate is indexed once the first item is renamed
+        index.push("a", 2);
+        assert_eq!(Some(0),                     index.get("a"));
+        index.rename("a", "d", 0, ["d", "c", "a"].iter().copied());
+        assert_eq!(Some(0),                     index.get("d"));
+        assert_eq!(Some(2),                     index.get("a"));
+
+        index.rebuild(["c", "a"].iter().copied());
+        assert_eq!(None,                        index.get("d"));
+        assert_eq!(Some(0),                     index.get("c"));
+        assert_eq!(Some(1),                     index.get("a"));
+    }
+}
@@ -3,3 +3,4 @@ mod tests_variable;
 mod tests_var_attribute;
 mod tests_global_attribute;
 mod tests_dimension;
+mod tests_navigation;
@@ -1,7 +1,7 @@
 #![cfg(test)]
 use std::rc::Rc;
 
-use crate::{DataSet, Dimension, DimensionType, InvalidDataSet};
+use crate::{DataSet, DimId, Dimension, DimensionType, InvalidDataSet, NameError};
 
 #[test]
 fn test_add_fixed_size_dims() {
@@ -647,7 +647,7 @@ fn test_add_fixed_size_dim_error_dim_name_not_valid()
 
     // Try to add a fixed-size dimension with an invalid name
     assert_eq!(
-        InvalidDataSet::DimensionNameNotValid(INVALID_DIM_NAME.to_string()),
+        InvalidDataSet::DimensionNameNotValid{dim_name: INVALID_DIM_NAME.to_string(), reason: NameError::FirstCharNotAllowed{chr: '!'}},
         data_set.add_fixed_dim(INVALID_DIM_NAME, DIM_SIZE).unwrap_err()
     );
 
@@ -679,7 +679,7 @@ fn test_add_unlimited_size_dim_error_dim_name_not_valid()
 
     // Try to add a fixed-size dimension with an invalid name
     assert_eq!(
-        InvalidDataSet::DimensionNameNotValid(INVALID_DIM_NAME.to_string()),
+        InvalidDataSet::DimensionNameNotValid{dim_name: INVALID_DIM_NAME.to_string(), reason: NameError::FirstCharNotAllowed{chr: '!'}},
         data_set.set_unlimited_dim(INVALID_DIM_NAME, DIM_SIZE).unwrap_err()
     );
 
@@ -755,4 +755,60 @@ fn test_rc_dim_equality() {
     assert!(Rc::ptr_eq(&dim_a_1, &dim_a_2));
     assert!(Rc::ptr_eq(&dim_b_1, &dim_b_2));
     assert!(!Rc::ptr_eq(&dim_a_1, &dim_b_2));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_dim_ids() {
+    const UNLIM_DIM_NAME: &str = "time";
+    const FIXED_DIM_NAME: &str = "latitude";
+    const RENAMED_DIM_NAME: &str = "lat";
+
+    let mut data_set = DataSet::new();
+    let unlim_dim_id: DimId = data_set.set_unlimited_dim(UNLIM_DIM_NAME, 2).unwrap();
+    let fixed_dim_id: DimId = data_set.add_fixed_dim(FIXED_DIM_NAME, 3).unwrap();
+
+    assert_eq!(0,                               unlim_dim_id.index());
+    assert_eq!(1,                               fixed_dim_id.index());
+    assert_eq!(Some(unlim_dim_id),              data_set.dim_id(UNLIM_DIM_NAME));
+    assert_eq!(Some(fixed_dim_id),              data_set.dim_id(FIXED_DIM_NAME));
+    assert_eq!(None,                            data_set.dim_id("undef_dim"));
+
+    // the ids stay valid after a renaming
+    data_set.rename_dim(FIXED_DIM_NAME, RENAMED_DIM_NAME).unwrap();
+    assert_eq!(Some(fixed_dim_id),              data_set.dim_id(RENAMED_DIM_NAME));
+    data_set.add_var_f32("var_1", &[unlim_dim_id, fixed_dim_id]).unwrap();
+    assert_eq!(Some(vec![0, 1]),                data_set.get_var_dim_ids("var_1"));
+
+    // the names are still accepted
+    data_set.add_var_f32("var_2", &[UNLIM_DIM_NAME, RENAMED_DIM_NAME]).unwrap();
+    assert_eq!(Some(vec![0, 1]),                data_set.get_var_dim_ids("var_2"));
+}
+
+#[test]
+fn test_dim_ids_error_removed_dim() {
+    let mut data_set = DataSet::new();
+    let dim_id_1: DimId = data_set.add_fixed_dim("dim_1", 1).unwrap();
+    let dim_id_2: DimId = data_set.add_fixed_dim("dim_2", 2).unwrap();
+
+    data_set.remove_dim("dim_1").unwrap();
+    assert_eq!(
+        InvalidDataSet::DimensionsNotDefined{
+            var_name: String::from("var_1"),
+            undef_dim_names: vec![String::from("<dimension #1>")],
+        },
+        data_set.add_var_i32("var_1", &[dim_id_2]).unwrap_err()
+    );
+    assert_eq!(
+        InvalidDataSet::DimensionsNotDefined{
+            var_name: String::from("var_1"),
+            undef_dim_names: vec![String::from("<dimension #0>")],
+        },
+        data_set.add_var_i32("var_1", &[dim_id_1]).unwrap_err()
+    );
+
+    // the id returned after the removal is valid
+    let dim_id_2: DimId = data_set.dim_id("dim_2").unwrap();
+    assert_eq!(0,                               dim_id_2.index());
+    data_set.add_var_i32("var_1", &[dim_id_2]).unwrap();
+    assert_eq!(Some(vec![2]),                   data_set.var("var_1").map(|var| var.shape()));
+}
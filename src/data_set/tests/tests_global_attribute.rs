@@ -1,7 +1,7 @@
 #![cfg(test)]
 
 #![cfg(test)]
-use crate::{DataSet, DataType, InvalidDataSet};
+use crate::{DataSet, DataType, InvalidDataSet, NameError};
 
 #[test]
 fn test_add_global_attr_i8() {
@@ -315,7 +315,7 @@ fn test_add_global_attr_error_attr_name_not_valid() {
     assert_eq!(None,    data_set.get_global_attr_i8(INVALID_GLOBAL_ATTR_NAME));
 
     assert_eq!(
-        InvalidDataSet::GlobalAttributeNameNotValid(INVALID_GLOBAL_ATTR_NAME.to_string()),
+        InvalidDataSet::GlobalAttributeNameNotValid{attr_name: INVALID_GLOBAL_ATTR_NAME.to_string(), reason: NameError::FirstCharNotAllowed{chr: '!'}},
         data_set.add_global_attr_i8(INVALID_GLOBAL_ATTR_NAME, GLOBAL_ATTR_DATA.to_vec()).unwrap_err()
     );
 
@@ -474,4 +474,70 @@ fn test_remove_var_attr_error_attr_not_defined () {
     assert_eq!(None,    data_set.get_global_attr_data_type(UNDEF_GLOBAL_ATTR_NAME));
     assert_eq!(None,    data_set.get_global_attr_i8(UNDEF_GLOBAL_ATTR_NAME));
     assert_eq!(None,    data_set.get_global_attr_i8(UNDEF_GLOBAL_ATTR_NAME));
-}
\ No newline at end of file
+}
+#[cfg(feature = "std")]
+#[test]
+fn test_append_history_at() {
+    use std::time::{Duration, SystemTime};
+    const HISTORY_ATTR_NAME: &str = "history";
+
+    let mut data_set = DataSet::new();
+    data_set.add_global_attr_string("title", "test").unwrap();
+    data_set.add_global_attr_string("source", "model").unwrap();
+
+    // Creation of the attribute
+    data_set.append_history_at("first", SystemTime::UNIX_EPOCH).unwrap();
+    assert_eq!(3,                                                       data_set.num_global_attrs());
+    assert_eq!(Some(String::from("Thu Jan  1 00:00:00 1970: first")),   data_set.get_global_attr_as_string(HISTORY_ATTR_NAME));
+
+    // The new lines are prepended and the attribute keeps its position
+    data_set.add_global_attr_string("institution", "lab").unwrap();
+    data_set.append_history_at("second", SystemTime::UNIX_EPOCH + Duration::from_secs(951_827_696)).unwrap();
+    assert_eq!(
+        Some(String::from("Tue Feb 29 12:34:56 2000: second\nThu Jan  1 00:00:00 1970: first")),
+        data_set.get_global_attr_as_string(HISTORY_ATTR_NAME)
+    );
+    assert_eq!(
+        vec!["title", "source", "history", "institution"],
+        data_set.get_global_attrs().iter().map(|attr: &&crate::Attribute| attr.name()).collect::<Vec<&str>>()
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_append_history_error_mismatch_data_type() {
+    const HISTORY_ATTR_NAME: &str = "history";
+
+    let mut data_set = DataSet::new();
+    data_set.add_global_attr_f32(HISTORY_ATTR_NAME, vec![1.0]).unwrap();
+
+    assert_eq!(
+        InvalidDataSet::GlobalAttributeMismatchDataType{
+            attr_name: HISTORY_ATTR_NAME.to_string(),
+            req: DataType::U8,
+            get: DataType::F32,
+        },
+        data_set.append_history("edited").unwrap_err()
+    );
+    assert_eq!(Some(vec![1.0]),     data_set.get_global_attr_f32(HISTORY_ATTR_NAME).map(|data: &[f32]| data.to_vec()));
+}
+
+#[test]
+fn test_set_conventions_title_institution_source() {
+    let mut data_set = DataSet::new();
+    data_set.add_global_attr_i32("title", vec![1]).unwrap();
+    data_set.add_global_attr_string("comment", "test").unwrap();
+
+    data_set.set_title("Example").unwrap();
+    data_set.set_source("Model").unwrap();
+    data_set.set_institution("Lab").unwrap();
+    data_set.set_conventions("CF-1.8").unwrap();
+    data_set.set_source("Observations").unwrap();
+
+    assert_eq!(vec!["title", "comment", "source", "institution", "Conventions"],   data_set.get_global_attr_names());
+    assert_eq!(Some(DataType::U8),                  data_set.get_global_attr_data_type("title"));
+    assert_eq!(Some(String::from("Example")),       data_set.get_global_attr_as_string("title"));
+    assert_eq!(Some(String::from("Observations")),  data_set.get_global_attr_as_string("source"));
+    assert_eq!(Some(String::from("Lab")),           data_set.get_global_attr_as_string("institution"));
+    assert_eq!(Some(String::from("CF-1.8")),        data_set.get_global_attr_as_string("Conventions"));
+}
@@ -0,0 +1,114 @@
+use crate::{DataSet, DataType, DimRef, VarRef};
+
+fn init_data_set() -> DataSet {
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim("time", 2).unwrap();
+    data_set.add_fixed_dim("latitude", 3).unwrap();
+    data_set.add_fixed_dim("longitude", 4).unwrap();
+    data_set.add_var_f64("time", &["time"]).unwrap();
+    data_set.add_var_f32("latitude", &["longitude"]).unwrap();
+    data_set.add_var_i16("t2m", &["time", "latitude", "longitude"]).unwrap();
+    data_set.add_var_attr_string("t2m", "units", "K").unwrap();
+    data_set.add_var_attr_f32("t2m", "scale_factor", vec![0.01]).unwrap();
+    data_set.add_global_attr_string("title", "Example").unwrap();
+    return data_set;
+}
+
+#[test]
+fn test_var_ref() {
+    let data_set: DataSet = init_data_set();
+
+    assert!(data_set.var("undefined_var").is_none());
+
+    let t2m: VarRef = data_set.var("t2m").unwrap();
+    assert_eq!("t2m",                           t2m.name());
+    assert_eq!(DataType::I16,                   t2m.data_type());
+    assert_eq!(true,                            t2m.is_record_var());
+    assert_eq!(vec![2, 3, 4],                   t2m.shape());
+    assert_eq!(24,                              t2m.len());
+    assert!(core::ptr::eq(&data_set,            t2m.data_set()));
+    assert!(core::ptr::eq(data_set.get_var("t2m").unwrap(), t2m.variable()));
+
+    // attributes
+    assert_eq!(Some(String::from("K")),         t2m.attr("units").and_then(|attr| attr.as_string()));
+    assert_eq!(Some(0.01_f32 as f64),           t2m.attr("scale_factor").and_then(|attr| attr.first_as_f64()));
+    assert_eq!(None,                            t2m.attr("add_offset"));
+
+    // dimensions
+    assert_eq!(vec!["time", "latitude", "longitude"],
+               t2m.dim_refs().iter().map(|dim: &DimRef| dim.name()).collect::<Vec<String>>());
+    assert_eq!(Some(4),                         t2m.dim("longitude").map(|dim: DimRef| dim.size()));
+    assert!(data_set.var("time").unwrap().dim("latitude").is_none());
+}
+
+#[test]
+fn test_dim_ref() {
+    let data_set: DataSet = init_data_set();
+
+    assert!(data_set.dim("undefined_dim").is_none());
+
+    // unlimited dimension and its coordinate variable
+    let time: DimRef = data_set.dim("time").unwrap();
+    assert_eq!("time",                          time.name());
+    assert_eq!(2,                               time.size());
+    assert_eq!(true,                            time.is_unlimited());
+    assert!(core::ptr::eq(&data_set,            time.data_set()));
+    assert_eq!(Some("time"),                    time.coord_var().map(|var: VarRef| var.name()));
+    assert_eq!(vec!["time", "t2m"],             time.vars().iter().map(|var: &VarRef| var.name()).collect::<Vec<&str>>());
+
+    // the variable `latitude` is not defined over the dimension `latitude`
+    let latitude: DimRef = data_set.dim("latitude").unwrap();
+    assert_eq!(3,                               latitude.size());
+    assert_eq!(true,                            latitude.is_fixed());
+    assert!(latitude.coord_var().is_none());
+    assert_eq!(vec!["t2m"],                     latitude.vars().iter().map(|var: &VarRef| var.name()).collect::<Vec<&str>>());
+
+    let longitude: DimRef = data_set.dim("longitude").unwrap();
+    assert_eq!(vec!["latitude", "t2m"],         longitude.vars().iter().map(|var: &VarRef| var.name()).collect::<Vec<&str>>());
+}
+
+#[test]
+fn test_global_attr() {
+    let data_set: DataSet = init_data_set();
+
+    assert_eq!(Some(String::from("Example")),   data_set.attr("title").and_then(|attr| attr.as_string()));
+    assert_eq!(None,                            data_set.attr("history"));
+}
+
+#[test]
+fn test_coordinate_names() {
+    let data_set: DataSet = init_data_set();
+
+    // the variable `latitude` is not defined over the dimension `latitude`
+    assert_eq!(
+        vec![Some(String::from("time")), None, None],
+        data_set.get_var("t2m").unwrap().coordinate_names(&data_set)
+    );
+    assert_eq!(vec![Some(String::from("time"))],           data_set.get_var("time").unwrap().coordinate_names(&data_set));
+    assert_eq!(vec![None],                                  data_set.get_var("latitude").unwrap().coordinate_names(&data_set));
+
+    // scalar variable
+    let mut data_set: DataSet = init_data_set();
+    data_set.add_var_i32("scalar", &[] as &[&str]).unwrap();
+    assert_eq!(Vec::<Option<String>>::new(),               data_set.get_var("scalar").unwrap().coordinate_names(&data_set));
+}
+
+#[test]
+fn test_summary() {
+    assert_eq!("0 dimensions, 0 variables, 0 global attributes\n",     DataSet::new().summary());
+
+    let mut data_set: DataSet = init_data_set();
+    data_set.add_var_i32("scalar", &[] as &[&str]).unwrap();
+    data_set.add_global_attr_string("history", "x".repeat(100)).unwrap();
+    let summary: String = data_set.summary();
+    let lines: Vec<&str> = summary.lines().collect();
+    assert_eq!("3 dimensions, 4 variables, 2 global attributes",       lines[0]);
+    assert_eq!("    time = UNLIMITED (2 currently)",                    lines[2]);
+    assert_eq!("    short t2m(time, latitude, longitude)  // shape [2, 3, 4]",  lines[8]);
+    assert_eq!("    int scalar  // shape []",                           lines[9]);
+    assert_eq!("global attributes:",                                    lines[10]);
+    assert_eq!("    title = \"Example\"",                               lines[11]);
+    // the long values are truncated
+    assert_eq!(String::from("    history = \"") + &"x".repeat(66) + "...",  lines[12]);
+    assert_eq!(13,                                                      lines.len());
+}
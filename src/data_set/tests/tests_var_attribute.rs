@@ -1,5 +1,5 @@
 #![cfg(test)]
-use crate::{DataSet, DataType, InvalidDataSet};
+use crate::{Attribute, AttrValue, DataSet, DataSlice, DataType, InvalidDataSet, NameError};
 
 #[test]
 fn test_add_var_attr_i8() {
@@ -319,7 +319,8 @@ fn test_add_var_attr_error_attr_name_not_valid() {
     assert_eq!(
         InvalidDataSet::VariableAttributeNameNotValid{
             var_name: VAR_NAME.to_string(),
-            attr_name: INVALID_VAR_ATTR_NAME.to_string()
+            attr_name: INVALID_VAR_ATTR_NAME.to_string(),
+            reason: NameError::FirstCharNotAllowed{chr: '!'},
         },
         data_set.add_var_attr_i8(VAR_NAME, INVALID_VAR_ATTR_NAME, VAR_ATTR_DATA.to_vec()).unwrap_err()
     );
@@ -456,4 +457,132 @@ fn test_remove_var_attr_error_attr_not_defined () {
     );
 
     assert_eq!(Some(0), data_set.num_var_attrs(VAR_NAME));
-}
\ No newline at end of file
+}
+#[test]
+fn test_add_var_attr_error_mismatch_data_type() {
+    const VAR_NAME: &str = "var_1";
+
+    let mut data_set: DataSet = DataSet::new();
+    data_set.add_var_f32::<&str>(VAR_NAME, &[]).unwrap();
+
+    assert_eq!(
+        InvalidDataSet::VariableAttributeMismatchDataType{
+            var_name: VAR_NAME.to_string(),
+            attr_name: String::from("_FillValue"),
+            req: DataType::F32,
+            get: DataType::F64,
+        },
+        data_set.add_var_attr_f64(VAR_NAME, "_FillValue", vec![-999.0]).unwrap_err()
+    );
+    assert_eq!(
+        InvalidDataSet::VariableAttributeMismatchDataType{
+            var_name: VAR_NAME.to_string(),
+            attr_name: String::from("missing_value"),
+            req: DataType::F32,
+            get: DataType::I16,
+        },
+        data_set.add_var_attr_i16(VAR_NAME, "missing_value", vec![-1]).unwrap_err()
+    );
+    assert_eq!(Some(0),     data_set.num_var_attrs(VAR_NAME));
+
+    // The data type of the other attributes is not checked
+    data_set.add_var_attr_f32(VAR_NAME, "_FillValue", vec![-999.0]).unwrap();
+    data_set.add_var_attr_f32(VAR_NAME, "missing_value", vec![-1.0]).unwrap();
+    data_set.add_var_attr_f64(VAR_NAME, "valid_max", vec![100.0]).unwrap();
+    assert_eq!(Some(3),     data_set.num_var_attrs(VAR_NAME));
+    assert_eq!(Ok(()),      data_set.check_attr_data_types());
+}
+
+#[test]
+fn test_rename_var_attr_error_mismatch_data_type() {
+    const VAR_NAME: &str = "var_1";
+    const VAR_ATTR_NAME: &str = "attr_1";
+
+    let mut data_set: DataSet = DataSet::new();
+    data_set.add_var_i16::<&str>(VAR_NAME, &[]).unwrap();
+    data_set.add_var_attr_i32(VAR_NAME, VAR_ATTR_NAME, vec![-1]).unwrap();
+
+    assert_eq!(
+        InvalidDataSet::VariableAttributeMismatchDataType{
+            var_name: VAR_NAME.to_string(),
+            attr_name: String::from("_FillValue"),
+            req: DataType::I16,
+            get: DataType::I32,
+        },
+        data_set.rename_var_attr(VAR_NAME, VAR_ATTR_NAME, "_FillValue").unwrap_err()
+    );
+    // The attribute has not been renamed
+    assert_eq!(Some(true),  data_set.has_var_attr(VAR_NAME, VAR_ATTR_NAME));
+    assert_eq!(Some(false), data_set.has_var_attr(VAR_NAME, "_FillValue"));
+}
+
+#[test]
+fn test_add_var_attrs() {
+    const VAR_NAME: &str = "var_1";
+
+    let mut data_set: DataSet = DataSet::new();
+    data_set.add_var_i16::<&str>(VAR_NAME, &[]).unwrap();
+
+    data_set.add_var_attrs(VAR_NAME, &[
+        ("attr_i8", AttrValue::I8(-1)),
+        ("attr_u8", AttrValue::U8(2)),
+        ("attr_i16", AttrValue::I16(-3)),
+        ("attr_i32", AttrValue::I32(4)),
+        ("attr_f32", AttrValue::F32(5.0)),
+        ("attr_f64", AttrValue::F64(6.0)),
+        ("attr_str", AttrValue::Str("text")),
+        ("attr_slice", AttrValue::Slice(DataSlice::I16(&[7, 8]))),
+    ]).unwrap();
+
+    assert_eq!(Some(8),                                 data_set.num_var_attrs(VAR_NAME));
+    assert_eq!(Some(&[-1][..]),                         data_set.get_var_attr_i8(VAR_NAME, "attr_i8"));
+    assert_eq!(Some(&[2][..]),                          data_set.get_var_attr_u8(VAR_NAME, "attr_u8"));
+    assert_eq!(Some(&[-3][..]),                         data_set.get_var_attr_i16(VAR_NAME, "attr_i16"));
+    assert_eq!(Some(&[4][..]),                          data_set.get_var_attr_i32(VAR_NAME, "attr_i32"));
+    assert_eq!(Some(&[5.0][..]),                        data_set.get_var_attr_f32(VAR_NAME, "attr_f32"));
+    assert_eq!(Some(&[6.0][..]),                        data_set.get_var_attr_f64(VAR_NAME, "attr_f64"));
+    assert_eq!(Some(String::from("text")),              data_set.get_var_attr_as_string(VAR_NAME, "attr_str"));
+    assert_eq!(Some(&[7, 8][..]),                       data_set.get_var_attr_i16(VAR_NAME, "attr_slice"));
+    // the attribute names are kept in the given order
+    assert_eq!(
+        vec!["attr_i8", "attr_u8", "attr_i16", "attr_i32", "attr_f32", "attr_f64", "attr_str", "attr_slice"],
+        data_set.get_var_attrs(VAR_NAME).unwrap().iter().map(|attr: &&Attribute| attr.name()).collect::<Vec<&str>>()
+    );
+}
+
+#[test]
+fn test_add_var_attrs_error() {
+    const VAR_NAME: &str = "var_1";
+
+    let mut data_set: DataSet = DataSet::new();
+    data_set.add_var_i16::<&str>(VAR_NAME, &[]).unwrap();
+
+    // undefined variable
+    assert_eq!(
+        InvalidDataSet::VariableNotDefined(String::from("undef_var")),
+        data_set.add_var_attrs("undef_var", &[("units", AttrValue::Str("K"))]).unwrap_err()
+    );
+
+    // none of the attributes is added when one of them is not valid
+    assert_eq!(
+        InvalidDataSet::VariableAttributeMismatchDataType{
+            var_name: VAR_NAME.to_string(),
+            attr_name: String::from("_FillValue"),
+            req: DataType::I16,
+            get: DataType::I32,
+        },
+        data_set.add_var_attrs(VAR_NAME, &[
+            ("units", AttrValue::Str("K")),
+            ("scale_factor", AttrValue::F32(0.01)),
+            ("_FillValue", AttrValue::I32(-1)),
+        ]).unwrap_err()
+    );
+    assert_eq!(Some(0),                                 data_set.num_var_attrs(VAR_NAME));
+
+    // same name given twice
+    assert_eq!(
+        InvalidDataSet::VariableAttributeAlreadyExists{var_name: VAR_NAME.to_string(), attr_name: String::from("units")},
+        data_set.add_var_attrs(VAR_NAME, &[("units", AttrValue::Str("K")), ("units", AttrValue::Str("Celsius"))]).unwrap_err()
+    );
+    assert_eq!(Some(0),                                 data_set.num_var_attrs(VAR_NAME));
+}
@@ -1,4 +1,4 @@
-use crate::{DataSet, InvalidDataSet, DataType, DimensionType};
+use crate::{DataSet, InvalidDataSet, DataType, DimensionType, NameError, Version};
 
 #[test]
 fn test_add_var_error_invalid_name() {
@@ -12,7 +12,7 @@ fn test_add_var_error_invalid_name() {
     assert_eq!(None,    data_set.var_data_type(INVALID_VAR_NAME));
 
     assert_eq!(
-        InvalidDataSet::VariableNameNotValid(INVALID_VAR_NAME.to_string()),
+        InvalidDataSet::VariableNameNotValid{var_name: INVALID_VAR_NAME.to_string(), reason: NameError::FirstCharNotAllowed{chr: '!'}},
         data_set.add_var_i8::<&str>(INVALID_VAR_NAME, &vec![]).unwrap_err()
     );
 
@@ -386,7 +386,7 @@ fn test_rename_var_error_invalid_name() {
     assert_eq!(None,                data_set.var_data_type(INVALID_VAR_NAME));
 
     assert_eq!(
-        InvalidDataSet::VariableNameNotValid(INVALID_VAR_NAME.to_string()),
+        InvalidDataSet::VariableNameNotValid{var_name: INVALID_VAR_NAME.to_string(), reason: NameError::FirstCharNotAllowed{chr: '!'}},
         data_set.rename_var(VAR_NAME, INVALID_VAR_NAME).unwrap_err()
     );
 
@@ -452,3 +452,636 @@ fn test_remove_var_error_not_defined() {
     assert_eq!(None,    data_set.var_len(VAR_NAME));
     assert_eq!(None,    data_set.var_data_type(VAR_NAME));
 }
+
+#[test]
+fn test_check_format_limits() {
+    const LARGE_DIM_SIZE: usize = 40_000;
+    // 40_000 * 40_000 * 2 bytes, between the limits of the classic and the 64-bit offset versions
+    const MEDIUM_CHUNK_SIZE: usize = LARGE_DIM_SIZE * LARGE_DIM_SIZE * 2;
+    // 40_000 * 40_000 * 8 bytes, exceeds both limits
+    const LARGE_CHUNK_SIZE: usize = LARGE_DIM_SIZE * LARGE_DIM_SIZE * 8;
+
+    let mut data_set: DataSet = DataSet::new();
+    data_set.add_fixed_dim("dim_1", LARGE_DIM_SIZE).unwrap();
+    data_set.add_fixed_dim("dim_2", LARGE_DIM_SIZE).unwrap();
+    data_set.set_unlimited_dim("time", 1).unwrap();
+    data_set.add_var_i16("medium_var", &["dim_1", "dim_2"]).unwrap();
+    data_set.add_var_f64("large_var", &["dim_1", "dim_2"]).unwrap();
+
+    // the last *fixed-size* variable is not checked when no record variable is defined
+    assert_eq!(
+        Err(InvalidDataSet::MaximumVariableSizeExceeded{
+            var_name: String::from("medium_var"),
+            version: Version::Classic,
+            max: Version::Classic.max_chunk_size(),
+            get: MEDIUM_CHUNK_SIZE,
+        }),
+        data_set.check_format_limits(Version::Classic)
+    );
+    assert_eq!(Ok(()),      data_set.check_format_limits(Version::Offset64Bit));
+
+    // the limits of the 64-bit offset version are checked when the variables are added
+    assert_eq!(
+        Err(InvalidDataSet::MaximumVariableSizeExceeded{
+            var_name: String::from("large_var"),
+            version: Version::Offset64Bit,
+            max: Version::Offset64Bit.max_chunk_size(),
+            get: LARGE_CHUNK_SIZE,
+        }),
+        data_set.add_var_f64("large_record_var", &["time", "dim_1", "dim_2"])
+    );
+    assert_eq!(vec!["medium_var", "large_var"],     data_set.get_var_names());
+    assert_eq!(false,                               data_set.has_var("large_record_var"));
+
+    // the last record variable is not checked
+    data_set.remove_var("large_var").unwrap();
+    data_set.add_var_f64("large_record_var", &["time", "dim_1", "dim_2"]).unwrap();
+    assert_eq!(Ok(()),      data_set.check_format_limits(Version::Offset64Bit));
+
+    assert_eq!(
+        Err(InvalidDataSet::MaximumVariableSizeExceeded{
+            var_name: String::from("large_record_var"),
+            version: Version::Offset64Bit,
+            max: Version::Offset64Bit.max_chunk_size(),
+            get: LARGE_CHUNK_SIZE,
+        }),
+        data_set.add_var_i8("small_record_var", &["time"])
+    );
+    assert_eq!(false,       data_set.has_var("small_record_var"));
+
+    // the data type is not changed
+    assert_eq!(
+        Err(InvalidDataSet::MaximumVariableSizeExceeded{
+            var_name: String::from("medium_var"),
+            version: Version::Offset64Bit,
+            max: Version::Offset64Bit.max_chunk_size(),
+            get: LARGE_CHUNK_SIZE,
+        }),
+        data_set.set_var_data_type("medium_var", DataType::F64)
+    );
+    assert_eq!(Some(DataType::I16),     data_set.var_data_type("medium_var"));
+
+    // the *fixed-size* variables added after a record variable are checked
+    assert_eq!(
+        Err(InvalidDataSet::MaximumVariableSizeExceeded{
+            var_name: String::from("large_var"),
+            version: Version::Offset64Bit,
+            max: Version::Offset64Bit.max_chunk_size(),
+            get: LARGE_CHUNK_SIZE,
+        }),
+        data_set.add_var_f64("large_var", &["dim_1", "dim_2"])
+    );
+    assert_eq!(vec!["medium_var", "large_record_var"],      data_set.get_var_names());
+
+    // the previous last *fixed-size* variable is checked when another one is added
+    let mut data_set: DataSet = DataSet::new();
+    data_set.add_fixed_dim("dim_1", LARGE_DIM_SIZE).unwrap();
+    data_set.add_fixed_dim("dim_2", LARGE_DIM_SIZE).unwrap();
+    data_set.add_var_f64("large_var", &["dim_1", "dim_2"]).unwrap();
+    assert_eq!(
+        Err(InvalidDataSet::MaximumVariableSizeExceeded{
+            var_name: String::from("large_var"),
+            version: Version::Offset64Bit,
+            max: Version::Offset64Bit.max_chunk_size(),
+            get: LARGE_CHUNK_SIZE,
+        }),
+        data_set.add_var_i8("small_var", &["dim_1"])
+    );
+    assert_eq!(vec!["large_var"],       data_set.get_var_names());
+    assert_eq!(Ok(()),                  data_set.check_format_limits(Version::Offset64Bit));
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn test_size_overflow() {
+    const LARGE_DIM_SIZE: usize = 1 << 30;
+
+    let mut data_set: DataSet = DataSet::new();
+    data_set.add_fixed_dim("dim_1", LARGE_DIM_SIZE).unwrap();
+    data_set.add_fixed_dim("dim_2", LARGE_DIM_SIZE).unwrap();
+    data_set.add_fixed_dim("dim_3", LARGE_DIM_SIZE).unwrap();
+    data_set.set_unlimited_dim("time", 32).unwrap();
+
+    // the chunk size overflows `usize`
+    assert_eq!(
+        InvalidDataSet::VariableSizeOverflow(String::from("overflow_var")),
+        data_set.add_var_i8("overflow_var", &["dim_1", "dim_2", "dim_3"]).unwrap_err()
+    );
+    assert_eq!(false,                   data_set.has_var("overflow_var"));
+
+    // 2^60 * 8 bytes per record
+    data_set.add_var_f64("record_var_1", &["time", "dim_1", "dim_2"]).unwrap();
+    {
+        let var = data_set.get_var("record_var_1").unwrap();
+        assert_eq!(Some(1 << 60),       var.checked_chunk_len());
+        assert_eq!(Some(1 << 63),       var.checked_chunk_size());
+        // 32 records of 2^60 elements overflow `usize`
+        assert_eq!(None,                var.checked_len());
+        assert_eq!(usize::MAX,          var.len());
+    }
+    assert_eq!(Some(1 << 63),           data_set.record_size());
+
+    // the record size overflows `usize`
+    assert_eq!(
+        InvalidDataSet::RecordSizeOverflow(String::from("record_var_2")),
+        data_set.add_var_f64("record_var_2", &["time", "dim_1", "dim_2"]).unwrap_err()
+    );
+    assert_eq!(false,                   data_set.has_var("record_var_2"));
+    assert_eq!(Some(1 << 63),           data_set.record_size());
+}
+
+#[test]
+fn test_record_size_after_changes() {
+    let mut data_set: DataSet = DataSet::new();
+    data_set.set_unlimited_dim("time", 2).unwrap();
+    data_set.add_fixed_dim("time_fixed", 2).unwrap();
+    data_set.add_fixed_dim("x", 3).unwrap();
+    assert_eq!(Some(0),                 data_set.record_size());
+
+    // the only record variable is not padded
+    data_set.add_var_i8("var_1", &["time", "x"]).unwrap();
+    assert_eq!(Some(3),                 data_set.record_size());
+    data_set.add_var_i16("var_2", &["time"]).unwrap();
+    assert_eq!(Some(4 + 4),             data_set.record_size());
+    data_set.add_var_f64("var_3", &["time_fixed", "x"]).unwrap();
+    assert_eq!(Some(4 + 4),             data_set.record_size());
+
+    data_set.make_record_var("var_3").unwrap();
+    assert_eq!(Some(4 + 4 + 24),        data_set.record_size());
+    data_set.set_var_data_type("var_3", DataType::F32).unwrap();
+    assert_eq!(Some(4 + 4 + 12),        data_set.record_size());
+    data_set.make_fixed_var("var_1", "time_fixed").unwrap();
+    assert_eq!(Some(4 + 12),            data_set.record_size());
+    data_set.remove_var("var_2").unwrap();
+    assert_eq!(Some(12),                data_set.record_size());
+    data_set.remove_var("var_3").unwrap();
+    assert_eq!(Some(0),                 data_set.record_size());
+}
+
+#[test]
+fn test_var_lookups_after_renaming_and_removal() {
+    const NUM_VARS: usize = 1000;
+    const DIM_NAME: &str = "dim_1";
+
+    let mut data_set: DataSet = DataSet::new();
+    data_set.add_fixed_dim(DIM_NAME, 2).unwrap();
+    for i in 0..NUM_VARS {
+        data_set.add_var_i32(&format!("var_{}", i), &[DIM_NAME]).unwrap();
+        data_set.add_var_attr_i32(&format!("var_{}", i), "index", vec![i as i32]).unwrap();
+    }
+    assert_eq!(NUM_VARS,                data_set.num_vars());
+
+    // Remove the first variable, the positions of the next ones are shifted
+    data_set.remove_var("var_0").unwrap();
+    assert_eq!(false,                   data_set.has_var("var_0"));
+    assert_eq!(Some(&[1][..]),          data_set.get_var_attr_i32("var_1", "index"));
+    assert_eq!(Some(&[999][..]),        data_set.get_var_attr_i32("var_999", "index"));
+
+    // Rename a variable and one of its attributes
+    data_set.rename_var("var_500", "renamed_var").unwrap();
+    data_set.rename_var_attr("renamed_var", "index", "renamed_index").unwrap();
+    assert_eq!(false,                   data_set.has_var("var_500"));
+    assert_eq!(Some(false),             data_set.has_var_attr("renamed_var", "index"));
+    assert_eq!(Some(&[500][..]),        data_set.get_var_attr_i32("renamed_var", "renamed_index"));
+    data_set.add_var_i8("var_500", &[DIM_NAME]).unwrap();
+    assert_eq!(Some(DataType::I8),      data_set.var_data_type("var_500"));
+    assert_eq!(Some(DataType::I32),     data_set.var_data_type("renamed_var"));
+
+    // The insertion order is kept
+    let var_names: Vec<String> = data_set.get_var_names();
+    assert_eq!("var_1",                 var_names[0]);
+    assert_eq!("renamed_var",           var_names[499]);
+    assert_eq!("var_500",               var_names[NUM_VARS - 1]);
+}
+
+#[test]
+fn test_var_dims() {
+    let mut data_set: DataSet = DataSet::new();
+    data_set.set_unlimited_dim("time", 2).unwrap();
+    for dim_name in ["dim_1", "dim_2", "dim_3", "dim_4"].iter() {
+        data_set.add_fixed_dim(*dim_name, 3).unwrap();
+    }
+    data_set.add_var_f32("var_4d", &["time", "dim_1", "dim_2", "dim_3"]).unwrap();
+    data_set.add_var_f32("var_5d", &["time", "dim_1", "dim_2", "dim_3", "dim_4"]).unwrap();
+
+    // Up to 4 dimensions are stored without heap allocation
+    let var_4d = data_set.get_var("var_4d").unwrap();
+    assert_eq!(false,                                       var_4d.dims.spilled());
+    assert_eq!(var_4d.get_dims(),                           var_4d.dims().to_vec());
+    assert_eq!(vec!["time", "dim_1", "dim_2", "dim_3"],     var_4d.dims().iter().map(|dim| dim.name()).collect::<Vec<String>>());
+    assert_eq!(27,                                          var_4d.chunk_len());
+
+    let var_5d = data_set.get_var("var_5d").unwrap();
+    assert_eq!(true,                                        var_5d.dims.spilled());
+    assert_eq!(5,                                           var_5d.dims().len());
+    assert_eq!(81,                                          var_5d.chunk_len());
+
+    // The dimensions stay shared with the data set
+    data_set.rename_dim("dim_1", "dim_a").unwrap();
+    assert_eq!("dim_a",                                     data_set.get_var("var_5d").unwrap().dims()[1].name());
+}
+
+#[test]
+fn test_borrowed_accessors() {
+    let mut data_set: DataSet = DataSet::new();
+    data_set.set_unlimited_dim("time", 2).unwrap();
+    data_set.add_fixed_dim("dim_1", 3).unwrap();
+    data_set.add_var_f32("var_1", &["time", "dim_1"]).unwrap();
+    data_set.add_var_i8::<&str>("var_2", &[]).unwrap();
+    data_set.add_var_attr_string("var_1", "units", "K").unwrap();
+    data_set.add_global_attr_string("title", "test").unwrap();
+
+    // Same items as the collected ones
+    assert_eq!(data_set.get_dims(),                 data_set.dims().to_vec());
+    assert_eq!(data_set.get_vars(),                 data_set.vars().iter().collect::<Vec<_>>());
+    assert_eq!(data_set.get_global_attrs(),         data_set.global_attrs().iter().collect::<Vec<_>>());
+    let var_1 = data_set.get_var("var_1").unwrap();
+    assert_eq!(var_1.get_attrs(),                   var_1.attrs().iter().collect::<Vec<_>>());
+    assert_eq!(DataType::F32,                       var_1.data_type());
+
+    // The borrowed name follows the renaming of the dimension
+    assert_eq!("dim_1",                             &*data_set.dims()[1].borrow_name());
+    data_set.rename_dim("dim_1", "dim_a").unwrap();
+    assert_eq!("dim_a",                             &*data_set.get_var("var_1").unwrap().dims()[1].borrow_name());
+}
+
+#[test]
+fn test_index_var() {
+    let mut data_set: DataSet = DataSet::new();
+    data_set.add_fixed_dim("dim_1", 3).unwrap();
+    data_set.add_var_i16("var_1", &["dim_1"]).unwrap();
+    data_set.add_var_attr_i32("var_1", "valid_range", vec![0, 10]).unwrap();
+
+    assert!(core::ptr::eq(data_set.get_var("var_1").unwrap(),      &data_set["var_1"]));
+    assert_eq!(DataType::I16,                                       data_set["var_1"].data_type());
+    assert!(core::ptr::eq(data_set.get_var_attr("var_1", "valid_range").unwrap(),   &data_set["var_1"]["valid_range"]));
+    assert_eq!(Some(&[0, 10][..]),                                  data_set["var_1"]["valid_range"].get_i32());
+}
+
+#[test]
+#[should_panic(expected = "the variable `undef_var` is not defined")]
+fn test_index_var_not_defined() {
+    let data_set: DataSet = DataSet::new();
+    let _var = &data_set["undef_var"];
+}
+
+#[test]
+#[should_panic(expected = "the attribute `undef_attr` of the variable `var_1` is not defined")]
+fn test_index_var_attr_not_defined() {
+    let mut data_set: DataSet = DataSet::new();
+    data_set.add_var_i8::<&str>("var_1", &[]).unwrap();
+    let _attr = &data_set["var_1"]["undef_attr"];
+}
+
+#[test]
+fn test_var_eq_loaded_data() {
+    use crate::DataVector;
+
+    let mut data_set: DataSet = DataSet::new();
+    data_set.add_fixed_dim("x", 2).unwrap();
+    data_set.add_var_f64("var_1", &["x"]).unwrap();
+    data_set.get_var_mut("var_1").unwrap().data = Some(DataVector::F64(vec![1.0, f64::NAN]));
+
+    // only the definitions are compared, the NaN values do not make the variables unequal
+    let copied_data_set: DataSet = data_set.deep_clone();
+    assert_eq!(data_set,                            copied_data_set);
+    assert_eq!(data_set["var_1"],                   copied_data_set["var_1"]);
+    let mut unloaded_data_set: DataSet = data_set.deep_clone();
+    unloaded_data_set.get_var_mut("var_1").unwrap().data = None;
+    assert_eq!(data_set,                            unloaded_data_set);
+
+    // the loaded values are compared by `data_eq`, the NaN values being equal to each other
+    assert_eq!(true,                                data_set["var_1"].data_eq(&copied_data_set["var_1"]));
+    assert_eq!(false,                               data_set["var_1"].data_eq(&unloaded_data_set["var_1"]));
+    assert_eq!(true,                                unloaded_data_set["var_1"].data_eq(&unloaded_data_set["var_1"]));
+    let mut other_data_set: DataSet = data_set.deep_clone();
+    other_data_set.get_var_mut("var_1").unwrap().data = Some(DataVector::F64(vec![1.0, 2.0]));
+    assert_eq!(false,                               data_set["var_1"].data_eq(&other_data_set["var_1"]));
+    other_data_set.get_var_mut("var_1").unwrap().data = Some(DataVector::F32(vec![1.0, f32::NAN]));
+    assert_eq!(false,                               data_set["var_1"].data_eq(&other_data_set["var_1"]));
+}
+
+#[test]
+fn test_append_record() {
+    use std::collections::HashMap;
+    use crate::DataVector;
+
+    let record = |values: Vec<(&str, DataVector)>| -> HashMap<String, DataVector> {
+        return values.into_iter().map(|(var_name, values): (&str, DataVector)| (var_name.to_string(), values)).collect();
+    };
+
+    let mut data_set: DataSet = DataSet::new();
+    data_set.add_fixed_dim("station", 2).unwrap();
+    data_set.add_var_i32("station_id", &["station"]).unwrap();
+    assert_eq!(
+        Err(InvalidDataSet::UnlimitedDimensionNotDefined),
+        data_set.append_record(record(vec![]))
+    );
+
+    data_set.set_unlimited_dim("time", 1).unwrap();
+    data_set.add_var_f64("time", &["time"]).unwrap();
+    data_set.add_var_i16("count", &["time", "station"]).unwrap();
+    data_set.get_var_mut("station_id").unwrap().data = Some(DataVector::I32(vec![10, 20]));
+    data_set.get_var_mut("time").unwrap().data = Some(DataVector::F64(vec![0.0]));
+
+    // the previous record of `count` is not loaded
+    assert_eq!(
+        Err(InvalidDataSet::VariableDataNotLoaded(String::from("count"))),
+        data_set.append_record(record(vec![("time", DataVector::F64(vec![1.0])), ("count", DataVector::I16(vec![3, 4]))]))
+    );
+    data_set.get_var_mut("count").unwrap().data = Some(DataVector::I16(vec![1, 2, 3, 4]));
+    assert_eq!(
+        Err(InvalidDataSet::VariableMismatchDataLength{var_name: String::from("count"), req: 2, get: 4}),
+        data_set.append_record(record(vec![("time", DataVector::F64(vec![1.0])), ("count", DataVector::I16(vec![3, 4]))]))
+    );
+    data_set.get_var_mut("count").unwrap().data = Some(DataVector::I32(vec![1, 2]));
+    assert_eq!(
+        Err(InvalidDataSet::VariableMismatchDataType{var_name: String::from("count"), req: DataType::I16, get: DataType::I32}),
+        data_set.append_record(record(vec![("time", DataVector::F64(vec![1.0])), ("count", DataVector::I16(vec![3, 4]))]))
+    );
+    data_set.get_var_mut("count").unwrap().data = Some(DataVector::I16(vec![1, 2]));
+
+    assert_eq!(
+        Err(InvalidDataSet::VariableNotDefined(String::from("undef_var"))),
+        data_set.append_record(record(vec![("undef_var", DataVector::I8(vec![0]))]))
+    );
+    assert_eq!(
+        Err(InvalidDataSet::VariableNotRecord(String::from("station_id"))),
+        data_set.append_record(record(vec![("station_id", DataVector::I32(vec![30, 40]))]))
+    );
+    assert_eq!(
+        Err(InvalidDataSet::VariableMismatchDataType{var_name: String::from("time"), req: DataType::F64, get: DataType::F32}),
+        data_set.append_record(record(vec![("time", DataVector::F32(vec![1.0])), ("count", DataVector::I16(vec![3, 4]))]))
+    );
+    assert_eq!(
+        Err(InvalidDataSet::VariableMismatchDataLength{var_name: String::from("count"), req: 2, get: 3}),
+        data_set.append_record(record(vec![("time", DataVector::F64(vec![1.0])), ("count", DataVector::I16(vec![3, 4, 5]))]))
+    );
+    // nothing has been modified by the errors
+    assert_eq!(Some(1),                                     data_set.num_records());
+    assert_eq!(Some(&DataVector::F64(vec![0.0])),           data_set["time"].data());
+
+    data_set.append_record(record(vec![("time", DataVector::F64(vec![1.0])), ("count", DataVector::I16(vec![3, 4]))])).unwrap();
+    assert_eq!(Some(2),                                     data_set.num_records());
+    assert_eq!(Some(&DataVector::F64(vec![0.0, 1.0])),      data_set["time"].data());
+    assert_eq!(Some(&DataVector::I16(vec![1, 2, 3, 4])),    data_set["count"].data());
+    assert_eq!(Some(&DataVector::I32(vec![10, 20])),        data_set["station_id"].data());
+
+    // the values of the first record are loaded by its append
+    let mut data_set: DataSet = DataSet::new();
+    data_set.set_unlimited_dim("time", 0).unwrap();
+    data_set.add_var_f64("time", &["time"]).unwrap();
+    data_set.append_record(record(vec![("time", DataVector::F64(vec![0.0]))])).unwrap();
+    assert_eq!(Some(1),                                     data_set.num_records());
+    assert_eq!(Some(&DataVector::F64(vec![0.0])),           data_set["time"].data());
+}
+
+#[test]
+fn test_get_record() {
+    use std::collections::HashMap;
+    use crate::{DataSlice, DataVector};
+
+    let mut data_set: DataSet = DataSet::new();
+    assert_eq!(Err(InvalidDataSet::UnlimitedDimensionNotDefined),     data_set.get_record(0));
+
+    data_set.set_unlimited_dim("time", 3).unwrap();
+    data_set.add_fixed_dim("station", 2).unwrap();
+    data_set.add_var_u8("flag", &["time"]).unwrap();
+    data_set.add_var_i16("count", &["time", "station"]).unwrap();
+    data_set.get_var_mut("flag").unwrap().data = Some(DataVector::U8(vec![1, 2, 3]));
+    assert_eq!(
+        Err(InvalidDataSet::VariableDataNotLoaded(String::from("count"))),
+        data_set.get_record(0)
+    );
+    data_set.get_var_mut("count").unwrap().data = Some(DataVector::I16(vec![0, 1, 2, 3]));
+    assert_eq!(
+        Err(InvalidDataSet::VariableMismatchDataLength{var_name: String::from("count"), req: 6, get: 4}),
+        data_set.get_record(0)
+    );
+    data_set.get_var_mut("count").unwrap().data = Some(DataVector::I16(vec![0, 1, 2, 3, 4, 5]));
+
+    let record: HashMap<String, DataSlice> = data_set.get_record(2).unwrap();
+    assert_eq!(Some(&DataSlice::U8(&[3])),                  record.get("flag"));
+    assert_eq!(Some(&DataSlice::I16(&[4, 5])),              record.get("count"));
+    assert_eq!(
+        Err(InvalidDataSet::RecordIndexExceeded{index: 3, num_records: 3}),
+        data_set.get_record(3)
+    );
+
+    // the records are appended in the reverse order to another data set
+    let mut data_set_2: DataSet = DataSet::new();
+    data_set_2.set_unlimited_dim("time", 0).unwrap();
+    data_set_2.add_fixed_dim("station", 2).unwrap();
+    data_set_2.add_var_u8("flag", &["time"]).unwrap();
+    data_set_2.add_var_i16("count", &["time", "station"]).unwrap();
+    for record_index in (0..3).rev() {
+        let record: HashMap<String, DataVector> = data_set.get_record(record_index).unwrap().into_iter().map(|(var_name, values): (String, DataSlice)| {
+            let values: DataVector = match values {
+                DataSlice::U8(values) => DataVector::U8(values.to_vec()),
+                DataSlice::I16(values) => DataVector::I16(values.to_vec()),
+                _ => panic!("unexpected data type"),
+            };
+            (var_name, values)
+        }).collect();
+        data_set_2.append_record(record).unwrap();
+    }
+    assert_eq!(Some(3),                                     data_set_2.num_records());
+    assert_eq!(Some(&DataVector::U8(vec![3, 2, 1])),        data_set_2["flag"].data());
+    assert_eq!(Some(&DataVector::I16(vec![4, 5, 2, 3, 0, 1])),  data_set_2["count"].data());
+}
+
+#[test]
+fn test_make_record_and_fixed_var() {
+    let mut data_set: DataSet = DataSet::new();
+    data_set.add_fixed_dim("station", 2).unwrap();
+    data_set.add_var_f32("temperature", &["station"]).unwrap();
+    data_set.add_var_i8::<&str>("scalar", &[]).unwrap();
+    assert_eq!(Err(InvalidDataSet::UnlimitedDimensionNotDefined),       data_set.make_record_var("temperature"));
+    assert_eq!(Err(InvalidDataSet::UnlimitedDimensionNotDefined),       data_set.make_fixed_var("temperature", "time_fixed"));
+
+    data_set.set_unlimited_dim("time", 2).unwrap();
+    assert_eq!(Err(InvalidDataSet::VariableNotDefined(String::from("undef_var"))),     data_set.make_record_var("undef_var"));
+    assert_eq!(
+        Err(InvalidDataSet::NumRecordsMismatch{var_name: String::from("scalar"), num_records: 2, dim_size: None}),
+        data_set.make_record_var("scalar")
+    );
+
+    // the dimension `station` has the size of the unlimited-size dimension
+    data_set.make_record_var("temperature").unwrap();
+    assert_eq!(Some(true),                      data_set.is_record_var("temperature"));
+    assert_eq!(Some(2),                         data_set.var_len("temperature"));
+    assert_eq!(Some(1),                         data_set.get_var("temperature").map(|var| var.chunk_len()));
+    assert_eq!(Some(4),                         data_set.record_size());
+    // nothing is done for a record variable
+    data_set.make_record_var("temperature").unwrap();
+    assert_eq!(Some(true),                      data_set.is_record_var("temperature"));
+
+    // the fixed-size dimension is defined if necessary
+    data_set.add_fixed_dim("other_dim", 3).unwrap();
+    assert_eq!(
+        Err(InvalidDataSet::NumRecordsMismatch{var_name: String::from("temperature"), num_records: 2, dim_size: Some(3)}),
+        data_set.make_fixed_var("temperature", "other_dim")
+    );
+    assert_eq!(
+        Err(InvalidDataSet::UnlimitedDimensionAlreadyExists(String::from("time"))),
+        data_set.make_fixed_var("temperature", "time")
+    );
+    assert_eq!(false,                           data_set.has_dim("time_fixed"));
+    data_set.make_fixed_var("temperature", "time_fixed").unwrap();
+    assert_eq!(Some(false),                     data_set.is_record_var("temperature"));
+    assert_eq!(Some(2),                         data_set.dim_size("time_fixed"));
+    assert_eq!(Some(DimensionType::FixedSize),  data_set.dim_type("time_fixed"));
+    assert_eq!(Some(0),                         data_set.record_size());
+    assert_eq!(Some(vec![String::from("time_fixed")]),  data_set.get_var("temperature").map(|var| var.dim_names()));
+    // nothing is done for a fixed-size variable
+    data_set.make_fixed_var("temperature", "other_dim").unwrap();
+    assert_eq!(Some(vec![String::from("time_fixed")]),  data_set.get_var("temperature").map(|var| var.dim_names()));
+
+    // the dimension is already used by the variable
+    data_set.add_var_i16("count", &["time", "station"]).unwrap();
+    assert_eq!(
+        Err(InvalidDataSet::DimensionsUsedMultipleTimes{var_name: String::from("count"), get_dim_names: vec![String::from("station"), String::from("station")]}),
+        data_set.make_fixed_var("count", "station")
+    );
+}
+
+#[test]
+fn test_make_record_and_fixed_var_format_limits() {
+    const LARGE_DIM_SIZE: usize = 40_000;
+    // 40_000 * 40_000 * 8 bytes, exceeds the limits of the 64-bit offset version
+    const LARGE_CHUNK_SIZE: usize = LARGE_DIM_SIZE * LARGE_DIM_SIZE * 8;
+
+    let mut data_set: DataSet = DataSet::new();
+    data_set.set_unlimited_dim("time", LARGE_DIM_SIZE).unwrap();
+    data_set.add_fixed_dim("dim_1", LARGE_DIM_SIZE).unwrap();
+    data_set.add_fixed_dim("dim_2", LARGE_DIM_SIZE).unwrap();
+    data_set.add_var_i8("small_var", &["dim_1"]).unwrap();
+    // the last *fixed-size* variable is not checked while no record variable is defined
+    data_set.add_var_f64("large_var", &["dim_1", "dim_2"]).unwrap();
+
+    assert_eq!(
+        Err(InvalidDataSet::MaximumVariableSizeExceeded{
+            var_name: String::from("large_var"),
+            version: Version::Offset64Bit,
+            max: Version::Offset64Bit.max_chunk_size(),
+            get: LARGE_CHUNK_SIZE,
+        }),
+        data_set.make_record_var("small_var")
+    );
+    assert_eq!(Some(false),                     data_set.is_record_var("small_var"));
+    assert_eq!(Some(0),                         data_set.record_size());
+    assert_eq!(Ok(()),                          data_set.check_format_limits(Version::Offset64Bit));
+
+    // the records of `large_record_var` would become a *fixed-size* variable exceeding the limits
+    data_set.remove_var("large_var").unwrap();
+    data_set.add_var_f64("large_record_var", &["time", "dim_2"]).unwrap();
+    data_set.add_var_i8("small_record_var", &["time"]).unwrap();
+    assert_eq!(
+        Err(InvalidDataSet::MaximumVariableSizeExceeded{
+            var_name: String::from("large_record_var"),
+            version: Version::Offset64Bit,
+            max: Version::Offset64Bit.max_chunk_size(),
+            get: LARGE_CHUNK_SIZE,
+        }),
+        data_set.make_fixed_var("large_record_var", "time_fixed")
+    );
+    assert_eq!(Some(true),                      data_set.is_record_var("large_record_var"));
+    assert_eq!(false,                           data_set.has_dim("time_fixed"));
+    assert_eq!(Some(LARGE_DIM_SIZE * 8 + 4),    data_set.record_size());
+    assert_eq!(Ok(()),                          data_set.check_format_limits(Version::Offset64Bit));
+}
+
+#[test]
+fn test_rename_many() {
+    let mut data_set: DataSet = DataSet::new();
+    data_set.set_unlimited_dim("t", 2).unwrap();
+    data_set.add_fixed_dim("x", 3).unwrap();
+    data_set.add_var_f64("t", &["t"]).unwrap();
+    data_set.add_var_i16("a", &["t", "x"]).unwrap();
+    data_set.add_var_i16("b", &["x"]).unwrap();
+    data_set.add_var_attr_i16("a", "fill", vec![-1]).unwrap();
+    data_set.add_var_attr_string("a", "x", "attribute named like the dimension").unwrap();
+    data_set.add_global_attr_string("t", "global attribute").unwrap();
+
+    // errors, nothing is renamed
+    assert_eq!(
+        Err(InvalidDataSet::DimensionAlreadyExists(String::from("x"))),
+        data_set.rename_many(&[("t", "x")])
+    );
+    assert_eq!(
+        Err(InvalidDataSet::VariableNameNotValid{var_name: String::from("!b"), reason: NameError::FirstCharNotAllowed{chr: '!'}}),
+        data_set.rename_many(&[("x", "y"), ("b", "!b")])
+    );
+    assert_eq!(
+        Err(InvalidDataSet::VariableAttributeAlreadyExists{var_name: String::from("a"), attr_name: String::from("x")}),
+        data_set.rename_many(&[("fill", "x")])
+    );
+    assert_eq!(
+        Err(InvalidDataSet::VariableAttributeMismatchDataType{var_name: String::from("a"), attr_name: String::from("_FillValue"), req: DataType::I16, get: DataType::U8}),
+        data_set.rename_many(&[("fill", "missing_value"), ("x", "_FillValue")])
+    );
+    assert_eq!(vec!["t", "x"],                      data_set.dim_names());
+    assert_eq!(vec!["t", "a", "b"],                 data_set.get_var_names());
+    assert_eq!(vec!["fill", "x"],                   data_set.get_var("a").unwrap().get_attr_names());
+
+    // the names `a` and `b` are swapped, the first pair renaming `t` is used, `undef` is ignored
+    data_set.rename_many(&[("t", "time"), ("a", "b"), ("b", "a"), ("fill", "_FillValue"), ("t", "other"), ("undef", "other")]).unwrap();
+    assert_eq!(vec!["time", "x"],                   data_set.dim_names());
+    assert_eq!(Some(DataType::I16),                 data_set.var_data_type("b"));
+    assert_eq!(vec!["time", "b", "a"],              data_set.get_var_names());
+    assert_eq!(vec!["x"],                           data_set.get_var("a").unwrap().dim_names());
+    assert_eq!(vec!["time", "x"],                   data_set.get_var("b").unwrap().dim_names());
+    assert_eq!(Some(&[-1][..]),                     data_set.get_var_attr_i16("b", "_FillValue"));
+    assert_eq!(Some(String::from("global attribute")),      data_set.get_global_attr_as_string("time"));
+    assert_eq!(Some(2),                             data_set.dim_size("time"));
+    assert_eq!(None,                                data_set.dim_size("t"));
+}
+
+#[test]
+fn test_duplicate_var() {
+    use crate::DataVector;
+
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim("time", 2).unwrap();
+    data_set.add_fixed_dim("station", 3).unwrap();
+    data_set.add_var_f64("time", &["time"]).unwrap();
+    data_set.add_var_i16("t2m", &["time", "station"]).unwrap();
+    data_set.add_var_attr_i16("t2m", "_FillValue", vec![-1]).unwrap();
+    data_set.add_var_attr_string("t2m", "units", "K").unwrap();
+
+    data_set.get_var_mut("t2m").unwrap().data = Some(DataVector::I16(vec![1, 2, 3, 4, 5, 6]));
+    data_set.duplicate_var_with_data("t2m", "t2m_anomaly").unwrap();
+    // the values of `time` are not loaded
+    data_set.duplicate_var_with_data("time", "time_bounds").unwrap();
+    data_set.duplicate_var("t2m", "t2m_mask").unwrap();
+
+    assert_eq!(vec!["time", "t2m", "t2m_anomaly", "time_bounds", "t2m_mask"],       data_set.get_var_names());
+    assert_eq!(vec!["time", "station"],             data_set.get_var("t2m_anomaly").unwrap().dim_names());
+    assert_eq!(true,                                data_set.get_var("t2m_anomaly").unwrap().is_record_var());
+    assert_eq!(Some(DataType::I16),                 data_set.var_data_type("t2m_anomaly"));
+    assert_eq!(vec!["_FillValue", "units"],         data_set.get_var("t2m_anomaly").unwrap().get_attr_names());
+    assert_eq!(Some(&[-1][..]),                     data_set.get_var_attr_i16("t2m_anomaly", "_FillValue"));
+    assert_eq!(Some(8 + 8 + 8 + 8 + 8),             data_set.record_size());
+    assert_eq!(Some(&DataVector::I16(vec![1, 2, 3, 4, 5, 6])),  data_set["t2m_anomaly"].data());
+    assert_eq!(None,                                data_set["time_bounds"].data());
+    assert_eq!(None,                                data_set["t2m_mask"].data());
+
+    // the attributes of the duplicated variable are independent
+    data_set.remove_var_attr("t2m_anomaly", "units").unwrap();
+    assert_eq!(Some(true),                          data_set.has_var_attr("t2m", "units"));
+    assert_eq!(Some(false),                         data_set.has_var_attr("t2m_anomaly", "units"));
+
+    assert_eq!(
+        Err(InvalidDataSet::VariableNotDefined(String::from("undef"))),
+        data_set.duplicate_var("undef", "undef_2")
+    );
+    assert_eq!(
+        Err(InvalidDataSet::VariableAlreadyExists(String::from("time"))),
+        data_set.duplicate_var("t2m", "time")
+    );
+    assert_eq!(
+        Err(InvalidDataSet::VariableNameNotValid{var_name: String::from("!t2m"), reason: NameError::FirstCharNotAllowed{chr: '!'}}),
+        data_set.duplicate_var("t2m", "!t2m")
+    );
+    assert_eq!(5,                                   data_set.num_vars());
+}
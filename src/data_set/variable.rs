@@ -1,12 +1,24 @@
-use std::collections::HashSet;
-use std::iter::FromIterator;
-use std::rc::Rc;
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use alloc::collections::BTreeSet;
+use core::iter::FromIterator;
+use alloc::rc::Rc;
 
-use crate::{is_valid_name, Attribute, DataType, Dimension, InvalidDataSet, NC_MAX_VAR_DIMS};
+use smallvec::SmallVec;
+
+use crate::{Attribute, DataSet, DataType, DataVector, Dimension, InvalidDataSet, NameError, NameValidation, VariableView, NC_MAX_VAR_DIMS};
 use crate::{data_set::dimension::DimensionSize};
+use crate::data_set::name_index::NameIndex;
+use crate::name_string::check_name;
 use crate::io::compute_padding_size;
 
 
+/// Dimensions of a variable, stored inline up to 4 dimensions (the common case) to avoid a heap allocation.
+pub(crate) type VarDims = SmallVec<[Rc<Dimension>; 4]>;
+
+/// Names of the attributes whose data type must be the data type of their variable.
+pub(crate) const SAME_TYPE_ATTR_NAMES: [&str; 2] = ["_FillValue", "missing_value"];
+
 /// NetCDF-3 variable
 ///
 /// `Variable` instances are managed by the struct [`DataSet`](struct.DataSet.html).
@@ -114,19 +126,66 @@ use crate::io::compute_padding_size;
 /// assert_eq!(None,                            data_set.var_len(VAR_NAME));
 /// assert_eq!(None,                            data_set.var_data_type(VAR_NAME));
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Clone)]
 pub struct Variable {
     pub(crate) name: String,
     pub(crate) unlimited_dim: Option<Rc<Dimension>>,
-    pub(crate) dims: Vec<Rc<Dimension>>,
+    pub(crate) dims: VarDims,
     pub(crate) attrs: Vec<Attribute>,
     pub(crate) data_type: DataType,
+    /// Positions of the attributes, indexed by name
+    attrs_index: NameIndex,
+    /// Values loaded from the file (see `FileReader::load_vars`)
+    pub(crate) data: Option<DataVector>,
+}
+
+impl core::fmt::Debug for Variable {
+    /// The index of the attribute names is not displayed.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        return f.debug_struct("Variable")
+            .field("name", &self.name)
+            .field("unlimited_dim", &self.unlimited_dim)
+            .field("dims", &self.dims)
+            .field("attrs", &self.attrs)
+            .field("data_type", &self.data_type)
+            .field("data", &self.data)
+            .finish();
+    }
+}
+
+impl PartialEq for Variable {
+    /// Only the definitions are compared, see [`data_eq`](struct.Variable.html#method.data_eq) for the loaded values.
+    ///
+    /// The index of the attribute names is not compared, being built from the attributes.
+    fn eq(&self, other: &Self) -> bool {
+        return self.name == other.name
+            && self.unlimited_dim == other.unlimited_dim
+            && self.dims == other.dims
+            && self.attrs == other.attrs
+            && self.data_type == other.data_type;
+    }
+}
+
+/// Returns the attribute of the variable, see [`get_attr`](struct.Variable.html#method.get_attr) for the non-panicking lookup.
+///
+/// # Panics
+///
+/// Panics if the attribute is not defined.
+impl core::ops::Index<&str> for Variable {
+    type Output = Attribute;
+
+    fn index(&self, attr_name: &str) -> &Attribute {
+        return match self.get_attr(attr_name) {
+            Some(attr) => attr,
+            None => panic!("the attribute `{}` of the variable `{}` is not defined", attr_name, self.name),
+        };
+    }
 }
 
 impl Variable {
-    pub(in crate::data_set) fn new(var_name: &str, var_dims: Vec<Rc<Dimension>>, data_type: DataType) -> Result<Variable, InvalidDataSet> {
+    pub(in crate::data_set) fn new(var_name: &str, var_dims: Vec<Rc<Dimension>>, data_type: DataType, name_validation: NameValidation, allow_long_names: bool) -> Result<Variable, InvalidDataSet> {
         // Check if the name of the variable is a valid NetCDF-3 name.
-        let _ = Variable::check_var_name(var_name)?;
+        Variable::check_var_name(var_name, name_validation, allow_long_names)?;
 
         let unlimited_dim: Option<Rc<Dimension>> = match var_dims.first() {
             None => None,
@@ -137,17 +196,24 @@ impl Variable {
         };
         Variable::check_dims_validity(var_name, &var_dims)?;
 
-        Ok(Variable {
+        let var = Variable {
             name: var_name.to_string(),
             unlimited_dim: unlimited_dim,
-            dims: var_dims,
+            dims: SmallVec::from_vec(var_dims),
             attrs: vec![],
             data_type: data_type,
-            // data: None,
-        })
+            attrs_index: NameIndex::new(),
+            data: None,
+        };
+        // Check that the size of each chunk can be represented
+        if var.checked_chunk_size().is_none() {
+            return Err(InvalidDataSet::VariableSizeOverflow(var_name.to_string()));
+        }
+        return Ok(var);
     }
 
     /// Return the name of the variable.
+    #[inline]
     pub fn name(&self) -> &str {
         return &self.name;
     }
@@ -169,15 +235,49 @@ impl Variable {
     /// let var: &Variable = data_set.get_var(VAR_NAME).unwrap();
     /// assert_eq!(DataType::I32,               var.data_type());
     /// ```
+    #[inline]
     pub fn data_type(&self) -> DataType {
-        return self.data_type.clone();
+        return self.data_type;
+    }
+
+    /// Returns the values of the variable loaded from the file, `None` if they have not been loaded.
+    ///
+    /// The values are loaded by [`FileReader::load_vars`](struct.FileReader.html#method.load_vars), and dropped
+    /// when the data type of the variable is changed.
+    pub fn data(&self) -> Option<&DataVector> {
+        return self.data.as_ref();
+    }
+
+    /// Returns `true` if the loaded values of both variables are equal, the NaN values being equal to each other.
+    ///
+    /// The variables having no loaded values are equal, their definitions are not compared (see `==`).
+    pub fn data_eq(&self, other: &Variable) -> bool {
+        return match (&self.data, &other.data) {
+            (None, None) => true,
+            (Some(DataVector::F32(data_1)), Some(DataVector::F32(data_2))) => {
+                data_1.len() == data_2.len() && data_1.iter().zip(data_2).all(|(a, b): (&f32, &f32)| a == b || (a.is_nan() && b.is_nan()))
+            },
+            (Some(DataVector::F64(data_1)), Some(DataVector::F64(data_2))) => {
+                data_1.len() == data_2.len() && data_1.iter().zip(data_2).all(|(a, b): (&f64, &f64)| a == b || (a.is_nan() && b.is_nan()))
+            },
+            (Some(data_1), Some(data_2)) => data_1 == data_2,
+            _ => false,
+        };
     }
 
     /// Returns the total number of elements.
     ///
     /// If the variable is a record variable then `len = num_chunks * chunk_len`.
+    ///
+    /// Saturates at `usize::MAX` if the number of elements cannot be represented,
+    /// see [`checked_len`](struct.Variable.html#method.checked_len).
     pub fn len(&self) -> usize {
-        return self.num_chunks() * self.chunk_len();
+        return self.checked_len().unwrap_or(usize::MAX);
+    }
+
+    /// Returns the total number of elements, or `None` if it overflows `usize`.
+    pub fn checked_len(&self) -> Option<usize> {
+        return self.num_chunks().checked_mul(self.checked_chunk_len()?);
     }
 
     pub fn use_dim(&self, dim_name: &str) -> bool {
@@ -185,6 +285,7 @@ impl Variable {
     }
 
     /// Returns the number of dimensions (the rank) the the variables
+    #[inline]
     pub fn num_dims(&self) -> usize {
         return self.dims.len();
     }
@@ -192,7 +293,14 @@ impl Variable {
     /// Returns the list of the dimensions
     pub fn get_dims(&self) -> Vec<Rc<Dimension>>
     {
-        self.dims.clone()
+        self.dims.to_vec()
+    }
+
+    /// Returns the dimensions of the variable, without cloning them (see [`get_dims`](#method.get_dims)).
+    #[inline]
+    pub fn dims(&self) -> &[Rc<Dimension>]
+    {
+        return &self.dims;
     }
 
     /// Returns the list of the dimension names
@@ -203,10 +311,70 @@ impl Variable {
         }).collect()
     }
 
+    /// Returns the view of the values `data` of the variable with its shape (see [`VariableView`](struct.VariableView.html)),
+    /// or `None` if the number of values does not match the shape of the variable.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::{DataSet, Variable, VariableView};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.add_fixed_dim("latitude", 2).unwrap();
+    /// data_set.add_fixed_dim("longitude", 3).unwrap();
+    /// data_set.add_var_f32("t2m", &["latitude", "longitude"]).unwrap();
+    ///
+    /// let t2m: &Variable = data_set.get_var("t2m").unwrap();
+    /// let data: Vec<f32> = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+    /// let view: VariableView<f32> = t2m.view(&data).unwrap();
+    /// assert_eq!(4.0,         view[[1, 1]]);
+    /// assert_eq!(None,        t2m.view(&data[0..3]));
+    /// ```
+    pub fn view<'a, T: Clone>(&self, data: &'a [T]) -> Option<VariableView<'a, T>> {
+        let shape: Vec<usize> = self.dims.iter().map(|dim: &Rc<Dimension>| dim.size()).collect();
+        return VariableView::new(data, &shape);
+    }
+
+    /// Returns, for each dimension of the variable, the name of its coordinate variable defined in `data_set`, or `None`.
+    ///
+    /// The coordinate variable of a dimension is the 1D variable having the same name and defined over this dimension.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::DataSet;
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.set_unlimited_dim("time", 2).unwrap();
+    /// data_set.add_fixed_dim("latitude", 3).unwrap();
+    /// data_set.add_fixed_dim("longitude", 4).unwrap();
+    /// data_set.add_var_f64("time", &["time"]).unwrap();
+    /// data_set.add_var_f32("latitude", &["latitude"]).unwrap();
+    /// data_set.add_var_f32("t2m", &["time", "latitude", "longitude"]).unwrap();
+    ///
+    /// let t2m = data_set.get_var("t2m").unwrap();
+    /// assert_eq!(
+    ///     vec![Some(String::from("time")), Some(String::from("latitude")), None],
+    ///     t2m.coordinate_names(&data_set)
+    /// );
+    /// ```
+    pub fn coordinate_names(&self, data_set: &DataSet) -> Vec<Option<String>>
+    {
+        return self.dims.iter().map(|dim: &Rc<Dimension>| {
+            let dim_name: String = dim.name();
+            let coord_var: &Variable = data_set.get_var(&dim_name)?;
+            if coord_var.num_dims() != 1 || !Rc::ptr_eq(&coord_var.dims[0], dim) {
+                return None;
+            }
+            return Some(dim_name);
+        }).collect();
+    }
+
     /// Returns :
     ///
     /// - `true` if the variable is defined over the *unlimited size* dimension, then has several records
     /// - `false` otherwise
+    #[inline]
     pub fn is_record_var(&self) -> bool {
         match self.dims.first() {
             None => false,
@@ -215,6 +383,7 @@ impl Variable {
     }
 
     /// Returns the number of attributes.
+    #[inline]
     pub fn num_attrs(&self) -> usize {
         return self.attrs.len();
     }
@@ -231,10 +400,16 @@ impl Variable {
     ///
     /// If the variable id a *fixed-size* variable then `chunk_len = len`.
     pub fn chunk_len(&self) -> usize
+    {
+        return self.checked_chunk_len().unwrap_or(usize::MAX);
+    }
+
+    /// Returns the number of elements per chunk, or `None` if it overflows `usize`.
+    pub fn checked_chunk_len(&self) -> Option<usize>
     {
         let skip_len: usize = if self.is_record_var() { 1 } else { 0 };
-        self.dims.iter().skip(skip_len).fold(1, |product, dim| {
-            product * dim.size()
+        self.dims.iter().skip(skip_len).try_fold(1_usize, |product: usize, dim: &Rc<Dimension>| {
+            product.checked_mul(dim.size())
         })
     }
 
@@ -278,10 +453,14 @@ impl Variable {
     /// assert_eq!(8,           scalar_var_f64.chunk_size());
     /// ```
     pub fn chunk_size(&self) -> usize {
-        let mut chunk_size = self.chunk_len() * self.data_type.size_of();
+        return self.checked_chunk_size().unwrap_or(usize::MAX);
+    }
+
+    /// Returns the size of each chunk including the padding bytes, or `None` if it overflows `usize`.
+    pub fn checked_chunk_size(&self) -> Option<usize> {
+        let chunk_size: usize = self.checked_chunk_len()?.checked_mul(self.data_type.size_of())?;
         // append the bytes of the zero padding, if necessary
-        chunk_size += compute_padding_size(chunk_size);
-        return chunk_size
+        return chunk_size.checked_add(compute_padding_size(chunk_size));
     }
 
     /// Returns the number of chunks.
@@ -302,6 +481,12 @@ impl Variable {
         return self.attrs.iter().collect();
     }
 
+    /// Returns all the attributes of the variable, without collecting them (see [`get_attrs`](#method.get_attrs)).
+    #[inline]
+    pub fn attrs(&self) -> &[Attribute] {
+        return &self.attrs;
+    }
+
     /// Returns all attributs defined in the dataset or in the variable.
     pub fn get_attr_names(&self) -> Vec<String> {
         return self.attrs.iter().map(|attr: &Attribute| {
@@ -375,8 +560,17 @@ impl Variable {
 
     /// Appends a new attribute.
     ///
-    /// An error is returned if an other attribute with the same name has already been added.
+    /// An error is returned if an other attribute with the same name has already been added,
+    /// or if the data type of the attribute `_FillValue` or `missing_value` is not the data type of the variable.
     fn add_attr(&mut self, new_attr: Attribute) -> Result<(), InvalidDataSet> {
+        self.check_attr_data_type(&new_attr.name, new_attr.data_type())?;
+        return self.push_attr(new_attr);
+    }
+
+    /// Appends a new attribute without checking its data type.
+    ///
+    /// An error is returned if an other attribute with the same name has already been added.
+    fn push_attr(&mut self, new_attr: Attribute) -> Result<(), InvalidDataSet> {
         // Check if an other same name attribute already exists.
         if self.find_attr_from_name(&new_attr.name).is_ok() {
             return Err(InvalidDataSet::VariableAttributeAlreadyExists{
@@ -385,18 +579,56 @@ impl Variable {
             });
         }
         // append the new attribute
+        self.attrs_index.push(&new_attr.name, self.attrs.len());
         self.attrs.push(new_attr);
         return Ok(());
     }
 
+    /// Appends a new attribute, its name being checked with the rules `name_validation`.
+    ///
+    /// An error is returned if an other attribute with the same name has already been added.
+    pub(in crate::data_set) fn add_attr_with(&mut self, attr: Attribute, name_validation: NameValidation) -> Result<(), InvalidDataSet> {
+        self.check_attr_data_type(&attr.name, attr.data_type())?;
+        return self.add_parsed_attr_with(attr, name_validation, false);
+    }
+
+    /// Appends a new attribute read from a file, its name being checked with the rules `name_validation`
+    /// (its size is not checked if `allow_long_names`).
+    ///
+    /// Its data type is not checked, the attributes `_FillValue` and `missing_value` of an other data type than the variable
+    /// are reported by the diagnostics of the reader.
+    pub(in crate::data_set) fn add_parsed_attr_with(&mut self, attr: Attribute, name_validation: NameValidation, allow_long_names: bool) -> Result<(), InvalidDataSet> {
+        check_name(&attr.name, name_validation, allow_long_names)
+            .map_err(|reason: NameError| InvalidDataSet::VariableAttributeNameNotValid{
+                var_name: self.name.to_string(),
+                attr_name: attr.name.to_string(),
+                reason: reason,
+            })?;
+        return self.push_attr(attr);
+    }
+
+    /// Checks that the data type of the attribute `_FillValue` or `missing_value` is the data type of the variable.
+    pub(in crate::data_set) fn check_attr_data_type(&self, attr_name: &str, attr_data_type: DataType) -> Result<(), InvalidDataSet> {
+        if SAME_TYPE_ATTR_NAMES.contains(&attr_name) && attr_data_type != self.data_type {
+            return Err(InvalidDataSet::VariableAttributeMismatchDataType{
+                var_name: self.name.to_string(),
+                attr_name: attr_name.to_string(),
+                req: self.data_type,
+                get: attr_data_type,
+            });
+        }
+        return Ok(());
+    }
+
     /// Append a new `i8` attribute.
     ///
     /// An error is returned if an other attribute with the same name has already been added.
     pub fn add_attr_i8(&mut self, attr_name: &str, i8_data: Vec<i8>) -> Result<(), InvalidDataSet> {
         let attr: Attribute = Attribute::new_i8(attr_name, i8_data)
-            .map_err(|var_attr_name: String| InvalidDataSet::VariableAttributeNameNotValid{
+            .map_err(|reason: NameError| InvalidDataSet::VariableAttributeNameNotValid{
                 var_name: self.name.to_string(),
-                attr_name: var_attr_name,
+                attr_name: attr_name.to_string(),
+                reason: reason,
             })?;
         self.add_attr(attr)?;
         Ok(())
@@ -407,9 +639,10 @@ impl Variable {
     /// An error is returned if an other attribute with the same name has already been added.
     pub fn add_attr_u8(&mut self, attr_name: &str, u8_data: Vec<u8>) -> Result<(), InvalidDataSet> {
         let attr: Attribute = Attribute::new_u8(attr_name, u8_data)
-            .map_err(|var_attr_name: String| InvalidDataSet::VariableAttributeNameNotValid{
+            .map_err(|reason: NameError| InvalidDataSet::VariableAttributeNameNotValid{
                 var_name: self.name.to_string(),
-                attr_name: var_attr_name,
+                attr_name: attr_name.to_string(),
+                reason: reason,
             })?;
         self.add_attr(attr)?;
         Ok(())
@@ -428,9 +661,10 @@ impl Variable {
     /// An error is returned if an other attribute with the same name has already been added.
     pub fn add_attr_i16(&mut self, attr_name: &str, i16_data: Vec<i16>) -> Result<(), InvalidDataSet> {
         let attr: Attribute = Attribute::new_i16(attr_name, i16_data)
-            .map_err(|var_attr_name: String| InvalidDataSet::VariableAttributeNameNotValid{
+            .map_err(|reason: NameError| InvalidDataSet::VariableAttributeNameNotValid{
                 var_name: self.name.to_string(),
-                attr_name: var_attr_name,
+                attr_name: attr_name.to_string(),
+                reason: reason,
             })?;
         self.add_attr(attr)?;
         Ok(())
@@ -441,9 +675,10 @@ impl Variable {
     /// An error is returned if an other attribute with the same name has already been added.
     pub fn add_attr_i32(&mut self, attr_name: &str, i32_data: Vec<i32>) -> Result<(), InvalidDataSet> {
         let attr: Attribute = Attribute::new_i32(attr_name, i32_data)
-            .map_err(|var_attr_name: String| InvalidDataSet::VariableAttributeNameNotValid{
+            .map_err(|reason: NameError| InvalidDataSet::VariableAttributeNameNotValid{
                 var_name: self.name.to_string(),
-                attr_name: var_attr_name,
+                attr_name: attr_name.to_string(),
+                reason: reason,
             })?;
         self.add_attr(attr)?;
         Ok(())
@@ -454,9 +689,10 @@ impl Variable {
     /// An error is returned if an other attribute with the same name has already been added.
     pub fn add_attr_f32(&mut self, attr_name: &str, f32_data: Vec<f32>) -> Result<(), InvalidDataSet> {
         let attr: Attribute = Attribute::new_f32(attr_name, f32_data)
-            .map_err(|var_attr_name: String| InvalidDataSet::VariableAttributeNameNotValid{
+            .map_err(|reason: NameError| InvalidDataSet::VariableAttributeNameNotValid{
                 var_name: self.name.to_string(),
-                attr_name: var_attr_name,
+                attr_name: attr_name.to_string(),
+                reason: reason,
             })?;
         self.add_attr(attr)?;
         Ok(())
@@ -467,9 +703,10 @@ impl Variable {
     /// An error is returned if an other attribute with the same name has already been added.
     pub fn add_attr_f64(&mut self, attr_name: &str, f64_data: Vec<f64>) -> Result<(), InvalidDataSet> {
         let attr: Attribute = Attribute::new_f64(attr_name, f64_data)
-            .map_err(|var_attr_name: String| InvalidDataSet::VariableAttributeNameNotValid{
+            .map_err(|reason: NameError| InvalidDataSet::VariableAttributeNameNotValid{
                 var_name: self.name.to_string(),
-                attr_name: var_attr_name,
+                attr_name: attr_name.to_string(),
+                reason: reason,
             })?;
         self.add_attr(attr)?;
         Ok(())
@@ -481,7 +718,7 @@ impl Variable {
     ///  - the `old_attr_name`is not a valid NetCDF-3 name
     ///  - the `old_attr_name` attribute doesn't exist
     ///  - an other `new_attr_name` attribute already exist
-    pub(in crate::data_set) fn rename_attr(&mut self, old_attr_name: &str, new_attr_name: &str) -> Result<(), InvalidDataSet> {
+    pub(in crate::data_set) fn rename_attr(&mut self, old_attr_name: &str, new_attr_name: &str, name_validation: NameValidation) -> Result<(), InvalidDataSet> {
         if old_attr_name == new_attr_name {
             return Ok(());
         }
@@ -496,49 +733,50 @@ impl Variable {
         }
 
         // Check that `new_attr_name`is a valid NetCDF-3 name
-        Attribute::check_attr_name(new_attr_name)
-            .map_err(|var_attr_name: String| InvalidDataSet::VariableAttributeNameNotValid{
+        Attribute::check_attr_name(new_attr_name, name_validation)
+            .map_err(|reason: NameError| InvalidDataSet::VariableAttributeNameNotValid{
                 var_name: self.name.to_string(),
-                attr_name:var_attr_name.to_string()
+                attr_name: new_attr_name.to_string(),
+                reason: reason,
             })?;
+        self.check_attr_data_type(new_attr_name, self.attrs[renamed_attr_index].data_type())?;
         let renamed_attr: &mut Attribute = &mut self.attrs[renamed_attr_index];
         renamed_attr.name = new_attr_name.to_string();
+        self.attrs_index.rename(old_attr_name, new_attr_name, renamed_attr_index, self.attrs.iter().map(Attribute::name));
         return Ok(());
     }
 
+    /// Rebuilds the index of the attribute names, after renaming several attributes.
+    pub(in crate::data_set) fn rebuild_attrs_index(&mut self) {
+        self.attrs_index.rebuild(self.attrs.iter().map(Attribute::name));
+    }
+
     // Remove the attribute.
     pub fn remove_attr(&mut self, attr_name: &str) -> Result<Attribute, InvalidDataSet> {
         let removed_attr_index: usize = self.find_attr_from_name(attr_name)?.0;
         let removed_attr: Attribute = self.attrs.remove(removed_attr_index);
+        self.attrs_index.rebuild(self.attrs.iter().map(Attribute::name));
         return Ok(removed_attr);
     }
 
     /// Find a dataset's attribute from is name.
     pub(in crate::data_set) fn find_attr_from_name(&self, attr_name: &str) -> Result<(usize, &Attribute), InvalidDataSet> {
-        self.attrs
-            .iter()
-            .position(|attr| {
-                // First find the position
-                attr.name() == attr_name
-            })
-            .map(|index| {
-                // Then get the referance to the attribute
+        self.attrs_index.get(attr_name)
+            .map(|index: usize| {
                 return (index, &self.attrs[index]);
             })
-            .ok_or(InvalidDataSet::VariableAttributeNotDefined{
+            .ok_or_else(|| InvalidDataSet::VariableAttributeNotDefined{
                 var_name: self.name.to_string(),
                 attr_name: attr_name.to_string(),
             })
     }
 
-    pub(super) fn check_var_name(var_name: &str) -> Result<(), InvalidDataSet> {
-        return match is_valid_name(var_name) {
-            true => Ok(()),
-            false => Err(InvalidDataSet::VariableNameNotValid(var_name.to_string())),
-        };
+    pub(super) fn check_var_name(var_name: &str, name_validation: NameValidation, allow_long_names: bool) -> Result<(), InvalidDataSet> {
+        return check_name(var_name, name_validation, allow_long_names)
+            .map_err(|reason: NameError| InvalidDataSet::VariableNameNotValid{var_name: var_name.to_string(), reason: reason});
     }
 
-    fn check_dims_validity(var_name: &str, dims: &Vec<Rc<Dimension>>) -> Result<(), InvalidDataSet> {
+    fn check_dims_validity(var_name: &str, dims: &[Rc<Dimension>]) -> Result<(), InvalidDataSet> {
         if dims.is_empty() {
             return Ok(());
         }
@@ -564,7 +802,7 @@ impl Variable {
                 .collect();
             repeated_dim_names.extend(i32ernal_repeated_dim_names.into_iter());
         }
-        let repeated_dim_names = HashSet::<String>::from_iter(repeated_dim_names.into_iter());
+        let repeated_dim_names = BTreeSet::<String>::from_iter(repeated_dim_names.into_iter());
         if !repeated_dim_names.is_empty() {
             let dim_names: Vec<String> = dims.iter().map(|dim: &Rc<Dimension>| {
                 dim.name()
@@ -577,6 +815,7 @@ impl Variable {
         if dims.len() > NC_MAX_VAR_DIMS {
             return Err(InvalidDataSet::MaximumDimensionsPerVariableExceeded{
                 var_name: var_name.to_string(),
+                max: NC_MAX_VAR_DIMS,
                 num_dims: dims.len(),
             })
         }
@@ -0,0 +1,76 @@
+use crate::Variable;
+
+/// Sizes of the record variables and positions of the last variables of each kind, kept up to date while the variables are added,
+/// removed or changed.
+///
+/// The record size and the limits of the format are then checked without iterating over all the variables, adding `n` variables
+/// staying linear.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct VarsLayout {
+    /// Number of record variables
+    num_record_vars: usize,
+    /// Sum of the chunk sizes of the record variables, including the padding bytes
+    record_vars_size: usize,
+    /// Sum of the chunk sizes of the record variables, without the padding bytes
+    unpadded_record_vars_size: usize,
+    /// Position of the last *fixed-size* variable
+    last_fixed_var: Option<usize>,
+    /// Position of the last record variable
+    last_record_var: Option<usize>,
+}
+
+impl VarsLayout {
+
+    /// Creates the layout of a data set without variables.
+    pub(crate) fn new() -> VarsLayout {
+        return VarsLayout::default();
+    }
+
+    /// Computes the layout of the variables, `None` if the record size overflows `usize`.
+    pub(crate) fn compute(vars: &[Variable]) -> Option<VarsLayout> {
+        return vars.iter().enumerate().try_fold(VarsLayout::new(), |vars_layout: VarsLayout, (position, var): (usize, &Variable)| {
+            vars_layout.checked_push(var, position)
+        });
+    }
+
+    /// Returns the layout once the variable `var` is appended at `position`, `None` if the record size overflows `usize`.
+    pub(crate) fn checked_push(&self, var: &Variable, position: usize) -> Option<VarsLayout> {
+        if !var.is_record_var() {
+            return Some(VarsLayout{
+                last_fixed_var: Some(position),
+                ..*self
+            });
+        }
+        let unpadded_chunk_size: usize = var.checked_chunk_len()?.checked_mul(var.data_type().size_of())?;
+        return Some(VarsLayout{
+            num_record_vars: self.num_record_vars + 1,
+            record_vars_size: self.record_vars_size.checked_add(var.checked_chunk_size()?)?,
+            unpadded_record_vars_size: self.unpadded_record_vars_size + unpadded_chunk_size,
+            last_record_var: Some(position),
+            ..*self
+        });
+    }
+
+    /// Returns the number of record variables.
+    pub(crate) fn num_record_vars(&self) -> usize {
+        return self.num_record_vars;
+    }
+
+    /// Returns the position of the last *fixed-size* variable.
+    pub(crate) fn last_fixed_var(&self) -> Option<usize> {
+        return self.last_fixed_var;
+    }
+
+    /// Returns the position of the last record variable.
+    pub(crate) fn last_record_var(&self) -> Option<usize> {
+        return self.last_record_var;
+    }
+
+    /// Returns the record size, the chunk of the only record variable being not padded.
+    pub(crate) fn record_size(&self) -> usize {
+        return match self.num_record_vars {
+            1 => self.unpadded_record_vars_size,
+            _ => self.record_vars_size,
+        };
+    }
+}
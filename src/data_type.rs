@@ -34,7 +34,7 @@ const F64_TYPE_C_API_NAME: &'static str = "NC_DOUBLE";
 /// assert_eq!(8, DataType::F64.size_of());
 /// ```
 #[repr(u32)]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DataType {
     /// 8-bit signed integer, a.k.a. `NC_BYTE`
     I8 = 1,
@@ -50,21 +50,22 @@ pub enum DataType {
     F64 = 6,
 }
 
-impl std::fmt::Display for DataType {
-
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "DataType::{}", match self {
-            DataType::I8 => "I8",
-            DataType::U8 => "U8",
-            DataType::I16 => "I16",
-            DataType::I32 => "I32",
-            DataType::F32 => "F32",
-            DataType::F64 => "F64",
-        })
+/// Formats the name of the data type used in CDL (see [`cdl_name`](enum.DataType.html#method.cdl_name)).
+///
+/// # Example
+///
+/// ```
+/// # use netcdf3::DataType;
+/// assert_eq!("double", DataType::F64.to_string());
+/// ```
+impl core::fmt::Display for DataType {
+
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.cdl_name())
     }
 }
 
-impl std::convert::TryFrom<u32> for DataType {
+impl core::convert::TryFrom<u32> for DataType {
     type Error = &'static str;
     fn try_from(value: u32) -> Result<DataType, &'static str> {
         match value {
@@ -96,12 +97,12 @@ impl DataType {
     /// ```
     pub fn size_of(&self) -> usize {
         match self {
-            DataType::I8 => std::mem::size_of::<i8>(),
-            DataType::U8 => std::mem::size_of::<u8>(),
-            DataType::I16 => std::mem::size_of::<i16>(),
-            DataType::I32 => std::mem::size_of::<i32>(),
-            DataType::F32 => std::mem::size_of::<f32>(),
-            DataType::F64 => std::mem::size_of::<f64>(),
+            DataType::I8 => core::mem::size_of::<i8>(),
+            DataType::U8 => core::mem::size_of::<u8>(),
+            DataType::I16 => core::mem::size_of::<i16>(),
+            DataType::I32 => core::mem::size_of::<i32>(),
+            DataType::F32 => core::mem::size_of::<f32>(),
+            DataType::F64 => core::mem::size_of::<f64>(),
         }
     }
 
@@ -133,21 +134,45 @@ impl DataType {
             DataType::F64 => F64_TYPE_C_API_NAME,
         }
     }
+
+    /// Returns the name of the `DataType` used in CDL (the text notation printed by `ncdump`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use netcdf3::DataType;
+    /// assert_eq!("byte", DataType::I8.cdl_name());
+    /// assert_eq!("char", DataType::U8.cdl_name());
+    /// assert_eq!("short", DataType::I16.cdl_name());
+    /// assert_eq!("int", DataType::I32.cdl_name());
+    /// assert_eq!("float", DataType::F32.cdl_name());
+    /// assert_eq!("double", DataType::F64.cdl_name());
+    /// ```
+    pub fn cdl_name(&self) -> &'static str {
+        match self {
+            DataType::I8 => "byte",
+            DataType::U8 => "char",
+            DataType::I16 => "short",
+            DataType::I32 => "int",
+            DataType::F32 => "float",
+            DataType::F64 => "double",
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::DataType;
-    use std::convert::TryFrom;
+    use core::convert::TryFrom;
 
     #[test]
     fn test_data_type_display() {
-        assert_eq!("DataType::I8", format!("{}", DataType::I8));
-        assert_eq!("DataType::U8", format!("{}", DataType::U8));
-        assert_eq!("DataType::I16", format!("{}", DataType::I16));
-        assert_eq!("DataType::I32", format!("{}", DataType::I32));
-        assert_eq!("DataType::F32", format!("{}", DataType::F32));
-        assert_eq!("DataType::F64", format!("{}", DataType::F64));
+        assert_eq!("byte", format!("{}", DataType::I8));
+        assert_eq!("char", format!("{}", DataType::U8));
+        assert_eq!("short", format!("{}", DataType::I16));
+        assert_eq!("int", format!("{}", DataType::I32));
+        assert_eq!("float", format!("{}", DataType::F32));
+        assert_eq!("double", format!("{}", DataType::F64));
     }
 
     #[test]
@@ -1,5 +1,12 @@
 mod tests;
 
+mod view;
+pub use view::VariableView;
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use byteorder::{BigEndian, ByteOrder};
+
 use crate::DataType;
 
 /// Wraps the six NetCDF-3 data types.
@@ -45,7 +52,9 @@ use crate::DataType;
 /// let latitude: Vec<f32> = latitude.get_f32_into().unwrap();
 /// assert_eq!(LATITUDE_VAR_DATA.to_vec(),      latitude);
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+///
+/// The `Debug` output of the long vectors is truncated (see [`DataSlice`](enum.DataSlice.html)).
+#[derive(Clone, PartialEq)]
 pub enum DataVector {
     I8(Vec<i8>),
     U8(Vec<u8>),
@@ -57,6 +66,7 @@ pub enum DataVector {
 
 impl DataVector {
 
+    #[cfg(feature = "std")]
     pub(crate) fn new(data_type: DataType, length: usize) -> Self {
         match data_type {
             DataType::I8 => DataVector::I8(vec![0; length]),
@@ -68,6 +78,49 @@ impl DataVector {
         }
     }
 
+    /// Converts the big-endian bytes of the values, the number of bytes being a multiple of the size of the data type.
+    pub(crate) fn from_be_bytes(bytes: &[u8], data_type: DataType) -> Self {
+        let num_elements: usize = bytes.len() / data_type.size_of();
+        return match data_type {
+            DataType::I8 => DataVector::I8(bytes.iter().map(|byte: &u8| *byte as i8).collect()),
+            DataType::U8 => DataVector::U8(bytes.to_vec()),
+            DataType::I16 => {
+                let mut data: Vec<i16> = vec![0; num_elements];
+                BigEndian::read_i16_into(bytes, &mut data[..]);
+                DataVector::I16(data)
+            },
+            DataType::I32 => {
+                let mut data: Vec<i32> = vec![0; num_elements];
+                BigEndian::read_i32_into(bytes, &mut data[..]);
+                DataVector::I32(data)
+            },
+            DataType::F32 => {
+                let mut data: Vec<f32> = vec![0.0; num_elements];
+                BigEndian::read_f32_into(bytes, &mut data[..]);
+                DataVector::F32(data)
+            },
+            DataType::F64 => {
+                let mut data: Vec<f64> = vec![0.0; num_elements];
+                BigEndian::read_f64_into(bytes, &mut data[..]);
+                DataVector::F64(data)
+            },
+        };
+    }
+
+    /// Resizes the vector to `length` elements of `data_type`, reusing its allocation if the data type is unchanged.
+    #[cfg(feature = "read")]
+    pub(crate) fn reset(&mut self, data_type: DataType, length: usize) {
+        match (self, data_type) {
+            (DataVector::I8(data), DataType::I8) => { data.clear(); data.resize(length, 0); },
+            (DataVector::U8(data), DataType::U8) => { data.clear(); data.resize(length, 0); },
+            (DataVector::I16(data), DataType::I16) => { data.clear(); data.resize(length, 0); },
+            (DataVector::I32(data), DataType::I32) => { data.clear(); data.resize(length, 0); },
+            (DataVector::F32(data), DataType::F32) => { data.clear(); data.resize(length, 0.0); },
+            (DataVector::F64(data), DataType::F64) => { data.clear(); data.resize(length, 0.0); },
+            (data_vec, data_type) => { *data_vec = DataVector::new(data_type, length); },
+        }
+    }
+
     /// Return the NetCDF-3 data type.
     pub fn data_type(&self) -> DataType {
         match self {
@@ -135,14 +188,15 @@ impl DataVector {
         };
     }
 
-    pub(crate) fn get_as_string(&self) -> Option<String> {
+    /// Returns a copy of all the elements converted to `f64`.
+    pub(crate) fn get_as_f64_vec(&self) -> Vec<f64> {
         return match self {
-            DataVector::I8(_) => None,
-            DataVector::U8(data) => String::from_utf8(data.to_vec()).ok(),
-            DataVector::I16(_) => None,
-            DataVector::I32(_) => None,
-            DataVector::F32(_) => None,
-            DataVector::F64(_) => None,
+            DataVector::I8(data) => data.iter().map(|x: &i8| *x as f64).collect(),
+            DataVector::U8(data) => data.iter().map(|x: &u8| *x as f64).collect(),
+            DataVector::I16(data) => data.iter().map(|x: &i16| *x as f64).collect(),
+            DataVector::I32(data) => data.iter().map(|x: &i32| *x as f64).collect(),
+            DataVector::F32(data) => data.iter().map(|x: &f32| *x as f64).collect(),
+            DataVector::F64(data) => data.clone(),
         };
     }
 
@@ -276,3 +330,175 @@ impl DataVector {
         return Err(self);
     }
 }
+
+/// Borrows the values of one of the six NetCDF-3 data types.
+///
+/// It allows to write the data of several variables at once without copying them into `DataVector`s,
+/// see [FileWriter::write_records](struct.FileWriter.html#method.write_records).
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{DataSlice, DataVector, DataType};
+///
+/// let data: [f32; 3] = [1.0, 2.0, 3.0];
+/// let data_slice: DataSlice = DataSlice::from(&data[..]);
+/// assert_eq!(DataType::F32,               data_slice.data_type());
+/// assert_eq!(3,                           data_slice.len());
+///
+/// let data_vec = DataVector::I16(vec![1, 2]);
+/// assert_eq!(DataSlice::I16(&[1, 2]),     data_vec.as_slice());
+/// ```
+#[derive(Clone, Copy, PartialEq)]
+pub enum DataSlice<'a> {
+    I8(&'a [i8]),
+    U8(&'a [u8]),
+    I16(&'a [i16]),
+    I32(&'a [i32]),
+    F32(&'a [f32]),
+    F64(&'a [f64]),
+}
+
+impl<'a> DataSlice<'a> {
+
+    /// Return the NetCDF-3 data type.
+    pub fn data_type(&self) -> DataType {
+        match self {
+            DataSlice::I8(_) => DataType::I8,
+            DataSlice::U8(_) => DataType::U8,
+            DataSlice::I16(_) => DataType::I16,
+            DataSlice::I32(_) => DataType::I32,
+            DataSlice::F32(_) => DataType::F32,
+            DataSlice::F64(_) => DataType::F64,
+        }
+    }
+
+    /// Return the length (the number of elements) of the slice.
+    pub fn len(&self) -> usize {
+        match self {
+            DataSlice::I8(data) => data.len(),
+            DataSlice::U8(data) => data.len(),
+            DataSlice::I16(data) => data.len(),
+            DataSlice::I32(data) => data.len(),
+            DataSlice::F32(data) => data.len(),
+            DataSlice::F64(data) => data.len(),
+        }
+    }
+
+    /// Returns `true` if the slice contains no elements.
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    /// Returns the sub-slice of the elements from `start` to `end` (excluded).
+    #[cfg(feature = "std")]
+    pub(crate) fn sub_slice(&self, start: usize, end: usize) -> DataSlice<'a> {
+        return match self {
+            DataSlice::I8(data) => DataSlice::I8(&data[start..end]),
+            DataSlice::U8(data) => DataSlice::U8(&data[start..end]),
+            DataSlice::I16(data) => DataSlice::I16(&data[start..end]),
+            DataSlice::I32(data) => DataSlice::I32(&data[start..end]),
+            DataSlice::F32(data) => DataSlice::F32(&data[start..end]),
+            DataSlice::F64(data) => DataSlice::F64(&data[start..end]),
+        };
+    }
+}
+
+impl DataVector {
+    /// Borrows the values of the vector.
+    pub fn as_slice(&self) -> DataSlice<'_> {
+        return match self {
+            DataVector::I8(data) => DataSlice::I8(data),
+            DataVector::U8(data) => DataSlice::U8(data),
+            DataVector::I16(data) => DataSlice::I16(data),
+            DataVector::I32(data) => DataSlice::I32(data),
+            DataVector::F32(data) => DataSlice::F32(data),
+            DataVector::F64(data) => DataSlice::F64(data),
+        };
+    }
+}
+
+impl core::fmt::Debug for DataVector {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        return core::fmt::Debug::fmt(&self.as_slice(), f);
+    }
+}
+
+/// Number of leading and trailing values displayed by the `Debug` output of the long vectors and slices.
+const DEBUG_NUM_EDGE_VALUES: usize = 5;
+
+/// Displays only the leading and trailing values of the long vectors and slices, followed by their length,
+/// printing a large variable would otherwise flood the logs.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{DataSlice, DataVector};
+///
+/// assert_eq!("I32([1, 2, 3])",                format!("{:?}", DataSlice::I32(&[1, 2, 3])));
+///
+/// let data_vec = DataVector::I32((0..1_000_000).collect());
+/// assert_eq!(
+///     "I32 { len: 1000000, values: [0, 1, 2, 3, 4, ..., 999995, 999996, 999997, 999998, 999999] }",
+///     format!("{:?}", data_vec)
+/// );
+/// ```
+impl<'a> core::fmt::Debug for DataSlice<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        return match self {
+            DataSlice::I8(data) => fmt_debug_values(f, "I8", data),
+            DataSlice::U8(data) => fmt_debug_values(f, "U8", data),
+            DataSlice::I16(data) => fmt_debug_values(f, "I16", data),
+            DataSlice::I32(data) => fmt_debug_values(f, "I32", data),
+            DataSlice::F32(data) => fmt_debug_values(f, "F32", data),
+            DataSlice::F64(data) => fmt_debug_values(f, "F64", data),
+        };
+    }
+}
+
+fn fmt_debug_values<T: core::fmt::Debug>(f: &mut core::fmt::Formatter<'_>, variant_name: &str, values: &[T]) -> core::fmt::Result {
+    if values.len() <= 2 * DEBUG_NUM_EDGE_VALUES {
+        return f.debug_tuple(variant_name).field(&values).finish();
+    }
+    return f.debug_struct(variant_name)
+        .field("len", &values.len())
+        .field("values", &TruncatedValues(values))
+        .finish();
+}
+
+/// Leading and trailing values separated by an ellipsis.
+struct TruncatedValues<'a, T>(&'a [T]);
+
+impl<'a, T: core::fmt::Debug> core::fmt::Debug for TruncatedValues<'a, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let values: &[T] = self.0;
+        return f.debug_list()
+            .entries(values[..DEBUG_NUM_EDGE_VALUES].iter())
+            .entry(&format_args!("..."))
+            .entries(values[(values.len() - DEBUG_NUM_EDGE_VALUES)..].iter())
+            .finish();
+    }
+}
+
+impl<'a> From<&'a DataVector> for DataSlice<'a> {
+    fn from(data_vec: &'a DataVector) -> Self {
+        return data_vec.as_slice();
+    }
+}
+
+macro_rules! impl_data_slice_from {
+    ($prim_type:ty, $data_slice:path) => {
+        impl<'a> From<&'a [$prim_type]> for DataSlice<'a> {
+            fn from(data: &'a [$prim_type]) -> Self {
+                return $data_slice(data);
+            }
+        }
+    };
+}
+
+impl_data_slice_from!(i8, DataSlice::I8);
+impl_data_slice_from!(u8, DataSlice::U8);
+impl_data_slice_from!(i16, DataSlice::I16);
+impl_data_slice_from!(i32, DataSlice::I32);
+impl_data_slice_from!(f32, DataSlice::F32);
+impl_data_slice_from!(f64, DataSlice::F64);
@@ -1,6 +1,6 @@
 #![cfg(test)]
 
-use super::DataVector;
+use super::{DataVector, DataSlice};
 use crate::DataType;
 
 #[test]
@@ -416,3 +416,43 @@ fn test_equality_operator() {
         assert_ne!(data_f32, data_f64);
     }
 }
+
+#[test]
+fn test_data_slice() {
+    let data_vec = DataVector::F32(vec![1.0, 2.0, 3.0]);
+    let data_slice: DataSlice = data_vec.as_slice();
+    assert_eq!(DataType::F32,                   data_slice.data_type());
+    assert_eq!(3,                               data_slice.len());
+    assert_eq!(DataSlice::F32(&[1.0, 2.0, 3.0]), data_slice);
+    assert_eq!(DataSlice::F32(&[2.0]),          data_slice.sub_slice(1, 2));
+    assert_eq!(data_slice,                      DataSlice::from(&data_vec));
+
+    assert_eq!(DataSlice::I8(&[1, 2]),          DataSlice::from(&[1_i8, 2][..]));
+    assert_eq!(DataSlice::U8(&[1, 2]),          DataSlice::from(&[1_u8, 2][..]));
+    assert_eq!(DataSlice::I16(&[1, 2]),         DataSlice::from(&[1_i16, 2][..]));
+    assert_eq!(DataSlice::I32(&[1, 2]),         DataSlice::from(&[1_i32, 2][..]));
+    assert_eq!(DataSlice::F64(&[1.0, 2.0]),     DataSlice::from(&[1.0_f64, 2.0][..]));
+}
+
+#[test]
+fn test_debug() {
+    // short vectors and slices
+    assert_eq!("I8([])",                                format!("{:?}", DataVector::I8(vec![])));
+    assert_eq!("F32([0.0, 0.5, 1.0])",                  format!("{:?}", DataVector::F32(vec![0.0, 0.5, 1.0])));
+    assert_eq!("U8([1, 2])",                            format!("{:?}", DataSlice::U8(&[1, 2])));
+    assert_eq!(
+        "I16([0, 1, 2, 3, 4, 5, 6, 7, 8, 9])",
+        format!("{:?}", DataVector::I16((0..10).collect()))
+    );
+
+    // long vectors and slices
+    assert_eq!(
+        "I16 { len: 11, values: [0, 1, 2, 3, 4, ..., 6, 7, 8, 9, 10] }",
+        format!("{:?}", DataVector::I16((0..11).collect()))
+    );
+    let data: Vec<f64> = (0..100).map(|i: i32| i as f64).collect();
+    assert_eq!(
+        "F64 { len: 100, values: [0.0, 1.0, 2.0, 3.0, 4.0, ..., 95.0, 96.0, 97.0, 98.0, 99.0] }",
+        format!("{:?}", DataSlice::F64(&data))
+    );
+}
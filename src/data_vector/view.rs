@@ -0,0 +1,235 @@
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use alloc::borrow::Cow;
+use core::ops::Index;
+
+use crate::index::compute_flat_index;
+
+/// Values of a variable viewed with the shape of the variable, returned by the methods `FileReader::read_var_view_*`
+/// (see [`FileReader::read_var_view_f32`](struct.FileReader.html#method.read_var_view_f32)) and
+/// [`Variable::view`](struct.Variable.html#method.view).
+///
+/// The values are stored in row-major order (the last dimension varying the fastest), as in the NetCDF-3 files.
+/// The view computes the strides, the values are neither copied nor reordered.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::VariableView;
+///
+/// // 2 records of 2 x 3 values
+/// let data: Vec<i32> = (0..12).collect();
+/// let view: VariableView<i32> = VariableView::new(&data, &[2, 2, 3]).unwrap();
+/// assert_eq!(&[2, 2, 3],                  view.shape());
+/// assert_eq!(5,                           view[[0, 1, 2]]);
+/// assert_eq!(Some(&11),                   view.get(&[1, 1, 2]));
+/// assert_eq!(None,                        view.get(&[2, 0, 0]));
+///
+/// // the rows along the last dimension
+/// assert_eq!(Some(&[3, 4, 5][..]),        view.rows().nth(1));
+///
+/// // the subarrays along the first dimension
+/// let record: VariableView<i32> = view.subarray(1).unwrap();
+/// assert_eq!(&[2, 3],                     record.shape());
+/// assert_eq!(Some(vec![vec![6, 7, 8], vec![9, 10, 11]]),  record.to_vec_2d());
+/// assert_eq!(2,                           view.subarrays().count());
+///
+/// // the number of values must match the shape
+/// assert_eq!(None,                        VariableView::new(&data, &[5, 2]));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableView<'a, T: Clone> {
+    data: Cow<'a, [T]>,
+    shape: Vec<usize>,
+}
+
+impl<'a, T: Clone> VariableView<'a, T> {
+    /// Creates a view of the values `data` with the shape `shape`, or returns `None` if the number of values does not match
+    /// the shape.
+    ///
+    /// The empty shape is the shape of the scalar variables (one value).
+    pub fn new(data: &'a [T], shape: &[usize]) -> Option<VariableView<'a, T>> {
+        return VariableView::from_cow(Cow::Borrowed(data), shape);
+    }
+
+    /// Creates a view owning the values `data` (see [`new`](#method.new)).
+    pub fn from_vec(data: Vec<T>, shape: &[usize]) -> Option<VariableView<'static, T>> {
+        return VariableView::from_cow(Cow::Owned(data), shape);
+    }
+
+    fn from_cow<'b>(data: Cow<'b, [T]>, shape: &[usize]) -> Option<VariableView<'b, T>> {
+        let num_values: usize = shape.iter().try_fold(1_usize, |num_values: usize, dim_size: &usize| num_values.checked_mul(*dim_size))?;
+        if num_values != data.len() {
+            return None;
+        }
+        return Some(VariableView{
+            data: data,
+            shape: shape.to_vec(),
+        });
+    }
+
+    /// Returns the sizes of the dimensions.
+    pub fn shape(&self) -> &[usize] {
+        return &self.shape;
+    }
+
+    /// Returns the number of dimensions.
+    pub fn num_dims(&self) -> usize {
+        return self.shape.len();
+    }
+
+    /// Returns the number of values.
+    pub fn len(&self) -> usize {
+        return self.data.len();
+    }
+
+    /// Returns `true` if the view contains no value.
+    pub fn is_empty(&self) -> bool {
+        return self.data.is_empty();
+    }
+
+    /// Returns the values in row-major order.
+    pub fn as_slice(&self) -> &[T] {
+        return &self.data;
+    }
+
+    /// Returns the values in row-major order, copying them if they are borrowed.
+    pub fn into_vec(self) -> Vec<T> {
+        return self.data.into_owned();
+    }
+
+    /// Returns the position in the flat values of the value located at `indices` (one index per dimension),
+    /// or `None` if the number of indices differs from the number of dimensions or if an index is out of bounds.
+    pub fn flat_index(&self, indices: &[usize]) -> Option<usize> {
+        return compute_flat_index(&self.shape, indices);
+    }
+
+    /// Returns the value located at `indices` (one index per dimension), or `None` (see [`flat_index`](#method.flat_index)).
+    pub fn get(&self, indices: &[usize]) -> Option<&T> {
+        return self.flat_index(indices).map(|flat_index: usize| &self.data[flat_index]);
+    }
+
+    /// Returns an iterator over the rows of values along the last dimension.
+    ///
+    /// The scalar variables have one row of one value.
+    pub fn rows(&self) -> core::slice::Chunks<'_, T> {
+        let row_size: usize = self.shape.last().copied().unwrap_or(1);
+        // the empty views have no row
+        return self.data.chunks(core::cmp::max(row_size, 1));
+    }
+
+    /// Returns the view of the values located at the index `index` of the first dimension (a record of a record variable),
+    /// or `None` if the index is out of bounds or if the variable is a scalar.
+    pub fn subarray(&self, index: usize) -> Option<VariableView<'_, T>> {
+        let (first_dim_size, sub_shape): (&usize, &[usize]) = self.shape.split_first()?;
+        if index >= *first_dim_size {
+            return None;
+        }
+        let sub_len: usize = self.data.len() / first_dim_size;
+        return Some(VariableView{
+            data: Cow::Borrowed(&self.data[(index * sub_len)..((index + 1) * sub_len)]),
+            shape: sub_shape.to_vec(),
+        });
+    }
+
+    /// Returns an iterator over the subarrays along the first dimension (see [`subarray`](#method.subarray)).
+    pub fn subarrays(&self) -> impl Iterator<Item = VariableView<'_, T>> + '_ {
+        let num_subarrays: usize = self.shape.first().copied().unwrap_or(0);
+        return (0..num_subarrays).filter_map(move |index: usize| self.subarray(index));
+    }
+
+    /// Copies the values of a 2-dimensional view into nested `Vec`s (one `Vec` per row), or returns `None` if the view is not
+    /// 2-dimensional.
+    pub fn to_vec_2d(&self) -> Option<Vec<Vec<T>>> {
+        if self.num_dims() != 2 {
+            return None;
+        }
+        return Some(self.subarrays().map(|row: VariableView<'_, T>| row.into_vec()).collect());
+    }
+
+    /// Copies the values of a 3-dimensional view into nested `Vec`s, or returns `None` if the view is not 3-dimensional.
+    pub fn to_vec_3d(&self) -> Option<Vec<Vec<Vec<T>>>> {
+        if self.num_dims() != 3 {
+            return None;
+        }
+        return self.subarrays().map(|matrix: VariableView<'_, T>| matrix.to_vec_2d()).collect();
+    }
+}
+
+impl<'a, 'b, T: Clone> Index<&'b [usize]> for VariableView<'a, T> {
+    type Output = T;
+
+    /// Returns the value located at `indices`, panics if the indices are not valid (see [`get`](#method.get)).
+    fn index(&self, indices: &'b [usize]) -> &T {
+        return match self.get(indices) {
+            Some(value) => value,
+            None => panic!("indices {:?} out of the shape {:?}", indices, self.shape),
+        };
+    }
+}
+
+impl<'a, T: Clone, const N: usize> Index<[usize; N]> for VariableView<'a, T> {
+    type Output = T;
+
+    /// Returns the value located at `indices`, panics if the indices are not valid (see [`get`](#method.get)).
+    fn index(&self, indices: [usize; N]) -> &T {
+        return &self[&indices[..]];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::VariableView;
+
+    #[test]
+    fn test_variable_view_scalar() {
+        let view: VariableView<f64> = VariableView::new(&[1.5], &[]).unwrap();
+        assert_eq!(0,                               view.num_dims());
+        assert_eq!(1.5,                             view[[]]);
+        assert_eq!(vec![&[1.5][..]],                view.rows().collect::<Vec<&[f64]>>());
+        assert_eq!(None,                            view.subarray(0));
+        assert_eq!(0,                               view.subarrays().count());
+        assert_eq!(None,                            VariableView::<f64>::new(&[], &[]));
+    }
+
+    #[test]
+    fn test_variable_view_empty() {
+        // no record
+        let view: VariableView<i16> = VariableView::from_vec(vec![], &[0, 3]).unwrap();
+        assert_eq!(true,                            view.is_empty());
+        assert_eq!(0,                               view.rows().count());
+        assert_eq!(Some(vec![]),                    view.to_vec_2d());
+        assert_eq!(None,                            view.get(&[0, 0]));
+        // no value per record
+        let view: VariableView<i16> = VariableView::from_vec(vec![], &[2, 0]).unwrap();
+        assert_eq!(Some(vec![vec![], vec![]]),      view.to_vec_2d());
+    }
+
+    #[test]
+    fn test_variable_view_indices() {
+        let data: Vec<u8> = (0..24).collect();
+        let view: VariableView<u8> = VariableView::new(&data, &[2, 3, 4]).unwrap();
+        assert_eq!(Some(23),                        view.flat_index(&[1, 2, 3]));
+        assert_eq!(Some(13),                        view.flat_index(&[1, 0, 1]));
+        assert_eq!(None,                            view.flat_index(&[1, 3, 0]));
+        assert_eq!(None,                            view.flat_index(&[1, 2]));
+        assert_eq!(13,                              view[&[1, 0, 1][..]]);
+        assert_eq!(6,                               view.rows().count());
+        assert_eq!(None,                            view.to_vec_2d());
+        assert_eq!(
+            Some(vec![vec![vec![12, 13, 14, 15], vec![16, 17, 18, 19], vec![20, 21, 22, 23]]]),
+            view.subarray(1).map(|record: VariableView<u8>| vec![record.to_vec_2d().unwrap()])
+        );
+        assert_eq!(Some(data.clone()),              view.to_vec_3d().map(|values| values.concat().concat()));
+        // the shape overflowing `usize`
+        assert_eq!(None,                            VariableView::new(&data, &[usize::MAX, 2, 12]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_variable_view_index_out_of_bounds() {
+        let view: VariableView<i8> = VariableView::from_vec(vec![0; 6], &[2, 3]).unwrap();
+        let _ = view[[2, 0]];
+    }
+}
@@ -1,8 +1,9 @@
 pub mod parse_header_error;
 pub use parse_header_error::ParseHeaderError;
 
-use std::rc::Rc;
-use crate::{Dimension, DataType};
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::{DataType, NameError, Version};
 
 /// NetCDF-3 data set error
 ///
@@ -39,6 +40,7 @@ use crate::{Dimension, DataType};
 /// assert_eq!(false,       data_set.has_var(VAR_NAME));
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum InvalidDataSet {
     DimensionAlreadyExists(String),
     DimensionNotDefined(String),
@@ -46,46 +48,156 @@ pub enum InvalidDataSet {
     DimensionsUsedMultipleTimes{var_name: String, get_dim_names: Vec<String>},
     UnlimitedDimensionAlreadyExists(String),
     DimensionYetUsed{var_names: Vec<String>, dim_name: String},
-    DimensionNameNotValid(String),
+    /// The name of the dimension does not follow the naming rules of the data set, `reason` being the rule broken.
+    DimensionNameNotValid{dim_name: String, reason: NameError},
     DimensionIdsNotFound{defined: Vec<usize>, searched: Vec<usize>, not_found: Vec<usize>},
     FixedDimensionWithZeroSize(String),
-    MaximumFixedDimensionSizeExceeded{dim_name: String, get: usize},
-    DimensionsNotFound{defined: Vec<Rc<Dimension>>, searched: Vec<Rc<Dimension>>, not_found: Vec<Rc<Dimension>>},
+    MaximumFixedDimensionSizeExceeded{dim_name: String, max: usize, get: usize},
+    DimensionsNotFound{defined: Vec<String>, searched: Vec<String>, not_found: Vec<String>},
+    /// No *unlimited-size* dimension is defined (see [`DataSet::append_record`](../struct.DataSet.html#method.append_record)).
+    UnlimitedDimensionNotDefined,
 
     VariableAttributeAlreadyExists{var_name: String, attr_name: String},
     VariableAttributeNotDefined{var_name: String, attr_name: String},
-    VariableAttributeNameNotValid{var_name: String, attr_name: String},
+    /// The name of the variable attribute does not follow the naming rules of the data set, `reason` being the rule broken.
+    VariableAttributeNameNotValid{var_name: String, attr_name: String, reason: NameError},
+    /// The data type of the attribute `_FillValue` or `missing_value` differs from the data type `req` of its variable.
+    VariableAttributeMismatchDataType{var_name: String, attr_name: String, req: DataType, get: DataType},
 
     VariableNotDefined(String),
-    VariableNameNotValid(String),
+    /// The name of the variable does not follow the naming rules of the data set, `reason` being the rule broken.
+    VariableNameNotValid{var_name: String, reason: NameError},
     VariableAlreadyExists(String),
     VariableMismatchDataType{var_name: String, req: DataType, get: DataType},
     VariableMismatchDataLength{var_name: String, req: usize, get: usize},
     UnlimitedDimensionMustBeDefinedFirst{var_name: String, unlim_dim_name: String, get_dim_names: Vec<String>},
-    MaximumDimensionsPerVariableExceeded{var_name: String, num_dims: usize},
+    MaximumDimensionsPerVariableExceeded{var_name: String, max: usize, num_dims: usize},
+    /// The chunk size of the variable exceeds the maximum size allowed by the NetCDF-3 version, and the variable is not the last one
+    /// of its kind (see [`DataSet::check_format_limits`](../struct.DataSet.html#method.check_format_limits)).
+    MaximumVariableSizeExceeded{var_name: String, version: Version, max: usize, get: usize},
+    /// The number of bytes of each chunk of the variable cannot be represented by `usize`.
+    VariableSizeOverflow(String),
+    /// Adding the record variable would make the record size overflow `usize`.
+    RecordSizeOverflow(String),
+    /// The variable is not a record variable (see [`DataSet::append_record`](../struct.DataSet.html#method.append_record)).
+    VariableNotRecord(String),
+    /// The values of the variable have not been loaded (see [`DataSet::append_record`](../struct.DataSet.html#method.append_record)).
+    VariableDataNotLoaded(String),
+    /// The record `index` does not exist, `num_records` records being defined (see [`DataSet::get_record`](../struct.DataSet.html#method.get_record)).
+    RecordIndexExceeded{index: usize, num_records: usize},
+    /// The size `dim_size` of the dimension exchanged with the *unlimited-size* dimension differs from the number of records
+    /// (`None` if the variable has no dimension, see [`DataSet::make_record_var`](../struct.DataSet.html#method.make_record_var)).
+    NumRecordsMismatch{var_name: String, num_records: usize, dim_size: Option<usize>},
+    FlagMeaningNotValid{var_name: String, flag_meaning: String},
+    FlagValueOutOfRange{var_name: String, flag_value: i32, data_type: DataType},
 
     GlobalAttributeAlreadyExists(String),
     GlobalAttributeNotDefined(String),
-    GlobalAttributeNameNotValid(String),
+    /// The name of the global attribute does not follow the naming rules of the data set, `reason` being the rule broken.
+    GlobalAttributeNameNotValid{attr_name: String, reason: NameError},
+    /// The global attribute has the data type `get`, the data type `req` is required (see [`DataSet::append_history`](../struct.DataSet.html#method.append_history)).
+    GlobalAttributeMismatchDataType{attr_name: String, req: DataType, get: DataType},
 }
 
-impl std::fmt::Display for InvalidDataSet {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for InvalidDataSet {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(f, "{:?}", self)
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for InvalidDataSet {}
 
+/// I/O error wrapped by the errors of the crate.
+///
+/// The underlying `std::io::Error` is shared by the clones of the error and returned by their method `source`.
+/// Two I/O errors are equal if they have the same kind.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct IOError(std::sync::Arc<std::io::Error>);
+
+#[cfg(feature = "std")]
+impl IOError {
+    /// Returns the kind of the underlying `std::io::Error`.
+    pub fn kind(&self) -> std::io::ErrorKind {
+        return self.0.kind();
+    }
+}
+
+#[cfg(feature = "std")]
+impl AsRef<std::io::Error> for IOError {
+    fn as_ref(&self) -> &std::io::Error {
+        return &self.0;
+    }
+}
+
+#[cfg(feature = "std")]
+impl PartialEq for IOError {
+    fn eq(&self, other: &Self) -> bool {
+        return self.kind() == other.kind();
+    }
+}
+
+#[cfg(feature = "std")]
+impl Eq for IOError {}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for IOError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IOError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        return Some(self.as_ref());
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::convert::From<std::io::Error> for IOError {
+    fn from(err: std::io::Error) -> Self {
+        Self(std::sync::Arc::new(err))
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::convert::From<std::io::ErrorKind> for IOError {
+    fn from(kind: std::io::ErrorKind) -> Self {
+        Self::from(std::io::Error::from(kind))
+    }
+}
+
+/// Error occuring while decoding a NetCDF-3 header or reading a NetCDF-3 file.
+///
+/// The variant `IO` requires the feature `std`, the other ones are also returned by
+/// [`decode_header`](../fn.decode_header.html) in the `no_std` builds.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ReadError {
     ParseHeader(ParseHeaderError),
     DataSet(InvalidDataSet),
     VariableNotDefined(String),
     VariableMismatchDataType{var_name: String, req: DataType, get: DataType},
-    IOErrorKind(std::io::ErrorKind),
+    #[cfg(feature = "std")]
+    IO(IOError),
     ComputationNumberOfRecords,
     RecordIndexExceeded{index: usize, num_records: usize},
+    /// The offset of the record cannot be represented by `u64`.
+    RecordOffsetOverflow{var_name: String, record_index: usize},
+    /// Reading the variable requires more bytes than allowed (see [`FileReader::set_max_alloc_bytes`](../struct.FileReader.html#method.set_max_alloc_bytes)).
+    MaximumAllocationExceeded{var_name: String, max: usize, get: usize},
+    /// The data of the variable declared in the header end beyond the end of the input file (`end_offset` being
+    /// the offset of the end of the data, or the number of bytes to read).
+    VariableDataOutOfFile{var_name: String, end_offset: u64, file_size: u64},
+    /// The data of the variable begin before the end of the data of the previous variable, the *fixed-size* variables being
+    /// stored before the record variables, both in the order of the header (see
+    /// [`ReadOptions::allow_overlapping_vars`](../struct.ReadOptions.html#structfield.allow_overlapping_vars)).
+    OverlappingVariables{var_name: String, begin_offset: u64, prev_var_name: String, prev_end_offset: u64},
+    /// The slice `start..(start + count)` does not have the rank of the variable, or exceeds its shape
+    /// (see [`FileReader::read_var_slice`](../struct.FileReader.html#method.read_var_slice)).
+    SliceOutOfBounds{var_name: String, start: Vec<usize>, count: Vec<usize>, shape: Vec<usize>},
     Unexpected,
 }
 
@@ -105,49 +217,485 @@ impl ReadError {
     }
 }
 
-impl std::fmt::Display for ReadError {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for ReadError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(f, "{:?}", self)
     }
 }
 
-impl std::error::Error for ReadError {}
+#[cfg(feature = "std")]
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        return match self {
+            ReadError::ParseHeader(err) => Some(err),
+            ReadError::DataSet(err) => Some(err),
+            ReadError::IO(err) => Some(err.as_ref()),
+            _ => None,
+        };
+    }
+}
 
-impl std::convert::From<InvalidDataSet> for ReadError {
+impl core::convert::From<InvalidDataSet> for ReadError {
     fn from(err: InvalidDataSet) -> Self {
         Self::DataSet(err)
     }
 }
 
-impl std::convert::From<ParseHeaderError> for ReadError {
+impl core::convert::From<ParseHeaderError> for ReadError {
     fn from(err: ParseHeaderError) -> Self {
         Self::ParseHeader(err)
     }
 }
 
-impl std::convert::From<std::io::Error> for ReadError {
+#[cfg(feature = "std")]
+impl core::convert::From<std::io::Error> for ReadError {
     fn from(err: std::io::Error) -> Self {
-        Self::IOErrorKind(err.kind())
+        Self::IO(IOError::from(err))
     }
 }
 
 
+/// Error occuring while encoding a NetCDF-3 header or writing a NetCDF-3 file.
+///
+/// The variant `IO` requires the feature `std`, the other ones are also returned by
+/// [`encode_header`](../fn.encode_header.html) and [`encode_chunk`](../fn.encode_chunk.html) in the `no_std` builds.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum WriteError {
-    IOErrorKind(std::io::ErrorKind),
+    #[cfg(feature = "std")]
+    IO(IOError),
+    DataSet(InvalidDataSet),
     VariableNotDefined(String),
     VariableMismatchDataType{var_name: String, req: DataType, get: DataType},
     VariableMismatchDataLength{var_name: String, req: usize, get: usize},
-    ClassicVersionNotPossible,
+    /// The begin offset of the variable exceeds the maximum offset of the NetCDF-3 version (`2^31 - 1` for the classic version).
+    MaximumBeginOffsetExceeded{var_name: String, version: Version, max: u64, get: u64},
     HeaderAlreadyDefined,
     HeaderNotDefined,
+    /// The header size, padded to a multiple of 4 bytes, cannot be represented by `usize`.
+    HeaderSizeOverflow{header_min_size: usize},
     RecordIndexExceeded{index: usize, num_records: usize},
     RecordMismatchDataLength{var_name: String, req: usize, get: usize},
+    /// The variable is not a *record* variable.
+    VariableNotRecord(String),
+    /// The offset of the record cannot be represented by `u64`.
+    RecordOffsetOverflow{var_name: String, record_index: usize},
+    /// The file opened to append records cannot be read or completed (see [`FileWriter::open_append`](struct.FileWriter.html#method.open_append)).
+    #[cfg(feature = "read")]
+    Repair(RepairError),
+    /// The definition of the variable, or of the number of records, does not match the file opened to append records.
+    AppendDefinitionMismatch(String),
+    Unexpected,
+}
+
+impl core::fmt::Display for WriteError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WriteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        return match self {
+            WriteError::IO(err) => Some(err.as_ref()),
+            WriteError::DataSet(err) => Some(err),
+            #[cfg(feature = "read")]
+            WriteError::Repair(err) => Some(err),
+            _ => None,
+        };
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::convert::From<std::io::Error> for WriteError {
+    fn from(err: std::io::Error) -> Self {
+        WriteError::IO(IOError::from(err))
+    }
+}
+
+#[cfg(feature = "read")]
+impl core::convert::From<RepairError> for WriteError {
+    fn from(err: RepairError) -> Self {
+        WriteError::Repair(err)
+    }
+}
+
+impl core::convert::From<InvalidDataSet> for WriteError {
+    fn from(err: InvalidDataSet) -> Self {
+        WriteError::DataSet(err)
+    }
+}
+
+/// Error occuring while copying a NetCDF-3 file (see [`copy_nc3`](../fn.copy_nc3.html)).
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CopyError {
+    Read(ReadError),
+    Write(WriteError),
+    DataSet(InvalidDataSet),
+    VariableNotDefined(String),
+    DimensionNotDefined(String),
+    /// The range of indices is out of the dimension, is empty for a *fixed-size* dimension, or the dimension is sliced several times.
+    DimSliceNotValid{dim_name: String, start: usize, count: usize, dim_size: usize},
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for CopyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CopyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        return match self {
+            CopyError::Read(err) => Some(err),
+            CopyError::Write(err) => Some(err),
+            CopyError::DataSet(err) => Some(err),
+            _ => None,
+        };
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::convert::From<ReadError> for CopyError {
+    fn from(err: ReadError) -> Self {
+        Self::Read(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::convert::From<WriteError> for CopyError {
+    fn from(err: WriteError) -> Self {
+        Self::Write(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::convert::From<InvalidDataSet> for CopyError {
+    fn from(err: InvalidDataSet) -> Self {
+        Self::DataSet(err)
+    }
+}
+
+/// Error occuring while concatenating NetCDF-3 files (see [`concat_nc3`](../fn.concat_nc3.html)).
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConcatError {
+    Read(ReadError),
+    Write(WriteError),
+    DataSet(InvalidDataSet),
+    NoInputFiles,
+    UnlimitedDimNotDefined,
+    /// The dimension is not defined in one of the files, or its size differs from the one of the first file.
+    DimensionMismatch{file_index: usize, dim_name: String},
+    /// The variable is not defined in one of the files, or its dimensions or its data type differ from the ones of the first file.
+    VariableMismatch{file_index: usize, var_name: String},
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for ConcatError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConcatError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        return match self {
+            ConcatError::Read(err) => Some(err),
+            ConcatError::Write(err) => Some(err),
+            ConcatError::DataSet(err) => Some(err),
+            _ => None,
+        };
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::convert::From<ReadError> for ConcatError {
+    fn from(err: ReadError) -> Self {
+        Self::Read(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::convert::From<WriteError> for ConcatError {
+    fn from(err: WriteError) -> Self {
+        Self::Write(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::convert::From<InvalidDataSet> for ConcatError {
+    fn from(err: InvalidDataSet) -> Self {
+        Self::DataSet(err)
+    }
+}
+
+/// Error occuring while repairing a truncated NetCDF-3 file (see [`repair_nc3`](../fn.repair_nc3.html))
+/// or while dropping its last records (see [`truncate_records_nc3`](../fn.truncate_records_nc3.html)).
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RepairError {
+    Read(ReadError),
+    IO(IOError),
+    /// The file ends before the end of the *fixed-size* variables data, only the record data can be recovered.
+    FixedSizeDataTruncated{required_size: u64, file_size: u64},
+    /// The number of records exceeds the maximum number of records storable in the header.
+    NumberOfRecordsExceeded(usize),
+    /// More records are dropped than stored in the file (see [`truncate_records_nc3`](../fn.truncate_records_nc3.html)).
+    NotEnoughRecords{num_records: usize, num_dropped_records: usize},
+}
+
+#[cfg(feature = "std")]
+impl core::fmt::Display for RepairError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RepairError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        return match self {
+            RepairError::Read(err) => Some(err),
+            RepairError::IO(err) => Some(err.as_ref()),
+            _ => None,
+        };
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::convert::From<ReadError> for RepairError {
+    fn from(err: ReadError) -> Self {
+        Self::Read(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::convert::From<std::io::Error> for RepairError {
+    fn from(err: std::io::Error) -> Self {
+        Self::IO(IOError::from(err))
+    }
+}
+
+/// Error occuring while editing the header of a NetCDF-3 file (see [`edit_nc3`](../fn.edit_nc3.html)).
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EditError {
+    Read(ReadError),
+    Write(WriteError),
+    DataSet(InvalidDataSet),
+    IO(IOError),
+    /// The edition has changed the data type or the shape of the variables.
+    DataLayoutChanged,
     Unexpected,
 }
 
-impl std::convert::From<std::io::Error> for WriteError {
+#[cfg(feature = "std")]
+impl core::fmt::Display for EditError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EditError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        return match self {
+            EditError::Read(err) => Some(err),
+            EditError::Write(err) => Some(err),
+            EditError::DataSet(err) => Some(err),
+            EditError::IO(err) => Some(err.as_ref()),
+            _ => None,
+        };
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::convert::From<ReadError> for EditError {
+    fn from(err: ReadError) -> Self {
+        Self::Read(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::convert::From<WriteError> for EditError {
+    fn from(err: WriteError) -> Self {
+        Self::Write(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::convert::From<InvalidDataSet> for EditError {
+    fn from(err: InvalidDataSet) -> Self {
+        Self::DataSet(err)
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::convert::From<std::io::Error> for EditError {
     fn from(err: std::io::Error) -> Self {
-        WriteError::IOErrorKind(err.kind())
+        Self::IO(IOError::from(err))
+    }
+}
+
+/// Error occuring while decoding CF time coordinates (see [`cf::decode_time`](../cf/fn.decode_time.html)).
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeTimeError {
+    UnitsNotDefined(String),
+    UnitsNotValid{var_name: String, units: String},
+    CalendarNotSupported{var_name: String, calendar: String},
+    DateTimeOutOfRange{var_name: String, index: usize},
+}
+
+#[cfg(feature = "chrono")]
+impl core::fmt::Display for DecodeTimeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{:?}", self)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(feature = "chrono")]
+impl std::error::Error for DecodeTimeError {}
+
+/// Error occuring while selecting the records from a time interval (see [`cf::select_records_by_time`](../cf/fn.select_records_by_time.html)).
+#[cfg(feature = "chrono")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SelectTimeError {
+    Read(ReadError),
+    DecodeTime(DecodeTimeError),
+    UnlimitedDimensionNotDefined,
+    /// The coordinate variable of the *unlimited-size* dimension is not defined.
+    TimeCoordinateNotDefined(String),
+}
+
+#[cfg(feature = "chrono")]
+impl core::fmt::Display for SelectTimeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl std::error::Error for SelectTimeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        return match self {
+            SelectTimeError::Read(err) => Some(err),
+            SelectTimeError::DecodeTime(err) => Some(err),
+            _ => None,
+        };
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl core::convert::From<ReadError> for SelectTimeError {
+    fn from(err: ReadError) -> Self {
+        Self::Read(err)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl core::convert::From<DecodeTimeError> for SelectTimeError {
+    fn from(err: DecodeTimeError) -> Self {
+        Self::DecodeTime(err)
+    }
+}
+
+/// Error occuring while reading an OPeNDAP data set (see [`DapReader`](../struct.DapReader.html)).
+#[cfg(feature = "dap")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DapError {
+    HttpRequest{url: String, message: String},
+    DdsNotValid(String),
+    DasNotValid(String),
+    DataNotValid(String),
+    TypeNotSupported{var_name: String, dap_type: String},
+    VariableNotDefined(String),
+    DataSet(InvalidDataSet),
+    /// Decoding the variable requires more bytes than allowed (see [`DapReader::set_max_alloc_bytes`](../struct.DapReader.html#method.set_max_alloc_bytes)).
+    MaximumAllocationExceeded{var_name: String, max: usize, get: usize},
+}
+
+#[cfg(feature = "dap")]
+impl core::fmt::Display for DapError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature = "dap")]
+impl std::error::Error for DapError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        return match self {
+            DapError::DataSet(err) => Some(err),
+            _ => None,
+        };
+    }
+}
+
+#[cfg(feature = "dap")]
+impl core::convert::From<InvalidDataSet> for DapError {
+    fn from(err: InvalidDataSet) -> Self {
+        Self::DataSet(err)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+
+    use std::error::Error;
+
+    use super::{CopyError, IOError, InvalidDataSet, ParseHeaderError, ReadError, WriteError};
+
+    #[test]
+    fn test_errors_are_send_and_sync() {
+        fn assert_send_sync<E: Error + Send + Sync + 'static>() {}
+        assert_send_sync::<InvalidDataSet>();
+        assert_send_sync::<ParseHeaderError>();
+        assert_send_sync::<ReadError>();
+        assert_send_sync::<WriteError>();
+        assert_send_sync::<CopyError>();
+    }
+
+    #[test]
+    fn test_io_error_source() {
+        let io_err = std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "end of file");
+        let read_err: ReadError = ReadError::from(io_err);
+        assert_eq!(ReadError::IO(IOError::from(std::io::ErrorKind::UnexpectedEof)),     read_err);
+
+        let source: &(dyn Error + 'static) = read_err.source().unwrap();
+        let io_err: &std::io::Error = source.downcast_ref::<std::io::Error>().unwrap();
+        assert_eq!(std::io::ErrorKind::UnexpectedEof,       io_err.kind());
+        assert_eq!("end of file",                           io_err.to_string());
+        // the clones share the same `std::io::Error`
+        assert_eq!("end of file",                           read_err.clone().source().unwrap().to_string());
+    }
+
+    #[test]
+    fn test_source_chaining() {
+        let data_set_err = InvalidDataSet::VariableNotDefined(String::from("var"));
+        let copy_err: CopyError = CopyError::Read(ReadError::from(data_set_err.clone()));
+
+        let read_err: &(dyn Error + 'static) = copy_err.source().unwrap();
+        assert_eq!(Some(&ReadError::DataSet(data_set_err.clone())),     read_err.downcast_ref::<ReadError>());
+        let source: &(dyn Error + 'static) = read_err.source().unwrap();
+        assert_eq!(Some(&data_set_err),                                 source.downcast_ref::<InvalidDataSet>());
+        assert!(source.source().is_none());
+
+        let write_err = WriteError::from(data_set_err.clone());
+        assert_eq!(Some(&data_set_err),                                 write_err.source().unwrap().downcast_ref::<InvalidDataSet>());
+        assert!(WriteError::HeaderNotDefined.source().is_none());
+    }
+}
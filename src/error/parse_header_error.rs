@@ -1,20 +1,37 @@
 
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
 
-// aliases
-pub(crate) type NomErrorKind = nom::error::ErrorKind;
-pub(crate) type NomError<'a> = nom::Err<(&'a[u8], NomErrorKind)>;
+use core::num::NonZeroUsize;
 
+/// Error occuring while decoding a word of the header, located from the start of the header.
 #[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DecodeError {
+    /// The header ends before the word, `needed` bytes are missing.
+    Incomplete{needed: usize, offset: usize},
+    /// The bytes of the word are not valid.
+    Invalid{bytes: Vec<u8>, offset: usize},
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct ParseHeaderError {
     pub kind: ParseHeaderErrorKind,
     pub invalid_bytes: InvalidBytes,
+    /// Position of the invalid word from the start of the header.
+    pub offset: usize,
 }
 
 impl ParseHeaderError {
 
-    pub(crate) fn new<'a>(err: NomError<'a>, kind: ParseHeaderErrorKind) -> Self {
+    pub(crate) fn new(err: DecodeError, kind: ParseHeaderErrorKind) -> Self {
+        let offset: usize = match err {
+            DecodeError::Incomplete{needed: _, offset} => offset,
+            DecodeError::Invalid{bytes: _, offset} => offset,
+        };
         Self {
             kind: kind,
+            offset: offset,
             invalid_bytes: InvalidBytes::from(err),
         }
     }
@@ -27,24 +44,52 @@ impl ParseHeaderError {
     }
 }
 
+impl core::fmt::Display for ParseHeaderError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseHeaderError {}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum InvalidBytes {
-    Incomplete(nom::Needed),
+    Incomplete(Needed),
     Bytes(Vec<u8>)
 }
 
-impl<'a> std::convert::From<NomError<'a>> for InvalidBytes {
-    fn from(err: NomError<'a>) -> Self {
+impl core::convert::From<DecodeError> for InvalidBytes {
+    fn from(err: DecodeError) -> Self {
         match err {
-            NomError::Incomplete(needed) => InvalidBytes::Incomplete(needed),
-            NomError::Error((err_bytes, _err_kind)) => InvalidBytes::Bytes(err_bytes.to_owned()),
-            NomError::Failure((err_bytes, _err_kind)) => InvalidBytes::Bytes(err_bytes.to_owned()),
+            DecodeError::Incomplete{needed, offset: _} => InvalidBytes::Incomplete(Needed::new(needed)),
+            DecodeError::Invalid{bytes, offset: _} => InvalidBytes::Bytes(bytes),
         }
     }
 }
 
+/// Number of the missing bytes of an incomplete header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Needed {
+    /// The number of missing bytes is unknown.
+    Unknown,
+    /// At least this number of bytes is missing.
+    Size(NonZeroUsize),
+}
+
+impl Needed {
+    /// Returns `Needed::Size(num_bytes)`, or `Needed::Unknown` if `num_bytes` is zero.
+    pub fn new(num_bytes: usize) -> Self {
+        return match NonZeroUsize::new(num_bytes) {
+            Some(num_bytes) => Needed::Size(num_bytes),
+            None => Needed::Unknown,
+        };
+    }
+}
+
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ParseHeaderErrorKind {
     MagicWord,
     VersionNumber,
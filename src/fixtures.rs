@@ -0,0 +1,55 @@
+//! Sample NetCDF-3 files (requires the feature `fixtures`), allowing the downstream crates and the examples
+//! to exercise realistic files without any setup.
+//!
+//! Each file is provided by its name and its bytes, embedded into the library.
+//!
+//! # Example
+//!
+//! ```
+//! use std::io::Cursor;
+//! use netcdf3::FileReader;
+//! use netcdf3::fixtures::NC3_CLASSIC_FILE_BYTES;
+//!
+//! let file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+//! assert_eq!(true,            file_reader.data_set().has_var("temperature_f32"));
+//! ```
+
+/// Name of the file containing an empty data set.
+pub static EMPTY_DATA_SET_FILE_NAME: &str = "empty_data_set.nc";
+/// Empty data set.
+pub static EMPTY_DATA_SET_FILE_BYTES: &[u8] = include_bytes!("../data/empty_data_set.nc");
+
+/// Name of the classic file containing 3D temperatures.
+pub static NC3_CLASSIC_FILE_NAME: &str = "temp_3D_classic.nc";
+/// Classic file containing 3D temperatures (`time`, `latitude`, `longitude`) of all the data types.
+pub static NC3_CLASSIC_FILE_BYTES: &[u8] = include_bytes!("../data/temp_3D_classic.nc");
+
+/// Name of the 64-bit offset file containing 3D temperatures.
+pub static NC3_64BIT_OFFSET_FILE_NAME: &str = "temp_3D_64bit_offset.nc";
+/// 64-bit offset file containing the same data set as [`NC3_CLASSIC_FILE_BYTES`](static.NC3_CLASSIC_FILE_BYTES.html).
+pub static NC3_64BIT_OFFSET_FILE_BYTES: &[u8] = include_bytes!("../data/temp_3D_64bit_offset.nc");
+
+/// Name of the file containing the default fill values.
+pub static NC3_FILL_VALUES_FILE_NAME: &str = "nc_fill_values.nc";
+/// Scalar variables containing the default `NC_FILL` values.
+pub static NC3_FILL_VALUES_FILE_BYTES: &[u8] = include_bytes!("../data/nc_fill_values.nc");
+
+/// Name of the file containing scalar variables.
+pub static SCALAR_VARIABLES_FILE_NAME: &str = "scalar_vars.nc";
+/// Scalar variables of all the data types.
+pub static SCALAR_VARIABLES_FILE_BYTES: &[u8] = include_bytes!("../data/scalar_vars.nc");
+
+/// Name of the light classic file.
+pub static NC3_LIGHT_CLASSIC_FILE_NAME: &str = "temp_3D_classic_light.nc";
+/// Light version of the classic file containing 3D temperatures, used in the documentation examples.
+pub static NC3_LIGHT_CLASSIC_FILE_BYTES: &[u8] = include_bytes!("../data/temp_3D_classic_light.nc");
+
+/// Name of the file containing a zero-sized unlimited dimension.
+pub static NC3_ZERO_SIZED_UNLIMITED_DIM_FILE_NAME: &str = "zero_sized_unlimited_dim.nc";
+/// Data set containing only a zero-sized *unlimited-size* dimension.
+pub static NC3_ZERO_SIZED_UNLIMITED_DIM_FILE_BYTES: &[u8] = include_bytes!("../data/zero_sized_unlimited_dim.nc");
+
+/// Name of the file containing unset values.
+pub static NC3_CONTAINING_DEFAULT_FILL_VALUES_FILE_NAME: &str = "containing_default_fill_values.nc";
+/// Variables partially written, the unset values being the default fill values.
+pub static NC3_CONTAINING_DEFAULT_FILL_VALUES_FILE_BYTES: &[u8] = include_bytes!("../data/containing_default_fill_values.nc");
@@ -0,0 +1,118 @@
+//! Conversions between the multi-dimensional indices of the values of a variable and their positions in the flat data.
+//!
+//! The values are stored in row-major order (the last dimension varying the fastest), as in the NetCDF-3 files.
+//! These functions are used by the hyperslab reads, the copies of slices and [`VariableView`](../struct.VariableView.html).
+//!
+//! # Example
+//!
+//! ```
+//! use netcdf3::index::{compute_flat_index, compute_strides, unravel_index};
+//!
+//! // `temperature_f32` : (time: 2, latitude: 3, longitude: 5)
+//! let shape: [usize; 3] = [2, 3, 5];
+//! assert_eq!(Some(vec![15, 5, 1]),    compute_strides(&shape));
+//! assert_eq!(Some(22),                compute_flat_index(&shape, &[1, 1, 2]));
+//! assert_eq!(Some(vec![1, 1, 2]),     unravel_index(&shape, 22));
+//!
+//! // out of bounds
+//! assert_eq!(None,                    compute_flat_index(&shape, &[2, 0, 0]));
+//! assert_eq!(None,                    unravel_index(&shape, 30));
+//! ```
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+
+/// Returns the number of values between two consecutive indices along each dimension of the shape `shape`,
+/// or `None` if the number of values overflows `usize`.
+///
+/// The stride of the last dimension is `1`.
+pub fn compute_strides(shape: &[usize]) -> Option<Vec<usize>> {
+    let mut strides: Vec<usize> = vec![1; shape.len()];
+    let mut stride: usize = 1;
+    for (dim_stride, dim_size) in strides.iter_mut().zip(shape.iter()).rev() {
+        *dim_stride = stride;
+        stride = stride.checked_mul(*dim_size)?;
+    }
+    return Some(strides);
+}
+
+/// Returns the position in the flat data of the value located at `indices` (one index per dimension of the shape `shape`),
+/// or `None` if the number of indices differs from the number of dimensions or if an index is out of bounds.
+///
+/// The scalar variables (empty shape) have one value, located at the empty indices.
+pub fn compute_flat_index(shape: &[usize], indices: &[usize]) -> Option<usize> {
+    if indices.len() != shape.len() {
+        return None;
+    }
+    let mut flat_index: usize = 0;
+    for (index, dim_size) in indices.iter().zip(shape.iter()) {
+        if index >= dim_size {
+            return None;
+        }
+        flat_index = flat_index.checked_mul(*dim_size)?.checked_add(*index)?;
+    }
+    return Some(flat_index);
+}
+
+/// Returns the indices (one per dimension of the shape `shape`) of the value located at the position `flat_index` in the flat data,
+/// or `None` if the position is out of bounds.
+///
+/// This is the inverse of [`compute_flat_index`](fn.compute_flat_index.html).
+pub fn unravel_index(shape: &[usize], flat_index: usize) -> Option<Vec<usize>> {
+    let num_values: usize = shape.iter().try_fold(1_usize, |num_values: usize, dim_size: &usize| num_values.checked_mul(*dim_size))?;
+    if flat_index >= num_values {
+        return None;
+    }
+    let mut indices: Vec<usize> = vec![0; shape.len()];
+    let mut remainder: usize = flat_index;
+    for (index, dim_size) in indices.iter_mut().zip(shape.iter()).rev() {
+        *index = remainder % dim_size;
+        remainder /= dim_size;
+    }
+    return Some(indices);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{compute_flat_index, compute_strides, unravel_index};
+
+    #[test]
+    fn test_compute_strides() {
+        assert_eq!(Some(vec![]),                    compute_strides(&[]));
+        assert_eq!(Some(vec![1]),                   compute_strides(&[7]));
+        assert_eq!(Some(vec![12, 4, 1]),            compute_strides(&[2, 3, 4]));
+        // the size of the first dimension does not change the strides
+        assert_eq!(Some(vec![12, 4, 1]),            compute_strides(&[0, 3, 4]));
+        assert_eq!(None,                            compute_strides(&[1, usize::MAX, 2]));
+    }
+
+    #[test]
+    fn test_compute_flat_index() {
+        // scalar
+        assert_eq!(Some(0),                         compute_flat_index(&[], &[]));
+        assert_eq!(None,                            compute_flat_index(&[], &[0]));
+
+        assert_eq!(Some(0),                         compute_flat_index(&[2, 3, 4], &[0, 0, 0]));
+        assert_eq!(Some(13),                        compute_flat_index(&[2, 3, 4], &[1, 0, 1]));
+        assert_eq!(Some(23),                        compute_flat_index(&[2, 3, 4], &[1, 2, 3]));
+        assert_eq!(None,                            compute_flat_index(&[2, 3, 4], &[1, 3, 0]));
+        assert_eq!(None,                            compute_flat_index(&[2, 3, 4], &[1, 2]));
+        assert_eq!(None,                            compute_flat_index(&[2, 0, 4], &[0, 0, 0]));
+    }
+
+    #[test]
+    fn test_unravel_index() {
+        assert_eq!(Some(vec![]),                    unravel_index(&[], 0));
+        assert_eq!(None,                            unravel_index(&[], 1));
+        assert_eq!(None,                            unravel_index(&[2, 0, 4], 0));
+        assert_eq!(None,                            unravel_index(&[usize::MAX, 2], 0));
+
+        let shape: [usize; 3] = [2, 3, 4];
+        for flat_index in 0..24 {
+            let indices: Vec<usize> = unravel_index(&shape, flat_index).unwrap();
+            assert_eq!(Some(flat_index),            compute_flat_index(&shape, &indices));
+        }
+        assert_eq!(Some(vec![1, 2, 3]),             unravel_index(&shape, 23));
+        assert_eq!(None,                            unravel_index(&shape, 24));
+    }
+}
@@ -1,11 +1,109 @@
-
-
+#[cfg(feature = "read")]
 mod file_reader;
+#[cfg(feature = "write")]
 mod file_writer;
+#[cfg(feature = "dap")]
+mod dap_reader;
+#[cfg(feature = "http")]
+mod http_range_reader;
+#[cfg(feature = "mmap")]
+mod mmap_writer;
+#[cfg(feature = "std")]
+mod json;
+#[cfg(feature = "std")]
+mod raw_export;
+mod header_decoder;
+mod header;
+#[cfg(feature = "read")]
+mod manifest;
+#[cfg(all(feature = "read", feature = "write"))]
+mod copy;
+#[cfg(feature = "read")]
+mod repair;
+#[cfg(all(feature = "read", feature = "write"))]
+mod concat;
+#[cfg(all(feature = "read", feature = "write"))]
+mod optimize;
+#[cfg(feature = "checksums")]
+mod checksums;
+#[cfg(all(feature = "read", feature = "write"))]
+mod edit;
+#[cfg(feature = "read")]
+mod validate;
+#[cfg(feature = "read")]
+mod compare;
+mod diagnostics;
+#[cfg(any(feature = "read", feature = "write"))]
+mod variable_handle;
+#[cfg(all(feature = "read", feature = "write"))]
+mod nc_record;
+#[cfg(any(feature = "read", feature = "write"))]
+mod io_metrics;
+#[cfg(feature = "write")]
+mod write_summary;
+mod sniff;
 mod tests_io;
 
+pub use header::{decode_header, encode_chunk, encode_header};
+pub use header::{DuplicateAttrPolicy, DuplicateDimPolicy, LongNamePolicy, NameDecoding, PaddingCheck, ReadOptions};
+#[cfg(feature = "read")]
 pub use file_reader::FileReader;
-pub use file_writer::FileWriter;
+#[cfg(feature = "write")]
+pub use file_writer::{recommended_header_min_size, FileWriter, WriteOptions};
+#[cfg(feature = "dap")]
+pub use dap_reader::DapReader;
+#[cfg(feature = "http")]
+pub use http_range_reader::{HttpRangeReader, RangeBackend, ReqwestBackend};
+#[cfg(feature = "http-ureq")]
+pub use http_range_reader::UreqBackend;
+#[cfg(feature = "mmap")]
+pub use mmap_writer::MmapWriter;
+#[cfg(feature = "std")]
+pub use raw_export::{raw_sidecar_json, write_raw_data};
+#[cfg(all(feature = "read", feature = "write"))]
+pub use copy::{copy_nc3, copy_var, CopyOptions, DimSlice};
+#[cfg(all(feature = "read", feature = "write", any(test, feature = "testing")))]
+pub(crate) use copy::write_var_data;
+#[cfg(feature = "read")]
+pub use repair::{repair_nc3, RepairReport, truncate_records_nc3, TruncateReport};
+#[cfg(all(feature = "fs", feature = "read", feature = "write"))]
+pub(crate) use repair::RepairPlan;
+#[cfg(all(feature = "fs", feature = "read"))]
+pub use repair::{repair_nc3_file, truncate_records_nc3_file};
+#[cfg(all(feature = "read", feature = "write"))]
+pub use concat::concat_nc3;
+#[cfg(all(feature = "read", feature = "write"))]
+pub use optimize::{optimize_nc3, OptimizeOptions};
+#[cfg(all(feature = "fs", feature = "read", feature = "write"))]
+pub use optimize::optimize_nc3_file;
+#[cfg(feature = "checksums")]
+pub use checksums::{compute_checksums, read_checksums, verify_checksums, write_checksums, ChecksumMismatch};
+#[cfg(all(feature = "read", feature = "write"))]
+pub use edit::{edit_nc3, EditMode};
+#[cfg(all(feature = "fs", feature = "read", feature = "write"))]
+pub use edit::edit_nc3_file;
+#[cfg(feature = "read")]
+pub use validate::validate_nc3;
+#[cfg(feature = "read")]
+pub use compare::{compare_nc3, Divergence, Tolerance};
+#[cfg(all(feature = "fs", feature = "read"))]
+pub use compare::{files_equal, first_divergence};
+pub use diagnostics::{Diagnostic, Diagnostics, LayoutViolation};
+#[cfg(feature = "read")]
+pub use variable_handle::VariableReader;
+#[cfg(feature = "write")]
+pub use variable_handle::VariableWriter;
+#[cfg(all(feature = "read", feature = "write"))]
+pub use nc_record::{NcRecord, NcValue};
+#[cfg(any(feature = "read", feature = "write"))]
+pub use io_metrics::IoMetrics;
+#[cfg(feature = "write")]
+pub use write_summary::{VerifyReport, WriteSummary};
+pub use sniff::{sniff, FileKind, SNIFF_NUM_BYTES};
+#[cfg(feature = "fs")]
+pub use sniff::sniff_file;
+
+use crate::{Attribute, DataSet, Version};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub (crate) enum Offset {
@@ -13,7 +111,7 @@ pub (crate) enum Offset {
     I64(i64),
 }
 
-impl std::convert::From<Offset> for i64 {
+impl core::convert::From<Offset> for i64 {
 
     fn from(offset: Offset) -> Self
     {
@@ -44,4 +142,119 @@ pub fn compute_padding_size(num_bytes: usize) -> usize {
         0 => 0,
         n => ALIGNMENT_SIZE - n,
     };
-}
\ No newline at end of file
+}
+
+#[inline]
+/// Compute the offset of a record from the start of the file, or `None` if it overflows `u64`.
+///
+/// Arguments :
+/// - `begin_offset` : the offset of the first record of the variable
+/// - `record_index` : the index of the record
+/// - `record_size` : the number of bytes of each record
+#[cfg(any(feature = "read", feature = "write"))]
+pub(crate) fn compute_record_offset(begin_offset: u64, record_index: usize, record_size: usize) -> Option<u64> {
+    let record_index = <u64 as core::convert::TryFrom<usize>>::try_from(record_index).ok()?;
+    let record_size = <u64 as core::convert::TryFrom<usize>>::try_from(record_size).ok()?;
+    return begin_offset.checked_add(record_index.checked_mul(record_size)?);
+}
+
+/// Computes and returns the size (number of bytes) needed to write the file header.
+///
+/// The header only describes names and attribute values held in memory, then its size cannot overflow `usize`.
+pub(crate) fn compute_header_required_size(data_set: &DataSet, version: Version) -> usize
+{
+    fn compute_name_string_size(name: &str) -> usize {
+        let mut num_bytes: usize = 0;
+        // the number bytes for the name
+        num_bytes += core::mem::size_of::<i32>();
+        // the bytes of the name
+        let num_bytes_name = name.len();
+        num_bytes += num_bytes_name;
+        // the bytes of the zero-padding
+        num_bytes += compute_padding_size(num_bytes_name);
+
+        return num_bytes;
+    }
+    fn compute_attrs_list_size(attrs_list: &[Attribute]) -> usize {
+        let mut num_bytes: usize = 0;
+        // the global attributes
+        if attrs_list.is_empty() {
+            num_bytes += ABSENT_TAG.len();
+        }
+        else {
+            // the tag `ATTRIBUTE_TAG`
+            num_bytes += ATTRIBUTE_TAG.len();
+            // the number of attributes
+            num_bytes += core::mem::size_of::<i32>();
+            for attr in attrs_list.iter() {
+                // the name of the attributes
+                num_bytes += compute_name_string_size(&attr.name);
+                // the attribute data type
+                num_bytes += core::mem::size_of::<i32>();
+                // the number of elements
+                num_bytes += core::mem::size_of::<i32>();
+                // the ttribute data
+                let num_useful_bytes = attr.len() * attr.data_type().size_of();
+                num_bytes += num_useful_bytes;
+                // Zero-passing
+                num_bytes += compute_padding_size(num_useful_bytes);
+            }
+        }
+        return num_bytes;
+    }
+    let mut num_bytes = 0;
+    // the magic word `"CDF"`
+    num_bytes += 3;
+    // the version number
+    num_bytes += core::mem::size_of::<u8>();
+    // the length of the *unlimited-size* dimension
+    num_bytes += core::mem::size_of::<i32>();
+    // the dimensions list
+    if data_set.dims.is_empty() {
+        // the tag `ABSENT_TAG`
+        num_bytes += ABSENT_TAG.len();
+    }
+    else {
+        // the tag `DIMENSION_TAG`
+        num_bytes += DIMENSION_TAG.len();
+        // the number of dimensions
+        num_bytes += core::mem::size_of::<i32>();
+        for dim in data_set.dims.iter() {
+            // the name of the dimension
+            num_bytes += compute_name_string_size(&dim.name.borrow());
+            // the size og the dimension
+            num_bytes += core::mem::size_of::<i32>();
+        }
+    }
+    // the global attributes
+    num_bytes += compute_attrs_list_size(&data_set.attrs);
+    // the variables list
+    if data_set.vars.is_empty() {
+        num_bytes += ABSENT_TAG.len();
+    }
+    else {
+        num_bytes += VARIABLE_TAG.len();
+        // the number of variables
+        num_bytes += core::mem::size_of::<i32>();
+        for var in data_set.vars.iter() {
+            // the variable name
+            num_bytes += compute_name_string_size(&var.name);
+            // the number of dimensions
+            num_bytes += core::mem::size_of::<i32>();
+            // the ID of each dimension of the variable
+            num_bytes += var.num_dims() * core::mem::size_of::<i32>();
+            // the list of variable attributes
+            num_bytes += compute_attrs_list_size(&var.attrs);
+            // the variables data type
+            num_bytes += core::mem::size_of::<i32>();
+            // the number of bytes required each chunck
+            num_bytes += core::mem::size_of::<i32>();
+            // the begin offset depends of the NetCDF-3 version
+            num_bytes += match version {
+                Version::Classic => core::mem::size_of::<i32>(),
+                Version::Offset64Bit => core::mem::size_of::<i64>(),
+            }
+        }
+    }
+    return num_bytes;
+}
@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+use std::io::{BufRead, Read, Seek, Write};
+
+use sha2::{Digest, Sha256};
+
+use crate::{FileReader, Variable};
+use crate::error::ReadError;
+
+/// Number of bytes of the buffer through which the variable data are hashed.
+const CHECKSUM_BUFFER_SIZE: usize = 1 << 16;
+
+/// Difference between the data of a NetCDF-3 file and its checksums (see [`verify_checksums`](fn.verify_checksums.html)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChecksumMismatch {
+    /// The data of the variable differ from the checksummed ones.
+    DataChanged(String),
+    /// The variable has a checksum but is not defined in the file.
+    VariableNotDefined(String),
+    /// The variable is defined in the file but has no checksum.
+    VariableNotChecksummed(String),
+}
+
+/// Computes the SHA-256 checksum of the data of each variable.
+///
+/// The values are hashed as big-endian bytes, in the order of the file and without the padding bytes,
+/// so that the checksums do not depend on the layout of the file (its version, the order of the variables, etc.).
+/// The data are read through a fixed-size buffer.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::BTreeMap;
+/// use std::io::Cursor;
+/// use netcdf3::{FileReader, compute_checksums, verify_checksums, write_checksums, read_checksums};
+/// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+///
+/// let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+/// let checksums: BTreeMap<String, [u8; 32]> = compute_checksums(&mut file_reader).unwrap();
+/// assert_eq!(file_reader.data_set().num_vars(),       checksums.len());
+///
+/// // write and read the sidecar file
+/// let mut sidecar: Vec<u8> = vec![];
+/// write_checksums(&checksums, &mut sidecar).unwrap();
+/// assert_eq!(checksums,                               read_checksums(&sidecar[..]).unwrap());
+///
+/// assert_eq!(true,                                    verify_checksums(&mut file_reader, &checksums).unwrap().is_empty());
+/// ```
+pub fn compute_checksums<R: Read + Seek>(file_reader: &mut FileReader<R>) -> Result<BTreeMap<String, [u8; 32]>, ReadError> {
+    let mut buffer: Vec<u8> = vec![0; CHECKSUM_BUFFER_SIZE];
+    let mut checksums: BTreeMap<String, [u8; 32]> = BTreeMap::new();
+    for var_name in file_reader.data_set().get_var_names().into_iter() {
+        let byte_ranges: Vec<(u64, usize)> = file_reader.var_byte_ranges(&var_name).ok_or_else(|| ReadError::VariableNotDefined(var_name.clone()))?;
+        let mut hasher = Sha256::new();
+        for (offset, length) in byte_ranges.into_iter() {
+            let mut position: usize = 0;
+            while position < length {
+                let num_bytes: usize = std::cmp::min(length - position, buffer.len());
+                file_reader.read_bytes_at(offset + position as u64, &mut buffer[0..num_bytes])?;
+                hasher.update(&buffer[0..num_bytes]);
+                position += num_bytes;
+            }
+        }
+        checksums.insert(var_name, hasher.finalize().into());
+    }
+    return Ok(checksums);
+}
+
+/// Compares the data of a NetCDF-3 file with the checksums `checksums`, and returns the mismatches found
+/// (see [`compute_checksums`](fn.compute_checksums.html)).
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{FileReader, ChecksumMismatch, compute_checksums, verify_checksums};
+/// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+///
+/// let mut checksums = compute_checksums(&mut FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap()).unwrap();
+///
+/// // alter the first value of `latitude`
+/// let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+/// let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+/// let (offset, _length): (u64, usize) = file_reader.var_byte_ranges("latitude").unwrap()[0];
+/// bytes[offset as usize] ^= 0xff;
+///
+/// checksums.remove("longitude");
+/// let mut file_reader = FileReader::from_reader(Cursor::new(bytes)).unwrap();
+/// assert_eq!(
+///     vec![
+///         ChecksumMismatch::DataChanged(String::from("latitude")),
+///         ChecksumMismatch::VariableNotChecksummed(String::from("longitude")),
+///     ],
+///     verify_checksums(&mut file_reader, &checksums).unwrap()
+/// );
+/// ```
+pub fn verify_checksums<R: Read + Seek>(file_reader: &mut FileReader<R>, checksums: &BTreeMap<String, [u8; 32]>) -> Result<Vec<ChecksumMismatch>, ReadError> {
+    let computed_checksums: BTreeMap<String, [u8; 32]> = compute_checksums(file_reader)?;
+    let mut mismatches: Vec<ChecksumMismatch> = vec![];
+    for var in file_reader.data_set().vars().iter() {
+        let var_name: &str = var.name();
+        match checksums.get(var_name) {
+            None => mismatches.push(ChecksumMismatch::VariableNotChecksummed(var_name.to_string())),
+            Some(checksum) => if computed_checksums.get(var_name) != Some(checksum) {
+                mismatches.push(ChecksumMismatch::DataChanged(var_name.to_string()));
+            },
+        }
+    }
+    for var_name in checksums.keys() {
+        if !file_reader.data_set().vars().iter().any(|var: &Variable| var.name() == var_name) {
+            mismatches.push(ChecksumMismatch::VariableNotDefined(var_name.to_string()));
+        }
+    }
+    return Ok(mismatches);
+}
+
+/// Writes the checksums into a sidecar file, one line `<hexadecimal checksum>  <variable name>` per variable
+/// (the format of the tool `sha256sum`).
+pub fn write_checksums<W: Write>(checksums: &BTreeMap<String, [u8; 32]>, output: &mut W) -> std::io::Result<()> {
+    for (var_name, checksum) in checksums.iter() {
+        let hex_checksum: String = checksum.iter().map(|byte: &u8| format!("{:02x}", byte)).collect();
+        writeln!(output, "{}  {}", hex_checksum, var_name)?;
+    }
+    return output.flush();
+}
+
+/// Reads the checksums from a sidecar file (see [`write_checksums`](fn.write_checksums.html)).
+///
+/// An error of kind `InvalidData` is returned if a line is not valid, the empty lines are ignored.
+pub fn read_checksums<R: BufRead>(input: R) -> std::io::Result<BTreeMap<String, [u8; 32]>> {
+    let mut checksums: BTreeMap<String, [u8; 32]> = BTreeMap::new();
+    for (line_index, line) in input.lines().enumerate() {
+        let line: String = line?;
+        if line.is_empty() {
+            continue;
+        }
+        let invalid_line = || std::io::Error::new(std::io::ErrorKind::InvalidData, format!("invalid checksum line {}: {:?}", line_index + 1, line));
+        let (hex_checksum, var_name): (&str, &str) = match (line.get(0..64), line.get(64..66), line.get(66..)) {
+            (Some(hex_checksum), Some("  "), Some(var_name)) if hex_checksum.bytes().all(|byte: u8| byte.is_ascii_hexdigit()) && !var_name.is_empty() => {
+                (hex_checksum, var_name)
+            },
+            _ => return Err(invalid_line()),
+        };
+        let mut checksum: [u8; 32] = [0; 32];
+        for (i, byte) in checksum.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex_checksum[(2 * i)..(2 * i + 2)], 16).map_err(|_err: std::num::ParseIntError| invalid_line())?;
+        }
+        checksums.insert(var_name.to_string(), checksum);
+    }
+    return Ok(checksums);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeMap;
+    use std::io::Cursor;
+
+    use copy_to_tmp_file::{NC3_CLASSIC_FILE_BYTES, NC3_64BIT_OFFSET_FILE_BYTES};
+
+    use super::{compute_checksums, read_checksums, write_checksums};
+    use crate::FileReader;
+
+    #[test]
+    fn test_compute_checksums() {
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        let checksums: BTreeMap<String, [u8; 32]> = compute_checksums(&mut file_reader).unwrap();
+        // SHA-256 of the big-endian bytes of the values `[0.0, 0.5, 1.0]`
+        let latitude_checksum: [u8; 32] = {
+            use sha2::Digest;
+            let bytes: Vec<u8> = [0.0_f32, 0.5, 1.0].iter().flat_map(|value: &f32| value.to_be_bytes().to_vec()).collect();
+            sha2::Sha256::digest(&bytes).into()
+        };
+        assert_eq!(Some(&latitude_checksum),       checksums.get("latitude"));
+
+        // the checksums do not depend on the version of the file
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_64BIT_OFFSET_FILE_BYTES)).unwrap();
+        assert_eq!(checksums,                       compute_checksums(&mut file_reader).unwrap());
+    }
+
+    #[test]
+    fn test_write_read_checksums() {
+        let mut checksums: BTreeMap<String, [u8; 32]> = BTreeMap::new();
+        checksums.insert(String::from("var 1"), [0xab; 32]);
+        checksums.insert(String::from("var_2"), [0x01; 32]);
+        let mut sidecar: Vec<u8> = vec![];
+        write_checksums(&checksums, &mut sidecar).unwrap();
+        assert_eq!(
+            format!("{}  var 1\n{}  var_2\n", "ab".repeat(32), "01".repeat(32)),
+            String::from_utf8(sidecar.clone()).unwrap()
+        );
+        assert_eq!(checksums,                       read_checksums(&sidecar[..]).unwrap());
+    }
+
+    #[test]
+    fn test_read_checksums_error() {
+        for sidecar in ["abcd  var\n", &format!("{} var\n", "ab".repeat(32)), &format!("{}  \n", "ab".repeat(32)), &format!("{}  var\n", "zz".repeat(32))].iter() {
+            assert_eq!(std::io::ErrorKind::InvalidData,     read_checksums(sidecar.as_bytes()).unwrap_err().kind());
+        }
+    }
+}
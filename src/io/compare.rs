@@ -0,0 +1,441 @@
+use std::io::{Read, Seek};
+#[cfg(feature = "fs")]
+use std::path::Path;
+use std::rc::Rc;
+
+use crate::{Attribute, DataSet, DataType, DataVector, Dimension, DimensionType, FileReader, Variable};
+use crate::error::ReadError;
+use crate::index::unravel_index;
+
+/// Tolerance of the comparison of the numeric values (see [`compare_nc3`](fn.compare_nc3.html)).
+///
+/// Two finite values `a` and `b` are equal if `|a - b| <= abs + rel * max(|a|, |b|)`, the NaN values being equal to each other.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    /// Absolute tolerance.
+    pub abs: f64,
+    /// Relative tolerance.
+    pub rel: f64,
+}
+
+impl Tolerance {
+    /// Creates a tolerance from the absolute tolerance `abs` and the relative tolerance `rel`.
+    pub fn new(abs: f64, rel: f64) -> Tolerance {
+        return Tolerance{
+            abs: abs,
+            rel: rel,
+        };
+    }
+
+    /// Compares the values exactly (the default tolerance).
+    pub fn exact() -> Tolerance {
+        return Tolerance::new(0.0, 0.0);
+    }
+
+    /// Returns `true` if the values `a` and `b` are equal within the tolerance.
+    pub fn values_equal(&self, a: f64, b: f64) -> bool {
+        if a == b || (a.is_nan() && b.is_nan()) {
+            return true;
+        }
+        // the infinite values are only equal to themselves
+        if a.is_infinite() || b.is_infinite() {
+            return false;
+        }
+        return (a - b).abs() <= self.abs + self.rel * a.abs().max(b.abs());
+    }
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        return Tolerance::exact();
+    }
+}
+
+/// First difference found between two NetCDF-3 files (see [`compare_nc3`](fn.compare_nc3.html)).
+///
+/// The fields `a` and `b` describe the item in the first and in the second file, `None` if it is not defined.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Divergence {
+    /// The dimension is not defined in both files, or its size or its type differs.
+    DimensionMismatch{dim_name: String, a: Option<(usize, DimensionType)>, b: Option<(usize, DimensionType)>},
+    /// The global attribute is not defined in both files, or its values differ.
+    GlobalAttributeMismatch{attr_name: String, a: Option<DataVector>, b: Option<DataVector>},
+    /// The variable is not defined in both files, or its data type or its dimensions differ.
+    VariableMismatch{var_name: String, a: Option<(DataType, Vec<String>)>, b: Option<(DataType, Vec<String>)>},
+    /// The variable attribute is not defined in both files, or its values differ.
+    VariableAttributeMismatch{var_name: String, attr_name: String, a: Option<DataVector>, b: Option<DataVector>},
+    /// A value of the variable differs, `indices` being the indices of the first one.
+    ValueMismatch{var_name: String, indices: Vec<usize>, a: f64, b: f64},
+}
+
+impl core::fmt::Display for Divergence {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        fn side<T: core::fmt::Debug>(item: &Option<T>) -> String {
+            return match item {
+                None => String::from("not defined"),
+                Some(item) => format!("{:?}", item),
+            };
+        }
+        return match self {
+            Divergence::DimensionMismatch{dim_name, a, b} => {
+                write!(f, "dimension `{}` differs: {} in the first file, {} in the second file", dim_name, side(a), side(b))
+            },
+            Divergence::GlobalAttributeMismatch{attr_name, a, b} => {
+                write!(f, "global attribute `{}` differs: {} in the first file, {} in the second file", attr_name, side(a), side(b))
+            },
+            Divergence::VariableMismatch{var_name, a, b} => {
+                write!(f, "variable `{}` differs: {} in the first file, {} in the second file", var_name, side(a), side(b))
+            },
+            Divergence::VariableAttributeMismatch{var_name, attr_name, a, b} => {
+                write!(f, "attribute `{}:{}` differs: {} in the first file, {} in the second file", var_name, attr_name, side(a), side(b))
+            },
+            Divergence::ValueMismatch{var_name, indices, a, b} => {
+                write!(f, "value of `{}` at {:?} differs: {} in the first file, {} in the second file", var_name, indices, a, b)
+            },
+        };
+    }
+}
+
+/// Compares the contents of two NetCDF-3 files, and returns their first difference or `None` if they are equal.
+///
+/// The files are compared semantically : their versions, the space reserved for their headers and the order of their dimensions,
+/// attributes and variables are ignored. The dimensions, the global attributes, the variables, the variable attributes
+/// and then the values of the variables are compared. The values of the variables and the floating-point values of the attributes
+/// are compared with the tolerance `tolerance`, the texts and the integer values of the attributes exactly.
+///
+/// The values of each variable are read at once.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{compare_nc3, copy_nc3, CopyOptions, Divergence, FileReader, Tolerance, Version};
+/// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+///
+/// // the version and the header size of the copy are ignored
+/// let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+/// let options = CopyOptions{version: Some(Version::Offset64Bit), header_min_size: 4096, ..CopyOptions::default()};
+/// let copied_bytes: Vec<u8> = copy_nc3(&mut file_reader, Cursor::new(vec![]), &options).unwrap().into_inner();
+/// assert_eq!(None,    compare_nc3(Cursor::new(NC3_CLASSIC_FILE_BYTES), Cursor::new(&copied_bytes), Tolerance::exact()).unwrap());
+///
+/// // alter the second value of `latitude`
+/// let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+/// let (offset, _length): (u64, usize) = file_reader.var_byte_ranges("latitude").unwrap()[0];
+/// bytes[offset as usize + 7] ^= 0x01;
+///
+/// let divergence: Divergence = compare_nc3(Cursor::new(NC3_CLASSIC_FILE_BYTES), Cursor::new(&bytes), Tolerance::exact()).unwrap().unwrap();
+/// assert_eq!(
+///     "value of `latitude` at [1] differs: 0.5 in the first file, 0.5000000596046448 in the second file",
+///     divergence.to_string()
+/// );
+/// assert_eq!(None,    compare_nc3(Cursor::new(NC3_CLASSIC_FILE_BYTES), Cursor::new(&bytes), Tolerance::new(1e-6, 0.0)).unwrap());
+/// ```
+pub fn compare_nc3<RA: Read + Seek, RB: Read + Seek>(input_a: RA, input_b: RB, tolerance: Tolerance) -> Result<Option<Divergence>, ReadError> {
+    return compare_files(FileReader::from_reader(input_a)?, FileReader::from_reader(input_b)?, tolerance);
+}
+
+/// Compares the contents of two NetCDF-3 files (see [`compare_nc3`](fn.compare_nc3.html)), and returns their first difference
+/// or `None` if they are equal.
+#[cfg(feature = "fs")]
+pub fn first_divergence<PA: AsRef<Path>, PB: AsRef<Path>>(file_path_a: PA, file_path_b: PB, tolerance: Tolerance) -> Result<Option<Divergence>, ReadError> {
+    return compare_files(FileReader::open(file_path_a)?, FileReader::open(file_path_b)?, tolerance);
+}
+
+/// Returns `true` if the contents of two NetCDF-3 files are equal (see [`compare_nc3`](fn.compare_nc3.html)),
+/// their first difference being returned by [`first_divergence`](fn.first_divergence.html).
+#[cfg(feature = "fs")]
+pub fn files_equal<PA: AsRef<Path>, PB: AsRef<Path>>(file_path_a: PA, file_path_b: PB, tolerance: Tolerance) -> Result<bool, ReadError> {
+    return Ok(first_divergence(file_path_a, file_path_b, tolerance)?.is_none());
+}
+
+fn compare_files<RA: Read + Seek, RB: Read + Seek>(mut file_reader_a: FileReader<RA>, mut file_reader_b: FileReader<RB>, tolerance: Tolerance) -> Result<Option<Divergence>, ReadError> {
+    if let Some(divergence) = compare_data_sets(file_reader_a.data_set(), file_reader_b.data_set(), &tolerance) {
+        return Ok(Some(divergence));
+    }
+    // Both files define the same variables
+    for var_name in file_reader_a.data_set().get_var_names().into_iter() {
+        let data_a: DataVector = file_reader_a.read_var(&var_name)?;
+        let data_b: DataVector = file_reader_b.read_var(&var_name)?;
+        if let Some(flat_index) = find_divergent_value(&data_a, &data_b, &tolerance) {
+            let shape: Vec<usize> = file_reader_a.data_set().get_var(&var_name).map(|var: &Variable| {
+                var.get_dims().iter().map(|dim: &Rc<Dimension>| dim.size()).collect()
+            }).unwrap_or_default();
+            return Ok(Some(Divergence::ValueMismatch{
+                indices: unravel_index(&shape, flat_index).unwrap_or_else(|| vec![flat_index]),
+                var_name: var_name,
+                a: data_a.get_as_f64_vec()[flat_index],
+                b: data_b.get_as_f64_vec()[flat_index],
+            }));
+        }
+    }
+    return Ok(None);
+}
+
+/// Compares the definitions of the data sets, and returns their first difference.
+fn compare_data_sets(data_set_a: &DataSet, data_set_b: &DataSet, tolerance: &Tolerance) -> Option<Divergence> {
+    for dim_name in union_names(data_set_a.dim_names(), data_set_b.dim_names()).into_iter() {
+        let dim_a: Option<(usize, DimensionType)> = data_set_a.dim_size(&dim_name).zip(data_set_a.dim_type(&dim_name));
+        let dim_b: Option<(usize, DimensionType)> = data_set_b.dim_size(&dim_name).zip(data_set_b.dim_type(&dim_name));
+        if dim_a != dim_b {
+            return Some(Divergence::DimensionMismatch{dim_name: dim_name, a: dim_a, b: dim_b});
+        }
+    }
+
+    for attr_name in union_names(data_set_a.get_global_attr_names(), data_set_b.get_global_attr_names()).into_iter() {
+        let (attr_a, attr_b): (Option<&Attribute>, Option<&Attribute>) = (data_set_a.get_global_attr(&attr_name), data_set_b.get_global_attr(&attr_name));
+        if !attrs_equal(attr_a, attr_b, tolerance) {
+            return Some(Divergence::GlobalAttributeMismatch{
+                attr_name: attr_name,
+                a: attr_a.map(|attr: &Attribute| attr.data().clone()),
+                b: attr_b.map(|attr: &Attribute| attr.data().clone()),
+            });
+        }
+    }
+
+    for var_name in union_names(data_set_a.get_var_names(), data_set_b.get_var_names()).into_iter() {
+        let (var_a, var_b): (Option<&Variable>, Option<&Variable>) = (data_set_a.get_var(&var_name), data_set_b.get_var(&var_name));
+        let var_def_a: Option<(DataType, Vec<String>)> = var_a.map(|var: &Variable| (var.data_type(), var.dim_names()));
+        let var_def_b: Option<(DataType, Vec<String>)> = var_b.map(|var: &Variable| (var.data_type(), var.dim_names()));
+        let (var_a, var_b): (&Variable, &Variable) = match (var_a, var_b) {
+            (Some(var_a), Some(var_b)) if var_def_a == var_def_b => (var_a, var_b),
+            _ => return Some(Divergence::VariableMismatch{var_name: var_name, a: var_def_a, b: var_def_b}),
+        };
+        for attr_name in union_names(var_a.get_attr_names(), var_b.get_attr_names()).into_iter() {
+            let (attr_a, attr_b): (Option<&Attribute>, Option<&Attribute>) = (var_a.get_attr(&attr_name), var_b.get_attr(&attr_name));
+            if !attrs_equal(attr_a, attr_b, tolerance) {
+                return Some(Divergence::VariableAttributeMismatch{
+                    var_name: var_name,
+                    attr_name: attr_name,
+                    a: attr_a.map(|attr: &Attribute| attr.data().clone()),
+                    b: attr_b.map(|attr: &Attribute| attr.data().clone()),
+                });
+            }
+        }
+    }
+    return None;
+}
+
+/// Returns the names of `names_a`, followed by the names of `names_b` not contained in `names_a`.
+fn union_names(mut names_a: Vec<String>, names_b: Vec<String>) -> Vec<String> {
+    let num_names_a: usize = names_a.len();
+    for name in names_b.into_iter() {
+        if !names_a[0..num_names_a].contains(&name) {
+            names_a.push(name);
+        }
+    }
+    return names_a;
+}
+
+/// Compares the attributes, the texts and the integer values exactly and the floating-point values within the tolerance.
+fn attrs_equal(attr_a: Option<&Attribute>, attr_b: Option<&Attribute>, tolerance: &Tolerance) -> bool {
+    return match (attr_a, attr_b) {
+        (None, None) => true,
+        (Some(attr_a), Some(attr_b)) if attr_a.data_type() == attr_b.data_type() && attr_a.len() == attr_b.len() => {
+            match attr_a.data_type() {
+                DataType::F32 | DataType::F64 => find_divergent_value(attr_a.data(), attr_b.data(), tolerance).is_none(),
+                DataType::I8 | DataType::U8 | DataType::I16 | DataType::I32 => attr_a.data() == attr_b.data(),
+            }
+        },
+        _ => false,
+    };
+}
+
+/// Returns the position of the first value differing between `data_a` and `data_b`, which must have the same length.
+fn find_divergent_value(data_a: &DataVector, data_b: &DataVector, tolerance: &Tolerance) -> Option<usize> {
+    return data_a.get_as_f64_vec().into_iter()
+        .zip(data_b.get_as_f64_vec())
+        .position(|(value_a, value_b): (f64, f64)| !tolerance.values_equal(value_a, value_b));
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Cursor;
+
+    use copy_to_tmp_file::{NC3_CLASSIC_FILE_BYTES, NC3_64BIT_OFFSET_FILE_BYTES};
+
+    use super::{compare_nc3, Divergence, Tolerance};
+    use crate::{copy_nc3, CopyOptions, DataSet, DataType, DataVector, DimensionType, FileReader, FileWriter, Version};
+
+    /// Writes the data set and the values of its variables into bytes.
+    fn write_bytes(data_set: &DataSet, var_data: &[(&str, DataVector)]) -> Vec<u8> {
+        let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+        file_writer.set_def(data_set, Version::Classic, 0).unwrap();
+        for (var_name, data) in var_data.iter() {
+            file_writer.write_var_owned(var_name, data.clone()).unwrap();
+        }
+        return file_writer.close_into_inner().unwrap().into_inner();
+    }
+
+    #[test]
+    fn test_tolerance() {
+        assert_eq!(Tolerance::exact(),                  Tolerance::default());
+        assert_eq!(true,                                Tolerance::exact().values_equal(f64::NAN, f64::NAN));
+        assert_eq!(true,                                Tolerance::exact().values_equal(f64::INFINITY, f64::INFINITY));
+        assert_eq!(false,                               Tolerance::exact().values_equal(1.0, 1.0 + f64::EPSILON));
+        assert_eq!(false,                               Tolerance::new(1e9, 1e9).values_equal(f64::INFINITY, 1.0));
+        assert_eq!(true,                                Tolerance::new(0.1, 0.0).values_equal(1.0, 1.05));
+        assert_eq!(false,                               Tolerance::new(0.1, 0.0).values_equal(1000.0, 1000.2));
+        assert_eq!(true,                                Tolerance::new(0.0, 1e-3).values_equal(1000.0, 1000.2));
+    }
+
+    #[test]
+    fn test_compare_nc3_layout_ignored() {
+        // the header size and the version differ
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        let options = CopyOptions{version: Some(Version::Offset64Bit), header_min_size: 4096, ..CopyOptions::default()};
+        let bytes: Vec<u8> = copy_nc3(&mut file_reader, Cursor::new(vec![]), &options).unwrap().into_inner();
+        assert_ne!(NC3_64BIT_OFFSET_FILE_BYTES,         &bytes[..]);
+        assert_eq!(None,                                compare_nc3(Cursor::new(NC3_CLASSIC_FILE_BYTES), Cursor::new(&bytes), Tolerance::exact()).unwrap());
+
+        // the order of the dimensions, the attributes and the variables differ
+        let mut data_set_a = DataSet::new();
+        data_set_a.add_fixed_dim("x", 2).unwrap();
+        data_set_a.add_fixed_dim("y", 1).unwrap();
+        data_set_a.add_global_attr_string("title", "a").unwrap();
+        data_set_a.add_global_attr_i32("id", vec![1]).unwrap();
+        data_set_a.add_var_i8("a", &["x"]).unwrap();
+        data_set_a.add_var_f64("b", &["y"]).unwrap();
+        let mut data_set_b = DataSet::new();
+        data_set_b.add_fixed_dim("y", 1).unwrap();
+        data_set_b.add_fixed_dim("x", 2).unwrap();
+        data_set_b.add_global_attr_i32("id", vec![1]).unwrap();
+        data_set_b.add_global_attr_string("title", "a").unwrap();
+        data_set_b.add_var_f64("b", &["y"]).unwrap();
+        data_set_b.add_var_i8("a", &["x"]).unwrap();
+        let var_data = [("a", DataVector::I8(vec![1, 2])), ("b", DataVector::F64(vec![f64::NAN]))];
+        assert_eq!(
+            None,
+            compare_nc3(Cursor::new(write_bytes(&data_set_a, &var_data)), Cursor::new(write_bytes(&data_set_b, &var_data)), Tolerance::exact()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_compare_nc3_divergences() {
+        let mut data_set = DataSet::new();
+        data_set.set_unlimited_dim("time", 2).unwrap();
+        data_set.add_fixed_dim("x", 3).unwrap();
+        data_set.add_global_attr_string("title", "a").unwrap();
+        data_set.add_var_f32("t2m", &["time", "x"]).unwrap();
+        data_set.add_var_attr_f32("t2m", "scale_factor", vec![0.5]).unwrap();
+        let var_data = [("t2m", DataVector::F32(vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0]))];
+        let bytes: Vec<u8> = write_bytes(&data_set, &var_data);
+        let compare = |other_data_set: &DataSet, other_var_data: &[(&str, DataVector)]| -> Option<Divergence> {
+            return compare_nc3(Cursor::new(&bytes), Cursor::new(write_bytes(other_data_set, other_var_data)), Tolerance::exact()).unwrap();
+        };
+        assert_eq!(None,                                compare(&data_set, &var_data));
+
+        // dimension
+        let mut other_data_set = DataSet::new();
+        other_data_set.add_fixed_dim("time", 2).unwrap();
+        assert_eq!(
+            Some(Divergence::DimensionMismatch{
+                dim_name: String::from("time"),
+                a: Some((2, DimensionType::UnlimitedSize)),
+                b: Some((2, DimensionType::FixedSize)),
+            }),
+            compare(&other_data_set, &[])
+        );
+
+        // global attribute
+        let mut other_data_set = DataSet::new();
+        other_data_set.set_unlimited_dim("time", 2).unwrap();
+        other_data_set.add_fixed_dim("x", 3).unwrap();
+        assert_eq!(
+            Some(Divergence::GlobalAttributeMismatch{attr_name: String::from("title"), a: Some(DataVector::U8(b"a".to_vec())), b: None}),
+            compare(&other_data_set, &[])
+        );
+
+        // variable
+        other_data_set.add_global_attr_string("title", "a").unwrap();
+        other_data_set.add_var_f64("t2m", &["time", "x"]).unwrap();
+        let divergence: Divergence = compare(&other_data_set, &[]).unwrap();
+        assert_eq!(
+            Divergence::VariableMismatch{
+                var_name: String::from("t2m"),
+                a: Some((DataType::F32, vec![String::from("time"), String::from("x")])),
+                b: Some((DataType::F64, vec![String::from("time"), String::from("x")])),
+            },
+            divergence
+        );
+        assert_eq!(
+            "variable `t2m` differs: (F32, [\"time\", \"x\"]) in the first file, (F64, [\"time\", \"x\"]) in the second file",
+            divergence.to_string()
+        );
+
+        // variable attribute
+        other_data_set.remove_var("t2m").unwrap();
+        other_data_set.add_var_f32("t2m", &["time", "x"]).unwrap();
+        other_data_set.add_var_attr_f32("t2m", "scale_factor", vec![0.25]).unwrap();
+        assert_eq!(
+            Some(Divergence::VariableAttributeMismatch{
+                var_name: String::from("t2m"),
+                attr_name: String::from("scale_factor"),
+                a: Some(DataVector::F32(vec![0.5])),
+                b: Some(DataVector::F32(vec![0.25])),
+            }),
+            compare(&other_data_set, &var_data)
+        );
+
+        // value
+        let other_var_data = [("t2m", DataVector::F32(vec![0.0, 1.0, 2.0, 3.0, 4.5, 5.0]))];
+        let divergence: Divergence = compare(&data_set, &other_var_data).unwrap();
+        assert_eq!(
+            Divergence::ValueMismatch{var_name: String::from("t2m"), indices: vec![1, 1], a: 4.0, b: 4.5},
+            divergence
+        );
+        assert_eq!(
+            "value of `t2m` at [1, 1] differs: 4 in the first file, 4.5 in the second file",
+            divergence.to_string()
+        );
+        assert_eq!(
+            None,
+            compare_nc3(Cursor::new(&bytes), Cursor::new(write_bytes(&data_set, &other_var_data)), Tolerance::new(0.5, 0.0)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_compare_nc3_attrs_tolerance() {
+        let new_bytes = |title: &str, id: i32, scale_factor: f32| -> Vec<u8> {
+            let mut data_set = DataSet::new();
+            data_set.add_global_attr_string("title", title).unwrap();
+            data_set.add_global_attr_i32("id", vec![id]).unwrap();
+            data_set.add_global_attr_f32("scale_factor", vec![scale_factor]).unwrap();
+            return write_bytes(&data_set, &[]);
+        };
+        let bytes: Vec<u8> = new_bytes("a", 1, 0.5);
+        let tolerance = Tolerance::new(1.0, 0.0);
+
+        // only the floating-point values are compared within the tolerance
+        assert_eq!(None,                                compare_nc3(Cursor::new(&bytes), Cursor::new(new_bytes("a", 1, 1.5)), tolerance).unwrap());
+        assert_eq!(
+            Some(Divergence::GlobalAttributeMismatch{attr_name: String::from("id"), a: Some(DataVector::I32(vec![1])), b: Some(DataVector::I32(vec![2]))}),
+            compare_nc3(Cursor::new(&bytes), Cursor::new(new_bytes("a", 2, 0.5)), tolerance).unwrap()
+        );
+        // `b` (0x62) is within the tolerance of `a` (0x61)
+        assert_eq!(
+            Some(Divergence::GlobalAttributeMismatch{attr_name: String::from("title"), a: Some(DataVector::U8(b"a".to_vec())), b: Some(DataVector::U8(b"b".to_vec()))}),
+            compare_nc3(Cursor::new(&bytes), Cursor::new(new_bytes("b", 1, 0.5)), tolerance).unwrap()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "fs")]
+    fn test_files_equal() {
+        use copy_to_tmp_file::copy_bytes_to_tmp_file;
+        use super::{files_equal, first_divergence};
+
+        let (_tmp_dir_a, file_path_a) = copy_bytes_to_tmp_file(NC3_CLASSIC_FILE_BYTES, "file_a.nc");
+        let (_tmp_dir_b, file_path_b) = copy_bytes_to_tmp_file(NC3_CLASSIC_FILE_BYTES, "file_b.nc");
+        assert_eq!(true,                                files_equal(&file_path_a, &file_path_b, Tolerance::exact()).unwrap());
+        assert_eq!(None,                                first_divergence(&file_path_a, &file_path_b, Tolerance::exact()).unwrap());
+        // the titles of the test files differ
+        let (_tmp_dir_c, file_path_c) = copy_bytes_to_tmp_file(NC3_64BIT_OFFSET_FILE_BYTES, "file_c.nc");
+        assert_eq!(false,                               files_equal(&file_path_a, &file_path_c, Tolerance::exact()).unwrap());
+        match first_divergence(&file_path_a, &file_path_c, Tolerance::exact()).unwrap() {
+            Some(Divergence::GlobalAttributeMismatch{attr_name, ..}) => assert_eq!("title",     attr_name),
+            divergence => panic!("unexpected divergence {:?}", divergence),
+        }
+        assert_eq!(true,                                files_equal(&file_path_a, file_path_a.with_extension("undef"), Tolerance::exact()).is_err());
+    }
+}
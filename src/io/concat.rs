@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+use std::io::{Read, Seek, Write};
+use std::rc::Rc;
+
+use crate::{DataSet, DataVector, Dimension, FileReader, FileWriter, Variable, Version};
+use crate::data_set::DimensionSize;
+use crate::error::ConcatError;
+use crate::io::copy::{select_vars, write_record_data, write_var_data};
+
+/// Concatenates NetCDF-3 files along their *unlimited-size* dimension into `output`.
+///
+/// All the files must define the same dimensions (except the size of the *unlimited-size* dimension)
+/// and the same variables (same dimensions and same data types), this is checked before writing anything.
+/// The global attributes, the variable attributes and the *fixed-size* variables data are copied from the first file.
+///
+/// The output file uses the *64-bit offset* version if one of the input files does. The data are streamed record by record,
+/// so the memory usage does not depend on the number of records. The underlying output is returned once the concatenation is done.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{FileReader, concat_nc3};
+/// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+///
+/// let mut file_readers: Vec<FileReader<Cursor<&[u8]>>> = vec![
+///     FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap(),
+///     FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap(),
+/// ];
+/// let output: Cursor<Vec<u8>> = concat_nc3(&mut file_readers, Cursor::new(vec![])).unwrap();
+///
+/// let mut concat_reader = FileReader::from_reader(output).unwrap();
+/// assert_eq!(Some(4),                                         concat_reader.data_set().num_records());
+/// assert_eq!(vec![438300.0, 438324.0, 438300.0, 438324.0],    concat_reader.read_var_f32("time").unwrap());
+/// ```
+pub fn concat_nc3<R: Read + Seek, W: Write + Seek>(file_readers: &mut [FileReader<R>], output: W) -> Result<W, ConcatError> {
+    let (first_reader, other_readers): (&FileReader<R>, &[FileReader<R>]) = file_readers.split_first().ok_or(ConcatError::NoInputFiles)?;
+    let unlimited_dim: Rc<Dimension> = first_reader.data_set().get_unlimited_dim().ok_or(ConcatError::UnlimitedDimNotDefined)?;
+    for (i, file_reader) in other_readers.iter().enumerate() {
+        check_compatibility(first_reader.data_set(), file_reader.data_set(), i + 1)?;
+    }
+
+    // Define the output data set
+    let num_records: usize = file_readers.iter().map(|file_reader: &FileReader<R>| file_reader.data_set().num_records().unwrap_or(0)).sum();
+    let data_set: DataSet = select_vars(file_readers[0].data_set(), &file_readers[0].data_set().get_var_names(), &HashMap::new())?;
+    if let Some(dim) = data_set.get_dim(&unlimited_dim.name()) {
+        if let DimensionSize::Unlimited(dim_size) = &dim.size {
+            dim_size.replace(num_records);
+        }
+    }
+    let version: Version = match file_readers.iter().any(|file_reader: &FileReader<R>| file_reader.version() == Version::Offset64Bit) {
+        true => Version::Offset64Bit,
+        false => Version::Classic,
+    };
+    let mut file_writer: FileWriter<W> = FileWriter::from_writer(output);
+    file_writer.set_def(&data_set, version, 0)?;
+
+    // Copy the fixed-size variables of the first file, then the records of each file
+    let (record_vars, fixed_size_vars): (Vec<&Variable>, Vec<&Variable>) = data_set.vars().iter().partition(|var: &&Variable| var.is_record_var());
+    for var in fixed_size_vars.into_iter() {
+        let data: DataVector = file_readers[0].read_var(var.name())?;
+        write_var_data(&mut file_writer, var.name(), &data)?;
+    }
+    let mut output_record_index: usize = 0;
+    for file_reader in file_readers.iter_mut() {
+        for record_index in 0..file_reader.data_set().num_records().unwrap_or(0) {
+            for var in record_vars.iter() {
+                let record: DataVector = file_reader.read_record(var.name(), record_index)?;
+                write_record_data(&mut file_writer, var.name(), output_record_index, &record)?;
+            }
+            output_record_index += 1;
+        }
+    }
+    return Ok(file_writer.close_into_inner()?);
+}
+
+/// Checks that `data_set` defines the same dimensions and the same variables as `ref_data_set`.
+fn check_compatibility(ref_data_set: &DataSet, data_set: &DataSet, file_index: usize) -> Result<(), ConcatError> {
+    let dim_mismatch = |dim_name: String| ConcatError::DimensionMismatch{file_index: file_index, dim_name: dim_name};
+    if ref_data_set.num_dims() != data_set.num_dims() {
+        let dim_name: String = ref_data_set.dim_names().into_iter().chain(data_set.dim_names())
+            .find(|dim_name: &String| !ref_data_set.has_dim(dim_name) || !data_set.has_dim(dim_name))
+            .unwrap_or_default();
+        return Err(dim_mismatch(dim_name));
+    }
+    for ref_dim in ref_data_set.dims().iter() {
+        let dim: Rc<Dimension> = data_set.get_dim(&ref_dim.name()).ok_or_else(|| dim_mismatch(ref_dim.name()))?;
+        let is_compatible: bool = match ref_dim.is_unlimited() {
+            true => dim.is_unlimited(),
+            false => !dim.is_unlimited() && dim.size() == ref_dim.size(),
+        };
+        if !is_compatible {
+            return Err(dim_mismatch(ref_dim.name()));
+        }
+    }
+
+    let var_mismatch = |var_name: &str| ConcatError::VariableMismatch{file_index: file_index, var_name: var_name.to_string()};
+    if let Some(var) = data_set.vars().iter().find(|var: &&Variable| !ref_data_set.has_var(var.name())) {
+        return Err(var_mismatch(var.name()));
+    }
+    for ref_var in ref_data_set.vars().iter() {
+        let var: &Variable = data_set.get_var(ref_var.name()).ok_or_else(|| var_mismatch(ref_var.name()))?;
+        if var.data_type() != ref_var.data_type() || var.dim_names() != ref_var.dim_names() {
+            return Err(var_mismatch(ref_var.name()));
+        }
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Cursor;
+
+    use copy_to_tmp_file::{NC3_CLASSIC_FILE_BYTES, NC3_64BIT_OFFSET_FILE_BYTES, SCALAR_VARIABLES_FILE_BYTES};
+
+    use super::concat_nc3;
+    use crate::{DataSet, DataVector, FileReader, FileWriter, Version};
+    use crate::error::ConcatError;
+
+    fn file_reader(bytes: &[u8]) -> FileReader<Cursor<&[u8]>> {
+        return FileReader::from_reader(Cursor::new(bytes)).unwrap();
+    }
+
+    #[test]
+    fn test_concat_nc3() {
+        let mut file_readers = vec![file_reader(NC3_CLASSIC_FILE_BYTES), file_reader(NC3_64BIT_OFFSET_FILE_BYTES)];
+        let output: Cursor<Vec<u8>> = concat_nc3(&mut file_readers, Cursor::new(vec![])).unwrap();
+
+        let mut concat_reader = FileReader::from_reader(output).unwrap();
+        let mut ref_reader = file_reader(NC3_CLASSIC_FILE_BYTES);
+        assert_eq!(Version::Offset64Bit,                    concat_reader.version());
+        assert_eq!(Some(4),                                 concat_reader.data_set().num_records());
+        // the global attributes are copied from the first file
+        assert_eq!(ref_reader.data_set().get_global_attrs(),    concat_reader.data_set().get_global_attrs());
+        assert_eq!(ref_reader.read_var("latitude").unwrap(),    concat_reader.read_var("latitude").unwrap());
+        for record_index in 0..4 {
+            assert_eq!(
+                ref_reader.read_record("temperature_i16", record_index % 2).unwrap(),
+                concat_reader.read_record("temperature_i16", record_index).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_concat_nc3_empty_records() {
+        let data_set: DataSet = {
+            let mut data_set = DataSet::new();
+            data_set.set_unlimited_dim("time", 0).unwrap();
+            data_set.add_var_i32("time", &["time"]).unwrap();
+            data_set
+        };
+        let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+        file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+        let empty_bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+
+        let mut file_readers = vec![file_reader(&empty_bytes), file_reader(&empty_bytes)];
+        let output: Cursor<Vec<u8>> = concat_nc3(&mut file_readers, Cursor::new(vec![])).unwrap();
+        let mut concat_reader = FileReader::from_reader(output).unwrap();
+        assert_eq!(Version::Classic,                        concat_reader.version());
+        assert_eq!(Some(0),                                 concat_reader.data_set().num_records());
+        assert_eq!(DataVector::I32(vec![]),                 concat_reader.read_var("time").unwrap());
+    }
+
+    #[test]
+    fn test_concat_nc3_errors() {
+        let mut file_readers: Vec<FileReader<Cursor<&[u8]>>> = vec![];
+        assert_eq!(ConcatError::NoInputFiles,               concat_nc3(&mut file_readers, Cursor::new(vec![])).unwrap_err());
+
+        let mut file_readers = vec![file_reader(SCALAR_VARIABLES_FILE_BYTES)];
+        assert_eq!(ConcatError::UnlimitedDimNotDefined,     concat_nc3(&mut file_readers, Cursor::new(vec![])).unwrap_err());
+
+        let mut file_readers = vec![file_reader(NC3_CLASSIC_FILE_BYTES), file_reader(NC3_CLASSIC_FILE_BYTES), file_reader(SCALAR_VARIABLES_FILE_BYTES)];
+        assert_eq!(
+            ConcatError::DimensionMismatch{file_index: 2, dim_name: String::from("latitude")},
+            concat_nc3(&mut file_readers, Cursor::new(vec![])).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_concat_nc3_var_mismatch() {
+        let (data_set_1, data_set_2): (DataSet, DataSet) = {
+            let mut data_set_1 = DataSet::new();
+            data_set_1.set_unlimited_dim("time", 0).unwrap();
+            data_set_1.add_var_f32("time", &["time"]).unwrap();
+            let mut data_set_2 = DataSet::new();
+            data_set_2.set_unlimited_dim("time", 0).unwrap();
+            data_set_2.add_var_f64("time", &["time"]).unwrap();
+            (data_set_1, data_set_2)
+        };
+        let bytes: Vec<Vec<u8>> = [data_set_1, data_set_2].iter().map(|data_set: &DataSet| {
+            let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+            file_writer.set_def(data_set, Version::Classic, 0).unwrap();
+            file_writer.close_into_inner().unwrap().into_inner()
+        }).collect();
+
+        let mut file_readers = vec![file_reader(&bytes[0]), file_reader(&bytes[1])];
+        assert_eq!(
+            ConcatError::VariableMismatch{file_index: 1, var_name: String::from("time")},
+            concat_nc3(&mut file_readers, Cursor::new(vec![])).unwrap_err()
+        );
+    }
+}
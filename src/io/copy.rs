@@ -0,0 +1,571 @@
+use std::collections::HashMap;
+use std::io::{Read, Seek, Write};
+use std::rc::Rc;
+
+use crate::{DataSet, DataVector, Dimension, FileReader, FileWriter, Variable, Version};
+use crate::error::{CopyError, InvalidDataSet, WriteError};
+use crate::index::compute_strides;
+use crate::io::compute_header_required_size;
+
+/// Number of bytes of the buffer through which the variables are copied by [`copy_nc3`](fn.copy_nc3.html)
+const COPY_BUFFER_SIZE: usize = 1 << 16;
+
+/// Options of the function [`copy_nc3`](fn.copy_nc3.html).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CopyOptions {
+    /// Version of the output file, the version of the input file is kept if `None`.
+    pub version: Option<Version>,
+    /// Minimum number of bytes reserved for the header of the output file.
+    pub header_min_size: usize,
+    /// Names of the copied variables (the unused dimensions are not copied), all the variables are copied if `None`.
+    pub var_names: Option<Vec<String>>,
+    /// Alignment (number of bytes) of the beginning of the data part of the output file, no alignment if `0`.
+    pub data_alignment: usize,
+    /// Ranges of indices of the copied dimensions (hyperslabs), the whole dimensions are copied by default.
+    pub dim_slices: Vec<DimSlice>,
+}
+
+/// Range of indices `start..(start + count)` copied along a dimension (see [`CopyOptions`](struct.CopyOptions.html)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DimSlice {
+    pub dim_name: String,
+    pub start: usize,
+    pub count: usize,
+}
+
+impl DimSlice {
+    pub fn new(dim_name: &str, start: usize, count: usize) -> DimSlice {
+        return DimSlice{
+            dim_name: dim_name.to_string(),
+            start: start,
+            count: count,
+        };
+    }
+}
+
+/// Copies a NetCDF-3 file into `output`, possibly converting its version, reserving space for the header,
+/// selecting a subset of the variables and a range of indices along some dimensions.
+///
+/// The data are streamed : the *fixed-size* variables are copied one by one, and the record variables record by record.
+/// The chunks are copied as raw bytes through a fixed-size buffer (see [`copy_var`](fn.copy_var.html)), the sliced chunks
+/// by runs of contiguous selected bytes, so that the memory used does not depend on the size of the variables.
+/// The underlying output is returned once the copy is done.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{FileReader, Version, copy_nc3, CopyOptions, DimSlice};
+/// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+///
+/// let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+/// let options = CopyOptions{
+///     version: Some(Version::Offset64Bit),
+///     header_min_size: 0,
+///     var_names: Some(vec![String::from("latitude"), String::from("temperature_f32")]),
+///     data_alignment: 4096,
+///     dim_slices: vec![],
+/// };
+/// let output: Cursor<Vec<u8>> = copy_nc3(&mut file_reader, Cursor::new(vec![]), &options).unwrap();
+///
+/// let mut copy_reader = FileReader::from_reader(output).unwrap();
+/// assert_eq!(Version::Offset64Bit,                            copy_reader.version());
+/// assert_eq!(vec!["latitude", "temperature_f32"],             copy_reader.data_set().get_var_names());
+/// assert_eq!(vec!["latitude", "longitude", "time"],           copy_reader.data_set().dim_names());
+/// assert_eq!(file_reader.read_var("temperature_f32").unwrap(),    copy_reader.read_var("temperature_f32").unwrap());
+/// assert_eq!(Some(vec![(4096, 12)]),                          copy_reader.var_byte_ranges("latitude"));
+///
+/// // copy the last record and the 2 first latitudes
+/// let options = CopyOptions{
+///     dim_slices: vec![DimSlice::new("time", 1, 1), DimSlice::new("latitude", 0, 2)],
+///     ..CopyOptions::default()
+/// };
+/// let output: Cursor<Vec<u8>> = copy_nc3(&mut file_reader, Cursor::new(vec![]), &options).unwrap();
+///
+/// let mut copy_reader = FileReader::from_reader(output).unwrap();
+/// assert_eq!(Some(1),                                         copy_reader.data_set().num_records());
+/// assert_eq!(vec![438324.0],                                  copy_reader.read_var_f32("time").unwrap());
+/// assert_eq!(vec![0.0, 0.5],                                  copy_reader.read_var_f32("latitude").unwrap());
+/// assert_eq!(
+///     vec![15.0, 16.0, 17.0, 18.0, 19.0, 20.0, 21.0, 22.0, 23.0, 24.0],
+///     copy_reader.read_var_f32("temperature_f32").unwrap()
+/// );
+/// ```
+pub fn copy_nc3<R: Read + Seek, W: Write + Seek>(file_reader: &mut FileReader<R>, output: W, options: &CopyOptions) -> Result<W, CopyError> {
+    let dim_ranges: HashMap<String, (usize, usize)> = check_dim_slices(file_reader.data_set(), &options.dim_slices)?;
+    let data_set: DataSet = match &options.var_names {
+        None => select_vars(file_reader.data_set(), &file_reader.data_set().get_var_names(), &dim_ranges)?,
+        Some(var_names) => {
+            if let Some(var_name) = var_names.iter().find(|var_name: &&String| !file_reader.data_set().has_var(var_name)) {
+                return Err(CopyError::VariableNotDefined(var_name.to_string()));
+            }
+            // the variables keep the order of the input file
+            let var_names: Vec<String> = file_reader.data_set().get_var_names().into_iter()
+                .filter(|var_name: &String| var_names.contains(var_name))
+                .collect();
+            select_vars(file_reader.data_set(), &var_names, &dim_ranges)?
+        },
+    };
+    let version: Version = options.version.unwrap_or_else(|| file_reader.version());
+    return copy_data_set(file_reader, output, &data_set, version, options.header_min_size, options.data_alignment, &dim_ranges);
+}
+
+/// Writes the data set `data_set` (defining a subset of the variables of `file_reader`, possibly in another order) into `output`,
+/// and copies the data of its variables in the ranges `dim_ranges`.
+pub(crate) fn copy_data_set<R: Read + Seek, W: Write + Seek>(file_reader: &mut FileReader<R>, output: W, data_set: &DataSet, version: Version,
+    header_min_size: usize, data_alignment: usize, dim_ranges: &HashMap<String, (usize, usize)>) -> Result<W, CopyError>
+{
+    let header_min_size: usize = {
+        let mut header_min_size: usize = std::cmp::max(header_min_size, compute_header_required_size(data_set, version));
+        if data_alignment > 0 && header_min_size % data_alignment != 0 {
+            header_min_size += data_alignment - (header_min_size % data_alignment);
+        }
+        header_min_size
+    };
+
+    let mut file_writer: FileWriter<W> = FileWriter::from_writer(output);
+    file_writer.set_def(data_set, version, header_min_size)?;
+
+    // Ranges of indices of each copied variable along its dimensions
+    let var_ranges = |var: &Variable| -> Vec<(usize, usize)> {
+        var.dims().iter().map(|dim: &Rc<Dimension>| {
+            dim_ranges.get(&dim.name()).copied().unwrap_or((0, dim.size()))
+        }).collect()
+    };
+
+    // The variables which are not sliced are copied as raw bytes
+    let mut buffer: Vec<u8> = vec![0; COPY_BUFFER_SIZE];
+    let (record_vars, fixed_size_vars): (Vec<&Variable>, Vec<&Variable>) = data_set.vars().iter().partition(|var: &&Variable| var.is_record_var());
+    for var in fixed_size_vars.into_iter() {
+        let input_var: &Variable = file_reader.data_set().get_var(var.name()).ok_or_else(|| CopyError::VariableNotDefined(var.name().to_string()))?;
+        let (shape, ranges): (Vec<usize>, Vec<(usize, usize)>) = (input_var.dims().iter().map(|dim: &Rc<Dimension>| dim.size()).collect(), var_ranges(input_var));
+        if is_whole_range(&shape, &ranges) {
+            copy_chunk(file_reader, &mut file_writer, var.name(), 0, 0, &mut buffer)?;
+        } else {
+            let layout = SliceLayout::new(&shape, &ranges, input_var.data_type().size_of());
+            copy_sliced_chunk(file_reader, &mut file_writer, var.name(), 0, 0, &layout, &mut buffer)?;
+        }
+    }
+    let (first_record_index, num_records): (usize, usize) = match file_reader.data_set().get_unlimited_dim() {
+        Some(dim) => dim_ranges.get(&dim.name()).copied().unwrap_or((0, dim.size())),
+        None => (0, 0),
+    };
+    let record_vars: Vec<_> = record_vars.into_iter().map(|var: &Variable| {
+        // the ranges along the dimensions of a record (the *unlimited-size* dimension excepted)
+        let input_var: &Variable = file_reader.data_set().get_var(var.name()).unwrap();
+        let shape: Vec<usize> = input_var.dims().iter().skip(1).map(|dim: &Rc<Dimension>| dim.size()).collect();
+        let ranges: Vec<(usize, usize)> = var_ranges(input_var).into_iter().skip(1).collect();
+        let layout: Option<SliceLayout> = if is_whole_range(&shape, &ranges) {
+            None
+        } else {
+            Some(SliceLayout::new(&shape, &ranges, input_var.data_type().size_of()))
+        };
+        (var, layout)
+    }).collect();
+    for record_index in 0..num_records {
+        for (var, layout) in record_vars.iter() {
+            match layout {
+                None => copy_chunk(file_reader, &mut file_writer, var.name(), first_record_index + record_index, record_index, &mut buffer)?,
+                Some(layout) => copy_sliced_chunk(file_reader, &mut file_writer, var.name(), first_record_index + record_index, record_index, layout, &mut buffer)?,
+            }
+        }
+    }
+    return Ok(file_writer.close_into_inner()?);
+}
+
+/// Copies the data of a variable from `file_reader` into `file_writer`, through a buffer of at most `buffer_size` bytes.
+///
+/// The chunks (the whole data of a *fixed-size* variable, or each of its records) are copied part by part as raw bytes,
+/// without decoding the values, so that the memory used does not depend on the size of the variable.
+/// The variable must have the same data type and the same chunk length in both files,
+/// only the records existing in both files are copied (the other ones are filled when the writer is closed).
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{FileReader, FileWriter, DataSet, Version, copy_var};
+/// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+///
+/// let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+/// let mut data_set = DataSet::new();
+/// data_set.set_unlimited_dim("time", 2).unwrap();
+/// data_set.add_fixed_dim("latitude", 3).unwrap();
+/// data_set.add_fixed_dim("longitude", 5).unwrap();
+/// data_set.add_var_f32("temperature_f32", &["time", "latitude", "longitude"]).unwrap();
+///
+/// let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+/// file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+/// // copy the data 16 bytes at a time
+/// copy_var(&mut file_reader, &mut file_writer, "temperature_f32", 16).unwrap();
+/// let output: Cursor<Vec<u8>> = file_writer.close_into_inner().unwrap();
+///
+/// let mut copy_reader = FileReader::from_reader(output).unwrap();
+/// assert_eq!(file_reader.read_var("temperature_f32").unwrap(),    copy_reader.read_var("temperature_f32").unwrap());
+/// ```
+pub fn copy_var<R: Read + Seek, W: Write + Seek>(file_reader: &mut FileReader<R>, file_writer: &mut FileWriter<W>, var_name: &str, buffer_size: usize) -> Result<(), CopyError> {
+    let input_var: &Variable = file_reader.data_set().get_var(var_name).ok_or_else(|| CopyError::VariableNotDefined(var_name.to_string()))?;
+    let output_data_set: &DataSet = file_writer.data_set().ok_or(WriteError::HeaderNotDefined)?;
+    let output_var: &Variable = output_data_set.get_var(var_name).ok_or_else(|| CopyError::VariableNotDefined(var_name.to_string()))?;
+    if input_var.data_type() != output_var.data_type() {
+        return Err(WriteError::VariableMismatchDataType{var_name: var_name.to_string(), req: output_var.data_type(), get: input_var.data_type()}.into());
+    }
+    if input_var.chunk_len() != output_var.chunk_len() {
+        return Err(WriteError::RecordMismatchDataLength{var_name: var_name.to_string(), req: output_var.chunk_len(), get: input_var.chunk_len()}.into());
+    }
+    let num_chunks: usize = std::cmp::min(input_var.num_chunks(), output_var.num_chunks());
+    let chunk_size: usize = input_var.chunk_len() * input_var.data_type().size_of();
+
+    let mut buffer: Vec<u8> = vec![0; std::cmp::max(std::cmp::min(buffer_size, chunk_size), 1)];
+    for chunk_index in 0..num_chunks {
+        copy_chunk(file_reader, file_writer, var_name, chunk_index, chunk_index, &mut buffer)?;
+    }
+    return Ok(());
+}
+
+/// Copies the chunk `input_chunk_index` of a variable into the chunk `output_chunk_index`, part by part through `buffer`.
+///
+/// The data type and the chunk length of the variable are checked by the caller.
+fn copy_chunk<R: Read + Seek, W: Write + Seek>(file_reader: &mut FileReader<R>, file_writer: &mut FileWriter<W>, var_name: &str,
+    input_chunk_index: usize, output_chunk_index: usize, buffer: &mut [u8]) -> Result<(), CopyError>
+{
+    let begin_offset: u64 = file_reader.var_begin_offset(var_name).ok_or_else(|| CopyError::VariableNotDefined(var_name.to_string()))?;
+    let record_size: u64 = file_reader.data_set().record_size().unwrap_or(0) as u64;
+    let chunk_offset: u64 = begin_offset + (input_chunk_index as u64) * record_size;
+    return file_writer.write_chunk_by_parts(var_name, output_chunk_index, buffer, |offset: usize, part: &mut [u8]| -> Result<(), CopyError> {
+        file_reader.read_bytes_at(chunk_offset + offset as u64, part)?;
+        return Ok(());
+    });
+}
+
+/// Checks the slices of the dimensions, and returns the range `(start, count)` of each sliced dimension.
+fn check_dim_slices(data_set: &DataSet, dim_slices: &[DimSlice]) -> Result<HashMap<String, (usize, usize)>, CopyError> {
+    let mut dim_ranges: HashMap<String, (usize, usize)> = HashMap::new();
+    for dim_slice in dim_slices.iter() {
+        let dim: Rc<Dimension> = data_set.get_dim(&dim_slice.dim_name).ok_or_else(|| CopyError::DimensionNotDefined(dim_slice.dim_name.clone()))?;
+        // the *fixed-size* dimensions cannot be empty
+        let is_valid: bool = (dim_slice.count > 0 || dim.is_unlimited())
+            && dim_slice.start.checked_add(dim_slice.count).map(|end: usize| end <= dim.size()).unwrap_or(false);
+        if !is_valid || dim_ranges.contains_key(&dim_slice.dim_name) {
+            return Err(CopyError::DimSliceNotValid{
+                dim_name: dim_slice.dim_name.clone(),
+                start: dim_slice.start,
+                count: dim_slice.count,
+                dim_size: dim.size(),
+            });
+        }
+        dim_ranges.insert(dim_slice.dim_name.clone(), (dim_slice.start, dim_slice.count));
+    }
+    return Ok(dim_ranges);
+}
+
+/// Returns `true` if the ranges `(start, count)` select all the indices of an array of shape `shape`.
+fn is_whole_range(shape: &[usize], ranges: &[(usize, usize)]) -> bool {
+    return shape.iter().zip(ranges.iter()).all(|(size, (start, count)): (&usize, &(usize, usize))| *start == 0 && count == size);
+}
+
+/// Positions of the bytes of the elements selected in a chunk (an array of shape `shape`) by the ranges `(start, count)`
+/// along each dimension.
+///
+/// The selected bytes are stored in runs of contiguous bytes : the trailing dimensions which are entirely selected,
+/// and the selected indices of the previous dimension.
+#[derive(Debug)]
+struct SliceLayout {
+    /// Ranges of the dimensions preceding the runs
+    outer_ranges: Vec<(usize, usize)>,
+    /// Number of bytes between two consecutive indices of the dimensions preceding the runs
+    outer_strides: Vec<usize>,
+    /// Position of the first run in the chunk
+    run_start: usize,
+    run_size: usize,
+}
+
+impl SliceLayout {
+
+    /// The ranges do not select all the elements and no range is empty, the shape of the chunk of an existing variable does not overflow.
+    fn new(shape: &[usize], ranges: &[(usize, usize)], elem_size: usize) -> SliceLayout {
+        let strides: Vec<usize> = compute_strides(shape).unwrap_or_default().into_iter().map(|stride: usize| stride * elem_size).collect();
+        let run_dim: usize = (0..ranges.len()).rev()
+            .find(|dim_index: &usize| ranges[*dim_index] != (0, shape[*dim_index]))
+            .unwrap_or(0);
+        let (run_dim_start, run_dim_count): (usize, usize) = ranges[run_dim];
+        return SliceLayout{
+            outer_ranges: ranges[0..run_dim].to_vec(),
+            outer_strides: strides[0..run_dim].to_vec(),
+            run_start: run_dim_start * strides[run_dim],
+            run_size: run_dim_count * strides[run_dim],
+        };
+    }
+
+    /// Returns the position in the chunk of the selected byte `offset`, and the number of the selected bytes following it contiguously.
+    fn locate(&self, offset: usize) -> (usize, usize) {
+        let mut run_index: usize = offset / self.run_size;
+        let mut position: usize = self.run_start + offset % self.run_size;
+        for ((start, count), stride) in self.outer_ranges.iter().zip(self.outer_strides.iter()).rev() {
+            position += (start + run_index % count) * stride;
+            run_index /= count;
+        }
+        return (position, self.run_size - offset % self.run_size);
+    }
+}
+
+/// Copies the bytes of the chunk `input_chunk_index` of a variable selected by `layout` into the chunk `output_chunk_index`,
+/// part by part through `buffer`.
+fn copy_sliced_chunk<R: Read + Seek, W: Write + Seek>(file_reader: &mut FileReader<R>, file_writer: &mut FileWriter<W>, var_name: &str,
+    input_chunk_index: usize, output_chunk_index: usize, layout: &SliceLayout, buffer: &mut [u8]) -> Result<(), CopyError>
+{
+    let begin_offset: u64 = file_reader.var_begin_offset(var_name).ok_or_else(|| CopyError::VariableNotDefined(var_name.to_string()))?;
+    let record_size: u64 = file_reader.data_set().record_size().unwrap_or(0) as u64;
+    let chunk_offset: u64 = begin_offset + (input_chunk_index as u64) * record_size;
+    return file_writer.write_chunk_by_parts(var_name, output_chunk_index, buffer, |offset: usize, part: &mut [u8]| -> Result<(), CopyError> {
+        let mut num_copied_bytes: usize = 0;
+        while num_copied_bytes < part.len() {
+            let (position, run_size): (usize, usize) = layout.locate(offset + num_copied_bytes);
+            let num_bytes: usize = std::cmp::min(run_size, part.len() - num_copied_bytes);
+            file_reader.read_bytes_at(chunk_offset + position as u64, &mut part[num_copied_bytes..(num_copied_bytes + num_bytes)])?;
+            num_copied_bytes += num_bytes;
+        }
+        return Ok(());
+    });
+}
+
+/// Writes all the data of a variable, whatever its data type.
+pub(crate) fn write_var_data<W: Write + Seek>(file_writer: &mut FileWriter<W>, var_name: &str, data: &DataVector) -> Result<(), WriteError> {
+    return match data {
+        DataVector::I8(data) => file_writer.write_var_i8(var_name, data),
+        DataVector::U8(data) => file_writer.write_var_u8(var_name, data),
+        DataVector::I16(data) => file_writer.write_var_i16(var_name, data),
+        DataVector::I32(data) => file_writer.write_var_i32(var_name, data),
+        DataVector::F32(data) => file_writer.write_var_f32(var_name, data),
+        DataVector::F64(data) => file_writer.write_var_f64(var_name, data),
+    };
+}
+
+/// Writes a record of a variable, whatever its data type.
+pub(crate) fn write_record_data<W: Write + Seek>(file_writer: &mut FileWriter<W>, var_name: &str, record_index: usize, record: &DataVector) -> Result<(), WriteError> {
+    return match record {
+        DataVector::I8(record) => file_writer.write_record_i8(var_name, record_index, record),
+        DataVector::U8(record) => file_writer.write_record_u8(var_name, record_index, record),
+        DataVector::I16(record) => file_writer.write_record_i16(var_name, record_index, record),
+        DataVector::I32(record) => file_writer.write_record_i32(var_name, record_index, record),
+        DataVector::F32(record) => file_writer.write_record_f32(var_name, record_index, record),
+        DataVector::F64(record) => file_writer.write_record_f64(var_name, record_index, record),
+    };
+}
+
+/// Returns a new data set containing the selected variables (in the order of `var_names`), the dimensions they use and the global attributes.
+///
+/// The dimensions in `dim_ranges` are resized to the number of selected indices, the undefined variables are ignored.
+pub(crate) fn select_vars<T: AsRef<str>>(data_set: &DataSet, var_names: &[T], dim_ranges: &HashMap<String, (usize, usize)>) -> Result<DataSet, InvalidDataSet> {
+    let selected_vars: Vec<&Variable> = var_names.iter().filter_map(|var_name: &T| data_set.get_var(var_name.as_ref())).collect();
+
+    let mut selected_data_set = DataSet::new();
+    for dim in data_set.dims().iter() {
+        if !selected_vars.iter().any(|var: &&Variable| var.use_dim(&dim.name())) {
+            continue;
+        }
+        let dim_size: usize = dim_ranges.get(&dim.name()).map(|(_start, count): &(usize, usize)| *count).unwrap_or_else(|| dim.size());
+        if dim.is_unlimited() {
+            selected_data_set.set_unlimited_dim(dim.name(), dim_size)?;
+        } else {
+            selected_data_set.add_fixed_dim(dim.name(), dim_size)?;
+        }
+    }
+    selected_data_set.attrs = data_set.attrs.clone();
+    for var in selected_vars.into_iter() {
+        let dim_names: Vec<String> = var.dims().iter().map(|dim: &Rc<Dimension>| dim.name()).collect();
+        selected_data_set.add_var(var.name(), &dim_names, var.data_type())?;
+        let selected_var: &mut Variable = selected_data_set.get_var_mut(var.name()).ok_or_else(|| InvalidDataSet::VariableNotDefined(var.name().to_string()))?;
+        selected_var.attrs = var.attrs.clone();
+    }
+    return Ok(selected_data_set);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Cursor;
+
+    use copy_to_tmp_file::{NC3_CLASSIC_FILE_BYTES, NC3_64BIT_OFFSET_FILE_BYTES};
+
+    use super::{copy_nc3, copy_var, CopyOptions, DimSlice, SliceLayout};
+    use crate::{DataSet, DataType, FileReader, FileWriter, Version};
+    use crate::error::{CopyError, WriteError};
+
+    #[test]
+    fn test_copy_nc3_identity() {
+        // the copy of a file produced by the netCDF-C library is identical
+        for input_bytes in [NC3_CLASSIC_FILE_BYTES, NC3_64BIT_OFFSET_FILE_BYTES].iter() {
+            let mut file_reader = FileReader::from_reader(Cursor::new(*input_bytes)).unwrap();
+            let output: Vec<u8> = copy_nc3(&mut file_reader, Cursor::new(vec![]), &CopyOptions::default()).unwrap().into_inner();
+            assert_eq!(input_bytes.to_vec(),     output);
+        }
+    }
+
+    #[test]
+    fn test_copy_nc3_convert_version() {
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        let options = CopyOptions{version: Some(Version::Offset64Bit), ..CopyOptions::default()};
+        let output = copy_nc3(&mut file_reader, Cursor::new(vec![]), &options).unwrap();
+
+        let mut copy_reader = FileReader::from_reader(output).unwrap();
+        assert_eq!(Version::Offset64Bit,                    copy_reader.version());
+        assert_eq!(file_reader.data_set(),                  copy_reader.data_set());
+        assert_eq!(file_reader.read_all_vars().unwrap(),    copy_reader.read_all_vars().unwrap());
+    }
+
+    #[test]
+    fn test_copy_nc3_latin1_names() {
+        use crate::{Diagnostic, NameDecoding, ReadOptions};
+
+        // Replace the name of the dimension `latitude` by the Latin-1 encoded name `látitude`
+        let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+        let position: usize = bytes.windows(8).position(|window: &[u8]| window == b"latitude").unwrap();
+        bytes[position + 1] = 0xE1;
+
+        let options = ReadOptions{name_decoding: NameDecoding::Latin1, ..ReadOptions::default()};
+        let mut file_reader = FileReader::from_reader_with_options(Cursor::new(bytes), &options).unwrap();
+        assert_eq!(
+            vec![Diagnostic::NameNotValidUtf8{bytes: b"l\xE1titude".to_vec(), name: String::from("l\u{e1}titude")}],
+            file_reader.diagnostics().as_slice()
+        );
+
+        // the copy stores the UTF-8 encoded name (1 more byte), and is read without decoding option
+        let output: Vec<u8> = copy_nc3(&mut file_reader, Cursor::new(vec![]), &CopyOptions::default()).unwrap().into_inner();
+        assert_eq!(true,                                    output.windows(9).any(|window: &[u8]| window == "l\u{e1}titude".as_bytes()));
+        let mut copy_reader = FileReader::from_reader(Cursor::new(output)).unwrap();
+        assert_eq!(true,                                    copy_reader.diagnostics().is_empty());
+        assert_eq!(file_reader.data_set(),                  copy_reader.data_set());
+        assert_eq!(file_reader.read_all_vars().unwrap(),    copy_reader.read_all_vars().unwrap());
+    }
+
+    #[test]
+    fn test_copy_nc3_header_min_size() {
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        let options = CopyOptions{header_min_size: 10_000, ..CopyOptions::default()};
+        let output = copy_nc3(&mut file_reader, Cursor::new(vec![]), &options).unwrap();
+        let copy_reader = FileReader::from_reader(output).unwrap();
+        assert_eq!(Some(vec![(10_000, 12)]),        copy_reader.var_byte_ranges("latitude"));
+    }
+
+    #[test]
+    fn test_copy_nc3_undefined_var() {
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        let options = CopyOptions{var_names: Some(vec![String::from("undef_var")]), ..CopyOptions::default()};
+        assert_eq!(
+            CopyError::VariableNotDefined(String::from("undef_var")),
+            copy_nc3(&mut file_reader, Cursor::new(vec![]), &options).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_slice_layout() {
+        // 2x3 matrix of `i32`, the columns 1 and 2
+        let layout = SliceLayout::new(&[2, 3], &[(0, 2), (1, 2)], 4);
+        assert_eq!((4, 8),          layout.locate(0));
+        assert_eq!((10, 2),         layout.locate(6));
+        assert_eq!((16, 8),         layout.locate(8));
+        // the row 1, a single run
+        let layout = SliceLayout::new(&[2, 3], &[(1, 1), (0, 3)], 4);
+        assert_eq!((12, 12),        layout.locate(0));
+        assert_eq!((20, 4),         layout.locate(8));
+        // 2x2x3 array of `u8`, the last row of each matrix
+        let layout = SliceLayout::new(&[2, 2, 3], &[(0, 2), (1, 1), (0, 3)], 1);
+        assert_eq!((3, 3),          layout.locate(0));
+        assert_eq!((9, 3),          layout.locate(3));
+        assert_eq!((11, 1),         layout.locate(5));
+    }
+
+    #[test]
+    fn test_copy_nc3_dim_slices_not_valid() {
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        let copy_with_slices = |file_reader: &mut FileReader<Cursor<&[u8]>>, dim_slices: Vec<DimSlice>| -> CopyError {
+            let options = CopyOptions{dim_slices: dim_slices, ..CopyOptions::default()};
+            copy_nc3(file_reader, Cursor::new(vec![]), &options).unwrap_err()
+        };
+        assert_eq!(
+            CopyError::DimensionNotDefined(String::from("undef_dim")),
+            copy_with_slices(&mut file_reader, vec![DimSlice::new("undef_dim", 0, 1)])
+        );
+        assert_eq!(
+            CopyError::DimSliceNotValid{dim_name: String::from("latitude"), start: 2, count: 2, dim_size: 3},
+            copy_with_slices(&mut file_reader, vec![DimSlice::new("latitude", 2, 2)])
+        );
+        assert_eq!(
+            CopyError::DimSliceNotValid{dim_name: String::from("latitude"), start: 0, count: 0, dim_size: 3},
+            copy_with_slices(&mut file_reader, vec![DimSlice::new("latitude", 0, 0)])
+        );
+        assert_eq!(
+            CopyError::DimSliceNotValid{dim_name: String::from("time"), start: 0, count: 1, dim_size: 2},
+            copy_with_slices(&mut file_reader, vec![DimSlice::new("time", 1, 1), DimSlice::new("time", 0, 1)])
+        );
+        // no record
+        let options = CopyOptions{dim_slices: vec![DimSlice::new("time", 2, 0)], ..CopyOptions::default()};
+        let output = copy_nc3(&mut file_reader, Cursor::new(vec![]), &options).unwrap();
+        assert_eq!(Some(0),             FileReader::from_reader(output).unwrap().data_set().num_records());
+    }
+
+    #[test]
+    fn test_copy_var() {
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        let (data_set, _version): (DataSet, Version) = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap().close();
+        // the buffer is smaller than the chunks, or does not divide them
+        for buffer_size in [0, 1, 3, 7, 1_000_000].iter() {
+            let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+            file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+            for var_name in data_set.get_var_names().iter() {
+                copy_var(&mut file_reader, &mut file_writer, var_name, *buffer_size).unwrap();
+            }
+            let output: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+            assert_eq!(NC3_CLASSIC_FILE_BYTES.to_vec(),     output);
+        }
+    }
+
+    #[test]
+    fn test_copy_var_records() {
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        // only the first record is copied, the other ones are filled
+        let mut data_set = DataSet::new();
+        data_set.set_unlimited_dim("time", 3).unwrap();
+        data_set.add_var_f32("time", &["time"]).unwrap();
+        let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+        file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+        copy_var(&mut file_reader, &mut file_writer, "time", 2).unwrap();
+        let mut copy_reader = FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+        let mut expected_data: Vec<f32> = file_reader.read_var_f32("time").unwrap();
+        expected_data.push(crate::NC_FILL_F32);
+        assert_eq!(expected_data,                       copy_reader.read_var_f32("time").unwrap());
+    }
+
+    #[test]
+    fn test_copy_var_errors() {
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        let mut data_set = DataSet::new();
+        data_set.add_fixed_dim("latitude", 2).unwrap();
+        data_set.add_var_i32("latitude", &["latitude"]).unwrap();
+        data_set.add_var_f32("longitude", &["latitude"]).unwrap();
+
+        let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+        assert_eq!(
+            CopyError::Write(WriteError::HeaderNotDefined),
+            copy_var(&mut file_reader, &mut file_writer, "latitude", 16).unwrap_err()
+        );
+        file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+        assert_eq!(
+            CopyError::VariableNotDefined(String::from("undef_var")),
+            copy_var(&mut file_reader, &mut file_writer, "undef_var", 16).unwrap_err()
+        );
+        assert_eq!(
+            CopyError::VariableNotDefined(String::from("temperature_f32")),
+            copy_var(&mut file_reader, &mut file_writer, "temperature_f32", 16).unwrap_err()
+        );
+        assert_eq!(
+            CopyError::Write(WriteError::VariableMismatchDataType{var_name: String::from("latitude"), req: DataType::I32, get: DataType::F32}),
+            copy_var(&mut file_reader, &mut file_writer, "latitude", 16).unwrap_err()
+        );
+        assert_eq!(
+            CopyError::Write(WriteError::RecordMismatchDataLength{var_name: String::from("longitude"), req: 2, get: 5}),
+            copy_var(&mut file_reader, &mut file_writer, "longitude", 16).unwrap_err()
+        );
+    }
+}
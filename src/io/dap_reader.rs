@@ -0,0 +1,805 @@
+use std::collections::HashMap;
+use std::io::Read;
+
+use byteorder::{ReadBytesExt, BigEndian};
+
+use crate::{
+    DataSet,
+    DataType,
+    DataVector,
+    error::DapError,
+    io::compute_padding_size,
+};
+
+/// Name of the DAS container holding the global attributes.
+const GLOBAL_ATTRS_CONTAINER_NAME: &str = "NC_GLOBAL";
+/// Name of the DAS container holding the extra informations (the unlimited dimension, etc.).
+const DODS_EXTRA_CONTAINER_NAME: &str = "DODS_EXTRA";
+const UNLIMITED_DIM_ATTR_NAME: &str = "Unlimited_Dimension";
+/// Separator between the DDS and the XDR encoded data in the `.dods` responses.
+const DODS_DATA_SEPARATOR: &[u8] = b"\nData:\n";
+
+/// Allows to read the data sets served by an [OPeNDAP](https://www.opendap.org/) (DAP2) server (requires the feature `dap`).
+///
+/// The data set (dimensions, variables and attributes) is built from the DDS and the DAS responses when the reader is opened,
+/// the variable data are then fetched from the `.dods` responses.
+///
+/// The DAP2 types are converted into the NetCDF-3 data types :
+///
+/// | DAP2 type          | NetCDF-3 data type |
+/// |--------------------|--------------------|
+/// | `Byte`             | `DataType::U8`     |
+/// | `Int16`            | `DataType::I16`    |
+/// | `UInt16`, `Int32`  | `DataType::I32`    |
+/// | `Float32`          | `DataType::F32`    |
+/// | `UInt32`, `Float64`| `DataType::F64`    |
+///
+/// The `String` and `Url` variables, the `Structure` and the `Sequence` constructors are not supported.
+/// The arrays and the maps of the `Grid` constructors are defined as variables of the data set.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::collections::HashMap;
+/// use netcdf3::{DapReader, DataVector};
+///
+/// let dap_reader = DapReader::open("https://psl.noaa.gov/thredds/dodsC/Datasets/noaa.oisst.v2/sst.mnmean.nc").unwrap();
+/// assert_eq!(true,                    dap_reader.data_set().has_var("sst"));
+///
+/// // Read a whole variable
+/// let lat: DataVector = dap_reader.read_var("lat").unwrap();
+///
+/// // Read a subset of a variable using a constraint expression
+/// let sst: HashMap<String, DataVector> = dap_reader.read_subset("sst[0:1:0][0:1:9][0:1:9]").unwrap();
+/// assert_eq!(100,                     sst["sst"].len());
+/// ```
+#[derive(Debug)]
+pub struct DapReader {
+    url: String,
+    data_set: DataSet,
+    max_alloc_bytes: Option<usize>,
+}
+
+impl DapReader {
+
+    /// Fetches the DDS and the DAS of the data set located at `url` (without the extensions `.dds`, `.das`, etc.).
+    pub fn open(url: &str) -> Result<Self, DapError> {
+        let dds: String = fetch_text(&format!("{}.dds", url))?;
+        let das: String = fetch_text(&format!("{}.das", url))?;
+        let dds_vars: Vec<DdsVariable> = parse_dds(&dds)?;
+        let das_containers: Vec<DasContainer> = parse_das(&das)?;
+        let data_set: DataSet = build_data_set(&dds_vars, &das_containers)?;
+        return Ok(DapReader {
+            url: url.to_string(),
+            data_set: data_set,
+            max_alloc_bytes: None,
+        });
+    }
+
+    pub fn data_set(&self) -> &DataSet {
+        &self.data_set
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the maximum number of bytes allocated to decode the data of a variable (see [`set_max_alloc_bytes`](#method.set_max_alloc_bytes)).
+    pub fn max_alloc_bytes(&self) -> Option<usize> {
+        return self.max_alloc_bytes;
+    }
+
+    /// Sets the maximum number of bytes allocated to decode the data of a variable, `None` for no limit.
+    ///
+    /// The methods reading the variables fail with the error
+    /// [`DapError::MaximumAllocationExceeded`](error/enum.DapError.html#variant.MaximumAllocationExceeded)
+    /// instead of allocating more memory.
+    pub fn set_max_alloc_bytes(&mut self, max_alloc_bytes: Option<usize>) {
+        self.max_alloc_bytes = max_alloc_bytes;
+    }
+
+    /// Closes the reader and releases the data set.
+    pub fn close(self) -> DataSet {
+        self.data_set
+    }
+
+    /// Fetches all the variable data.
+    pub fn read_all_vars(&self) -> Result<HashMap<String, DataVector>, DapError> {
+        let dods: Vec<u8> = fetch_bytes(&format!("{}.dods", self.url))?;
+        return parse_dods(&dods, self.max_alloc_bytes);
+    }
+
+    /// Fetches the data of one variable.
+    pub fn read_var(&self, var_name: &str) -> Result<DataVector, DapError> {
+        if !self.data_set.has_var(var_name) {
+            return Err(DapError::VariableNotDefined(var_name.to_string()));
+        }
+        let mut data: HashMap<String, DataVector> = self.read_subset(var_name)?;
+        return data.remove(var_name).ok_or_else(|| DapError::DataNotValid(format!("variable `{}` is missing in the response", var_name)));
+    }
+
+    /// Fetches the variable data selected by a DAP2 constraint expression, evaluated by the server.
+    ///
+    /// For example the constraint expression `"sst[0:1:0][10:2:20][0:1:9],lat"` selects :
+    ///
+    /// - the first record, one latitude over two between the indices `10` and `20`,
+    ///   and the 10 first longitudes of the variable `sst`,
+    /// - the whole variable `lat`.
+    ///
+    /// The arrays and the maps of the selected grids are returned.
+    pub fn read_subset(&self, constraint: &str) -> Result<HashMap<String, DataVector>, DapError> {
+        let dods: Vec<u8> = fetch_bytes(&format!("{}.dods?{}", self.url, encode_constraint(constraint)))?;
+        return parse_dods(&dods, self.max_alloc_bytes);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// HTTP requests
+// ---------------------------------------------------------------------------
+
+fn fetch_bytes(url: &str) -> Result<Vec<u8>, DapError> {
+    let http_error = |message: String| DapError::HttpRequest{url: url.to_string(), message: message};
+    let response = ureq::get(url).call().map_err(|err: ureq::Error| http_error(err.to_string()))?;
+    let mut bytes: Vec<u8> = vec![];
+    response.into_reader().read_to_end(&mut bytes).map_err(|err: std::io::Error| http_error(err.to_string()))?;
+    return Ok(bytes);
+}
+
+fn fetch_text(url: &str) -> Result<String, DapError> {
+    let bytes: Vec<u8> = fetch_bytes(url)?;
+    return String::from_utf8(bytes).map_err(|_err| DapError::HttpRequest{url: url.to_string(), message: String::from("the response is not UTF-8 encoded")});
+}
+
+/// Percent-encodes the characters of a constraint expression which are not allowed in the query of an URL.
+fn encode_constraint(constraint: &str) -> String {
+    let mut encoded = String::with_capacity(constraint.len());
+    for byte in constraint.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.~,:=&*!()".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    return encoded;
+}
+
+// ---------------------------------------------------------------------------
+// DAP2 types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DapType {
+    Byte,
+    Int16,
+    UInt16,
+    Int32,
+    UInt32,
+    Float32,
+    Float64,
+    String,
+    Url,
+}
+
+impl DapType {
+
+    fn parse(type_name: &str) -> Option<DapType> {
+        let dap_type: DapType = match type_name.to_ascii_lowercase().as_str() {
+            "byte" => DapType::Byte,
+            "int16" => DapType::Int16,
+            "uint16" => DapType::UInt16,
+            "int32" => DapType::Int32,
+            "uint32" => DapType::UInt32,
+            "float32" => DapType::Float32,
+            "float64" => DapType::Float64,
+            "string" => DapType::String,
+            "url" => DapType::Url,
+            _ => return None,
+        };
+        return Some(dap_type);
+    }
+
+    /// Returns the NetCDF-3 data type able to store the values, `None` for the strings.
+    fn data_type(&self) -> Option<DataType> {
+        return match self {
+            DapType::Byte => Some(DataType::U8),
+            DapType::Int16 => Some(DataType::I16),
+            DapType::UInt16 => Some(DataType::I32),
+            DapType::Int32 => Some(DataType::I32),
+            DapType::UInt32 => Some(DataType::F64),
+            DapType::Float32 => Some(DataType::F32),
+            DapType::Float64 => Some(DataType::F64),
+            DapType::String => None,
+            DapType::Url => None,
+        };
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tokenizer of the DDS and the DAS
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Punct(char),
+}
+
+const PUNCT_CHARS: &str = "{}[];=:,";
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens: Vec<Token> = vec![];
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        if PUNCT_CHARS.contains(c) {
+            tokens.push(Token::Punct(c));
+        } else if c == '"' {
+            let mut quoted = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => match chars.next() {
+                        Some(escaped) => quoted.push(escaped),
+                        None => return Err(String::from("unterminated string")),
+                    },
+                    Some(c) => quoted.push(c),
+                    None => return Err(String::from("unterminated string")),
+                }
+            }
+            tokens.push(Token::Quoted(quoted));
+        } else {
+            let mut word = String::new();
+            word.push(c);
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' || PUNCT_CHARS.contains(c) {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+        }
+    }
+    return Ok(tokens);
+}
+
+/// Cursor over the tokens, the errors are returned as messages.
+struct TokenStream {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl TokenStream {
+
+    fn new(text: &str) -> Result<Self, String> {
+        return Ok(TokenStream{tokens: tokenize(text)?, pos: 0});
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset)
+    }
+
+    fn next(&mut self) -> Result<Token, String> {
+        let token: Token = self.tokens.get(self.pos).cloned().ok_or_else(|| String::from("unexpected end of the input"))?;
+        self.pos += 1;
+        return Ok(token);
+    }
+
+    fn next_is_punct(&self, punct: char) -> bool {
+        self.peek() == Some(&Token::Punct(punct))
+    }
+
+    fn expect_punct(&mut self, punct: char) -> Result<(), String> {
+        match self.next()? {
+            Token::Punct(c) if c == punct => Ok(()),
+            token => Err(format!("`{}` expected, found {:?}", punct, token)),
+        }
+    }
+
+    fn expect_word(&mut self) -> Result<String, String> {
+        match self.next()? {
+            Token::Word(word) => Ok(word),
+            token => Err(format!("word expected, found {:?}", token)),
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<(), String> {
+        let word: String = self.expect_word()?;
+        if !word.eq_ignore_ascii_case(keyword) {
+            return Err(format!("`{}` expected, found `{}`", keyword, word));
+        }
+        return Ok(());
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Parsing of the DDS
+// ---------------------------------------------------------------------------
+
+/// Variable declared in a DDS, the `Grid` constructors are flattened into their array and their maps.
+#[derive(Debug, Clone, PartialEq)]
+struct DdsVariable {
+    name: String,
+    dap_type: DapType,
+    /// Names (can be empty) and sizes of the dimensions
+    dims: Vec<(String, usize)>,
+}
+
+impl DdsVariable {
+
+    /// Returns the number of elements, `None` if it overflows `usize`.
+    fn len(&self) -> Option<usize> {
+        self.dims.iter().try_fold(1_usize, |len: usize, (_dim_name, dim_size): &(String, usize)| len.checked_mul(*dim_size))
+    }
+}
+
+fn parse_dds(dds: &str) -> Result<Vec<DdsVariable>, DapError> {
+    let mut tokens = TokenStream::new(dds).map_err(DapError::DdsNotValid)?;
+    return parse_dds_tokens(&mut tokens);
+}
+
+fn parse_dds_tokens(tokens: &mut TokenStream) -> Result<Vec<DdsVariable>, DapError> {
+    tokens.expect_keyword("Dataset").map_err(DapError::DdsNotValid)?;
+    tokens.expect_punct('{').map_err(DapError::DdsNotValid)?;
+    let mut vars: Vec<DdsVariable> = vec![];
+    while !tokens.next_is_punct('}') {
+        let type_name: String = tokens.expect_word().map_err(DapError::DdsNotValid)?;
+        if type_name.eq_ignore_ascii_case("Grid") {
+            tokens.expect_punct('{').map_err(DapError::DdsNotValid)?;
+            tokens.expect_keyword("ARRAY").map_err(DapError::DdsNotValid)?;
+            tokens.expect_punct(':').map_err(DapError::DdsNotValid)?;
+            let array_type_name: String = tokens.expect_word().map_err(DapError::DdsNotValid)?;
+            vars.push(parse_dds_var_decl(tokens, &array_type_name)?);
+            tokens.expect_keyword("MAPS").map_err(DapError::DdsNotValid)?;
+            tokens.expect_punct(':').map_err(DapError::DdsNotValid)?;
+            while !tokens.next_is_punct('}') {
+                let map_type_name: String = tokens.expect_word().map_err(DapError::DdsNotValid)?;
+                vars.push(parse_dds_var_decl(tokens, &map_type_name)?);
+            }
+            tokens.expect_punct('}').map_err(DapError::DdsNotValid)?;
+            tokens.expect_word().map_err(DapError::DdsNotValid)?;
+            tokens.expect_punct(';').map_err(DapError::DdsNotValid)?;
+        } else if type_name.eq_ignore_ascii_case("Structure") || type_name.eq_ignore_ascii_case("Sequence") {
+            let var_name: String = skip_constructor(tokens)?;
+            return Err(DapError::TypeNotSupported{var_name: var_name, dap_type: type_name});
+        } else {
+            vars.push(parse_dds_var_decl(tokens, &type_name)?);
+        }
+    }
+    tokens.expect_punct('}').map_err(DapError::DdsNotValid)?;
+    return Ok(vars);
+}
+
+/// Parses `name[dim_name = dim_size]...;`, the type name having already been parsed.
+fn parse_dds_var_decl(tokens: &mut TokenStream, type_name: &str) -> Result<DdsVariable, DapError> {
+    let var_name: String = tokens.expect_word().map_err(DapError::DdsNotValid)?;
+    let dap_type: DapType = DapType::parse(type_name).ok_or_else(|| DapError::TypeNotSupported{var_name: var_name.clone(), dap_type: type_name.to_string()})?;
+    let mut dims: Vec<(String, usize)> = vec![];
+    while tokens.next_is_punct('[') {
+        tokens.expect_punct('[').map_err(DapError::DdsNotValid)?;
+        let dim_name: String = match tokens.peek_at(1) {
+            Some(Token::Punct('=')) => {
+                let dim_name: String = tokens.expect_word().map_err(DapError::DdsNotValid)?;
+                tokens.expect_punct('=').map_err(DapError::DdsNotValid)?;
+                dim_name
+            },
+            _ => String::new(),
+        };
+        let dim_size: String = tokens.expect_word().map_err(DapError::DdsNotValid)?;
+        let dim_size: usize = dim_size.parse().map_err(|_err| DapError::DdsNotValid(format!("invalid size `{}` of the variable `{}`", dim_size, var_name)))?;
+        tokens.expect_punct(']').map_err(DapError::DdsNotValid)?;
+        dims.push((dim_name, dim_size));
+    }
+    tokens.expect_punct(';').map_err(DapError::DdsNotValid)?;
+    let dds_var = DdsVariable{name: var_name, dap_type: dap_type, dims: dims};
+    if dds_var.len().is_none() {
+        return Err(DapError::DdsNotValid(format!("the number of elements of the variable `{}` overflows", dds_var.name)));
+    }
+    return Ok(dds_var);
+}
+
+/// Skips a `Structure` or a `Sequence` constructor and returns its name.
+fn skip_constructor(tokens: &mut TokenStream) -> Result<String, DapError> {
+    tokens.expect_punct('{').map_err(DapError::DdsNotValid)?;
+    let mut depth: usize = 1;
+    while depth > 0 {
+        match tokens.next().map_err(DapError::DdsNotValid)? {
+            Token::Punct('{') => depth += 1,
+            Token::Punct('}') => depth -= 1,
+            _ => {},
+        }
+    }
+    return tokens.expect_word().map_err(DapError::DdsNotValid);
+}
+
+// ---------------------------------------------------------------------------
+// Parsing of the DAS
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq)]
+struct DasAttribute {
+    name: String,
+    dap_type: DapType,
+    values: Vec<String>,
+}
+
+/// Top-level container of attributes, the nested containers are ignored.
+#[derive(Debug, Clone, PartialEq)]
+struct DasContainer {
+    name: String,
+    attrs: Vec<DasAttribute>,
+}
+
+fn parse_das(das: &str) -> Result<Vec<DasContainer>, DapError> {
+    let mut tokens = TokenStream::new(das).map_err(DapError::DasNotValid)?;
+    tokens.expect_keyword("Attributes").map_err(DapError::DasNotValid)?;
+    tokens.expect_punct('{').map_err(DapError::DasNotValid)?;
+    let mut containers: Vec<DasContainer> = vec![];
+    while !tokens.next_is_punct('}') {
+        let name: String = tokens.expect_word().map_err(DapError::DasNotValid)?;
+        let attrs: Vec<DasAttribute> = parse_das_container(&mut tokens)?;
+        containers.push(DasContainer{name: name, attrs: attrs});
+    }
+    tokens.expect_punct('}').map_err(DapError::DasNotValid)?;
+    return Ok(containers);
+}
+
+fn parse_das_container(tokens: &mut TokenStream) -> Result<Vec<DasAttribute>, DapError> {
+    tokens.expect_punct('{').map_err(DapError::DasNotValid)?;
+    let mut attrs: Vec<DasAttribute> = vec![];
+    while !tokens.next_is_punct('}') {
+        let word: String = tokens.expect_word().map_err(DapError::DasNotValid)?;
+        if tokens.next_is_punct('{') {
+            // nested container
+            parse_das_container(tokens)?;
+            continue;
+        }
+        let attr_name: String = tokens.expect_word().map_err(DapError::DasNotValid)?;
+        let dap_type: DapType = DapType::parse(&word).ok_or_else(|| DapError::DasNotValid(format!("unknown type `{}` of the attribute `{}`", word, attr_name)))?;
+        let mut values: Vec<String> = vec![];
+        loop {
+            match tokens.next().map_err(DapError::DasNotValid)? {
+                Token::Word(value) | Token::Quoted(value) => values.push(value),
+                token => return Err(DapError::DasNotValid(format!("value of the attribute `{}` expected, found {:?}", attr_name, token))),
+            }
+            if !tokens.next_is_punct(',') {
+                break;
+            }
+            tokens.expect_punct(',').map_err(DapError::DasNotValid)?;
+        }
+        tokens.expect_punct(';').map_err(DapError::DasNotValid)?;
+        attrs.push(DasAttribute{name: attr_name, dap_type: dap_type, values: values});
+    }
+    tokens.expect_punct('}').map_err(DapError::DasNotValid)?;
+    return Ok(attrs);
+}
+
+// ---------------------------------------------------------------------------
+// Definition of the data set
+// ---------------------------------------------------------------------------
+
+fn build_data_set(dds_vars: &[DdsVariable], das_containers: &[DasContainer]) -> Result<DataSet, DapError> {
+    let unlimited_dim_name: Option<String> = das_containers.iter()
+        .filter(|container: &&DasContainer| container.name == DODS_EXTRA_CONTAINER_NAME)
+        .flat_map(|container: &DasContainer| container.attrs.iter())
+        .find(|attr: &&DasAttribute| attr.name == UNLIMITED_DIM_ATTR_NAME)
+        .and_then(|attr: &DasAttribute| attr.values.first().cloned());
+
+    let mut data_set = DataSet::new();
+    for dds_var in dds_vars.iter() {
+        // the maps of the grids can be declared several times
+        if data_set.has_var(&dds_var.name) {
+            continue;
+        }
+        let data_type: DataType = dds_var.dap_type.data_type().ok_or_else(|| DapError::TypeNotSupported{
+            var_name: dds_var.name.clone(),
+            dap_type: format!("{:?}", dds_var.dap_type),
+        })?;
+        let mut dim_names: Vec<String> = vec![];
+        for (i, (dim_name, dim_size)) in dds_var.dims.iter().enumerate() {
+            let dim_name: String = if dim_name.is_empty() { format!("{}_dim_{}", dds_var.name, i) } else { dim_name.clone() };
+            match data_set.dim_size(&dim_name) {
+                Some(size) if size != *dim_size => {
+                    return Err(DapError::DdsNotValid(format!("the dimension `{}` has several sizes ({} and {})", dim_name, size, dim_size)));
+                },
+                Some(_) => {},
+                None if Some(&dim_name) == unlimited_dim_name.as_ref() => { data_set.set_unlimited_dim(&dim_name, *dim_size)?; },
+                None => { data_set.add_fixed_dim(&dim_name, *dim_size)?; },
+            }
+            dim_names.push(dim_name);
+        }
+        data_set.add_var(&dds_var.name, &dim_names, data_type)?;
+    }
+
+    for container in das_containers.iter() {
+        let var_name: Option<&str> = if container.name == GLOBAL_ATTRS_CONTAINER_NAME {
+            None
+        } else if data_set.has_var(&container.name) {
+            Some(&container.name)
+        } else {
+            continue;
+        };
+        for attr in container.attrs.iter() {
+            add_das_attr(&mut data_set, var_name, attr)?;
+        }
+    }
+    return Ok(data_set);
+}
+
+/// Adds a variable attribute, or a global attribute if `var_name` is `None`.
+fn add_das_attr(data_set: &mut DataSet, var_name: Option<&str>, attr: &DasAttribute) -> Result<(), DapError> {
+    macro_rules! parse_values {
+        ($prim_type:ty) => {{
+            let values: Result<Vec<$prim_type>, DapError> = attr.values.iter().map(|value: &String| {
+                value.parse::<$prim_type>().map_err(|_err| DapError::DasNotValid(format!("invalid value `{}` of the attribute `{}`", value, attr.name)))
+            }).collect();
+            values?
+        }};
+    }
+    macro_rules! add_attr {
+        ($add_var_attr:ident, $add_global_attr:ident, $values:expr) => {
+            match var_name {
+                Some(var_name) => data_set.$add_var_attr(var_name, &attr.name, $values),
+                None => data_set.$add_global_attr(&attr.name, $values),
+            }
+        };
+    }
+    match attr.dap_type {
+        DapType::Byte => add_attr!(add_var_attr_u8, add_global_attr_u8, parse_values!(u8)),
+        DapType::Int16 => add_attr!(add_var_attr_i16, add_global_attr_i16, parse_values!(i16)),
+        DapType::UInt16 => add_attr!(add_var_attr_i32, add_global_attr_i32, parse_values!(u16).into_iter().map(|value: u16| value as i32).collect()),
+        DapType::Int32 => add_attr!(add_var_attr_i32, add_global_attr_i32, parse_values!(i32)),
+        DapType::UInt32 => add_attr!(add_var_attr_f64, add_global_attr_f64, parse_values!(u32).into_iter().map(|value: u32| value as f64).collect()),
+        DapType::Float32 => add_attr!(add_var_attr_f32, add_global_attr_f32, parse_values!(f32)),
+        DapType::Float64 => add_attr!(add_var_attr_f64, add_global_attr_f64, parse_values!(f64)),
+        DapType::String | DapType::Url => add_attr!(add_var_attr_string, add_global_attr_string, attr.values.join("\n")),
+    }?;
+    return Ok(());
+}
+
+// ---------------------------------------------------------------------------
+// Decoding of the `.dods` responses
+// ---------------------------------------------------------------------------
+
+/// Decodes a `.dods` response : the DDS of the selected variables followed by their XDR encoded data.
+///
+/// The decoding of a variable fails if its data exceed `max_alloc_bytes` or the remaining bytes of the response.
+fn parse_dods(dods: &[u8], max_alloc_bytes: Option<usize>) -> Result<HashMap<String, DataVector>, DapError> {
+    let separator_pos: usize = dods.windows(DODS_DATA_SEPARATOR.len())
+        .position(|window: &[u8]| window == DODS_DATA_SEPARATOR)
+        .ok_or_else(|| DapError::DataNotValid(String::from("the DDS and the data are not separated")))?;
+    let dds: &str = std::str::from_utf8(&dods[0..separator_pos]).map_err(|_err| DapError::DataNotValid(String::from("the DDS is not UTF-8 encoded")))?;
+    let dds_vars: Vec<DdsVariable> = parse_dds(dds)?;
+
+    let mut input: &[u8] = &dods[(separator_pos + DODS_DATA_SEPARATOR.len())..];
+    let mut data: HashMap<String, DataVector> = HashMap::new();
+    for dds_var in dds_vars.into_iter() {
+        let var_data: DataVector = read_xdr_var(&mut input, &dds_var, max_alloc_bytes)?;
+        data.insert(dds_var.name, var_data);
+    }
+    return Ok(data);
+}
+
+fn read_xdr_var(input: &mut &[u8], dds_var: &DdsVariable, max_alloc_bytes: Option<usize>) -> Result<DataVector, DapError> {
+    let truncated = |_err: std::io::Error| DapError::DataNotValid(format!("the data of the variable `{}` are truncated", dds_var.name));
+    let is_array: bool = !dds_var.dims.is_empty();
+    let len: usize = dds_var.len().ok_or_else(|| DapError::DataNotValid(format!("the number of elements of the variable `{}` overflows", dds_var.name)))?;
+    let data_type: DataType = dds_var.dap_type.data_type().ok_or_else(|| DapError::TypeNotSupported{
+        var_name: dds_var.name.clone(),
+        dap_type: format!("{:?}", dds_var.dap_type),
+    })?;
+    if let Some(max_alloc_bytes) = max_alloc_bytes {
+        let num_bytes: usize = len.saturating_mul(data_type.size_of());
+        if num_bytes > max_alloc_bytes {
+            return Err(DapError::MaximumAllocationExceeded{var_name: dds_var.name.clone(), max: max_alloc_bytes, get: num_bytes});
+        }
+    }
+    // the packed bytes arrays use 1 byte per element, the other values at least 4 bytes
+    let xdr_elem_size: usize = if is_array && dds_var.dap_type == DapType::Byte { 1 } else { std::cmp::max(data_type.size_of(), 4) };
+    if len.saturating_mul(xdr_elem_size) > input.len() {
+        return Err(truncated(std::io::Error::from(std::io::ErrorKind::UnexpectedEof)));
+    }
+    if is_array {
+        // the length of the arrays is written twice
+        for _ in 0..2 {
+            let encoded_len: usize = input.read_u32::<BigEndian>().map_err(truncated)? as usize;
+            if encoded_len != len {
+                return Err(DapError::DataNotValid(format!("the variable `{}` has {} elements, {} expected", dds_var.name, encoded_len, len)));
+            }
+        }
+    }
+
+    macro_rules! read_values {
+        ($read_func:ident, $prim_type:ty) => {{
+            let values: Result<Vec<$prim_type>, std::io::Error> = (0..len).map(|_| input.$read_func::<BigEndian>()).collect();
+            values.map_err(truncated)?
+        }};
+    }
+    let data_vec: DataVector = match dds_var.dap_type {
+        DapType::Byte if is_array => {
+            // the bytes arrays are packed, then padded
+            let mut values: Vec<u8> = vec![0; len + compute_padding_size(len)];
+            input.read_exact(&mut values).map_err(truncated)?;
+            values.truncate(len);
+            DataVector::U8(values)
+        },
+        DapType::Byte => DataVector::U8(read_values!(read_u32, u32).into_iter().map(|value: u32| value as u8).collect()),
+        DapType::Int16 => DataVector::I16(read_values!(read_i32, i32).into_iter().map(|value: i32| value as i16).collect()),
+        DapType::UInt16 => DataVector::I32(read_values!(read_u32, u32).into_iter().map(|value: u32| value as u16 as i32).collect()),
+        DapType::Int32 => DataVector::I32(read_values!(read_i32, i32)),
+        DapType::UInt32 => DataVector::F64(read_values!(read_u32, u32).into_iter().map(|value: u32| value as f64).collect()),
+        DapType::Float32 => DataVector::F32(read_values!(read_f32, f32)),
+        DapType::Float64 => DataVector::F64(read_values!(read_f64, f64)),
+        DapType::String | DapType::Url => {
+            return Err(DapError::TypeNotSupported{var_name: dds_var.name.clone(), dap_type: format!("{:?}", dds_var.dap_type)});
+        },
+    };
+    return Ok(data_vec);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{build_data_set, encode_constraint, parse_das, parse_dds, parse_dods, DapType, DdsVariable};
+    use crate::{DataType, DataVector, DimensionType, error::DapError};
+
+    const DDS: &str = r#"Dataset {
+    Float32 lat[lat = 2];
+    Float32 lon[lon = 3];
+    Float64 time[time = 1];
+    Grid {
+     ARRAY:
+        Int16 sst[time = 1][lat = 2][lon = 3];
+     MAPS:
+        Float64 time[time = 1];
+        Float32 lat[lat = 2];
+        Float32 lon[lon = 3];
+    } sst;
+    Byte mask[lat = 2][lon = 3];
+    Int32 scalar;
+} sst.mnmean.nc;"#;
+
+    const DAS: &str = r#"Attributes {
+    lat {
+        String units "degrees_north";
+        Float32 actual_range 88.0, -88.0;
+    }
+    sst {
+        String long_name "Monthly \"Mean\" of SST";
+        Int16 missing_value 32767;
+        Float32 scale_factor 0.01;
+        UInt16 counts 1, 65535;
+        Nested {
+            String ignored "value";
+        }
+    }
+    NC_GLOBAL {
+        String title "NOAA OI SST";
+        Byte flags 255;
+    }
+    DODS_EXTRA {
+        String Unlimited_Dimension "time";
+    }
+}"#;
+
+    #[test]
+    fn test_parse_dds() {
+        let vars: Vec<DdsVariable> = parse_dds(DDS).unwrap();
+        assert_eq!(9,                                   vars.len());
+        assert_eq!(
+            DdsVariable{
+                name: String::from("sst"),
+                dap_type: DapType::Int16,
+                dims: vec![(String::from("time"), 1), (String::from("lat"), 2), (String::from("lon"), 3)],
+            },
+            vars[3]
+        );
+        assert_eq!(String::from("time"),                vars[4].name);
+        assert_eq!(DapType::Int32,                      vars[8].dap_type);
+        assert_eq!(0,                                   vars[8].dims.len());
+
+        // unnamed dimensions
+        let vars: Vec<DdsVariable> = parse_dds("Dataset { Float64 x[4]; } test;").unwrap();
+        assert_eq!(vec![(String::new(), 4)],            vars[0].dims);
+    }
+
+    #[test]
+    fn test_parse_dds_errors() {
+        assert_eq!(
+            DapError::TypeNotSupported{var_name: String::from("obs"), dap_type: String::from("Sequence")},
+            parse_dds("Dataset { Sequence { Float32 t; Float32 s; } obs; } test;").unwrap_err()
+        );
+        assert_eq!(
+            DapError::TypeNotSupported{var_name: String::from("x"), dap_type: String::from("Int64")},
+            parse_dds("Dataset { Int64 x[4]; } test;").unwrap_err()
+        );
+        assert!(matches!(parse_dds("Dataset { Float64 x[4] } test;"),   Err(DapError::DdsNotValid(_))));
+        // number of elements overflowing `usize`
+        assert!(matches!(
+            parse_dds(&format!("Dataset {{ Float64 x[{}][{}]; }} test;", usize::MAX, 2)),
+            Err(DapError::DdsNotValid(_))
+        ));
+    }
+
+    #[test]
+    fn test_build_data_set() {
+        let data_set = build_data_set(&parse_dds(DDS).unwrap(), &parse_das(DAS).unwrap()).unwrap();
+
+        assert_eq!(vec!["lat", "lon", "time"],          data_set.dim_names());
+        assert_eq!(Some(DimensionType::UnlimitedSize),  data_set.dim_type("time"));
+        assert_eq!(Some(DimensionType::FixedSize),      data_set.dim_type("lat"));
+        assert_eq!(vec!["lat", "lon", "time", "sst", "mask", "scalar"],     data_set.get_var_names());
+        assert_eq!(Some(DataType::I16),                 data_set.var_data_type("sst"));
+        assert_eq!(Some(DataType::U8),                  data_set.var_data_type("mask"));
+        assert_eq!(Some(1),                             data_set.var_len("scalar"));
+
+        assert_eq!(Some(String::from("degrees_north")),             data_set.get_var_attr_as_string("lat", "units"));
+        assert_eq!(Some(&[88.0_f32, -88.0][..]),                    data_set.get_var_attr_f32("lat", "actual_range"));
+        assert_eq!(Some(String::from("Monthly \"Mean\" of SST")),   data_set.get_var_attr_as_string("sst", "long_name"));
+        assert_eq!(Some(&[32767_i16][..]),                          data_set.get_var_attr_i16("sst", "missing_value"));
+        assert_eq!(Some(&[1_i32, 65535][..]),                       data_set.get_var_attr_i32("sst", "counts"));
+        assert_eq!(Some(4),                                         data_set.num_var_attrs("sst"));
+        assert_eq!(Some(String::from("NOAA OI SST")),               data_set.get_global_attr_as_string("title"));
+        assert_eq!(Some(&[255_u8][..]),                             data_set.get_global_attr_u8("flags"));
+    }
+
+    #[test]
+    fn test_build_data_set_errors() {
+        let dds_vars: Vec<DdsVariable> = parse_dds("Dataset { Float64 x[n = 4]; Float64 y[n = 5]; } test;").unwrap();
+        assert!(matches!(build_data_set(&dds_vars, &[]),    Err(DapError::DdsNotValid(_))));
+
+        let dds_vars: Vec<DdsVariable> = parse_dds("Dataset { String name; } test;").unwrap();
+        assert_eq!(
+            DapError::TypeNotSupported{var_name: String::from("name"), dap_type: String::from("String")},
+            build_data_set(&dds_vars, &[]).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_dods() {
+        let mut dods: Vec<u8> = b"Dataset {\n    Int16 sst[lat = 2];\n    Byte mask[lat = 2];\n    Float64 t;\n} test;\nData:\n".to_vec();
+        // `sst`
+        dods.extend_from_slice(&[0, 0, 0, 2, 0, 0, 0, 2]);
+        dods.extend_from_slice(&[0xff, 0xff, 0xff, 0xfe, 0, 0, 0, 7]);
+        // `mask`
+        dods.extend_from_slice(&[0, 0, 0, 2, 0, 0, 0, 2]);
+        dods.extend_from_slice(&[1, 255, 0, 0]);
+        // `t`
+        dods.extend_from_slice(&1.5_f64.to_be_bytes());
+
+        let data = parse_dods(&dods, None).unwrap();
+        assert_eq!(3,                                       data.len());
+        assert_eq!(Some(&DataVector::I16(vec![-2, 7])),     data.get("sst"));
+        assert_eq!(Some(&DataVector::U8(vec![1, 255])),     data.get("mask"));
+        assert_eq!(Some(&DataVector::F64(vec![1.5])),       data.get("t"));
+
+        // truncated data
+        assert!(matches!(parse_dods(&dods[0..(dods.len() - 1)], None),  Err(DapError::DataNotValid(_))));
+
+        // maximum allocation
+        assert!(parse_dods(&dods, Some(8)).is_ok());
+        assert_eq!(
+            DapError::MaximumAllocationExceeded{var_name: String::from("t"), max: 7, get: 8},
+            parse_dods(&dods, Some(7)).unwrap_err()
+        );
+
+        // declared size exceeding the response, rejected before allocating
+        let mut dods: Vec<u8> = b"Dataset {\n    Float64 x[n = 1000000000];\n} test;\nData:\n".to_vec();
+        dods.extend_from_slice(&1_000_000_000_u32.to_be_bytes());
+        dods.extend_from_slice(&1_000_000_000_u32.to_be_bytes());
+        assert!(matches!(parse_dods(&dods, None),   Err(DapError::DataNotValid(_))));
+    }
+
+    #[test]
+    fn test_encode_constraint() {
+        assert_eq!("sst%5B0:1:0%5D%5B10:2:20%5D,lat",   encode_constraint("sst[0:1:0][10:2:20],lat"));
+        assert_eq!("x&x%3E1",                           encode_constraint("x&x>1"));
+    }
+}
@@ -0,0 +1,264 @@
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use crate::{DataType, NameError};
+#[cfg(feature = "read")]
+use crate::{validate_name, Attribute, DataSet, Variable};
+
+/// Name of the attribute defining the fill value of a variable.
+#[cfg(feature = "read")]
+const FILL_VALUE_ATTR_NAME: &str = "_FillValue";
+
+/// Inconsistency between the header of a NetCDF-3 file and its data part (see [`validate_nc3`](fn.validate_nc3.html)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayoutViolation {
+    /// The data of the variable begin inside the header.
+    DataOverlapsHeader{var_name: String, begin_offset: u64, header_size: u64},
+    /// The data of the variable begin before the data of the previous variable
+    /// (the *fixed-size* variables must be stored before the record variables, both in the order of the header).
+    OffsetsNotIncreasing{var_name: String, prev_var_name: String},
+    /// The data of the variable begin before the end of the data of the previous variable.
+    DataOverlap{var_name: String, prev_var_name: String},
+    /// The data of the record variable exceed the size of a record.
+    RecordOverflow{var_name: String},
+    /// The chunk size stored in the header differs from the one computed from the dimensions of the variable.
+    ChunkSizeMismatch{var_name: String, header_chunk_size: usize, chunk_size: usize},
+    /// The file ends before the end of the data.
+    FileTooShort{required_size: u64, file_size: u64},
+    /// Unexpected bytes are stored after the end of the data.
+    TrailingBytes{data_end: u64, file_size: u64},
+    /// A non-zero byte is stored in the space reserved after the header.
+    NonZeroPadding{offset: u64},
+}
+
+/// Non-fatal finding collected while reading a NetCDF-3 file (see [`FileReader::diagnostics`](struct.FileReader.html#method.diagnostics)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Diagnostic {
+    /// Inconsistency between the header and the data part of the file.
+    ///
+    /// Only the bytes loaded while parsing the header are checked for the
+    /// [`NonZeroPadding`](enum.LayoutViolation.html#variant.NonZeroPadding), see [`validate_nc3`](fn.validate_nc3.html)
+    /// for a complete check.
+    Layout(LayoutViolation),
+    /// The data type of the attribute differs from the one of its variable.
+    AttributeTypeMismatch{var_name: String, attr_name: String, attr_data_type: DataType, var_data_type: DataType},
+    /// The attribute contains an unexpected number of values.
+    AttributeLengthNotValid{var_name: String, attr_name: String, req: usize, get: usize},
+    /// The text attribute contains null bytes, the variable name is `None` for the global attributes.
+    AttributeNullBytes{var_name: Option<String>, attr_name: String},
+    /// The name is not valid UTF-8, it has been decoded as `name` (see [`NameDecoding`](enum.NameDecoding.html)).
+    NameNotValidUtf8{bytes: Vec<u8>, name: String},
+    /// The name does not follow the NetCDF-3 naming convention (see [`validate_name`](fn.validate_name.html)), `reason` being
+    /// the rule broken, it has been accepted by the lenient rules (see
+    /// [`ReadOptions::name_validation`](struct.ReadOptions.html#structfield.name_validation)).
+    NameNotValid{name: String, reason: NameError},
+    /// The name exceeds [`NC_MAX_NAME_SIZE`](constant.NC_MAX_NAME_SIZE.html) bytes, it has been truncated to `new_name` or kept
+    /// as is when `new_name` is `None` (see [`LongNamePolicy`](enum.LongNamePolicy.html)).
+    NameTooLong{name: String, new_name: Option<String>},
+    /// The name of the dimension `dim_id` is already used by a previous dimension, the dimension has been renamed `new_name`
+    /// or kept with its name when `new_name` is `None` (see [`DuplicateDimPolicy`](enum.DuplicateDimPolicy.html)).
+    DuplicateDimension{dim_id: usize, name: String, new_name: Option<String>},
+    /// The name of the attribute is already used by a previous attribute, the attribute has been renamed `new_name` or has
+    /// replaced the previous one when `new_name` is `None` (see [`DuplicateAttrPolicy`](enum.DuplicateAttrPolicy.html)).
+    ///
+    /// The variable name is `None` for the global attributes.
+    DuplicateAttribute{var_name: Option<String>, attr_name: String, new_name: Option<String>},
+    /// The record data are truncated, only the `num_records` complete records are read (see
+    /// [`ReadOptions::allow_truncated_records`](struct.ReadOptions.html#structfield.allow_truncated_records)).
+    ///
+    /// The number of records stored in the header is `None` for the *streaming* files.
+    RecordsTruncated{header_num_records: Option<usize>, num_records: usize},
+    /// The padding bytes following a name or an attribute value at `offset` are not zero bytes (see
+    /// [`ReadOptions::padding_check`](struct.ReadOptions.html#structfield.padding_check)).
+    PaddingNotZero{offset: u64, bytes: Vec<u8>},
+}
+
+/// Collection of the [`Diagnostic`](enum.Diagnostic.html)s found while reading a NetCDF-3 file.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{Diagnostic, FileReader, LayoutViolation};
+/// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+///
+/// let file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+/// assert_eq!(true,        file_reader.diagnostics().is_empty());
+///
+/// // Append unknown bytes at the end of the file
+/// let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+/// bytes.extend_from_slice(&[0_u8; 4]);
+/// let file_reader = FileReader::from_reader(Cursor::new(bytes)).unwrap();
+/// assert_eq!(
+///     vec![&Diagnostic::Layout(LayoutViolation::TrailingBytes{data_end: 2332, file_size: 2336})],
+///     file_reader.diagnostics().iter().collect::<Vec<&Diagnostic>>()
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Diagnostics(Vec<Diagnostic>);
+
+impl Diagnostics {
+
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    pub fn len(&self) -> usize {
+        return self.0.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.0.is_empty();
+    }
+
+    pub fn iter(&self) -> core::slice::Iter<'_, Diagnostic> {
+        return self.0.iter();
+    }
+
+    pub fn as_slice(&self) -> &[Diagnostic] {
+        return &self.0;
+    }
+
+    #[cfg(feature = "read")]
+    pub(crate) fn push(&mut self, diagnostic: Diagnostic) {
+        self.0.push(diagnostic);
+    }
+}
+
+impl<'a> IntoIterator for &'a Diagnostics {
+    type Item = &'a Diagnostic;
+    type IntoIter = core::slice::Iter<'a, Diagnostic>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.0.iter();
+    }
+}
+
+impl IntoIterator for Diagnostics {
+    type Item = Diagnostic;
+    type IntoIter = alloc::vec::IntoIter<Diagnostic>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.0.into_iter();
+    }
+}
+
+/// Checks the attributes of the data set : type and length of the fill values, null bytes in the text attributes.
+#[cfg(feature = "read")]
+pub(crate) fn check_attrs(data_set: &DataSet) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = vec![];
+    for attr in data_set.global_attrs().iter() {
+        if has_null_bytes(attr) {
+            diagnostics.push(Diagnostic::AttributeNullBytes{var_name: None, attr_name: attr.name().to_string()});
+        }
+    }
+    for var in data_set.vars().iter() {
+        for attr in var.attrs().iter() {
+            if has_null_bytes(attr) {
+                diagnostics.push(Diagnostic::AttributeNullBytes{var_name: Some(var.name().to_string()), attr_name: attr.name().to_string()});
+            }
+        }
+        if let Some(fill_value) = var.get_attr(FILL_VALUE_ATTR_NAME) {
+            diagnostics.extend(check_fill_value(var, fill_value));
+        }
+    }
+    return diagnostics;
+}
+
+/// Checks the names of the dimensions, the variables and the attributes with the strict rules.
+#[cfg(feature = "read")]
+pub(crate) fn check_names(data_set: &DataSet) -> Vec<Diagnostic> {
+    let mut names: Vec<String> = data_set.dim_names();
+    names.extend(data_set.get_global_attr_names());
+    for var in data_set.vars().iter() {
+        names.push(var.name().to_string());
+        names.extend(var.attrs().iter().map(|attr: &Attribute| attr.name().to_string()));
+    }
+    return names.into_iter()
+        .filter_map(|name: String| match validate_name(&name) {
+            // the long names are reported by `Diagnostic::NameTooLong`
+            Ok(()) | Err(NameError::TooLong{..}) => None,
+            Err(reason) => Some(Diagnostic::NameNotValid{name: name, reason: reason}),
+        })
+        .collect();
+}
+
+#[cfg(feature = "read")]
+fn has_null_bytes(attr: &Attribute) -> bool {
+    return attr.get_u8().map(|bytes: &[u8]| bytes.contains(&0_u8)).unwrap_or(false);
+}
+
+#[cfg(feature = "read")]
+fn check_fill_value(var: &Variable, fill_value: &Attribute) -> Vec<Diagnostic> {
+    let mut diagnostics: Vec<Diagnostic> = vec![];
+    if fill_value.data_type() != var.data_type() {
+        diagnostics.push(Diagnostic::AttributeTypeMismatch{
+            var_name: var.name().to_string(),
+            attr_name: fill_value.name().to_string(),
+            attr_data_type: fill_value.data_type(),
+            var_data_type: var.data_type(),
+        });
+    }
+    if fill_value.len() != 1 {
+        diagnostics.push(Diagnostic::AttributeLengthNotValid{
+            var_name: var.name().to_string(),
+            attr_name: fill_value.name().to_string(),
+            req: 1,
+            get: fill_value.len(),
+        });
+    }
+    return diagnostics;
+}
+
+#[cfg(all(test, feature = "read"))]
+mod tests {
+
+    use super::{check_attrs, check_names, Diagnostic};
+    use crate::{Attribute, DataSet, DataType, DataVector, NameError, NameValidation};
+
+    #[test]
+    fn test_check_attrs() {
+        let mut data_set = DataSet::new();
+        data_set.add_global_attr_u8("title", b"title\0\0\0".to_vec()).unwrap();
+        data_set.add_var_i16::<&str>("var_1", &[]).unwrap();
+        data_set.add_var_attr_i16("var_1", "_FillValue", vec![-1]).unwrap();
+        data_set.add_var_attr_string("var_1", "units", "K").unwrap();
+        data_set.add_var_f32::<&str>("var_2", &[]).unwrap();
+        // the data type of the attributes read from the files is not checked
+        data_set.add_var_attr_from("var_2", Attribute::from_data(String::from("_FillValue"), DataVector::F64(vec![-1.0, -2.0]))).unwrap();
+
+        assert_eq!(
+            vec![
+                Diagnostic::AttributeNullBytes{var_name: None, attr_name: String::from("title")},
+                Diagnostic::AttributeTypeMismatch{
+                    var_name: String::from("var_2"),
+                    attr_name: String::from("_FillValue"),
+                    attr_data_type: DataType::F64,
+                    var_data_type: DataType::F32,
+                },
+                Diagnostic::AttributeLengthNotValid{var_name: String::from("var_2"), attr_name: String::from("_FillValue"), req: 1, get: 2},
+            ],
+            check_attrs(&data_set)
+        );
+    }
+
+    #[test]
+    fn test_check_names() {
+        let mut data_set = DataSet::new();
+        data_set.set_name_validation(NameValidation::Lenient);
+        data_set.add_fixed_dim("dim ", 1).unwrap();
+        data_set.add_global_attr_string(".title", "title").unwrap();
+        data_set.add_var_i8("var_1", &["dim "]).unwrap();
+        data_set.add_var_attr_string("var_1", "units", "K").unwrap();
+        data_set.add_var_attr_string("var_1", "-comment", "comment").unwrap();
+
+        assert_eq!(
+            vec![
+                Diagnostic::NameNotValid{name: String::from("dim "), reason: NameError::TrailingSpace{index: 3}},
+                Diagnostic::NameNotValid{name: String::from(".title"), reason: NameError::FirstCharNotAllowed{chr: '.'}},
+                Diagnostic::NameNotValid{name: String::from("-comment"), reason: NameError::FirstCharNotAllowed{chr: '-'}},
+            ],
+            check_names(&data_set)
+        );
+    }
+}
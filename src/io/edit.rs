@@ -0,0 +1,299 @@
+use std::io::{Read, Seek, SeekFrom, Write};
+#[cfg(feature = "fs")]
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crate::{DataSet, DataType, Dimension, FileReader, Variable};
+use crate::error::{EditError, InvalidDataSet};
+use crate::io::compute_padding_size;
+use crate::io::{compute_header_required_size, header::header_bytes};
+
+/// Size of the buffer used to move the data when the header has to be enlarged.
+const SHIFT_BUFFER_SIZE: usize = 1024 * 1024;
+
+/// How the header has been written by [`edit_nc3`](fn.edit_nc3.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditMode {
+    /// The edited header fits in the space reserved for the header, the data have not been moved.
+    InPlace,
+    /// The header has been enlarged, and the data have been moved toward the end of the file.
+    Rewritten,
+}
+
+/// Edits the header of an existing NetCDF-3 file : renaming of the variables and dimensions, addition, modification
+/// and deletion of attributes, etc.
+///
+/// The function `edit` is applied to the data set of the file. It must not change the layout of the data, namely the data type
+/// and the shape of each variable, otherwise the error
+/// [`EditError::DataLayoutChanged`](enum.EditError.html#variant.DataLayoutChanged) is returned and the file is not modified.
+///
+/// The header is rewritten in place when the edited header fits in the space reserved for it (see the option
+/// [`CopyOptions::header_min_size`](struct.CopyOptions.html#structfield.header_min_size)).
+/// Otherwise the data are moved toward the end of `file` to enlarge the header, which is not safe against an interruption :
+/// the files should be edited with [`edit_nc3_file`](fn.edit_nc3_file.html), which rewrites them into a temporary file.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{FileReader, edit_nc3, EditMode};
+/// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+///
+/// let mut file = Cursor::new(NC3_CLASSIC_FILE_BYTES.to_vec());
+/// let edit_mode: EditMode = edit_nc3(&mut file, |data_set| {
+///     data_set.rename_var("temperature_f32", "temp")?;
+///     data_set.rename_dim("latitude", "lat")?;
+///     data_set.add_var_attr_string("temp", "comment", "Air temperature")?;
+///     data_set.remove_global_attr("title")?;
+///     Ok(())
+/// }).unwrap();
+/// assert_eq!(EditMode::InPlace,                   edit_mode);
+///
+/// let mut file_reader = FileReader::from_reader(file).unwrap();
+/// assert_eq!(true,                                file_reader.data_set().has_var("temp"));
+/// assert_eq!(vec!["lat", "longitude", "time"],    file_reader.data_set().dim_names());
+/// assert_eq!(Some(String::from("Air temperature")),   file_reader.data_set().get_var_attr_as_string("temp", "comment"));
+/// assert_eq!(false,                               file_reader.data_set().has_global_attr("title"));
+/// assert_eq!(30,                                  file_reader.read_var_f32("temp").unwrap().len());
+/// ```
+pub fn edit_nc3<F, E>(file: &mut F, edit: E) -> Result<EditMode, EditError>
+where
+    F: Read + Write + Seek,
+    E: FnOnce(&mut DataSet) -> Result<(), InvalidDataSet>,
+{
+    let header_edit: HeaderEdit = HeaderEdit::new(file, edit)?;
+    if header_edit.fits_in_place() {
+        write_header(file, &header_edit.header_bytes, header_edit.header_size)?;
+        return Ok(EditMode::InPlace);
+    }
+
+    // Move the data to enlarge the header
+    let shift: u64 = header_edit.new_header_size - header_edit.header_size;
+    shift_data(file, header_edit.header_size, header_edit.file_size, shift)?;
+    write_header(file, &header_edit.header_bytes, header_edit.new_header_size)?;
+    return Ok(EditMode::Rewritten);
+}
+
+/// Edits the header of an existing NetCDF-3 file (see [`edit_nc3`](fn.edit_nc3.html)).
+///
+/// The header is rewritten in place when it fits in the space reserved for it.
+/// Otherwise the file is rewritten into a temporary file of the same directory (named after the file, with the suffix `.edit.tmp`),
+/// which replaces the original file once complete. The original file is left unchanged when the edition fails.
+#[cfg(feature = "fs")]
+pub fn edit_nc3_file<P, E>(file_path: P, edit: E) -> Result<EditMode, EditError>
+where
+    P: AsRef<Path>,
+    E: FnOnce(&mut DataSet) -> Result<(), InvalidDataSet>,
+{
+    let file_path: &Path = file_path.as_ref();
+    let mut file: std::fs::File = std::fs::OpenOptions::new().read(true).write(true).open(file_path)?;
+    let header_edit: HeaderEdit = HeaderEdit::new(&mut file, edit)?;
+    if header_edit.fits_in_place() {
+        write_header(&mut file, &header_edit.header_bytes, header_edit.header_size)?;
+        file.sync_all()?;
+        return Ok(EditMode::InPlace);
+    }
+
+    let tmp_file_path: PathBuf = {
+        let mut file_name = file_path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(".edit.tmp");
+        file_path.with_file_name(file_name)
+    };
+    let result: Result<(), EditError> = (|| -> Result<(), EditError> {
+        let mut tmp_file = std::io::BufWriter::new(std::fs::File::create(&tmp_file_path)?);
+        write_header(&mut tmp_file, &header_edit.header_bytes, header_edit.new_header_size)?;
+        file.seek(SeekFrom::Start(header_edit.header_size))?;
+        let data_size: u64 = header_edit.file_size - header_edit.header_size;
+        let num_copied_bytes: u64 = std::io::copy(&mut (&mut file).take(data_size), &mut tmp_file)?;
+        if num_copied_bytes != data_size {
+            return Err(EditError::Unexpected);
+        }
+        let tmp_file: std::fs::File = tmp_file.into_inner().map_err(|err: std::io::IntoInnerError<_>| err.into_error())?;
+        tmp_file.sync_all()?;
+        // the input file is closed before being replaced
+        drop(file);
+        std::fs::rename(&tmp_file_path, file_path)?;
+        return Ok(());
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp_file_path);
+    }
+    result?;
+    return Ok(EditMode::Rewritten);
+}
+
+/// Edited header of a file, and the position of its data.
+struct HeaderEdit {
+    /// Size of the edited file.
+    file_size: u64,
+    /// Size of the header (reserved bytes included) of the edited file.
+    header_size: u64,
+    /// Size of the new header, equal to `header_size` when the new header fits in the space reserved for the header.
+    new_header_size: u64,
+    /// Bytes of the new header, without the reserved bytes.
+    header_bytes: Vec<u8>,
+}
+
+impl HeaderEdit {
+    /// Reads the header of the file, applies the `edit` function and encodes the new header.
+    fn new<F, E>(file: &mut F, edit: E) -> Result<HeaderEdit, EditError>
+    where
+        F: Read + Seek,
+        E: FnOnce(&mut DataSet) -> Result<(), InvalidDataSet>,
+    {
+        let file_size: u64 = file.seek(SeekFrom::End(0))?;
+        let (mut data_set, version, begin_offsets, header_size) = {
+            let file_reader = FileReader::from_reader(&mut *file)?;
+            let begin_offsets: Vec<u64> = file_reader.data_set().get_var_names().iter().map(|var_name: &String| {
+                file_reader.var_begin_offset(var_name)
+            }).collect::<Option<Vec<u64>>>().ok_or(EditError::Unexpected)?;
+            let header_size: u64 = file_reader.header_size() as u64;
+            let (data_set, version) = file_reader.close();
+            (data_set, version, begin_offsets, header_size)
+        };
+
+        let data_layout: Vec<(DataType, Vec<usize>)> = get_data_layout(&data_set);
+        edit(&mut data_set)?;
+        if get_data_layout(&data_set) != data_layout {
+            return Err(EditError::DataLayoutChanged);
+        }
+
+        let header_required_size: u64 = compute_header_required_size(&data_set, version) as u64;
+        let new_header_size: u64 = if header_required_size <= header_size {
+            header_size
+        } else {
+            header_required_size + compute_padding_size(header_required_size as usize) as u64
+        };
+        let shift: u64 = new_header_size - header_size;
+        let begin_offsets: Vec<u64> = begin_offsets.into_iter().map(|begin_offset: u64| begin_offset + shift).collect();
+        let header_bytes: Vec<u8> = header_bytes(&data_set, version, &begin_offsets)?;
+        return Ok(HeaderEdit {
+            file_size: file_size,
+            header_size: header_size,
+            new_header_size: new_header_size,
+            header_bytes: header_bytes,
+        });
+    }
+
+    /// Returns `true` if the new header fits in the space reserved for the header.
+    fn fits_in_place(&self) -> bool {
+        return self.new_header_size == self.header_size;
+    }
+}
+
+/// Returns the data type and the shape of each variable.
+fn get_data_layout(data_set: &DataSet) -> Vec<(DataType, Vec<usize>)> {
+    return data_set.vars().iter().map(|var: &Variable| {
+        (var.data_type(), var.dims().iter().map(|dim: &Rc<Dimension>| dim.size()).collect())
+    }).collect();
+}
+
+/// Writes the header bytes at the beginning of the file, followed by zero bytes up to `header_size`.
+fn write_header<F: Write + Seek>(file: &mut F, bytes: &[u8], header_size: u64) -> std::io::Result<()> {
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(bytes)?;
+    file.write_all(&vec![0_u8; (header_size as usize).saturating_sub(bytes.len())])?;
+    file.flush()?;
+    return Ok(());
+}
+
+/// Moves the bytes `start..end` of `shift` bytes toward the end of the file, starting with the last bytes.
+fn shift_data<F: Read + Write + Seek>(file: &mut F, start: u64, end: u64, shift: u64) -> std::io::Result<()> {
+    let mut buffer: Vec<u8> = vec![0_u8; SHIFT_BUFFER_SIZE];
+    let mut chunk_end: u64 = end;
+    while chunk_end > start {
+        let chunk_start: u64 = std::cmp::max(start, chunk_end.saturating_sub(SHIFT_BUFFER_SIZE as u64));
+        let chunk: &mut [u8] = &mut buffer[0..((chunk_end - chunk_start) as usize)];
+        file.seek(SeekFrom::Start(chunk_start))?;
+        file.read_exact(chunk)?;
+        file.seek(SeekFrom::Start(chunk_start + shift))?;
+        file.write_all(chunk)?;
+        chunk_end = chunk_start;
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Cursor;
+
+    use copy_to_tmp_file::{NC3_CLASSIC_FILE_BYTES, NC3_64BIT_OFFSET_FILE_BYTES, EMPTY_DATA_SET_FILE_BYTES};
+
+    use super::{edit_nc3, shift_data, EditMode};
+    use crate::{copy_nc3, CopyOptions, FileReader};
+    use crate::error::{EditError, InvalidDataSet};
+
+    #[test]
+    fn test_edit_nc3_in_place() {
+        // reserve space for the header
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        let options = CopyOptions{header_min_size: 4096, ..CopyOptions::default()};
+        let mut file: Cursor<Vec<u8>> = copy_nc3(&mut file_reader, Cursor::new(vec![]), &options).unwrap();
+        let file_size: usize = file.get_ref().len();
+
+        assert_eq!(
+            EditMode::InPlace,
+            edit_nc3(&mut file, |data_set| data_set.add_global_attr_string("history", "edited")).unwrap()
+        );
+        assert_eq!(file_size,                   file.get_ref().len());
+        let mut edited_reader = FileReader::from_reader(file).unwrap();
+        assert_eq!(4096,                        edited_reader.header_size());
+        assert_eq!(Some(String::from("edited")),    edited_reader.data_set().get_global_attr_as_string("history"));
+        assert_eq!(file_reader.read_all_vars().unwrap(),    edited_reader.read_all_vars().unwrap());
+
+        // shrink the header
+        let mut file: Cursor<Vec<u8>> = Cursor::new(NC3_64BIT_OFFSET_FILE_BYTES.to_vec());
+        assert_eq!(
+            EditMode::InPlace,
+            edit_nc3(&mut file, |data_set| data_set.remove_global_attr("title").map(|_attr| ())).unwrap()
+        );
+        let mut edited_reader = FileReader::from_reader(file).unwrap();
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_64BIT_OFFSET_FILE_BYTES)).unwrap();
+        assert_eq!(false,                       edited_reader.data_set().has_global_attr("title"));
+        assert_eq!(file_reader.read_all_vars().unwrap(),    edited_reader.read_all_vars().unwrap());
+    }
+
+    #[test]
+    fn test_edit_nc3_rewritten() {
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        let mut file: Cursor<Vec<u8>> = Cursor::new(NC3_CLASSIC_FILE_BYTES.to_vec());
+        assert_eq!(
+            EditMode::Rewritten,
+            edit_nc3(&mut file, |data_set| data_set.add_var_attr_string("time", "comment", "Time (hours since 1900-01-01)")).unwrap()
+        );
+        let mut edited_reader = FileReader::from_reader(file).unwrap();
+        assert!(edited_reader.header_size() > file_reader.header_size());
+        assert_eq!(file_reader.read_all_vars().unwrap(),    edited_reader.read_all_vars().unwrap());
+
+        // without variable
+        let mut file: Cursor<Vec<u8>> = Cursor::new(EMPTY_DATA_SET_FILE_BYTES[0..32].to_vec());
+        assert_eq!(
+            EditMode::Rewritten,
+            edit_nc3(&mut file, |data_set| data_set.add_global_attr_string("title", "empty")).unwrap()
+        );
+        let edited_reader = FileReader::from_reader(file).unwrap();
+        assert_eq!(Some(String::from("empty")),    edited_reader.data_set().get_global_attr_as_string("title"));
+    }
+
+    #[test]
+    fn test_edit_nc3_errors() {
+        let mut file: Cursor<Vec<u8>> = Cursor::new(NC3_CLASSIC_FILE_BYTES.to_vec());
+        assert_eq!(
+            EditError::DataSet(InvalidDataSet::VariableNotDefined(String::from("undef_var"))),
+            edit_nc3(&mut file, |data_set| data_set.rename_var("undef_var", "var")).unwrap_err()
+        );
+        assert_eq!(
+            EditError::DataLayoutChanged,
+            edit_nc3(&mut file, |data_set| data_set.add_var_f32::<&str>("new_var", &[])).unwrap_err()
+        );
+        assert_eq!(NC3_CLASSIC_FILE_BYTES.to_vec(),     file.into_inner());
+    }
+
+    #[test]
+    fn test_shift_data() {
+        let mut file: Cursor<Vec<u8>> = Cursor::new(vec![1, 2, 3, 4, 5, 6]);
+        shift_data(&mut file, 2, 6, 3).unwrap();
+        assert_eq!(vec![1, 2, 3, 4, 5, 3, 4, 5, 6],    file.into_inner());
+    }
+}
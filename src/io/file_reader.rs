@@ -2,35 +2,12 @@ mod tests_file_reader;
 
 use std::convert::TryFrom;
 use std::rc::Rc;
-use std::io::{Read, Seek, SeekFrom};
+use std::borrow::Cow;
+use std::io::{Cursor, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
 use std::collections::HashMap;
 
-use byteorder::{ReadBytesExt, BigEndian};
-
-use nom::{
-    combinator::{
-        verify,
-        map_res,
-    },
-    bytes::streaming::{
-        tag,
-        take,
-    },
-    number::streaming::{
-        be_i8,
-        be_u8,
-        be_i16,
-        be_i32,
-        be_f32,
-        be_f64,
-        be_i64,
-        be_u32,
-    },
-    branch::alt,
-    multi::many_m_n,
-};
-
+use byteorder::{ReadBytesExt, BigEndian, ByteOrder};
 
 use crate::{
     data_set::DimensionSize,
@@ -39,12 +16,27 @@ use crate::{
     Dimension,
     DataVector,
     Variable,
+    VariableView,
     Version,
-    error::ReadError,
-    error::parse_header_error::{ParseHeaderError, ParseHeaderErrorKind, NomError},
-    io::{compute_padding_size, Offset, ABSENT_TAG, DIMENSION_TAG, VARIABLE_TAG, ATTRIBUTE_TAG},
+    error::{InvalidDataSet, ReadError},
+    io::{compute_padding_size, compute_record_offset},
+};
+use crate::io::header_decoder::{HeaderDecoder, LoadBytes};
+use crate::io::header::{parse_header, HeaderParseState, NameDecoder, PaddingCheck, ReadOptions, VariableParsedMetadata};
+use crate::io::manifest::byte_ranges_manifest_json;
+use crate::io::diagnostics::{check_attrs, check_names, Diagnostic, Diagnostics};
+use crate::index::compute_flat_index;
+use crate::io::LayoutViolation;
+use crate::io::validate::{check_layout, find_overlapping_vars, VarLayout};
+use crate::io::VariableReader;
+use crate::io::io_metrics::{IoMetrics, MeteredIo, PositionedIo};
+#[cfg(feature = "http")]
+use crate::io::HttpRangeReader;
+#[cfg(feature = "fs")]
+use crate::io::{
+    json::json_attrs,
+    raw_export::{raw_data_file_name, raw_sidecar_file_name, raw_sidecar_json, write_raw_data, GLOBAL_ATTRS_FILE_NAME},
 };
-
 
 /// Allows to read NetCDF-3 files (the *classic* and the *64-bit offset* versions).
 ///
@@ -205,13 +197,39 @@ use crate::{
 /// // ...
 /// # tmp_dir.close();
 /// ```
+///
+/// # Reading from memory
+///
+/// Any `Read + Seek` source can be used instead of a file (see the method [`from_reader`](#method.from_reader)).
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{FileReader, Version};
+/// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+///
+/// let mut file_reader: FileReader<Cursor<&[u8]>> = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+/// assert_eq!(Version::Classic,                    file_reader.version());
+/// assert_eq!(None,                                file_reader.file_path());
+/// assert_eq!(vec![0.0, 0.5, 1.0],                 file_reader.read_var_f32("latitude").unwrap());
+/// ```
 #[derive(Debug)]
-pub struct FileReader {
+pub struct FileReader<R: Read + Seek = std::fs::File> {
     data_set: DataSet,
     version: Version,
-    input_file_path: PathBuf,
-    input_file: std::fs::File,
-    vars_info: Vec<VariableParsedMetadata>
+    input_file_path: Option<PathBuf>,
+    input_file: MeteredIo<R>,
+    input_file_size: usize,
+    /// Number of bytes used by the header, without the bytes reserved after it
+    header_used_size: usize,
+    vars_info: Vec<VariableParsedMetadata>,
+    /// Offsets of the data of each variable, indexed like the variables of the data set
+    var_offsets: Vec<VarOffsets>,
+    numrecs_is_streaming: bool,
+    diagnostics: Diagnostics,
+    max_alloc_bytes: Option<usize>,
+    buffer_size: usize,
+    /// Buffers reused by `read_var_buffered`, one per data type
+    buffers: Vec<DataVector>,
 }
 
 macro_rules! impl_read_typed_var {
@@ -233,6 +251,20 @@ macro_rules! impl_read_typed_var {
     };
 }
 
+macro_rules! impl_read_typed_var_view {
+    ($func_name:ident, $read_var:ident, $prim_type:ty) => {
+        /// Reads the typed variable and returns its values viewed with the shape of the variable
+        /// (see [`VariableView`](struct.VariableView.html)).
+        pub fn $func_name(&mut self, var_name: &str) -> Result<VariableView<'static, $prim_type>, ReadError> {
+            let data: Vec<$prim_type> = self.$read_var(var_name)?;
+            // the variable is defined, it has been checked while reading it
+            let shape: Vec<usize> = self.data_set.get_var(var_name).ok_or(ReadError::Unexpected)?
+                .dims().iter().map(|dim: &Rc<Dimension>| dim.size()).collect();
+            return VariableView::from_vec(data, &shape).ok_or(ReadError::Unexpected);
+        }
+    };
+}
+
 macro_rules! impl_read_typed_record {
     ($func_name:ident, $prim_type:ty, $data_type:path, $data_vector:path) => {
         /// Reads the typed records and returns its values into a typed`Vec`.
@@ -253,85 +285,450 @@ macro_rules! impl_read_typed_record {
     };
 }
 
-impl FileReader {
+#[cfg(feature = "fs")]
+impl FileReader<std::fs::File> {
 
-    /// Returns the data set managed by the reader.
-    pub fn data_set(&self) -> &DataSet {
-        return &self.data_set;
-    }
-
-    pub fn version(&self) -> Version {
-        return self.version.clone();
+    /// Opens the file and parses the header of the NetCDF-3.
+    pub fn open<P: AsRef<Path>>(input_file_path: P) -> Result<Self, ReadError>
+    {
+        return FileReader::open_with_options(input_file_path, &ReadOptions::default());
     }
 
-    /// Returns the data set managed by the reader.
-    pub fn file_path(&self) -> &std::path::Path
+    /// Opens the file and parses the header of the NetCDF-3 using the options (see [`ReadOptions`](struct.ReadOptions.html)),
+    /// [`open`](#method.open) using the default ones.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::{FileReader, ReadOptions, PaddingCheck};
+    /// # use copy_to_tmp_file::{
+    /// #     copy_bytes_to_tmp_file,
+    /// #     NC3_CLASSIC_FILE_NAME, NC3_CLASSIC_FILE_BYTES,
+    /// # };
+    /// # let (tmp_dir, input_file_path) = copy_bytes_to_tmp_file(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    ///
+    /// let options = ReadOptions::lenient()
+    ///     .with_padding_check(PaddingCheck::Ignore)
+    ///     .with_max_alloc_bytes(1_000_000)
+    ///     .with_buffer_size(4096)
+    ///     .with_lazy_attrs(true);
+    /// let mut file_reader: FileReader = FileReader::open_with(input_file_path, options).unwrap();
+    /// assert_eq!(true,                                    file_reader.diagnostics().is_empty());
+    /// assert_eq!(Some(&[0.0, 0.5, 1.0][..]),              file_reader.read_var("latitude").unwrap().get_f32());
+    /// # tmp_dir.close();
+    /// ```
+    pub fn open_with<P: AsRef<Path>>(input_file_path: P, options: ReadOptions) -> Result<Self, ReadError>
     {
-        return &self.input_file_path;
+        return FileReader::open_with_options(input_file_path, &options);
     }
 
-    /// Opens the file and parses the header of the NetCDF-3.
-    pub fn open<P: AsRef<Path>>(input_file_path: P) -> Result<Self, ReadError>
+    /// Opens the file and parses the header of the NetCDF-3 using the options given by reference (see [`open_with`](#method.open_with)).
+    pub fn open_with_options<P: AsRef<Path>>(input_file_path: P, options: &ReadOptions) -> Result<Self, ReadError>
     {
-        const BUFFER_SIZE: usize = 1024;
         // Open the file
         let input_file_path: PathBuf = {
             let mut path = PathBuf::new();
             path.push(input_file_path);
             path
         };
-        let mut input_file = std::fs::File::open(input_file_path.clone())?;
-        let file_size: usize = std::fs::metadata(&input_file_path)?.len() as usize; 
-        
-        // Parse the header
-        let (data_set, version, vars_info): (DataSet, Version, Vec<VariableParsedMetadata>) = {
-            let mut buffer: Vec<u8> = vec![];
-            let (data_set, version, vars_info): (DataSet, Version, Vec<VariableParsedMetadata>);
-            loop {
-                // Load bytes
-                let old_buf_start: usize = buffer.len();
-                let new_buf_size: usize = std::cmp::min(buffer.len() + BUFFER_SIZE, file_size);
-                let start: &usize = &old_buf_start;
-                let end: &usize = &new_buf_size;
-                buffer.resize(new_buf_size, 0_u8);
-                let _num_of_bytes = input_file.read(&mut buffer[*start..*end])?;
-
-                let parsing_result: Result<(DataSet, Version, Vec<VariableParsedMetadata>), ReadError>;
-                parsing_result = FileReader::parse_header(&buffer, file_size);
-                match parsing_result {
-                    Ok((data_set_2, version_2, vars_info_2)) => {
-                        data_set = data_set_2;
-                        version = version_2;
-                        vars_info = vars_info_2;
-                        break;
-                    },
-                    Err(read_err) => {
-                        if read_err.header_is_incomplete() {
-                            let buf_size: usize = buffer.len();
-                            if buf_size < file_size {
-                                // nothing to do
-                            }
-                            else {
-                                return Err(read_err);
-                            }
-                        }
-                        else {
-                            return Err(read_err);
-                        }
-                    },
-                }
+        let input_file = std::fs::File::open(input_file_path.clone())?;
+        let mut file_reader: FileReader = FileReader::from_reader_with_options(input_file, options)?;
+        // the chunks are read through positional reads
+        #[cfg(any(unix, windows))]
+        {
+            let file: std::fs::File = file_reader.input_file.get_ref().try_clone()?;
+            file_reader.input_file.set_file(file);
+        }
+        file_reader.input_file_path = Some(input_file_path);
+        return Ok(file_reader);
+    }
+
+    /// Reads the variable through a shared reference, using a positional read for each chunk (see [`read_var`](#method.read_var)).
+    ///
+    /// No seek is required, then several variables can be read while the reader is borrowed, and the number of system calls
+    /// is halved for the record variables.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::{FileReader, DataVector};
+    /// # use copy_to_tmp_file::{
+    /// #     copy_bytes_to_tmp_file,
+    /// #     NC3_CLASSIC_FILE_NAME, NC3_CLASSIC_FILE_BYTES,
+    /// # };
+    /// # let (tmp_dir, input_file_path) = copy_bytes_to_tmp_file(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    ///
+    /// let file_reader: FileReader = FileReader::open(input_file_path).unwrap();
+    /// let latitude: DataVector = file_reader.read_var_shared("latitude").unwrap();
+    /// let longitude: DataVector = file_reader.read_var_shared("longitude").unwrap();
+    /// assert_eq!(Some(&[0.0, 0.5, 1.0][..]),              latitude.get_f32());
+    /// assert_eq!(Some(&[0.0, 0.5, 1.0, 1.5, 2.0][..]),    longitude.get_f32());
+    /// # tmp_dir.close();
+    /// ```
+    pub fn read_var_shared(&self, var_name: &str) -> Result<DataVector, ReadError>
+    {
+        let (_, var): (usize, &Variable) = self.data_set.find_var_from_name(var_name).map_err(|_err|{
+            ReadError::VariableNotDefined(String::from(var_name))
+        })?;
+        let var_len: usize = var.checked_len().ok_or_else(|| {
+            ReadError::DataSet(InvalidDataSet::VariableSizeOverflow(String::from(var_name)))
+        })?;
+        self.check_alloc(var, var_len)?;
+        self.check_var_in_file(var_name)?;
+        let byte_ranges: Vec<(u64, usize)> = self.var_byte_ranges(var_name).ok_or(ReadError::Unexpected)?;
+        let mut bytes: Vec<u8> = vec![0_u8; var_len * var.data_type().size_of()];
+        read_ranges_at(self.input_file.get_ref(), &byte_ranges, &mut bytes)?;
+        self.input_file.counters().add_bytes_read(bytes.len());
+        self.input_file.counters().add_chunks_read(byte_ranges.len());
+        return Ok(DataVector::from_be_bytes(&bytes, var.data_type()));
+    }
+
+    /// Reads the variable like [`read_var_shared`](#method.read_var_shared), the records being split across `num_threads` threads
+    /// (requires the feature `parallel`).
+    ///
+    /// Each thread reads a contiguous range of records into its own part of the output, which speeds up the loading of the record
+    /// variables spanning many records on fast storage. A *fixed-size* variable is read by a single thread.
+    /// On the targets other than Unix and Windows, the positional reads are not available and the reads of the threads are serialized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::{FileReader, DataVector};
+    /// # use copy_to_tmp_file::{
+    /// #     copy_bytes_to_tmp_file,
+    /// #     NC3_CLASSIC_FILE_NAME, NC3_CLASSIC_FILE_BYTES,
+    /// # };
+    /// # let (tmp_dir, input_file_path) = copy_bytes_to_tmp_file(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    ///
+    /// let file_reader: FileReader = FileReader::open(input_file_path).unwrap();
+    /// let time: DataVector = file_reader.read_var_parallel("time", 2).unwrap();
+    /// assert_eq!(Some(&[438_300.0, 438_324.0][..]),       time.get_f32());
+    /// # tmp_dir.close();
+    /// ```
+    #[cfg(feature = "parallel")]
+    pub fn read_var_parallel(&self, var_name: &str, num_threads: usize) -> Result<DataVector, ReadError>
+    {
+        let (_, var): (usize, &Variable) = self.data_set.find_var_from_name(var_name).map_err(|_err|{
+            ReadError::VariableNotDefined(String::from(var_name))
+        })?;
+        let var_len: usize = var.checked_len().ok_or_else(|| {
+            ReadError::DataSet(InvalidDataSet::VariableSizeOverflow(String::from(var_name)))
+        })?;
+        self.check_alloc(var, var_len)?;
+        self.check_var_in_file(var_name)?;
+        let byte_ranges: Vec<(u64, usize)> = self.var_byte_ranges(var_name).ok_or(ReadError::Unexpected)?;
+        let mut bytes: Vec<u8> = vec![0_u8; var_len * var.data_type().size_of()];
+
+        // Split the records into contiguous groups, each group being read by a thread into a disjoint part of the output
+        let num_threads: usize = num_threads.clamp(1, std::cmp::max(byte_ranges.len(), 1));
+        let num_ranges_per_thread: usize = std::cmp::max(byte_ranges.len().div_ceil(num_threads), 1);
+        let input_file: &std::fs::File = self.input_file.get_ref();
+        std::thread::scope(|scope| -> Result<(), ReadError> {
+            let mut remaining_bytes: &mut [u8] = &mut bytes[..];
+            let mut handles = Vec::with_capacity(num_threads);
+            for thread_ranges in byte_ranges.chunks(num_ranges_per_thread) {
+                let num_bytes: usize = thread_ranges.iter().map(|(_offset, length): &(u64, usize)| *length).sum();
+                let (thread_bytes, other_bytes): (&mut [u8], &mut [u8]) = std::mem::take(&mut remaining_bytes).split_at_mut(num_bytes);
+                remaining_bytes = other_bytes;
+                handles.push(scope.spawn(move || read_ranges_at(input_file, thread_ranges, thread_bytes)));
+            }
+            for handle in handles.into_iter() {
+                handle.join().map_err(|_err| ReadError::Unexpected)??;
             }
-            (data_set, version, vars_info)
+            return Ok(());
+        })?;
+        self.input_file.counters().add_bytes_read(bytes.len());
+        self.input_file.counters().add_chunks_read(byte_ranges.len());
+        return Ok(DataVector::from_be_bytes(&bytes, var.data_type()));
+    }
+
+    /// Reads a record of the variable through a shared reference, using a single positional read (see [`read_record`](#method.read_record)).
+    pub fn read_record_shared(&self, var_name: &str, record_index: usize) -> Result<DataVector, ReadError>
+    {
+        let (_var_index, var): (usize, &Variable) = self.data_set.find_var_from_name(var_name).map_err(|_err|{
+            ReadError::VariableNotDefined(String::from(var_name))
+        })?;
+        let num_records: usize = self.data_set.num_records().unwrap_or(1); // fixed-size variables haves exaclty one record
+        if record_index >= num_records {
+            return Err(ReadError::RecordIndexExceeded{index: record_index, num_records: num_records});
+        }
+        let var_offsets: VarOffsets = self.find_var(var_name)?.1;
+        let record_offset: u64 = var_offsets.chunk_offset(record_index).ok_or_else(|| {
+            ReadError::RecordOffsetOverflow{var_name: String::from(var_name), record_index: record_index}
+        })?;
+        self.check_alloc(var, var.chunk_len())?;
+        let mut bytes: Vec<u8> = vec![0_u8; var.chunk_len() * var.data_type().size_of()];
+        read_exact_at(self.input_file.get_ref(), &mut bytes, record_offset)?;
+        self.input_file.counters().add_bytes_read(bytes.len());
+        self.input_file.counters().add_chunks_read(1);
+        return Ok(DataVector::from_be_bytes(&bytes, var.data_type()));
+    }
+}
+
+#[cfg(feature = "http")]
+impl FileReader<HttpRangeReader> {
+
+    /// Opens a remote file through HTTP(S) range requests and parses the header of the NetCDF-3.
+    ///
+    /// Only the header is downloaded, the variable data are fetched when they are read (see [`HttpRangeReader`](struct.HttpRangeReader.html)).
+    pub fn open_url(url: &str) -> Result<Self, ReadError>
+    {
+        let http_reader = HttpRangeReader::open(url)?;
+        return FileReader::from_reader(http_reader);
+    }
+}
+
+/// Zero-copy reads from the in-memory inputs.
+impl<T: AsRef<[u8]>> FileReader<Cursor<T>> {
+
+    /// Returns the raw bytes of a `i8` or `u8` variable, borrowed from the in-memory input when they are contiguous.
+    ///
+    /// The bytes need no endian conversion, the values of a `i8` variable are obtained by `byte as i8`.
+    /// The data of a record variable are copied into a new `Vec` only if they are interleaved with the other record variables
+    /// (see [`read_var`](#method.read_var)).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    /// use std::io::Cursor;
+    /// use netcdf3::FileReader;
+    /// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+    ///
+    /// let file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+    ///
+    /// // `temperature_u8` : 2 records of 15 `u8` values, interleaved with the other record variables
+    /// let bytes: Cow<[u8]> = file_reader.read_var_bytes("temperature_u8").unwrap();
+    /// assert_eq!((0..30).collect::<Vec<u8>>(),        bytes.into_owned());
+    ///
+    /// // `temperature_i8` : the bytes are reinterpreted as `i8`
+    /// let values: Vec<i8> = file_reader.read_var_bytes("temperature_i8").unwrap().iter().map(|byte: &u8| *byte as i8).collect();
+    /// assert_eq!((0..30).collect::<Vec<i8>>(),        values);
+    /// ```
+    pub fn read_var_bytes(&self, var_name: &str) -> Result<Cow<'_, [u8]>, ReadError>
+    {
+        let (_, var): (usize, &Variable) = self.data_set.find_var_from_name(var_name).map_err(|_err|{
+            ReadError::VariableNotDefined(String::from(var_name))
+        })?;
+        let data_type: DataType = var.data_type();
+        if data_type != DataType::I8 && data_type != DataType::U8 {
+            return Err(ReadError::VariableMismatchDataType{var_name: String::from(var_name), req: data_type, get: DataType::U8});
+        }
+        let input: &[u8] = self.input_file.get_ref().get_ref().as_ref();
+        let byte_ranges: Vec<(u64, usize)> = self.var_byte_ranges(var_name).ok_or(ReadError::Unexpected)?;
+        let chunk_bytes = |(offset, length): (u64, usize)| -> Result<&[u8], ReadError> {
+            let start: usize = usize::try_from(offset).map_err(|_err| ReadError::Unexpected)?;
+            return start.checked_add(length).and_then(|end: usize| input.get(start..end)).ok_or_else(|| {
+                ReadError::from(std::io::Error::from(std::io::ErrorKind::UnexpectedEof))
+            });
+        };
+        // the chunks follow each other (fixed-size variable, single record variable, or at most one record)
+        let contiguous: bool = byte_ranges.windows(2).all(|ranges: &[(u64, usize)]| {
+            ranges[0].0 + ranges[0].1 as u64 == ranges[1].0
+        });
+        if contiguous {
+            let (offset, _): (u64, usize) = byte_ranges.first().copied().unwrap_or((0, 0));
+            let length: usize = byte_ranges.iter().map(|(_, length): &(u64, usize)| *length).sum();
+            return Ok(Cow::Borrowed(chunk_bytes((offset, length))?));
+        }
+        let var_len: usize = byte_ranges.iter().map(|(_, length): &(u64, usize)| *length).sum();
+        self.check_alloc(var, var_len)?;
+        let mut bytes: Vec<u8> = Vec::with_capacity(var_len);
+        for byte_range in byte_ranges.into_iter() {
+            bytes.extend_from_slice(chunk_bytes(byte_range)?);
+        }
+        return Ok(Cow::Owned(bytes));
+    }
+}
+
+impl<R: Read + Seek> FileReader<R> {
+
+    /// Returns the data set managed by the reader.
+    #[inline]
+    pub fn data_set(&self) -> &DataSet {
+        return &self.data_set;
+    }
+
+    #[inline]
+    pub fn version(&self) -> Version {
+        return self.version;
+    }
+
+    /// Returns the path of the input file (`None` if the reader has not been created by the method [`open`](#method.open)).
+    pub fn file_path(&self) -> Option<&Path>
+    {
+        return self.input_file_path.as_deref();
+    }
+
+    /// Parses the header of the NetCDF-3 data read from `input` (a file, an in-memory buffer, etc.).
+    pub fn from_reader(input: R) -> Result<Self, ReadError>
+    {
+        return FileReader::parse_from_reader(input, None, &ReadOptions::default());
+    }
+
+    /// Parses the header of the NetCDF-3 data read from `input` using the options (see [`ReadOptions`](struct.ReadOptions.html)).
+    pub fn from_reader_with_options(input: R, options: &ReadOptions) -> Result<Self, ReadError>
+    {
+        return FileReader::parse_from_reader(input, None, options);
+    }
+
+    /// Parses the header, the number of records stored in it is replaced by `num_records` if defined
+    /// (allows to parse the header of a truncated file).
+    pub(crate) fn parse_from_reader(input: R, num_records: Option<usize>, options: &ReadOptions) -> Result<Self, ReadError>
+    {
+        let mut input_file: MeteredIo<R> = MeteredIo::new(input);
+        let file_size: usize = input_file.seek(SeekFrom::End(0))? as usize;
+        input_file.seek(SeekFrom::Start(0))?;
+
+        // Parse the header, its bytes being loaded as the parsing goes along
+        let mut state = HeaderParseState::new(NameDecoder::with_options(options));
+        let mut loader = HeaderLoader{
+            input: &mut input_file,
+            buffer_size: std::cmp::max(options.buffer_size, 1),
+            file_size: file_size,
+            num_records: num_records,
+            allow_truncated_records: options.allow_truncated_records,
+            numrecs_is_streaming: false,
+            error: None,
         };
+        let (parsing_result, loaded_bytes) = {
+            let mut decoder = HeaderDecoder::with_loader(file_size, &mut loader);
+            let parsing_result = parse_header(&mut decoder, &mut state);
+            (parsing_result, decoder.into_loaded_bytes())
+        };
+        let HeaderLoader{numrecs_is_streaming, error, ..} = loader;
+        if let Some(io_err) = error {
+            return Err(ReadError::from(io_err));
+        }
+        let (data_set, version, vars_info, header_end): (DataSet, Version, Vec<VariableParsedMetadata>, usize) = parsing_result?;
 
         // Return the result
-        return Ok(FileReader{
+        let var_offsets: Vec<VarOffsets> = VarOffsets::compute_all(&data_set, &vars_info);
+        let mut file_reader = FileReader{
             data_set: data_set,
             version: version,
-            input_file_path: input_file_path,
+            input_file_path: None,
             input_file: input_file,
+            input_file_size: file_size,
+            header_used_size: header_end,
             vars_info: vars_info,  // convert the list of tuples to a map
-        })
+            var_offsets: var_offsets,
+            numrecs_is_streaming: numrecs_is_streaming && num_records.is_none(),
+            diagnostics: Diagnostics::new(),
+            max_alloc_bytes: options.max_alloc_bytes,
+            buffer_size: std::cmp::max(options.buffer_size, 1),
+            buffers: vec![],
+        };
+        if !options.allow_overlapping_vars {
+            let vars_layout: Vec<VarLayout> = file_reader.vars_layout()?;
+            if let Some((var, prev_var)) = find_overlapping_vars(&vars_layout) {
+                return Err(ReadError::OverlappingVariables{
+                    var_name: var.name.clone(),
+                    begin_offset: var.begin_offset,
+                    prev_var_name: prev_var.name.clone(),
+                    prev_end_offset: prev_var.end_offset(),
+                });
+            }
+        }
+        let truncated_records: Option<Diagnostic> = match options.allow_truncated_records {
+            true => file_reader.truncate_records(),
+            false => None,
+        };
+        file_reader.diagnostics = file_reader.collect_diagnostics(&loaded_bytes, state)?;
+        if let Some(diagnostic) = truncated_records {
+            file_reader.diagnostics.push(diagnostic);
+        }
+        return Ok(file_reader);
+    }
+
+    /// Reduces the number of records to the number of complete records stored in the file.
+    ///
+    /// Returns a diagnostic if some records are missing or incomplete.
+    fn truncate_records(&mut self) -> Option<Diagnostic>
+    {
+        let records_begin: u64 = self.data_set.vars().iter()
+            .filter(|var: &&Variable| var.is_record_var())
+            .filter_map(|var: &Variable| self.var_begin_offset(var.name()))
+            .min()?;
+        let record_size: u64 = self.data_set.record_size().filter(|record_size: &usize| *record_size > 0)? as u64;
+        let records_size: u64 = (self.input_file_size as u64).saturating_sub(records_begin);
+        let num_complete_records: usize = (records_size / record_size) as usize;
+        let header_num_records: Option<usize> = match self.numrecs_is_streaming {
+            true => None,
+            false => self.data_set.num_records(),
+        };
+        let num_records: usize = match header_num_records {
+            Some(header_num_records) => std::cmp::min(header_num_records, num_complete_records),
+            None => num_complete_records,
+        };
+        if let Some(unlimited_dim) = self.data_set.get_unlimited_dim() {
+            if let DimensionSize::Unlimited(dim_size) = &unlimited_dim.size {
+                dim_size.replace(num_records);
+            }
+        }
+        let is_complete: bool = match header_num_records {
+            Some(header_num_records) => header_num_records == num_records,
+            None => records_size % record_size == 0,
+        };
+        if is_complete {
+            return None;
+        }
+        return Some(Diagnostic::RecordsTruncated{header_num_records: header_num_records, num_records: num_records});
+    }
+
+    /// Collects the non-fatal findings, `loaded_bytes` are the first bytes of the file loaded while parsing the header.
+    fn collect_diagnostics(&self, loaded_bytes: &[u8], state: HeaderParseState) -> Result<Diagnostics, ReadError>
+    {
+        let mut diagnostics = Diagnostics::new();
+        let header_used_size: usize = self.header_used_size;
+        let vars_layout: Vec<VarLayout> = self.vars_layout()?;
+        for violation in check_layout(&self.data_set, &vars_layout, header_used_size as u64, self.input_file_size as u64).into_iter() {
+            diagnostics.push(Diagnostic::Layout(violation));
+        }
+        // Zero padding of the loaded part of the header
+        let padding_end: usize = std::cmp::min(self.data_section_offset(), loaded_bytes.len());
+        if header_used_size < padding_end && state.names.padding_check != PaddingCheck::Ignore {
+            if let Some(position) = loaded_bytes[header_used_size..padding_end].iter().position(|byte: &u8| *byte != 0) {
+                diagnostics.push(Diagnostic::Layout(LayoutViolation::NonZeroPadding{offset: (header_used_size + position) as u64}));
+            }
+        }
+        for diagnostic in check_attrs(&self.data_set).into_iter() {
+            diagnostics.push(diagnostic);
+        }
+        for diagnostic in check_names(&self.data_set).into_iter() {
+            diagnostics.push(diagnostic);
+        }
+        for (bytes, name) in state.substitutions.into_iter() {
+            diagnostics.push(Diagnostic::NameNotValidUtf8{bytes: bytes, name: name});
+        }
+        for diagnostic in state.duplicates.into_iter() {
+            diagnostics.push(diagnostic);
+        }
+        for diagnostic in state.long_names.into_iter() {
+            diagnostics.push(diagnostic);
+        }
+        for diagnostic in state.non_zero_paddings.into_iter() {
+            diagnostics.push(diagnostic);
+        }
+        return Ok(diagnostics);
+    }
+
+    /// Returns the non-fatal findings collected while parsing the header (see [`Diagnostic`](enum.Diagnostic.html)).
+    ///
+    /// The read succeeds despite these findings, they allow to report the data quality issues of the file.
+    pub fn diagnostics(&self) -> &Diagnostics {
+        return &self.diagnostics;
+    }
+
+    /// Returns the counters of the bytes read, the seeks and the chunks read since the opening of the file
+    /// or the last call to [`reset_io_metrics`](#method.reset_io_metrics) (see [`IoMetrics`](struct.IoMetrics.html)).
+    pub fn io_metrics(&self) -> IoMetrics {
+        return self.input_file.counters().snapshot();
+    }
+
+    /// Resets the I/O counters (see [`io_metrics`](#method.io_metrics)).
+    pub fn reset_io_metrics(&mut self) {
+        self.input_file.counters().reset();
     }
 
     /// Closes the file and releases the data set and the file version.
@@ -345,13 +742,111 @@ impl FileReader {
     pub fn read_all_vars(&mut self) -> Result<HashMap<String, DataVector>, ReadError>
     {
         let var_names: Vec<String> = self.data_set.get_var_names();
-        var_names.into_iter()
-            .map(|var_name: String| {
-                let var_data: DataVector = self.read_var(&var_name)?;
-                Ok((var_name, var_data))
+        let var_names: Vec<&str> = var_names.iter().map(String::as_str).collect();
+        return self.read_vars(&var_names).map(|vars_data: Vec<(String, DataVector)>| vars_data.into_iter().collect());
+    }
+
+    /// Reads the variables `var_names`, and stores their values into the variables of the data set
+    /// (see [`Variable::data`](struct.Variable.html#method.data)).
+    ///
+    /// The data set returned by [`close`](#method.close) is then self-contained. All the variables are checked (their definitions
+    /// and their sizes) before reading any of them, and the values are stored only if all the variables have been read.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::{FileReader, DataSet, DataVector};
+    /// use netcdf3::error::ReadError;
+    /// # use std::io::Cursor;
+    /// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+    ///
+    /// let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+    /// assert_eq!(
+    ///     Err(ReadError::VariableNotDefined(String::from("undef_var"))),
+    ///     file_reader.load_vars(&["latitude", "undef_var"]).map(|_data_set: &DataSet| ())
+    /// );
+    /// file_reader.load_vars(&["latitude", "temperature_f32"]).unwrap();
+    ///
+    /// let data_set: DataSet = file_reader.close().0;
+    /// assert_eq!(Some(&DataVector::F32(vec![0.0, 0.5, 1.0])),     data_set["latitude"].data());
+    /// assert_eq!(Some(30),                                        data_set["temperature_f32"].data().map(DataVector::len));
+    /// assert_eq!(None,                                            data_set["longitude"].data());
+    /// ```
+    pub fn load_vars(&mut self, var_names: &[&str]) -> Result<&DataSet, ReadError>
+    {
+        let vars_data: Vec<(String, DataVector)> = self.read_vars(var_names)?;
+        for (var_name, var_data) in vars_data.into_iter() {
+            let var_index: usize = self.data_set.find_var_from_name(&var_name)?.0;
+            self.data_set.vars[var_index].data = Some(var_data);
+        }
+        return Ok(&self.data_set);
+    }
+
+    /// Reads the variables `var_names`, checking all of them before reading any of them.
+    fn read_vars(&mut self, var_names: &[&str]) -> Result<Vec<(String, DataVector)>, ReadError>
+    {
+        // Check the size of all the variables before reading any of them
+        for var_name in var_names.iter() {
+            let var: &Variable = self.data_set.get_var(var_name).ok_or_else(|| ReadError::VariableNotDefined(var_name.to_string()))?;
+            let var_len: usize = var.checked_len().ok_or_else(|| {
+                ReadError::DataSet(InvalidDataSet::VariableSizeOverflow(var.name().to_string()))
+            })?;
+            self.check_alloc(var, var_len)?;
+        }
+        var_names.iter()
+            .map(|var_name: &&str| {
+                let var_data: DataVector = self.read_var(var_name)?;
+                Ok((var_name.to_string(), var_data))
             }).collect()
     }
 
+    /// Exports each variable into the directory `output_dir` (created if necessary) as :
+    ///
+    /// - a flat binary file `<var_name>.bin` containing the elements in the native byte order (see [`write_raw_data`](fn.write_raw_data.html)),
+    /// - a JSON sidecar `<var_name>.json` describing its data type, shape and attributes (see [`raw_sidecar_json`](fn.raw_sidecar_json.html)).
+    ///
+    /// The global attributes are exported into the JSON file `%global_attributes.json`.
+    ///
+    /// The characters of the variable names which are not allowed or special in the file names (`/`, `\`, `%`, the control characters,
+    /// the characters reserved on Windows and a leading `.`) are escaped as `%XX`, the hexadecimal value of their bytes,
+    /// so the exported files stay inside `output_dir` and never collide with the file of the global attributes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::FileReader;
+    /// # use copy_to_tmp_file::{
+    /// #     copy_bytes_to_tmp_file,
+    /// #     NC3_CLASSIC_FILE_NAME, NC3_CLASSIC_FILE_BYTES,
+    /// # };
+    /// # let (tmp_dir, input_file_path) = copy_bytes_to_tmp_file(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    /// # let output_dir = tmp_dir.path().join("raw");
+    ///
+    /// let mut file_reader: FileReader = FileReader::open(input_file_path).unwrap();
+    /// file_reader.export_raw_vars(&output_dir).unwrap();
+    ///
+    /// assert_eq!(true,        output_dir.join("latitude.bin").exists());
+    /// assert_eq!(true,        output_dir.join("latitude.json").exists());
+    /// assert_eq!(12,          std::fs::metadata(output_dir.join("latitude.bin")).unwrap().len());
+    /// ```
+    #[cfg(feature = "fs")]
+    pub fn export_raw_vars<P: AsRef<Path>>(&mut self, output_dir: P) -> Result<(), ReadError>
+    {
+        let output_dir: &Path = output_dir.as_ref();
+        std::fs::create_dir_all(output_dir)?;
+        for var_name in self.data_set.get_var_names() {
+            let data: DataVector = self.read_var(&var_name)?;
+            let mut data_file = std::io::BufWriter::new(std::fs::File::create(output_dir.join(raw_data_file_name(&var_name)))?);
+            write_raw_data(&data, &mut data_file)?;
+            std::io::Write::flush(&mut data_file)?;
+            let sidecar: String = raw_sidecar_json(&self.data_set, &var_name).ok_or(ReadError::Unexpected)?;
+            std::fs::write(output_dir.join(raw_sidecar_file_name(&var_name)), sidecar)?;
+        }
+        let global_attrs: String = format!("{}\n", json_attrs(&self.data_set.get_global_attrs(), ""));
+        std::fs::write(output_dir.join(GLOBAL_ATTRS_FILE_NAME), global_attrs)?;
+        return Ok(());
+    }
+
     /// Reads the typed variable and returns its values into `Vec`.
     ///
     /// # Example
@@ -401,26 +896,61 @@ impl FileReader {
     /// ```
     pub fn read_var(&mut self, var_name: &str) -> Result<DataVector, ReadError>
     {
-        let (_, var): (usize, &Variable) = self.data_set.find_var_from_name(var_name).map_err(|_err|{
+        // An empty vector does not allocate memory, it is replaced by the vector of the variable data type
+        let mut data_vec = DataVector::U8(vec![]);
+        self.read_var_into(var_name, &mut data_vec)?;
+        Ok(data_vec)
+    }
+
+    /// Reads the variable into `data_vec`, its allocation being reused if its data type is the variable one
+    /// (see [`read_var`](#method.read_var)).
+    ///
+    /// Repeated reads of variables of the same data type and length do not reallocate memory.
+    /// If an error occurs, the content of `data_vec` is unspecified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use netcdf3::{FileReader, DataVector};
+    /// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+    ///
+    /// let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+    /// let mut data_vec = DataVector::F32(Vec::with_capacity(16));
+    /// file_reader.read_var_into("latitude", &mut data_vec).unwrap();
+    /// assert_eq!(Some(&[0.0, 0.5, 1.0][..]),     data_vec.get_f32());
+    ///
+    /// file_reader.read_var_into("time", &mut data_vec).unwrap();
+    /// assert_eq!(Some(&[438_300.0, 438_324.0][..]),    data_vec.get_f32());
+    /// ```
+    pub fn read_var_into(&mut self, var_name: &str, data_vec: &mut DataVector) -> Result<(), ReadError>
+    {
+        // the fields are borrowed separately, the input being read afterward
+        let (var_index, var): (usize, &Variable) = self.data_set.find_var_from_name(var_name).map_err(|_err|{
             ReadError::VariableNotDefined(String::from(var_name))
         })?;
-        let record_size: usize = self.data_set.record_size().unwrap_or(0);
+        let var_offsets: VarOffsets = *self.var_offsets.get(var_index).ok_or(ReadError::Unexpected)?;
+        let record_size: usize = var_offsets.stride as usize;
         let num_records: usize = self.data_set.num_records().unwrap_or(0);
-        let begin_offset: u64 = {
-            let var_info: &VariableParsedMetadata = self.find_var_info(var_name).ok_or(ReadError::Unexpected)?;
-            i64::from(var_info.begin_offset.clone()) as u64
-        };
+        let num_chunks: usize = if var.is_record_var() { num_records } else { 1 };
+        let begin_offset: u64 = var_offsets.begin_offset;
         let data_type: DataType = var.data_type();
         let chunk_len: usize = var.chunk_len();
+        let var_len: usize = var.checked_len().ok_or_else(|| {
+            ReadError::DataSet(InvalidDataSet::VariableSizeOverflow(String::from(var_name)))
+        })?;
+        self.check_alloc(var, var_len)?;
+        self.check_var_in_file(var_name)?;
         let padding_size: usize = {
             let num_bytes: usize = chunk_len * data_type.size_of();
             compute_padding_size(num_bytes)
         };
-        let ref mut input = self.input_file;
-        input.seek(SeekFrom::Start(begin_offset))?;
-        // memory allocation
-        let mut data_vec = DataVector::new(data_type, var.len());
-        if !var.is_record_var() {
+        let mut input: PositionedIo<R> = self.input_file.positioned(begin_offset)?;
+        // memory allocation, if the buffer cannot be reused
+        data_vec.reset(data_type, var_len);
+        // The records of the only record variable are contiguous (without padding bytes), they are read at once
+        let records_are_packed: bool = var.is_record_var() && self.data_set.records_are_packed();
+        if !var.is_record_var() || records_are_packed {
             match data_vec {
                 DataVector::I8(ref mut data) => { input.read_i8_into(&mut data[..]) },
                 DataVector::U8(ref mut data) => { input.read_exact(&mut data[..]) },
@@ -429,18 +959,39 @@ impl FileReader {
                 DataVector::F32(ref mut data) => { input.read_f32_into::<BigEndian>(&mut data[..]) },
                 DataVector::F64(ref mut data) => { input.read_f64_into::<BigEndian>(&mut data[..]) },
             }?;
-            if padding_size > 0
+            if padding_size > 0 && !records_are_packed
             {
-                input.seek(SeekFrom::Current(padding_size as i64))?;
+                input.skip(padding_size as u64)?;
             }
         }
         else {
             let chunk_size: usize = var.chunk_size();
 
-            let offset_size: i64 = (record_size + padding_size - chunk_size) as i64;
+            let offset_size: u64 = (record_size + padding_size - chunk_size) as u64;
+            // Read several records at once if they fit into the buffer
+            let batch_len: usize = self.buffer_size.checked_div(record_size).unwrap_or(0);
+            if batch_len > 1 {
+                let useful_size: usize = chunk_len * data_type.size_of();
+                let mut bytes: Vec<u8> = vec![];
+                let mut record_index: usize = 0;
+                while record_index < num_records {
+                    let num_batch_records: usize = std::cmp::min(batch_len, num_records - record_index);
+                    // the bytes following the last record of the batch are not read
+                    bytes.resize((num_batch_records - 1) * record_size + useful_size, 0_u8);
+                    input.read_exact(&mut bytes[..])?;
+                    for i in 0..num_batch_records {
+                        let start: usize = (record_index + i) * chunk_len;
+                        let record_bytes: &[u8] = &bytes[(i * record_size)..(i * record_size + useful_size)];
+                        read_be_bytes_into(record_bytes, data_vec, start, start + chunk_len);
+                    }
+                    input.skip(offset_size)?;
+                    record_index += num_batch_records;
+                }
+                input.counters().add_chunks_read(num_records);
+                return Ok(());
+            }
             for i in 0_usize..num_records
             {
-                // reader.seek(SeekFrom::)
                 let start: usize = i * chunk_len;
                 let end: usize = (i + 1) * chunk_len;
                 match data_vec {
@@ -451,10 +1002,59 @@ impl FileReader {
                     DataVector::F32(ref mut data) => { input.read_f32_into::<BigEndian>(&mut data[start..end]) },
                     DataVector::F64(ref mut data) => { input.read_f64_into::<BigEndian>(&mut data[start..end]) },
                 }?;
-                input.seek(SeekFrom::Current(offset_size))?;
+                input.skip(offset_size)?;
             }
         }
-        Ok(data_vec)
+        input.counters().add_chunks_read(num_chunks);
+        Ok(())
+    }
+
+    /// Returns a handle on the variable, bound to the reader (see [`VariableReader`](struct.VariableReader.html)).
+    ///
+    /// Returns an error if the variable is not defined.
+    pub fn variable(&mut self, var_name: &str) -> Result<VariableReader<'_, R>, ReadError>
+    {
+        return VariableReader::new(self, var_name);
+    }
+
+    /// Reads the variable into an internal buffer and returns a reference to it (see [`read_var`](#method.read_var)).
+    ///
+    /// One buffer is kept for each data type and reused by the next reads, the memory being released
+    /// by [`clear_buffers`](#method.clear_buffers).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use netcdf3::FileReader;
+    /// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+    ///
+    /// let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+    /// assert_eq!(Some(&[0.0, 0.5, 1.0][..]),     file_reader.read_var_buffered("latitude").unwrap().get_f32());
+    /// assert_eq!(Some(&[0.0, 0.5, 1.0, 1.5, 2.0][..]),   file_reader.read_var_buffered("longitude").unwrap().get_f32());
+    ///
+    /// // Release the memory of the buffers
+    /// file_reader.clear_buffers();
+    /// ```
+    pub fn read_var_buffered(&mut self, var_name: &str) -> Result<&DataVector, ReadError>
+    {
+        let data_type: DataType = self.data_set.var_data_type(var_name).ok_or_else(|| {
+            ReadError::VariableNotDefined(String::from(var_name))
+        })?;
+        let mut buffer: DataVector = match self.buffers.iter().position(|buffer: &DataVector| buffer.data_type() == data_type) {
+            Some(buffer_index) => self.buffers.swap_remove(buffer_index),
+            None => DataVector::new(data_type, 0),
+        };
+        let result: Result<(), ReadError> = self.read_var_into(var_name, &mut buffer);
+        self.buffers.push(buffer);
+        result?;
+        return self.buffers.last().ok_or(ReadError::Unexpected);
+    }
+
+    /// Releases the memory of the internal buffers used by [`read_var_buffered`](#method.read_var_buffered).
+    pub fn clear_buffers(&mut self)
+    {
+        self.buffers = vec![];
     }
 
     impl_read_typed_var!(read_var_i8, i8, DataType::I8, DataVector::I8);
@@ -464,6 +1064,13 @@ impl FileReader {
     impl_read_typed_var!(read_var_f32, f32, DataType::F32, DataVector::F32);
     impl_read_typed_var!(read_var_f64, f64, DataType::F64, DataVector::F64);
 
+    impl_read_typed_var_view!(read_var_view_i8, read_var_i8, i8);
+    impl_read_typed_var_view!(read_var_view_u8, read_var_u8, u8);
+    impl_read_typed_var_view!(read_var_view_i16, read_var_i16, i16);
+    impl_read_typed_var_view!(read_var_view_i32, read_var_i32, i32);
+    impl_read_typed_var_view!(read_var_view_f32, read_var_f32, f32);
+    impl_read_typed_var_view!(read_var_view_f64, read_var_f64, f64);
+
     /// Reads the typed records and returns its values into a typed`Vec`.
     pub fn read_record(&mut self, var_name: &str, record_index: usize) -> Result<DataVector, ReadError>
     {
@@ -476,21 +1083,25 @@ impl FileReader {
         }
 
         // Compute the record offset from the start of the NetCDF3 file
-        let var_info: &VariableParsedMetadata = self.find_var_info(var_name).ok_or(ReadError::Unexpected)?;
-        let record_offset: u64 = (i64::from(var_info.begin_offset.clone()) as u64) + ((record_index * self.data_set.record_size().unwrap_or(0)) as u64);
-        self.input_file.seek(SeekFrom::Start(record_offset))?;
+        let var_offsets: VarOffsets = self.find_var(var_name)?.1;
+        let record_offset: u64 = var_offsets.chunk_offset(record_index).ok_or_else(|| {
+            ReadError::RecordOffsetOverflow{var_name: String::from(var_name), record_index: record_index}
+        })?;
 
         // Read the data
+        self.check_alloc(var, var.chunk_len())?;
         let data_type: DataType = var.data_type();
         let mut data_vec: DataVector = DataVector::new(data_type, var.chunk_len());
+        let mut input: PositionedIo<R> = self.input_file.positioned(record_offset)?;
         match data_vec {
-            DataVector::I8(ref mut data) => self.input_file.read_i8_into(&mut data[..]),
-            DataVector::U8(ref mut data) => self.input_file.read_exact(&mut data[..]),
-            DataVector::I16(ref mut data) => self.input_file.read_i16_into::<BigEndian>(&mut data[..]),
-            DataVector::I32(ref mut data) => self.input_file.read_i32_into::<BigEndian>(&mut data[..]),
-            DataVector::F32(ref mut data) => self.input_file.read_f32_into::<BigEndian>(&mut data[..]),
-            DataVector::F64(ref mut data) => self.input_file.read_f64_into::<BigEndian>(&mut data[..]),
+            DataVector::I8(ref mut data) => input.read_i8_into(&mut data[..]),
+            DataVector::U8(ref mut data) => input.read_exact(&mut data[..]),
+            DataVector::I16(ref mut data) => input.read_i16_into::<BigEndian>(&mut data[..]),
+            DataVector::I32(ref mut data) => input.read_i32_into::<BigEndian>(&mut data[..]),
+            DataVector::F32(ref mut data) => input.read_f32_into::<BigEndian>(&mut data[..]),
+            DataVector::F64(ref mut data) => input.read_f64_into::<BigEndian>(&mut data[..]),
         }?;
+        self.input_file.counters().add_chunks_read(1);
         return Ok(data_vec);
     }
 
@@ -501,384 +1112,648 @@ impl FileReader {
     impl_read_typed_record!(read_record_f32, f32, DataType::F32, DataVector::F32);
     impl_read_typed_record!(read_record_f64, f64, DataType::F64, DataVector::F64);
 
-    /// Parses the NetCDF-3 header
-    fn parse_header(input: &[u8], total_file_size: usize) -> Result<(DataSet, Version, Vec<VariableParsedMetadata>), ReadError> {
-        // the magic word
-        let (input, _): (&[u8], &[u8]) = FileReader::parse_magic_word(input)?;
-        // the version number
-        let (input, version) : (&[u8], Version) = FileReader::parse_version(input)?;
-
-        // the number of records
-        let (input, num_records): (&[u8], Option<usize>) = FileReader::parse_as_usize_optional(input)?;
-        let (input, dims_list): (&[u8], Vec<(String, usize)>) = FileReader::parse_dims_list(input)?;
-        let (input, global_attrs_list): (&[u8], Vec<_>) = FileReader::parse_attrs_list(input)?;
-        let (_input, var_info_list): (&[u8], Vec<VariableParsedMetadata>) = FileReader::parse_vars_list(input, version.clone())?;
-
-        // Create a new dataset
-        let mut data_set = DataSet::new();
-        let (num_records, num_records_is_determinated): (usize, bool) = match num_records {
-            Some(num_records) => (num_records, true),
-            None => (0, false),
-        };
-
-        // Append it the dimensions
-        for (dim_name, dim_size) in dims_list.into_iter() {
-            if dim_size == 0 {
-                data_set.set_unlimited_dim(dim_name, num_records)?;
-            } else {
-                data_set.add_fixed_dim(dim_name, dim_size)?;
-            }
+    /// Reads the hyperslab `start[i]..(start[i] + count[i])` of the variable along each of its dimensions
+    /// (the number of records along the *unlimited-size* dimension), and returns its values in row-major order.
+    ///
+    /// Only the selected values are read, the elements contiguous along the last dimension being read at once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use netcdf3::{FileReader, ReadError};
+    /// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+    ///
+    /// let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+    /// // `temperature_f32` : (time: 2, latitude: 3, longitude: 5)
+    /// let data = file_reader.read_var_slice("temperature_f32", &[1, 0, 3], &[1, 2, 2]).unwrap();
+    /// assert_eq!(Some(&[18.0, 19.0, 23.0, 24.0][..]),     data.get_f32());
+    ///
+    /// assert_eq!(
+    ///     ReadError::SliceOutOfBounds{
+    ///         var_name: String::from("temperature_f32"),
+    ///         start: vec![2, 0, 0],
+    ///         count: vec![1, 3, 5],
+    ///         shape: vec![2, 3, 5],
+    ///     },
+    ///     file_reader.read_var_slice("temperature_f32", &[2, 0, 0], &[1, 3, 5]).unwrap_err()
+    /// );
+    /// ```
+    pub fn read_var_slice(&mut self, var_name: &str, start: &[usize], count: &[usize]) -> Result<DataVector, ReadError>
+    {
+        let (var, var_offsets): (&Variable, VarOffsets) = self.find_var(var_name)?;
+        let shape: Vec<usize> = var.dims().iter().map(|dim: &Rc<Dimension>| dim.size()).collect();
+        let slice_is_valid: bool = start.len() == shape.len() && count.len() == shape.len()
+            && shape.iter().zip(start.iter().zip(count.iter())).all(|(dim_size, (dim_start, dim_count)): (&usize, (&usize, &usize))| {
+                dim_start.checked_add(*dim_count).map(|dim_end: usize| dim_end <= *dim_size).unwrap_or(false)
+            });
+        if !slice_is_valid {
+            return Err(ReadError::SliceOutOfBounds{
+                var_name: String::from(var_name),
+                start: start.to_vec(),
+                count: count.to_vec(),
+                shape: shape,
+            });
         }
-
-        // Append ot the global attributes
-        for (attr_name, attr_data) in global_attrs_list.into_iter() {
-            use DataVector::*;
-            match attr_data {
-                I8(data) => {
-                    data_set.add_global_attr_i8(&attr_name, data)?;
-                }
-                U8(data) => {
-                    data_set.add_global_attr_u8(&attr_name, data)?;
-                }
-                I16(data) => {
-                    data_set.add_global_attr_i16(&attr_name, data)?;
-                }
-                I32(data) => {
-                    data_set.add_global_attr_i32(&attr_name, data)?;
-                }
-                F32(data) => {
-                    data_set.add_global_attr_f32(&attr_name, data)?
-                }
-                F64(data) => {
-                    data_set.add_global_attr_f64(&attr_name, data)?;
-                }
-            }
+        let slice_len: usize = count.iter().product();
+        self.check_alloc(var, slice_len)?;
+        let data_type: DataType = var.data_type();
+        let mut data_vec: DataVector = DataVector::new(data_type, slice_len);
+        if slice_len == 0 {
+            return Ok(data_vec);
         }
-
-        // Append the variables
-        let mut record_var_begin_offsets: Vec<Offset> = vec![];  // used to computed the number of records if necessaray
-        for var_info in var_info_list.iter() {
-            let dim_refs: Vec<Rc<Dimension>> = data_set.get_dims_from_dim_ids(&var_info.dim_ids)?;
-            // Create the variable the variable
-            let var: &Variable = data_set.add_var_using_dim_refs(&var_info.name, dim_refs, var_info.data_type.clone())?;
-            // Keep the `begin_offset` of the variable
-            if var.is_record_var() {
-                record_var_begin_offsets.push(var_info.begin_offset.clone());
-            }
-            // Append variable attributes
-            let var_name: String = var_info.name.clone();
-            for (attr_name, attr_data) in var_info.attrs_list.iter() {
-                use DataVector::*;
-                match attr_data {
-                    I8(data) => {
-                        data_set.add_var_attr_i8(&var_name, &attr_name, data.clone())?;
-                    }
-                    U8(data) => {
-                        data_set.add_var_attr_u8(&var_name, &attr_name, data.clone())?;
-                    }
-                    I16(data) => {
-                        data_set.add_var_attr_i16(&var_name, &attr_name, data.clone())?;
-                    }
-                    I32(data) => {
-                        data_set.add_var_attr_i32(&var_name, &attr_name, data.clone())?;
-                    }
-                    F32(data) => {
-                        data_set.add_var_attr_f32(&var_name, &attr_name, data.clone())?;
-                    }
-                    F64(data) => {
-                        data_set.add_var_attr_f64(&var_name, &attr_name, data.clone())?;
-                    }
+        // The chunk index is the index along the *unlimited-size* dimension for a record variable
+        let num_chunk_dims: usize = if var.is_record_var() { 1 } else { 0 };
+        let num_dims: usize = shape.len();
+        // The runs are contiguous along the last dimension, except along the *unlimited-size* dimension (the records are interleaved)
+        let num_run_dims: usize = if num_dims > num_chunk_dims { 1 } else { 0 };
+        let run_len: usize = if num_run_dims == 1 { count[num_dims - 1] } else { 1 };
+        let mut bytes: Vec<u8> = vec![0_u8; run_len * data_type.size_of()];
+        // Indices of the first element of the current run
+        let mut indices: Vec<usize> = start.to_vec();
+        for run_index in 0..(slice_len / run_len) {
+            let chunk_index: usize = if num_chunk_dims == 1 { indices[0] } else { 0 };
+            let chunk_offset: u64 = var_offsets.chunk_offset(chunk_index).ok_or_else(|| {
+                ReadError::RecordOffsetOverflow{var_name: String::from(var_name), record_index: chunk_index}
+            })?;
+            let element_index: usize = compute_flat_index(&shape[num_chunk_dims..], &indices[num_chunk_dims..]).ok_or(ReadError::Unexpected)?;
+            self.input_file.read_exact_at(&mut bytes[..], chunk_offset + (element_index * data_type.size_of()) as u64)?;
+            read_be_bytes_into(&bytes, &mut data_vec, run_index * run_len, (run_index + 1) * run_len);
+            // Move to the next run, the dimension of the runs being excluded
+            for dim_index in (0..(num_dims - num_run_dims)).rev() {
+                indices[dim_index] += 1;
+                if indices[dim_index] < start[dim_index] + count[dim_index] {
+                    break;
                 }
+                indices[dim_index] = start[dim_index];
             }
         }
+        return Ok(data_vec);
+    }
 
-        if !num_records_is_determinated {
-            // Case an *unlimited-size* dim s defined
-            if let Some(dim) = data_set.get_unlimited_dim() {
-                let num_records: usize;
-                // Case: the unlimited dim  is defined but no record variable is defined
-                if record_var_begin_offsets.is_empty() {
-                    num_records = 0;
-                }
-                else {
-                    // Computation of the number of records
-                    let first_begin_offset: usize = record_var_begin_offsets.into_iter().map(|begin_offset: Offset| i64::from(begin_offset) as usize).min().unwrap();
-                    let all_records_size: usize = total_file_size - first_begin_offset; // the size allocated for all record data
-                    let record_size: usize = data_set.record_size().ok_or(ReadError::Unexpected)?;
-                    if record_size == 0 {  // cannot be zero
-                        return Err(ReadError::Unexpected);
-                    }
-                    num_records = all_records_size.checked_div_euclid(record_size).ok_or(ReadError::Unexpected)?;
-                    let num_rem_bytes: usize = all_records_size.checked_rem_euclid(record_size).ok_or(ReadError::Unexpected)?;  // the number of remaining bytes
-                    if num_rem_bytes != 0 {
-                        return Err(ReadError::ComputationNumberOfRecords);
-                    }
-                }
-                match &dim.size {
-                    DimensionSize::Unlimited(dim_size) => {
-                        dim_size.replace(num_records);
-                    },
-                    _ => {},
-                }
-            }
+    /// Returns the byte ranges `(offset, length)` of the variable data stored in the file, without the padding bytes.
+    ///
+    /// A *fixed-size* variable is stored in one range, a record variable in one range per record.
+    ///
+    /// Returns `None` if the variable is not defined.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::FileReader;
+    /// # use copy_to_tmp_file::{
+    /// #     copy_bytes_to_tmp_file,
+    /// #     NC3_CLASSIC_FILE_NAME, NC3_CLASSIC_FILE_BYTES,
+    /// # };
+    /// # let (tmp_dir, input_file_path) = copy_bytes_to_tmp_file(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    ///
+    /// let file_reader: FileReader = FileReader::open(input_file_path).unwrap();
+    ///
+    /// // `latitude` : 3 `f32` values
+    /// let byte_ranges: Vec<(u64, usize)> = file_reader.var_byte_ranges("latitude").unwrap();
+    /// assert_eq!(1,           byte_ranges.len());
+    /// assert_eq!(12,          byte_ranges[0].1);
+    ///
+    /// // `temperature_f32` : 2 records of 15 `f32` values
+    /// let byte_ranges: Vec<(u64, usize)> = file_reader.var_byte_ranges("temperature_f32").unwrap();
+    /// assert_eq!(2,           byte_ranges.len());
+    /// assert_eq!(60,          byte_ranges[0].1);
+    ///
+    /// assert_eq!(None,        file_reader.var_byte_ranges("undef_var"));
+    /// ```
+    pub fn var_byte_ranges(&self, var_name: &str) -> Option<Vec<(u64, usize)>>
+    {
+        let (var, var_offsets): (&Variable, VarOffsets) = self.find_var(var_name).ok()?;
+        let chunk_length: usize = var.chunk_len() * var.data_type().size_of();
+        if !var.is_record_var() {
+            return Some(vec![(var_offsets.begin_offset, chunk_length)]);
         }
-        Ok((data_set, version, var_info_list))
+        let num_records: usize = self.data_set.num_records().unwrap_or(0);
+        return Some((0..num_records).map(|record_index: usize| {
+            (var_offsets.begin_offset + (record_index as u64) * var_offsets.stride, chunk_length)
+        }).collect());
     }
 
-    fn parse_magic_word(input: &[u8]) -> Result<(&[u8], &[u8]), ParseHeaderError>
+    /// Returns the offset of the first byte of the variable data (the offset of its first record for the record variables).
+    ///
+    /// Returns `None` if the variable is not defined.
+    pub fn var_begin_offset(&self, var_name: &str) -> Option<u64>
     {
-        let (input, tag_value): (&[u8], &[u8]) = tag(&b"CDF"[..])(input).map_err(|err: NomError|{
-            ParseHeaderError::new(err, ParseHeaderErrorKind::MagicWord)
-        })?;
-        Ok((input, tag_value))
+        return self.find_var(var_name).ok().map(|(_var, var_offsets): (&Variable, VarOffsets)| var_offsets.begin_offset);
     }
 
-    fn parse_version(input: &[u8]) -> Result<(&[u8], Version), ParseHeaderError>
+    /// Reads the bytes located at `offset` in the input into `buf`.
+    #[cfg(any(feature = "write", feature = "checksums"))]
+    pub(crate) fn read_bytes_at(&mut self, offset: u64, buf: &mut [u8]) -> Result<(), ReadError>
     {
-        let (input, version_number): (&[u8], u8) = verify(be_u8, |ver_num: &u8|{
-            ver_num == &(Version::Classic as u8) || ver_num == &(Version::Offset64Bit as u8)
-        })(input).map_err(|err: NomError|{
-            ParseHeaderError::new(err, ParseHeaderErrorKind::VersionNumber)
-        })?;
-        let version = Version::try_from(version_number).unwrap();  // previously checked
-        Ok((input, version))
+        self.input_file.read_exact_at(buf, offset)?;
+        return Ok(());
     }
 
-    /// Parses a `i32` word and checks that it is non-negative.
-    fn parse_non_neg_i32(input: &[u8]) -> Result<(&[u8], i32), ParseHeaderError> {
-        verify(be_i32, |number: &i32| *number >= 0_i32)(input).map_err(|err: NomError|{
-            ParseHeaderError::new(err, ParseHeaderErrorKind::NonNegativeI32)
-        })
+    /// Returns the `vsize` of the variable stored in the header, namely the number of bytes of each chunk including the padding bytes.
+    ///
+    /// The indeterminate value `4294967295` (2^32 - 1) is stored for the very large variables, whose chunk size exceeds 2^31 - 1 bytes.
+    /// The data are then located using the chunk size recomputed from the dimensions of the variable
+    /// (see [`var_chunk_size`](#method.var_chunk_size)).
+    ///
+    /// Returns `None` if the variable is not defined.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use netcdf3::FileReader;
+    /// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+    ///
+    /// let file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+    /// assert_eq!(Some(12),        file_reader.var_vsize("latitude"));
+    /// assert_eq!(Some(12),        file_reader.var_chunk_size("latitude"));
+    /// assert_eq!(Some(16),        file_reader.var_vsize("temperature_i8"));
+    /// assert_eq!(Some(16),        file_reader.var_chunk_size("temperature_i8"));
+    /// assert_eq!(None,            file_reader.var_vsize("undef_var"));
+    /// ```
+    pub fn var_vsize(&self, var_name: &str) -> Option<u32>
+    {
+        const INDETERMINATE_VSIZE: u32 = std::u32::MAX;
+        let var_info: &VariableParsedMetadata = self.find_var_info(var_name)?;
+        return Some(var_info.chunk_size.map(|chunk_size: usize| chunk_size as u32).unwrap_or(INDETERMINATE_VSIZE));
     }
 
-    /// Parses a non-negative `i32` word and converts it to a `usize`.
-    fn parse_as_usize(input: &[u8]) -> Result<(&[u8], usize), ParseHeaderError> {
-        let (input, number): (&[u8], i32) = FileReader::parse_non_neg_i32(input)?;
-        Ok((input, number as usize))
+    /// Returns the number of bytes of each chunk of the variable including the padding bytes, computed from its dimensions
+    /// and its data type (see [`Variable::chunk_size`](struct.Variable.html#method.chunk_size)).
+    ///
+    /// Unlike the [`vsize`](#method.var_vsize) stored in the header, the value is always determined, even for the very large variables.
+    ///
+    /// Returns `None` if the variable is not defined.
+    pub fn var_chunk_size(&self, var_name: &str) -> Option<usize>
+    {
+        return self.data_set.get_var(var_name).map(|var: &Variable| var.chunk_size());
     }
 
-    /// Parses the number of records
+    /// Returns the layout of the data of each variable, in the order of the header.
+    pub(crate) fn vars_layout(&self) -> Result<Vec<VarLayout>, ReadError>
+    {
+        return self.data_set.vars().iter().map(|var: &Variable| {
+            Ok(VarLayout{
+                name: var.name().to_string(),
+                is_record_var: var.is_record_var(),
+                begin_offset: self.var_begin_offset(var.name()).ok_or(ReadError::Unexpected)?,
+                chunk_size: var.chunk_size(),
+                header_chunk_size: self.var_header_chunk_size(var.name()),
+            })
+        }).collect();
+    }
+
+    /// Returns the size of each chunk of the variable (number of bytes) stored in the header.
     ///
-    /// Returns :
-    /// - The numbers of records if it is a valid integer.
-    /// - `None` if the number of records is indeterminated
-    fn parse_as_usize_optional(input: &[u8]) -> Result<(&[u8], Option<usize>), ParseHeaderError> {
-        const INDETERMINATE_VALUE: u32 = std::u32::MAX;
-        let (input, value): (&[u8], u32) = verify(be_u32, |number: &u32| *number <= (std::i32::MAX as u32) || *number == INDETERMINATE_VALUE)(input).map_err(|err: NomError|{
-            ParseHeaderError::new(err, ParseHeaderErrorKind::NonNegativeI32)
-        })?;
-        let value: Option<usize> = match value {
-            INDETERMINATE_VALUE => None,
-            _ => Some(value as usize),
-        };
-        Ok((input, value))
+    /// Returns `None` if the variable is not defined or if its chunk size is too large to be stored in the header.
+    pub(crate) fn var_header_chunk_size(&self, var_name: &str) -> Option<usize>
+    {
+        return self.find_var_info(var_name)?.chunk_size;
     }
 
-    /// Parses a non-negative `i32` word and converts it to a `u32`.
-    fn parse_as_u32(input: &[u8]) -> Result<(&[u8], u32), ParseHeaderError> {
-        let (input, number): (&[u8], i32) = FileReader::parse_non_neg_i32(input)?;
-        Ok((input, number as u32))
+    /// Returns `true` if the number of records stored in the header is the *streaming* value `0xFFFFFFFF` (indeterminate number of records).
+    ///
+    /// In this case the number of records is deduced from the size of the file (also see
+    /// [`FileWriter::set_numrecs_streaming`](struct.FileWriter.html#method.set_numrecs_streaming), and
+    /// [`ReadOptions::allow_truncated_records`](struct.ReadOptions.html#structfield.allow_truncated_records) for the incomplete last record).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use netcdf3::FileReader;
+    /// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+    ///
+    /// let file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+    /// assert_eq!(false,           file_reader.numrecs_is_streaming());
+    ///
+    /// let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+    /// bytes[4..8].copy_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF]);
+    /// let file_reader = FileReader::from_reader(Cursor::new(bytes)).unwrap();
+    /// assert_eq!(true,            file_reader.numrecs_is_streaming());
+    /// assert_eq!(Some(2),         file_reader.data_set().num_records());
+    /// ```
+    pub fn numrecs_is_streaming(&self) -> bool
+    {
+        return self.numrecs_is_streaming;
     }
-    /// Parses a string
-    fn parse_name_string(input: &[u8]) -> Result<(&[u8], String), ParseHeaderError>
+
+    /// Returns the number of records, or `None` if no *unlimited-size* dimension is defined
+    /// (same as [`DataSet::num_records`](struct.DataSet.html#method.num_records)).
+    ///
+    /// The number of records deduced from the file size is returned if the header stores the *streaming* value
+    /// (see [`numrecs_is_streaming`](#method.numrecs_is_streaming)).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use netcdf3::FileReader;
+    /// # use copy_to_tmp_file::{NC3_CLASSIC_FILE_BYTES, EMPTY_DATA_SET_FILE_BYTES};
+    ///
+    /// let file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+    /// assert_eq!(Some(2),                         file_reader.num_records());
+    /// assert_eq!(Some(String::from("time")),      file_reader.unlimited_dim_name());
+    /// assert_eq!(true,                            file_reader.has_record_vars());
+    ///
+    /// let file_reader = FileReader::from_reader(Cursor::new(EMPTY_DATA_SET_FILE_BYTES)).unwrap();
+    /// assert_eq!(None,                            file_reader.num_records());
+    /// assert_eq!(None,                            file_reader.unlimited_dim_name());
+    /// assert_eq!(false,                           file_reader.has_record_vars());
+    /// ```
+    pub fn num_records(&self) -> Option<usize>
     {
-        let (input, num_of_bytes): (&[u8], usize) = FileReader::parse_as_usize(input)?;
-        let (input, name): (&[u8], String) = map_res(take(num_of_bytes), |bytes: &[u8]| {
-            String::from_utf8(bytes.to_vec())
-        })(input).map_err(|err: NomError|{
-            ParseHeaderError::new(err, ParseHeaderErrorKind::Utf8)
-        })?;
-        // Take the zero padding bytes if necessary
-        let (input, _zero_padding_bytes): (&[u8], &[u8]) = FileReader::parse_zero_padding(input, compute_padding_size(num_of_bytes))?;
-        Ok((input, name))
+        return self.data_set.num_records();
     }
 
-    // Parses a NetCDF-3 data type.
-    fn parse_data_type(input: &[u8]) -> Result<(&[u8], DataType), ParseHeaderError>
+    /// Returns the name of the *unlimited-size* dimension, or `None` if it is not defined.
+    pub fn unlimited_dim_name(&self) -> Option<String>
     {
-        let start: &[u8] = input;
-        let (input, data_type_number): (&[u8], u32) = FileReader::parse_as_u32(input)?;
-        let data_type: DataType = DataType::try_from(data_type_number).map_err(|_err|{
-            nom::Err::Error((&start[0..4], nom::error::ErrorKind::Verify))
-        }).map_err(|err: NomError|{
-            ParseHeaderError::new(err, ParseHeaderErrorKind::DataType)
-        })?;
-        Ok((input, data_type))
+        return self.data_set.unlimited_dim.as_ref().map(|dim: &Rc<Dimension>| dim.name());
     }
 
-    fn parse_typed_data_elements(input: &[u8], num_of_elements: usize, data_type: DataType) -> Result<(&[u8], DataVector), ParseHeaderError>
+    /// Returns `true` if at least one record variable is defined.
+    pub fn has_record_vars(&self) -> bool
     {
-        // Parsed the useful data
-        let (input, data_vector): (&[u8], DataVector) = match data_type {
-            DataType::I8 => many_m_n(num_of_elements, num_of_elements, be_i8)(input).map(|(input, data): (&[u8], Vec<i8>)| (input, DataVector::I8(data))),
-            DataType::U8 => many_m_n(num_of_elements, num_of_elements, be_u8)(input).map(|(input, data): (&[u8], Vec<u8>)| (input, DataVector::U8(data))),
-            DataType::I16 => many_m_n(num_of_elements, num_of_elements, be_i16)(input).map(|(input, data): (&[u8], Vec<i16>)| (input, DataVector::I16(data))),
-            DataType::I32 => many_m_n(num_of_elements, num_of_elements, be_i32)(input).map(|(input, data): (&[u8], Vec<i32>)| (input, DataVector::I32(data))),
-            DataType::F32 => many_m_n(num_of_elements, num_of_elements, be_f32)(input).map(|(input, data): (&[u8], Vec<f32>)| (input, DataVector::F32(data))),
-            DataType::F64 => many_m_n(num_of_elements, num_of_elements, be_f64)(input).map(|(input, data): (&[u8], Vec<f64>)| (input, DataVector::F64(data))),
-        }.map_err(|err: NomError|{
-            ParseHeaderError::new(err, ParseHeaderErrorKind::DataElements)
-        })?;
+        return self.data_set.vars().iter().any(|var: &Variable| var.is_record_var());
+    }
 
-        // Parse the zero padding bytes if necessary
-        let num_of_bytes: usize = data_type.size_of() * num_of_elements;
-        let (input, _zero_padding_bytes): (&[u8], &[u8]) = FileReader::parse_zero_padding(input, compute_padding_size(num_of_bytes))?;
-        Ok((input, data_vector))
+    /// Returns the maximum number of bytes allocated to read the data of a variable (see [`set_max_alloc_bytes`](#method.set_max_alloc_bytes)).
+    pub fn max_alloc_bytes(&self) -> Option<usize>
+    {
+        return self.max_alloc_bytes;
     }
 
-    fn parse_zero_padding(input: &[u8], num_bytes: usize) -> Result<(&[u8], &[u8]), ParseHeaderError>
+    /// Sets the maximum number of bytes allocated to read the data of a variable, `None` for no limit.
+    ///
+    /// The methods reading the variables and the records fail with the error
+    /// [`ReadError::MaximumAllocationExceeded`](error/enum.ReadError.html#variant.MaximumAllocationExceeded) instead of
+    /// allocating more memory, [`read_all_vars`](#method.read_all_vars) checks all the variables before reading any of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use netcdf3::{FileReader, ReadError};
+    /// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+    ///
+    /// let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+    /// file_reader.set_max_alloc_bytes(Some(100));
+    /// assert_eq!(Some(100),       file_reader.max_alloc_bytes());
+    ///
+    /// // 3 `f32` latitudes
+    /// assert_eq!(vec![0.0, 0.5, 1.0],     file_reader.read_var_f32("latitude").unwrap());
+    /// // 2 records of 15 `f64` values
+    /// assert_eq!(
+    ///     ReadError::MaximumAllocationExceeded{var_name: String::from("temperature_f64"), max: 100, get: 240},
+    ///     file_reader.read_var_f64("temperature_f64").unwrap_err()
+    /// );
+    /// ```
+    pub fn set_max_alloc_bytes(&mut self, max_alloc_bytes: Option<usize>)
     {
-        verify(take(num_bytes), |padding_bytes: &[u8]| {
-            padding_bytes.iter().all(|byte: &u8| {
-                *byte == 0_u8
-            })
-        })(input).map_err(|err: NomError|{
-            ParseHeaderError::new(err, ParseHeaderErrorKind::ZeroPadding)
-        })
+        self.max_alloc_bytes = max_alloc_bytes;
     }
 
-    // Parses the list of the dimensions from the header.
-    fn parse_dims_list(input: &[u8]) -> Result<(&[u8], Vec<(String, usize)>), ParseHeaderError>
+    /// Returns the maximum number of bytes read at once from the input (see [`ReadOptions::buffer_size`](struct.ReadOptions.html#structfield.buffer_size)).
+    pub fn buffer_size(&self) -> usize
     {
-        fn parse_dim(input: &[u8]) -> Result<(&[u8], (String, usize)), ParseHeaderError>
-        {
-            let (input, dim_name): (&[u8], String) = FileReader::parse_name_string(input)?;
-            let (input, dim_size): (&[u8], usize) = FileReader::parse_as_usize(input)?;
-            Ok((input, (dim_name, dim_size)))
-        }
-        let (input, dim_tag): (&[u8], &[u8]) = alt((tag(ABSENT_TAG), tag(DIMENSION_TAG)))(input).map_err(|err: NomError|{
-            ParseHeaderError::new(err, ParseHeaderErrorKind::DimTag)
-        })?;
-        if dim_tag == &ABSENT_TAG {
-            return Ok((input, vec![]));
-        }
-        let (mut input, num_of_dims): (&[u8], usize) = FileReader::parse_as_usize(input)?;
-        let mut dims_list: Vec<(String, usize)> = Vec::with_capacity(num_of_dims);
-        for _ in 0..num_of_dims{
-            let (rem_input, dim): (&[u8], (String, usize)) = parse_dim(input)?;
-            input = rem_input;
-            dims_list.push(dim);
-        }
+        return self.buffer_size;
+    }
 
-        Ok((input, dims_list))
+    /// Sets the maximum number of bytes read at once from the input, at least 1 byte.
+    pub fn set_buffer_size(&mut self, buffer_size: usize)
+    {
+        self.buffer_size = std::cmp::max(buffer_size, 1);
     }
 
-    // Parses a list of attributes (global of from any variables) from the header.
-    fn parse_attrs_list(input: &[u8]) -> Result<(&[u8], Vec<(String, DataVector)>), ParseHeaderError>
+    /// Checks that the `num_elements` elements of the variable can be allocated.
+    fn check_alloc(&self, var: &Variable, num_elements: usize) -> Result<(), ReadError>
     {
-        fn parse_attr(input: &[u8]) -> Result<(&[u8], (String, DataVector)), ParseHeaderError>
-        {
-            let (input, attr_name): (&[u8], String) = FileReader::parse_name_string(input)?;
-            let (input, attr_data_type): (&[u8], DataType) = FileReader::parse_data_type(input)?;
-            let (input, num_of_elements): (&[u8], usize) = FileReader::parse_as_usize(input)?;
-            let (input, attr_data): (&[u8], DataVector) = FileReader::parse_typed_data_elements(input, num_of_elements, attr_data_type)?;
-            Ok((input, (attr_name, attr_data)))
-        }
-        let (input, attr_tag): (&[u8], &[u8]) = alt((tag(ABSENT_TAG), tag(ATTRIBUTE_TAG)))(input).map_err(|err: NomError|{
-            ParseHeaderError::new(err, ParseHeaderErrorKind::AttrTag)
-        })?;
-        if attr_tag == &ABSENT_TAG {
-            return Ok((input, vec![]));
+        let num_bytes: usize = num_elements.saturating_mul(var.data_type().size_of());
+        if let Some(max_alloc_bytes) = self.max_alloc_bytes {
+            if num_bytes > max_alloc_bytes {
+                return Err(ReadError::MaximumAllocationExceeded{var_name: var.name().to_string(), max: max_alloc_bytes, get: num_bytes});
+            }
         }
-        let (mut input, num_of_attrs): (&[u8], usize) = FileReader::parse_as_usize(input)?;
-        let mut attrs_list: Vec<(String, DataVector)> = Vec::with_capacity(num_of_attrs);
-        for _ in 0..num_of_attrs
-        {
-            let (rem_input, attr): (&[u8], (String, DataVector)) = parse_attr(input)?;
-            input = rem_input;
-            attrs_list.push(attr);
+        // The data read cannot be larger than the input file, whatever the dimensions declared in the header
+        if num_bytes > self.input_file_size {
+            return Err(ReadError::VariableDataOutOfFile{
+                var_name: var.name().to_string(),
+                end_offset: num_bytes as u64,
+                file_size: self.input_file_size as u64,
+            });
         }
-        Ok((input, attrs_list))
+        return Ok(());
     }
 
-    // Parses a list of variables from the header.
-    fn parse_vars_list(input: &[u8], version: Version) -> Result<(&[u8], Vec<VariableParsedMetadata>), ParseHeaderError>
+    /// Checks that all the chunks of the variable end before the end of the input file, before allocating the memory to read them.
+    fn check_var_in_file(&self, var_name: &str) -> Result<(), ReadError>
     {
-        fn parse_dim_ids_list(input: &[u8]) -> Result<(&[u8], Vec<usize>), ParseHeaderError>
-        {
-                // number of dimensions
-                let (mut input, num_of_dims): (&[u8], usize) = FileReader::parse_as_usize(input)?;
-                // list of the dimension ids
-                let mut dim_ids_list: Vec<usize> = Vec::with_capacity(num_of_dims);
-                for _ in 0..num_of_dims {
-                    let(rem_input, dim_id): (&[u8], usize) = FileReader::parse_as_usize(input)?;
-                    input = rem_input;
-                    dim_ids_list.push(dim_id);
-                }
-                Ok((input, dim_ids_list))
+        let (var, var_offsets): (&Variable, VarOffsets) = self.find_var(var_name)?;
+        let chunk_size: u64 = (var.chunk_len() as u64).saturating_mul(var.data_type().size_of() as u64);
+        let num_chunks: u64 = if var.is_record_var() { self.data_set.num_records().unwrap_or(0) as u64 } else { 1 };
+        if chunk_size == 0 || num_chunks == 0 {
+            return Ok(());
+        }
+        let end_offset: u64 = var_offsets.begin_offset
+            .saturating_add((num_chunks - 1).saturating_mul(var_offsets.stride))
+            .saturating_add(chunk_size);
+        if end_offset > self.input_file_size as u64 {
+            return Err(ReadError::VariableDataOutOfFile{
+                var_name: String::from(var_name),
+                end_offset: end_offset,
+                file_size: self.input_file_size as u64,
+            });
         }
+        return Ok(());
+    }
 
-        fn parse_offset(input: &[u8], version: Version) -> Result<(&[u8], Offset), ParseHeaderError>
-        {
-            match version {
-                Version::Classic => {
-                    be_i32(input).map(|(input, num_of_bytes): (&[u8], i32)| {
-                        (input, Offset::I32(num_of_bytes))
-                    })
-                },
-                Version::Offset64Bit => {
-                    be_i64(input).map(|(input, num_of_bytes): (&[u8], i64)| {
-                        (input, Offset::I64(num_of_bytes))
-                    })
+    /// Returns the size (number of bytes) of the input file.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use netcdf3::FileReader;
+    /// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+    ///
+    /// let file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+    /// assert_eq!(NC3_CLASSIC_FILE_BYTES.len(),    file_reader.file_size());
+    /// ```
+    pub fn file_size(&self) -> usize
+    {
+        return self.input_file_size;
+    }
+
+    /// Returns the size (number of bytes) of the header, including the bytes reserved after it.
+    ///
+    /// The data of the variables start at this offset, equal to the [data section offset](#method.data_section_offset)
+    /// (the whole file is returned if no variable is defined). See [`header_used_size`](#method.header_used_size) for the bytes
+    /// used by the header.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::FileReader;
+    /// # use copy_to_tmp_file::{
+    /// #     copy_bytes_to_tmp_file,
+    /// #     NC3_CLASSIC_FILE_NAME, NC3_CLASSIC_FILE_BYTES,
+    /// # };
+    /// # let (tmp_dir, input_file_path) = copy_bytes_to_tmp_file(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    ///
+    /// let file_reader: FileReader = FileReader::open(input_file_path).unwrap();
+    /// assert_eq!(1684,                file_reader.header_size());
+    /// assert_eq!(Some(1684),          file_reader.var_begin_offset("latitude"));
+    /// ```
+    pub fn header_size(&self) -> usize
+    {
+        return self.data_section_offset();
+    }
+
+    /// Returns the number of bytes used by the header, without the bytes reserved after it.
+    ///
+    /// The bytes between the end of the header and the [data section offset](#method.data_section_offset)
+    /// are reserved for the header growth, and allow to rewrite a larger header without moving the data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use netcdf3::{FileReader, FileWriter, DataSet, DataType, Version};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.add_fixed_dim("x", 3).unwrap();
+    /// data_set.add_var_f32("x", &["x"]).unwrap();
+    ///
+    /// // reserve bytes after the header, up to 256 bytes
+    /// let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+    /// file_writer.set_def(&data_set, Version::Classic, 256).unwrap();
+    /// file_writer.write_var_f32("x", &[0.0, 1.0, 2.0]).unwrap();
+    ///
+    /// let file_reader = FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+    /// assert_eq!(80,                  file_reader.header_used_size());
+    /// assert_eq!(256,                 file_reader.header_size());
+    /// assert_eq!(268,                 file_reader.file_size());
+    /// ```
+    pub fn header_used_size(&self) -> usize
+    {
+        return self.header_used_size;
+    }
+
+    /// Returns the offset of the data section, i.e. the size of the header including the bytes reserved after it.
+    ///
+    /// The data of the variables start at this offset (the whole file is returned if no variable is defined).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use netcdf3::FileReader;
+    /// # use copy_to_tmp_file::{
+    /// #     copy_bytes_to_tmp_file,
+    /// #     NC3_CLASSIC_FILE_NAME, NC3_CLASSIC_FILE_BYTES,
+    /// # };
+    /// # let (tmp_dir, input_file_path) = copy_bytes_to_tmp_file(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    ///
+    /// let file_reader: FileReader = FileReader::open(input_file_path).unwrap();
+    /// assert_eq!(1684,                file_reader.data_section_offset());
+    /// assert_eq!(Some(1684),          file_reader.var_begin_offset("latitude"));
+    /// assert_eq!(Some(1716),          file_reader.var_begin_offset("time"));
+    /// assert_eq!(Some(308),           file_reader.data_set().record_size());
+    /// ```
+    pub fn data_section_offset(&self) -> usize
+    {
+        let first_begin_offset: Option<u64> = self.vars_info.iter().map(|var_info: &VariableParsedMetadata| {
+            i64::from(var_info.begin_offset.clone()) as u64
+        }).min();
+        return match first_begin_offset {
+            Some(first_begin_offset) => first_begin_offset as usize,
+            None => self.input_file_size,
+        };
+    }
+
+    /// Returns a JSON manifest listing the byte ranges of all the variables (see [`var_byte_ranges`](#method.var_byte_ranges)).
+    ///
+    /// It allows other tools to read the variable data through direct range reads, without parsing the header again :
+    ///
+    /// ```text
+    /// {
+    ///   "version": "classic",
+    ///   "byte_order": "big",
+    ///   "num_records": 2,
+    ///   "variables": {
+    ///     "latitude": {
+    ///       "data_type": "f32",
+    ///       "shape": [3],
+    ///       "dim_names": ["latitude"],
+    ///       "chunks": [[1684, 12]]
+    ///     },
+    ///     ...
+    ///   }
+    /// }
+    /// ```
+    pub fn byte_ranges_manifest(&self) -> String
+    {
+        let var_byte_ranges: HashMap<String, Vec<(u64, usize)>> = self.data_set.get_var_names().into_iter().filter_map(|var_name: String| {
+            let byte_ranges: Vec<(u64, usize)> = self.var_byte_ranges(&var_name)?;
+            Some((var_name, byte_ranges))
+        }).collect();
+        return byte_ranges_manifest_json(&self.data_set, self.version, &var_byte_ranges);
+    }
+
+    fn find_var_info(&self, var_name: &str) -> Option<&VariableParsedMetadata> {
+        let (var_index, _var): (usize, &Variable) = self.data_set.find_var_from_name(var_name).ok()?;
+        return self.vars_info.get(var_index);
+    }
+
+    /// Returns the variable and the offsets of its data, without scanning the list of the variables.
+    fn find_var(&self, var_name: &str) -> Result<(&Variable, VarOffsets), ReadError> {
+        let (var_index, var): (usize, &Variable) = self.data_set.find_var_from_name(var_name).map_err(|_err|{
+            ReadError::VariableNotDefined(String::from(var_name))
+        })?;
+        let var_offsets: VarOffsets = *self.var_offsets.get(var_index).ok_or(ReadError::Unexpected)?;
+        return Ok((var, var_offsets));
+    }
+}
+
+/// Converts the big-endian bytes into the elements `start..end` of `data_vec`.
+fn read_be_bytes_into(bytes: &[u8], data_vec: &mut DataVector, start: usize, end: usize)
+{
+    match data_vec {
+        DataVector::I8(data) => {
+            data[start..end].iter_mut().zip(bytes.iter()).for_each(|(value, byte): (&mut i8, &u8)| *value = *byte as i8);
+        },
+        DataVector::U8(data) => data[start..end].copy_from_slice(bytes),
+        DataVector::I16(data) => BigEndian::read_i16_into(bytes, &mut data[start..end]),
+        DataVector::I32(data) => BigEndian::read_i32_into(bytes, &mut data[start..end]),
+        DataVector::F32(data) => BigEndian::read_f32_into(bytes, &mut data[start..end]),
+        DataVector::F64(data) => BigEndian::read_f64_into(bytes, &mut data[start..end]),
+    }
+}
+
+/// Reads exactly `buf.len()` bytes located at `offset` in the file, through a shared reference.
+///
+/// A single positional read is done on Unix and Windows (the cursor of the file is not moved on Unix), instead of a seek followed by a read.
+/// On the other targets, the seeks and the reads are serialized by a lock, the cursor of the file being shared.
+#[cfg(feature = "fs")]
+fn read_exact_at(file: &std::fs::File, buf: &mut [u8], offset: u64) -> std::io::Result<()>
+{
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileExt;
+        return file.read_exact_at(buf, offset);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::FileExt;
+        let mut buf: &mut [u8] = buf;
+        let mut offset: u64 = offset;
+        while !buf.is_empty() {
+            match file.seek_read(buf, offset) {
+                Ok(0) => return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof)),
+                Ok(num_bytes) => {
+                    buf = &mut buf[num_bytes..];
+                    offset += num_bytes as u64;
                 },
-            }.map_err(|err: NomError| {
-                ParseHeaderError::new(err, ParseHeaderErrorKind::Offset)
-            })
+                Err(ref err) if err.kind() == std::io::ErrorKind::Interrupted => {},
+                Err(err) => return Err(err),
+            }
         }
+        return Ok(());
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        // The cursor of the file is shared by the threads, each seek and the following read are done at once
+        static SEEK_READ_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+        let _guard = SEEK_READ_LOCK.lock().unwrap_or_else(|err: std::sync::PoisonError<std::sync::MutexGuard<()>>| err.into_inner());
+        let mut file: &std::fs::File = file;
+        file.seek(SeekFrom::Start(offset))?;
+        return file.read_exact(buf);
+    }
+}
 
-        fn parse_var(input: &[u8], version: Version) -> Result<(&[u8], VariableParsedMetadata), ParseHeaderError> {
-            // Variable name
-            let (input, var_name): (&[u8], String) = FileReader::parse_name_string(input)?;
-
-            // list of the dimensions
-            let (input, dim_ids): (&[u8], Vec<usize>) = parse_dim_ids_list(input)?;
-            // list of the variable attributes
-            let (input, attrs_list): (&[u8], Vec<(String, DataVector)>) = FileReader::parse_attrs_list(input)?;
-            // data type of the variable
-            let (input, data_type): (& [u8], DataType) = FileReader::parse_data_type(input)?;
-            // size occupied in each record by the variable (number of bytes)
-            let (input, chunk_size): (&[u8], Option<usize>) = FileReader::parse_as_usize_optional(input)?;
-            // begin offset (number of bytes)
-            let (input, begin_offset): (&[u8], Offset) = parse_offset(input, version)?;
-            let var_def = VariableParsedMetadata {
-                name: var_name,
-                dim_ids: dim_ids,
-                attrs_list: attrs_list,
-                data_type: data_type,
-                _chunk_size: chunk_size,
-                begin_offset: begin_offset,
-            };
-            return Ok((input, var_def));
+/// Reads the byte ranges `(offset, length)` of the file one after the other into `bytes`.
+#[cfg(feature = "fs")]
+fn read_ranges_at(file: &std::fs::File, byte_ranges: &[(u64, usize)], bytes: &mut [u8]) -> std::io::Result<()>
+{
+    let mut start: usize = 0;
+    for (offset, length) in byte_ranges.iter() {
+        read_exact_at(file, &mut bytes[start..(start + length)], *offset)?;
+        start += length;
+    }
+    return Ok(());
+}
+
+/// Loads the bytes of the header from the input, the number of records stored in it being replaced if necessary.
+struct HeaderLoader<'a, R: Read> {
+    input: &'a mut MeteredIo<R>,
+    /// Minimum number of bytes read at once.
+    buffer_size: usize,
+    file_size: usize,
+    /// Number of records replacing the one stored in the header, if defined.
+    num_records: Option<usize>,
+    allow_truncated_records: bool,
+    /// `true` if the header stores the number of records of the *streaming* files.
+    numrecs_is_streaming: bool,
+    /// I/O error occured while loading, no more bytes are loaded afterwards.
+    error: Option<std::io::Error>,
+}
+
+impl<'a, R: Read> LoadBytes for HeaderLoader<'a, R> {
+    fn load(&mut self, bytes: &mut Vec<u8>, num_bytes: usize) {
+        const NUM_RECORDS_OFFSET: usize = 4;
+        const NUM_RECORDS_SIZE: usize = 4;
+        const STREAMING_NUM_RECORDS: u32 = std::u32::MAX;
+        if self.error.is_some() {
+            return;
         }
-        let (input, var_tag): (&[u8], &[u8]) = alt((tag(ABSENT_TAG), tag(VARIABLE_TAG)))(input).map_err(|err: NomError| {
-            ParseHeaderError::new(err, ParseHeaderErrorKind::VarTag)
-        })?;
-        if var_tag == &ABSENT_TAG {
-            return Ok((input, vec![]));
+        let start: usize = bytes.len();
+        let end: usize = std::cmp::min(start + std::cmp::max(self.buffer_size, num_bytes), self.file_size);
+        bytes.resize(end, 0_u8);
+        if let Err(err) = self.input.read_exact(&mut bytes[start..end]) {
+            bytes.truncate(start);
+            self.error = Some(err);
+            return;
         }
-        let (mut input, num_of_vars): (&[u8], usize) = FileReader::parse_as_usize(input)?;
-        let mut vars_list: Vec<VariableParsedMetadata> = vec![];
-        for _ in 0..num_of_vars {
-            let (temp_input, var) = parse_var(input, version.clone())?;
-            input = temp_input;
-            vars_list.push(var);
+        if start < NUM_RECORDS_OFFSET + NUM_RECORDS_SIZE && end >= NUM_RECORDS_OFFSET + NUM_RECORDS_SIZE {
+            self.numrecs_is_streaming = bytes[NUM_RECORDS_OFFSET..(NUM_RECORDS_OFFSET + NUM_RECORDS_SIZE)] == STREAMING_NUM_RECORDS.to_be_bytes();
+            // the number of records of the truncated *streaming* files is computed after the parsing
+            let num_records: Option<usize> = match self.num_records {
+                None if self.numrecs_is_streaming && self.allow_truncated_records => Some(0),
+                _ => self.num_records,
+            };
+            if let Some(num_records) = num_records {
+                bytes[NUM_RECORDS_OFFSET..(NUM_RECORDS_OFFSET + NUM_RECORDS_SIZE)].copy_from_slice(&(num_records as u32).to_be_bytes());
+            }
         }
-        Ok((input, vars_list))
     }
+}
 
-    fn find_var_info(&self, var_name: &str) -> Option<&VariableParsedMetadata> {
-        self.vars_info.iter().find(|var_info| var_info.name == var_name)
-    }
+/// Location of the data of a variable, computed once after the parsing of the header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct VarOffsets {
+    /// Offset of the first chunk
+    begin_offset: u64,
+    /// Number of bytes between the starts of two consecutive chunks (the record size for a record variable, `0` otherwise)
+    stride: u64,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-struct VariableParsedMetadata {
-    name: String,
-    dim_ids: Vec<usize>,
-    attrs_list: Vec<(String, DataVector)>,
-    data_type: DataType,
-    _chunk_size: Option<usize>,
-    begin_offset: Offset,
+impl VarOffsets {
+    /// Returns the offsets of the data of each variable, the variables of `data_set` being defined by `vars_info`.
+    fn compute_all(data_set: &DataSet, vars_info: &[VariableParsedMetadata]) -> Vec<VarOffsets> {
+        let record_size: u64 = data_set.record_size().unwrap_or(0) as u64;
+        return data_set.vars().iter().zip(vars_info.iter()).map(|(var, var_info): (&Variable, &VariableParsedMetadata)| {
+            VarOffsets{
+                begin_offset: i64::from(var_info.begin_offset.clone()) as u64,
+                stride: if var.is_record_var() { record_size } else { 0 },
+            }
+        }).collect();
+    }
+
+    /// Returns the offset of a chunk, or `None` if it overflows `u64`.
+    fn chunk_offset(&self, chunk_index: usize) -> Option<u64> {
+        return compute_record_offset(self.begin_offset, chunk_index, self.stride as usize);
+    }
 }
 
 
@@ -1,17 +1,24 @@
-#![cfg(test)]
+#![cfg(all(test, feature = "write"))]
+use std::collections::HashMap;
 use std::num::NonZeroUsize;
 use byteorder::{WriteBytesExt, BigEndian};
 
+use crate::io::header::{self, HeaderParseState, NameDecoder};
+use crate::io::header_decoder::HeaderDecoder;
 use crate::{
-    FileReader, Variable, DataSet, Attribute, Dimension, DataType, DimensionType, Version,
+    DuplicateAttrPolicy, DuplicateDimPolicy, FileReader, InvalidDataSet, NameDecoding, NameError, NameValidation, PaddingCheck, ReadOptions, Variable, DataSet, Attribute, Dimension, DataType, DataVector, DimensionType, Version,
+    Diagnostic, IoMetrics, LayoutViolation, VariableView,
     error::ReadError,
-    error::parse_header_error::{ParseHeaderError, ParseHeaderErrorKind, InvalidBytes},
+    error::parse_header_error::{ParseHeaderError, ParseHeaderErrorKind, InvalidBytes, Needed},
     io::compute_padding_size,
 };
 
 use copy_to_tmp_file::{
     copy_bytes_to_tmp_file,
     NC3_CLASSIC_FILE_NAME, NC3_CLASSIC_FILE_BYTES,
+    NC3_64BIT_OFFSET_FILE_BYTES,
+    NC3_FILL_VALUES_FILE_BYTES,
+    NC3_CONTAINING_DEFAULT_FILL_VALUES_FILE_BYTES,
     SCALAR_VARIABLES_FILE_NAME, SCALAR_VARIABLES_FILE_BYTES,
     EMPTY_DATA_SET_FILE_NAME, EMPTY_DATA_SET_FILE_BYTES,
     NC3_ZERO_SIZED_UNLIMITED_DIM_FILE_NAME, NC3_ZERO_SIZED_UNLIMITED_DIM_FILE_BYTES,
@@ -268,6 +275,58 @@ fn test_file_reader_read_var_f32() {
     assert_eq!(Some(DataType::F32),             data_set.var_data_type(TEMP_F32_VAR_NAME));
 }
 
+#[test]
+fn test_file_reader_read_var_view_f32() {
+    let mut file_reader = FileReader::from_reader(std::io::Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+
+    // shape: time x latitude x longitude
+    let view: VariableView<f32> = file_reader.read_var_view_f32(TEMP_F32_VAR_NAME).unwrap();
+    assert_eq!(&[2, 3, 5],                          view.shape());
+    assert_eq!(&TEMP_F32_VAR_DATA[..],              view.as_slice());
+    assert_eq!(22.0,                                view[[1, 1, 2]]);
+    assert_eq!(Some(&[25., 26., 27., 28., 29.][..]),    view.rows().last());
+    assert_eq!(2,                                   view.subarrays().count());
+
+    assert_eq!(
+        ReadError::VariableMismatchDataType{var_name: String::from(TEMP_I8_VAR_NAME), req: DataType::I8, get: DataType::F32},
+        file_reader.read_var_view_f32(TEMP_I8_VAR_NAME).unwrap_err()
+    );
+    assert_eq!(
+        ReadError::VariableNotDefined(String::from("undef_var")),
+        file_reader.read_var_view_f32("undef_var").unwrap_err()
+    );
+}
+
+#[test]
+fn test_file_reader_load_vars() {
+    let mut file_reader = FileReader::from_reader(std::io::Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+    let data_set: &DataSet = file_reader.load_vars(&[TEMP_I8_VAR_NAME, TEMP_F64_VAR_NAME, TEMP_I8_VAR_NAME]).unwrap();
+    assert_eq!(2,                                   data_set.vars().iter().filter(|var: &&Variable| var.data().is_some()).count());
+    assert_eq!(Some(&TEMP_I8_VAR_DATA[..]),         data_set[TEMP_I8_VAR_NAME].data().and_then(DataVector::get_i8));
+    assert_eq!(Some(&TEMP_F64_VAR_DATA[..]),        data_set[TEMP_F64_VAR_NAME].data().and_then(DataVector::get_f64));
+    assert_eq!(None,                                data_set[TEMP_F32_VAR_NAME].data());
+    file_reader.load_vars(&[]).unwrap();
+
+    // The loaded values are kept by the data set, and dropped when the data type changes
+    let data: HashMap<String, DataVector> = file_reader.read_all_vars().unwrap();
+    let mut data_set: DataSet = file_reader.close().0;
+    assert_eq!(Some(&data[TEMP_I8_VAR_NAME]),       data_set[TEMP_I8_VAR_NAME].data());
+    data_set.set_var_data_type(TEMP_I8_VAR_NAME, DataType::I16).unwrap();
+    assert_eq!(None,                                data_set[TEMP_I8_VAR_NAME].data());
+
+    // All the variables are checked before reading any of them
+    let options = ReadOptions::new().with_max_alloc_bytes(100);
+    let mut file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(NC3_CLASSIC_FILE_BYTES), &options).unwrap();
+    file_reader.reset_io_metrics();
+    assert!(matches!(file_reader.load_vars(&[TEMP_I8_VAR_NAME, TEMP_F64_VAR_NAME]), Err(ReadError::MaximumAllocationExceeded{..})));
+    assert_eq!(
+        Err(ReadError::VariableNotDefined(String::from("undef_var"))),
+        file_reader.load_vars(&[TEMP_I8_VAR_NAME, "undef_var"]).map(|_data_set: &DataSet| ())
+    );
+    assert_eq!(file_reader.io_metrics(),            IoMetrics::default());
+    assert_eq!(None,                                file_reader.data_set()[TEMP_I8_VAR_NAME].data());
+}
+
 #[test]
 fn test_file_reader_read_var_f64() {
     let (tmp_dir, input_data_file_path) = copy_bytes_to_tmp_file(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
@@ -316,7 +375,7 @@ fn test_file_reader_read_var_f64() {
 #[test]
 fn test_parse_header() {
     use std::rc::Rc;
-    use super::VariableParsedMetadata;
+    use crate::io::header::VariableParsedMetadata;
 
     const LATITUDE_DIM_NAME: &str = "latitude";
     const LATITUDE_VAR_NAME: &str = LATITUDE_DIM_NAME;
@@ -330,14 +389,15 @@ fn test_parse_header() {
     const TIME_VAR_NAME: &str = TIME_DIM_NAME;
     const TIME_VAR_LEN: usize = 2;
 
-    let num_of_bytes: usize = NC3_CLASSIC_FILE_BYTES.len();
-    let parsing_result: Result<(DataSet, Version, Vec<VariableParsedMetadata>), ReadError>;
-    parsing_result = FileReader::parse_header(NC3_CLASSIC_FILE_BYTES, num_of_bytes);
+    let parsing_result: Result<(DataSet, Version, Vec<VariableParsedMetadata>, usize), ReadError>;
+    parsing_result = header::parse_header(&mut HeaderDecoder::new(NC3_CLASSIC_FILE_BYTES), &mut HeaderParseState::new(NameDecoder::new(NameDecoding::Utf8, NameValidation::Strict)));
     assert_eq!(true,                        parsing_result.is_ok());
-    let (data_set, version, _vars_info) = parsing_result.unwrap();
+    let (data_set, version, _vars_info, header_end) = parsing_result.unwrap();
 
     // Check the version
     assert_eq!(Version::Classic,         version);
+    // Check the end of the header
+    assert_eq!(1_684,                    header_end);
 
     // Check the global-attributes
     {
@@ -568,9 +628,8 @@ fn test_parse_truncated_header()
     {
         // Copy truncated bytes to a temporary file
         let truncated_file_bytes: &[u8] = &b""[..];
-        let file_size: usize = truncated_file_bytes.len();
         // Open the NetCDF-3 file
-        let parsing_res: Result<(DataSet, Version, Vec<_>), ReadError> = FileReader::parse_header(truncated_file_bytes, file_size);
+        let parsing_res: Result<(DataSet, Version, Vec<_>, usize), ReadError> = header::parse_header(&mut HeaderDecoder::new(truncated_file_bytes), &mut HeaderParseState::new(NameDecoder::new(NameDecoding::Utf8, NameValidation::Strict)));
         assert_eq!(true,                parsing_res.is_err());
         let parsing_err: ReadError = parsing_res.unwrap_err();
         assert_eq!(true,                parsing_err.header_is_incomplete());
@@ -579,9 +638,8 @@ fn test_parse_truncated_header()
     {
         // Copy truncated bytes to a temporary file
         let truncated_file_bytes: &[u8] = &NC3_CLASSIC_FILE_BYTES[..1];
-        let file_size: usize = truncated_file_bytes.len();
         // Open the NetCDF-3 file
-        let parsing_res: Result<(DataSet, Version, Vec<_>), ReadError> = FileReader::parse_header(truncated_file_bytes, file_size);
+        let parsing_res: Result<(DataSet, Version, Vec<_>, usize), ReadError> = header::parse_header(&mut HeaderDecoder::new(truncated_file_bytes), &mut HeaderParseState::new(NameDecoder::new(NameDecoding::Utf8, NameValidation::Strict)));
         assert_eq!(true,                parsing_res.is_err());
         let parsing_err: ReadError = parsing_res.unwrap_err();
         assert_eq!(true,                parsing_err.header_is_incomplete());
@@ -590,9 +648,8 @@ fn test_parse_truncated_header()
     {
         // Copy truncated bytes to a temporary file
         let truncated_file_bytes: &[u8] = &NC3_CLASSIC_FILE_BYTES[..(HEADER_NUM_OF_BYTES - 1)];
-        let file_size: usize = truncated_file_bytes.len();
         // Open the NetCDF-3 file
-        let parsing_res: Result<(DataSet, Version, Vec<_>), ReadError> = FileReader::parse_header(truncated_file_bytes, file_size);
+        let parsing_res: Result<(DataSet, Version, Vec<_>, usize), ReadError> = header::parse_header(&mut HeaderDecoder::new(truncated_file_bytes), &mut HeaderParseState::new(NameDecoder::new(NameDecoding::Utf8, NameValidation::Strict)));
         assert_eq!(true,                parsing_res.is_err());
         let parsing_err: ReadError = parsing_res.unwrap_err();
         assert_eq!(true,                parsing_err.header_is_incomplete());
@@ -601,9 +658,8 @@ fn test_parse_truncated_header()
     {
         // Copy truncated bytes to a temporary file
         let truncated_file_bytes: &[u8] = &NC3_CLASSIC_FILE_BYTES[..(HEADER_NUM_OF_BYTES)];
-        let file_size: usize = truncated_file_bytes.len();
         // Open the NetCDF-3 file
-        let parsing_res: Result<(DataSet, Version, Vec<_>), ReadError> = FileReader::parse_header(truncated_file_bytes, file_size);
+        let parsing_res: Result<(DataSet, Version, Vec<_>, usize), ReadError> = header::parse_header(&mut HeaderDecoder::new(truncated_file_bytes), &mut HeaderParseState::new(NameDecoder::new(NameDecoding::Utf8, NameValidation::Strict)));
         assert_eq!(true,                parsing_res.is_ok());
     }
 }
@@ -615,7 +671,9 @@ fn test_parse_non_neg_i32() {
         let a: i32 = 0_i32;
         let bytes: [u8; 4] = a.to_be_bytes();
         // parse the integer
-        let (rem_bytes, b): (&[u8], i32) = FileReader::parse_non_neg_i32(&bytes[..]).unwrap();
+        let mut input = HeaderDecoder::new(&bytes[..]);
+        let b: i32 = header::parse_non_neg_i32(&mut input).unwrap();
+        let rem_bytes: &[u8] = input.remaining();
         // test remaining bytes and the parsed value
         assert_eq!(&[] as &[u8], rem_bytes);
         assert_eq!(0_i32, b);
@@ -626,7 +684,9 @@ fn test_parse_non_neg_i32() {
         let a: i32 = 1_i32;
         let bytes: [u8; 4] = a.to_be_bytes();
         // parse the integer
-        let (rem_bytes, b): (&[u8], i32) = FileReader::parse_non_neg_i32(&bytes[..]).unwrap();
+        let mut input = HeaderDecoder::new(&bytes[..]);
+        let b: i32 = header::parse_non_neg_i32(&mut input).unwrap();
+        let rem_bytes: &[u8] = input.remaining();
         // test remaining bytes and the parsed value
         assert_eq!(&[] as &[u8], rem_bytes);
         assert_eq!(1_i32, b);
@@ -637,7 +697,9 @@ fn test_parse_non_neg_i32() {
         let a: i32 = std::i32::MAX;
         let bytes: [u8; 4] = a.to_be_bytes();
         // parse the integer
-        let (rem_bytes, b): (&[u8], i32) = FileReader::parse_non_neg_i32(&bytes[..]).unwrap();
+        let mut input = HeaderDecoder::new(&bytes[..]);
+        let b: i32 = header::parse_non_neg_i32(&mut input).unwrap();
+        let rem_bytes: &[u8] = input.remaining();
         // test remaining bytes and the parsed value
         assert_eq!(&[] as &[u8], rem_bytes);
         assert_eq!(std::i32::MAX, b);
@@ -648,7 +710,7 @@ fn test_parse_non_neg_i32() {
         let a: i32 = -1_i32;
         let bytes: [u8; 4] = a.to_be_bytes();
         // parse the integer
-        let parsing_result = FileReader::parse_non_neg_i32(&bytes[..]);
+        let parsing_result = header::parse_non_neg_i32(&mut HeaderDecoder::new(&bytes[..]));
         // check the returned error
         assert!(parsing_result.is_err());
         let parsing_err: ParseHeaderError = parsing_result.unwrap_err();
@@ -665,7 +727,7 @@ fn test_parse_non_neg_i32() {
         let a: i32 = std::i32::MIN;
         let bytes: [u8; 4] = a.to_be_bytes();
         // parse the integer
-        let parsing_result = FileReader::parse_non_neg_i32(&bytes[..]);
+        let parsing_result = header::parse_non_neg_i32(&mut HeaderDecoder::new(&bytes[..]));
         // check the returned error
         assert!(parsing_result.is_err());
         let parsing_err: ParseHeaderError = parsing_result.unwrap_err();
@@ -687,7 +749,9 @@ fn test_parse_non_neg_i32() {
         bytes.push(43);
         bytes.push(44);
         // parse the integer
-        let (rem_bytes, b): (&[u8], i32) = FileReader::parse_non_neg_i32(&bytes[..]).unwrap();
+        let mut input = HeaderDecoder::new(&bytes[..]);
+        let b: i32 = header::parse_non_neg_i32(&mut input).unwrap();
+        let rem_bytes: &[u8] = input.remaining();
         // test remaining bytes and the parsed value
         assert_eq!(&[42, 43, 44], rem_bytes);
         assert_eq!(1_i32, b);
@@ -699,13 +763,13 @@ fn test_parse_non_neg_i32() {
         let bytes: Vec<u8> = Vec::from(&a.to_be_bytes()[..2]);
         assert_eq!(2, bytes.len());
         // check the returned error
-        let parsing_result = FileReader::parse_non_neg_i32(&bytes[..]);
+        let parsing_result = header::parse_non_neg_i32(&mut HeaderDecoder::new(&bytes[..]));
         assert!(parsing_result.is_err());
         let parsing_err: ParseHeaderError = parsing_result.unwrap_err();
         assert!(parsing_err.header_is_incomplete());
         assert_eq!(ParseHeaderErrorKind::NonNegativeI32 ,parsing_err.kind);
         assert_eq!(
-            InvalidBytes::Incomplete(nom::Needed::Size(NonZeroUsize::new(2).unwrap())),
+            InvalidBytes::Incomplete(Needed::Size(NonZeroUsize::new(2).unwrap())),
             parsing_err.invalid_bytes
         );
     }
@@ -718,7 +782,9 @@ fn test_parse_num_records() {
         let a: u32 = std::u32::MAX;
         let bytes: [u8; 4] = a.to_be_bytes();
         // parse the integer
-        let (rem_bytes, b): (&[u8], Option<usize>) = FileReader::parse_as_usize_optional(&bytes[..]).unwrap();
+        let mut input = HeaderDecoder::new(&bytes[..]);
+        let b: Option<usize> = header::parse_as_usize_optional(&mut input).unwrap();
+        let rem_bytes: &[u8] = input.remaining();
         // test remaining bytes and the parsed value
         assert_eq!(&[] as &[u8],                    rem_bytes);
         assert_eq!(None,                            b);
@@ -729,7 +795,9 @@ fn test_parse_num_records() {
         let a: u32 = 0_u32;
         let bytes: [u8; 4] = a.to_be_bytes();
         // parse the integer
-        let (rem_bytes, b): (&[u8],Option<usize>) = FileReader::parse_as_usize_optional(&bytes[..]).unwrap();
+        let mut input = HeaderDecoder::new(&bytes[..]);
+        let b: Option<usize> = header::parse_as_usize_optional(&mut input).unwrap();
+        let rem_bytes: &[u8] = input.remaining();
         // test remaining bytes and the parsed value
         assert_eq!(&[] as &[u8],                rem_bytes);
         assert_eq!(Some(0),                     b);
@@ -740,7 +808,9 @@ fn test_parse_num_records() {
         let a: u32 = 1_u32;
         let bytes: [u8; 4] = a.to_be_bytes();
         // parse the integer
-        let (rem_bytes, b): (&[u8],Option<usize>) = FileReader::parse_as_usize_optional(&bytes[..]).unwrap();
+        let mut input = HeaderDecoder::new(&bytes[..]);
+        let b: Option<usize> = header::parse_as_usize_optional(&mut input).unwrap();
+        let rem_bytes: &[u8] = input.remaining();
         // test remaining bytes and the parsed value
         assert_eq!(&[] as &[u8],                rem_bytes);
         assert_eq!(Some(1),                     b);
@@ -751,7 +821,9 @@ fn test_parse_num_records() {
         let a: u32 = std::i32::MAX as u32;
         let bytes: [u8; 4] = a.to_be_bytes();
         // parse the integer
-        let (rem_bytes, b): (&[u8], Option<usize>) = FileReader::parse_as_usize_optional(&bytes[..]).unwrap();
+        let mut input = HeaderDecoder::new(&bytes[..]);
+        let b: Option<usize> = header::parse_as_usize_optional(&mut input).unwrap();
+        let rem_bytes: &[u8] = input.remaining();
         // test remaining bytes and the parsed value
         assert_eq!(&[] as &[u8],                    rem_bytes);
         assert_eq!(Some(std::i32::MAX as usize),    b);
@@ -762,7 +834,7 @@ fn test_parse_num_records() {
         let a: i32 = std::i32::MIN;
         let bytes: [u8; 4] = a.to_be_bytes();
         // parse the integer
-        let parsing_result = FileReader::parse_as_usize_optional(&bytes[..]);
+        let parsing_result = header::parse_as_usize_optional(&mut HeaderDecoder::new(&bytes[..]));
         // check the returned error
         assert_eq!(true,                                        parsing_result.is_err());
         let parsing_err: ParseHeaderError = parsing_result.unwrap_err();
@@ -779,7 +851,7 @@ fn test_parse_num_records() {
         let a: u32 = (std::i32::MIN as u32) + 1;
         let bytes: [u8; 4] = a.to_be_bytes();
         // parse the integer
-        let parsing_result = FileReader::parse_as_usize_optional(&bytes[..]);
+        let parsing_result = header::parse_as_usize_optional(&mut HeaderDecoder::new(&bytes[..]));
         // check the returned error
         assert_eq!(true,                                        parsing_result.is_err());
         let parsing_err: ParseHeaderError = parsing_result.unwrap_err();
@@ -794,13 +866,13 @@ fn test_parse_num_records() {
         let bytes: Vec<u8> = Vec::from(&a.to_be_bytes()[0..3]);
         // parse the integer
         // parse the integer
-        let parsing_result = FileReader::parse_as_usize_optional(&bytes[..]);
+        let parsing_result = header::parse_as_usize_optional(&mut HeaderDecoder::new(&bytes[..]));
         // check the returned error
         assert!(parsing_result.is_err());
         let parsing_err: ParseHeaderError = parsing_result.unwrap_err();
         assert_eq!(true,                                                                            parsing_err.header_is_incomplete());
         assert_eq!(ParseHeaderErrorKind::NonNegativeI32,                                            parsing_err.kind);
-        assert_eq!(InvalidBytes::Incomplete(nom::Needed::Size(NonZeroUsize::new(1).unwrap())),      parsing_err.invalid_bytes);
+        assert_eq!(InvalidBytes::Incomplete(Needed::Size(NonZeroUsize::new(1).unwrap())),      parsing_err.invalid_bytes);
     }
 }
 
@@ -827,7 +899,9 @@ fn test_parse_name_string() {
                 bytes
             };
             // Parse the bytes into a string
-            let (rem_bytes, name): (&[u8], String)= FileReader::parse_name_string(&bytes).unwrap();
+            let mut input = HeaderDecoder::new(&bytes[..]);
+            let name: String = header::parse_name_string(&mut input, &mut HeaderParseState::new(NameDecoder::new(NameDecoding::Utf8, NameValidation::Strict))).unwrap();
+            let rem_bytes: &[u8] = input.remaining();
             // Test the parsed string
             assert_eq!("foo", name);
             // And test the remaining bytes
@@ -856,7 +930,9 @@ fn test_parse_name_string() {
                 bytes
             };
             // Parse the bytes into a string
-            let (rem_bytes, name): (&[u8], String)= FileReader::parse_name_string(&bytes).unwrap();
+            let mut input = HeaderDecoder::new(&bytes[..]);
+            let name: String = header::parse_name_string(&mut input, &mut HeaderParseState::new(NameDecoder::new(NameDecoding::Utf8, NameValidation::Strict))).unwrap();
+            let rem_bytes: &[u8] = input.remaining();
             // Test the parsed string
             assert_eq!("foo", name);
             // And test the remaining bytes
@@ -889,7 +965,7 @@ fn test_parse_name_string() {
                 bytes
             };
             // check the returned error
-            let parsing_result = FileReader::parse_name_string(&bytes[..]);
+            let parsing_result = header::parse_name_string(&mut HeaderDecoder::new(&bytes[..]), &mut HeaderParseState::new(NameDecoder::new(NameDecoding::Utf8, NameValidation::Strict)));
             assert!(parsing_result.is_err());
             let parsing_err: ParseHeaderError = parsing_result.unwrap_err();
             assert_eq!(false,                               parsing_err.header_is_incomplete());
@@ -917,7 +993,9 @@ fn test_parse_name_string() {
                 bytes
             };
             // Parse the bytes into a string
-            let (rem_bytes, name): (&[u8], String)= FileReader::parse_name_string(&bytes).unwrap();
+            let mut input = HeaderDecoder::new(&bytes[..]);
+            let name: String = header::parse_name_string(&mut input, &mut HeaderParseState::new(NameDecoder::new(NameDecoding::Utf8, NameValidation::Strict))).unwrap();
+            let rem_bytes: &[u8] = input.remaining();
             // Test the parsed string
             assert_eq!("café", name);
             // And test the remaining bytes
@@ -945,7 +1023,7 @@ fn test_parse_name_string() {
                 bytes
             };
             // Parse the bytes into a string
-            let parsing_result: Result<_, _> = FileReader::parse_name_string(&bytes);
+            let parsing_result: Result<_, _> = header::parse_name_string(&mut HeaderDecoder::new(&bytes[..]), &mut HeaderParseState::new(NameDecoder::new(NameDecoding::Utf8, NameValidation::Strict)));
             // Test the parsed string
             assert!(parsing_result.is_err());
             assert!(parsing_result.is_err());
@@ -981,14 +1059,14 @@ fn test_parse_name_string() {
                 bytes
             };
             // Parse the bytes into a string
-            let parsing_result: Result<_, _> = FileReader::parse_name_string(&bytes);
+            let parsing_result: Result<_, _> = header::parse_name_string(&mut HeaderDecoder::new(&bytes[..]), &mut HeaderParseState::new(NameDecoder::new(NameDecoding::Utf8, NameValidation::Strict)));
             // Test the parsed string
             assert!(parsing_result.is_err());
             let parsing_err: ParseHeaderError = parsing_result.unwrap_err();
             assert!(parsing_err.header_is_incomplete());
             assert_eq!(ParseHeaderErrorKind::ZeroPadding ,parsing_err.kind);
             assert_eq!(
-                InvalidBytes::Incomplete(nom::Needed::Size(NonZeroUsize::new(1).unwrap())),
+                InvalidBytes::Incomplete(Needed::Size(NonZeroUsize::new(1).unwrap())),
                 parsing_err.invalid_bytes,
             );
         }
@@ -1003,7 +1081,9 @@ fn test_parse_data_type() {
     {
         let a: u32 = DataType::I8 as u32;
         let bytes: [u8; 4] = a.to_be_bytes();
-        let (rem_input, data_type): (&[u8], DataType) = FileReader::parse_data_type(&bytes[..]).unwrap();
+        let mut input = HeaderDecoder::new(&bytes[..]);
+        let data_type: DataType = header::parse_data_type(&mut input).unwrap();
+        let rem_input: &[u8] = input.remaining();
         assert_eq!(DataType::I8, data_type);
         assert_eq!(&[] as &[u8], rem_input);
     }
@@ -1012,7 +1092,9 @@ fn test_parse_data_type() {
     {
         let a: u32 = DataType::U8 as u32;
         let bytes: [u8; 4] = a.to_be_bytes();
-        let (rem_input, data_type): (&[u8], DataType) = FileReader::parse_data_type(&bytes[..]).unwrap();
+        let mut input = HeaderDecoder::new(&bytes[..]);
+        let data_type: DataType = header::parse_data_type(&mut input).unwrap();
+        let rem_input: &[u8] = input.remaining();
         assert_eq!(DataType::U8, data_type);
         assert_eq!(&[] as &[u8], rem_input);
     }
@@ -1021,7 +1103,9 @@ fn test_parse_data_type() {
     {
         let a: u32 = DataType::I16 as u32;
         let bytes: [u8; 4] = a.to_be_bytes();
-        let (rem_input, data_type): (&[u8], DataType) = FileReader::parse_data_type(&bytes[..]).unwrap();
+        let mut input = HeaderDecoder::new(&bytes[..]);
+        let data_type: DataType = header::parse_data_type(&mut input).unwrap();
+        let rem_input: &[u8] = input.remaining();
         assert_eq!(DataType::I16, data_type);
         assert_eq!(&[] as &[u8], rem_input);
     }
@@ -1030,7 +1114,9 @@ fn test_parse_data_type() {
     {
         let a: u32 = DataType::I32 as u32;
         let bytes: [u8; 4] = a.to_be_bytes();
-        let (rem_input, data_type): (&[u8], DataType) = FileReader::parse_data_type(&bytes[..]).unwrap();
+        let mut input = HeaderDecoder::new(&bytes[..]);
+        let data_type: DataType = header::parse_data_type(&mut input).unwrap();
+        let rem_input: &[u8] = input.remaining();
         assert_eq!(DataType::I32, data_type);
         assert_eq!(&[] as &[u8], rem_input);
     }
@@ -1039,7 +1125,9 @@ fn test_parse_data_type() {
     {
         let a: u32 = DataType::F32 as u32;
         let bytes: [u8; 4] = a.to_be_bytes();
-        let (rem_input, data_type): (&[u8], DataType) = FileReader::parse_data_type(&bytes[..]).unwrap();
+        let mut input = HeaderDecoder::new(&bytes[..]);
+        let data_type: DataType = header::parse_data_type(&mut input).unwrap();
+        let rem_input: &[u8] = input.remaining();
         assert_eq!(DataType::F32, data_type);
         assert_eq!(&[] as &[u8], rem_input);
     }
@@ -1048,7 +1136,9 @@ fn test_parse_data_type() {
     {
         let a: u32 = DataType::F64 as u32;
         let bytes: [u8; 4] = a.to_be_bytes();
-        let (rem_input, data_type): (&[u8], DataType) = FileReader::parse_data_type(&bytes[..]).unwrap();
+        let mut input = HeaderDecoder::new(&bytes[..]);
+        let data_type: DataType = header::parse_data_type(&mut input).unwrap();
+        let rem_input: &[u8] = input.remaining();
         assert_eq!(DataType::F64, data_type);
         assert_eq!(&[] as &[u8], rem_input);
     }
@@ -1059,7 +1149,7 @@ fn test_parse_data_type() {
         assert!(DataType::try_from(a).is_err());
 
         let bytes: [u8; 4] = a.to_be_bytes();
-        let parsing_result = FileReader::parse_data_type(&bytes[..]);
+        let parsing_result = header::parse_data_type(&mut HeaderDecoder::new(&bytes[..]));
         assert!(parsing_result.is_err());
     }
 
@@ -1068,7 +1158,7 @@ fn test_parse_data_type() {
         let a: i32 = -1_i32;
 
         let bytes: [u8; 4] = a.to_be_bytes();
-        let parsing_result = FileReader::parse_data_type(&bytes[..]);
+        let parsing_result = header::parse_data_type(&mut HeaderDecoder::new(&bytes[..]));
         // Check the return error
         assert!(parsing_result.is_err());
         let parsing_err: ParseHeaderError = parsing_result.unwrap_err();
@@ -1089,7 +1179,9 @@ fn test_parse_data_type() {
         bytes.push(43);
         bytes.push(44);
 
-        let (rem_input, data_type): (&[u8], DataType) = FileReader::parse_data_type(&bytes[..]).unwrap();
+        let mut input = HeaderDecoder::new(&bytes[..]);
+        let data_type: DataType = header::parse_data_type(&mut input).unwrap();
+        let rem_input: &[u8] = input.remaining();
         assert_eq!(DataType::F64, data_type);
         assert_eq!(
             &[42, 43, 44],
@@ -1102,14 +1194,14 @@ fn test_parse_data_type() {
         let a: u32 = DataType::F64 as u32;
         let bytes: Vec<u8> = Vec::from(&a.to_be_bytes()[..3]);
         assert_eq!(3, bytes.len());
-        let parsing_result = FileReader::parse_data_type(&bytes[..]);
+        let parsing_result = header::parse_data_type(&mut HeaderDecoder::new(&bytes[..]));
         // Check the return error
         assert!(parsing_result.is_err());
         let parsing_err: ParseHeaderError = parsing_result.unwrap_err();
         assert!(parsing_err.header_is_incomplete());
         assert_eq!(ParseHeaderErrorKind::NonNegativeI32, parsing_err.kind);
         assert_eq!(
-            InvalidBytes::Incomplete(nom::Needed::Size(NonZeroUsize::new(1).unwrap())),
+            InvalidBytes::Incomplete(Needed::Size(NonZeroUsize::new(1).unwrap())),
             parsing_err.invalid_bytes
         );
     }
@@ -1120,15 +1212,16 @@ fn test_parse_zero_padding() {
     // Test valid zero padding
     {
         let bytes: [u8; 3] = [0_u8; 3];
-        let (rem_input, zero_padding): (&[u8], &[u8]) = FileReader::parse_zero_padding(&bytes, 3).unwrap();
-        assert_eq!(0, rem_input.len());
-        assert_eq!(&[0, 0, 0], zero_padding);
+        let mut input = HeaderDecoder::new(&bytes[..]);
+        header::parse_zero_padding(&mut input, 3).unwrap();
+        assert_eq!(0, input.remaining().len());
+        assert_eq!(3, input.offset());
 
     }
     // Test not valid zero padding
     {
         let bytes: [u8; 3] = [0, 1, 0];
-        let parsing_result = FileReader::parse_zero_padding(&bytes, 3);
+        let parsing_result = header::parse_zero_padding(&mut HeaderDecoder::new(&bytes[..]), 3);
         // Check the return error
         assert!(parsing_result.is_err());
         let parsing_err = parsing_result.unwrap_err();
@@ -1145,7 +1238,7 @@ fn test_parse_zero_padding() {
     // Test missing bytes
     {
         let bytes: [u8; 3] = [0_u8; 3];
-        let parsing_result = FileReader::parse_zero_padding(&bytes[0..2], 3);
+        let parsing_result = header::parse_zero_padding(&mut HeaderDecoder::new(&bytes[0..2]), 3);
         // Check the return error
         assert!(parsing_result.is_err());
         let parsing_err = parsing_result.unwrap_err();
@@ -1155,7 +1248,7 @@ fn test_parse_zero_padding() {
             parsing_err.kind,
         );
         assert_eq!(
-            InvalidBytes::Incomplete(nom::Needed::Size(NonZeroUsize::new(1).unwrap())),
+            InvalidBytes::Incomplete(Needed::Size(NonZeroUsize::new(1).unwrap())),
             parsing_err.invalid_bytes
         );
     }
@@ -1698,3 +1791,1152 @@ fn test_file_reader_read_record_f64() {
     let _ = file_reader.close();
     tmp_dir.close().unwrap();
 }
+
+#[test]
+fn test_file_reader_diagnostics() {
+    // Valid files
+    for bytes in [
+        NC3_CLASSIC_FILE_BYTES,
+        NC3_64BIT_OFFSET_FILE_BYTES,
+        NC3_FILL_VALUES_FILE_BYTES,
+        NC3_CONTAINING_DEFAULT_FILL_VALUES_FILE_BYTES,
+        SCALAR_VARIABLES_FILE_BYTES,
+        EMPTY_DATA_SET_FILE_BYTES,
+        NC3_ZERO_SIZED_UNLIMITED_DIM_FILE_BYTES,
+    ].iter() {
+        let file_reader = FileReader::from_reader(std::io::Cursor::new(bytes)).unwrap();
+        assert_eq!(true,                                file_reader.diagnostics().is_empty());
+    }
+
+    // Non-zero padding after the header, and truncated record data
+    let mut bytes: Vec<u8> = EMPTY_DATA_SET_FILE_BYTES.to_vec();
+    bytes[100] = 1;
+    let file_reader = FileReader::from_reader(std::io::Cursor::new(bytes)).unwrap();
+    assert_eq!(
+        vec![Diagnostic::Layout(LayoutViolation::NonZeroPadding{offset: 100})],
+        file_reader.diagnostics().as_slice()
+    );
+    let file_reader = FileReader::from_reader(std::io::Cursor::new(&NC3_CLASSIC_FILE_BYTES[0..2000])).unwrap();
+    assert_eq!(
+        vec![Diagnostic::Layout(LayoutViolation::FileTooShort{required_size: 2332, file_size: 2000})],
+        file_reader.diagnostics().as_slice()
+    );
+}
+
+#[test]
+fn test_file_reader_name_decoding() {
+    // Replace the name of the dimension `latitude` by the Latin-1 encoded name `látitude`
+    let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+    let position: usize = bytes.windows(8).position(|window: &[u8]| window == b"latitude").unwrap();
+    bytes[position + 1] = 0xE1;
+    let latin1_bytes: Vec<u8> = b"l\xE1titude".to_vec();
+
+    // Strict UTF-8 decoding
+    let parsing_result = FileReader::from_reader(std::io::Cursor::new(bytes.clone()));
+    match parsing_result.unwrap_err() {
+        ReadError::ParseHeader(err) => assert_eq!(ParseHeaderErrorKind::Utf8,  err.kind),
+        err => panic!("Unexpected error: {:?}", err),
+    }
+    let options = ReadOptions{name_decoding: NameDecoding::Utf8, ..ReadOptions::default()};
+    assert!(FileReader::from_reader_with_options(std::io::Cursor::new(bytes.clone()), &options).is_err());
+
+    // Lossy UTF-8 decoding
+    let options = ReadOptions{name_decoding: NameDecoding::Utf8Lossy, ..ReadOptions::default()};
+    let file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(bytes.clone()), &options).unwrap();
+    assert_eq!(vec!["l\u{FFFD}titude", "longitude", "time"],    file_reader.data_set().dim_names());
+    assert_eq!(
+        vec![Diagnostic::NameNotValidUtf8{bytes: latin1_bytes.clone(), name: String::from("l\u{FFFD}titude")}],
+        file_reader.diagnostics().as_slice()
+    );
+
+    // Latin-1 decoding
+    let options = ReadOptions{name_decoding: NameDecoding::Latin1, ..ReadOptions::default()};
+    let mut file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(bytes), &options).unwrap();
+    assert_eq!(vec!["látitude", "longitude", "time"],           file_reader.data_set().dim_names());
+    assert_eq!(
+        vec![Diagnostic::NameNotValidUtf8{bytes: latin1_bytes, name: String::from("látitude")}],
+        file_reader.diagnostics().as_slice()
+    );
+    assert_eq!(TEMP_F32_VAR_DATA.to_vec(),                      file_reader.read_var_f32(TEMP_F32_VAR_NAME).unwrap());
+
+    // The valid UTF-8 names are not reported
+    let file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(NC3_CLASSIC_FILE_BYTES), &options).unwrap();
+    assert_eq!(true,                                            file_reader.diagnostics().is_empty());
+}
+
+#[test]
+fn test_file_reader_name_validation() {
+    // Replace the name of the dimension `latitude` by the name `.atitude` (leading special character)
+    let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+    let position: usize = bytes.windows(8).position(|window: &[u8]| window == b"latitude").unwrap();
+    bytes[position] = b'.';
+
+    // Strict rules (default)
+    let parsing_result = FileReader::from_reader(std::io::Cursor::new(bytes.clone()));
+    assert_eq!(
+        ReadError::DataSet(InvalidDataSet::DimensionNameNotValid{dim_name: String::from(".atitude"), reason: NameError::FirstCharNotAllowed{chr: '.'}}),
+        parsing_result.unwrap_err()
+    );
+
+    // Lenient rules
+    let options = ReadOptions{name_validation: NameValidation::Lenient, ..ReadOptions::default()};
+    let mut file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(bytes), &options).unwrap();
+    assert_eq!(vec![".atitude", "longitude", "time"],           file_reader.data_set().dim_names());
+    assert_eq!(
+        vec![Diagnostic::NameNotValid{name: String::from(".atitude"), reason: NameError::FirstCharNotAllowed{chr: '.'}}],
+        file_reader.diagnostics().as_slice()
+    );
+    assert_eq!(TEMP_F32_VAR_DATA.to_vec(),                      file_reader.read_var_f32(TEMP_F32_VAR_NAME).unwrap());
+    // the names defined afterward are checked with the strict rules
+    assert_eq!(NameValidation::Strict,                          file_reader.data_set().name_validation());
+}
+
+#[test]
+fn test_file_reader_long_name_policy() {
+    use crate::{FileWriter, LongNamePolicy, NC_MAX_NAME_SIZE};
+    use crate::error::WriteError;
+
+    // Write a header defining the dimension `latitude`, and replace its name by a name of 300 bytes (2-byte characters)
+    let mut data_set = DataSet::new();
+    data_set.add_fixed_dim("latitude", 3).unwrap();
+    let mut file_writer = FileWriter::from_writer(std::io::Cursor::new(vec![]));
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    let mut bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+    let long_name: String = "\u{e9}".repeat(150);
+    let position: usize = bytes.windows(8).position(|window: &[u8]| window == b"latitude").unwrap();
+    bytes.splice((position - 4)..(position + 8), [&300_u32.to_be_bytes()[..], long_name.as_bytes()].concat());
+
+    // Truncated names
+    let options = ReadOptions{long_name_policy: LongNamePolicy::Truncate, ..ReadOptions::default()};
+    let file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(bytes.clone()), &options).unwrap();
+    let truncated_name: String = "\u{e9}".repeat(NC_MAX_NAME_SIZE / 2);
+    assert_eq!(vec![truncated_name.clone()],                    file_reader.data_set().dim_names());
+    assert_eq!(
+        vec![Diagnostic::NameTooLong{name: long_name.clone(), new_name: Some(truncated_name)}],
+        file_reader.diagnostics().as_slice()
+    );
+
+    // Preserved names
+    let options = ReadOptions{long_name_policy: LongNamePolicy::Preserve, ..ReadOptions::default()};
+    let file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(bytes), &options).unwrap();
+    assert_eq!(vec![long_name.clone()],                         file_reader.data_set().dim_names());
+    assert_eq!(
+        vec![Diagnostic::NameTooLong{name: long_name.clone(), new_name: None}],
+        file_reader.diagnostics().as_slice()
+    );
+    let (mut data_set, _version): (DataSet, Version) = file_reader.close();
+    let too_long = InvalidDataSet::DimensionNameNotValid{dim_name: long_name.clone(), reason: NameError::TooLong{max: NC_MAX_NAME_SIZE, get: 300}};
+    assert_eq!(Err(too_long.clone()),                           data_set.check_name_sizes());
+    // the long names cannot be written
+    let mut file_writer = FileWriter::from_writer(std::io::Cursor::new(vec![]));
+    assert_eq!(WriteError::DataSet(too_long),                   file_writer.set_def(&data_set, Version::Classic, 0).unwrap_err());
+    // nor defined afterward
+    assert_eq!(
+        InvalidDataSet::DimensionNameNotValid{dim_name: long_name.clone() + "_2", reason: NameError::TooLong{max: NC_MAX_NAME_SIZE, get: 302}},
+        data_set.add_fixed_dim(&(long_name + "_2"), 1).unwrap_err()
+    );
+}
+
+#[test]
+fn test_file_reader_indeterminate_vsize() {
+    use crate::io::{compute_header_required_size, header::header_bytes};
+
+    const SMALL_VAR_NAME: &str = "small_var";
+    const LARGE_VAR_NAME: &str = "large_var";
+    const LARGE_DIM_SIZE: usize = 65536;
+    // 2^32 values of 8 bytes, the `vsize` exceeds 2^31 - 1
+    const LARGE_VAR_CHUNK_SIZE: usize = LARGE_DIM_SIZE * LARGE_DIM_SIZE * 8;
+
+    let mut data_set = DataSet::new();
+    data_set.add_fixed_dim("small_dim", 3).unwrap();
+    data_set.add_fixed_dim("large_dim_1", LARGE_DIM_SIZE).unwrap();
+    data_set.add_fixed_dim("large_dim_2", LARGE_DIM_SIZE).unwrap();
+    data_set.add_var_i16(SMALL_VAR_NAME, &["small_dim"]).unwrap();
+    data_set.add_var_f64(LARGE_VAR_NAME, &["large_dim_1", "large_dim_2"]).unwrap();
+
+    // Write only the header, the data part of the file is not required to parse it
+    let header_size: usize = compute_header_required_size(&data_set, Version::Offset64Bit);
+    let begin_offsets: Vec<u64> = vec![header_size as u64, (header_size + 8) as u64];
+    let bytes: Vec<u8> = header_bytes(&data_set, Version::Offset64Bit, &begin_offsets).unwrap();
+    assert_eq!(header_size,                         bytes.len());
+
+    let file_reader = FileReader::from_reader(std::io::Cursor::new(bytes)).unwrap();
+    assert_eq!(Some(8),                             file_reader.var_vsize(SMALL_VAR_NAME));
+    assert_eq!(Some(8),                             file_reader.var_chunk_size(SMALL_VAR_NAME));
+    assert_eq!(Some(std::u32::MAX),                 file_reader.var_vsize(LARGE_VAR_NAME));
+    assert_eq!(Some(LARGE_VAR_CHUNK_SIZE),          file_reader.var_chunk_size(LARGE_VAR_NAME));
+    assert_eq!(Some(vec![(header_size as u64 + 8, LARGE_VAR_CHUNK_SIZE)]),    file_reader.var_byte_ranges(LARGE_VAR_NAME));
+    // The indeterminate `vsize` is not reported as a mismatch
+    assert_eq!(
+        vec![Diagnostic::Layout(LayoutViolation::FileTooShort{
+            required_size: (header_size + 8 + LARGE_VAR_CHUNK_SIZE) as u64,
+            file_size: header_size as u64,
+        })],
+        file_reader.diagnostics().as_slice()
+    );
+
+    assert_eq!(None,                                file_reader.var_vsize("undef_var"));
+    assert_eq!(None,                                file_reader.var_chunk_size("undef_var"));
+}
+
+#[test]
+fn test_file_reader_open_with() {
+    // The second record (308 bytes) is partially written
+    let (tmp_dir, input_data_file_path) = copy_bytes_to_tmp_file(&NC3_CLASSIC_FILE_BYTES[..(NC3_CLASSIC_FILE_BYTES.len() - 100)], NC3_CLASSIC_FILE_NAME);
+
+    // The default options are strict
+    assert_eq!(ReadOptions::default(),              ReadOptions::new());
+    assert_eq!(NameValidation::Strict,              ReadOptions::new().name_validation);
+    {
+        let mut file_reader = FileReader::open(&input_data_file_path).unwrap();
+        assert!(file_reader.read_all_vars().is_err());
+    }
+
+    let options = ReadOptions::lenient().with_max_alloc_bytes(100).with_buffer_size(0).with_lazy_attrs(true);
+    assert_eq!(true,                                options.allow_truncated_records);
+    assert_eq!(Some(100),                           options.max_alloc_bytes);
+    assert_eq!(1,                                   options.buffer_size);
+    let mut file_reader = FileReader::open_with(&input_data_file_path, options).unwrap();
+    tmp_dir.close().unwrap();
+    assert_eq!(Some(1),                             file_reader.data_set().num_records());
+    assert_eq!(TEMP_F32_VAR_DATA[0..15].to_vec(),   file_reader.read_var_f32(TEMP_F32_VAR_NAME).unwrap());
+    assert!(matches!(file_reader.read_var(TEMP_F64_VAR_NAME), Err(ReadError::MaximumAllocationExceeded{..})));
+    assert_eq!(
+        true,
+        file_reader.data_set().global_attrs().iter().all(|attr: &Attribute| attr.is_decoded() == (attr.data_type() == DataType::U8))
+    );
+}
+
+#[test]
+fn test_file_reader_allow_truncated_records() {
+    const TIME_VAR_NAME: &str = "time";
+    let options = ReadOptions{allow_truncated_records: true, ..ReadOptions::default()};
+
+    // The second record (308 bytes) is partially written
+    let truncated_bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES[..(NC3_CLASSIC_FILE_BYTES.len() - 100)].to_vec();
+    {
+        let mut file_reader = FileReader::from_reader(std::io::Cursor::new(truncated_bytes.clone())).unwrap();
+        assert_eq!(Some(2),                                 file_reader.data_set().num_records());
+        assert!(file_reader.read_all_vars().is_err());
+    }
+    {
+        let mut file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(truncated_bytes.clone()), &options).unwrap();
+        assert_eq!(Some(1),                                 file_reader.data_set().num_records());
+        assert_eq!(vec![438300.0],                          file_reader.read_var_f32(TIME_VAR_NAME).unwrap());
+        assert_eq!(TEMP_F32_VAR_DATA[0..15].to_vec(),       file_reader.read_var_f32(TEMP_F32_VAR_NAME).unwrap());
+        assert_eq!(vec![0.0, 0.5, 1.0],                     file_reader.read_var_f32("latitude").unwrap());
+        assert_eq!(
+            vec![
+                Diagnostic::Layout(LayoutViolation::TrailingBytes{data_end: 2024, file_size: 2232}),
+                Diagnostic::RecordsTruncated{header_num_records: Some(2), num_records: 1},
+            ],
+            file_reader.diagnostics().as_slice()
+        );
+    }
+
+    // The *streaming* number of records
+    let mut streaming_bytes: Vec<u8> = truncated_bytes;
+    streaming_bytes[4..8].copy_from_slice(&std::u32::MAX.to_be_bytes());
+    {
+        let parsing_result = FileReader::from_reader(std::io::Cursor::new(streaming_bytes.clone()));
+        assert_eq!(ReadError::ComputationNumberOfRecords,   parsing_result.unwrap_err());
+    }
+    {
+        let mut file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(streaming_bytes), &options).unwrap();
+        assert_eq!(true,                                    file_reader.numrecs_is_streaming());
+        assert_eq!(Some(1),                                 file_reader.data_set().num_records());
+        assert_eq!(vec![438300.0],                          file_reader.read_var_f32(TIME_VAR_NAME).unwrap());
+        assert_eq!(
+            Some(&Diagnostic::RecordsTruncated{header_num_records: None, num_records: 1}),
+            file_reader.diagnostics().iter().last()
+        );
+    }
+
+    // Complete files
+    let mut streaming_bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+    streaming_bytes[4..8].copy_from_slice(&std::u32::MAX.to_be_bytes());
+    for bytes in [NC3_CLASSIC_FILE_BYTES, &streaming_bytes[..]].iter() {
+        let mut file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(bytes), &options).unwrap();
+        assert_eq!(Some(2),                                 file_reader.data_set().num_records());
+        assert_eq!(vec![438300.0, 438324.0],                file_reader.read_var_f32(TIME_VAR_NAME).unwrap());
+        assert_eq!(true,                                    file_reader.diagnostics().is_empty());
+    }
+}
+
+#[test]
+fn test_file_reader_duplicate_dim_policy() {
+    use crate::NC_MAX_NAME_SIZE;
+    use crate::io::{compute_header_required_size, header::header_bytes};
+
+    // Define the dimension `x` twice, each variable being defined over one of them
+    let mut data_set = DataSet::new();
+    data_set.add_fixed_dim("x", 2).unwrap();
+    data_set.push_fixed_dim("x", 3).unwrap();
+    data_set.add_fixed_dim("x_1", 4).unwrap();
+    data_set.add_var_using_dim_refs("a", data_set.get_dims_from_dim_ids(&[0]).unwrap(), DataType::I8).unwrap();
+    data_set.add_var_using_dim_refs("b", data_set.get_dims_from_dim_ids(&[1]).unwrap(), DataType::I8).unwrap();
+    let header_size: usize = compute_header_required_size(&data_set, Version::Classic);
+    let mut bytes: Vec<u8> = header_bytes(&data_set, Version::Classic, &[header_size as u64, (header_size + 4) as u64]).unwrap();
+    bytes.extend_from_slice(&[1, 2, 0, 0, 3, 4, 5, 0]);
+
+    // Error (default)
+    assert_eq!(
+        ReadError::DataSet(InvalidDataSet::DimensionAlreadyExists(String::from("x"))),
+        FileReader::from_reader(std::io::Cursor::new(bytes.clone())).unwrap_err()
+    );
+
+    // Rename
+    let options = ReadOptions{duplicate_dim_policy: DuplicateDimPolicy::Rename, ..ReadOptions::default()};
+    let mut file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(bytes.clone()), &options).unwrap();
+    assert_eq!(vec!["x", "x_2", "x_1"],                 file_reader.data_set().dim_names());
+    // the renamed names do not change the end of the parsed header
+    assert_eq!(header_size,                             file_reader.header_used_size());
+    assert_eq!(vec![String::from("x_2")],               file_reader.data_set().get_var("b").unwrap().dim_names());
+    assert_eq!(
+        vec![Diagnostic::DuplicateDimension{dim_id: 1, name: String::from("x"), new_name: Some(String::from("x_2"))}],
+        file_reader.diagnostics().as_slice()
+    );
+    assert_eq!(vec![1, 2],                              file_reader.read_var_i8("a").unwrap());
+    assert_eq!(vec![3, 4, 5],                           file_reader.read_var_i8("b").unwrap());
+
+    // Keep by id
+    let options = ReadOptions{duplicate_dim_policy: DuplicateDimPolicy::KeepById, ..ReadOptions::default()};
+    let mut file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(bytes.clone()), &options).unwrap();
+    assert_eq!(vec!["x", "x", "x_1"],                   file_reader.data_set().dim_names());
+    assert_eq!(Some(2),                                 file_reader.data_set().dim_size("x"));
+    assert_eq!(Some(vec![1]),                           file_reader.data_set().get_var_dim_ids("b"));
+    assert_eq!(
+        vec![Diagnostic::DuplicateDimension{dim_id: 1, name: String::from("x"), new_name: None}],
+        file_reader.diagnostics().as_slice()
+    );
+    assert_eq!(vec![1, 2],                              file_reader.read_var_i8("a").unwrap());
+    assert_eq!(vec![3, 4, 5],                           file_reader.read_var_i8("b").unwrap());
+    // the header is written back unchanged
+    assert_eq!(
+        bytes[0..header_size].to_vec(),
+        header_bytes(file_reader.data_set(), Version::Classic, &[header_size as u64, (header_size + 4) as u64]).unwrap()
+    );
+
+    // Rename the duplicate names of the maximum size, truncated before the suffix
+    let long_name: String = "y".repeat(NC_MAX_NAME_SIZE);
+    let mut data_set = DataSet::new();
+    data_set.add_fixed_dim(&long_name, 2).unwrap();
+    data_set.push_fixed_dim(&long_name, 3).unwrap();
+    data_set.add_var_using_dim_refs("a", data_set.get_dims_from_dim_ids(&[0]).unwrap(), DataType::I8).unwrap();
+    data_set.add_var_using_dim_refs("b", data_set.get_dims_from_dim_ids(&[1]).unwrap(), DataType::I8).unwrap();
+    let header_size: usize = compute_header_required_size(&data_set, Version::Classic);
+    let mut bytes: Vec<u8> = header_bytes(&data_set, Version::Classic, &[header_size as u64, (header_size + 4) as u64]).unwrap();
+    bytes.extend_from_slice(&[1, 2, 0, 0, 3, 4, 5, 0]);
+
+    let options = ReadOptions{duplicate_dim_policy: DuplicateDimPolicy::Rename, ..ReadOptions::default()};
+    let mut file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(bytes.clone()), &options).unwrap();
+    let new_name: String = "y".repeat(NC_MAX_NAME_SIZE - 2) + "_1";
+    assert_eq!(vec![long_name.clone(), new_name.clone()],   file_reader.data_set().dim_names());
+    assert_eq!(vec![new_name.clone()],                  file_reader.data_set().get_var("b").unwrap().dim_names());
+    assert_eq!(vec![3, 4, 5],                           file_reader.read_var_i8("b").unwrap());
+}
+
+#[test]
+fn test_file_reader_duplicate_attr_policy() {
+    use crate::io::{compute_header_required_size, header::header_bytes};
+
+    // Define the global attribute `title` and the variable attribute `units` twice
+    let mut data_set = DataSet::new();
+    data_set.add_fixed_dim("x", 2).unwrap();
+    data_set.add_var_i8("a", &["x"]).unwrap();
+    data_set.attrs.push(Attribute::from_data(String::from("title"), DataVector::U8(b"first".to_vec())));
+    data_set.attrs.push(Attribute::from_data(String::from("title"), DataVector::U8(b"second".to_vec())));
+    data_set.attrs.push(Attribute::from_data(String::from("title_1"), DataVector::U8(b"third".to_vec())));
+    data_set.vars[0].attrs.push(Attribute::from_data(String::from("units"), DataVector::U8(b"K".to_vec())));
+    data_set.vars[0].attrs.push(Attribute::from_data(String::from("long_name"), DataVector::U8(b"temperature".to_vec())));
+    data_set.vars[0].attrs.push(Attribute::from_data(String::from("units"), DataVector::U8(b"degC".to_vec())));
+    let header_size: usize = compute_header_required_size(&data_set, Version::Classic);
+    let mut bytes: Vec<u8> = header_bytes(&data_set, Version::Classic, &[header_size as u64]).unwrap();
+    bytes.extend_from_slice(&[1, 2, 0, 0]);
+
+    // Error (default)
+    assert_eq!(
+        ReadError::DataSet(InvalidDataSet::GlobalAttributeAlreadyExists(String::from("title"))),
+        FileReader::from_reader(std::io::Cursor::new(bytes.clone())).unwrap_err()
+    );
+
+    // Last wins
+    let options = ReadOptions{duplicate_attr_policy: DuplicateAttrPolicy::LastWins, ..ReadOptions::default()};
+    let mut file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(bytes.clone()), &options).unwrap();
+    assert_eq!(vec!["title", "title_1"],                file_reader.data_set().get_global_attr_names());
+    assert_eq!(Some(String::from("second")),            file_reader.data_set().get_global_attr_as_string("title"));
+    assert_eq!(Some(String::from("degC")),              file_reader.data_set().get_var_attr_as_string("a", "units"));
+    // the replaced attributes keep their position
+    assert_eq!(vec!["units", "long_name"],              file_reader.data_set().get_var("a").unwrap().get_attr_names());
+    // the bytes of the replaced attributes are part of the header
+    assert_eq!(header_size,                             file_reader.header_used_size());
+    assert_eq!(
+        vec![
+            Diagnostic::DuplicateAttribute{var_name: None, attr_name: String::from("title"), new_name: None},
+            Diagnostic::DuplicateAttribute{var_name: Some(String::from("a")), attr_name: String::from("units"), new_name: None},
+        ],
+        file_reader.diagnostics().as_slice()
+    );
+    assert_eq!(vec![1, 2],                              file_reader.read_var_i8("a").unwrap());
+
+    // Rename
+    let options = ReadOptions{duplicate_attr_policy: DuplicateAttrPolicy::Rename, ..ReadOptions::default()};
+    let file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(bytes), &options).unwrap();
+    assert_eq!(vec!["title", "title_2", "title_1"],     file_reader.data_set().get_global_attr_names());
+    assert_eq!(Some(String::from("second")),            file_reader.data_set().get_global_attr_as_string("title_2"));
+    assert_eq!(Some(String::from("degC")),              file_reader.data_set().get_var_attr_as_string("a", "units_1"));
+    assert_eq!(
+        vec![
+            Diagnostic::DuplicateAttribute{var_name: None, attr_name: String::from("title"), new_name: Some(String::from("title_2"))},
+            Diagnostic::DuplicateAttribute{var_name: Some(String::from("a")), attr_name: String::from("units"), new_name: Some(String::from("units_1"))},
+        ],
+        file_reader.diagnostics().as_slice()
+    );
+}
+
+#[test]
+fn test_file_reader_corrupted_header_does_not_panic() {
+    const HEADER_SIZE: usize = 1684;
+    // Overwrite each word of the header with large or negative values (number of elements, sizes, offsets, etc.)
+    for word_start in (0..HEADER_SIZE).step_by(4) {
+        for value in [std::i32::MAX, std::i32::MAX - 3, -1, 0].iter() {
+            let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+            bytes[word_start..word_start + 4].copy_from_slice(&value.to_be_bytes());
+            let _ = FileReader::from_reader(std::io::Cursor::new(bytes));
+        }
+    }
+    // Truncate the header
+    for header_len in 0..HEADER_SIZE {
+        let bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES[0..header_len].to_vec();
+        assert_eq!(true,        FileReader::from_reader(std::io::Cursor::new(bytes)).is_err());
+    }
+}
+
+#[test]
+fn test_file_reader_max_alloc_bytes() {
+    // No limit by default
+    {
+        let mut file_reader = FileReader::from_reader(std::io::Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        assert_eq!(None,                        file_reader.max_alloc_bytes());
+        assert_eq!(TEMP_F64_VAR_LEN,            file_reader.read_var_f64(TEMP_F64_VAR_NAME).unwrap().len());
+    }
+
+    let options = ReadOptions{max_alloc_bytes: Some(TEMP_F64_VAR_LEN * 8 - 1), ..ReadOptions::default()};
+    let mut file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(NC3_CLASSIC_FILE_BYTES), &options).unwrap();
+    assert_eq!(Some(TEMP_F64_VAR_LEN * 8 - 1),  file_reader.max_alloc_bytes());
+    assert_eq!(
+        ReadError::MaximumAllocationExceeded{var_name: String::from(TEMP_F64_VAR_NAME), max: TEMP_F64_VAR_LEN * 8 - 1, get: TEMP_F64_VAR_LEN * 8},
+        file_reader.read_var(TEMP_F64_VAR_NAME).unwrap_err()
+    );
+    assert_eq!(
+        ReadError::MaximumAllocationExceeded{var_name: String::from(TEMP_F64_VAR_NAME), max: TEMP_F64_VAR_LEN * 8 - 1, get: TEMP_F64_VAR_LEN * 8},
+        file_reader.read_all_vars().unwrap_err()
+    );
+    // a record is smaller than the limit
+    assert_eq!(TEMP_F64_VAR_LEN / 2,            file_reader.read_record_f64(TEMP_F64_VAR_NAME, 0).unwrap().len());
+    assert_eq!(TEMP_I8_VAR_LEN,                 file_reader.read_var_i8(TEMP_I8_VAR_NAME).unwrap().len());
+
+    // Exactly the size of the variable
+    file_reader.set_max_alloc_bytes(Some(TEMP_F64_VAR_LEN * 8));
+    assert_eq!(TEMP_F64_VAR_LEN,                file_reader.read_var_f64(TEMP_F64_VAR_NAME).unwrap().len());
+
+    // Remove the limit
+    file_reader.set_max_alloc_bytes(None);
+    assert_eq!(true,                            file_reader.read_all_vars().is_ok());
+}
+
+#[test]
+fn test_file_reader_var_data_out_of_file() {
+    // The header declares many more records than stored in the file
+    let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+    bytes[4..8].copy_from_slice(&std::i32::MAX.to_be_bytes());
+    let file_size: u64 = bytes.len() as u64;
+    let mut file_reader = FileReader::from_reader(std::io::Cursor::new(bytes)).unwrap();
+    assert_eq!(Some(std::i32::MAX as usize),            file_reader.data_set().num_records());
+    // the data are not allocated
+    assert!(matches!(
+        file_reader.read_var(TEMP_F64_VAR_NAME),
+        Err(ReadError::VariableDataOutOfFile{ref var_name, file_size: size, ..}) if var_name == TEMP_F64_VAR_NAME && size == file_size
+    ));
+    assert!(matches!(file_reader.read_all_vars(),                       Err(ReadError::VariableDataOutOfFile{..})));
+    // the stored records and the *fixed-size* variables are still readable
+    assert_eq!(TEMP_F64_VAR_LEN / 2,                    file_reader.read_record_f64(TEMP_F64_VAR_NAME, 0).unwrap().len());
+    assert_eq!(vec![0.0, 0.5, 1.0],                     file_reader.read_var_f32("latitude").unwrap());
+
+    // The last record is partially written
+    let truncated_bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES[..(NC3_CLASSIC_FILE_BYTES.len() - 100)].to_vec();
+    let mut file_reader = FileReader::from_reader(std::io::Cursor::new(truncated_bytes)).unwrap();
+    assert_eq!(
+        ReadError::VariableDataOutOfFile{var_name: String::from(TEMP_F64_VAR_NAME), end_offset: 2_332, file_size: 2_232},
+        file_reader.read_var(TEMP_F64_VAR_NAME).unwrap_err()
+    );
+}
+
+#[test]
+fn test_file_reader_overlapping_vars() {
+    const HEADER_SIZE: usize = 1684;
+    // move the data of `longitude` (begin offset 1696) on the data of `latitude` (begin offset 1684, 12 bytes)
+    let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+    let position: usize = bytes[0..HEADER_SIZE].windows(4).rposition(|window: &[u8]| window == &1696_u32.to_be_bytes()[..]).unwrap();
+    bytes[position..(position + 4)].copy_from_slice(&1688_u32.to_be_bytes());
+    assert_eq!(
+        ReadError::OverlappingVariables{
+            var_name: String::from("longitude"),
+            begin_offset: 1688,
+            prev_var_name: String::from("latitude"),
+            prev_end_offset: 1696,
+        },
+        FileReader::from_reader(std::io::Cursor::new(bytes.clone())).unwrap_err()
+    );
+
+    // the overlap is reported by a diagnostic
+    let options = ReadOptions{allow_overlapping_vars: true, ..ReadOptions::default()};
+    let file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(bytes), &options).unwrap();
+    assert_eq!(
+        Some(&Diagnostic::Layout(LayoutViolation::DataOverlap{var_name: String::from("longitude"), prev_var_name: String::from("latitude")})),
+        file_reader.diagnostics().iter().next()
+    );
+}
+
+#[test]
+fn test_file_reader_padding_check() {
+    // Write a global attribute `i8` padded with junk bytes
+    let bytes: Vec<u8> = {
+        let mut data_set = DataSet::new();
+        data_set.add_global_attr_i8("attr", vec![1]).unwrap();
+        let mut file_writer = crate::FileWriter::from_writer(std::io::Cursor::new(vec![]));
+        file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+        let mut bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+        assert_eq!(&[1, 0, 0, 0],               &bytes[(bytes.len() - 12)..(bytes.len() - 8)]);
+        let padding_start: usize = bytes.len() - 11;
+        bytes[padding_start..(padding_start + 3)].copy_from_slice(&[0xAA, 0xBB, 0xCC]);
+        bytes
+    };
+    let padding_offset: u64 = (bytes.len() - 11) as u64;
+
+    // Default : the non-zero padding is reported
+    let file_reader = FileReader::from_reader(std::io::Cursor::new(bytes.clone())).unwrap();
+    assert_eq!(Some(vec![1]),                   file_reader.data_set().get_global_attr_i8("attr").map(|data: &[i8]| data.to_vec()));
+    assert_eq!(
+        vec![Diagnostic::PaddingNotZero{offset: padding_offset, bytes: vec![0xAA, 0xBB, 0xCC]}],
+        file_reader.diagnostics().iter().cloned().collect::<Vec<Diagnostic>>()
+    );
+
+    // Strict mode
+    let options = ReadOptions{padding_check: PaddingCheck::Strict, ..ReadOptions::default()};
+    let parsing_err: ReadError = FileReader::from_reader_with_options(std::io::Cursor::new(bytes.clone()), &options).unwrap_err();
+    match parsing_err {
+        ReadError::ParseHeader(err) => assert_eq!(ParseHeaderErrorKind::ZeroPadding,   err.kind),
+        _ => panic!("Unexpected error: {:?}", parsing_err),
+    }
+
+    // Permissive mode
+    let options = ReadOptions{padding_check: PaddingCheck::Ignore, ..ReadOptions::default()};
+    let file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(bytes), &options).unwrap();
+    assert_eq!(Some(vec![1]),                   file_reader.data_set().get_global_attr_i8("attr").map(|data: &[i8]| data.to_vec()));
+    assert_eq!(true,                            file_reader.diagnostics().is_empty());
+}
+
+#[test]
+fn test_parse_typed_data_elements() {
+    // 3 `i16` elements, followed by 2 zero padding bytes and an other byte
+    let bytes: Vec<u8> = vec![0x00, 0x01, 0xff, 0xfe, 0x7f, 0xff, 0x00, 0x00, 0x2a];
+    let mut input = HeaderDecoder::new(&bytes[..]);
+    let data: DataVector = header::parse_typed_data_elements(&mut input, 3, DataType::I16, &mut HeaderParseState::new(NameDecoder::new(NameDecoding::Utf8, NameValidation::Strict))).unwrap();
+    let rem_bytes: &[u8] = input.remaining();
+    assert_eq!(DataVector::I16(vec![1, -2, i16::MAX]),      data);
+    assert_eq!(&[0x2a][..],                                 rem_bytes);
+
+    let bytes: Vec<u8> = [1.5_f64.to_be_bytes(), (-0.25_f64).to_be_bytes()].concat();
+    let mut input = HeaderDecoder::new(&bytes[..]);
+    let data: DataVector = header::parse_typed_data_elements(&mut input, 2, DataType::F64, &mut HeaderParseState::new(NameDecoder::new(NameDecoding::Utf8, NameValidation::Strict))).unwrap();
+    let rem_bytes: &[u8] = input.remaining();
+    assert_eq!(DataVector::F64(vec![1.5, -0.25]),           data);
+    assert_eq!(true,                                        rem_bytes.is_empty());
+
+    let bytes: Vec<u8> = vec![0x81, 0x7f, 0x00, 0x00];
+    let mut input = HeaderDecoder::new(&bytes[..]);
+    let data: DataVector = header::parse_typed_data_elements(&mut input, 2, DataType::I8, &mut HeaderParseState::new(NameDecoder::new(NameDecoding::Utf8, NameValidation::Strict))).unwrap();
+    assert_eq!(DataVector::I8(vec![-127, 127]),             data);
+
+    // Missing elements
+    let parsing_err: ParseHeaderError = header::parse_typed_data_elements(&mut HeaderDecoder::new(&bytes[..]), 2, DataType::F32, &mut HeaderParseState::new(NameDecoder::new(NameDecoding::Utf8, NameValidation::Strict))).unwrap_err();
+    assert_eq!(true,                                        parsing_err.header_is_incomplete());
+    assert_eq!(ParseHeaderErrorKind::DataElements,          parsing_err.kind);
+}
+
+#[test]
+fn test_file_reader_reused_buffers() {
+    let mut file_reader = FileReader::from_reader(std::io::Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+
+    // The allocation of the vector is reused for the same data type
+    let mut data_vec = DataVector::F64(Vec::with_capacity(TEMP_F64_VAR_LEN));
+    let data_ptr: *const f64 = data_vec.get_f64().unwrap().as_ptr();
+    file_reader.read_var_into(TEMP_F64_VAR_NAME, &mut data_vec).unwrap();
+    assert_eq!(Some(&TEMP_F64_VAR_DATA[..]),        data_vec.get_f64());
+    assert_eq!(data_ptr,                            data_vec.get_f64().unwrap().as_ptr());
+    // and replaced for an other data type
+    file_reader.read_var_into(TEMP_I8_VAR_NAME, &mut data_vec).unwrap();
+    assert_eq!(Some(&TEMP_I8_VAR_DATA[..]),         data_vec.get_i8());
+    assert_eq!(
+        ReadError::VariableNotDefined(String::from("undef_var")),
+        file_reader.read_var_into("undef_var", &mut data_vec).unwrap_err()
+    );
+
+    // One internal buffer is kept per data type
+    let data_ptr: *const f64 = file_reader.read_var_buffered(TEMP_F64_VAR_NAME).unwrap().get_f64().unwrap().as_ptr();
+    assert_eq!(Some(&TEMP_I32_VAR_DATA[..]),        file_reader.read_var_buffered(TEMP_I32_VAR_NAME).unwrap().get_i32());
+    assert_eq!(Some(&TEMP_F64_VAR_DATA[..]),        file_reader.read_var_buffered(TEMP_F64_VAR_NAME).unwrap().get_f64());
+    assert_eq!(data_ptr,                            file_reader.read_var_buffered(TEMP_F64_VAR_NAME).unwrap().get_f64().unwrap().as_ptr());
+    assert_eq!(2,                                   file_reader.buffers.len());
+
+    // The memory ceiling is still checked
+    file_reader.set_max_alloc_bytes(Some(8));
+    assert_eq!(
+        ReadError::MaximumAllocationExceeded{var_name: String::from(TEMP_F64_VAR_NAME), max: 8, get: 8 * TEMP_F64_VAR_LEN},
+        file_reader.read_var_buffered(TEMP_F64_VAR_NAME).unwrap_err()
+    );
+    assert_eq!(2,                                   file_reader.buffers.len());
+
+    file_reader.clear_buffers();
+    assert_eq!(true,                                file_reader.buffers.is_empty());
+}
+
+#[test]
+fn test_file_reader_read_var_bytes() {
+    use std::borrow::Cow;
+    use std::io::Cursor;
+    use crate::FileWriter;
+
+    // The interleaved records are gathered into a new vector
+    {
+        let file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        let bytes: Cow<[u8]> = file_reader.read_var_bytes(TEMP_U8_VAR_NAME).unwrap();
+        assert_eq!(true,                                matches!(bytes, Cow::Owned(_)));
+        assert_eq!(&TEMP_U8_VAR_DATA[..],               &bytes[..]);
+        let bytes: Cow<[u8]> = file_reader.read_var_bytes(TEMP_I8_VAR_NAME).unwrap();
+        assert_eq!(&TEMP_I8_VAR_DATA[..],               &bytes.iter().map(|byte: &u8| *byte as i8).collect::<Vec<i8>>()[..]);
+        assert_eq!(
+            ReadError::VariableMismatchDataType{var_name: String::from(TEMP_F64_VAR_NAME), req: DataType::F64, get: DataType::U8},
+            file_reader.read_var_bytes(TEMP_F64_VAR_NAME).unwrap_err()
+        );
+        assert_eq!(
+            ReadError::VariableNotDefined(String::from("undef_var")),
+            file_reader.read_var_bytes("undef_var").unwrap_err()
+        );
+    }
+
+    // The fixed-size variables and the records of a single record variable are borrowed from the input
+    let bytes: Vec<u8> = {
+        let mut data_set = DataSet::new();
+        data_set.add_fixed_dim("x", 5).unwrap();
+        data_set.set_unlimited_dim("time", 3).unwrap();
+        data_set.add_var_u8("image", &["x"]).unwrap();
+        data_set.add_var_i8("series", &["time"]).unwrap();
+        let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+        file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+        file_writer.write_var_u8("image", &[1, 2, 3, 4, 5]).unwrap();
+        file_writer.write_var_i8("series", &[-1, 0, 1]).unwrap();
+        file_writer.close_into_inner().unwrap().into_inner()
+    };
+    let mut file_reader = FileReader::from_reader(Cursor::new(&bytes[..])).unwrap();
+    let image: Cow<[u8]> = file_reader.read_var_bytes("image").unwrap();
+    assert_eq!(true,                                    matches!(image, Cow::Borrowed(_)));
+    assert_eq!(&[1_u8, 2, 3, 4, 5][..],                 &image[..]);
+    let series: Cow<[u8]> = file_reader.read_var_bytes("series").unwrap();
+    assert_eq!(true,                                    matches!(series, Cow::Borrowed(_)));
+    assert_eq!(&[255_u8, 0, 1][..],                     &series[..]);
+
+    // The borrowed views do not allocate memory
+    file_reader.set_max_alloc_bytes(Some(1));
+    assert_eq!(&[1_u8, 2, 3, 4, 5][..],                 &file_reader.read_var_bytes("image").unwrap()[..]);
+
+    // The truncated inputs are rejected
+    let file_reader = FileReader::from_reader(Cursor::new(&bytes[..bytes.len() - 1])).unwrap();
+    assert_eq!(true,                                    matches!(file_reader.read_var_bytes("series"), Err(ReadError::IO(_))));
+}
+
+#[test]
+fn test_file_reader_shared_reads() {
+    let (tmp_dir, input_file_path) = copy_bytes_to_tmp_file(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    let mut file_reader: FileReader = FileReader::open(input_file_path).unwrap();
+
+    // Same data as the reads through `seek`
+    for var_name in file_reader.data_set().get_var_names() {
+        let data: DataVector = file_reader.read_var_shared(&var_name).unwrap();
+        assert_eq!(file_reader.read_var(&var_name).unwrap(),            data);
+        for record_index in 0..file_reader.data_set().num_records().unwrap() {
+            let record: Result<DataVector, ReadError> = file_reader.read_record_shared(&var_name, record_index);
+            assert_eq!(file_reader.read_record(&var_name, record_index),    record);
+        }
+    }
+    assert_eq!(Some(&TEMP_F64_VAR_DATA[..]),                            file_reader.read_var_shared(TEMP_F64_VAR_NAME).unwrap().get_f64());
+    assert_eq!(
+        ReadError::VariableNotDefined(String::from("undef_var")),
+        file_reader.read_var_shared("undef_var").unwrap_err()
+    );
+    assert_eq!(
+        ReadError::RecordIndexExceeded{index: 2, num_records: 2},
+        file_reader.read_record_shared(TEMP_F64_VAR_NAME, 2).unwrap_err()
+    );
+
+    // The memory ceiling is still checked
+    file_reader.set_max_alloc_bytes(Some(8));
+    assert_eq!(
+        ReadError::MaximumAllocationExceeded{var_name: String::from(TEMP_F64_VAR_NAME), max: 8, get: 8 * TEMP_F64_VAR_LEN},
+        file_reader.read_var_shared(TEMP_F64_VAR_NAME).unwrap_err()
+    );
+    tmp_dir.close().unwrap();
+}
+
+#[cfg(any(unix, windows))]
+#[test]
+fn test_file_reader_positional_reads() {
+    let (tmp_dir, input_file_path) = copy_bytes_to_tmp_file(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    let mut file_reader: FileReader = FileReader::open(input_file_path).unwrap();
+    file_reader.reset_io_metrics();
+
+    // The chunks are read without any seek
+    assert_eq!(TEMP_F64_VAR_DATA.to_vec(),                  file_reader.read_var_f64(TEMP_F64_VAR_NAME).unwrap());
+    assert_eq!(TEMP_F64_VAR_DATA[15..30].to_vec(),          file_reader.read_record_f64(TEMP_F64_VAR_NAME, 1).unwrap());
+    assert_eq!(vec![0.5, 1.0],                              file_reader.read_var_slice("latitude", &[1], &[2]).unwrap().get_f32_into().unwrap());
+    assert_eq!(vec![0.0, 0.5, 1.0],                         file_reader.read_var_f32("latitude").unwrap());
+    assert_eq!(0,                                           file_reader.io_metrics().seeks);
+    assert_eq!(4,                                           file_reader.io_metrics().chunks_read);
+    tmp_dir.close().unwrap();
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_file_reader_read_var_parallel() {
+    // The records are split across the threads
+    {
+        let (tmp_dir, input_file_path) = copy_bytes_to_tmp_file(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+        let mut file_reader: FileReader = FileReader::open(input_file_path).unwrap();
+        for var_name in file_reader.data_set().get_var_names() {
+            let data: DataVector = file_reader.read_var(&var_name).unwrap();
+            for num_threads in [0, 1, 2, 3].iter() {
+                assert_eq!(data,                        file_reader.read_var_parallel(&var_name, *num_threads).unwrap());
+            }
+        }
+        assert_eq!(
+            ReadError::VariableNotDefined(String::from("undef_var")),
+            file_reader.read_var_parallel("undef_var", 2).unwrap_err()
+        );
+        file_reader.set_max_alloc_bytes(Some(8));
+        assert_eq!(
+            ReadError::MaximumAllocationExceeded{var_name: String::from(TEMP_F64_VAR_NAME), max: 8, get: 8 * TEMP_F64_VAR_LEN},
+            file_reader.read_var_parallel(TEMP_F64_VAR_NAME, 2).unwrap_err()
+        );
+        tmp_dir.close().unwrap();
+    }
+
+    // Many records, interleaved with an other record variable
+    {
+        const NUM_RECORDS: usize = 1000;
+        let tmp_dir = tempdir::TempDir::new("netcdf3_tests_").unwrap();
+        let file_path = tmp_dir.path().join("records.nc");
+        let series: Vec<f64> = (0..(2 * NUM_RECORDS)).map(|i: usize| i as f64).collect();
+        let flags: Vec<i8> = (0..NUM_RECORDS).map(|i: usize| (i % 100) as i8).collect();
+        {
+            let mut data_set = DataSet::new();
+            data_set.set_unlimited_dim("time", NUM_RECORDS).unwrap();
+            data_set.add_fixed_dim("pair", 2).unwrap();
+            data_set.add_var_f64("series", &["time", "pair"]).unwrap();
+            data_set.add_var_i8("flags", &["time"]).unwrap();
+            let mut file_writer = crate::FileWriter::create_new(&file_path).unwrap();
+            file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+            file_writer.write_var_f64("series", &series).unwrap();
+            file_writer.write_var_i8("flags", &flags).unwrap();
+            file_writer.close().unwrap();
+        }
+        let file_reader: FileReader = FileReader::open(&file_path).unwrap();
+        for num_threads in [1, 4, 7, 2 * NUM_RECORDS].iter() {
+            assert_eq!(Some(&series[..]),               file_reader.read_var_parallel("series", *num_threads).unwrap().get_f64());
+            assert_eq!(Some(&flags[..]),                file_reader.read_var_parallel("flags", *num_threads).unwrap().get_i8());
+        }
+        tmp_dir.close().unwrap();
+    }
+}
+
+#[test]
+fn test_file_reader_lazy_attrs() {
+    let mut data_set = DataSet::new();
+    data_set.add_fixed_dim("latitude", 2).unwrap();
+    data_set.add_var_f32("latitude", &["latitude"]).unwrap();
+    data_set.add_var_attr_f32("latitude", "valid_range", vec![-90.0, 90.0]).unwrap();
+    data_set.add_var_attr_string("latitude", "units", "degree_north").unwrap();
+    data_set.add_global_attr_i16("version", vec![1, 2]).unwrap();
+    let bytes: Vec<u8> = {
+        let mut file_writer = crate::FileWriter::from_writer(std::io::Cursor::new(vec![]));
+        file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+        file_writer.close_into_inner().unwrap().into_inner()
+    };
+
+    let options = ReadOptions{lazy_attrs: true, ..ReadOptions::default()};
+    let file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(bytes), &options).unwrap();
+    // the values are kept as raw bytes while reading the header
+    let valid_range_attr: &Attribute = file_reader.data_set().get_var("latitude").unwrap().get_attr("valid_range").unwrap();
+    assert_eq!(false,                       valid_range_attr.is_decoded());
+    assert_eq!(DataType::F32,               valid_range_attr.data_type());
+    assert_eq!(2,                           valid_range_attr.len());
+    assert_eq!(false,                       valid_range_attr.is_decoded());
+    // and decoded at the first access
+    assert_eq!(Some(&[-90.0_f32, 90.0][..]),    valid_range_attr.get_f32());
+    assert_eq!(true,                        valid_range_attr.is_decoded());
+    // the `u8` values are the raw bytes
+    let units_attr: &Attribute = file_reader.data_set().get_var("latitude").unwrap().get_attr("units").unwrap();
+    assert_eq!(true,                        units_attr.is_decoded());
+    assert_eq!(Some(String::from("degree_north")),  units_attr.get_as_string());
+    // the cloned attributes are decoded independently
+    let version_attr: Attribute = file_reader.data_set().get_global_attr("version").unwrap().clone();
+    assert_eq!(Some(&[1_i16, 2][..]),       version_attr.get_i16());
+    assert_eq!(true,                        version_attr.is_decoded());
+
+    assert_eq!(false,                       file_reader.data_set().get_global_attr("version").unwrap().is_decoded());
+    assert_eq!(&data_set,                   file_reader.data_set());
+    assert_eq!(true,                        data_set.get_global_attr("version").unwrap().is_decoded());
+}
+
+#[test]
+fn test_file_reader_contiguous_records() {
+    struct CountingReader {
+        inner: std::io::Cursor<Vec<u8>>,
+        num_reads: usize,
+    }
+
+    impl std::io::Read for CountingReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.num_reads += 1;
+            return self.inner.read(buf);
+        }
+    }
+
+    impl std::io::Seek for CountingReader {
+        fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+            return self.inner.seek(pos);
+        }
+    }
+
+    const NUM_RECORDS: usize = 10;
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim("unlimited_dim", NUM_RECORDS).unwrap();
+    data_set.add_fixed_dim("fixed_dim", 3).unwrap();
+    data_set.add_var_i16("var_1", &["unlimited_dim", "fixed_dim"]).unwrap();
+    let data: Vec<i16> = (0..(3 * NUM_RECORDS as i16)).collect();
+    let mut file_writer = crate::FileWriter::from_writer(std::io::Cursor::new(vec![]));
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.write_var_i16("var_1", &data).unwrap();
+    let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+
+    // The records of the only record variable are read at once, even with a small buffer
+    let options = ReadOptions{buffer_size: 1, ..ReadOptions::default()};
+    let mut file_reader = FileReader::from_reader_with_options(CountingReader{inner: std::io::Cursor::new(bytes), num_reads: 0}, &options).unwrap();
+    let num_reads: usize = file_reader.input_file.get_ref().num_reads;
+    assert_eq!(data,                        file_reader.read_var_i16("var_1").unwrap());
+    assert_eq!(1,                           file_reader.input_file.get_ref().num_reads - num_reads);
+}
+
+#[test]
+fn test_file_reader_parse_header_error_offset() {
+    fn parse_header_err(bytes: Vec<u8>) -> ParseHeaderError {
+        return match FileReader::from_reader(std::io::Cursor::new(bytes)).unwrap_err() {
+            ReadError::ParseHeader(err) => err,
+            err => panic!("unexpected error: {:?}", err),
+        };
+    }
+
+    // Not valid version number
+    let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+    bytes[3] = 3;
+    let parsing_err: ParseHeaderError = parse_header_err(bytes);
+    assert_eq!(ParseHeaderErrorKind::VersionNumber,     parsing_err.kind);
+    assert_eq!(InvalidBytes::Bytes(vec![3]),            parsing_err.invalid_bytes);
+    assert_eq!(3,                                       parsing_err.offset);
+
+    // Not valid dimension tag
+    let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+    bytes[11] = 0x0B;
+    let parsing_err: ParseHeaderError = parse_header_err(bytes);
+    assert_eq!(ParseHeaderErrorKind::DimTag,            parsing_err.kind);
+    assert_eq!(8,                                       parsing_err.offset);
+
+    // Truncated header, the bytes may begin the absent tag (8 bytes)
+    let bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES[0..10].to_vec();
+    let parsing_err: ParseHeaderError = parse_header_err(bytes);
+    assert_eq!(true,                                    parsing_err.header_is_incomplete());
+    assert_eq!(ParseHeaderErrorKind::DimTag,            parsing_err.kind);
+    assert_eq!(InvalidBytes::Incomplete(Needed::Size(NonZeroUsize::new(6).unwrap())),   parsing_err.invalid_bytes);
+    assert_eq!(8,                                       parsing_err.offset);
+}
+
+#[test]
+fn test_file_reader_var_offsets() {
+    let file_reader = FileReader::from_reader(std::io::Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+    assert_eq!(file_reader.data_set().num_vars(),           file_reader.var_offsets.len());
+
+    // fixed-size variable
+    let (var, var_offsets): (&Variable, super::VarOffsets) = file_reader.find_var("latitude").unwrap();
+    assert_eq!("latitude",                                  var.name());
+    assert_eq!(super::VarOffsets{begin_offset: 1684, stride: 0},       var_offsets);
+    assert_eq!(Some(1684),                                  var_offsets.chunk_offset(0));
+
+    // record variable
+    let (var, var_offsets): (&Variable, super::VarOffsets) = file_reader.find_var("time").unwrap();
+    assert_eq!("time",                                      var.name());
+    assert_eq!(super::VarOffsets{begin_offset: 1716, stride: 308},     var_offsets);
+    assert_eq!(Some(1716 + 308),                            var_offsets.chunk_offset(1));
+
+    // each variable is located like in the header
+    for var in file_reader.data_set().vars().iter() {
+        let var_info = file_reader.vars_info.iter().find(|var_info| var_info.name == var.name()).unwrap();
+        assert_eq!(Some(i64::from(var_info.begin_offset.clone()) as u64),    file_reader.var_begin_offset(var.name()));
+    }
+
+    assert_eq!(ReadError::VariableNotDefined(String::from("undef_var")),     file_reader.find_var("undef_var").unwrap_err());
+}
+
+#[test]
+fn test_file_reader_buffer_size() {
+    let file_reader = FileReader::from_reader(std::io::Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+    assert_eq!(header::READ_BUFFER_SIZE,     file_reader.buffer_size());
+    let mut file_reader = file_reader;
+    let expected_vars = file_reader.read_all_vars().unwrap();
+
+    // the header is loaded by small blocks, and the records read one by one or by batches
+    for buffer_size in [0, 16, 100, 200, 1000].iter() {
+        let options = ReadOptions{buffer_size: *buffer_size, ..ReadOptions::default()};
+        let mut file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(NC3_CLASSIC_FILE_BYTES), &options).unwrap();
+        assert_eq!(std::cmp::max(*buffer_size, 1),  file_reader.buffer_size());
+        assert_eq!(expected_vars,               file_reader.read_all_vars().unwrap());
+    }
+
+    let mut file_reader = FileReader::from_reader(std::io::Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+    file_reader.set_buffer_size(0);
+    assert_eq!(1,                           file_reader.buffer_size());
+    assert_eq!(expected_vars,               file_reader.read_all_vars().unwrap());
+}
+
+#[test]
+fn test_file_reader_header_loaded_once() {
+    /// Reader failing after `max_bytes` bytes.
+    #[derive(Debug)]
+    struct FailingReader {
+        inner: std::io::Cursor<&'static [u8]>,
+        max_bytes: u64,
+    }
+
+    impl std::io::Read for FailingReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.inner.position() >= self.max_bytes {
+                return Err(std::io::Error::new(std::io::ErrorKind::Other, "read failure"));
+            }
+            return self.inner.read(buf);
+        }
+    }
+
+    impl std::io::Seek for FailingReader {
+        fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+            return self.inner.seek(pos);
+        }
+    }
+
+    // The header is decoded while its bytes are read, each byte being read once
+    for buffer_size in [1, 16, 100].iter() {
+        let options = ReadOptions{buffer_size: *buffer_size, ..ReadOptions::default()};
+        let file_reader = FileReader::from_reader_with_options(std::io::Cursor::new(NC3_CLASSIC_FILE_BYTES), &options).unwrap();
+        let num_loaded_bytes: u64 = file_reader.io_metrics().bytes_read;
+        assert_eq!(true,                        num_loaded_bytes >= 1_684);
+        assert_eq!(true,                        num_loaded_bytes < 1_684 + (*buffer_size as u64));
+    }
+
+    // The I/O errors occuring while loading the header are returned
+    let input = FailingReader{inner: std::io::Cursor::new(NC3_CLASSIC_FILE_BYTES), max_bytes: 100};
+    let options = ReadOptions{buffer_size: 16, ..ReadOptions::default()};
+    match FileReader::from_reader_with_options(input, &options).unwrap_err() {
+        ReadError::IO(io_err) => assert_eq!(std::io::ErrorKind::Other, io_err.kind()),
+        err => panic!("unexpected error: {:?}", err),
+    }
+}
+
+#[test]
+fn test_file_reader_read_var_slice() {
+    let mut file_reader = FileReader::from_reader(std::io::Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+
+    // fixed-size variable
+    assert_eq!(DataVector::F32(vec![0.5, 1.0]),             file_reader.read_var_slice("latitude", &[1], &[2]).unwrap());
+    // record variable : (time: 2, latitude: 3, longitude: 5)
+    assert_eq!(DataVector::I16(TEMP_I16_VAR_DATA.to_vec()), file_reader.read_var_slice(TEMP_I16_VAR_NAME, &[0, 0, 0], &[2, 3, 5]).unwrap());
+    assert_eq!(DataVector::I16(vec![1, 16]),                file_reader.read_var_slice(TEMP_I16_VAR_NAME, &[0, 0, 1], &[2, 1, 1]).unwrap());
+    assert_eq!(DataVector::I16(vec![8, 9, 13, 14, 23, 24, 28, 29]),
+                                                            file_reader.read_var_slice(TEMP_I16_VAR_NAME, &[0, 1, 3], &[2, 2, 2]).unwrap());
+    assert_eq!(DataVector::F64(vec![]),                     file_reader.read_var_slice(TEMP_F64_VAR_NAME, &[1, 0, 0], &[1, 0, 5]).unwrap());
+    // record variable defined only over the unlimited-size dimension, the records are not contiguous
+    assert_eq!(file_reader.read_var("time").unwrap(),      file_reader.read_var_slice("time", &[0], &[2]).unwrap());
+    assert_eq!(file_reader.read_record("time", 1).unwrap(), file_reader.read_var_slice("time", &[1], &[1]).unwrap());
+
+    // invalid slices
+    assert_eq!(
+        ReadError::SliceOutOfBounds{var_name: String::from("latitude"), start: vec![2], count: vec![2], shape: vec![3]},
+        file_reader.read_var_slice("latitude", &[2], &[2]).unwrap_err()
+    );
+    assert_eq!(
+        ReadError::SliceOutOfBounds{var_name: String::from("latitude"), start: vec![0, 0], count: vec![1, 1], shape: vec![3]},
+        file_reader.read_var_slice("latitude", &[0, 0], &[1, 1]).unwrap_err()
+    );
+    assert_eq!(
+        ReadError::SliceOutOfBounds{var_name: String::from("latitude"), start: vec![usize::MAX], count: vec![1], shape: vec![3]},
+        file_reader.read_var_slice("latitude", &[usize::MAX], &[1]).unwrap_err()
+    );
+    assert_eq!(ReadError::VariableNotDefined(String::from("undef_var")),    file_reader.read_var_slice("undef_var", &[], &[]).unwrap_err());
+
+    // the allocation is limited to the slice
+    file_reader.set_max_alloc_bytes(Some(8));
+    assert_eq!(DataVector::F64(vec![4.0]),                  file_reader.read_var_slice(TEMP_F64_VAR_NAME, &[0, 0, 4], &[1, 1, 1]).unwrap());
+    assert_eq!(
+        ReadError::MaximumAllocationExceeded{var_name: String::from(TEMP_F64_VAR_NAME), max: 8, get: 16},
+        file_reader.read_var_slice(TEMP_F64_VAR_NAME, &[0, 0, 3], &[1, 1, 2]).unwrap_err()
+    );
+}
+
+#[test]
+fn test_file_reader_read_var_slice_scalar() {
+    let mut data_set = DataSet::new();
+    data_set.add_var_i32::<&str>("scalar", &[]).unwrap();
+    let mut file_writer = crate::FileWriter::from_writer(std::io::Cursor::new(Vec::<u8>::new()));
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.write_var_i32("scalar", &[42]).unwrap();
+    let mut file_reader = FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+
+    assert_eq!(DataVector::I32(vec![42]),                   file_reader.read_var_slice("scalar", &[], &[]).unwrap());
+    assert_eq!(true,                                        file_reader.read_var_slice("scalar", &[0], &[1]).is_err());
+}
+
+#[test]
+fn test_file_reader_variable() {
+    let mut file_reader = FileReader::from_reader(std::io::Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+    assert_eq!(ReadError::VariableNotDefined(String::from("undef_var")),   file_reader.variable("undef_var").unwrap_err());
+
+    let mut var = file_reader.variable(TEMP_I32_VAR_NAME).unwrap();
+    assert_eq!(TEMP_I32_VAR_NAME,                           var.name());
+    assert_eq!(TEMP_I32_VAR_NAME,                           var.variable().name());
+    assert_eq!(DataType::I32,                               var.data_type());
+    assert_eq!(vec![2, 3, 5],                               var.shape());
+    assert_eq!(var.variable().attrs(),                      var.attrs());
+    assert_eq!(Some(String::from("Celsius")),               var.attr("units").and_then(|attr: &Attribute| attr.as_string()));
+    assert_eq!(None,                                        var.attr("undef_attr"));
+
+    assert_eq!(DataVector::I32(TEMP_I32_VAR_DATA.to_vec()), var.read().unwrap());
+    assert_eq!(DataVector::I32(TEMP_I32_VAR_DATA[15..30].to_vec()),     var.read_record(1).unwrap());
+    assert_eq!(DataVector::I32(vec![5, 6]),                 var.read_slice(&[0, 1, 0], &[1, 1, 2]).unwrap());
+    let mut data_vec = DataVector::I32(vec![]);
+    var.read_into(&mut data_vec).unwrap();
+    assert_eq!(DataVector::I32(TEMP_I32_VAR_DATA.to_vec()), data_vec);
+    assert_eq!(
+        ReadError::RecordIndexExceeded{index: 2, num_records: 2},
+        var.read_record(2).unwrap_err()
+    );
+}
+
+#[test]
+fn test_file_reader_io_metrics() {
+    let mut file_reader = FileReader::from_reader(std::io::Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+    // the whole file fits into the buffer of the header parsing
+    assert_eq!(NC3_CLASSIC_FILE_BYTES.len() as u64,        file_reader.io_metrics().bytes_read);
+    assert_eq!(2,                                           file_reader.io_metrics().seeks);
+
+    file_reader.reset_io_metrics();
+    assert_eq!(IoMetrics::default(),                        file_reader.io_metrics());
+
+    // one record of 15 `i16` values
+    file_reader.read_record(TEMP_I16_VAR_NAME, 1).unwrap();
+    assert_eq!(
+        IoMetrics{bytes_read: 30, seeks: 1, chunks_read: 1, ..IoMetrics::default()},
+        file_reader.io_metrics()
+    );
+
+    // a fixed-size variable, and a record variable of 2 records
+    file_reader.reset_io_metrics();
+    file_reader.read_var("latitude").unwrap();
+    file_reader.read_var(TEMP_F32_VAR_NAME).unwrap();
+    assert_eq!(3,                                           file_reader.io_metrics().chunks_read);
+
+    // the partial chunks are not counted
+    file_reader.reset_io_metrics();
+    file_reader.read_var_slice(TEMP_I16_VAR_NAME, &[0, 0, 1], &[2, 1, 1]).unwrap();
+    assert_eq!(
+        IoMetrics{bytes_read: 4, seeks: 2, chunks_read: 0, ..IoMetrics::default()},
+        file_reader.io_metrics()
+    );
+}
+
+#[test]
+fn test_file_reader_file_and_header_sizes() {
+    // header without reserved bytes
+    let file_reader = FileReader::from_reader(std::io::Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+    assert_eq!(NC3_CLASSIC_FILE_BYTES.len(),                file_reader.file_size());
+    assert_eq!(1684,                                        file_reader.data_section_offset());
+    assert_eq!(Some(1684),                                  file_reader.var_begin_offset("latitude"));
+    assert_eq!(1684,                                        file_reader.header_size());
+    assert_eq!(1684,                                        file_reader.header_used_size());
+
+    // header followed by reserved bytes
+    let (data_set, version): (DataSet, Version) = file_reader.close();
+    let mut file_writer = crate::FileWriter::from_writer(std::io::Cursor::new(Vec::<u8>::new()));
+    file_writer.set_def(&data_set, version, 4096).unwrap();
+    let file_reader = FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+    assert_eq!(4096,                                        file_reader.data_section_offset());
+    assert_eq!(4096,                                        file_reader.header_size());
+    assert_eq!(crate::io::compute_header_required_size(&data_set, version),   file_reader.header_used_size());
+
+    // without variable, the whole file is the header and its reserved bytes
+    let file_reader = FileReader::from_reader(std::io::Cursor::new(EMPTY_DATA_SET_FILE_BYTES)).unwrap();
+    assert_eq!(EMPTY_DATA_SET_FILE_BYTES.len(),             file_reader.file_size());
+    assert_eq!(32,                                          file_reader.header_used_size());
+    assert_eq!(EMPTY_DATA_SET_FILE_BYTES.len(),             file_reader.header_size());
+    assert_eq!(EMPTY_DATA_SET_FILE_BYTES.len(),             file_reader.data_section_offset());
+}
+
+#[test]
+fn test_file_reader_unlimited_dim() {
+    // zero-sized unlimited dimension, without record variable
+    let file_reader = FileReader::from_reader(std::io::Cursor::new(NC3_ZERO_SIZED_UNLIMITED_DIM_FILE_BYTES)).unwrap();
+    assert_eq!(Some(0),                                     file_reader.num_records());
+    assert_eq!(Some(String::from("unlim_dim")),             file_reader.unlimited_dim_name());
+    assert_eq!(false,                                       file_reader.has_record_vars());
+
+    // streaming number of records
+    let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+    bytes[4..8].copy_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF]);
+    let file_reader = FileReader::from_reader(std::io::Cursor::new(bytes)).unwrap();
+    assert_eq!(true,                                        file_reader.numrecs_is_streaming());
+    assert_eq!(Some(2),                                     file_reader.num_records());
+}
+
+#[cfg(feature = "fs")]
+#[test]
+fn test_file_reader_export_raw_vars() {
+    use std::io::Cursor;
+    use crate::FileWriter;
+
+    let bytes: Vec<u8> = {
+        let mut data_set = DataSet::new();
+        data_set.add_fixed_dim("x", 2).unwrap();
+        data_set.add_var_i16("global_attributes", &["x"]).unwrap();
+        data_set.add_global_attr_string("title", "example").unwrap();
+        let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+        file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+        file_writer.write_var_i16("global_attributes", &[1, 2]).unwrap();
+        file_writer.close_into_inner().unwrap().into_inner()
+    };
+    let tmp_dir = tempdir::TempDir::new("netcdf3_tests_").unwrap();
+    let output_dir = tmp_dir.path().join("raw");
+    let mut file_reader = FileReader::from_reader(Cursor::new(bytes)).unwrap();
+    file_reader.export_raw_vars(&output_dir).unwrap();
+
+    // the variable `global_attributes` does not overwrite the global attributes
+    assert_eq!(4,                                       std::fs::metadata(output_dir.join("global_attributes.bin")).unwrap().len());
+    assert_eq!(true,                                    std::fs::read_to_string(output_dir.join("global_attributes.json")).unwrap().contains("\"shape\": [2]"));
+    assert_eq!("{\n  \"title\": \"example\"\n}\n",      std::fs::read_to_string(output_dir.join("%global_attributes.json")).unwrap());
+    tmp_dir.close().unwrap();
+}
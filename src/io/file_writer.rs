@@ -1,21 +1,35 @@
 mod tests_file_writer;
 mod tests_computed_data_set_metadata;
 
-use std::io::{Write, Seek, SeekFrom};
-use std::rc::Rc;
+use std::borrow::Cow;
+use std::io::{Read, Write, Seek};
 use std::path::{Path, PathBuf};
-use std::convert::TryFrom;
-use std::collections::BTreeSet;
-
-use crate::{DataSet, Version, Dimension, Attribute, DataType, Variable};
-use crate::io::Offset;
-use crate::data_set::DimensionSize;
-use crate::data_vector::DataVector;
-use crate::error::WriteError;
+use std::collections::{BTreeMap, BTreeSet};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+use std::ops::Range;
+use std::time::Instant;
+
+use crate::{DataSet, Version, DataType, Variable};
+use crate::io::VariableWriter;
+use crate::io::header::{write_header_parts, ComputedDataSetMetadata, ComputedVariableMetadata};
+use crate::io::io_metrics::{IoMetrics, MeteredIo, PositionedIo};
+use crate::io::write_summary::{VerifyReport, WriteSummary};
+use crate::data_vector::{DataVector, DataSlice};
+use crate::error::{InvalidDataSet, WriteError};
+#[cfg(all(feature = "fs", feature = "read"))]
+use crate::{FileReader, ReadOptions};
+#[cfg(all(feature = "fs", feature = "read"))]
+use crate::io::RepairPlan;
+#[cfg(all(feature = "fs", feature = "read"))]
+use crate::error::RepairError;
+#[cfg(feature = "mmap")]
+use crate::io::MmapWriter;
 
 use crate::io::{
-    ABSENT_TAG, DIMENSION_TAG, VARIABLE_TAG, ATTRIBUTE_TAG,
+    compute_header_required_size,
     compute_padding_size,
+    compute_record_offset,
 };
 
 use crate::{
@@ -27,28 +41,41 @@ use crate::{
     NC_FILL_F64,
 };
 
+/// Default maximum number of bytes serialized into the buffer of the writer before being written into the output stream
+/// (see [`WriteOptions::buffer_size`](struct.WriteOptions.html#structfield.buffer_size))
+const WRITE_BUFFER_SIZE: usize = 1 << 20;
+
+/// Maximum number of bytes of the block of fill values written repeatedly into the unwritten chunks
+const FILL_BLOCK_SIZE: usize = 1 << 20;
+
 macro_rules! impl_write_typed_chunk {
     ($func_name:ident, $prim_type:ty, $nc_fill_value:ident) => {
-        /// Write the `$prim_type` slice into the output stream.
-        fn $func_name<T: Write>(out_stream: &mut T, slice: &[$prim_type]) -> Result<usize, std::io::Error>
+        /// Write the `$prim_type` slice into the output stream, followed by the padding bytes if `padding` is `true`.
+        ///
+        /// The bytes are serialized into `buffer` first, and written at once (by batches of at most `buffer_size` bytes).
+        fn $func_name<T: Write>(out_stream: &mut T, buffer: &mut Vec<u8>, buffer_size: usize, slice: &[$prim_type], padding: bool) -> Result<usize, std::io::Error>
         {
-            // Write the useful bytes
             const SIZE_OF: usize = std::mem::size_of::<$prim_type>();
-            let mut bytes: [u8; SIZE_OF];
+            let mut num_bytes: usize = 0;
+            buffer.clear();
+            // Serialize the useful bytes
             for value in slice.iter() {
-                bytes = value.to_be_bytes();
-                out_stream.write_all(&bytes)?;
+                if buffer.len() + SIZE_OF > buffer_size {
+                    out_stream.write_all(&buffer[..])?;
+                    num_bytes += buffer.len();
+                    buffer.clear();
+                }
+                buffer.extend_from_slice(&value.to_be_bytes());
             }
-            let mut num_bytes: usize = slice.len() * std::mem::size_of::<$prim_type>();
 
-            // Write the padding bytes if necessary
-            let padding_size: usize = compute_padding_size(num_bytes);
-            if padding_size > 0 {
+            // Serialize the padding bytes if necessary
+            let padding_size: usize = compute_padding_size(slice.len() * SIZE_OF);
+            if padding && padding_size > 0 {
                 let nc_fill_bytes: [u8; SIZE_OF] = $nc_fill_value.to_be_bytes();
-                let padding_bytes: Vec<u8> = nc_fill_bytes.to_vec().into_iter().cycle().take(padding_size).collect();
-                out_stream.write_all(&padding_bytes)?;
-                num_bytes += padding_size;
+                buffer.extend(nc_fill_bytes.iter().cycle().take(padding_size));
             }
+            out_stream.write_all(&buffer[..])?;
+            num_bytes += buffer.len();
 
             // Return the number of written bytes
             Ok(num_bytes)
@@ -60,7 +87,7 @@ macro_rules! impl_write_typed_var {
     ($func_name:ident, $write_typed_chunk: path, $prim_type:ty, $data_type:path, $data_vector:path) => {
         pub fn $func_name(&mut self, var_name: &str, data: &[$prim_type]) -> Result<(), WriteError> {
             let header_def: &HeaderDefinition = self.header_def.as_ref().ok_or(WriteError::HeaderNotDefined)?;
-            let var: &Variable = header_def.data_set.find_var_from_name(var_name).map_err(|_err| WriteError::VariableNotDefined(var_name.to_owned()))?.1;
+            let (var_index, var): (usize, &Variable) = header_def.data_set.find_var_from_name(var_name).map_err(|_err| WriteError::VariableNotDefined(var_name.to_owned()))?;
             if var.data_type != $data_type {
                 return Err(WriteError::VariableMismatchDataType{var_name: var_name.to_owned(), req:var.data_type(), get: $data_type });
             }
@@ -71,29 +98,38 @@ macro_rules! impl_write_typed_var {
 
             // Write the `$prim_type` data
             let begin_offset: u64 = i64::from(var_metadata.begin_offset.clone()) as u64;
+            let records_are_packed: bool = var.is_record_var() && header_def.data_set_metadata.records_are_packed;
             match header_def.data_set.record_size() {
                 None => {  // fixed-size variable
-                    self.output_file.seek(SeekFrom::Start(begin_offset))?;
-                    let _chunk_size: usize = $write_typed_chunk(&mut self.output_file, data)?;
+                    let mut output: PositionedIo<W> = self.output_file.positioned(begin_offset)?;
+                    let _chunk_size: usize = $write_typed_chunk(&mut output, &mut self.buffer, self.buffer_size, data, true)?;
+                },
+                Some(_) if records_are_packed => {  // only record variable, its records are contiguous and written at once
+                    let mut output: PositionedIo<W> = self.output_file.positioned(begin_offset)?;
+                    let _chunk_size: usize = $write_typed_chunk(&mut output, &mut self.buffer, self.buffer_size, data, false)?;
                 },
                 Some(record_size) => {  // record variable
                     let num_chunks: usize = var.num_chunks();
                     let chunk_len: usize = var.chunk_len();
+                    let padding: bool = !(var.is_record_var() && header_def.data_set_metadata.records_are_packed);
                     // Loop over data chunks
                     for i in 0..num_chunks {
                         let start: usize = i * chunk_len;
                         let end: usize = (i + 1) * chunk_len;
                         let chunk_slice: &[$prim_type] = &data[start..end];
-                        let position: u64 = begin_offset + ((i * record_size) as u64);
-                        self.output_file.seek(SeekFrom::Start(position))?;
-                        let _chunk_size: usize = $write_typed_chunk(&mut self.output_file, chunk_slice)?;
+                        let position: u64 = compute_record_offset(begin_offset, i, record_size).ok_or_else(|| {
+                            WriteError::RecordOffsetOverflow{var_name: var_name.to_owned(), record_index: i}
+                        })?;
+                        let mut output: PositionedIo<W> = self.output_file.positioned(position)?;
+                        let _chunk_size: usize = $write_typed_chunk(&mut output, &mut self.buffer, self.buffer_size, chunk_slice, padding)?;
                     }
                 }
             }
 
             // Save the records already written
-            let num_records: usize = header_def.data_set.num_records().unwrap_or(1);
-            self.written_records.push((var, (0..num_records).collect()));
+            self.save_chunk_checksums(var_index, var, 0, DataSlice::from(data));
+            self.output_file.counters().add_chunks_written(var.num_chunks());
+            self.written_records.push((var, (0..var.num_chunks()).collect()));
             Ok(())
         }
     };
@@ -105,7 +141,7 @@ macro_rules! impl_write_typed_record {
             // Check that the defintion has been set
             let header_def: &HeaderDefinition = self.header_def.as_ref().ok_or(WriteError::HeaderNotDefined)?;
             // Check that the variable has been defined
-            let var: &Variable = header_def.data_set.find_var_from_name(var_name).map_err(|_err| WriteError::VariableNotDefined(var_name.to_owned()))?.1;
+            let (var_index, var): (usize, &Variable) = header_def.data_set.find_var_from_name(var_name).map_err(|_err| WriteError::VariableNotDefined(var_name.to_owned()))?;
             if var.data_type != $data_type {
                 return Err(WriteError::VariableMismatchDataType{var_name: var_name.to_owned(), req:var.data_type(), get: $data_type});
             }
@@ -121,12 +157,16 @@ macro_rules! impl_write_typed_record {
             let var_metadata: &ComputedVariableMetadata = header_def.get_var_metadata(var)?;
             let record_size: usize = header_def.data_set.record_size().unwrap_or(0);
 
-            // Set the output cursor to the record offset
-            let begin_offset: u64 = i64::from(var_metadata.begin_offset.clone()) as u64 + (record_size * record_index) as u64;
-            self.output_file.seek(SeekFrom::Start(begin_offset))?;
-            let _chunk_size: usize = $write_typed_chunk(&mut self.output_file, record)?;
+            // Compute the record offset
+            let begin_offset: u64 = compute_record_offset(i64::from(var_metadata.begin_offset.clone()) as u64, record_index, record_size).ok_or_else(|| {
+                WriteError::RecordOffsetOverflow{var_name: var_name.to_owned(), record_index: record_index}
+            })?;
+            let padding: bool = !(var.is_record_var() && header_def.data_set_metadata.records_are_packed);
+            let mut output: PositionedIo<W> = self.output_file.positioned(begin_offset)?;
+            let _chunk_size: usize = $write_typed_chunk(&mut output, &mut self.buffer, self.buffer_size, record, padding)?;
 
             // Save the written record
+            self.save_chunk_checksums(var_index, var, record_index, DataSlice::from(record));
             self.update_written_records(var, &[record_index][..])?;
             Ok(())
         }
@@ -134,33 +174,87 @@ macro_rules! impl_write_typed_record {
 }
 
 macro_rules! impl_write_typed_chunk_nc_fill {
-    ($func_name: ident, $prim_type:ty, $nc_fill_value:path) => {
-        /// Fill the output stream with the default value [`$nc_fill_value`](constant.$nc_fill_value.html).
-        fn $func_name<T: Write>(out_stream: &mut T, num_values: usize) -> Result<usize, std::io::Error>
+    ($func_name: ident, $prim_type:ty, $data_type:path, $nc_fill_value:path) => {
+        /// Fill the output stream with the default value [`$nc_fill_value`](constant.$nc_fill_value.html),
+        /// followed by the padding bytes if `padding` is `true`.
+        ///
+        /// The same block of fill values is written repeatedly (see `FillBlock`).
+        fn $func_name<T: Write>(out_stream: &mut T, fill_block: &mut FillBlock, num_values: usize, padding: bool) -> Result<usize, std::io::Error>
         {
-            // Write the useful bytes
             const SIZE_OF: usize = std::mem::size_of::<$prim_type>();
-            let bytes: [u8; SIZE_OF] = $nc_fill_value.to_be_bytes();
-            for _ in 0..num_values {
-                out_stream.write_all(&bytes)?;
-            }
-            let mut num_bytes: usize = num_values * std::mem::size_of::<$prim_type>();
-
-            // Write the padding bytes if necessary
-            let padding_size: usize = compute_padding_size(num_bytes);
-            if padding_size > 0 {
-                let nc_fill_bytes: [u8; SIZE_OF] = $nc_fill_value.to_be_bytes();
-                let padding_bytes: Vec<u8> = nc_fill_bytes.to_vec().into_iter().cycle().take(padding_size).collect();
-                out_stream.write_all(&padding_bytes)?;
-                num_bytes += padding_size;
+            let useful_size: usize = num_values * SIZE_OF;
+            // The padding bytes are also filled with the fill value
+            let padding_size: usize = if padding { compute_padding_size(useful_size) } else { 0 };
+            let chunk_size: usize = useful_size + padding_size;
+            let block: &[u8] = fill_block.get($data_type, &$nc_fill_value.to_be_bytes(), chunk_size);
+            let mut num_remaining_bytes: usize = chunk_size;
+            while num_remaining_bytes > 0 {
+                let num_bytes: usize = std::cmp::min(num_remaining_bytes, block.len());
+                out_stream.write_all(&block[0..num_bytes])?;
+                num_remaining_bytes -= num_bytes;
             }
 
             // Return the number of written bytes
-            Ok(num_bytes)
+            Ok(chunk_size)
         }
     };
 }
 
+/// Options of the [`FileWriter`](struct.FileWriter.html).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// Maximum number of bytes serialized before being written into the output, 1 MiB by default.
+    ///
+    /// The header is also written by blocks of this size. Large values reduce the number of writes on the network file systems,
+    /// small values the memory used.
+    pub buffer_size: usize,
+    /// Percentage of the header size reserved after the header, `0` by default.
+    ///
+    /// The reserved bytes allow to edit the attributes in place later (see [`edit_nc3`](fn.edit_nc3.html)),
+    /// like the argument `h_minfree` of the function `nc__enddef` of the NetCDF C library.
+    /// The argument `header_min_size` of [`FileWriter::set_def`](struct.FileWriter.html#method.set_def) is used if it is larger.
+    pub reserve_header_percent: usize,
+    /// Keeps a checksum of each chunk written with the values given by the user, `false` by default.
+    ///
+    /// The chunks are read back and compared with these checksums by [`FileWriter::close_verified`](struct.FileWriter.html#method.close_verified),
+    /// the files of the file system are then also opened for reading.
+    pub verify: bool,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        return WriteOptions{
+            buffer_size: WRITE_BUFFER_SIZE,
+            reserve_header_percent: 0,
+            verify: false,
+        };
+    }
+}
+
+/// Block of repeated fill values of a data type, reused to fill the unwritten chunks.
+#[derive(Debug, Default)]
+struct FillBlock {
+    data_type: Option<DataType>,
+    bytes: Vec<u8>,
+}
+
+impl FillBlock {
+
+    /// Returns the block of the fill values of the data type, containing at least `min(num_bytes, FILL_BLOCK_SIZE)` bytes.
+    ///
+    /// The block is only rebuilt if the data type changes or if it is too short, its length is a multiple of the size of the values.
+    fn get(&mut self, data_type: DataType, nc_fill_bytes: &[u8], num_bytes: usize) -> &[u8] {
+        let num_bytes: usize = std::cmp::min(num_bytes, FILL_BLOCK_SIZE);
+        if self.data_type != Some(data_type) || self.bytes.len() < num_bytes {
+            let block_size: usize = num_bytes.div_ceil(nc_fill_bytes.len()) * nc_fill_bytes.len();
+            self.bytes.clear();
+            self.bytes.extend(nc_fill_bytes.iter().cycle().take(block_size));
+            self.data_type = Some(data_type);
+        }
+        return &self.bytes;
+    }
+}
+
 /// Allows to write NetCDF-3 files (the *classic* and the *64-bit offset* versions).
 ///
 /// # Example
@@ -236,41 +330,59 @@ macro_rules! impl_write_typed_chunk_nc_fill {
 /// assert_eq!(NC3_LIGHT_CLASSIC_FILE_BYTES,            &nc3_file_bytes[..]);
 /// ```
 #[derive(Debug)]
-pub struct FileWriter<'a>
+pub struct FileWriter<'a, W: Write + Seek = std::fs::File>
 {
-    /// Path of the output file
-    output_file_path: PathBuf,
-    /// Opened file on the file system
-    output_file: std::fs::File,
+    /// Path of the output file (if it has been opened by the writer)
+    output_file_path: Option<PathBuf>,
+    /// Opened file on the file system, or any other output
+    output_file: MeteredIo<W>,
     /// Defintion of the data set.
     header_def: Option<HeaderDefinition<'a>>,
     /// List of already written records of each variable
     written_records: Vec<(&'a Variable, BTreeSet<usize>)>,
+    /// Writes the *streaming* number of records in the header
+    numrecs_streaming: bool,
+    /// Reused buffer in which the chunks are serialized before being written
+    buffer: Vec<u8>,
+    /// Maximum number of bytes of `buffer`
+    buffer_size: usize,
+    /// Percentage of the header size reserved after the header
+    reserve_header_percent: usize,
+    /// Reused block of fill values written into the unwritten chunks
+    fill_block: FillBlock,
+    /// Creation time of the writer
+    created_at: Instant,
+    /// Checksums of the written chunks (indexed by the variable index and the chunk index), kept if the written data are verified
+    chunk_checksums: Option<BTreeMap<(usize, usize), u64>>,
 }
 
+#[cfg(feature = "fs")]
 impl<'a> FileWriter<'a> {
 
     /// Opens and overwrites an existing NetCDF-3 file or creates one.
      pub fn open<P: std::convert::AsRef<Path>>(output_file_path: P) -> Result<FileWriter<'a>, WriteError> {
+        return FileWriter::open_with_options(output_file_path, &WriteOptions::default());
+    }
+
+    /// Opens and overwrites an existing NetCDF-3 file or creates one, with custom options (see [`WriteOptions`](struct.WriteOptions.html)).
+    pub fn open_with_options<P: std::convert::AsRef<Path>>(output_file_path: P, options: &WriteOptions) -> Result<FileWriter<'a>, WriteError> {
         let output_file_path: PathBuf = {
             let mut path = PathBuf::new();
             path.push(output_file_path);
             path
         };
         let output_file: std::fs::File = std::fs::OpenOptions::new()
-            .read(false)
+            .read(options.verify)
             .write(true)
             .create(true)
             .create_new(false)
             .truncate(true)
             .append(false)
             .open(output_file_path.clone())?;
-        Ok(FileWriter{
-            output_file: output_file,
-            output_file_path: output_file_path,
-            header_def: None,
-            written_records: vec![],
-        })
+        let mut file_writer: FileWriter<'a> = FileWriter::from_writer_with_options(output_file, options);
+        file_writer.set_positional_file()?;
+        file_writer.output_file_path = Some(output_file_path);
+        Ok(file_writer)
     }
 
     /// Creates a new NetCDF-3 file.
@@ -289,17 +401,225 @@ impl<'a> FileWriter<'a> {
             .write(true)
             .create_new(true)
             .open(output_file_path.clone())?;
-        Ok(FileWriter{
-            output_file: output_file,
-            output_file_path: output_file_path,
+        let mut file_writer: FileWriter<'a> = FileWriter::from_writer(output_file);
+        file_writer.set_positional_file()?;
+        file_writer.output_file_path = Some(output_file_path);
+        Ok(file_writer)
+    }
+
+    /// Opens an existing NetCDF-3 file to append records to it, `data_set` being the definition of the file with the total number of records.
+    ///
+    /// A partial trailing record, left by an interrupted writing, is completed with the fill values of the variables
+    /// (see [`repair_nc3`](fn.repair_nc3.html)) once the definition of the file is checked, the file being left unmodified
+    /// on error. The data stored in the file are kept, the appended records are written as usual,
+    /// and the unwritten ones are filled when the writer is closed.
+    ///
+    /// # Error
+    ///
+    /// The error [`WriteError::AppendDefinitionMismatch`](enum.WriteError.html#variant.AppendDefinitionMismatch) is returned
+    /// if a variable of `data_set` is not stored in the same way in the file, or if `data_set` has less records than the file.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    /// use netcdf3::{FileReader, FileWriter, DataSet, DataVector, Version, NC_FILL_F32};
+    /// use tempdir::TempDir;
+    ///
+    /// let tmp_dir: TempDir = TempDir::new("netcdf3_tests_").unwrap();
+    /// let file_path: PathBuf = tmp_dir.path().join("append.nc");
+    /// let new_data_set = |num_records: usize| -> DataSet {
+    ///     let mut data_set = DataSet::new();
+    ///     data_set.set_unlimited_dim("time", num_records).unwrap();
+    ///     data_set.add_var_f64("time", &["time"]).unwrap();
+    ///     data_set.add_var_f32("temperature", &["time"]).unwrap();
+    ///     data_set
+    /// };
+    ///
+    /// // the writing of the second record is interrupted after the variable `time`
+    /// let data_set: DataSet = new_data_set(2);
+    /// let mut file_writer: FileWriter = FileWriter::open(&file_path).unwrap();
+    /// file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    /// file_writer.write_record_f64("time", 0, &[0.0]).unwrap();
+    /// file_writer.write_record_f32("temperature", 0, &[14.5]).unwrap();
+    /// file_writer.write_record_f64("time", 1, &[1.0]).unwrap();
+    /// file_writer.close().unwrap();
+    /// let file = std::fs::OpenOptions::new().write(true).open(&file_path).unwrap();
+    /// file.set_len(file.metadata().unwrap().len() - 4).unwrap();
+    ///
+    /// // the second record is completed, and a third one is appended
+    /// let data_set: DataSet = new_data_set(3);
+    /// let mut file_writer: FileWriter = FileWriter::open_append(&file_path, &data_set).unwrap();
+    /// file_writer.write_record_f64("time", 2, &[2.0]).unwrap();
+    /// file_writer.write_record_f32("temperature", 2, &[15.0]).unwrap();
+    /// file_writer.close().unwrap();
+    ///
+    /// let mut file_reader = FileReader::open(&file_path).unwrap();
+    /// assert_eq!(Some(3),                                                 file_reader.data_set().num_records());
+    /// assert_eq!(DataVector::F64(vec![0.0, 1.0, 2.0]),                    file_reader.read_var("time").unwrap());
+    /// assert_eq!(DataVector::F32(vec![14.5, NC_FILL_F32, 15.0]),          file_reader.read_var("temperature").unwrap());
+    /// ```
+    #[cfg(feature = "read")]
+    pub fn open_append<P: std::convert::AsRef<Path>>(output_file_path: P, data_set: &'a DataSet) -> Result<FileWriter<'a>, WriteError> {
+        let output_file_path: PathBuf = {
+            let mut path = PathBuf::new();
+            path.push(output_file_path);
+            path
+        };
+        let mut output_file: std::fs::File = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(output_file_path.clone())?;
+        // Check that the variables are stored in the same way, the number of records being computed from the size of the file
+        let repair_plan: RepairPlan = RepairPlan::new(&mut output_file)?;
+        let num_records: usize = repair_plan.num_records();
+        let header_def: HeaderDefinition<'a> = {
+            let file_reader = FileReader::parse_from_reader(&mut output_file, Some(num_records), &ReadOptions::default()).map_err(RepairError::from)?;
+            let file_data_set: &DataSet = file_reader.data_set();
+            let header_def: HeaderDefinition<'a> = HeaderDefinition::new(data_set, file_reader.version(), file_reader.header_size())?;
+            for var in data_set.vars.iter() {
+                let begin_offset: u64 = i64::from(header_def.get_var_metadata(var)?.begin_offset.clone()) as u64;
+                let is_matching: bool = match file_data_set.get_var(var.name()) {
+                    None => false,
+                    Some(file_var) => {
+                        file_var.data_type() == var.data_type() && file_var.is_record_var() == var.is_record_var() &&
+                        file_var.chunk_size() == var.chunk_size() && file_reader.var_begin_offset(var.name()) == Some(begin_offset)
+                    },
+                };
+                if !is_matching {
+                    return Err(WriteError::AppendDefinitionMismatch(var.name().to_string()));
+                }
+            }
+            if let Some(file_var) = file_data_set.vars().iter().find(|file_var: &&Variable| data_set.get_var(file_var.name()).is_none()) {
+                return Err(WriteError::AppendDefinitionMismatch(file_var.name().to_string()));
+            }
+            if data_set.num_records().unwrap_or(0) < num_records {
+                let dim_name: String = data_set.get_unlimited_dim().map(|dim: std::rc::Rc<crate::Dimension>| dim.name()).unwrap_or_default();
+                return Err(WriteError::AppendDefinitionMismatch(dim_name));
+            }
+            header_def
+        };
+        // Complete the partial trailing record, once the definitions are matching
+        let _ = repair_plan.apply(&mut output_file)?;
+
+        let mut file_writer: FileWriter<'a> = FileWriter::from_writer(output_file);
+        file_writer.set_positional_file()?;
+        file_writer.output_file_path = Some(output_file_path);
+        // The data stored in the file are not filled when closing the writer
+        for var in data_set.vars.iter() {
+            let num_stored_chunks: usize = if var.is_record_var() { num_records } else { var.num_chunks() };
+            file_writer.written_records.push((var, (0..num_stored_chunks).collect()));
+        }
+        file_writer.header_def = Some(header_def);
+        let _ = file_writer.write_header()?;
+        return Ok(file_writer);
+    }
+
+    /// Writes the chunks through positional writes, on a second handle on the output file.
+    fn set_positional_file(&mut self) -> Result<(), WriteError> {
+        #[cfg(any(unix, windows))]
+        {
+            let file: std::fs::File = self.output_file.get_ref().try_clone()?;
+            self.output_file.set_file(file);
+        }
+        return Ok(());
+    }
+
+}
+
+#[cfg(feature = "mmap")]
+impl<'a> FileWriter<'a, MmapWriter> {
+
+    /// Creates or overwrites a NetCDF-3 file written through a memory map, and defines its header (see [`set_def`](#method.set_def)).
+    ///
+    /// The file is pre-sized for the data set before being mapped (see [`MmapWriter`](struct.MmapWriter.html)).
+    pub fn create_mmap<P: AsRef<Path>>(output_file_path: P, data_set: &'a DataSet, version: Version, header_min_size: usize) -> Result<Self, WriteError> {
+        let output_file_path: PathBuf = {
+            let mut path = PathBuf::new();
+            path.push(output_file_path);
+            path
+        };
+        let header_def: HeaderDefinition<'a> = HeaderDefinition::new(data_set, version, header_min_size)?;
+        let output_file = MmapWriter::create(&output_file_path, header_def.file_size())?;
+        let mut file_writer: FileWriter<'a, MmapWriter> = FileWriter::from_writer(output_file);
+        file_writer.output_file_path = Some(output_file_path);
+        file_writer.header_def = Some(header_def);
+        let _ = file_writer.write_header()?;
+        return Ok(file_writer);
+    }
+}
+
+impl<'a, W: Write + Seek> FileWriter<'a, W> {
+
+    /// Creates a writer on any `Write + Seek` output (a file, an in-memory buffer, etc.).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use netcdf3::{FileWriter, DataSet, Version};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.add_fixed_dim("dim_1", 2).unwrap();
+    /// data_set.add_var_i16("var_1", &["dim_1"]).unwrap();
+    ///
+    /// let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+    /// assert_eq!(None,                file_writer.file_path());
+    /// file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    /// file_writer.write_var_i16("var_1", &[1, 2]).unwrap();
+    /// let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+    ///
+    /// assert_eq!(&b"CDF\x01"[..],     &bytes[0..4]);
+    /// assert_eq!(&[0, 1, 0, 2][..],   &bytes[bytes.len() - 4..]);
+    /// ```
+    pub fn from_writer(output: W) -> FileWriter<'a, W> {
+        return FileWriter::from_writer_with_options(output, &WriteOptions::default());
+    }
+
+    /// Creates a writer on any `Write + Seek` output with custom options (see [`WriteOptions`](struct.WriteOptions.html)).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use netcdf3::{FileWriter, DataSet, Version, WriteOptions};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.add_fixed_dim("dim_1", 1000).unwrap();
+    /// data_set.add_var_f64("var_1", &["dim_1"]).unwrap();
+    ///
+    /// // the data are written by batches of 4 KiB
+    /// let options = WriteOptions{buffer_size: 4096, ..WriteOptions::default()};
+    /// let mut file_writer = FileWriter::from_writer_with_options(Cursor::new(Vec::<u8>::new()), &options);
+    /// assert_eq!(4096,                file_writer.buffer_size());
+    /// file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    /// file_writer.write_var_f64("var_1", &[1.0; 1000]).unwrap();
+    /// file_writer.close_into_inner().unwrap();
+    /// ```
+    pub fn from_writer_with_options(output: W, options: &WriteOptions) -> FileWriter<'a, W> {
+        FileWriter{
+            output_file: MeteredIo::new(output),
+            output_file_path: None,
             header_def: None,
             written_records: vec![],
-        })
+            numrecs_streaming: false,
+            buffer: vec![],
+            buffer_size: std::cmp::max(options.buffer_size, 1),
+            reserve_header_percent: options.reserve_header_percent,
+            fill_block: FillBlock::default(),
+            created_at: Instant::now(),
+            chunk_checksums: if options.verify { Some(BTreeMap::new()) } else { None },
+        }
+    }
+
+    /// Returns the maximum number of bytes serialized before being written into the output (see [`WriteOptions`](struct.WriteOptions.html)).
+    pub fn buffer_size(&self) -> usize {
+        return self.buffer_size;
     }
 
-    /// Path of the output file.
-    pub fn file_path(&self) -> &Path {
-        return &self.output_file_path;
+    /// Path of the output file (`None` if the writer has not been created by the methods [`open`](#method.open) or [`create_new`](#method.create_new)).
+    pub fn file_path(&self) -> Option<&Path> {
+        return self.output_file_path.as_deref();
     }
 
     /// Set the NetCDF-3 definition.
@@ -349,12 +669,66 @@ impl<'a> FileWriter<'a> {
     pub fn set_def(&mut self, data_set: &'a DataSet, version: Version, header_min_size: usize) -> Result<(), WriteError> {
         match &self.header_def {
             Some(_) => return Err(WriteError::HeaderAlreadyDefined),
-            None => self.header_def = Some(HeaderDefinition::new(data_set, version, header_min_size)?),
+            None => self.header_def = Some(self.new_header_def(data_set, version, header_min_size)?),
         }
         let _ = self.write_header()?;
         Ok(())
     }
 
+    /// Set the NetCDF-3 definition like [`set_def`](#method.set_def), and selects the version automatically.
+    ///
+    /// The classic version is selected if the begin offsets and the sizes of all the variables fit into its limits,
+    /// otherwise the 64-bit offset version is selected. Returns the selected version.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use netcdf3::{FileWriter, DataSet, Version};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.add_fixed_dim("x", 1 << 30).unwrap();
+    /// data_set.add_var_i8("a", &["x"]).unwrap();
+    ///
+    /// let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+    /// assert_eq!(Version::Classic,        file_writer.set_def_auto(&data_set, 0).unwrap());
+    ///
+    /// // the begin offset of `c` exceeds `i32::MAX`
+    /// data_set.add_var_i8("b", &["x"]).unwrap();
+    /// data_set.add_var_i8("c", &["x"]).unwrap();
+    /// let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+    /// assert_eq!(Version::Offset64Bit,    file_writer.set_def_auto(&data_set, 0).unwrap());
+    /// ```
+    pub fn set_def_auto(&mut self, data_set: &'a DataSet, header_min_size: usize) -> Result<Version, WriteError> {
+        if self.header_def.is_some() {
+            return Err(WriteError::HeaderAlreadyDefined);
+        }
+        let header_def: HeaderDefinition<'a> = match self.new_header_def(data_set, Version::Classic, header_min_size) {
+            Ok(header_def) => header_def,
+            Err(WriteError::MaximumBeginOffsetExceeded{..}) | Err(WriteError::DataSet(InvalidDataSet::MaximumVariableSizeExceeded{..})) => {
+                self.new_header_def(data_set, Version::Offset64Bit, header_min_size)?
+            },
+            Err(err) => return Err(err),
+        };
+        let version: Version = header_def.version;
+        self.header_def = Some(header_def);
+        let _ = self.write_header()?;
+        return Ok(version);
+    }
+
+    /// Computes the definition of the header, reserving the bytes of the option `reserve_header_percent` after it.
+    fn new_header_def(&self, data_set: &'a DataSet, version: Version, header_min_size: usize) -> Result<HeaderDefinition<'a>, WriteError> {
+        let header_min_size: usize = match self.reserve_header_percent {
+            0 => header_min_size,
+            reserve_header_percent => {
+                let header_required_size: usize = compute_header_required_size(data_set, version);
+                let reserved_size: usize = header_required_size.saturating_mul(reserve_header_percent) / 100;
+                std::cmp::max(header_min_size, header_required_size.saturating_add(reserved_size))
+            },
+        };
+        return HeaderDefinition::new(data_set, version, header_min_size);
+    }
+
     pub fn header_is_defined(&self) -> bool {
         return self.header_def.is_some();
     }
@@ -364,32 +738,161 @@ impl<'a> FileWriter<'a> {
     }
 
     pub fn version(&self) -> Option<Version> {
-        return self.header_def.as_ref().map(|header_def| header_def.version.clone());
+        return self.header_def.as_ref().map(|header_def| header_def.version);
     }
 
     pub fn header_min_size(&self) -> Option<usize> {
         return self.header_def.as_ref().map(|header_def| header_def.header_min_size);
     }
 
+    /// Writes the *streaming* value `0xFFFFFFFF` instead of the number of records in the header, as allowed by the file format
+    /// specification for the files produced incrementally.
+    ///
+    /// The readers deduce then the number of records from the size of the file (see
+    /// [`FileReader::numrecs_is_streaming`](struct.FileReader.html#method.numrecs_is_streaming)). The mode can be enabled or disabled
+    /// before or after the definition of the header, and it has no effect on the data sets without *unlimited-size* dimension.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use netcdf3::{DataSet, FileReader, FileWriter, Version};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.set_unlimited_dim("time", 2).unwrap();
+    /// data_set.add_var_i16("var_1", &["time"]).unwrap();
+    ///
+    /// let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    /// file_writer.set_numrecs_streaming(true).unwrap();
+    /// file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    /// file_writer.write_record_i16("var_1", 0, &[1]).unwrap();
+    /// file_writer.write_record_i16("var_1", 1, &[2]).unwrap();
+    /// let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+    /// assert_eq!(&[0xFF, 0xFF, 0xFF, 0xFF][..],   &bytes[4..8]);
+    ///
+    /// let mut file_reader = FileReader::from_reader(Cursor::new(bytes)).unwrap();
+    /// assert_eq!(true,                            file_reader.numrecs_is_streaming());
+    /// assert_eq!(Some(2),                         file_reader.data_set().num_records());
+    /// assert_eq!(vec![1, 2],                      file_reader.read_var_i16("var_1").unwrap());
+    /// ```
+    pub fn set_numrecs_streaming(&mut self, numrecs_streaming: bool) -> Result<(), WriteError> {
+        self.numrecs_streaming = numrecs_streaming;
+        if self.header_def.is_some() {
+            let _ = self.write_header()?;
+        }
+        return Ok(());
+    }
+
+    /// Returns `true` if the *streaming* value is written instead of the number of records (see
+    /// [`set_numrecs_streaming`](#method.set_numrecs_streaming)).
+    pub fn numrecs_is_streaming(&self) -> bool {
+        return self.numrecs_streaming;
+    }
+
+
+    /// Fills the unwritten data, closes the NetCDF-3 file and returns the summary of the writing (see [`WriteSummary`](struct.WriteSummary.html)).
+    pub fn close(mut self) -> Result<WriteSummary, WriteError>
+    {
+        return self.finish();
+    }
 
-    /// Fills the unwritten data, and closes the NetCDF-3 file.
-    pub fn close(mut self) -> Result<(), WriteError>
+    /// Fills the unwritten data, flushes the output and returns the summary of the writing.
+    fn finish(&mut self) -> Result<WriteSummary, WriteError>
+    {
+        let vars_written: Vec<String> = match self.header_def {
+            None => vec![],
+            Some(ref header_def) => header_def.data_set.vars.iter()
+                .filter(|var: &&Variable| self.written_records.iter().any(|(var_2, written_records): &(&'a Variable, BTreeSet<usize>)| {
+                    var == var_2 && !written_records.is_empty()
+                }))
+                .map(|var: &Variable| var.name.clone())
+                .collect(),
+        };
+        let vars_filled: Vec<String> = self.fill_unwritten_records()?;
+        self.output_file.flush()?;
+        return Ok(WriteSummary{
+            bytes_written: self.io_metrics().bytes_written,
+            vars_written: vars_written,
+            vars_filled: vars_filled,
+            num_records: self.header_def.as_ref().and_then(|header_def| header_def.data_set.num_records()).unwrap_or(0),
+            elapsed: self.created_at.elapsed(),
+        });
+    }
+
+    /// Returns the counters of the bytes written, the seeks, the chunks written and the fill bytes written since the creation of the writer
+    /// or the last call to [`reset_io_metrics`](#method.reset_io_metrics) (see [`IoMetrics`](struct.IoMetrics.html)).
+    ///
+    /// The fill values of the unwritten chunks are written when the writer is closed,
+    /// the final counters are returned by [`close_with_metrics`](#method.close_with_metrics).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use netcdf3::{FileWriter, DataSet, IoMetrics, Version};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.set_unlimited_dim("time", 3).unwrap();
+    /// data_set.add_var_f64("time", &["time"]).unwrap();
+    ///
+    /// let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+    /// file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    /// file_writer.reset_io_metrics();  // the bytes of the header are not counted
+    /// file_writer.write_record_f64("time", 0, &[0.0]).unwrap();
+    /// file_writer.write_record_f64("time", 2, &[2.0]).unwrap();
+    ///
+    /// let metrics: IoMetrics = file_writer.io_metrics();
+    /// assert_eq!(2,               metrics.chunks_written);
+    /// assert_eq!(16,              metrics.bytes_written);
+    /// assert_eq!(0,               metrics.fill_bytes_written);
+    ///
+    /// // the record 1 is filled with the default fill value
+    /// let metrics: IoMetrics = file_writer.close_with_metrics().unwrap();
+    /// assert_eq!(8,               metrics.fill_bytes_written);
+    /// ```
+    pub fn io_metrics(&self) -> IoMetrics {
+        return self.output_file.counters().snapshot();
+    }
+
+    /// Resets the I/O counters (see [`io_metrics`](#method.io_metrics)).
+    pub fn reset_io_metrics(&mut self) {
+        self.output_file.counters().reset();
+    }
+
+    /// Fills the unwritten data, and returns the final I/O counters (see [`io_metrics`](#method.io_metrics)).
+    pub fn close_with_metrics(mut self) -> Result<IoMetrics, WriteError>
+    {
+        self.fill_unwritten_records()?;
+        self.output_file.flush()?;
+        return Ok(self.io_metrics());
+    }
+
+    /// Fills the unwritten data, and returns the underlying output.
+    pub fn close_into_inner(mut self) -> Result<W, WriteError>
+    {
+        self.fill_unwritten_records()?;
+        self.output_file.flush()?;
+        Ok(self.output_file.into_inner())
+    }
+
+    /// Fills the unwritten chunks with the fill values, and returns the names of the filled variables.
+    fn fill_unwritten_records(&mut self) -> Result<Vec<String>, WriteError>
     {
         let header_def: &HeaderDefinition = match self.header_def {
-            None => return Ok(()),
+            None => return Ok(vec![]),
             Some(ref header_def) => header_def,
         };
-        let num_records: usize = header_def.data_set.num_records().unwrap_or(1);
-        let all_records: BTreeSet<usize> = (0..num_records).collect();
         let not_written_records: Vec<(&'a Variable, Vec<usize>)> = {
             let num_vars = header_def.data_set.vars.len();
             let mut not_written_records: Vec<(&'a Variable, Vec<usize>)> = Vec::with_capacity(num_vars);
             for var in header_def.data_set.vars.iter() {
+                // A *fixed-size* variable has a single chunk, even if the data set has several records
+                let all_records: BTreeSet<usize> = (0..var.num_chunks()).collect();
                 let written_records: Option<&BTreeSet<usize>> = self.written_records.iter()
                     .find(|(var_2, _written_records): &&(&'a Variable, BTreeSet<usize>)| var == *var_2)
                     .map(|(_var_2, written_records): &(&'a Variable, BTreeSet<_>)| written_records);
                 let not_written_record: Vec<usize> = match written_records {
-                    None => all_records.clone().into_iter().collect(),
+                    None => all_records.into_iter().collect(),
                     Some(written_records) => all_records.difference(&written_records).cloned().collect(),
                 };
                 not_written_records.push((var, not_written_record));
@@ -398,34 +901,35 @@ impl<'a> FileWriter<'a> {
         };
 
         let record_size: usize = header_def.data_set.record_size().unwrap_or(0);
+        let mut vars_filled: Vec<String> = vec![];
         for (var, not_written_records) in not_written_records.into_iter() {
+            if !not_written_records.is_empty() {
+                vars_filled.push(var.name.clone());
+            }
             // let num_chunks: usize = var.num_chunks();
             let chunk_len: usize = var.chunk_len();
+            let padding: bool = !(var.is_record_var() && header_def.data_set_metadata.records_are_packed);
             let var_metadata: &ComputedVariableMetadata = header_def.get_var_metadata(var)?;
-            let begin_offset: usize = i64::from(var_metadata.begin_offset.clone()) as usize;
+            let begin_offset: u64 = i64::from(var_metadata.begin_offset.clone()) as u64;
             for i in not_written_records.into_iter() {
-                let position: usize = begin_offset + (i * record_size);
-                self.output_file.seek(SeekFrom::Start(position as u64))?;
-                let _num_bytes: usize = match var.data_type() {
-                    DataType::I8 => FileWriter::write_chunk_nc_fill_i8(&mut self.output_file, chunk_len),
-                    DataType::U8 => FileWriter::write_chunk_nc_fill_u8(&mut self.output_file, chunk_len),
-                    DataType::I16 => FileWriter::write_chunk_nc_fill_i16(&mut self.output_file, chunk_len),
-                    DataType::I32 => FileWriter::write_chunk_nc_fill_i32(&mut self.output_file, chunk_len),
-                    DataType::F32 => FileWriter::write_chunk_nc_fill_f32(&mut self.output_file, chunk_len),
-                    DataType::F64 => FileWriter::write_chunk_nc_fill_f64(&mut self.output_file, chunk_len),
+                let position: u64 = compute_record_offset(begin_offset, i, record_size).ok_or_else(|| {
+                    WriteError::RecordOffsetOverflow{var_name: var.name.clone(), record_index: i}
+                })?;
+                let mut output: PositionedIo<W> = self.output_file.positioned(position)?;
+                let num_bytes: usize = match var.data_type() {
+                    DataType::I8 => FileWriter::write_chunk_nc_fill_i8(&mut output, &mut self.fill_block, chunk_len, padding),
+                    DataType::U8 => FileWriter::write_chunk_nc_fill_u8(&mut output, &mut self.fill_block, chunk_len, padding),
+                    DataType::I16 => FileWriter::write_chunk_nc_fill_i16(&mut output, &mut self.fill_block, chunk_len, padding),
+                    DataType::I32 => FileWriter::write_chunk_nc_fill_i32(&mut output, &mut self.fill_block, chunk_len, padding),
+                    DataType::F32 => FileWriter::write_chunk_nc_fill_f32(&mut output, &mut self.fill_block, chunk_len, padding),
+                    DataType::F64 => FileWriter::write_chunk_nc_fill_f64(&mut output, &mut self.fill_block, chunk_len, padding),
                 }?;
+                self.output_file.counters().add_fill_bytes_written(num_bytes);
             }
         }
-        Ok(())
+        Ok(vars_filled)
     }
 
-    impl_write_typed_chunk!(write_chunk_i8, i8, NC_FILL_I8);
-    impl_write_typed_chunk!(write_chunk_u8, u8, NC_FILL_U8);
-    impl_write_typed_chunk!(write_chunk_i16, i16, NC_FILL_I16);
-    impl_write_typed_chunk!(write_chunk_i32, i32, NC_FILL_I32);
-    impl_write_typed_chunk!(write_chunk_f32, f32, NC_FILL_F32);
-    impl_write_typed_chunk!(write_chunk_f64, f64, NC_FILL_F64);
-
     impl_write_typed_var!(write_var_i8, FileWriter::write_chunk_i8, i8, DataType::I8, DataVector::I8);
     impl_write_typed_var!(write_var_u8, FileWriter::write_chunk_u8, u8, DataType::U8, DataVector::U8);
     impl_write_typed_var!(write_var_i16, FileWriter::write_chunk_i16, i16, DataType::I16, DataVector::I16);
@@ -440,17 +944,281 @@ impl<'a> FileWriter<'a> {
     impl_write_typed_record!(write_record_f32, FileWriter::write_chunk_f32, f32, DataType::F32);
     impl_write_typed_record!(write_record_f64, FileWriter::write_chunk_f64, f64, DataType::F64);
 
+    /// Returns a handle on the variable, bound to the writer (see [`VariableWriter`](struct.VariableWriter.html)).
+    ///
+    /// Returns an error if the header is not defined or if the variable is not defined.
+    pub fn variable(&mut self, var_name: &str) -> Result<VariableWriter<'_, 'a, W>, WriteError> {
+        return VariableWriter::new(self, var_name);
+    }
+
+    /// Writes all the data of a variable, consuming `data` whose memory is reused to serialize the values.
+    ///
+    /// The values are converted in place into their big-endian representation and written directly from `data`,
+    /// so no other buffer is allocated, which matters for the very large variables
+    /// (the methods [`write_var_i8`](#method.write_var_i8), etc. serialize the values into an internal buffer).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use netcdf3::{FileReader, FileWriter, DataSet, DataVector, Version};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.set_unlimited_dim("time", 2).unwrap();
+    /// data_set.add_fixed_dim("dim_1", 3).unwrap();
+    /// data_set.add_var_f64("var_1", &["time", "dim_1"]).unwrap();
+    ///
+    /// let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+    /// file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    /// let data: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+    /// file_writer.write_var_owned("var_1", DataVector::F64(data)).unwrap();
+    ///
+    /// let mut file_reader = FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+    /// assert_eq!(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0],     file_reader.read_var_f64("var_1").unwrap());
+    /// ```
+    pub fn write_var_owned(&mut self, var_name: &str, mut data: DataVector) -> Result<(), WriteError> {
+        let header_def: &HeaderDefinition = self.header_def.as_ref().ok_or(WriteError::HeaderNotDefined)?;
+        let (var_index, var): (usize, &'a Variable) = header_def.data_set.find_var_from_name(var_name).map_err(|_err| WriteError::VariableNotDefined(var_name.to_owned()))?;
+        if var.data_type() != data.data_type() {
+            return Err(WriteError::VariableMismatchDataType{var_name: var_name.to_owned(), req: var.data_type(), get: data.data_type()});
+        }
+        if var.len() != data.len() {
+            return Err(WriteError::VariableMismatchDataLength{var_name: var_name.to_owned(), req: var.len(), get: data.len()});
+        }
+        let num_chunks: usize = var.num_chunks();
+        let chunk_size: usize = var.chunk_len() * var.data_type().size_of();
+        let records_are_packed: bool = var.is_record_var() && header_def.data_set_metadata.records_are_packed;
+        self.save_chunk_checksums(var_index, var, 0, data.as_slice());
+        let bytes: &[u8] = be_bytes_in_place(&mut data);
+        if records_are_packed {
+            // The records of the only record variable are contiguous, they are written at once
+            if num_chunks > 0 {
+                let (position, _padding_size): (u64, usize) = self.locate_chunk(var, 0)?;
+                self.output_file.write_all_at(bytes, position)?;
+            }
+        }
+        else {
+            for chunk_index in 0..num_chunks {
+                let (position, padding_size): (u64, usize) = self.locate_chunk(var, chunk_index)?;
+                self.output_file.write_all_at(&bytes[(chunk_index * chunk_size)..((chunk_index + 1) * chunk_size)], position)?;
+                self.write_padding(var.data_type(), position + chunk_size as u64, padding_size)?;
+            }
+        }
+
+        // Save the records already written
+        self.update_written_records(var, &(0..num_chunks).collect::<Vec<usize>>())?;
+        Ok(())
+    }
+
+    /// Writes all the data of a variable, borrowed or owned (see [`write_var_owned`](#method.write_var_owned)).
+    ///
+    /// The owned data are consumed and serialized in place, the borrowed data are serialized into an internal buffer.
+    pub fn write_var_cow(&mut self, var_name: &str, data: Cow<'_, DataVector>) -> Result<(), WriteError> {
+        return match data {
+            Cow::Owned(data) => self.write_var_owned(var_name, data),
+            Cow::Borrowed(DataVector::I8(data)) => self.write_var_i8(var_name, data),
+            Cow::Borrowed(DataVector::U8(data)) => self.write_var_u8(var_name, data),
+            Cow::Borrowed(DataVector::I16(data)) => self.write_var_i16(var_name, data),
+            Cow::Borrowed(DataVector::I32(data)) => self.write_var_i32(var_name, data),
+            Cow::Borrowed(DataVector::F32(data)) => self.write_var_f32(var_name, data),
+            Cow::Borrowed(DataVector::F64(data)) => self.write_var_f64(var_name, data),
+        };
+    }
+
+    /// Writes the records `records` of several *record* variables, one record after the other.
+    ///
+    /// Each element of `vars_data` contains the values of a variable for all the records of `records` (`records.len()` chunks).
+    /// Within each record the chunks are written in the order of the file,
+    /// so the output is written sequentially when all the *record* variables are given.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use netcdf3::{FileReader, FileWriter, DataSet, DataSlice, Version};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.set_unlimited_dim("time", 4).unwrap();
+    /// data_set.add_fixed_dim("cell", 2).unwrap();
+    /// data_set.add_var_f64("time", &["time"]).unwrap();
+    /// data_set.add_var_f32("temperature", &["time", "cell"]).unwrap();
+    ///
+    /// let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+    /// file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    /// // Write the records by steps of 2 records
+    /// for step in 0..2 {
+    ///     let time: Vec<f64> = vec![(2 * step) as f64, (2 * step + 1) as f64];
+    ///     let temperature: Vec<f32> = vec![1.0, 2.0, 3.0, 4.0];
+    ///     file_writer.write_records((2 * step)..(2 * step + 2), &[
+    ///         ("time", DataSlice::from(&time[..])),
+    ///         ("temperature", DataSlice::from(&temperature[..])),
+    ///     ]).unwrap();
+    /// }
+    ///
+    /// let mut file_reader = FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+    /// assert_eq!(vec![0.0, 1.0, 2.0, 3.0],        file_reader.read_var_f64("time").unwrap());
+    /// ```
+    pub fn write_records(&mut self, records: Range<usize>, vars_data: &[(&str, DataSlice)]) -> Result<(), WriteError> {
+        let header_def: &HeaderDefinition = self.header_def.as_ref().ok_or(WriteError::HeaderNotDefined)?;
+        let data_set: &'a DataSet = header_def.data_set;
+        let num_records: usize = data_set.num_records().unwrap_or(0);
+        if records.end > num_records {
+            return Err(WriteError::RecordIndexExceeded{index: records.end - 1, num_records: num_records});
+        }
 
-    impl_write_typed_chunk_nc_fill!(write_chunk_nc_fill_i8, i8, NC_FILL_I8);
-    impl_write_typed_chunk_nc_fill!(write_chunk_nc_fill_u8, u8, NC_FILL_U8);
-    impl_write_typed_chunk_nc_fill!(write_chunk_nc_fill_i16, i16, NC_FILL_I16);
-    impl_write_typed_chunk_nc_fill!(write_chunk_nc_fill_i32, i32, NC_FILL_I32);
-    impl_write_typed_chunk_nc_fill!(write_chunk_nc_fill_f32, f32, NC_FILL_F32);
-    impl_write_typed_chunk_nc_fill!(write_chunk_nc_fill_f64, f64, NC_FILL_F64);
+        // Check the variables and sort them in the order of the file
+        let mut vars: Vec<(usize, &'a Variable, u64, DataSlice)> = vec![];
+        for (var_name, data) in vars_data.iter() {
+            let (var_index, var): (usize, &'a Variable) = data_set.find_var_from_name(var_name).map_err(|_err| WriteError::VariableNotDefined(String::from(*var_name)))?;
+            if !var.is_record_var() {
+                return Err(WriteError::VariableNotRecord(String::from(*var_name)));
+            }
+            if var.data_type() != data.data_type() {
+                return Err(WriteError::VariableMismatchDataType{var_name: String::from(*var_name), req: var.data_type(), get: data.data_type()});
+            }
+            let data_len: usize = records.len() * var.chunk_len();
+            if data.len() != data_len {
+                return Err(WriteError::RecordMismatchDataLength{var_name: String::from(*var_name), req: data_len, get: data.len()});
+            }
+            let begin_offset: u64 = i64::from(header_def.get_var_metadata(var)?.begin_offset.clone()) as u64;
+            vars.push((var_index, var, begin_offset, *data));
+        }
+        vars.sort_by_key(|(_var_index, _var, begin_offset, _data): &(usize, &'a Variable, u64, DataSlice)| *begin_offset);
+
+        let record_size: usize = data_set.record_size().unwrap_or(0);
+        let padding: bool = !header_def.data_set_metadata.records_are_packed;
+        let mut position: Option<u64> = vars.first().and_then(|(_var_index, _var, begin_offset, _data): &(usize, &'a Variable, u64, DataSlice)| {
+            compute_record_offset(*begin_offset, records.start, record_size)
+        });
+        let mut output: PositionedIo<W> = self.output_file.positioned(position.unwrap_or(0))?;
+        for (i, record_index) in records.clone().enumerate() {
+            for (_var_index, var, begin_offset, data) in vars.iter() {
+                let chunk_position: u64 = compute_record_offset(*begin_offset, record_index, record_size).ok_or_else(|| {
+                    WriteError::RecordOffsetOverflow{var_name: var.name().to_owned(), record_index: record_index}
+                })?;
+                // Move only if the chunk does not follow the previous one
+                if position != Some(chunk_position) {
+                    output.set_offset(chunk_position)?;
+                }
+                let chunk_len: usize = var.chunk_len();
+                let chunk_size: usize = match data.sub_slice(i * chunk_len, (i + 1) * chunk_len) {
+                    DataSlice::I8(chunk) => FileWriter::write_chunk_i8(&mut output, &mut self.buffer, self.buffer_size, chunk, padding),
+                    DataSlice::U8(chunk) => FileWriter::write_chunk_u8(&mut output, &mut self.buffer, self.buffer_size, chunk, padding),
+                    DataSlice::I16(chunk) => FileWriter::write_chunk_i16(&mut output, &mut self.buffer, self.buffer_size, chunk, padding),
+                    DataSlice::I32(chunk) => FileWriter::write_chunk_i32(&mut output, &mut self.buffer, self.buffer_size, chunk, padding),
+                    DataSlice::F32(chunk) => FileWriter::write_chunk_f32(&mut output, &mut self.buffer, self.buffer_size, chunk, padding),
+                    DataSlice::F64(chunk) => FileWriter::write_chunk_f64(&mut output, &mut self.buffer, self.buffer_size, chunk, padding),
+                }?;
+                position = Some(chunk_position + chunk_size as u64);
+            }
+        }
+
+        // Save the written records
+        for (var_index, var, _begin_offset, data) in vars.iter() {
+            self.save_chunk_checksums(*var_index, var, records.start, *data);
+        }
+        let records: Vec<usize> = records.collect();
+        for (_var_index, var, _begin_offset, _data) in vars.into_iter() {
+            self.update_written_records(var, &records)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a chunk of a variable (its whole data for a *fixed-size* variable, or one of its records) from its big-endian bytes,
+    /// followed by the padding bytes if necessary.
+    ///
+    /// The bytes are read part by part into `buffer` by `read_part(offset, part)`, `offset` being the position of the part in the chunk.
+    /// The data type of the bytes is not checked, `buffer` must not be empty.
+    #[cfg(feature = "read")]
+    pub(crate) fn write_chunk_by_parts<E, F>(&mut self, var_name: &str, chunk_index: usize, buffer: &mut [u8], mut read_part: F) -> Result<(), E>
+    where
+        E: From<WriteError>,
+        F: FnMut(usize, &mut [u8]) -> Result<(), E>,
+    {
+        debug_assert!(!buffer.is_empty());
+        let header_def: &HeaderDefinition = self.header_def.as_ref().ok_or(WriteError::HeaderNotDefined)?;
+        let (var_index, var): (usize, &'a Variable) = header_def.data_set.find_var_from_name(var_name).map_err(|_err| WriteError::VariableNotDefined(var_name.to_owned()))?;
+        let (position, padding_size): (u64, usize) = self.locate_chunk(var, chunk_index)?;
+        let useful_size: usize = var.chunk_len() * var.data_type().size_of();
+
+        let mut output: PositionedIo<W> = self.output_file.positioned(position).map_err(WriteError::from)?;
+        let mut hasher: Option<DefaultHasher> = self.chunk_checksums.as_ref().map(|_checksums| DefaultHasher::new());
+        let mut offset: usize = 0;
+        while offset < useful_size {
+            let part_size: usize = std::cmp::min(useful_size - offset, buffer.len());
+            read_part(offset, &mut buffer[0..part_size])?;
+            output.write_all(&buffer[0..part_size]).map_err(WriteError::from)?;
+            if let Some(ref mut hasher) = hasher {
+                hasher.write(&buffer[0..part_size]);
+            }
+            offset += part_size;
+        }
+        self.write_padding(var.data_type(), position + useful_size as u64, padding_size)?;
+
+        // Save the written record
+        if let (Some(hasher), Some(checksums)) = (hasher, self.chunk_checksums.as_mut()) {
+            checksums.insert((var_index, chunk_index), hasher.finish());
+        }
+        self.update_written_records(var, &[chunk_index][..])?;
+        Ok(())
+    }
+
+    /// Returns the position of a chunk of the variable in the output, and the number of its padding bytes.
+    fn locate_chunk(&self, var: &Variable, chunk_index: usize) -> Result<(u64, usize), WriteError> {
+        let header_def: &HeaderDefinition = self.header_def.as_ref().ok_or(WriteError::HeaderNotDefined)?;
+        if chunk_index >= var.num_chunks() {
+            return Err(WriteError::RecordIndexExceeded{index: chunk_index, num_records: var.num_chunks()});
+        }
+        let var_metadata: &ComputedVariableMetadata = header_def.get_var_metadata(var)?;
+        let record_size: usize = header_def.data_set.record_size().unwrap_or(0);
+        let position: u64 = compute_record_offset(i64::from(var_metadata.begin_offset.clone()) as u64, chunk_index, record_size).ok_or_else(|| {
+            WriteError::RecordOffsetOverflow{var_name: var.name().to_owned(), record_index: chunk_index}
+        })?;
+        let useful_size: usize = var.chunk_len() * var.data_type().size_of();
+        let padding: bool = !(var.is_record_var() && header_def.data_set_metadata.records_are_packed);
+        let padding_size: usize = if padding { compute_padding_size(useful_size) } else { 0 };
+        return Ok((position, padding_size));
+    }
+
+    /// Writes the padding bytes following a chunk at `position`, filled with the fill value of the data type.
+    fn write_padding(&mut self, data_type: DataType, position: u64, padding_size: usize) -> Result<(), WriteError> {
+        if padding_size == 0 {
+            return Ok(());
+        }
+        let nc_fill_bytes: Vec<u8> = match data_type {
+            DataType::I8 => NC_FILL_I8.to_be_bytes().to_vec(),
+            DataType::U8 => NC_FILL_U8.to_be_bytes().to_vec(),
+            DataType::I16 => NC_FILL_I16.to_be_bytes().to_vec(),
+            DataType::I32 => NC_FILL_I32.to_be_bytes().to_vec(),
+            DataType::F32 => NC_FILL_F32.to_be_bytes().to_vec(),
+            DataType::F64 => NC_FILL_F64.to_be_bytes().to_vec(),
+        };
+        let padding_bytes: Vec<u8> = nc_fill_bytes.into_iter().cycle().take(padding_size).collect();
+        self.output_file.write_all_at(&padding_bytes, position)?;
+        return Ok(());
+    }
+
+    /// Saves the checksum of each chunk of `data`, the first one being the chunk `first_chunk_index` of the variable,
+    /// if the written data are verified (see [`WriteOptions::verify`](struct.WriteOptions.html#structfield.verify)).
+    fn save_chunk_checksums(&mut self, var_index: usize, var: &Variable, first_chunk_index: usize, data: DataSlice) {
+        let chunk_checksums: &mut BTreeMap<(usize, usize), u64> = match self.chunk_checksums.as_mut() {
+            None => return,
+            Some(chunk_checksums) => chunk_checksums,
+        };
+        let chunk_len: usize = var.chunk_len();
+        if chunk_len == 0 {
+            return;
+        }
+        for i in 0..(data.len() / chunk_len) {
+            let checksum: u64 = checksum_be_bytes(data.sub_slice(i * chunk_len, (i + 1) * chunk_len));
+            chunk_checksums.insert((var_index, first_chunk_index + i), checksum);
+        }
+    }
 
     fn update_written_records(&mut self, var: &'a Variable, records: &[usize]) -> Result<(), WriteError>
     {
-        let mut records_set: BTreeSet<usize> = records.iter().map(|index: &usize| index.clone()).collect();
+        self.output_file.counters().add_chunks_written(records.len());
+        let mut records_set: BTreeSet<usize> = records.iter().map(|index: &usize| *index).collect();
         // Get already written records for the variable
         let ref mut written_records: Option<&mut BTreeSet<usize>> = self.written_records.iter_mut()
             .find(|(var_2, _written_records): &&mut (&'a Variable, BTreeSet<usize>)| var == *var_2)
@@ -466,209 +1234,196 @@ impl<'a> FileWriter<'a> {
 
     fn write_header(&mut self) -> Result<usize, WriteError>{
         let header_def: &HeaderDefinition = self.header_def.as_ref().ok_or(WriteError::HeaderNotDefined)?;
-        self.output_file.seek(SeekFrom::Start(0))?;
-        let mut num_bytes = 0;
-        // the magic word
-        num_bytes += self.output_file.write("CDF".as_bytes())?;
-        //the version number
-        num_bytes += self.output_file.write(&[header_def.version.clone() as u8])?;
-        // the size of the *unlimited-size* dimension
-        let num_records: u32 = match header_def.data_set.unlimited_dim.as_ref() {
-            None => 0,  // No unlimited-size dim is defined
-            Some(unlim_dim) => {
-                let num_records: usize = unlim_dim.size();
-                if num_records <= (std::i32::MAX as usize) {
-                    num_records as u32
-                } else {
-                    std::u32::MAX  // indeterminate numbe of records records
-                }
-            }
-        };
-        let bytes: [u8; 4] = num_records.to_be_bytes();
-        num_bytes += self.output_file.write(&bytes)?;
-        // the list of the dimensions
-        num_bytes += FileWriter::write_dims_list(&mut self.output_file, &header_def.data_set.dims)?;
-        // the list of the global attributes
-        num_bytes += FileWriter::write_attrs_list(&mut self.output_file, &header_def.data_set.attrs)?;
-
-        // the list of the variables
-        // -------------------------
-        // compute the number of bytes *begin-offset* for each variable of the dataset
         let data_set_metadata: &ComputedDataSetMetadata = &header_def.data_set_metadata;
-        num_bytes += FileWriter::write_vars_list(&mut self.output_file, &data_set_metadata.vars_metadata)?;
-        let zero_padding_size: &usize = &data_set_metadata.header_zero_padding_size;
-        for _ in 0..*zero_padding_size {
-            num_bytes +=  self.output_file.write(&[0_u8])?;
+        // The many small parts of the header are gathered before being written
+        let mut bytes: Vec<u8> = Vec::with_capacity(data_set_metadata.header_required_size + data_set_metadata.header_zero_padding_size);
+        write_header_parts(&mut bytes, header_def.data_set, header_def.version, &data_set_metadata.vars_metadata, self.numrecs_streaming);
+        bytes.resize(bytes.len() + data_set_metadata.header_zero_padding_size, 0_u8);
+        let mut output: PositionedIo<W> = self.output_file.positioned(0)?;
+        for part in bytes.chunks(self.buffer_size) {
+            output.write_all(part)?;
         }
-        Ok(num_bytes)
-    }
-
-    fn write_name_string<T: Write>(out_stream: &mut T, name: &str) -> Result<usize, std::io::Error> {
-        let name_bytes: &[u8] = name.as_bytes();
-        let zero_padding_size = compute_padding_size(name_bytes.len());
-        let mut num_bytes = 0;
-
-        // Write the number of useful bytes
-        let bytes: [u8; 4] = (name_bytes.len() as i32).to_be_bytes();
-        num_bytes += out_stream.write(&bytes)?;
-        // Write the name
-        num_bytes += out_stream.write(name_bytes)?;
-        // Write the zero padding bytes
-        if zero_padding_size > 0 {
-            num_bytes += out_stream.write(&vec![0_u8; zero_padding_size])?;
-        }
-
-        Ok(num_bytes)
+        return Ok(bytes.len());
     }
 
-    fn write_data_type<T: Write>(out_stream: &mut T, data_type: DataType) -> Result<usize, std::io::Error> {
-        let bytes: [u8; 4] = (data_type as i32).to_be_bytes();
-        let num_bytes: usize = out_stream.write(&bytes)?;
-        Ok(num_bytes)
-    }
-
-    fn write_dims_list<T: Write>(out_stream: &mut T, dims_list: &[Rc<Dimension>]) -> Result<usize, std::io::Error> {
-        fn write_dim<T: Write>(out_stream: &mut T, dim: &Rc<Dimension>) -> Result<usize, std::io::Error> {
-            // First write the dimension name
-            let mut num_bytes = FileWriter::write_name_string(out_stream, dim.name().as_ref())?;
-            // Then write the dimension size
-            let dim_size: usize = match dim.size {
-                DimensionSize::Unlimited(_) => 0,  // the unlimited-size is recorded as 0
-                DimensionSize::Fixed(fixed_size) => fixed_size,
-            };
-            let bytes: [u8; 4] = (dim_size as i32).to_be_bytes();
-            num_bytes += out_stream.write(&bytes)?;
-
-            Ok(num_bytes)
-        }
-        let mut num_bytes: usize = 0;
-        if dims_list.is_empty() {
-            // Write the ABSENT_TAG
-            num_bytes += out_stream.write(&ABSENT_TAG)?;
-        }
-        else {
-            // Write the DIENSION_TAG
-            num_bytes += out_stream.write(&DIMENSION_TAG)?;
+}
 
-            // Write the number of dimensions
-            let num_dims: usize = dims_list.len();
-            let bytes: [u8; 4] = (num_dims as i32).to_be_bytes();
-            num_bytes += out_stream.write(&bytes)?;
+impl<'a, W: Read + Write + Seek> FileWriter<'a, W> {
 
-            // Write each dimension of the list
-            for dim in dims_list {
-                num_bytes += write_dim(out_stream, dim)?;
+    /// Fills the unwritten data, reads back the chunks written with the values given by the user and compares them with these values,
+    /// and returns the verification report (see [`VerifyReport`](struct.VerifyReport.html)).
+    ///
+    /// The option [`WriteOptions::verify`](struct.WriteOptions.html#structfield.verify) must be enabled when the writer is created,
+    /// no chunk is verified otherwise. The chunks are read through a buffer of
+    /// [`WriteOptions::buffer_size`](struct.WriteOptions.html#structfield.buffer_size) bytes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use netcdf3::{FileWriter, DataSet, Version, VerifyReport, WriteOptions};
+    ///
+    /// let mut data_set = DataSet::new();
+    /// data_set.set_unlimited_dim("time", 2).unwrap();
+    /// data_set.add_fixed_dim("cell", 3).unwrap();
+    /// data_set.add_var_f64("cell", &["cell"]).unwrap();
+    /// data_set.add_var_f32("temperature", &["time", "cell"]).unwrap();
+    ///
+    /// let options = WriteOptions{verify: true, ..WriteOptions::default()};
+    /// let mut file_writer = FileWriter::from_writer_with_options(Cursor::new(Vec::<u8>::new()), &options);
+    /// file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    /// file_writer.write_var_f64("cell", &[0.0, 1.0, 2.0]).unwrap();
+    /// file_writer.write_record_f32("temperature", 0, &[10.0, 11.0, 12.0]).unwrap();
+    ///
+    /// // the record 1 of `temperature` is filled, and not verified
+    /// let report: VerifyReport = file_writer.close_verified().unwrap();
+    /// assert_eq!(true,                        report.is_verified());
+    /// assert_eq!(2,                           report.num_verified_chunks);
+    /// assert_eq!(vec!["temperature"],         report.summary.vars_filled);
+    /// ```
+    pub fn close_verified(mut self) -> Result<VerifyReport, WriteError>
+    {
+        let summary: WriteSummary = self.finish()?;
+        let chunk_checksums: BTreeMap<(usize, usize), u64> = self.chunk_checksums.take().unwrap_or_default();
+        let mut buffer: Vec<u8> = vec![0; self.buffer_size];
+        let mut mismatched_chunks: Vec<(String, usize)> = vec![];
+        for ((var_index, chunk_index), checksum) in chunk_checksums.iter() {
+            let header_def: &HeaderDefinition = self.header_def.as_ref().ok_or(WriteError::HeaderNotDefined)?;
+            let var: &'a Variable = header_def.data_set.vars.get(*var_index).ok_or(WriteError::Unexpected)?;
+            let (position, _padding_size): (u64, usize) = self.locate_chunk(var, *chunk_index)?;
+            let useful_size: usize = var.chunk_len() * var.data_type().size_of();
+
+            let mut input: PositionedIo<W> = self.output_file.positioned(position)?;
+            let mut hasher = DefaultHasher::new();
+            let mut offset: usize = 0;
+            while offset < useful_size {
+                let part_size: usize = std::cmp::min(useful_size - offset, buffer.len());
+                input.read_exact(&mut buffer[0..part_size])?;
+                hasher.write(&buffer[0..part_size]);
+                offset += part_size;
             }
-        }
-        Ok(num_bytes)
-    }
-
-    fn write_attrs_list<T: Write>(out_stream: &mut T, attrs_list: &[Attribute]) -> Result<usize, std::io::Error> {
-        fn write_attr<T: Write>(out_stream: &mut T, attr: &Attribute) -> Result<usize, std::io::Error> {
-            // The name of the attribute
-            let mut num_bytes = FileWriter::write_name_string(out_stream, &attr.name)?;
-            // The data type of the attribute
-            num_bytes += FileWriter::write_data_type(out_stream, attr.data_type())?;
-            // The number of elements
-            let num_elements: usize = attr.len();
-            let bytes: [u8; 4] = (num_elements as i32).to_be_bytes();
-            num_bytes += out_stream.write(&bytes)?;
-            // The data of the attribute
-            num_bytes += match &attr.data {
-                DataVector::I8(slice) => FileWriter::write_chunk_i8(out_stream, slice)?,
-                DataVector::U8(slice) => FileWriter::write_chunk_u8(out_stream, slice)?,
-                DataVector::I16(slice) => FileWriter::write_chunk_i16(out_stream, slice)?,
-                DataVector::I32(slice) => FileWriter::write_chunk_i32(out_stream, slice)?,
-                DataVector::F32(slice) => FileWriter::write_chunk_f32(out_stream, slice)?,
-                DataVector::F64(slice) => FileWriter::write_chunk_f64(out_stream, slice)?,
-            };
-
-            Ok(num_bytes)
-        }
-        // The number of bytes recorded into the output stream
-        let mut num_bytes: usize = 0;
-
-        if attrs_list.is_empty() {
-            // Write the ABSENT_TAG
-            num_bytes += out_stream.write(&ABSENT_TAG)?;
-        }
-        else {
-            // Write the ATTRIBUTE_TAG
-            num_bytes += out_stream.write(&ATTRIBUTE_TAG)?;
-            // Write the number of attributes
-            let num_attrs: usize = attrs_list.len();
-            let bytes: [u8; 4] = (num_attrs as i32).to_be_bytes();
-            num_bytes += out_stream.write(&bytes)?;
-
-            // Write for each attribute:  its name, data type and data
-            for attr in attrs_list {
-                num_bytes += write_attr(out_stream, attr)?;
+            if hasher.finish() != *checksum {
+                mismatched_chunks.push((var.name().to_string(), *chunk_index));
             }
         }
-        Ok(num_bytes)
-    }
-
-    fn write_vars_list<T: Write>(out_stream: &mut T, vars_metadata_list: &[(&Variable, ComputedVariableMetadata)]) -> Result<usize, WriteError> {
-        fn write_var<T: Write>(out_stream: &mut T, var: &Variable, var_metadata: &ComputedVariableMetadata) -> Result<usize, WriteError> {
-            // Write the name of the variable
-            let mut num_bytes: usize = FileWriter::write_name_string(out_stream, &var.name)?;
-            // Write the number of dimensions
-            let num_dims = var.num_dims();
-            let mut bytes: [u8; 4] = (num_dims as i32).to_be_bytes();
-            num_bytes += out_stream.write(&bytes)?;
-            // Write each variable dimension ID
-            for dim_id in var_metadata.dim_ids.iter() {
-                bytes = (*dim_id as i32).to_be_bytes();
-                num_bytes += out_stream.write(&bytes)?;
-            }
-            // Write variable attributes
-            num_bytes += FileWriter::write_attrs_list(out_stream, &var.attrs)?;
-            // Write the variable data type
-            num_bytes += FileWriter::write_data_type(out_stream, var.data_type.clone())?;
-            // Write the `var_size` the number of bytes used per chunk (including the zero padding bytes)
-            bytes = {
-                let mut chunk_size: usize = var_metadata.chunk_size;
-                if chunk_size > (std::i32::MAX as usize) {
-                    chunk_size = std::u32::MAX as usize;
-                }
-                (chunk_size as u32).to_be_bytes()
-            };
-            num_bytes += out_stream.write(&bytes)?;
-            // Write the `begin_offset`
-            match var_metadata.begin_offset {
-                Offset::I32(begin_offset) => {
-                    let bytes: [u8; 4] = begin_offset.to_be_bytes();
-                    num_bytes += out_stream.write(&bytes)?;
-                },
-                Offset::I64(begin_offset) => {
-                    let bytes: [u8; 8] = begin_offset.to_be_bytes();
-                    num_bytes += out_stream.write(&bytes)?;
-                },
+        return Ok(VerifyReport{
+            summary: summary,
+            num_verified_chunks: chunk_checksums.len(),
+            mismatched_chunks: mismatched_chunks,
+        });
+    }
+}
+
+/// Returns the checksum of the big-endian bytes of the values, compared with the bytes read back by
+/// [`FileWriter::close_verified`](struct.FileWriter.html#method.close_verified).
+fn checksum_be_bytes(data: DataSlice) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    macro_rules! write_be_bytes {
+        ($values:expr) => {
+            for value in $values.iter() {
+                hasher.write(&value.to_be_bytes());
             }
-            Ok(num_bytes)
-        }
-        // The number of bytes recorded into the output stream
-        let mut num_bytes: usize = 0;
-        if vars_metadata_list.is_empty() {
-            // Write the ABSENT_TAG
-            num_bytes += out_stream.write(&ABSENT_TAG)?;
-        }
-        else {
-            // Write the VARIABLE_TAG
-            num_bytes += out_stream.write(&VARIABLE_TAG)?;
+        };
+    }
+    match data {
+        DataSlice::I8(values) => write_be_bytes!(values),
+        DataSlice::U8(values) => hasher.write(values),
+        DataSlice::I16(values) => write_be_bytes!(values),
+        DataSlice::I32(values) => write_be_bytes!(values),
+        DataSlice::F32(values) => write_be_bytes!(values),
+        DataSlice::F64(values) => write_be_bytes!(values),
+    }
+    return hasher.finish();
+}
 
-            // Write the number of variables
-            let num_vars: usize = vars_metadata_list.len();
-            let bytes: [u8; 4] = (num_vars as i32).to_be_bytes();
-            num_bytes += out_stream.write(&bytes)?;
+/// Converts the values into their big-endian representation in place, and returns their bytes.
+fn be_bytes_in_place(data: &mut DataVector) -> &[u8] {
+    /// Views the values of a primitive numeric type as bytes.
+    fn as_bytes<T: Copy>(values: &[T]) -> &[u8] {
+        // SAFETY: the primitive numeric types have no padding bytes, and the bytes have no alignment requirement
+        return unsafe { std::slice::from_raw_parts(values.as_ptr() as *const u8, std::mem::size_of_val(values)) };
+    }
+    return match data {
+        DataVector::I8(values) => as_bytes(values),
+        DataVector::U8(values) => values,
+        DataVector::I16(values) => {
+            values.iter_mut().for_each(|value: &mut i16| *value = value.to_be());
+            as_bytes(values)
+        },
+        DataVector::I32(values) => {
+            values.iter_mut().for_each(|value: &mut i32| *value = value.to_be());
+            as_bytes(values)
+        },
+        DataVector::F32(values) => {
+            values.iter_mut().for_each(|value: &mut f32| *value = f32::from_bits(value.to_bits().to_be()));
+            as_bytes(values)
+        },
+        DataVector::F64(values) => {
+            values.iter_mut().for_each(|value: &mut f64| *value = f64::from_bits(value.to_bits().to_be()));
+            as_bytes(values)
+        },
+    };
+}
 
-            // Write for each variable :  its name, data type, ...
-            for (var, var_metadata) in vars_metadata_list.iter() {
-                num_bytes += write_var(out_stream, var, var_metadata)?;
-            }
-        }
-        Ok(num_bytes)
+/// Returns a `header_min_size` (see [`FileWriter::set_def`](struct.FileWriter.html#method.set_def)) allowing the header
+/// of the data set to grow by the factor `growth_factor`, for example `1.5` to edit later the attributes in place
+/// with 50 % of additional bytes.
+///
+/// The size is computed for the 64-bit offset version, and is then suitable for both versions.
+/// The factors lower than `1.0` are replaced by `1.0`.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{recommended_header_min_size, DataSet, EditMode, FileReader, FileWriter, Version, edit_nc3};
+///
+/// let mut data_set = DataSet::new();
+/// data_set.add_fixed_dim("x", 3).unwrap();
+/// data_set.add_var_f32("x", &["x"]).unwrap();
+/// data_set.add_var_attr_string("x", "units", "m").unwrap();
+///
+/// let header_min_size: usize = recommended_header_min_size(&data_set, 2.0);
+/// assert_eq!(216,                 header_min_size);
+///
+/// let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+/// file_writer.set_def(&data_set, Version::Classic, header_min_size).unwrap();
+/// file_writer.write_var_f32("x", &[0.0, 1.0, 2.0]).unwrap();
+/// let mut file: Cursor<Vec<u8>> = file_writer.close_into_inner().unwrap();
+///
+/// // the header is rewritten without moving the data
+/// assert_eq!(
+///     EditMode::InPlace,
+///     edit_nc3(&mut file, |data_set| data_set.add_var_attr_string("x", "long_name", "distance")).unwrap()
+/// );
+/// ```
+pub fn recommended_header_min_size(data_set: &DataSet, growth_factor: f64) -> usize {
+    let header_required_size: usize = compute_header_required_size(data_set, Version::Offset64Bit);
+    let growth_factor: f64 = if growth_factor > 1.0 { growth_factor } else { 1.0 };
+    let header_min_size: f64 = (header_required_size as f64 * growth_factor).ceil();
+    if header_min_size >= usize::MAX as f64 {
+        return usize::MAX;
     }
+    let header_min_size: usize = header_min_size as usize;
+    return header_min_size.saturating_add(compute_padding_size(header_min_size));
+}
+
+/// Serialization of the NetCDF-3 headers and data chunks, independent of the output.
+impl<'a> FileWriter<'a> {
+
+    impl_write_typed_chunk!(write_chunk_i8, i8, NC_FILL_I8);
+    impl_write_typed_chunk!(write_chunk_u8, u8, NC_FILL_U8);
+    impl_write_typed_chunk!(write_chunk_i16, i16, NC_FILL_I16);
+    impl_write_typed_chunk!(write_chunk_i32, i32, NC_FILL_I32);
+    impl_write_typed_chunk!(write_chunk_f32, f32, NC_FILL_F32);
+    impl_write_typed_chunk!(write_chunk_f64, f64, NC_FILL_F64);
+
+    impl_write_typed_chunk_nc_fill!(write_chunk_nc_fill_i8, i8, DataType::I8, NC_FILL_I8);
+    impl_write_typed_chunk_nc_fill!(write_chunk_nc_fill_u8, u8, DataType::U8, NC_FILL_U8);
+    impl_write_typed_chunk_nc_fill!(write_chunk_nc_fill_i16, i16, DataType::I16, NC_FILL_I16);
+    impl_write_typed_chunk_nc_fill!(write_chunk_nc_fill_i32, i32, DataType::I32, NC_FILL_I32);
+    impl_write_typed_chunk_nc_fill!(write_chunk_nc_fill_f32, f32, DataType::F32, NC_FILL_F32);
+    impl_write_typed_chunk_nc_fill!(write_chunk_nc_fill_f64, f64, DataType::F64, NC_FILL_F64);
 }
 
 #[derive(Debug)]
@@ -684,15 +1439,31 @@ struct HeaderDefinition<'a> {
 }
 
 impl <'a> HeaderDefinition<'a> {
-    fn new(data_set: &'a DataSet, version: Version, header_min_size: usize) -> Result<HeaderDefinition, WriteError> {
+    fn new(data_set: &'a DataSet, version: Version, header_min_size: usize) -> Result<HeaderDefinition<'a>, WriteError> {
         Ok(HeaderDefinition{
             data_set: data_set,
-            version: version.clone(),
+            version: version,
             header_min_size: header_min_size,
             data_set_metadata: ComputedDataSetMetadata::new(data_set, version, header_min_size)?,
         })
     }
 
+    /// Returns the size (number of bytes) of the file once all the data are written.
+    #[cfg(feature = "mmap")]
+    fn file_size(&self) -> u64 {
+        let data_set_metadata: &ComputedDataSetMetadata = &self.data_set_metadata;
+        let header_size: u64 = (data_set_metadata.header_required_size + data_set_metadata.header_zero_padding_size) as u64;
+        let fixed_size_vars_size: u64 = data_set_metadata.vars_metadata.iter()
+            .filter(|(var, _var_metadata): &&(&Variable, ComputedVariableMetadata)| !var.is_record_var())
+            .fold(0, |sum: u64, (_var, var_metadata): &(&Variable, ComputedVariableMetadata)| sum.saturating_add(var_metadata.chunk_size as u64));
+        let records_size: u64 = {
+            let record_size: u64 = self.data_set.record_size().unwrap_or(0) as u64;
+            let num_records: u64 = self.data_set.num_records().unwrap_or(0) as u64;
+            record_size.saturating_mul(num_records)
+        };
+        return header_size.saturating_add(fixed_size_vars_size).saturating_add(records_size);
+    }
+
     fn get_var_metadata(&self, var: &'a Variable) -> Result<&ComputedVariableMetadata, WriteError> {
         self.data_set_metadata.vars_metadata.iter()
             .find(|(var_2, _var_metadata): &&(&Variable,  ComputedVariableMetadata)| var == *var_2)
@@ -701,190 +1472,3 @@ impl <'a> HeaderDefinition<'a> {
     }
 }
 
-#[derive(Debug)]
-struct  ComputedDataSetMetadata<'a> {
-    /// The number of bytes required for the header (containing useful bytes)
-    header_required_size: usize,
-    /// The number of the bytes of the zero padding append to the header
-    header_zero_padding_size: usize,
-    /// Metadata computed for each variable
-    vars_metadata: Vec<(&'a Variable, ComputedVariableMetadata)>
-}
-
-#[derive(Debug)]
-struct ComputedVariableMetadata {
-    /// The dimension IDs of the variable
-    dim_ids: Vec<usize>,
-    /// The number of bytes required to build each chunk of the variable
-    chunk_size: usize,
-    /// The offset (number of bytes) of the first chunck from the begin offset.
-    begin_offset: Offset,
-}
-
-impl<'a> ComputedDataSetMetadata<'a> {
-
-    /// Computes and returns all metadata required for each variable, namely :
-    ///
-    /// 0. The position of the variables stored in the *data part* (a `usize` instance).
-    /// 1. The header metadata of each variable :
-    ///     0. A reference to the variable (a `&Variable` instance).
-    ///     1. The IDs of its dimensions (a `Vec<usize>` instance)
-    ///     2. The `data_offset` to located the first chunck of the variable **from the begining of the data part** (a`usize` instance).
-    fn new(data_set: &'a DataSet, version: Version, header_min_size: usize) -> Result<ComputedDataSetMetadata, WriteError> {
-        // Create a partition of variables to distinguish :
-        // 1. Fist the *fixed-size* variables.
-        // 2. Then the *record* variables.
-        let (record_vars, non_record_vars): (Vec<(usize, &Variable)>, Vec<(usize, &Variable)>) = data_set.vars.iter()
-            .enumerate()  // keep the original positions of the variables in the header
-            .partition(|(_var_pos, var): &(usize, &Variable)|{
-                var.is_record_var()
-            });
-        let partitioned_vars: Vec<(usize, &Variable)> = non_record_vars.into_iter().chain(record_vars).collect();
-
-        // Compute the actual header size
-        let header_required_size: usize = ComputedDataSetMetadata::compute_header_required_size(data_set, version.clone());
-        let header_size: usize = {
-            let mut header_size: usize = std::cmp::max(header_min_size, header_required_size);
-            header_size += compute_padding_size(header_size);
-            header_size
-        };
-
-        // Compute the metadata for each variable
-        let mut begin_offset: usize = header_size;
-        let mut vars_metadata: Vec<(usize, (&Variable, ComputedVariableMetadata))> = vec![];
-        for (header_part_pos, var) in partitioned_vars.into_iter() {
-            let chunk_size: usize = var.chunk_size();
-            vars_metadata.push((
-                header_part_pos,
-                (
-                    var,
-                    ComputedVariableMetadata{
-                        dim_ids: data_set.get_var_dim_ids(&var.name).unwrap(),
-                        chunk_size: chunk_size,
-                        begin_offset: match &version{
-                            Version::Classic => {
-                                let offset: i32 = i32::try_from(begin_offset).map_err(|_err| WriteError::ClassicVersionNotPossible)?;
-                                Offset::I32(offset)
-                            },
-                            Version::Offset64Bit => {
-                                Offset::I64(begin_offset as i64)
-                            }
-                        },
-                    }
-                )
-            ));
-            begin_offset += chunk_size;
-        }
-
-        // Retrieve the original position
-        vars_metadata.sort_by_key(|(header_part_pos, (_var, _var_metadata)): &(usize, (&Variable, ComputedVariableMetadata))| *header_part_pos);
-        // Remove the header positions of the variables
-        let vars_metadata: Vec<(&'a Variable, ComputedVariableMetadata)> = vars_metadata.into_iter().map(|x| x.1).collect();
-
-        // Returns the meta data only
-        Ok(ComputedDataSetMetadata{
-            header_required_size: header_required_size,
-            header_zero_padding_size: header_size - header_required_size,
-            vars_metadata: vars_metadata,
-        })
-    }
-
-    /// Computes and returns the size (number of bytes) needed to write the file header.
-    fn compute_header_required_size(data_set: &'a DataSet, version: Version) -> usize
-    {
-        fn compute_name_string_size(name: &str) -> usize {
-            let mut num_bytes: usize = 0;
-            // the number bytes for the name
-            num_bytes += std::mem::size_of::<i32>();
-            // the bytes of the name
-            let num_bytes_name = name.as_bytes().len();
-            num_bytes += num_bytes_name;
-            // the bytes of the zero-padding
-            num_bytes += compute_padding_size(num_bytes_name);
-
-            return num_bytes;
-        }
-        fn compute_attrs_list_size(attrs_list: &[Attribute]) -> usize {
-            let mut num_bytes: usize = 0;
-            // the global attributes
-            if attrs_list.is_empty() {
-                num_bytes += ABSENT_TAG.len();
-            }
-            else {
-                // the tag `ATTRIBUTE_TAG`
-                num_bytes += ATTRIBUTE_TAG.len();
-                // the number of attributes
-                num_bytes += std::mem::size_of::<i32>();
-                for attr in attrs_list.iter() {
-                    // the name of the attributes
-                    num_bytes += compute_name_string_size(&attr.name);
-                    // the attribute data type
-                    num_bytes += std::mem::size_of::<i32>();
-                    // the number of elements
-                    num_bytes += std::mem::size_of::<i32>();
-                    // the ttribute data
-                    let num_useful_bytes = attr.len() * attr.data_type().size_of();
-                    num_bytes += num_useful_bytes;
-                    // Zero-passing
-                    num_bytes += compute_padding_size(num_useful_bytes);
-                }
-            }
-            return num_bytes;
-        }
-        let mut num_bytes = 0;
-        // the magic word `"CDF"`
-        num_bytes += 3;
-        // the version number
-        num_bytes += std::mem::size_of::<u8>();
-        // the length of the *unlimited-size* dimension
-        num_bytes += std::mem::size_of::<i32>();
-        // the dimensions list
-        if data_set.dims.is_empty() {
-            // the tag `ABSENT_TAG`
-            num_bytes += ABSENT_TAG.len();
-        }
-        else {
-            // the tag `DIMENSION_TAG`
-            num_bytes += DIMENSION_TAG.len();
-            // the number of dimensions
-            num_bytes += std::mem::size_of::<i32>();
-            for dim in data_set.dims.iter() {
-                // the name of the dimension
-                num_bytes += compute_name_string_size(&dim.name.borrow());
-                // the size og the dimension
-                num_bytes += std::mem::size_of::<i32>();
-            }
-        }
-        // the global attributes
-        num_bytes += compute_attrs_list_size(&data_set.attrs);
-        // the variables list
-        if data_set.vars.is_empty() {
-            num_bytes += ABSENT_TAG.len();
-        }
-        else {
-            num_bytes += VARIABLE_TAG.len();
-            // the number of variables
-            num_bytes += std::mem::size_of::<i32>();
-            for var in data_set.vars.iter() {
-                // the variable name
-                num_bytes += compute_name_string_size(&var.name);
-                // the number of dimensions
-                num_bytes += std::mem::size_of::<i32>();
-                // the ID of each dimension of the variable
-                num_bytes += var.num_dims() * std::mem::size_of::<i32>();
-                // the list of variable attributes
-                num_bytes += compute_attrs_list_size(&var.attrs);
-                // the variables data type
-                num_bytes += std::mem::size_of::<i32>();
-                // the number of bytes required each chunck
-                num_bytes += std::mem::size_of::<i32>();
-                // the begin offset depends of the NetCDF-3 version
-                num_bytes += match version {
-                    Version::Classic => std::mem::size_of::<i32>(),
-                    Version::Offset64Bit => std::mem::size_of::<i64>(),
-                }
-            }
-        }
-        return num_bytes;
-    }
-}
\ No newline at end of file
@@ -1,11 +1,11 @@
-#![cfg(test)]
+#![cfg(all(test, feature = "read"))]
 
 use crate::{
     DataSet, Version,
     FileReader,
 };
 
-use super::ComputedDataSetMetadata;
+use super::compute_header_required_size;
 
 use copy_to_tmp_file::{
     copy_bytes_to_tmp_file,
@@ -25,6 +25,6 @@ fn test_compute_header_required_size() {
         (data_set, version)
     };
 
-    let header_size: usize = ComputedDataSetMetadata::compute_header_required_size(&data_set, version);
+    let header_size: usize = compute_header_required_size(&data_set, version);
     assert_eq!(EXPECTED_HEADER_SIZE,        header_size);
 }
@@ -1,5 +1,6 @@
-#![cfg(test)]
+#![cfg(all(test, feature = "read"))]
 use std::rc::Rc;
+use std::borrow::Cow;
 use std::io::{Read, Cursor};
 use std::path::PathBuf;
 
@@ -8,10 +9,15 @@ use tempdir::TempDir;
 use byteorder::{ReadBytesExt, BigEndian};
 
 use crate::Dimension;
+use crate::NameValidation;
 use crate::FileReader;
 use crate::Variable;
 use crate::DataType;
-use crate::error::WriteError;
+use crate::DataVector;
+use crate::DataSlice;
+use crate::InvalidDataSet;
+use crate::error::{IOError, WriteError};
+use crate::{IoMetrics, VerifyReport, WriteSummary};
 use crate::NC_FILL_I8;
 use crate::NC_FILL_U8;
 use crate::NC_FILL_I16;
@@ -19,9 +25,11 @@ use crate::NC_FILL_I32;
 use crate::NC_FILL_F32;
 use crate::NC_FILL_F64;
 
+use crate::io::{header, ABSENT_TAG, DIMENSION_TAG};
 use super::{
-    FileWriter, DataSet, Version,
-    ABSENT_TAG, DIMENSION_TAG,
+    FileWriter, DataSet, Version, WriteOptions,
+    WRITE_BUFFER_SIZE, FILL_BLOCK_SIZE,
+    compute_header_required_size, recommended_header_min_size,
 };
 
 const TMP_DIR_PREFIX: &str = "netcdf3_tests_";
@@ -63,6 +71,43 @@ fn test_file_writer_open() {
     tmp_dir.close().unwrap();
 }
 
+#[cfg(any(unix, windows))]
+#[test]
+fn test_file_writer_positional_writes() {
+    const TEST_FILE_NAME: &str = "test_file_writer_positional_writes.nc";
+    let tmp_dir: TempDir = TempDir::new(TMP_DIR_PREFIX).unwrap();
+    let test_file_path: PathBuf = tmp_dir.path().join(TEST_FILE_NAME);
+
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim("time", 3).unwrap();
+    data_set.add_fixed_dim("cell", 3).unwrap();
+    data_set.add_var_i16("cell", &["cell"]).unwrap();
+    data_set.add_var_f32("temperature", &["time", "cell"]).unwrap();
+    data_set.add_var_f64("time", &["time"]).unwrap();
+
+    let options = WriteOptions{verify: true, ..WriteOptions::default()};
+    let mut file_writer: FileWriter = FileWriter::open_with_options(&test_file_path, &options).unwrap();
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.write_var_i16("cell", &[1, 2, 3]).unwrap();
+    file_writer.write_record_f32("temperature", 0, &[10.0, 11.0, 12.0]).unwrap();
+    file_writer.write_records(1..2, &[("temperature", DataSlice::from(&[20.0_f32, 21.0, 22.0][..])), ("time", DataSlice::from(&[1.0_f64][..]))]).unwrap();
+    file_writer.write_var_owned("time", DataVector::F64(vec![0.0, 1.0, 2.0])).unwrap();
+    // The header and the chunks are written without any seek
+    assert_eq!(0,                                           file_writer.io_metrics().seeks);
+    let report: VerifyReport = file_writer.close_verified().unwrap();
+    assert_eq!(true,                                        report.is_verified());
+    assert_eq!(6,                                           report.num_verified_chunks);
+
+    let mut file_reader: FileReader = FileReader::open(&test_file_path).unwrap();
+    assert_eq!(vec![1, 2, 3],                               file_reader.read_var_i16("cell").unwrap());
+    assert_eq!(vec![0.0, 1.0, 2.0],                         file_reader.read_var_f64("time").unwrap());
+    assert_eq!(
+        vec![10.0, 11.0, 12.0, 20.0, 21.0, 22.0, NC_FILL_F32, NC_FILL_F32, NC_FILL_F32],
+        file_reader.read_var_f32("temperature").unwrap()
+    );
+    tmp_dir.close().unwrap();
+}
+
 #[test]
 fn test_file_writer_create_new() {
     const TEST_FILE_NAME: &str = "test_file_writer_create_new.nc";
@@ -82,7 +127,7 @@ fn test_file_writer_create_new() {
 
     // Try to recreate the already existing file
     assert_eq!(
-        WriteError::IOErrorKind(std::io::ErrorKind::AlreadyExists),
+        WriteError::IO(IOError::from(std::io::ErrorKind::AlreadyExists)),
         FileWriter::create_new(&test_file_path).unwrap_err(),
     );
     assert_eq!(true,                test_file_path.exists());
@@ -97,6 +142,73 @@ fn test_file_writer_create_new() {
     tmp_dir.close().unwrap();
 }
 
+#[test]
+fn test_file_writer_open_append() {
+    const TEST_FILE_NAME: &str = "test_file_writer_open_append.nc";
+    fn new_data_set(num_records: usize) -> DataSet {
+        let mut data_set = DataSet::new();
+        data_set.set_unlimited_dim("time", num_records).unwrap();
+        data_set.add_fixed_dim("cell", 2).unwrap();
+        data_set.add_var_i32("cell", &["cell"]).unwrap();
+        data_set.add_var_i16("var_1", &["time", "cell"]).unwrap();
+        data_set.add_var_attr_i16("var_1", "_FillValue", vec![-1]).unwrap();
+        data_set.add_var_f64("var_2", &["time"]).unwrap();
+        data_set
+    }
+
+    let tmp_dir: TempDir = TempDir::new(TMP_DIR_PREFIX).unwrap();
+    let test_file_path: PathBuf = tmp_dir.path().join(TEST_FILE_NAME);
+    let data_set_1: DataSet = new_data_set(2);
+    {
+        let mut file_writer: FileWriter = FileWriter::open(&test_file_path).unwrap();
+        file_writer.set_def(&data_set_1, Version::Classic, 0).unwrap();
+        file_writer.write_var_i32("cell", &[10, 11]).unwrap();
+        file_writer.write_record_i16("var_1", 0, &[1, 2]).unwrap();
+        file_writer.write_record_f64("var_2", 0, &[1.0]).unwrap();
+        file_writer.write_record_i16("var_1", 1, &[3, 4]).unwrap();
+        file_writer.close().unwrap();
+    }
+    // the writing of the second record is interrupted in the middle of `var_1` (4 + 8 bytes)
+    let file_size: u64 = std::fs::metadata(&test_file_path).unwrap().len();
+    std::fs::OpenOptions::new().write(true).open(&test_file_path).unwrap().set_len(file_size - 10).unwrap();
+
+    // a data set with less records or other variables does not match the file
+    let data_set_2: DataSet = new_data_set(1);
+    assert_eq!(
+        WriteError::AppendDefinitionMismatch(String::from("time")),
+        FileWriter::open_append(&test_file_path, &data_set_2).unwrap_err()
+    );
+    let mut data_set_3: DataSet = new_data_set(3);
+    data_set_3.remove_var("var_2").unwrap();
+    assert_eq!(
+        WriteError::AppendDefinitionMismatch(String::from("var_2")),
+        FileWriter::open_append(&test_file_path, &data_set_3).unwrap_err()
+    );
+    let mut data_set_4: DataSet = new_data_set(3);
+    data_set_4.add_var_attr_string("var_2", "units", "m").unwrap();
+    assert_eq!(
+        WriteError::AppendDefinitionMismatch(String::from("cell")),
+        FileWriter::open_append(&test_file_path, &data_set_4).unwrap_err()
+    );
+    // the partial record is not completed when the definitions are not matching
+    assert_eq!(file_size - 10,                                  std::fs::metadata(&test_file_path).unwrap().len());
+
+    // the partial record is completed with the fill values, and a third record is appended
+    let data_set_5: DataSet = new_data_set(3);
+    let mut file_writer: FileWriter = FileWriter::open_append(&test_file_path, &data_set_5).unwrap();
+    file_writer.write_record_i16("var_1", 2, &[5, 6]).unwrap();
+    let summary: WriteSummary = file_writer.close().unwrap();
+    assert_eq!(3,                                               summary.num_records);
+
+    let mut file_reader: FileReader = FileReader::open(&test_file_path).unwrap();
+    assert_eq!(Some(3),                                         file_reader.data_set().num_records());
+    assert_eq!(DataVector::I32(vec![10, 11]),                   file_reader.read_var("cell").unwrap());
+    assert_eq!(DataVector::I16(vec![1, 2, 3, -1, 5, 6]),        file_reader.read_var("var_1").unwrap());
+    assert_eq!(DataVector::F64(vec![1.0, NC_FILL_F64, NC_FILL_F64]),    file_reader.read_var("var_2").unwrap());
+
+    tmp_dir.close().unwrap();
+}
+
 #[test]
 fn test_file_writer_fill_missing_data_at_closing() {
     const TEST_FILE_NAME: &str = "test_file_writer_fill_missing_data_at_closing.nc";
@@ -1160,7 +1272,7 @@ fn test_write_dims_list() {
     {
         let bytes: Vec<u8> = {
             let mut bytes: Vec<u8> = vec![];
-            let _ = FileWriter::write_dims_list(&mut bytes, &[]).unwrap();
+            header::write_dims_list(&mut bytes, &[]);
             bytes
         };
 
@@ -1173,10 +1285,10 @@ fn test_write_dims_list() {
         const DIM_NAME: &str = "dim_1";
         const DIM_SIZE: usize = 10;
         let mut cursor: Cursor<Vec<u8>> = {
-            let dim_1 = Rc::new(Dimension::new_fixed_size(DIM_NAME, DIM_SIZE).unwrap());
+            let dim_1 = Rc::new(Dimension::new_fixed_size(DIM_NAME, DIM_SIZE, NameValidation::Strict, false).unwrap());
 
             let mut bytes: Vec<u8> = vec![];
-            let _ = FileWriter::write_dims_list(&mut bytes, &[dim_1]).unwrap();
+            header::write_dims_list(&mut bytes, &[dim_1]);
             Cursor::new(bytes)
         };
 
@@ -1208,10 +1320,10 @@ fn test_write_dims_list() {
         const DIM_NAME: &str = "dim_1";
         const DIM_SIZE: usize = 10;
         let mut cursor: Cursor<Vec<u8>> = {
-            let dim_1 = Rc::new(Dimension::new_unlimited_size(DIM_NAME, DIM_SIZE).unwrap());
+            let dim_1 = Rc::new(Dimension::new_unlimited_size(DIM_NAME, DIM_SIZE, NameValidation::Strict, false).unwrap());
 
             let mut bytes: Vec<u8> = vec![];
-            let _ = FileWriter::write_dims_list(&mut bytes, &[dim_1]).unwrap();
+            header::write_dims_list(&mut bytes, &[dim_1]);
             Cursor::new(bytes)
         };
 
@@ -1246,7 +1358,7 @@ fn test_write_name_string() {
     {
         let mut cursor: Cursor<Vec<u8>> = {
             let mut bytes: Vec<u8> = vec![];
-            FileWriter::write_name_string(&mut bytes, "a").unwrap();
+            header::write_name_string(&mut bytes, "a");
             Cursor::new(bytes)
         };
 
@@ -1262,7 +1374,7 @@ fn test_write_name_string() {
     {
         let mut cursor: Cursor<Vec<u8>> = {
             let mut bytes: Vec<u8> = vec![];
-            FileWriter::write_name_string(&mut bytes, "abcd").unwrap();
+            header::write_name_string(&mut bytes, "abcd");
             Cursor::new(bytes)
         };
 
@@ -1278,7 +1390,7 @@ fn test_write_name_string() {
     {
         let mut cursor: Cursor<Vec<u8>> = {
             let mut bytes: Vec<u8> = vec![];
-            FileWriter::write_name_string(&mut bytes, "abcde").unwrap();
+            header::write_name_string(&mut bytes, "abcde");
             Cursor::new(bytes)
         };
 
@@ -1298,7 +1410,7 @@ fn test_write_name_string() {
     {
         let mut cursor: Cursor<Vec<u8>> = {
             let mut bytes: Vec<u8> = vec![];
-            FileWriter::write_name_string(&mut bytes, "café").unwrap();
+            header::write_name_string(&mut bytes, "café");
             Cursor::new(bytes)
         };
 
@@ -1313,4 +1425,815 @@ fn test_write_name_string() {
         assert_eq!(0,           cursor.read_u8().unwrap());
         assert_eq!(0,           cursor.read_u8().unwrap());
     }
-}
\ No newline at end of file
+}
+#[test]
+fn test_file_writer_numrecs_streaming() {
+    const VAR_I32_NAME: &str = "var_i32";
+    const UNLIM_DIM_NAME: &str = "unlimited_dim";
+    const UNLIM_DIM_SIZE: usize = 3;
+
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim(UNLIM_DIM_NAME, UNLIM_DIM_SIZE).unwrap();
+    data_set.add_var_i32(VAR_I32_NAME, &[UNLIM_DIM_NAME]).unwrap();
+
+    // Enable the streaming mode after the definition of the header
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    assert_eq!(false,                       file_writer.numrecs_is_streaming());
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.set_numrecs_streaming(true).unwrap();
+    assert_eq!(true,                        file_writer.numrecs_is_streaming());
+    file_writer.write_record_i32(VAR_I32_NAME, 0, &[1]).unwrap();
+    file_writer.write_record_i32(VAR_I32_NAME, 1, &[2]).unwrap();
+    let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+    assert_eq!(std::u32::MAX,               (&bytes[4..8]).read_u32::<BigEndian>().unwrap());
+
+    // The number of records is deduced from the size of the file
+    let mut file_reader = FileReader::from_reader(Cursor::new(&bytes[..(bytes.len() - 4)])).unwrap();
+    assert_eq!(true,                        file_reader.numrecs_is_streaming());
+    assert_eq!(Some(2),                     file_reader.data_set().num_records());
+    assert_eq!(vec![1, 2],                  file_reader.read_var_i32(VAR_I32_NAME).unwrap());
+
+    // Disable the streaming mode before closing the file
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    file_writer.set_numrecs_streaming(true).unwrap();
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.set_numrecs_streaming(false).unwrap();
+    let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+    assert_eq!(UNLIM_DIM_SIZE as u32,       (&bytes[4..8]).read_u32::<BigEndian>().unwrap());
+    let file_reader = FileReader::from_reader(Cursor::new(bytes)).unwrap();
+    assert_eq!(false,                       file_reader.numrecs_is_streaming());
+    assert_eq!(Some(UNLIM_DIM_SIZE),        file_reader.data_set().num_records());
+}
+
+#[test]
+fn test_file_writer_format_limits() {
+    const DIM_SIZE: usize = 40_000;
+    // 40_000 * 40_000 * 1 byte (~1.49 GiB)
+    const CHUNK_SIZE: usize = DIM_SIZE * DIM_SIZE;
+
+    let mut data_set = DataSet::new();
+    data_set.add_fixed_dim("dim_1", DIM_SIZE).unwrap();
+    data_set.add_fixed_dim("dim_2", DIM_SIZE).unwrap();
+    data_set.add_var_i8("var_1", &["dim_1", "dim_2"]).unwrap();
+    data_set.add_var_i8("var_2", &["dim_1", "dim_2"]).unwrap();
+    data_set.add_var_i8("var_3", &["dim_1", "dim_2"]).unwrap();
+    let header_size: usize = compute_header_required_size(&data_set, Version::Classic);
+
+    // The begin offset of `var_3` exceeds `2^31 - 1` for the classic version
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    assert_eq!(
+        WriteError::MaximumBeginOffsetExceeded{
+            var_name: String::from("var_3"),
+            version: Version::Classic,
+            max: std::i32::MAX as u64,
+            get: (header_size + 2 * CHUNK_SIZE) as u64,
+        },
+        file_writer.set_def(&data_set, Version::Classic, 0).unwrap_err()
+    );
+    file_writer.set_def(&data_set, Version::Offset64Bit, 0).unwrap();
+
+    // The chunk size of `var_1` exceeds the limit of the 64-bit offset version
+    data_set.add_fixed_dim("dim_3", 5).unwrap();
+    data_set.remove_var("var_2").unwrap();
+    data_set.remove_var("var_3").unwrap();
+    data_set.remove_var("var_1").unwrap();
+    data_set.add_var_i8("var_1", &["dim_1", "dim_2", "dim_3"]).unwrap();
+    let too_large = InvalidDataSet::MaximumVariableSizeExceeded{
+        var_name: String::from("var_1"),
+        version: Version::Offset64Bit,
+        max: Version::Offset64Bit.max_chunk_size(),
+        get: CHUNK_SIZE * 5,
+    };
+    assert_eq!(Err(too_large.clone()),      data_set.add_var_i8("var_2", &["dim_1"]));
+    // defined without the checks, like the variables read from a file
+    data_set.add_var_using_dim_refs("var_2", data_set.get_dims_from_dim_ids(&[0]).unwrap(), DataType::I8).unwrap();
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    assert_eq!(
+        WriteError::DataSet(too_large),
+        file_writer.set_def(&data_set, Version::Offset64Bit, 0).unwrap_err()
+    );
+}
+
+#[test]
+fn test_file_writer_header_size_overflow() {
+    let mut data_set = DataSet::new();
+    data_set.add_fixed_dim("dim_1", 1).unwrap();
+    data_set.add_var_i8("var_1", &["dim_1"]).unwrap();
+
+    // The padding of the header size overflows `usize`
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    assert_eq!(
+        WriteError::HeaderSizeOverflow{header_min_size: std::usize::MAX},
+        file_writer.set_def(&data_set, Version::Classic, std::usize::MAX).unwrap_err()
+    );
+    // The begin offset of `var_1` exceeds `2^31 - 1` for the classic version
+    assert_eq!(
+        WriteError::MaximumBeginOffsetExceeded{
+            var_name: String::from("var_1"),
+            version: Version::Classic,
+            max: std::i32::MAX as u64,
+            get: (std::usize::MAX - 3) as u64,
+        },
+        file_writer.set_def(&data_set, Version::Classic, std::usize::MAX - 3).unwrap_err()
+    );
+}
+
+#[test]
+fn test_file_writer_single_record_var_packing() {
+    const VAR_I16_NAME: &str = "var_i16";
+    const VAR_I8_NAME: &str = "var_i8";
+    const UNLIM_DIM_NAME: &str = "unlimited_dim";
+    const UNLIM_DIM_SIZE: usize = 3;
+    const FIXED_DIM_NAME: &str = "fixed_dim";
+    const FIXED_DIM_SIZE: usize = 3;
+
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim(UNLIM_DIM_NAME, UNLIM_DIM_SIZE).unwrap();
+    data_set.add_fixed_dim(FIXED_DIM_NAME, FIXED_DIM_SIZE).unwrap();
+    data_set.add_var_i16(VAR_I16_NAME, &[UNLIM_DIM_NAME, FIXED_DIM_NAME]).unwrap();
+    // 3 * 2 bytes per record, without the zero padding bytes
+    assert_eq!(Some(6),                     data_set.record_size());
+    assert_eq!(8,                           data_set.get_var(VAR_I16_NAME).unwrap().chunk_size());
+    let header_size: usize = compute_header_required_size(&data_set, Version::Classic);
+
+    // The records are written in any order, each one is contiguous to the previous one
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.write_record_i16(VAR_I16_NAME, 2, &[7, 8, 9]).unwrap();
+    file_writer.write_record_i16(VAR_I16_NAME, 0, &[1, 2, 3]).unwrap();
+    let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+    assert_eq!(header_size + 3 * 6,         bytes.len());
+    let mut file_reader = FileReader::from_reader(Cursor::new(bytes)).unwrap();
+    assert_eq!(Some(6),                     file_reader.data_set().record_size());
+    assert_eq!(vec![1, 2, 3, NC_FILL_I16, NC_FILL_I16, NC_FILL_I16, 7, 8, 9],   file_reader.read_var_i16(VAR_I16_NAME).unwrap());
+    assert_eq!(vec![7, 8, 9],               file_reader.read_record_i16(VAR_I16_NAME, 2).unwrap());
+    assert_eq!(true,                        file_reader.diagnostics().is_empty());
+
+    // With a second record variable, the records are padded
+    data_set.add_var_i8(VAR_I8_NAME, &[UNLIM_DIM_NAME]).unwrap();
+    assert_eq!(Some(8 + 4),                 data_set.record_size());
+    let header_size: usize = compute_header_required_size(&data_set, Version::Classic);
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.write_var_i16(VAR_I16_NAME, &[1, 2, 3, 4, 5, 6, 7, 8, 9]).unwrap();
+    file_writer.write_var_i8(VAR_I8_NAME, &[1, 2, 3]).unwrap();
+    let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+    assert_eq!(header_size + 3 * 12,        bytes.len());
+    let mut file_reader = FileReader::from_reader(Cursor::new(bytes)).unwrap();
+    assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8, 9],     file_reader.read_var_i16(VAR_I16_NAME).unwrap());
+    assert_eq!(vec![1, 2, 3],               file_reader.read_var_i8(VAR_I8_NAME).unwrap());
+}
+
+#[test]
+fn test_file_writer_fill_fixed_size_vars_with_records() {
+    const FIXED_VAR_NAME: &str = "fixed_var";
+    const RECORD_VAR_NAME: &str = "record_var";
+    const UNLIM_DIM_NAME: &str = "unlimited_dim";
+    const FIXED_DIM_NAME: &str = "fixed_dim";
+
+    // The fixed-size variable is filled once, not once per record (that overwrote the records)
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim(UNLIM_DIM_NAME, 3).unwrap();
+    data_set.add_var_i32(RECORD_VAR_NAME, &[UNLIM_DIM_NAME]).unwrap();
+    data_set.add_var_i32(FIXED_VAR_NAME, &[] as &[&str]).unwrap();
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.write_var_i32(RECORD_VAR_NAME, &[1, 2, 3]).unwrap();
+    let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+    let mut file_reader = FileReader::from_reader(Cursor::new(bytes)).unwrap();
+    assert_eq!(vec![1, 2, 3],               file_reader.read_var_i32(RECORD_VAR_NAME).unwrap());
+    assert_eq!(vec![NC_FILL_I32],           file_reader.read_var_i32(FIXED_VAR_NAME).unwrap());
+
+    // The data of the fixed-size variable are kept without any record
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim(UNLIM_DIM_NAME, 0).unwrap();
+    data_set.add_fixed_dim(FIXED_DIM_NAME, 2).unwrap();
+    data_set.add_var_i32(FIXED_VAR_NAME, &[FIXED_DIM_NAME]).unwrap();
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.write_var_i32(FIXED_VAR_NAME, &[1, 2]).unwrap();
+    let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+    let mut file_reader = FileReader::from_reader(Cursor::new(bytes)).unwrap();
+    assert_eq!(vec![1, 2],                  file_reader.read_var_i32(FIXED_VAR_NAME).unwrap());
+}
+
+/// Output counting the calls of `write`
+struct CountingWriter {
+    inner: Cursor<Vec<u8>>,
+    num_writes: usize,
+    num_seeks: usize,
+}
+
+impl std::io::Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.num_writes += 1;
+        return self.inner.write(buf);
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        return self.inner.flush();
+    }
+}
+
+impl std::io::Seek for CountingWriter {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        self.num_seeks += 1;
+        return self.inner.seek(pos);
+    }
+}
+
+#[test]
+fn test_file_writer_buffered_chunks() {
+    const UNLIM_DIM_NAME: &str = "unlimited_dim";
+    const FIXED_DIM_NAME: &str = "fixed_dim";
+    const NUM_RECORDS: usize = 4;
+    const LARGE_DIM_SIZE: usize = WRITE_BUFFER_SIZE + 1;
+
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim(UNLIM_DIM_NAME, NUM_RECORDS).unwrap();
+    data_set.add_fixed_dim(FIXED_DIM_NAME, 3).unwrap();
+    data_set.add_var_i16("record_var_1", &[UNLIM_DIM_NAME, FIXED_DIM_NAME]).unwrap();
+    data_set.add_var_i8("record_var_2", &[UNLIM_DIM_NAME]).unwrap();
+
+    // Each record is serialized with its padding bytes, then written at once
+    let mut file_writer = FileWriter::from_writer(CountingWriter{inner: Cursor::new(vec![]), num_writes: 0, num_seeks: 0});
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.write_var_i8("record_var_2", &[1, 2, 3, 4]).unwrap();
+    let num_writes: usize = file_writer.output_file.get_ref().num_writes;
+    file_writer.write_var_i16("record_var_1", &(0..12).collect::<Vec<i16>>()).unwrap();
+    assert_eq!(NUM_RECORDS,                 file_writer.output_file.get_ref().num_writes - num_writes);
+    let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().inner.into_inner();
+    let mut file_reader = FileReader::from_reader(Cursor::new(bytes)).unwrap();
+    assert_eq!((0..12).collect::<Vec<i16>>(),   file_reader.read_var_i16("record_var_1").unwrap());
+    assert_eq!(vec![1, 2, 3, 4],                file_reader.read_var_i8("record_var_2").unwrap());
+
+    // The large chunks are written by batches, the buffer keeping a bounded size
+    let mut data_set = DataSet::new();
+    data_set.add_fixed_dim(FIXED_DIM_NAME, LARGE_DIM_SIZE).unwrap();
+    data_set.add_var_i32("large_var", &[FIXED_DIM_NAME]).unwrap();
+    data_set.add_var_u8("fill_var", &[FIXED_DIM_NAME]).unwrap();
+    let large_data: Vec<i32> = (0..LARGE_DIM_SIZE as i32).collect();
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.write_var_i32("large_var", &large_data).unwrap();
+    assert_eq!(true,                        file_writer.buffer.len() <= WRITE_BUFFER_SIZE);
+    let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+    let mut file_reader = FileReader::from_reader(Cursor::new(bytes)).unwrap();
+    assert_eq!(large_data,                  file_reader.read_var_i32("large_var").unwrap());
+    assert_eq!(vec![NC_FILL_U8; LARGE_DIM_SIZE],    file_reader.read_var_u8("fill_var").unwrap());
+}
+
+#[test]
+fn test_file_writer_contiguous_records() {
+    const UNLIM_DIM_NAME: &str = "unlimited_dim";
+    const FIXED_DIM_NAME: &str = "fixed_dim";
+    const NUM_RECORDS: usize = 10;
+
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim(UNLIM_DIM_NAME, NUM_RECORDS).unwrap();
+    data_set.add_fixed_dim(FIXED_DIM_NAME, 3).unwrap();
+    data_set.add_var_i16("var_1", &[UNLIM_DIM_NAME, FIXED_DIM_NAME]).unwrap();
+    let data: Vec<i16> = (0..(3 * NUM_RECORDS as i16)).collect();
+
+    // Write the records one by one
+    let mut file_writer = FileWriter::from_writer(CountingWriter{inner: Cursor::new(vec![]), num_writes: 0, num_seeks: 0});
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    for (i, record) in data.chunks(3).enumerate() {
+        file_writer.write_record_i16("var_1", i, record).unwrap();
+    }
+    let expected_bytes: Vec<u8> = file_writer.close_into_inner().unwrap().inner.into_inner();
+
+    // The records of the only record variable are written at once
+    let mut file_writer = FileWriter::from_writer(CountingWriter{inner: Cursor::new(vec![]), num_writes: 0, num_seeks: 0});
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    let num_writes: usize = file_writer.output_file.get_ref().num_writes;
+    file_writer.write_var_i16("var_1", &data).unwrap();
+    assert_eq!(1,                           file_writer.output_file.get_ref().num_writes - num_writes);
+    let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().inner.into_inner();
+    assert_eq!(expected_bytes,              bytes);
+
+    let mut file_writer = FileWriter::from_writer(CountingWriter{inner: Cursor::new(vec![]), num_writes: 0, num_seeks: 0});
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    let num_writes: usize = file_writer.output_file.get_ref().num_writes;
+    file_writer.write_var_owned("var_1", DataVector::I16(data.clone())).unwrap();
+    assert_eq!(1,                           file_writer.output_file.get_ref().num_writes - num_writes);
+    let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().inner.into_inner();
+    assert_eq!(expected_bytes,              bytes);
+}
+
+#[test]
+fn test_file_writer_write_records() {
+    const UNLIM_DIM_NAME: &str = "unlimited_dim";
+    const FIXED_DIM_NAME: &str = "fixed_dim";
+    const NUM_RECORDS: usize = 4;
+
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim(UNLIM_DIM_NAME, NUM_RECORDS).unwrap();
+    data_set.add_fixed_dim(FIXED_DIM_NAME, 3).unwrap();
+    data_set.add_var_i32("fixed_var", &[FIXED_DIM_NAME]).unwrap();
+    data_set.add_var_f64("var_1", &[UNLIM_DIM_NAME]).unwrap();
+    data_set.add_var_i8("var_2", &[UNLIM_DIM_NAME, FIXED_DIM_NAME]).unwrap();
+    data_set.add_var_f32("var_3", &[UNLIM_DIM_NAME, FIXED_DIM_NAME]).unwrap();
+    let data_1: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0];
+    let data_2: Vec<i8> = (0..12).collect();
+    let data_3: Vec<f32> = (0..12).map(|x: i32| x as f32 * 0.5).collect();
+
+    // Write the variables one by one
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.write_var_f64("var_1", &data_1).unwrap();
+    file_writer.write_var_i8("var_2", &data_2).unwrap();
+    file_writer.write_var_f32("var_3", &data_3).unwrap();
+    let expected_bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+
+    // Write all the records at once, the output is written sequentially
+    let mut file_writer = FileWriter::from_writer(CountingWriter{inner: Cursor::new(vec![]), num_writes: 0, num_seeks: 0});
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    let num_seeks: usize = file_writer.output_file.get_ref().num_seeks;
+    file_writer.write_records(0..NUM_RECORDS, &[
+        ("var_3", DataSlice::from(&data_3[..])),
+        ("var_1", DataSlice::from(&data_1[..])),
+        ("var_2", DataSlice::from(&data_2[..])),
+    ]).unwrap();
+    assert_eq!(1,                           file_writer.output_file.get_ref().num_seeks - num_seeks);
+    let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().inner.into_inner();
+    assert_eq!(expected_bytes,              bytes);
+
+    // Write the records by steps, and a variable apart
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.write_records(0..1, &[
+        ("var_1", DataSlice::from(&data_1[0..1])),
+        ("var_3", DataSlice::from(&data_3[0..3])),
+    ]).unwrap();
+    file_writer.write_records(1..4, &[
+        ("var_1", DataSlice::from(&data_1[1..4])),
+        ("var_3", DataSlice::from(&data_3[3..12])),
+    ]).unwrap();
+    file_writer.write_records(0..4, &[("var_2", DataSlice::from(&data_2[..]))]).unwrap();
+    file_writer.write_records(0..0, &[]).unwrap();
+    let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+    assert_eq!(expected_bytes,              bytes);
+
+    // Errors
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    assert_eq!(
+        WriteError::HeaderNotDefined,
+        file_writer.write_records(0..1, &[("var_1", DataSlice::from(&data_1[0..1]))]).unwrap_err()
+    );
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    assert_eq!(
+        WriteError::RecordIndexExceeded{index: 4, num_records: NUM_RECORDS},
+        file_writer.write_records(3..5, &[("var_1", DataSlice::from(&data_1[0..2]))]).unwrap_err()
+    );
+    assert_eq!(
+        WriteError::VariableNotDefined(String::from("undef_var")),
+        file_writer.write_records(0..1, &[("undef_var", DataSlice::from(&data_1[0..1]))]).unwrap_err()
+    );
+    assert_eq!(
+        WriteError::VariableNotRecord(String::from("fixed_var")),
+        file_writer.write_records(0..1, &[("fixed_var", DataSlice::I32(&[1, 2, 3]))]).unwrap_err()
+    );
+    assert_eq!(
+        WriteError::VariableMismatchDataType{var_name: String::from("var_2"), req: DataType::I8, get: DataType::F64},
+        file_writer.write_records(0..1, &[("var_2", DataSlice::from(&data_1[0..3]))]).unwrap_err()
+    );
+    assert_eq!(
+        WriteError::RecordMismatchDataLength{var_name: String::from("var_2"), req: 6, get: 3},
+        file_writer.write_records(0..2, &[("var_2", DataSlice::from(&data_2[0..3]))]).unwrap_err()
+    );
+}
+
+#[test]
+fn test_file_writer_buffer_size() {
+    const FIXED_DIM_NAME: &str = "fixed_dim";
+
+    let mut data_set = DataSet::new();
+    data_set.add_fixed_dim(FIXED_DIM_NAME, 100).unwrap();
+    data_set.add_var_i16("var_1", &[FIXED_DIM_NAME]).unwrap();
+    data_set.add_global_attr_string("title", "buffered header").unwrap();
+    let data: Vec<i16> = (0..100).collect();
+
+    // The header is written at once by default
+    let mut file_writer = FileWriter::from_writer(CountingWriter{inner: Cursor::new(vec![]), num_writes: 0, num_seeks: 0});
+    assert_eq!(WRITE_BUFFER_SIZE,           file_writer.buffer_size());
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    assert_eq!(1,                           file_writer.output_file.get_ref().num_writes);
+    file_writer.write_var_i16("var_1", &data).unwrap();
+    assert_eq!(2,                           file_writer.output_file.get_ref().num_writes);
+    let expected_bytes: Vec<u8> = file_writer.close_into_inner().unwrap().inner.into_inner();
+
+    // Small buffers
+    let options = WriteOptions{buffer_size: 16, ..WriteOptions::default()};
+    let mut file_writer = FileWriter::from_writer_with_options(CountingWriter{inner: Cursor::new(vec![]), num_writes: 0, num_seeks: 0}, &options);
+    assert_eq!(16,                          file_writer.buffer_size());
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    let num_writes: usize = file_writer.output_file.get_ref().num_writes;
+    assert_eq!(true,                        num_writes > 1);
+    file_writer.write_var_i16("var_1", &data).unwrap();
+    assert_eq!(200 / 16 + 1,                file_writer.output_file.get_ref().num_writes - num_writes);
+    assert_eq!(true,                        file_writer.buffer.len() <= 16);
+    let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().inner.into_inner();
+    assert_eq!(expected_bytes,              bytes);
+
+    // The buffer contains at least one value
+    let options = WriteOptions{buffer_size: 0, ..WriteOptions::default()};
+    let file_writer = FileWriter::from_writer_with_options(Cursor::new(vec![]), &options);
+    assert_eq!(1,                           file_writer.buffer_size());
+}
+
+#[test]
+fn test_file_writer_write_var_owned() {
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim("time", 2).unwrap();
+    data_set.add_fixed_dim("dim_1", 3).unwrap();
+    data_set.add_var_i16("var_1", &["dim_1"]).unwrap();
+    data_set.add_var_f32("var_2", &["time", "dim_1"]).unwrap();
+    data_set.add_var_i8("var_3", &["time", "dim_1"]).unwrap();
+    data_set.add_var_f64("var_4", &["time"]).unwrap();
+    let data_1: Vec<i16> = vec![1, -2, 3];
+    let data_2: Vec<f32> = vec![1.5, -2.5, 3.5, 4.5, 5.5, 6.5];
+    let data_3: Vec<i8> = vec![1, 2, 3, 4, 5, 6];
+    let data_4: Vec<f64> = vec![1.25, -2.25];
+
+    // Write the variables from borrowed data
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.write_var_i16("var_1", &data_1).unwrap();
+    file_writer.write_var_f32("var_2", &data_2).unwrap();
+    file_writer.write_var_i8("var_3", &data_3).unwrap();
+    file_writer.write_var_f64("var_4", &data_4).unwrap();
+    let expected_bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+
+    // Write the variables from owned data
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.write_var_owned("var_1", DataVector::I16(data_1.clone())).unwrap();
+    file_writer.write_var_owned("var_2", DataVector::F32(data_2.clone())).unwrap();
+    file_writer.write_var_owned("var_3", DataVector::I8(data_3.clone())).unwrap();
+    file_writer.write_var_owned("var_4", DataVector::F64(data_4.clone())).unwrap();
+    let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+    assert_eq!(expected_bytes,              bytes);
+
+    // Write the variables from borrowed or owned data
+    let data_vec_1 = DataVector::I16(data_1.clone());
+    let data_vec_3 = DataVector::I8(data_3.clone());
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.write_var_cow("var_1", Cow::Borrowed(&data_vec_1)).unwrap();
+    file_writer.write_var_cow("var_2", Cow::Owned(DataVector::F32(data_2.clone()))).unwrap();
+    file_writer.write_var_cow("var_3", Cow::Borrowed(&data_vec_3)).unwrap();
+    file_writer.write_var_cow("var_4", Cow::Owned(DataVector::F64(data_4.clone()))).unwrap();
+    let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+    assert_eq!(expected_bytes,              bytes);
+
+    // Errors
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    assert_eq!(
+        WriteError::HeaderNotDefined,
+        file_writer.write_var_owned("var_1", DataVector::I16(data_1.clone())).unwrap_err()
+    );
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    assert_eq!(
+        WriteError::VariableNotDefined(String::from("undef_var")),
+        file_writer.write_var_owned("undef_var", DataVector::I16(data_1.clone())).unwrap_err()
+    );
+    assert_eq!(
+        WriteError::VariableMismatchDataType{var_name: String::from("var_1"), req: DataType::I16, get: DataType::I32},
+        file_writer.write_var_owned("var_1", DataVector::I32(vec![1, 2, 3])).unwrap_err()
+    );
+    assert_eq!(
+        WriteError::VariableMismatchDataLength{var_name: String::from("var_1"), req: 3, get: 2},
+        file_writer.write_var_owned("var_1", DataVector::I16(vec![1, 2])).unwrap_err()
+    );
+}
+
+#[test]
+fn test_file_writer_fill_block() {
+    const UNLIM_DIM_NAME: &str = "unlimited_dim";
+    const FIXED_DIM_NAME: &str = "fixed_dim";
+    const NUM_RECORDS: usize = 3;
+    const LARGE_DIM_SIZE: usize = FILL_BLOCK_SIZE + 1;
+
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim(UNLIM_DIM_NAME, NUM_RECORDS).unwrap();
+    data_set.add_fixed_dim(FIXED_DIM_NAME, LARGE_DIM_SIZE).unwrap();
+    data_set.add_var_i16("large_var", &[FIXED_DIM_NAME]).unwrap();
+    data_set.add_var_i8("record_var_1", &[UNLIM_DIM_NAME]).unwrap();
+    data_set.add_var_i16("record_var_2", &[UNLIM_DIM_NAME]).unwrap();
+
+    // The large chunk is filled by 3 writes of the same block (2 * FILL_BLOCK_SIZE + 4 bytes including the padding)
+    let mut file_writer = FileWriter::from_writer(CountingWriter{inner: Cursor::new(vec![]), num_writes: 0, num_seeks: 0});
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.write_var_i8("record_var_1", &[1, 2, 3]).unwrap();
+    file_writer.write_var_i16("record_var_2", &[4, 5, 6]).unwrap();
+    let num_writes: usize = file_writer.output_file.get_ref().num_writes;
+    file_writer.fill_unwritten_records().unwrap();
+    assert_eq!(3,                                   file_writer.output_file.get_ref().num_writes - num_writes);
+    assert_eq!(Some(DataType::I16),                 file_writer.fill_block.data_type);
+    assert_eq!(FILL_BLOCK_SIZE,                     file_writer.fill_block.bytes.len());
+    let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().inner.into_inner();
+    let mut file_reader = FileReader::from_reader(Cursor::new(bytes)).unwrap();
+    assert_eq!(vec![NC_FILL_I16; LARGE_DIM_SIZE],   file_reader.read_var_i16("large_var").unwrap());
+
+    // The records and their padding bytes are filled
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.write_var_i16("large_var", &vec![0; LARGE_DIM_SIZE]).unwrap();
+    file_writer.write_record_i16("record_var_2", 1, &[5]).unwrap();
+    let bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+    let mut file_reader = FileReader::from_reader(Cursor::new(bytes.clone())).unwrap();
+    assert_eq!(vec![NC_FILL_I8; NUM_RECORDS],       file_reader.read_var_i8("record_var_1").unwrap());
+    assert_eq!(vec![NC_FILL_I16, 5, NC_FILL_I16],   file_reader.read_var_i16("record_var_2").unwrap());
+    let record_offset: usize = bytes.len() - 8;
+    let fill_i8: u8 = NC_FILL_I8 as u8;
+    let [fill_i16_0, fill_i16_1]: [u8; 2] = NC_FILL_I16.to_be_bytes();
+    assert_eq!(&[fill_i8, fill_i8, fill_i8, fill_i8, fill_i16_0, fill_i16_1, fill_i16_0, fill_i16_1][..],     &bytes[record_offset..]);
+}
+
+#[test]
+fn test_file_writer_variable() {
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim("time", 2).unwrap();
+    data_set.add_fixed_dim("cell", 3).unwrap();
+    data_set.add_var_u8("title", &["cell"]).unwrap();
+    data_set.add_var_i16("count", &["time", "cell"]).unwrap();
+    data_set.add_var_attr_string("count", "units", "1").unwrap();
+
+    // the header is not defined
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    assert_eq!(WriteError::HeaderNotDefined,                            file_writer.variable("count").unwrap_err());
+
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    assert_eq!(WriteError::VariableNotDefined(String::from("undef_var")),  file_writer.variable("undef_var").unwrap_err());
+
+    {
+        let mut count = file_writer.variable("count").unwrap();
+        assert_eq!("count",                                 count.name());
+        assert_eq!("count",                                 count.variable().name());
+        assert_eq!(DataType::I16,                           count.data_type());
+        assert_eq!(vec![2, 3],                              count.shape());
+        assert_eq!(1,                                       count.attrs().len());
+        assert_eq!(Some(String::from("1")),                 count.attr("units").and_then(|attr| attr.as_string()));
+
+        count.write_record(1, DataSlice::I16(&[4, 5, 6])).unwrap();
+        assert_eq!(
+            WriteError::VariableMismatchDataType{var_name: String::from("count"), req: DataType::I16, get: DataType::I32},
+            count.write_record(0, DataSlice::I32(&[1, 2, 3])).unwrap_err()
+        );
+        assert_eq!(
+            WriteError::RecordIndexExceeded{index: 2, num_records: 2},
+            count.write_record(2, DataSlice::I16(&[1, 2, 3])).unwrap_err()
+        );
+    }
+    file_writer.variable("title").unwrap().write(DataSlice::U8(b"abc")).unwrap();
+
+    let mut file_reader = FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+    assert_eq!(b"abc".to_vec(),                                 file_reader.read_var_u8("title").unwrap());
+    assert_eq!(vec![NC_FILL_I16, NC_FILL_I16, NC_FILL_I16, 4, 5, 6],    file_reader.read_var_i16("count").unwrap());
+
+    // write the whole variable consuming the data
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.variable("count").unwrap().write_owned(DataVector::I16(vec![1, 2, 3, 4, 5, 6])).unwrap();
+    let mut file_reader = FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+    assert_eq!(vec![1, 2, 3, 4, 5, 6],                          file_reader.read_var_i16("count").unwrap());
+}
+
+#[test]
+fn test_file_writer_io_metrics() {
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim("time", 2).unwrap();
+    data_set.add_fixed_dim("cell", 3).unwrap();
+    data_set.add_var_u8("title", &["cell"]).unwrap();
+    data_set.add_var_i16("count", &["time", "cell"]).unwrap();
+
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    assert_eq!(IoMetrics::default(),                    file_writer.io_metrics());
+    // the header is written
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    assert_eq!(compute_header_required_size(&data_set, Version::Classic) as u64,    file_writer.io_metrics().bytes_written);
+
+    file_writer.reset_io_metrics();
+    // 3 bytes followed by 1 padding byte
+    file_writer.write_var_u8("title", b"abc").unwrap();
+    assert_eq!(
+        IoMetrics{bytes_written: 4, seeks: 1, chunks_written: 1, ..IoMetrics::default()},
+        file_writer.io_metrics()
+    );
+    // the records of the only record variable are not padded
+    file_writer.write_record_i16("count", 0, &[1, 2, 3]).unwrap();
+    assert_eq!(
+        IoMetrics{bytes_written: 10, seeks: 2, chunks_written: 2, ..IoMetrics::default()},
+        file_writer.io_metrics()
+    );
+
+    // the record 1 is filled when the writer is closed
+    assert_eq!(
+        IoMetrics{bytes_written: 16, seeks: 3, chunks_written: 2, fill_bytes_written: 6, ..IoMetrics::default()},
+        file_writer.close_with_metrics().unwrap()
+    );
+}
+
+#[test]
+fn test_file_writer_set_def_auto() {
+    const DIM_SIZE: usize = 1 << 30;
+    let mut data_set = DataSet::new();
+    data_set.add_fixed_dim("x", DIM_SIZE).unwrap();
+    data_set.add_var_i8("a", &["x"]).unwrap();
+    data_set.add_var_i8("b", &["x"]).unwrap();
+
+    // the begin offsets fit into `i32`
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    assert_eq!(Version::Classic,                        file_writer.set_def_auto(&data_set, 0).unwrap());
+    assert_eq!(Some(Version::Classic),                  file_writer.version());
+    assert_eq!(WriteError::HeaderAlreadyDefined,        file_writer.set_def_auto(&data_set, 0).unwrap_err());
+
+    // the begin offset of `c` exceeds `i32::MAX`
+    data_set.add_var_i8("c", &["x"]).unwrap();
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    assert_eq!(Version::Offset64Bit,                    file_writer.set_def_auto(&data_set, 0).unwrap());
+    assert_eq!(Some(Version::Offset64Bit),              file_writer.version());
+
+    // the size of the fixed-size variable `d` (not the last one) exceeds the classic limit
+    let mut data_set = DataSet::new();
+    data_set.add_fixed_dim("x", DIM_SIZE).unwrap();
+    data_set.add_fixed_dim("y", 3).unwrap();
+    data_set.add_var_i8("d", &["y", "x"]).unwrap();
+    data_set.add_var_i8("e", &["y"]).unwrap();
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    assert_eq!(Version::Offset64Bit,                    file_writer.set_def_auto(&data_set, 0).unwrap());
+}
+
+#[test]
+fn test_file_writer_reserve_header_percent() {
+    let mut data_set = DataSet::new();
+    data_set.add_fixed_dim("x", 3).unwrap();
+    data_set.add_var_f32("x", &["x"]).unwrap();
+    let header_required_size: usize = compute_header_required_size(&data_set, Version::Classic);
+    assert_eq!(80,                                      header_required_size);
+
+    // 50 % of the header size are reserved after it
+    let options = WriteOptions{reserve_header_percent: 50, ..WriteOptions::default()};
+    let mut file_writer = FileWriter::from_writer_with_options(Cursor::new(vec![]), &options);
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    assert_eq!(Some(120),                               file_writer.header_min_size());
+    let file_reader = FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+    assert_eq!(80,                                      file_reader.header_used_size());
+    assert_eq!(120,                                     file_reader.header_size());
+
+    // a larger `header_min_size` is kept
+    let mut file_writer = FileWriter::from_writer_with_options(Cursor::new(vec![]), &options);
+    file_writer.set_def(&data_set, Version::Classic, 1024).unwrap();
+    assert_eq!(Some(1024),                              file_writer.header_min_size());
+
+    // the reserve is computed for the selected version
+    let mut file_writer = FileWriter::from_writer_with_options(Cursor::new(vec![]), &options);
+    file_writer.set_def(&data_set, Version::Offset64Bit, 0).unwrap();
+    assert_eq!(Some(126),                               file_writer.header_min_size());
+    let file_reader = FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+    assert_eq!(128,                                     file_reader.data_section_offset());
+}
+
+#[test]
+fn test_recommended_header_min_size() {
+    let mut data_set = DataSet::new();
+    assert_eq!(32,                                      recommended_header_min_size(&data_set, 1.0));
+    data_set.add_fixed_dim("x", 3).unwrap();
+    data_set.add_var_f32("x", &["x"]).unwrap();
+    let header_required_size: usize = compute_header_required_size(&data_set, Version::Offset64Bit);
+    assert_eq!(84,                                      header_required_size);
+
+    assert_eq!(84,                                      recommended_header_min_size(&data_set, 1.0));
+    // rounded up to a multiple of 4 bytes
+    assert_eq!(128,                                     recommended_header_min_size(&data_set, 1.5));
+    assert_eq!(168,                                     recommended_header_min_size(&data_set, 2.0));
+    // the factors lower than 1 are ignored
+    assert_eq!(84,                                      recommended_header_min_size(&data_set, 0.5));
+    assert_eq!(84,                                      recommended_header_min_size(&data_set, f64::NAN));
+    assert_eq!(usize::MAX,                              recommended_header_min_size(&data_set, f64::INFINITY));
+}
+
+#[test]
+fn test_file_writer_close_summary() {
+    // no header defined
+    let summary: WriteSummary = FileWriter::from_writer(Cursor::new(vec![])).close().unwrap();
+    assert_eq!(0,                                       summary.bytes_written);
+    assert_eq!(true,                                    summary.vars_written.is_empty());
+    assert_eq!(true,                                    summary.vars_filled.is_empty());
+    assert_eq!(0,                                       summary.num_records);
+
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim("time", 2).unwrap();
+    data_set.add_fixed_dim("cell", 3).unwrap();
+    data_set.add_var_u8("title", &["cell"]).unwrap();
+    data_set.add_var_i16("count", &["time", "cell"]).unwrap();
+    data_set.add_var_i16("flag", &["time"]).unwrap();
+
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    file_writer.write_record_i16("count", 0, &[1, 2, 3]).unwrap();
+    file_writer.write_record_i16("count", 1, &[4, 5, 6]).unwrap();
+    file_writer.write_record_i16("flag", 1, &[1]).unwrap();
+    let metrics: IoMetrics = file_writer.io_metrics();
+    let summary: WriteSummary = file_writer.close().unwrap();
+    assert_eq!(vec!["count", "flag"],                   summary.vars_written);
+    assert_eq!(vec!["title", "flag"],                   summary.vars_filled);
+    assert_eq!(2,                                       summary.num_records);
+    assert!(summary.bytes_written > metrics.bytes_written);
+}
+
+#[test]
+fn test_file_writer_error_attr_mismatch_data_type() {
+    let mut data_set = DataSet::new();
+    data_set.add_fixed_dim("dim_1", 2).unwrap();
+    data_set.add_var_i16("var_1", &["dim_1"]).unwrap();
+    // the data type of the attributes read from the files is not checked when they are added
+    data_set.add_var_attr_from("var_1", crate::Attribute::from_data(String::from("_FillValue"), DataVector::I32(vec![-1]))).unwrap();
+
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    assert_eq!(
+        WriteError::DataSet(InvalidDataSet::VariableAttributeMismatchDataType{
+            var_name: String::from("var_1"),
+            attr_name: String::from("_FillValue"),
+            req: DataType::I16,
+            get: DataType::I32,
+        }),
+        file_writer.set_def(&data_set, Version::Classic, 0).unwrap_err()
+    );
+    assert_eq!(0,                                       file_writer.io_metrics().bytes_written);
+}
+
+#[test]
+fn test_file_writer_close_verified() {
+    /// Output returning a corrupted byte when the byte `corrupted_offset` is read back
+    struct CorruptedOutput {
+        output: Cursor<Vec<u8>>,
+        corrupted_offset: u64,
+    }
+    impl Read for CorruptedOutput {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let position: u64 = self.output.position();
+            let num_bytes: usize = self.output.read(buf)?;
+            if position <= self.corrupted_offset && self.corrupted_offset < position + num_bytes as u64 {
+                buf[(self.corrupted_offset - position) as usize] ^= 0xff;
+            }
+            Ok(num_bytes)
+        }
+    }
+    impl std::io::Write for CorruptedOutput {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> { self.output.write(buf) }
+        fn flush(&mut self) -> std::io::Result<()> { self.output.flush() }
+    }
+    impl std::io::Seek for CorruptedOutput {
+        fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> { self.output.seek(pos) }
+    }
+
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim("time", 3).unwrap();
+    data_set.add_fixed_dim("cell", 3).unwrap();
+    data_set.add_var_i16("cell", &["cell"]).unwrap();
+    data_set.add_var_f64("time", &["time"]).unwrap();
+    data_set.add_var_f32("temperature", &["time", "cell"]).unwrap();
+    let write_data = |output: CorruptedOutput, options: &WriteOptions, buffer: &mut [u8]| -> VerifyReport {
+        let mut file_writer = FileWriter::from_writer_with_options(output, options);
+        file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+        file_writer.write_var_owned("cell", DataVector::I16(vec![1, 2, 3])).unwrap();
+        file_writer.write_records(0..2, &[
+            ("time", DataSlice::F64(&[0.0, 1.0])),
+            ("temperature", DataSlice::F32(&[10.0, 11.0, 12.0, 20.0, 21.0, 22.0])),
+        ]).unwrap();
+        file_writer.write_record_f32("temperature", 1, &[30.0, 31.0, 32.0]).unwrap();
+        file_writer.write_chunk_by_parts("time", 2, buffer, |offset: usize, part: &mut [u8]| -> Result<(), WriteError> {
+            part.copy_from_slice(&2.0_f64.to_be_bytes()[offset..(offset + part.len())]);
+            Ok(())
+        }).unwrap();
+        file_writer.close_verified().unwrap()
+    };
+    let header_size: u64 = {
+        let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+        file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+        file_writer.close_into_inner().unwrap().into_inner().len() as u64 - 8 - 3 * (8 + 12)
+    };
+
+    // without corruption, the data are verified through buffers smaller than the chunks
+    let options = WriteOptions{verify: true, buffer_size: 3, ..WriteOptions::default()};
+    let output = CorruptedOutput{output: Cursor::new(vec![]), corrupted_offset: u64::MAX};
+    let report: VerifyReport = write_data(output, &options, &mut [0; 3]);
+    assert_eq!(true,                                    report.is_verified());
+    assert_eq!(6,                                       report.num_verified_chunks);
+    assert_eq!(vec!["temperature"],                     report.summary.vars_filled);
+
+    // corrupt the second value of `temperature` in the record 1
+    let corrupted_offset: u64 = header_size + 8 + (8 + 12) + 8 + 4;
+    let output = CorruptedOutput{output: Cursor::new(vec![]), corrupted_offset: corrupted_offset};
+    let report: VerifyReport = write_data(output, &options, &mut [0; 3]);
+    assert_eq!(false,                                   report.is_verified());
+    assert_eq!(vec![(String::from("temperature"), 1)],  report.mismatched_chunks);
+
+    // nothing is verified if the option is disabled
+    let output = CorruptedOutput{output: Cursor::new(vec![]), corrupted_offset: corrupted_offset};
+    let report: VerifyReport = write_data(output, &WriteOptions::default(), &mut [0; 3]);
+    assert_eq!(true,                                    report.is_verified());
+    assert_eq!(0,                                       report.num_verified_chunks);
+}
@@ -0,0 +1,1298 @@
+//! Decoding and encoding of the NetCDF-3 headers, independent of the input and the output.
+//!
+//! This module does no I/O and only requires `alloc`, the reader and the writer use it to parse and to serialize the headers,
+//! and the functions [`decode_header`](../fn.decode_header.html), [`encode_header`](../fn.encode_header.html) and
+//! [`encode_chunk`](../fn.encode_chunk.html) expose it to the `no_std` builds.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+use alloc::rc::Rc;
+use core::convert::TryFrom;
+
+use crate::{
+    data_set::DimensionSize,
+    Attribute,
+    DataSet,
+    DataSlice,
+    DataType,
+    DataVector,
+    Dimension,
+    NameValidation,
+    NC_MAX_NAME_SIZE,
+    name_string::truncate_name,
+    Variable,
+    Version,
+    error::{ReadError, WriteError},
+    error::parse_header_error::{DecodeError, ParseHeaderError, ParseHeaderErrorKind},
+    io::{compute_header_required_size, compute_padding_size, Offset, ABSENT_TAG, DIMENSION_TAG, VARIABLE_TAG, ATTRIBUTE_TAG},
+    NC_FILL_I8, NC_FILL_U8, NC_FILL_I16, NC_FILL_I32, NC_FILL_F32, NC_FILL_F64,
+};
+use crate::io::header_decoder::HeaderDecoder;
+use crate::io::diagnostics::Diagnostic;
+
+/// Default maximum number of bytes read at once from the input (see [`ReadOptions::buffer_size`](struct.ReadOptions.html#structfield.buffer_size))
+pub(crate) const READ_BUFFER_SIZE: usize = 1 << 20;
+
+/// Decoding of the names (dimensions, attributes and variables) which are not valid UTF-8.
+///
+/// The valid UTF-8 names are always kept as is, and each substitution is reported by
+/// a [`Diagnostic::NameNotValidUtf8`](enum.Diagnostic.html#variant.NameNotValidUtf8).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameDecoding {
+    /// The names which are not valid UTF-8 are rejected (default).
+    Utf8,
+    /// The invalid UTF-8 sequences are replaced by the character `U+FFFD`.
+    Utf8Lossy,
+    /// Each byte is decoded as a Latin-1 (ISO-8859-1) character, the encoding of many legacy European files.
+    ///
+    /// The names are written back UTF-8 encoded when the data set is rewritten (see [`copy_nc3`](fn.copy_nc3.html)),
+    /// the original bytes being kept by the diagnostics.
+    Latin1,
+}
+
+impl Default for NameDecoding {
+    fn default() -> Self {
+        return NameDecoding::Utf8;
+    }
+}
+
+/// Policy applied to the dimensions whose name is already used by a previous dimension of the header.
+///
+/// Each duplicate dimension accepted by the policy is reported by a
+/// [`Diagnostic::DuplicateDimension`](enum.Diagnostic.html#variant.DuplicateDimension).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateDimPolicy {
+    /// The error [`InvalidDataSet::DimensionAlreadyExists`](enum.InvalidDataSet.html#variant.DimensionAlreadyExists) is returned (default).
+    Error,
+    /// The duplicate dimension is renamed with the first free suffix `_1`, `_2`, etc. (the name being
+    /// truncated before the suffix if needed, not to exceed [`NC_MAX_NAME_SIZE`](constant.NC_MAX_NAME_SIZE.html) bytes).
+    Rename,
+    /// The duplicate dimension keeps its name, the variables refer to their dimensions by ids.
+    ///
+    /// The methods of the [`DataSet`](struct.DataSet.html) using the dimension names refer to the first dimension.
+    KeepById,
+}
+
+impl Default for DuplicateDimPolicy {
+    fn default() -> Self {
+        return DuplicateDimPolicy::Error;
+    }
+}
+
+/// Policy applied to the names of the header exceeding [`NC_MAX_NAME_SIZE`](constant.NC_MAX_NAME_SIZE.html) bytes,
+/// which are written by some nonconforming writers.
+///
+/// Each long name accepted by the policy is reported by a [`Diagnostic::NameTooLong`](enum.Diagnostic.html#variant.NameTooLong).
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{DataSet, Diagnostic, FileReader, FileWriter, InvalidDataSet, LongNamePolicy, NameError, ReadOptions, Version};
+/// use netcdf3::error::ReadError;
+///
+/// // Write a file defining the dimension `latitude`, and replace its name by a name of 300 bytes
+/// let mut data_set = DataSet::new();
+/// data_set.add_fixed_dim("latitude", 3).unwrap();
+/// let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+/// file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+/// let mut bytes: Vec<u8> = file_writer.close_into_inner().unwrap().into_inner();
+/// let long_name: String = "l".repeat(300);
+/// let position: usize = bytes.windows(8).position(|window: &[u8]| window == b"latitude").unwrap();
+/// bytes.splice((position - 4)..(position + 8), [&300_u32.to_be_bytes()[..], long_name.as_bytes()].concat());
+///
+/// assert_eq!(
+///     ReadError::DataSet(InvalidDataSet::DimensionNameNotValid{dim_name: long_name.clone(), reason: NameError::TooLong{max: 256, get: 300}}),
+///     FileReader::from_reader(Cursor::new(bytes.clone())).unwrap_err()
+/// );
+///
+/// let options = ReadOptions{long_name_policy: LongNamePolicy::Truncate, ..ReadOptions::default()};
+/// let file_reader = FileReader::from_reader_with_options(Cursor::new(bytes.clone()), &options).unwrap();
+/// assert_eq!(vec!["l".repeat(256)],           file_reader.data_set().dim_names());
+///
+/// let options = ReadOptions{long_name_policy: LongNamePolicy::Preserve, ..ReadOptions::default()};
+/// let file_reader = FileReader::from_reader_with_options(Cursor::new(bytes), &options).unwrap();
+/// assert_eq!(vec![long_name.clone()],         file_reader.data_set().dim_names());
+/// assert_eq!(
+///     Some(&Diagnostic::NameTooLong{name: long_name, new_name: None}),
+///     file_reader.diagnostics().iter().next()
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LongNamePolicy {
+    /// The error [`NameError::TooLong`](enum.NameError.html#variant.TooLong) is returned (default).
+    Error,
+    /// The name is truncated to its first [`NC_MAX_NAME_SIZE`](constant.NC_MAX_NAME_SIZE.html) bytes (without splitting a
+    /// multibyte character), the duplicate names it may cause are handled by the policies of the duplicate names.
+    Truncate,
+    /// The name is kept as is. The [`FileWriter`](struct.FileWriter.html) refuses to write it
+    /// (see [`DataSet::check_name_sizes`](struct.DataSet.html#method.check_name_sizes)).
+    Preserve,
+}
+
+impl Default for LongNamePolicy {
+    fn default() -> Self {
+        return LongNamePolicy::Error;
+    }
+}
+
+/// Policy applied to the attributes whose name is already used by a previous attribute of the same variable (or by a previous
+/// global attribute).
+///
+/// Each duplicate attribute accepted by the policy is reported by a
+/// [`Diagnostic::DuplicateAttribute`](enum.Diagnostic.html#variant.DuplicateAttribute).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuplicateAttrPolicy {
+    /// The error [`InvalidDataSet::GlobalAttributeAlreadyExists`](enum.InvalidDataSet.html#variant.GlobalAttributeAlreadyExists)
+    /// or [`InvalidDataSet::VariableAttributeAlreadyExists`](enum.InvalidDataSet.html#variant.VariableAttributeAlreadyExists)
+    /// is returned (default).
+    Error,
+    /// The value of the previous attribute is replaced by the last one, the attribute keeping the position of its first definition.
+    LastWins,
+    /// The duplicate attribute is renamed with the first free suffix `_1`, `_2`, etc. (the name being
+    /// truncated before the suffix if needed).
+    Rename,
+}
+
+impl Default for DuplicateAttrPolicy {
+    fn default() -> Self {
+        return DuplicateAttrPolicy::Error;
+    }
+}
+
+/// Check of the padding bytes of the header (following the names and the attribute values), which must be zero bytes.
+///
+/// Several writers fill these bytes with junk, the files being readable otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaddingCheck {
+    /// The error [`ParseHeaderErrorKind::ZeroPadding`](error/parse_header_error/enum.ParseHeaderErrorKind.html#variant.ZeroPadding)
+    /// is returned.
+    Strict,
+    /// Each non-zero padding is reported by a [`Diagnostic::PaddingNotZero`](enum.Diagnostic.html#variant.PaddingNotZero) (default).
+    Warn,
+    /// The padding bytes are not checked, the space reserved after the header included.
+    Ignore,
+}
+
+impl Default for PaddingCheck {
+    fn default() -> Self {
+        return PaddingCheck::Warn;
+    }
+}
+
+/// Options of the parsing of the header (see [`FileReader::from_reader_with_options`](struct.FileReader.html#method.from_reader_with_options)).
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{Diagnostic, FileReader, NameDecoding, ReadOptions};
+/// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+///
+/// // Replace the name `latitude` by the Latin-1 encoded name `latitudé`
+/// let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+/// let position: usize = bytes.windows(8).position(|window: &[u8]| window == b"latitude").unwrap();
+/// bytes[position + 7] = 0xE9;
+///
+/// assert!(FileReader::from_reader(Cursor::new(bytes.clone())).is_err());
+///
+/// let options = ReadOptions{name_decoding: NameDecoding::Latin1, ..ReadOptions::default()};
+/// let file_reader = FileReader::from_reader_with_options(Cursor::new(bytes), &options).unwrap();
+/// assert_eq!(true,        file_reader.data_set().has_dim("latitudé"));
+/// assert_eq!(
+///     Some(&Diagnostic::NameNotValidUtf8{bytes: b"latitud\xE9".to_vec(), name: String::from("latitudé")}),
+///     file_reader.diagnostics().iter().next()
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadOptions {
+    /// Decoding of the names which are not valid UTF-8.
+    pub name_decoding: NameDecoding,
+    /// Rules checking the names of the header ([`NameValidation::Strict`](enum.NameValidation.html#variant.Strict) by default).
+    ///
+    /// The NetCDF C library does not check the names while reading the files, the lenient rules allow to read the names
+    /// which cannot be defined with the strict rules (see [`ReadOptions::lenient`](#method.lenient)). Each of these names is reported by a
+    /// [`Diagnostic::NameNotValid`](enum.Diagnostic.html#variant.NameNotValid).
+    pub name_validation: NameValidation,
+    /// Policy applied to the duplicate dimension names.
+    pub duplicate_dim_policy: DuplicateDimPolicy,
+    /// Policy applied to the duplicate attribute names.
+    pub duplicate_attr_policy: DuplicateAttrPolicy,
+    /// Policy applied to the names exceeding [`NC_MAX_NAME_SIZE`](constant.NC_MAX_NAME_SIZE.html) bytes.
+    pub long_name_policy: LongNamePolicy,
+    /// Reads only the complete records when the record data are truncated, instead of failing while reading them.
+    ///
+    /// The number of records is then reduced to the number of complete records stored in the file, and reported by
+    /// a [`Diagnostic::RecordsTruncated`](enum.Diagnostic.html#variant.RecordsTruncated). The number of records of the
+    /// *streaming* files is computed ignoring their incomplete last record.
+    pub allow_truncated_records: bool,
+    /// Maximum number of bytes allocated to read the data of a variable (unlimited by default).
+    ///
+    /// Reading a larger variable fails with the error
+    /// [`ReadError::MaximumAllocationExceeded`](error/enum.ReadError.html#variant.MaximumAllocationExceeded)
+    /// before allocating its memory (see [`FileReader::set_max_alloc_bytes`](struct.FileReader.html#method.set_max_alloc_bytes)).
+    pub max_alloc_bytes: Option<usize>,
+    /// Reads the files whose variable data overlap (offsets not increasing or data overlapping the previous variable),
+    /// instead of failing with the error [`ReadError::OverlappingVariables`](error/enum.ReadError.html#variant.OverlappingVariables).
+    ///
+    /// These overlaps are then reported by [`Diagnostic::Layout`](enum.Diagnostic.html#variant.Layout).
+    pub allow_overlapping_vars: bool,
+    /// Check of the padding bytes of the header.
+    pub padding_check: PaddingCheck,
+    /// Maximum number of bytes read at once from the input, 1 MiB by default.
+    ///
+    /// The header is loaded by blocks of this size, and the records of a variable are read by batches fitting into it.
+    /// Large values reduce the number of reads on the network file systems, small values the memory used.
+    pub buffer_size: usize,
+    /// Keeps the values of the attributes as raw bytes while reading the header, each attribute being decoded at its first access
+    /// (see [`Attribute::is_decoded`](struct.Attribute.html#method.is_decoded)).
+    ///
+    /// It speeds up the scanning of many files when only the names, the shapes and a few attributes are used.
+    pub lazy_attrs: bool,
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        return ReadOptions{
+            name_decoding: NameDecoding::default(),
+            name_validation: NameValidation::Strict,
+            duplicate_dim_policy: DuplicateDimPolicy::default(),
+            duplicate_attr_policy: DuplicateAttrPolicy::default(),
+            long_name_policy: LongNamePolicy::default(),
+            allow_truncated_records: false,
+            max_alloc_bytes: None,
+            allow_overlapping_vars: false,
+            padding_check: PaddingCheck::default(),
+            buffer_size: READ_BUFFER_SIZE,
+            lazy_attrs: false,
+        };
+    }
+}
+
+impl ReadOptions {
+    /// Returns the default options (see [`FileReader::open`](struct.FileReader.html#method.open)).
+    ///
+    /// The names are checked with the strict rules and the non-zero padding bytes are reported, the other defects
+    /// (invalid UTF-8 names, names not valid, duplicate or long names, truncated records, overlapping variables) make the reading fail.
+    pub fn new() -> Self {
+        return ReadOptions::default();
+    }
+
+    /// Returns the options reading as many files as possible, each defect accepted in addition to the default options
+    /// being reported by a [`Diagnostic`](enum.Diagnostic.html).
+    ///
+    /// The invalid UTF-8 sequences of the names are replaced, the duplicate names are renamed, the long names are kept,
+    /// and the truncated records and the overlapping variables are accepted.
+    pub fn lenient() -> Self {
+        return ReadOptions{
+            name_decoding: NameDecoding::Utf8Lossy,
+            name_validation: NameValidation::Lenient,
+            duplicate_dim_policy: DuplicateDimPolicy::Rename,
+            duplicate_attr_policy: DuplicateAttrPolicy::Rename,
+            long_name_policy: LongNamePolicy::Preserve,
+            allow_truncated_records: true,
+            allow_overlapping_vars: true,
+            padding_check: PaddingCheck::Warn,
+            ..ReadOptions::default()
+        };
+    }
+
+    /// Sets the decoding of the names which are not valid UTF-8.
+    pub fn with_name_decoding(mut self, name_decoding: NameDecoding) -> Self {
+        self.name_decoding = name_decoding;
+        return self;
+    }
+
+    /// Sets the rules checking the names of the header.
+    pub fn with_name_validation(mut self, name_validation: NameValidation) -> Self {
+        self.name_validation = name_validation;
+        return self;
+    }
+
+    /// Sets the policy applied to the duplicate dimension names.
+    pub fn with_duplicate_dim_policy(mut self, duplicate_dim_policy: DuplicateDimPolicy) -> Self {
+        self.duplicate_dim_policy = duplicate_dim_policy;
+        return self;
+    }
+
+    /// Sets the policy applied to the duplicate attribute names.
+    pub fn with_duplicate_attr_policy(mut self, duplicate_attr_policy: DuplicateAttrPolicy) -> Self {
+        self.duplicate_attr_policy = duplicate_attr_policy;
+        return self;
+    }
+
+    /// Sets the policy applied to the names exceeding [`NC_MAX_NAME_SIZE`](constant.NC_MAX_NAME_SIZE.html) bytes.
+    pub fn with_long_name_policy(mut self, long_name_policy: LongNamePolicy) -> Self {
+        self.long_name_policy = long_name_policy;
+        return self;
+    }
+
+    /// Sets if the complete records are read when the record data are truncated.
+    pub fn with_allow_truncated_records(mut self, allow_truncated_records: bool) -> Self {
+        self.allow_truncated_records = allow_truncated_records;
+        return self;
+    }
+
+    /// Sets if the files whose variable data overlap are read.
+    pub fn with_allow_overlapping_vars(mut self, allow_overlapping_vars: bool) -> Self {
+        self.allow_overlapping_vars = allow_overlapping_vars;
+        return self;
+    }
+
+    /// Sets the maximum number of bytes allocated to read the data of a variable.
+    pub fn with_max_alloc_bytes(mut self, max_alloc_bytes: usize) -> Self {
+        self.max_alloc_bytes = Some(max_alloc_bytes);
+        return self;
+    }
+
+    /// Sets the check of the padding bytes of the header.
+    pub fn with_padding_check(mut self, padding_check: PaddingCheck) -> Self {
+        self.padding_check = padding_check;
+        return self;
+    }
+
+    /// Sets the maximum number of bytes read at once from the input (at least 1 byte).
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size.max(1);
+        return self;
+    }
+
+    /// Sets if the values of the attributes are decoded at their first access.
+    pub fn with_lazy_attrs(mut self, lazy_attrs: bool) -> Self {
+        self.lazy_attrs = lazy_attrs;
+        return self;
+    }
+}
+
+/// Decodes the names of the header and applies the policies of the reader to them.
+#[derive(Debug, Clone)]
+pub(crate) struct NameDecoder {
+    name_decoding: NameDecoding,
+    name_validation: NameValidation,
+    duplicate_dim_policy: DuplicateDimPolicy,
+    duplicate_attr_policy: DuplicateAttrPolicy,
+    long_name_policy: LongNamePolicy,
+    pub(crate) padding_check: PaddingCheck,
+    lazy_attrs: bool,
+}
+
+impl NameDecoder {
+
+    pub(crate) fn new(name_decoding: NameDecoding, name_validation: NameValidation) -> Self {
+        return NameDecoder{
+            name_decoding: name_decoding,
+            name_validation: name_validation,
+            duplicate_dim_policy: DuplicateDimPolicy::default(),
+            duplicate_attr_policy: DuplicateAttrPolicy::default(),
+            long_name_policy: LongNamePolicy::default(),
+            padding_check: PaddingCheck::Strict,
+            lazy_attrs: false,
+        };
+    }
+
+    pub(crate) fn with_options(options: &ReadOptions) -> Self {
+        let mut names = NameDecoder::new(options.name_decoding, options.name_validation);
+        names.duplicate_dim_policy = options.duplicate_dim_policy;
+        names.duplicate_attr_policy = options.duplicate_attr_policy;
+        names.long_name_policy = options.long_name_policy;
+        names.padding_check = options.padding_check;
+        names.lazy_attrs = options.lazy_attrs;
+        return names;
+    }
+
+    /// Returns the duplicate `name` followed by the first suffix `_1`, `_2`, etc. not used.
+    ///
+    /// The name is truncated before the suffix, the new name not exceeding [`NC_MAX_NAME_SIZE`](constant.NC_MAX_NAME_SIZE.html) bytes.
+    fn rename(&self, name: &str, is_used: impl Fn(&str) -> bool) -> String {
+        let suffixed_name = |suffix: usize| -> String {
+            let suffix: String = format!("_{}", suffix);
+            return format!("{}{}", truncate_name(name, NC_MAX_NAME_SIZE.saturating_sub(suffix.len())), suffix);
+        };
+        let mut suffix: usize = 1;
+        let mut new_name: String = suffixed_name(suffix);
+        while is_used(&new_name) {
+            suffix += 1;
+            new_name = suffixed_name(suffix);
+        }
+        return new_name;
+    }
+
+    /// Decodes the bytes of a name, and returns if the bytes have been substituted (not valid UTF-8).
+    fn decode(&self, bytes: &[u8]) -> Result<(String, bool), alloc::string::FromUtf8Error> {
+        return match String::from_utf8(bytes.to_vec()) {
+            Ok(name) => Ok((name, false)),
+            Err(err) => {
+                let name: String = match self.name_decoding {
+                    NameDecoding::Utf8 => return Err(err),
+                    NameDecoding::Utf8Lossy => String::from_utf8_lossy(bytes).into_owned(),
+                    NameDecoding::Latin1 => bytes.iter().map(|byte: &u8| *byte as char).collect(),
+                };
+                Ok((name, true))
+            },
+        };
+    }
+
+    /// Applies the policy of the names exceeding `NC_MAX_NAME_SIZE` bytes, the other names are returned as is.
+    ///
+    /// Returns the diagnostic reporting the long names.
+    fn limit_size(&self, name: String) -> (String, Option<Diagnostic>) {
+        if name.len() <= NC_MAX_NAME_SIZE {
+            return (name, None);
+        }
+        return match self.long_name_policy {
+            LongNamePolicy::Error => (name, None),
+            LongNamePolicy::Truncate => {
+                let new_name: String = truncate_name(&name, NC_MAX_NAME_SIZE).to_string();
+                (new_name.clone(), Some(Diagnostic::NameTooLong{name: name, new_name: Some(new_name)}))
+            },
+            LongNamePolicy::Preserve => {
+                (name.clone(), Some(Diagnostic::NameTooLong{name: name, new_name: None}))
+            },
+        };
+    }
+}
+
+/// State of the parsing of a header : the non-fatal findings collected while parsing.
+#[derive(Debug)]
+pub(crate) struct HeaderParseState {
+    pub(crate) names: NameDecoder,
+    /// Bytes of the names which are not valid UTF-8, and the decoded names.
+    pub(crate) substitutions: Vec<(Vec<u8>, String)>,
+    pub(crate) duplicates: Vec<Diagnostic>,
+    pub(crate) long_names: Vec<Diagnostic>,
+    pub(crate) non_zero_paddings: Vec<Diagnostic>,
+}
+
+impl HeaderParseState {
+
+    pub(crate) fn new(names: NameDecoder) -> Self {
+        return HeaderParseState{
+            names: names,
+            substitutions: vec![],
+            duplicates: vec![],
+            long_names: vec![],
+            non_zero_paddings: vec![],
+        };
+    }
+
+    /// Decodes the bytes of a name, and keeps the substituted and the long names.
+    fn decode_name(&mut self, bytes: &[u8]) -> Result<String, alloc::string::FromUtf8Error> {
+        let (name, is_substituted): (String, bool) = self.names.decode(bytes)?;
+        if is_substituted {
+            self.substitutions.push((bytes.to_vec(), name.clone()));
+        }
+        let (name, long_name): (String, Option<Diagnostic>) = self.names.limit_size(name);
+        if let Some(long_name) = long_name {
+            self.long_names.push(long_name);
+        }
+        return Ok(name);
+    }
+}
+
+/// Parses the NetCDF-3 header, and returns the offset where the parsing has stopped (the end of the header, the reserved bytes excluded).
+pub(crate) fn parse_header(input: &mut HeaderDecoder, state: &mut HeaderParseState) -> Result<(DataSet, Version, Vec<VariableParsedMetadata>, usize), ReadError> {
+    let total_file_size: usize = input.input_size();
+    // the magic word
+    parse_magic_word(input)?;
+    // the version number
+    let version: Version = parse_version(input)?;
+
+    // the number of records
+    let num_records: Option<usize> = parse_as_usize_optional(input)?;
+    let dims_list: Vec<(String, usize)> = parse_dims_list(input, state)?;
+    let global_attrs_list: Vec<Attribute> = parse_attrs_list(input, state)?;
+    let var_info_list: Vec<VariableParsedMetadata> = parse_vars_list(input, version, state)?;
+    let header_end: usize = input.offset();
+    let names: &NameDecoder = &state.names;
+
+    // Create a new dataset
+    let mut data_set = DataSet::new();
+    data_set.set_name_validation(names.name_validation);
+    data_set.allow_long_names = names.long_name_policy == LongNamePolicy::Preserve;
+    let (num_records, num_records_is_determinated): (usize, bool) = match num_records {
+        Some(num_records) => (num_records, true),
+        None => (0, false),
+    };
+
+    // Append it the dimensions
+    let dim_names: Vec<String> = dims_list.iter().map(|(dim_name, _dim_size): &(String, usize)| dim_name.clone()).collect();
+    for (dim_id, (dim_name, dim_size)) in dims_list.into_iter().enumerate() {
+        let dim_name: String = match (data_set.has_dim(&dim_name), names.duplicate_dim_policy) {
+            (false, _) | (true, DuplicateDimPolicy::Error) => dim_name,
+            (true, DuplicateDimPolicy::Rename) => {
+                let new_name: String = names.rename(&dim_name, |name: &str| data_set.has_dim(name) || dim_names.iter().any(|dim_name: &String| dim_name == name));
+                state.duplicates.push(Diagnostic::DuplicateDimension{dim_id: dim_id, name: dim_name, new_name: Some(new_name.clone())});
+                new_name
+            },
+            (true, DuplicateDimPolicy::KeepById) => {
+                if dim_size == 0 {
+                    data_set.push_unlimited_dim(&dim_name, num_records)?;
+                } else {
+                    data_set.push_fixed_dim(&dim_name, dim_size)?;
+                }
+                state.duplicates.push(Diagnostic::DuplicateDimension{dim_id: dim_id, name: dim_name, new_name: None});
+                continue;
+            },
+        };
+        if dim_size == 0 {
+            data_set.set_unlimited_dim(dim_name, num_records)?;
+        } else {
+            data_set.add_fixed_dim(dim_name, dim_size)?;
+        }
+    }
+
+    // Append ot the global attributes
+    let global_attr_names: Vec<String> = global_attrs_list.iter().map(|attr: &Attribute| attr.name.clone()).collect();
+    for mut attr in global_attrs_list.into_iter() {
+        let attr_name: String = attr.name.clone();
+        attr.name = match (data_set.has_global_attr(&attr_name), names.duplicate_attr_policy) {
+            (false, _) | (true, DuplicateAttrPolicy::Error) => attr_name,
+            (true, DuplicateAttrPolicy::LastWins) => {
+                // the value is replaced, the attribute keeps the position of its first definition
+                let _replaced_attr: Attribute = data_set.replace_global_attr_from(attr)?;
+                state.duplicates.push(Diagnostic::DuplicateAttribute{var_name: None, attr_name: attr_name, new_name: None});
+                continue;
+            },
+            (true, DuplicateAttrPolicy::Rename) => {
+                let new_name: String = names.rename(&attr_name, |name: &str| data_set.has_global_attr(name) || global_attr_names.iter().any(|attr_name: &String| attr_name == name));
+                state.duplicates.push(Diagnostic::DuplicateAttribute{var_name: None, attr_name: attr_name, new_name: Some(new_name.clone())});
+                new_name
+            },
+        };
+        data_set.add_global_attr_from(attr)?;
+    }
+
+    // Append the variables
+    let mut record_var_begin_offsets: Vec<Offset> = vec![];  // used to computed the number of records if necessaray
+    for var_info in var_info_list.iter() {
+        let dim_refs: Vec<Rc<Dimension>> = data_set.get_dims_from_dim_ids(&var_info.dim_ids)?;
+        // Create the variable the variable
+        let var: &Variable = data_set.add_var_using_dim_refs(&var_info.name, dim_refs, var_info.data_type)?;
+        // Keep the `begin_offset` of the variable
+        if var.is_record_var() {
+            record_var_begin_offsets.push(var_info.begin_offset.clone());
+        }
+        // Append variable attributes
+        let var_name: String = var_info.name.clone();
+        for attr in var_info.attrs_list.iter() {
+            let attr_name: &String = &attr.name;
+            let attr_name: String = match (data_set.has_var_attr(&var_name, attr_name) == Some(true), names.duplicate_attr_policy) {
+                (false, _) | (true, DuplicateAttrPolicy::Error) => attr_name.clone(),
+                (true, DuplicateAttrPolicy::LastWins) => {
+                    // the value is replaced, the attribute keeps the position of its first definition
+                    let _replaced_attr: Attribute = data_set.replace_var_attr_from(&var_name, attr.clone())?;
+                    state.duplicates.push(Diagnostic::DuplicateAttribute{var_name: Some(var_name.clone()), attr_name: attr_name.clone(), new_name: None});
+                    continue;
+                },
+                (true, DuplicateAttrPolicy::Rename) => {
+                    let new_name: String = names.rename(attr_name, |name: &str| {
+                        data_set.has_var_attr(&var_name, name) == Some(true) || var_info.attrs_list.iter().any(|attr: &Attribute| attr.name == name)
+                    });
+                    state.duplicates.push(Diagnostic::DuplicateAttribute{var_name: Some(var_name.clone()), attr_name: attr_name.clone(), new_name: Some(new_name.clone())});
+                    new_name
+                },
+            };
+            let mut attr: Attribute = attr.clone();
+            attr.name = attr_name;
+            data_set.add_var_attr_from(&var_name, attr)?;
+        }
+    }
+
+    if !num_records_is_determinated {
+        // Case an *unlimited-size* dim s defined
+        if let Some(dim) = data_set.get_unlimited_dim() {
+            let num_records: usize;
+            let first_begin_offset: Option<usize> = record_var_begin_offsets.into_iter().map(|begin_offset: Offset| i64::from(begin_offset) as usize).min();
+            // Case: the unlimited dim  is defined but no record variable is defined
+            if let Some(first_begin_offset) = first_begin_offset {
+                // Computation of the number of records
+                let all_records_size: usize = total_file_size.checked_sub(first_begin_offset).ok_or(ReadError::ComputationNumberOfRecords)?; // the size allocated for all record data
+                let record_size: usize = data_set.record_size().ok_or(ReadError::Unexpected)?;
+                if record_size == 0 {  // cannot be zero
+                    return Err(ReadError::Unexpected);
+                }
+                num_records = all_records_size.checked_div_euclid(record_size).ok_or(ReadError::Unexpected)?;
+                let num_rem_bytes: usize = all_records_size.checked_rem_euclid(record_size).ok_or(ReadError::Unexpected)?;  // the number of remaining bytes
+                if num_rem_bytes != 0 {
+                    return Err(ReadError::ComputationNumberOfRecords);
+                }
+            }
+            else {
+                num_records = 0;
+            }
+            match &dim.size {
+                DimensionSize::Unlimited(dim_size) => {
+                    dim_size.replace(num_records);
+                },
+                _ => {},
+            }
+        }
+    }
+    // the names defined afterward are checked with the default rules
+    data_set.set_name_validation(NameValidation::default());
+    data_set.allow_long_names = false;
+    Ok((data_set, version, var_info_list, header_end))
+}
+
+fn parse_magic_word(input: &mut HeaderDecoder) -> Result<(), ParseHeaderError>
+{
+    input.tag(&[&b"CDF"[..]]).map_err(|err: DecodeError|{
+        ParseHeaderError::new(err, ParseHeaderErrorKind::MagicWord)
+    })?;
+    Ok(())
+}
+
+fn parse_version(input: &mut HeaderDecoder) -> Result<Version, ParseHeaderError>
+{
+    let start: usize = input.offset();
+    let version_number: u8 = input.be_u8().map_err(|err: DecodeError|{
+        ParseHeaderError::new(err, ParseHeaderErrorKind::VersionNumber)
+    })?;
+    let version: Version = Version::try_from(version_number).map_err(|_err|{
+        ParseHeaderError::new(input.invalid_from(start), ParseHeaderErrorKind::VersionNumber)
+    })?;
+    Ok(version)
+}
+
+/// Returns the capacity to reserve for `num_items` items of at least `min_item_size` bytes each,
+/// bounded by the remaining input to avoid huge allocations for corrupted counts.
+fn bounded_capacity(input: &HeaderDecoder, num_items: usize, min_item_size: usize) -> usize {
+    return core::cmp::min(num_items, input.remaining_size() / min_item_size);
+}
+
+/// Parses a `i32` word and checks that it is non-negative.
+pub(crate) fn parse_non_neg_i32(input: &mut HeaderDecoder) -> Result<i32, ParseHeaderError> {
+    input.verify(HeaderDecoder::be_i32, |number: &i32| *number >= 0_i32).map_err(|err: DecodeError|{
+        ParseHeaderError::new(err, ParseHeaderErrorKind::NonNegativeI32)
+    })
+}
+
+/// Parses a non-negative `i32` word and converts it to a `usize`.
+fn parse_as_usize(input: &mut HeaderDecoder) -> Result<usize, ParseHeaderError> {
+    let number: i32 = parse_non_neg_i32(input)?;
+    Ok(number as usize)
+}
+
+/// Parses the number of records
+///
+/// Returns :
+/// - The numbers of records if it is a valid integer.
+/// - `None` if the number of records is indeterminated
+pub(crate) fn parse_as_usize_optional(input: &mut HeaderDecoder) -> Result<Option<usize>, ParseHeaderError> {
+    const INDETERMINATE_VALUE: u32 = u32::MAX;
+    let value: u32 = input.verify(HeaderDecoder::be_u32, |number: &u32| *number <= (i32::MAX as u32) || *number == INDETERMINATE_VALUE).map_err(|err: DecodeError|{
+        ParseHeaderError::new(err, ParseHeaderErrorKind::NonNegativeI32)
+    })?;
+    let value: Option<usize> = match value {
+        INDETERMINATE_VALUE => None,
+        _ => Some(value as usize),
+    };
+    Ok(value)
+}
+
+/// Parses a non-negative `i32` word and converts it to a `u32`.
+fn parse_as_u32(input: &mut HeaderDecoder) -> Result<u32, ParseHeaderError> {
+    let number: i32 = parse_non_neg_i32(input)?;
+    Ok(number as u32)
+}
+/// Parses a string
+pub(crate) fn parse_name_string(input: &mut HeaderDecoder, state: &mut HeaderParseState) -> Result<String, ParseHeaderError>
+{
+    let num_of_bytes: usize = parse_as_usize(input)?;
+    let start: usize = input.offset();
+    let name_bytes: &[u8] = input.take(num_of_bytes).map_err(|err: DecodeError|{
+        ParseHeaderError::new(err, ParseHeaderErrorKind::Utf8)
+    })?;
+    let name: String = state.decode_name(name_bytes).map_err(|_err| {
+        ParseHeaderError::new(DecodeError::Invalid{bytes: name_bytes.to_vec(), offset: start}, ParseHeaderErrorKind::Utf8)
+    })?;
+    // Take the zero padding bytes if necessary
+    parse_padding(input, compute_padding_size(num_of_bytes), state)?;
+    Ok(name)
+}
+
+// Parses a NetCDF-3 data type.
+pub(crate) fn parse_data_type(input: &mut HeaderDecoder) -> Result<DataType, ParseHeaderError>
+{
+    let start: usize = input.offset();
+    let data_type_number: u32 = parse_as_u32(input)?;
+    let data_type: DataType = DataType::try_from(data_type_number).map_err(|_err|{
+        ParseHeaderError::new(input.invalid_from(start), ParseHeaderErrorKind::DataType)
+    })?;
+    Ok(data_type)
+}
+
+pub(crate) fn parse_typed_data_elements(input: &mut HeaderDecoder, num_of_elements: usize, data_type: DataType, state: &mut HeaderParseState) -> Result<DataVector, ParseHeaderError>
+{
+    // Convert the useful data in bulk from the big-endian bytes
+    let data_bytes: Vec<u8> = parse_data_bytes(input, num_of_elements, data_type, state)?;
+    Ok(DataVector::from_be_bytes(&data_bytes, data_type))
+}
+
+/// Parses the big-endian bytes of the data elements, followed by their padding bytes.
+fn parse_data_bytes(input: &mut HeaderDecoder, num_of_elements: usize, data_type: DataType, state: &mut HeaderParseState) -> Result<Vec<u8>, ParseHeaderError>
+{
+    // Check that all the elements are available, before allocating their memory
+    let num_of_bytes: usize = data_type.size_of().checked_mul(num_of_elements).ok_or_else(|| {
+        // the size of the elements overflows `usize`
+        ParseHeaderError::new(input.invalid_from(input.offset()), ParseHeaderErrorKind::DataElements)
+    })?;
+    let data_bytes: Vec<u8> = input.take(num_of_bytes).map_err(|err: DecodeError|{
+        ParseHeaderError::new(err, ParseHeaderErrorKind::DataElements)
+    })?.to_vec();
+
+    // Parse the zero padding bytes if necessary
+    parse_padding(input, compute_padding_size(num_of_bytes), state)?;
+    Ok(data_bytes)
+}
+
+/// Parses the padding bytes, checked following the [`PaddingCheck`](enum.PaddingCheck.html) of the decoder.
+fn parse_padding(input: &mut HeaderDecoder, num_bytes: usize, state: &mut HeaderParseState) -> Result<(), ParseHeaderError>
+{
+    if state.names.padding_check == PaddingCheck::Strict {
+        return parse_zero_padding(input, num_bytes);
+    }
+    let offset: usize = input.offset();
+    let padding_bytes: &[u8] = input.take(num_bytes).map_err(|err: DecodeError|{
+        ParseHeaderError::new(err, ParseHeaderErrorKind::ZeroPadding)
+    })?;
+    if state.names.padding_check == PaddingCheck::Warn && padding_bytes.iter().any(|byte: &u8| *byte != 0_u8) {
+        state.non_zero_paddings.push(Diagnostic::PaddingNotZero{offset: offset as u64, bytes: padding_bytes.to_vec()});
+    }
+    return Ok(());
+}
+
+pub(crate) fn parse_zero_padding(input: &mut HeaderDecoder, num_bytes: usize) -> Result<(), ParseHeaderError>
+{
+    input.take_valid(num_bytes, |padding_bytes: &[u8]| {
+        padding_bytes.iter().all(|byte: &u8| {
+            *byte == 0_u8
+        })
+    }).map_err(|err: DecodeError|{
+        ParseHeaderError::new(err, ParseHeaderErrorKind::ZeroPadding)
+    })?;
+    Ok(())
+}
+
+// Parses the list of the dimensions from the header.
+fn parse_dims_list(input: &mut HeaderDecoder, state: &mut HeaderParseState) -> Result<Vec<(String, usize)>, ParseHeaderError>
+{
+    fn parse_dim(input: &mut HeaderDecoder, state: &mut HeaderParseState) -> Result<(String, usize), ParseHeaderError>
+    {
+        let dim_name: String = parse_name_string(input, state)?;
+        let dim_size: usize = parse_as_usize(input)?;
+        Ok((dim_name, dim_size))
+    }
+    let dim_tag: &[u8] = input.tag(&[&ABSENT_TAG, &DIMENSION_TAG]).map_err(|err: DecodeError|{
+        ParseHeaderError::new(err, ParseHeaderErrorKind::DimTag)
+    })?;
+    if dim_tag == &ABSENT_TAG {
+        return Ok(vec![]);
+    }
+    let num_of_dims: usize = parse_as_usize(input)?;
+    // each dimension takes at least 8 bytes (name length and size)
+    let mut dims_list: Vec<(String, usize)> = Vec::with_capacity(bounded_capacity(input, num_of_dims, 8));
+    for _ in 0..num_of_dims{
+        let dim: (String, usize) = parse_dim(input, state)?;
+        dims_list.push(dim);
+    }
+
+    Ok(dims_list)
+}
+
+// Parses a list of attributes (global of from any variables) from the header.
+fn parse_attrs_list(input: &mut HeaderDecoder, state: &mut HeaderParseState) -> Result<Vec<Attribute>, ParseHeaderError>
+{
+    fn parse_attr(input: &mut HeaderDecoder, state: &mut HeaderParseState) -> Result<Attribute, ParseHeaderError>
+    {
+        let attr_name: String = parse_name_string(input, state)?;
+        let attr_data_type: DataType = parse_data_type(input)?;
+        let num_of_elements: usize = parse_as_usize(input)?;
+        if state.names.lazy_attrs {
+            // Keep the raw bytes, decoded at the first access
+            let attr_bytes: Vec<u8> = parse_data_bytes(input, num_of_elements, attr_data_type, state)?;
+            return Ok(Attribute::from_be_bytes(attr_name, attr_data_type, attr_bytes));
+        }
+        let attr_data: DataVector = parse_typed_data_elements(input, num_of_elements, attr_data_type, state)?;
+        Ok(Attribute::from_data(attr_name, attr_data))
+    }
+    let attr_tag: &[u8] = input.tag(&[&ABSENT_TAG, &ATTRIBUTE_TAG]).map_err(|err: DecodeError|{
+        ParseHeaderError::new(err, ParseHeaderErrorKind::AttrTag)
+    })?;
+    if attr_tag == &ABSENT_TAG {
+        return Ok(vec![]);
+    }
+    let num_of_attrs: usize = parse_as_usize(input)?;
+    // each attribute takes at least 12 bytes (name length, data type and number of elements)
+    let mut attrs_list: Vec<Attribute> = Vec::with_capacity(bounded_capacity(input, num_of_attrs, 12));
+    for _ in 0..num_of_attrs
+    {
+        let attr: Attribute = parse_attr(input, state)?;
+        attrs_list.push(attr);
+    }
+    Ok(attrs_list)
+}
+
+// Parses a list of variables from the header.
+fn parse_vars_list(input: &mut HeaderDecoder, version: Version, state: &mut HeaderParseState) -> Result<Vec<VariableParsedMetadata>, ParseHeaderError>
+{
+    fn parse_dim_ids_list(input: &mut HeaderDecoder) -> Result<Vec<usize>, ParseHeaderError>
+    {
+            // number of dimensions
+            let num_of_dims: usize = parse_as_usize(input)?;
+            // list of the dimension ids
+            let mut dim_ids_list: Vec<usize> = Vec::with_capacity(bounded_capacity(input, num_of_dims, 4));
+            for _ in 0..num_of_dims {
+                let dim_id: usize = parse_as_usize(input)?;
+                dim_ids_list.push(dim_id);
+            }
+            Ok(dim_ids_list)
+    }
+
+    fn parse_offset(input: &mut HeaderDecoder, version: Version) -> Result<Offset, ParseHeaderError>
+    {
+        match version {
+            Version::Classic => {
+                input.be_i32().map(|num_of_bytes: i32| {
+                    Offset::I32(num_of_bytes)
+                })
+            },
+            Version::Offset64Bit => {
+                input.be_i64().map(|num_of_bytes: i64| {
+                    Offset::I64(num_of_bytes)
+                })
+            },
+        }.map_err(|err: DecodeError| {
+            ParseHeaderError::new(err, ParseHeaderErrorKind::Offset)
+        })
+    }
+
+    fn parse_var(input: &mut HeaderDecoder, version: Version, state: &mut HeaderParseState) -> Result<VariableParsedMetadata, ParseHeaderError> {
+        // Variable name
+        let var_name: String = parse_name_string(input, state)?;
+
+        // list of the dimensions
+        let dim_ids: Vec<usize> = parse_dim_ids_list(input)?;
+        // list of the variable attributes
+        let attrs_list: Vec<Attribute> = parse_attrs_list(input, state)?;
+        // data type of the variable
+        let data_type: DataType = parse_data_type(input)?;
+        // size occupied in each record by the variable (number of bytes)
+        let chunk_size: Option<usize> = parse_as_usize_optional(input)?;
+        // begin offset (number of bytes)
+        let begin_offset: Offset = parse_offset(input, version)?;
+        let var_def = VariableParsedMetadata {
+            name: var_name,
+            dim_ids: dim_ids,
+            attrs_list: attrs_list,
+            data_type: data_type,
+            chunk_size: chunk_size,
+            begin_offset: begin_offset,
+        };
+        return Ok(var_def);
+    }
+    let var_tag: &[u8] = input.tag(&[&ABSENT_TAG, &VARIABLE_TAG]).map_err(|err: DecodeError| {
+        ParseHeaderError::new(err, ParseHeaderErrorKind::VarTag)
+    })?;
+    if var_tag == &ABSENT_TAG {
+        return Ok(vec![]);
+    }
+    let num_of_vars: usize = parse_as_usize(input)?;
+    let mut vars_list: Vec<VariableParsedMetadata> = vec![];
+    for _ in 0..num_of_vars {
+        let var: VariableParsedMetadata = parse_var(input, version, state)?;
+        vars_list.push(var);
+    }
+    Ok(vars_list)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct VariableParsedMetadata {
+    pub(crate) name: String,
+    pub(crate) dim_ids: Vec<usize>,
+    pub(crate) attrs_list: Vec<Attribute>,
+    pub(crate) data_type: DataType,
+    pub(crate) chunk_size: Option<usize>,
+    pub(crate) begin_offset: Offset,
+}
+
+
+/// Decodes the header of a NetCDF-3 file from its first bytes, and returns the data set and the version of the file.
+///
+/// The names are decoded and checked following `options`, the other options of the reader are not used and
+/// the non-fatal findings are not kept (see [`FileReader::diagnostics`](struct.FileReader.html#method.diagnostics)).
+/// The number of records of the *streaming* files is computed from the length of `bytes`, which must then contain the whole file.
+///
+/// This function does no I/O and is available in the `no_std` builds.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{decode_header, DataSet, ReadOptions, Version};
+/// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+///
+/// let (data_set, version): (DataSet, Version) = decode_header(NC3_CLASSIC_FILE_BYTES, &ReadOptions::default()).unwrap();
+/// assert_eq!(Version::Classic,            version);
+/// assert_eq!(true,                        data_set.has_var("latitude"));
+///
+/// // the header is incomplete
+/// assert_eq!(true,                        decode_header(&NC3_CLASSIC_FILE_BYTES[0..100], &ReadOptions::default()).unwrap_err().header_is_incomplete());
+/// ```
+pub fn decode_header(bytes: &[u8], options: &ReadOptions) -> Result<(DataSet, Version), ReadError> {
+    let mut state = HeaderParseState::new(NameDecoder::with_options(options));
+    let (data_set, version, _vars_info, _header_end): (DataSet, Version, Vec<VariableParsedMetadata>, usize) = parse_header(&mut HeaderDecoder::new(bytes), &mut state)?;
+    return Ok((data_set, version));
+}
+
+/// Encodes the header of the data set, followed by the zero bytes reserved up to `header_min_size` bytes
+/// (see [`FileWriter::set_def`](struct.FileWriter.html#method.set_def)).
+///
+/// The data of the variables are laid out like by the [`FileWriter`](struct.FileWriter.html) : the chunks of the *fixed-size*
+/// variables follow the header in the order of the variables, then each record contains a chunk of each record variable in the same order.
+/// These chunks are encoded by [`encode_chunk`](fn.encode_chunk.html).
+///
+/// This function does no I/O and is available in the `no_std` builds.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{encode_chunk, encode_header, DataSet, DataSlice, FileReader, FileWriter, Version};
+///
+/// let mut data_set = DataSet::new();
+/// data_set.set_unlimited_dim("time", 2).unwrap();
+/// data_set.add_fixed_dim("cell", 3).unwrap();
+/// data_set.add_var_i32("cell", &["cell"]).unwrap();
+/// data_set.add_var_i16("temperature", &["time", "cell"]).unwrap();
+/// data_set.add_var_f64("time", &["time"]).unwrap();
+///
+/// // Encode the file
+/// let mut bytes: Vec<u8> = encode_header(&data_set, Version::Classic, 0).unwrap();
+/// bytes.extend(encode_chunk(&data_set, "cell", DataSlice::from(&[0_i32, 1, 2][..])).unwrap());
+/// for record_index in 0..2 {
+///     bytes.extend(encode_chunk(&data_set, "temperature", DataSlice::from(&[10_i16, 11, 12][..])).unwrap());
+///     bytes.extend(encode_chunk(&data_set, "time", DataSlice::from(&[record_index as f64][..])).unwrap());
+/// }
+///
+/// // Same bytes as the ones written by the `FileWriter`
+/// let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+/// file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+/// file_writer.write_var_i32("cell", &[0, 1, 2]).unwrap();
+/// file_writer.write_var_i16("temperature", &[10, 11, 12, 10, 11, 12]).unwrap();
+/// file_writer.write_var_f64("time", &[0.0, 1.0]).unwrap();
+/// assert_eq!(file_writer.close_into_inner().unwrap().into_inner(),     bytes);
+///
+/// let mut file_reader = FileReader::from_reader(Cursor::new(bytes)).unwrap();
+/// assert_eq!(vec![0.0, 1.0],              file_reader.read_var_f64("time").unwrap());
+/// ```
+pub fn encode_header(data_set: &DataSet, version: Version, header_min_size: usize) -> Result<Vec<u8>, WriteError> {
+    let data_set_metadata: ComputedDataSetMetadata = ComputedDataSetMetadata::new(data_set, version, header_min_size)?;
+    let mut bytes: Vec<u8> = Vec::with_capacity(data_set_metadata.header_required_size + data_set_metadata.header_zero_padding_size);
+    write_header_parts(&mut bytes, data_set, version, &data_set_metadata.vars_metadata, false);
+    bytes.resize(bytes.len() + data_set_metadata.header_zero_padding_size, 0_u8);
+    return Ok(bytes);
+}
+
+/// Encodes a chunk of the variable (all its values for a *fixed-size* variable, one record otherwise) into big-endian bytes,
+/// followed by the padding bytes filled with the default fill value.
+///
+/// No padding bytes are appended when the variable is the only record variable, its records being contiguous.
+/// The layout of the chunks is described by [`encode_header`](fn.encode_header.html).
+///
+/// This function does no I/O and is available in the `no_std` builds.
+pub fn encode_chunk(data_set: &DataSet, var_name: &str, chunk: DataSlice<'_>) -> Result<Vec<u8>, WriteError> {
+    let var: &Variable = data_set.find_var_from_name(var_name).map_err(|_err| WriteError::VariableNotDefined(var_name.to_string()))?.1;
+    if var.data_type() != chunk.data_type() {
+        return Err(WriteError::VariableMismatchDataType{var_name: var_name.to_string(), req: var.data_type(), get: chunk.data_type()});
+    }
+    if chunk.len() != var.chunk_len() {
+        return Err(match var.is_record_var() {
+            true => WriteError::RecordMismatchDataLength{var_name: var_name.to_string(), req: var.chunk_len(), get: chunk.len()},
+            false => WriteError::VariableMismatchDataLength{var_name: var_name.to_string(), req: var.chunk_len(), get: chunk.len()},
+        });
+    }
+    let mut bytes: Vec<u8> = Vec::with_capacity(var.chunk_size());
+    write_be_values(&mut bytes, chunk);
+    let padding: bool = !(var.is_record_var() && data_set.records_are_packed());
+    if padding {
+        let padding_size: usize = compute_padding_size(bytes.len());
+        let nc_fill_bytes: Vec<u8> = nc_fill_be_bytes(var.data_type());
+        bytes.extend(nc_fill_bytes.iter().cycle().take(padding_size));
+    }
+    return Ok(bytes);
+}
+
+/// Returns the big-endian bytes of the default fill value of the data type.
+fn nc_fill_be_bytes(data_type: DataType) -> Vec<u8> {
+    return match data_type {
+        DataType::I8 => NC_FILL_I8.to_be_bytes().to_vec(),
+        DataType::U8 => NC_FILL_U8.to_be_bytes().to_vec(),
+        DataType::I16 => NC_FILL_I16.to_be_bytes().to_vec(),
+        DataType::I32 => NC_FILL_I32.to_be_bytes().to_vec(),
+        DataType::F32 => NC_FILL_F32.to_be_bytes().to_vec(),
+        DataType::F64 => NC_FILL_F64.to_be_bytes().to_vec(),
+    };
+}
+
+/// Appends the big-endian bytes of the values.
+fn write_be_values(bytes: &mut Vec<u8>, values: DataSlice<'_>) {
+    macro_rules! extend_be_bytes {
+        ($values:expr) => {
+            for value in $values.iter() {
+                bytes.extend_from_slice(&value.to_be_bytes());
+            }
+        };
+    }
+    match values {
+        DataSlice::I8(values) => extend_be_bytes!(values),
+        DataSlice::U8(values) => bytes.extend_from_slice(values),
+        DataSlice::I16(values) => extend_be_bytes!(values),
+        DataSlice::I32(values) => extend_be_bytes!(values),
+        DataSlice::F32(values) => extend_be_bytes!(values),
+        DataSlice::F64(values) => extend_be_bytes!(values),
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ComputedDataSetMetadata<'a> {
+    /// The number of bytes required for the header (containing useful bytes)
+    pub(crate) header_required_size: usize,
+    /// The number of the bytes of the zero padding append to the header
+    pub(crate) header_zero_padding_size: usize,
+    /// Metadata computed for each variable
+    pub(crate) vars_metadata: Vec<(&'a Variable, ComputedVariableMetadata)>,
+    /// `true` if the chunks of the record variables are not padded (only one record variable is defined)
+    #[cfg(feature = "write")]
+    pub(crate) records_are_packed: bool,
+}
+
+#[derive(Debug)]
+pub(crate) struct ComputedVariableMetadata {
+    /// The dimension IDs of the variable
+    pub(crate) dim_ids: Vec<usize>,
+    /// The number of bytes required to build each chunk of the variable
+    pub(crate) chunk_size: usize,
+    /// The offset (number of bytes) of the first chunck from the begin offset.
+    pub(crate) begin_offset: Offset,
+}
+
+impl<'a> ComputedDataSetMetadata<'a> {
+
+    /// Computes and returns all metadata required for each variable, namely :
+    ///
+    /// 0. The position of the variables stored in the *data part* (a `usize` instance).
+    /// 1. The header metadata of each variable :
+    ///     0. A reference to the variable (a `&Variable` instance).
+    ///     1. The IDs of its dimensions (a `Vec<usize>` instance)
+    ///     2. The `data_offset` to located the first chunck of the variable **from the begining of the data part** (a`usize` instance).
+    pub(crate) fn new(data_set: &'a DataSet, version: Version, header_min_size: usize) -> Result<ComputedDataSetMetadata<'a>, WriteError> {
+        data_set.check_format_limits(version)?;
+        data_set.check_attr_data_types()?;
+        data_set.check_name_sizes()?;
+        // Create a partition of variables to distinguish :
+        // 1. Fist the *fixed-size* variables.
+        // 2. Then the *record* variables.
+        let (record_vars, non_record_vars): (Vec<(usize, &Variable)>, Vec<(usize, &Variable)>) = data_set.vars.iter()
+            .enumerate()  // keep the original positions of the variables in the header
+            .partition(|(_var_pos, var): &(usize, &Variable)|{
+                var.is_record_var()
+            });
+        #[cfg(feature = "write")]
+        let records_are_packed: bool = record_vars.len() == 1;
+        let partitioned_vars: Vec<(usize, &Variable)> = non_record_vars.into_iter().chain(record_vars).collect();
+
+        // Compute the actual header size
+        let header_required_size: usize = compute_header_required_size(data_set, version);
+        let header_size: usize = {
+            let header_size: usize = core::cmp::max(header_min_size, header_required_size);
+            header_size.checked_add(compute_padding_size(header_size)).ok_or(WriteError::HeaderSizeOverflow{header_min_size: header_min_size})?
+        };
+
+        // Compute the metadata for each variable
+        // Accumulated as `u64` and saturated, an overflow is reported as an exceeded begin offset
+        let mut begin_offset: u64 = header_size as u64;
+        let mut vars_metadata: Vec<(usize, (&Variable, ComputedVariableMetadata))> = vec![];
+        for (header_part_pos, var) in partitioned_vars.into_iter() {
+            let chunk_size: usize = var.chunk_size();
+            vars_metadata.push((
+                header_part_pos,
+                (
+                    var,
+                    ComputedVariableMetadata{
+                        dim_ids: data_set.get_var_dim_ids(&var.name).unwrap(),
+                        chunk_size: chunk_size,
+                        begin_offset: compute_begin_offset(var, version, begin_offset)?,
+                    }
+                )
+            ));
+            begin_offset = begin_offset.saturating_add(chunk_size as u64);
+        }
+
+        // Retrieve the original position
+        vars_metadata.sort_by_key(|(header_part_pos, (_var, _var_metadata)): &(usize, (&Variable, ComputedVariableMetadata))| *header_part_pos);
+        // Remove the header positions of the variables
+        let vars_metadata: Vec<(&'a Variable, ComputedVariableMetadata)> = vars_metadata.into_iter().map(|x| x.1).collect();
+
+        // Returns the meta data only
+        Ok(ComputedDataSetMetadata{
+            header_required_size: header_required_size,
+            header_zero_padding_size: header_size - header_required_size,
+            vars_metadata: vars_metadata,
+            #[cfg(feature = "write")]
+            records_are_packed: records_are_packed,
+        })
+    }
+}
+
+/// Returns the bytes of the header of the data set (without zero padding), the data of the variables starting at `begin_offsets`.
+///
+/// The begin offsets are given in the order of the variables of the data set.
+#[cfg(all(feature = "read", feature = "write"))]
+pub(crate) fn header_bytes(data_set: &DataSet, version: Version, begin_offsets: &[u64]) -> Result<Vec<u8>, WriteError> {
+    if begin_offsets.len() != data_set.num_vars() {
+        return Err(WriteError::Unexpected);
+    }
+    data_set.check_format_limits(version)?;
+    data_set.check_attr_data_types()?;
+    data_set.check_name_sizes()?;
+    let vars_metadata: Vec<(&Variable, ComputedVariableMetadata)> = data_set.vars.iter().zip(begin_offsets.iter()).map(|(var, begin_offset): (&Variable, &u64)| {
+        let begin_offset: Offset = compute_begin_offset(var, version, *begin_offset)?;
+        Ok((var, ComputedVariableMetadata{
+            dim_ids: data_set.get_var_dim_ids(&var.name).ok_or(WriteError::Unexpected)?,
+            chunk_size: var.chunk_size(),
+            begin_offset: begin_offset,
+        }))
+    }).collect::<Result<Vec<(&Variable, ComputedVariableMetadata)>, WriteError>>()?;
+    let mut bytes: Vec<u8> = vec![];
+    write_header_parts(&mut bytes, data_set, version, &vars_metadata, false);
+    return Ok(bytes);
+}
+
+/// Converts the begin offset of the variable, returns an error if it exceeds the maximum offset of the version.
+fn compute_begin_offset(var: &Variable, version: Version, begin_offset: u64) -> Result<Offset, WriteError> {
+    let max_begin_offset: u64 = version.max_begin_offset();
+    if begin_offset > max_begin_offset {
+        return Err(WriteError::MaximumBeginOffsetExceeded{
+            var_name: var.name().to_string(),
+            version: version,
+            max: max_begin_offset,
+            get: begin_offset,
+        });
+    }
+    return Ok(match version {
+        Version::Classic => Offset::I32(begin_offset as i32),
+        Version::Offset64Bit => Offset::I64(begin_offset as i64),
+    });
+}
+
+/// Appends the header, except its zero padding, the number of records is replaced by the *streaming* value if `numrecs_streaming`.
+pub(crate) fn write_header_parts(bytes: &mut Vec<u8>, data_set: &DataSet, version: Version, vars_metadata: &[(&Variable, ComputedVariableMetadata)], numrecs_streaming: bool) {
+    // the magic word
+    bytes.extend_from_slice("CDF".as_bytes());
+    //the version number
+    bytes.push(version as u8);
+    // the size of the *unlimited-size* dimension
+    let num_records: u32 = match data_set.unlimited_dim.as_ref() {
+        None => 0,  // No unlimited-size dim is defined
+        Some(_) if numrecs_streaming => u32::MAX,
+        Some(unlim_dim) => {
+            let num_records: usize = unlim_dim.size();
+            if num_records <= (i32::MAX as usize) {
+                num_records as u32
+            } else {
+                u32::MAX  // indeterminate numbe of records records
+            }
+        }
+    };
+    bytes.extend_from_slice(&num_records.to_be_bytes());
+    // the list of the dimensions
+    write_dims_list(bytes, &data_set.dims);
+    // the list of the global attributes
+    write_attrs_list(bytes, &data_set.attrs);
+    // the list of the variables
+    write_vars_list(bytes, vars_metadata);
+}
+
+pub(crate) fn write_name_string(bytes: &mut Vec<u8>, name: &str) {
+    let name_bytes: &[u8] = name.as_bytes();
+    // the number of useful bytes
+    bytes.extend_from_slice(&(name_bytes.len() as i32).to_be_bytes());
+    // the name
+    bytes.extend_from_slice(name_bytes);
+    // the zero padding bytes
+    bytes.resize(bytes.len() + compute_padding_size(name_bytes.len()), 0_u8);
+}
+
+fn write_data_type(bytes: &mut Vec<u8>, data_type: DataType) {
+    bytes.extend_from_slice(&(data_type as i32).to_be_bytes());
+}
+
+pub(crate) fn write_dims_list(bytes: &mut Vec<u8>, dims_list: &[Rc<Dimension>]) {
+    if dims_list.is_empty() {
+        bytes.extend_from_slice(&ABSENT_TAG);
+        return;
+    }
+    bytes.extend_from_slice(&DIMENSION_TAG);
+    // the number of dimensions
+    bytes.extend_from_slice(&(dims_list.len() as i32).to_be_bytes());
+    for dim in dims_list.iter() {
+        write_name_string(bytes, dim.name().as_ref());
+        let dim_size: usize = match dim.size {
+            DimensionSize::Unlimited(_) => 0,  // the unlimited-size is recorded as 0
+            DimensionSize::Fixed(fixed_size) => fixed_size,
+        };
+        bytes.extend_from_slice(&(dim_size as i32).to_be_bytes());
+    }
+}
+
+fn write_attrs_list(bytes: &mut Vec<u8>, attrs_list: &[Attribute]) {
+    if attrs_list.is_empty() {
+        bytes.extend_from_slice(&ABSENT_TAG);
+        return;
+    }
+    bytes.extend_from_slice(&ATTRIBUTE_TAG);
+    // the number of attributes
+    bytes.extend_from_slice(&(attrs_list.len() as i32).to_be_bytes());
+    for attr in attrs_list.iter() {
+        write_name_string(bytes, &attr.name);
+        write_data_type(bytes, attr.data_type());
+        // the number of elements
+        bytes.extend_from_slice(&(attr.len() as i32).to_be_bytes());
+        // the data, followed by the zero padding bytes
+        let data_start: usize = bytes.len();
+        write_be_values(bytes, attr.data().as_slice());
+        let num_data_bytes: usize = bytes.len() - data_start;
+        bytes.resize(bytes.len() + compute_padding_size(num_data_bytes), 0_u8);
+    }
+}
+
+fn write_vars_list(bytes: &mut Vec<u8>, vars_metadata_list: &[(&Variable, ComputedVariableMetadata)]) {
+    if vars_metadata_list.is_empty() {
+        bytes.extend_from_slice(&ABSENT_TAG);
+        return;
+    }
+    bytes.extend_from_slice(&VARIABLE_TAG);
+    // the number of variables
+    bytes.extend_from_slice(&(vars_metadata_list.len() as i32).to_be_bytes());
+    for (var, var_metadata) in vars_metadata_list.iter() {
+        write_name_string(bytes, &var.name);
+        // the IDs of the dimensions
+        bytes.extend_from_slice(&(var.num_dims() as i32).to_be_bytes());
+        for dim_id in var_metadata.dim_ids.iter() {
+            bytes.extend_from_slice(&(*dim_id as i32).to_be_bytes());
+        }
+        write_attrs_list(bytes, &var.attrs);
+        write_data_type(bytes, var.data_type);
+        // the `vsize`, the number of bytes used per chunk (including the zero padding bytes)
+        let chunk_size: u32 = match var_metadata.chunk_size {
+            chunk_size if chunk_size > (i32::MAX as usize) => u32::MAX,
+            chunk_size => chunk_size as u32,
+        };
+        bytes.extend_from_slice(&chunk_size.to_be_bytes());
+        // the `begin_offset`
+        match var_metadata.begin_offset {
+            Offset::I32(begin_offset) => bytes.extend_from_slice(&begin_offset.to_be_bytes()),
+            Offset::I64(begin_offset) => bytes.extend_from_slice(&begin_offset.to_be_bytes()),
+        }
+    }
+}
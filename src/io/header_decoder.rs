@@ -0,0 +1,185 @@
+//! Decoding of the words of the NetCDF-3 header.
+//!
+//! The decoder checks explicitly that the input contains each word before decoding it. The bytes of the header
+//! are given at once, or loaded from the input as the decoding goes along (see [`LoadBytes`]), so that the header
+//! is decoded in a single pass whatever the number of loaded bytes.
+
+#[cfg(not(feature = "std"))]
+use crate::alloc_prelude::*;
+
+use alloc::borrow::Cow;
+
+use crate::error::parse_header_error::DecodeError;
+
+/// Source of the bytes of the header, loaded while decoding it.
+pub(crate) trait LoadBytes {
+    /// Appends the next bytes of the input to `bytes`, at least `num_bytes` bytes unless the input ends before.
+    fn load(&mut self, bytes: &mut Vec<u8>, num_bytes: usize);
+}
+
+/// Decoder of the words of the header, tracking the position of the next word from the start of the input.
+pub(crate) struct HeaderDecoder<'a> {
+    /// Bytes of the input loaded so far.
+    bytes: Cow<'a, [u8]>,
+    /// Position of the next word.
+    offset: usize,
+    /// Number of bytes of the whole input.
+    input_size: usize,
+    loader: Option<&'a mut dyn LoadBytes>,
+}
+
+impl<'a> HeaderDecoder<'a> {
+
+    /// Decodes the bytes given at once.
+    pub(crate) fn new(bytes: &'a [u8]) -> Self {
+        return HeaderDecoder{
+            bytes: Cow::Borrowed(bytes),
+            offset: 0,
+            input_size: bytes.len(),
+            loader: None,
+        };
+    }
+
+    /// Decodes the `input_size` bytes of an input, loaded by `loader` when the next word is needed.
+    #[cfg(feature = "read")]
+    pub(crate) fn with_loader(input_size: usize, loader: &'a mut dyn LoadBytes) -> Self {
+        return HeaderDecoder{
+            bytes: Cow::Owned(vec![]),
+            offset: 0,
+            input_size: input_size,
+            loader: Some(loader),
+        };
+    }
+
+    /// Returns the position of the next word from the start of the input.
+    pub(crate) fn offset(&self) -> usize {
+        return self.offset;
+    }
+
+    /// Returns the number of bytes of the whole input.
+    pub(crate) fn input_size(&self) -> usize {
+        return self.input_size;
+    }
+
+    /// Returns the number of bytes from the next word to the end of the input, loaded or not.
+    pub(crate) fn remaining_size(&self) -> usize {
+        return self.input_size.saturating_sub(self.offset);
+    }
+
+    /// Returns the loaded bytes following the next word.
+    #[cfg(test)]
+    pub(crate) fn remaining(&self) -> &[u8] {
+        return &self.bytes[self.offset..];
+    }
+
+    /// Returns the bytes of the input loaded while decoding.
+    #[cfg(feature = "read")]
+    pub(crate) fn into_loaded_bytes(self) -> Vec<u8> {
+        return self.bytes.into_owned();
+    }
+
+    /// Returns the error reporting the bytes from `start` to the next word as invalid.
+    pub(crate) fn invalid_from(&self, start: usize) -> DecodeError {
+        return DecodeError::Invalid{bytes: self.bytes[start..self.offset].to_vec(), offset: start};
+    }
+
+    /// Loads the bytes of the input up to `num_bytes` bytes from the next word, if they are not loaded yet.
+    fn load(&mut self, num_bytes: usize) {
+        let end: usize = core::cmp::min(self.offset.saturating_add(num_bytes), self.input_size);
+        if self.bytes.len() >= end {
+            return;
+        }
+        if let Some(loader) = self.loader.as_mut() {
+            let num_missing_bytes: usize = end - self.bytes.len();
+            loader.load(self.bytes.to_mut(), num_missing_bytes);
+        }
+    }
+
+    /// Takes the next `num_bytes` bytes of the input.
+    pub(crate) fn take(&mut self, num_bytes: usize) -> Result<&[u8], DecodeError> {
+        self.load(num_bytes);
+        let num_available_bytes: usize = self.bytes.len() - self.offset;
+        if num_available_bytes < num_bytes {
+            return Err(DecodeError::Incomplete{needed: num_bytes - num_available_bytes, offset: self.offset});
+        }
+        let start: usize = self.offset;
+        self.offset += num_bytes;
+        return Ok(&self.bytes[start..self.offset]);
+    }
+
+    /// Takes the next `num_bytes` bytes of the input, and checks them with `is_valid`.
+    pub(crate) fn take_valid<F>(&mut self, num_bytes: usize, is_valid: F) -> Result<&[u8], DecodeError>
+    where
+        F: FnOnce(&[u8]) -> bool,
+    {
+        let start: usize = self.offset;
+        let bytes: &[u8] = self.take(num_bytes)?;
+        if !is_valid(bytes) {
+            return Err(DecodeError::Invalid{bytes: bytes.to_vec(), offset: start});
+        }
+        return Ok(&self.bytes[start..self.offset]);
+    }
+
+    /// Takes the first of the `tags` starting the next bytes of the input.
+    pub(crate) fn tag<'t>(&mut self, tags: &[&'t [u8]]) -> Result<&'t [u8], DecodeError> {
+        let num_bytes: usize = tags.iter().map(|tag: &&[u8]| tag.len()).max().unwrap_or(0);
+        self.load(num_bytes);
+        let input: &[u8] = &self.bytes[self.offset..];
+        for tag in tags.iter() {
+            if input.len() < tag.len() {
+                // the input may be the beginning of the tag
+                if tag.starts_with(input) {
+                    return Err(DecodeError::Incomplete{needed: tag.len() - input.len(), offset: self.offset});
+                }
+            }
+            else if input.starts_with(tag) {
+                self.offset += tag.len();
+                return Ok(tag);
+            }
+        }
+        let invalid_bytes: Vec<u8> = input[0..core::cmp::min(num_bytes, input.len())].to_vec();
+        return Err(DecodeError::Invalid{bytes: invalid_bytes, offset: self.offset});
+    }
+
+    /// Takes the next `N` bytes of the input as an array.
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let bytes: &[u8] = self.take(N)?;
+        let mut array: [u8; N] = [0_u8; N];
+        array.copy_from_slice(bytes);
+        return Ok(array);
+    }
+
+    pub(crate) fn be_u8(&mut self) -> Result<u8, DecodeError> {
+        let bytes: [u8; 1] = self.take_array()?;
+        return Ok(bytes[0]);
+    }
+
+    pub(crate) fn be_i32(&mut self) -> Result<i32, DecodeError> {
+        let bytes: [u8; 4] = self.take_array()?;
+        return Ok(i32::from_be_bytes(bytes));
+    }
+
+    pub(crate) fn be_u32(&mut self) -> Result<u32, DecodeError> {
+        let bytes: [u8; 4] = self.take_array()?;
+        return Ok(u32::from_be_bytes(bytes));
+    }
+
+    pub(crate) fn be_i64(&mut self) -> Result<i64, DecodeError> {
+        let bytes: [u8; 8] = self.take_array()?;
+        return Ok(i64::from_be_bytes(bytes));
+    }
+
+    /// Decodes a word with `decode`, and checks its value with `is_valid`.
+    pub(crate) fn verify<T, D, F>(&mut self, decode: D, is_valid: F) -> Result<T, DecodeError>
+    where
+        D: FnOnce(&mut Self) -> Result<T, DecodeError>,
+        F: FnOnce(&T) -> bool,
+    {
+        let start: usize = self.offset;
+        let value: T = decode(self)?;
+        if !is_valid(&value) {
+            return Err(self.invalid_from(start));
+        }
+        return Ok(value);
+    }
+}
@@ -0,0 +1,428 @@
+use std::io::{Read, Seek, SeekFrom};
+
+/// Source of the byte ranges of a remote file, allows to use any HTTP client with [`HttpRangeReader`](struct.HttpRangeReader.html).
+///
+/// The crate provides the implementations [`ReqwestBackend`](struct.ReqwestBackend.html) and [`UreqBackend`](struct.UreqBackend.html)
+/// (requires the feature `http-ureq`).
+///
+/// # Example
+///
+/// ```
+/// use std::io::Read;
+/// use netcdf3::{HttpRangeReader, RangeBackend};
+///
+/// /// Serves the bytes of an in-memory file, in place of an HTTP client.
+/// struct MemoryBackend(Vec<u8>);
+///
+/// impl RangeBackend for MemoryBackend {
+///     fn size(&mut self) -> std::io::Result<u64> {
+///         return Ok(self.0.len() as u64);
+///     }
+///
+///     fn fetch_range(&mut self, start: u64, end: u64) -> std::io::Result<Vec<u8>> {
+///         return Ok(self.0[(start as usize)..(end as usize)].to_vec());
+///     }
+/// }
+///
+/// let mut range_reader = HttpRangeReader::from_backend(MemoryBackend(b"CDF\x01".to_vec())).unwrap();
+/// let mut bytes: Vec<u8> = vec![];
+/// range_reader.read_to_end(&mut bytes).unwrap();
+/// assert_eq!(b"CDF\x01".to_vec(),    bytes);
+/// ```
+pub trait RangeBackend {
+
+    /// Returns the size (number of bytes) of the remote file.
+    fn size(&mut self) -> std::io::Result<u64>;
+
+    /// Fetches the bytes `start..end` of the remote file, `start < end <= size`.
+    fn fetch_range(&mut self, start: u64, end: u64) -> std::io::Result<Vec<u8>>;
+}
+
+/// Range backend sending the requests with the blocking client of [`reqwest`](https://docs.rs/reqwest), default backend of
+/// [`HttpRangeReader`](struct.HttpRangeReader.html).
+///
+/// The blocking client does not require the program to run an async runtime, and matches the blocking `Read + Seek` interface
+/// of the readers. Its TLS support uses `rustls` (no system OpenSSL library).
+///
+/// The server has to provide the size of the file (header `Content-Length` of the `HEAD` response) and to support the range requests.
+#[derive(Debug)]
+pub struct ReqwestBackend {
+    client: reqwest::blocking::Client,
+    url: String,
+}
+
+impl ReqwestBackend {
+
+    pub fn new(url: &str) -> Self {
+        return ReqwestBackend::with_client(reqwest::blocking::Client::new(), url);
+    }
+
+    /// Sends the requests with an already configured client (timeouts, proxy, authentication headers, etc.).
+    pub fn with_client(client: reqwest::blocking::Client, url: &str) -> Self {
+        return ReqwestBackend{client: client, url: url.to_string()};
+    }
+
+    pub fn url(&self) -> &str {
+        return &self.url;
+    }
+}
+
+impl RangeBackend for ReqwestBackend {
+
+    fn size(&mut self) -> std::io::Result<u64> {
+        let response: reqwest::blocking::Response = self.client.head(&self.url)
+            .send()
+            .and_then(|response: reqwest::blocking::Response| response.error_for_status())
+            .map_err(|err: reqwest::Error| http_error(&self.url, err.to_string()))?;
+        let size: u64 = response.headers().get(reqwest::header::CONTENT_LENGTH)
+            .and_then(|content_length: &reqwest::header::HeaderValue| content_length.to_str().ok())
+            .and_then(|content_length: &str| content_length.trim().parse().ok())
+            .ok_or_else(|| http_error(&self.url, String::from("the size of the file is not provided")))?;
+        return Ok(size);
+    }
+
+    fn fetch_range(&mut self, start: u64, end: u64) -> std::io::Result<Vec<u8>> {
+        let response: reqwest::blocking::Response = self.client.get(&self.url)
+            .header(reqwest::header::RANGE, format!("bytes={}-{}", start, end - 1))
+            .send()
+            .and_then(|response: reqwest::blocking::Response| response.error_for_status())
+            .map_err(|err: reqwest::Error| http_error(&self.url, err.to_string()))?;
+        if response.status() != reqwest::StatusCode::PARTIAL_CONTENT {
+            return Err(http_error(&self.url, String::from("the range requests are not supported by the server")));
+        }
+        let num_bytes: u64 = end - start;
+        let mut bytes: Vec<u8> = Vec::with_capacity(num_bytes as usize);
+        response.take(num_bytes).read_to_end(&mut bytes)?;
+        return Ok(bytes);
+    }
+}
+
+/// Range backend sending the requests with the HTTP client [`ureq`](https://docs.rs/ureq) (requires the feature `http-ureq`).
+///
+/// Lighter than [`ReqwestBackend`](struct.ReqwestBackend.html) (no `hyper` and `tokio` dependencies), it is used through
+/// [`HttpRangeReader::from_backend`](struct.HttpRangeReader.html#method.from_backend).
+///
+/// The server has to provide the size of the file (header `Content-Length` of the `HEAD` response) and to support the range requests.
+#[cfg(feature = "http-ureq")]
+#[derive(Debug)]
+pub struct UreqBackend {
+    url: String,
+}
+
+#[cfg(feature = "http-ureq")]
+impl UreqBackend {
+
+    pub fn new(url: &str) -> Self {
+        return UreqBackend{url: url.to_string()};
+    }
+
+    pub fn url(&self) -> &str {
+        return &self.url;
+    }
+}
+
+#[cfg(feature = "http-ureq")]
+impl RangeBackend for UreqBackend {
+
+    fn size(&mut self) -> std::io::Result<u64> {
+        let response = ureq::head(&self.url).call().map_err(|err: ureq::Error| http_error(&self.url, err.to_string()))?;
+        let size: u64 = response.header("Content-Length")
+            .and_then(|content_length: &str| content_length.trim().parse().ok())
+            .ok_or_else(|| http_error(&self.url, String::from("the size of the file is not provided")))?;
+        return Ok(size);
+    }
+
+    fn fetch_range(&mut self, start: u64, end: u64) -> std::io::Result<Vec<u8>> {
+        let response = ureq::get(&self.url)
+            .set("Range", &format!("bytes={}-{}", start, end - 1))
+            .call()
+            .map_err(|err: ureq::Error| http_error(&self.url, err.to_string()))?;
+        if response.status() != 206 {
+            return Err(http_error(&self.url, String::from("the range requests are not supported by the server")));
+        }
+        let num_bytes: u64 = end - start;
+        let mut bytes: Vec<u8> = Vec::with_capacity(num_bytes as usize);
+        response.into_reader().take(num_bytes).read_to_end(&mut bytes)?;
+        return Ok(bytes);
+    }
+}
+
+/// Reads a remote file through range requests (requires the feature `http`).
+///
+/// Only the requested bytes are downloaded, which allows to parse the header and to read a few variables of a large NetCDF-3 file
+/// without downloading it entirely. The successive small reads are coalesced : each request fetches at least
+/// [`min_request_size`](#method.min_request_size) bytes, and the next reads are served from this buffer.
+///
+/// The requests are sent by a [`RangeBackend`](trait.RangeBackend.html), by default the HTTP(S) client
+/// [`ReqwestBackend`](struct.ReqwestBackend.html).
+///
+/// # Example
+///
+/// ```no_run
+/// use netcdf3::{FileReader, HttpRangeReader, DataVector};
+///
+/// const URL: &str = "https://example.com/data/file.nc";
+///
+/// // using the default coalescing
+/// let mut file_reader = FileReader::open_url(URL).unwrap();
+/// let latitude: DataVector = file_reader.read_var("latitude").unwrap();
+///
+/// // using larger requests
+/// let http_reader = HttpRangeReader::open(URL).unwrap().with_min_request_size(1024 * 1024);
+/// let mut file_reader = FileReader::from_reader(http_reader).unwrap();
+/// let latitude: DataVector = file_reader.read_var("latitude").unwrap();
+/// ```
+#[derive(Debug)]
+pub struct HttpRangeReader<B: RangeBackend = ReqwestBackend> {
+    backend: B,
+    size: u64,
+    pos: u64,
+    min_request_size: usize,
+    /// Bytes fetched by the last request, starting at `buffer_start`
+    buffer: Vec<u8>,
+    buffer_start: u64,
+    num_requests: usize,
+}
+
+impl HttpRangeReader<ReqwestBackend> {
+
+    /// Requests the size of the remote file, no other byte is fetched.
+    pub fn open(url: &str) -> std::io::Result<Self> {
+        return HttpRangeReader::from_backend(ReqwestBackend::new(url));
+    }
+
+    pub fn url(&self) -> &str {
+        return self.backend.url();
+    }
+}
+
+impl<B: RangeBackend> HttpRangeReader<B> {
+
+    /// Default minimum number of bytes fetched by each range request.
+    pub const DEFAULT_MIN_REQUEST_SIZE: usize = 64 * 1024;
+
+    /// Requests the size of the remote file to the backend, no other byte is fetched.
+    pub fn from_backend(mut backend: B) -> std::io::Result<Self> {
+        let size: u64 = backend.size()?;
+        return Ok(HttpRangeReader{
+            backend: backend,
+            size: size,
+            pos: 0,
+            min_request_size: Self::DEFAULT_MIN_REQUEST_SIZE,
+            buffer: vec![],
+            buffer_start: 0,
+            num_requests: 1,
+        });
+    }
+
+    /// Sets the minimum number of bytes fetched by each range request (at least 1 byte).
+    pub fn with_min_request_size(mut self, min_request_size: usize) -> Self {
+        self.min_request_size = min_request_size.max(1);
+        return self;
+    }
+
+    pub fn backend(&self) -> &B {
+        return &self.backend;
+    }
+
+    /// Returns the size (number of bytes) of the remote file.
+    pub fn size(&self) -> u64 {
+        return self.size;
+    }
+
+    pub fn min_request_size(&self) -> usize {
+        return self.min_request_size;
+    }
+
+    /// Returns the number of requests sent so far (including the initial request of the size).
+    pub fn num_requests(&self) -> usize {
+        return self.num_requests;
+    }
+
+    fn buffer_contains(&self, pos: u64) -> bool {
+        return pos >= self.buffer_start && pos < self.buffer_start + self.buffer.len() as u64;
+    }
+
+    /// Fetches the bytes `start..end` of the remote file.
+    fn fetch_range(&mut self, start: u64, end: u64) -> std::io::Result<Vec<u8>> {
+        self.num_requests += 1;
+        let bytes: Vec<u8> = self.backend.fetch_range(start, end)?;
+        if bytes.len() as u64 != end - start {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                format!("incomplete range response: {} bytes received, {} expected", bytes.len(), end - start)
+            ));
+        }
+        return Ok(bytes);
+    }
+}
+
+impl<B: RangeBackend> Read for HttpRangeReader<B> {
+
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if buf.is_empty() || self.pos >= self.size {
+            return Ok(0);
+        }
+        if !self.buffer_contains(self.pos) {
+            let request_size: u64 = buf.len().max(self.min_request_size) as u64;
+            let end: u64 = std::cmp::min(self.pos + request_size, self.size);
+            self.buffer = self.fetch_range(self.pos, end)?;
+            self.buffer_start = self.pos;
+        }
+        let offset: usize = (self.pos - self.buffer_start) as usize;
+        let num_bytes: usize = std::cmp::min(buf.len(), self.buffer.len() - offset);
+        buf[0..num_bytes].copy_from_slice(&self.buffer[offset..(offset + num_bytes)]);
+        self.pos += num_bytes as u64;
+        return Ok(num_bytes);
+    }
+}
+
+impl<B: RangeBackend> Seek for HttpRangeReader<B> {
+
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let new_pos: i128 = match pos {
+            SeekFrom::Start(offset) => offset as i128,
+            SeekFrom::End(offset) => self.size as i128 + offset as i128,
+            SeekFrom::Current(offset) => self.pos as i128 + offset as i128,
+        };
+        if new_pos < 0 {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "invalid seek to a negative position"));
+        }
+        self.pos = new_pos as u64;
+        return Ok(self.pos);
+    }
+}
+
+fn http_error(url: &str, message: String) -> std::io::Error {
+    return std::io::Error::other(format!("{}: {}", url, message));
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::{BufRead, BufReader, Cursor, Read, Seek, SeekFrom, Write};
+    use std::net::{TcpListener, TcpStream};
+
+    use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+
+    use super::{HttpRangeReader, RangeBackend};
+    use crate::{FileReader, DataVector};
+
+    /// Serves `bytes` on a local port, supports the `HEAD` requests and the `GET` requests with a single range.
+    fn serve_bytes(bytes: &'static [u8]) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url: String = format!("http://{}/file.nc", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                handle_request(stream.unwrap(), bytes);
+            }
+        });
+        return url;
+    }
+
+    fn handle_request(mut stream: TcpStream, bytes: &[u8]) {
+        let mut lines = BufReader::new(stream.try_clone().unwrap()).lines();
+        let request_line: String = lines.next().unwrap().unwrap();
+        let mut range: Option<(usize, usize)> = None;
+        for line in lines {
+            let line: String = line.unwrap();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(value) = line.strip_prefix("range: bytes=").or_else(|| line.strip_prefix("Range: bytes=")) {
+                let mut bounds = value.split('-').map(|bound: &str| bound.parse::<usize>().unwrap());
+                range = Some((bounds.next().unwrap(), bounds.next().unwrap()));
+            }
+        }
+        let response: Vec<u8> = if request_line.starts_with("HEAD") {
+            format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\nAccept-Ranges: bytes\r\nConnection: close\r\n\r\n", bytes.len()).into_bytes()
+        } else {
+            let (start, end): (usize, usize) = range.unwrap();
+            let body: &[u8] = &bytes[start..=end];
+            let mut response: Vec<u8> = format!(
+                "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {}-{}/{}\r\nConnection: close\r\n\r\n",
+                body.len(), start, end, bytes.len()
+            ).into_bytes();
+            response.extend_from_slice(body);
+            response
+        };
+        stream.write_all(&response).unwrap();
+    }
+
+    #[test]
+    fn test_http_range_reader_read_seek() {
+        let url: String = serve_bytes(NC3_CLASSIC_FILE_BYTES);
+        let mut http_reader = HttpRangeReader::open(&url).unwrap().with_min_request_size(16);
+        assert_eq!(NC3_CLASSIC_FILE_BYTES.len() as u64,     http_reader.size());
+        assert_eq!(1,                                       http_reader.num_requests());
+
+        let mut buffer: [u8; 4] = [0; 4];
+        http_reader.read_exact(&mut buffer).unwrap();
+        assert_eq!(&NC3_CLASSIC_FILE_BYTES[0..4],           &buffer[..]);
+        // served from the previous request
+        http_reader.read_exact(&mut buffer).unwrap();
+        assert_eq!(&NC3_CLASSIC_FILE_BYTES[4..8],           &buffer[..]);
+        assert_eq!(2,                                       http_reader.num_requests());
+
+        // read the end of the file
+        http_reader.seek(SeekFrom::End(-4)).unwrap();
+        let mut bytes: Vec<u8> = vec![];
+        http_reader.read_to_end(&mut bytes).unwrap();
+        assert_eq!(&NC3_CLASSIC_FILE_BYTES[(NC3_CLASSIC_FILE_BYTES.len() - 4)..],     &bytes[..]);
+        assert_eq!(3,                                       http_reader.num_requests());
+
+        assert!(http_reader.seek(SeekFrom::Current(-(NC3_CLASSIC_FILE_BYTES.len() as i64) - 1)).is_err());
+    }
+
+    #[test]
+    fn test_file_reader_open_url() {
+        let url: String = serve_bytes(NC3_CLASSIC_FILE_BYTES);
+        let mut file_reader: FileReader<HttpRangeReader> = FileReader::open_url(&url).unwrap();
+        let mut expected_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+
+        assert_eq!(expected_reader.data_set().get_var_names(),      file_reader.data_set().get_var_names());
+        for var_name in expected_reader.data_set().get_var_names() {
+            let expected_data: DataVector = expected_reader.read_var(&var_name).unwrap();
+            assert_eq!(expected_data,                               file_reader.read_var(&var_name).unwrap());
+        }
+    }
+
+    #[cfg(feature = "http-ureq")]
+    #[test]
+    fn test_http_range_reader_ureq_backend() {
+        let url: String = serve_bytes(NC3_CLASSIC_FILE_BYTES);
+        let http_reader = HttpRangeReader::from_backend(super::UreqBackend::new(&url)).unwrap();
+        assert_eq!(url.as_str(),                                    http_reader.backend().url());
+        let mut file_reader = FileReader::from_reader(http_reader).unwrap();
+        let mut expected_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+
+        for var_name in expected_reader.data_set().get_var_names() {
+            let expected_data: DataVector = expected_reader.read_var(&var_name).unwrap();
+            assert_eq!(expected_data,                               file_reader.read_var(&var_name).unwrap());
+        }
+    }
+
+    /// Backend returning one byte less than requested.
+    struct ShortBackend;
+
+    impl RangeBackend for ShortBackend {
+        fn size(&mut self) -> std::io::Result<u64> {
+            return Ok(NC3_CLASSIC_FILE_BYTES.len() as u64);
+        }
+
+        fn fetch_range(&mut self, start: u64, end: u64) -> std::io::Result<Vec<u8>> {
+            return Ok(NC3_CLASSIC_FILE_BYTES[(start as usize)..((end - 1) as usize)].to_vec());
+        }
+    }
+
+    #[test]
+    fn test_http_range_reader_custom_backend() {
+        let mut range_reader = HttpRangeReader::from_backend(ShortBackend).unwrap();
+        assert_eq!(NC3_CLASSIC_FILE_BYTES.len() as u64,     range_reader.size());
+
+        let mut buffer: [u8; 4] = [0; 4];
+        let err: std::io::Error = range_reader.read_exact(&mut buffer).unwrap_err();
+        assert_eq!(std::io::ErrorKind::UnexpectedEof,       err.kind());
+        assert_eq!(2,                                       range_reader.num_requests());
+    }
+}
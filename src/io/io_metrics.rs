@@ -0,0 +1,312 @@
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Counters of the I/O operations done by a [`FileReader`](struct.FileReader.html) or a [`FileWriter`](struct.FileWriter.html),
+/// returned by their methods `io_metrics`.
+///
+/// The counters are accumulated since the creation of the reader or the writer, or since the last call to `reset_io_metrics`.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{FileReader, IoMetrics};
+/// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+///
+/// let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+/// file_reader.reset_io_metrics();  // the bytes of the header are not counted
+///
+/// // `latitude` : 3 `f32` values
+/// file_reader.read_var("latitude").unwrap();
+/// let metrics: IoMetrics = file_reader.io_metrics();
+/// assert_eq!(1,               metrics.chunks_read);
+/// assert_eq!(12,              metrics.bytes_read);
+/// assert_eq!(0,               metrics.bytes_written);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IoMetrics {
+    /// Number of bytes read from the input (header included).
+    pub bytes_read: u64,
+    /// Number of bytes written into the output (header, padding and fill values included).
+    pub bytes_written: u64,
+    /// Number of seeks done on the input or the output.
+    pub seeks: u64,
+    /// Number of chunks read, a chunk being the data of a *fixed-size* variable or one record of a record variable.
+    ///
+    /// The partial chunks read by [`FileReader::read_var_slice`](struct.FileReader.html#method.read_var_slice) are not counted.
+    pub chunks_read: u64,
+    /// Number of chunks written with the values given by the user.
+    pub chunks_written: u64,
+    /// Number of bytes of the fill values written into the chunks which have not been written by the user, padding included.
+    pub fill_bytes_written: u64,
+}
+
+/// Counters shared by the threads reading the input through a shared reference.
+#[derive(Debug, Default)]
+pub(crate) struct IoCounters {
+    bytes_read: AtomicU64,
+    bytes_written: AtomicU64,
+    seeks: AtomicU64,
+    chunks_read: AtomicU64,
+    chunks_written: AtomicU64,
+    fill_bytes_written: AtomicU64,
+}
+
+impl IoCounters {
+    pub(crate) fn add_bytes_read(&self, num_bytes: usize) {
+        self.bytes_read.fetch_add(num_bytes as u64, Ordering::Relaxed);
+    }
+
+    #[cfg(feature = "read")]
+    pub(crate) fn add_chunks_read(&self, num_chunks: usize) {
+        self.chunks_read.fetch_add(num_chunks as u64, Ordering::Relaxed);
+    }
+
+    #[cfg(feature = "write")]
+    pub(crate) fn add_chunks_written(&self, num_chunks: usize) {
+        self.chunks_written.fetch_add(num_chunks as u64, Ordering::Relaxed);
+    }
+
+    #[cfg(feature = "write")]
+    pub(crate) fn add_fill_bytes_written(&self, num_bytes: usize) {
+        self.fill_bytes_written.fetch_add(num_bytes as u64, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> IoMetrics {
+        return IoMetrics {
+            bytes_read: self.bytes_read.load(Ordering::Relaxed),
+            bytes_written: self.bytes_written.load(Ordering::Relaxed),
+            seeks: self.seeks.load(Ordering::Relaxed),
+            chunks_read: self.chunks_read.load(Ordering::Relaxed),
+            chunks_written: self.chunks_written.load(Ordering::Relaxed),
+            fill_bytes_written: self.fill_bytes_written.load(Ordering::Relaxed),
+        };
+    }
+
+    pub(crate) fn reset(&self) {
+        for counter in [&self.bytes_read, &self.bytes_written, &self.seeks, &self.chunks_read, &self.chunks_written, &self.fill_bytes_written].iter() {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Input or output of the readers and the writers, counting the bytes read and written and the seeks.
+#[derive(Debug)]
+pub(crate) struct MeteredIo<T> {
+    inner: T,
+    counters: IoCounters,
+    /// Handle on the file of `inner` (if `inner` is a file), the chunks being then read and written through positional I/O
+    #[cfg(any(unix, windows))]
+    file: Option<std::fs::File>,
+}
+
+impl<T> MeteredIo<T> {
+    pub(crate) fn new(inner: T) -> MeteredIo<T> {
+        return MeteredIo {
+            inner: inner,
+            counters: IoCounters::default(),
+            #[cfg(any(unix, windows))]
+            file: None,
+        };
+    }
+
+    /// Reads and writes the chunks through positional I/O on `file`, a handle on the same file as `inner`.
+    ///
+    /// Afterward, the cursor of `inner` must not be relied on (see `positioned`).
+    #[cfg(all(feature = "fs", any(unix, windows)))]
+    pub(crate) fn set_file(&mut self, file: std::fs::File) {
+        self.file = Some(file);
+    }
+
+    #[cfg(any(feature = "read", all(feature = "fs", any(unix, windows))))]
+    pub(crate) fn get_ref(&self) -> &T {
+        return &self.inner;
+    }
+
+    #[cfg(feature = "write")]
+    pub(crate) fn into_inner(self) -> T {
+        return self.inner;
+    }
+
+    pub(crate) fn counters(&self) -> &IoCounters {
+        return &self.counters;
+    }
+}
+
+impl<T: Read> Read for MeteredIo<T> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let num_bytes: usize = self.inner.read(buf)?;
+        self.counters.add_bytes_read(num_bytes);
+        return Ok(num_bytes);
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        self.inner.read_exact(buf)?;
+        self.counters.add_bytes_read(buf.len());
+        return Ok(());
+    }
+}
+
+impl<T: Write> Write for MeteredIo<T> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let num_bytes: usize = self.inner.write(buf)?;
+        self.counters.bytes_written.fetch_add(num_bytes as u64, Ordering::Relaxed);
+        return Ok(num_bytes);
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.inner.write_all(buf)?;
+        self.counters.bytes_written.fetch_add(buf.len() as u64, Ordering::Relaxed);
+        return Ok(());
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        return self.inner.flush();
+    }
+}
+
+impl<T: Seek> Seek for MeteredIo<T> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        self.counters.seeks.fetch_add(1, Ordering::Relaxed);
+        return self.inner.seek(pos);
+    }
+}
+
+impl<T: Seek> MeteredIo<T> {
+    /// Returns the input or output positioned at `offset` (see `PositionedIo`).
+    pub(crate) fn positioned(&mut self, offset: u64) -> std::io::Result<PositionedIo<'_, T>> {
+        let mut positioned_io = PositionedIo{io: self, offset: offset};
+        positioned_io.set_offset(offset)?;
+        return Ok(positioned_io);
+    }
+}
+
+#[cfg(feature = "read")]
+impl<T: Read + Seek> MeteredIo<T> {
+    /// Reads exactly `buf.len()` bytes located at `offset`.
+    pub(crate) fn read_exact_at(&mut self, buf: &mut [u8], offset: u64) -> std::io::Result<()> {
+        return self.positioned(offset)?.read_exact(buf);
+    }
+}
+
+#[cfg(feature = "write")]
+impl<T: Write + Seek> MeteredIo<T> {
+    /// Writes all the bytes of `buf` at `offset`.
+    pub(crate) fn write_all_at(&mut self, buf: &[u8], offset: u64) -> std::io::Result<()> {
+        return self.positioned(offset)?.write_all(buf);
+    }
+}
+
+/// Input or output read or written from an offset, the offset being moved forward by the bytes read or written.
+///
+/// Positional reads and writes are done if the handle on the file is set (see `MeteredIo::set_file`),
+/// so no seek is required. Otherwise the cursor of the input or output is moved to the offset by a seek.
+pub(crate) struct PositionedIo<'a, T> {
+    io: &'a mut MeteredIo<T>,
+    offset: u64,
+}
+
+impl<'a, T: Seek> PositionedIo<'a, T> {
+    /// Moves to `offset`.
+    pub(crate) fn set_offset(&mut self, offset: u64) -> std::io::Result<()> {
+        if !self.is_positional() {
+            self.io.seek(SeekFrom::Start(offset))?;
+        }
+        self.offset = offset;
+        return Ok(());
+    }
+
+    /// Skips the next `num_bytes` bytes.
+    #[cfg(feature = "read")]
+    pub(crate) fn skip(&mut self, num_bytes: u64) -> std::io::Result<()> {
+        let offset: u64 = self.offset + num_bytes;
+        return self.set_offset(offset);
+    }
+
+    #[cfg(feature = "read")]
+    pub(crate) fn counters(&self) -> &IoCounters {
+        return self.io.counters();
+    }
+
+    #[cfg(any(unix, windows))]
+    fn is_positional(&self) -> bool {
+        return self.io.file.is_some();
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    fn is_positional(&self) -> bool {
+        return false;
+    }
+}
+
+impl<'a, T: Read> Read for PositionedIo<'a, T> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        #[cfg(any(unix, windows))]
+        {
+            if let Some(ref file) = self.io.file {
+                let num_bytes: usize = read_at(file, buf, self.offset)?;
+                self.io.counters.add_bytes_read(num_bytes);
+                self.offset += num_bytes as u64;
+                return Ok(num_bytes);
+            }
+        }
+        let num_bytes: usize = self.io.read(buf)?;
+        self.offset += num_bytes as u64;
+        return Ok(num_bytes);
+    }
+}
+
+impl<'a, T: Write> Write for PositionedIo<'a, T> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        #[cfg(any(unix, windows))]
+        {
+            if let Some(ref file) = self.io.file {
+                let num_bytes: usize = write_at(file, buf, self.offset)?;
+                self.io.counters.bytes_written.fetch_add(num_bytes as u64, Ordering::Relaxed);
+                self.offset += num_bytes as u64;
+                return Ok(num_bytes);
+            }
+        }
+        let num_bytes: usize = self.io.write(buf)?;
+        self.offset += num_bytes as u64;
+        return Ok(num_bytes);
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        return self.io.flush();
+    }
+}
+
+/// Reads the bytes located at `offset` in the file into `buf` through a single positional read, and returns the number of bytes read.
+///
+/// The cursor of the file is not moved on Unix.
+#[cfg(any(unix, windows))]
+pub(crate) fn read_at(file: &std::fs::File, buf: &mut [u8], offset: u64) -> std::io::Result<usize> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileExt;
+        return file.read_at(buf, offset);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::FileExt;
+        return file.seek_read(buf, offset);
+    }
+}
+
+/// Writes the bytes of `buf` at `offset` in the file through a single positional write, and returns the number of bytes written.
+///
+/// The cursor of the file is not moved on Unix.
+#[cfg(any(unix, windows))]
+pub(crate) fn write_at(file: &std::fs::File, buf: &[u8], offset: u64) -> std::io::Result<usize> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileExt;
+        return file.write_at(buf, offset);
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::FileExt;
+        return file.seek_write(buf, offset);
+    }
+}
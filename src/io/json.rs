@@ -0,0 +1,98 @@
+//! Minimal JSON serialization used by the exporters (the crate does not depend on `serde`).
+
+use crate::{Attribute, DataType, DataVector};
+
+/// Returns the JSON string literal of `value`.
+pub(crate) fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    return json;
+}
+
+/// Returns the JSON number, or `null` for the NaN and infinite values (not representable in JSON).
+pub(crate) fn json_f64(value: f64) -> String {
+    if value.is_finite() { format!("{:?}", value) } else { String::from("null") }
+}
+
+pub(crate) fn json_f32(value: f32) -> String {
+    if value.is_finite() { format!("{:?}", value) } else { String::from("null") }
+}
+
+/// Returns the JSON array of the elements.
+pub(crate) fn json_array<T, F: Fn(&T) -> String>(values: &[T], to_json: F) -> String {
+    let values: Vec<String> = values.iter().map(to_json).collect();
+    return format!("[{}]", values.join(", "));
+}
+
+/// Returns the JSON value of an attribute : a string for the valid UTF-8 `u8` attributes, an array of numbers otherwise.
+pub(crate) fn json_attr_value(attr: &Attribute) -> String {
+    if let Some(value) = attr.get_as_string() {
+        return json_string(&value);
+    }
+    return match attr.data() {
+        DataVector::I8(data) => json_array(data, |value: &i8| value.to_string()),
+        DataVector::U8(data) => json_array(data, |value: &u8| value.to_string()),
+        DataVector::I16(data) => json_array(data, |value: &i16| value.to_string()),
+        DataVector::I32(data) => json_array(data, |value: &i32| value.to_string()),
+        DataVector::F32(data) => json_array(data, |value: &f32| json_f32(*value)),
+        DataVector::F64(data) => json_array(data, |value: &f64| json_f64(*value)),
+    };
+}
+
+/// Returns the JSON object `{"name": value, ...}` of the attributes, indented by `indent`.
+pub(crate) fn json_attrs(attrs: &[&Attribute], indent: &str) -> String {
+    if attrs.is_empty() {
+        return String::from("{}");
+    }
+    let members: Vec<String> = attrs.iter().map(|attr: &&Attribute| {
+        format!("{}  {}: {}", indent, json_string(attr.name()), json_attr_value(attr))
+    }).collect();
+    return format!("{{\n{}\n{}}}", members.join(",\n"), indent);
+}
+
+/// Returns the name of the primitive Rust type storing the elements of the data type (`"i8"`, `"u8"`, etc.).
+pub(crate) fn json_data_type(data_type: DataType) -> String {
+    let name: &str = match data_type {
+        DataType::I8 => "i8",
+        DataType::U8 => "u8",
+        DataType::I16 => "i16",
+        DataType::I32 => "i32",
+        DataType::F32 => "f32",
+        DataType::F64 => "f64",
+    };
+    return json_string(name);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{json_f64, json_string};
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!("\"abc\"",                   json_string("abc"));
+        assert_eq!("\"a\\\"b\\\\c\\nd\\u0001\"",  json_string("a\"b\\c\nd\u{1}"));
+        assert_eq!("\"café\"",                  json_string("café"));
+    }
+
+    #[test]
+    fn test_json_f64() {
+        assert_eq!("1.0",                       json_f64(1.0));
+        assert_eq!("-0.5",                      json_f64(-0.5));
+        assert_eq!("1e20",                      json_f64(1e20));
+        assert_eq!("null",                      json_f64(f64::NAN));
+        assert_eq!("null",                      json_f64(f64::INFINITY));
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use crate::{DataSet, Dimension, Variable, Version};
+use crate::io::json::{json_array, json_data_type, json_string};
+
+/// Returns the JSON manifest listing the byte ranges `[offset, length]` of the chunks of each variable.
+///
+/// The variables without byte ranges in `var_byte_ranges` are skipped.
+pub(crate) fn byte_ranges_manifest_json(data_set: &DataSet, version: Version, var_byte_ranges: &HashMap<String, Vec<(u64, usize)>>) -> String {
+    let version: &str = match version {
+        Version::Classic => "classic",
+        Version::Offset64Bit => "64-bit offset",
+    };
+    let num_records: String = data_set.num_records().map(|num_records: usize| num_records.to_string()).unwrap_or_else(|| String::from("null"));
+    let vars: Vec<String> = data_set.vars().iter().filter_map(|var: &Variable| {
+        let byte_ranges: &Vec<(u64, usize)> = var_byte_ranges.get(var.name())?;
+        let shape: Vec<usize> = var.dims().iter().map(|dim: &std::rc::Rc<Dimension>| dim.size()).collect();
+        let members: Vec<String> = vec![
+            format!("\"data_type\": {}", json_data_type(var.data_type())),
+            format!("\"shape\": {}", json_array(&shape, |size: &usize| size.to_string())),
+            format!("\"dim_names\": {}", json_array(&var.dim_names(), |dim_name: &String| json_string(dim_name))),
+            format!("\"chunks\": {}", json_array(byte_ranges, |(offset, length): &(u64, usize)| format!("[{}, {}]", offset, length))),
+        ];
+        Some(format!("    {}: {{\n      {}\n    }}", json_string(var.name()), members.join(",\n      ")))
+    }).collect();
+    let vars: String = if vars.is_empty() { String::from("{}") } else { format!("{{\n{}\n  }}", vars.join(",\n")) };
+    let members: Vec<String> = vec![
+        format!("\"version\": {}", json_string(version)),
+        format!("\"byte_order\": {}", json_string("big")),
+        format!("\"num_records\": {}", num_records),
+        format!("\"variables\": {}", vars),
+    ];
+    return format!("{{\n  {}\n}}\n", members.join(",\n  "));
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Cursor;
+
+    use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+
+    use crate::{FileReader, DataVector};
+
+    #[test]
+    fn test_var_byte_ranges() {
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        for var_name in file_reader.data_set().get_var_names() {
+            // the bytes of the ranges are the big-endian values of the variable
+            let data: DataVector = file_reader.read_var(&var_name).unwrap();
+            let expected_bytes: Vec<u8> = match data {
+                DataVector::I8(data) => data.iter().map(|value: &i8| *value as u8).collect(),
+                DataVector::U8(data) => data,
+                DataVector::I16(data) => data.iter().flat_map(|value: &i16| value.to_be_bytes().to_vec()).collect(),
+                DataVector::I32(data) => data.iter().flat_map(|value: &i32| value.to_be_bytes().to_vec()).collect(),
+                DataVector::F32(data) => data.iter().flat_map(|value: &f32| value.to_be_bytes().to_vec()).collect(),
+                DataVector::F64(data) => data.iter().flat_map(|value: &f64| value.to_be_bytes().to_vec()).collect(),
+            };
+            let bytes: Vec<u8> = file_reader.var_byte_ranges(&var_name).unwrap().into_iter().flat_map(|(offset, length): (u64, usize)| {
+                NC3_CLASSIC_FILE_BYTES[(offset as usize)..(offset as usize + length)].to_vec()
+            }).collect();
+            assert_eq!(expected_bytes,      bytes);
+        }
+    }
+
+    #[test]
+    fn test_byte_ranges_manifest() {
+        let file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        let manifest: String = file_reader.byte_ranges_manifest();
+        assert!(manifest.starts_with("{\n  \"version\": \"classic\",\n  \"byte_order\": \"big\",\n  \"num_records\": 2,\n"));
+        assert!(manifest.contains(concat!(
+            "    \"time\": {\n",
+            "      \"data_type\": \"f32\",\n",
+            "      \"shape\": [2],\n",
+            "      \"dim_names\": [\"time\"],\n",
+            "      \"chunks\": [[1716, 4], [2024, 4]]\n",
+            "    }",
+        )));
+    }
+}
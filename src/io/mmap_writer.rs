@@ -0,0 +1,256 @@
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::Path;
+
+use memmap2::MmapMut;
+
+/// Writes a file through a mutable memory map (requires the feature `mmap`).
+///
+/// The file is pre-sized to the given capacity and mapped once, the writes are then copied into the map without system call,
+/// which is faster than the file writes for many small records. The capacity is doubled when a write goes beyond it,
+/// and the file is truncated to the written bytes when the writer is flushed or dropped.
+///
+/// The file must not be modified by an other process while it is mapped.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{DataSet, FileReader, FileWriter, MmapWriter, Version};
+/// # use tempdir::TempDir;
+/// # let tmp_dir: TempDir = TempDir::new("netcdf3_tests_").unwrap();
+/// # let file_path = tmp_dir.path().join("mmap.nc");
+///
+/// let mut data_set = DataSet::new();
+/// data_set.set_unlimited_dim("time", 3).unwrap();
+/// data_set.add_var_i16("var_1", &["time"]).unwrap();
+///
+/// // the file is pre-sized for the data set
+/// let mut file_writer: FileWriter<MmapWriter> = FileWriter::create_mmap(&file_path, &data_set, Version::Classic, 0).unwrap();
+/// for record_index in 0..3 {
+///     file_writer.write_record_i16("var_1", record_index, &[record_index as i16]).unwrap();
+/// }
+/// file_writer.close().unwrap();
+///
+/// let mut file_reader = FileReader::open(&file_path).unwrap();
+/// assert_eq!(vec![0, 1, 2],       file_reader.read_var_i16("var_1").unwrap());
+/// ```
+#[derive(Debug)]
+pub struct MmapWriter {
+    file: File,
+    /// Map of the `capacity` first bytes of the file (`None` if the capacity is zero)
+    mmap: Option<MmapMut>,
+    capacity: u64,
+    /// End of the furthest write
+    len: u64,
+    pos: u64,
+}
+
+impl MmapWriter {
+
+    /// Creates or overwrites the file, and pre-sizes it to `capacity` bytes.
+    pub fn create<P: AsRef<Path>>(file_path: P, capacity: u64) -> std::io::Result<Self> {
+        // the file has to be readable to be mapped
+        let file: File = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(file_path)?;
+        let mut mmap_writer = MmapWriter{
+            file: file,
+            mmap: None,
+            capacity: 0,
+            len: 0,
+            pos: 0,
+        };
+        mmap_writer.resize(capacity)?;
+        return Ok(mmap_writer);
+    }
+
+    /// Returns the number of bytes currently allocated for the file.
+    pub fn capacity(&self) -> u64 {
+        return self.capacity;
+    }
+
+    /// Returns the number of bytes written, the file being truncated to this size when the writer is flushed.
+    pub fn len(&self) -> u64 {
+        return self.len;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.len == 0;
+    }
+
+    /// Allocates at least `capacity` bytes for the file, the capacity is never reduced.
+    pub fn reserve(&mut self, capacity: u64) -> std::io::Result<()> {
+        if capacity > self.capacity {
+            self.resize(capacity)?;
+        }
+        return Ok(());
+    }
+
+    /// Flushes and unmaps the file, and maps it again with the new size.
+    fn resize(&mut self, capacity: u64) -> std::io::Result<()> {
+        if let Some(mmap) = self.mmap.take() {
+            mmap.flush()?;
+        }
+        self.file.set_len(capacity)?;
+        self.capacity = capacity;
+        if capacity > 0 {
+            let map_len: usize = usize::try_from(capacity).map_err(|_err| {
+                std::io::Error::new(std::io::ErrorKind::InvalidInput, "the file cannot be mapped into memory")
+            })?;
+            // SAFETY: the file is owned by the writer and is not modified otherwise while it is mapped
+            let mmap: MmapMut = unsafe { memmap2::MmapOptions::new().len(map_len).map_mut(&self.file)? };
+            self.mmap = Some(mmap);
+        }
+        return Ok(());
+    }
+}
+
+impl Write for MmapWriter {
+
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let end: u64 = self.pos.checked_add(buf.len() as u64).ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "the position of the write overflows")
+        })?;
+        if end > self.capacity {
+            self.reserve(std::cmp::max(end, self.capacity.saturating_mul(2)))?;
+        }
+        let mmap: &mut MmapMut = self.mmap.as_mut().ok_or_else(|| std::io::Error::from(std::io::ErrorKind::Other))?;
+        let start: usize = self.pos as usize;
+        mmap[start..(start + buf.len())].copy_from_slice(buf);
+        self.pos = end;
+        self.len = std::cmp::max(self.len, end);
+        return Ok(buf.len());
+    }
+
+    /// Flushes the map and truncates the file to the written bytes.
+    fn flush(&mut self) -> std::io::Result<()> {
+        if let Some(mmap) = self.mmap.as_ref() {
+            mmap.flush()?;
+        }
+        if self.capacity != self.len {
+            self.resize(self.len)?;
+        }
+        return Ok(());
+    }
+}
+
+impl Seek for MmapWriter {
+
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let new_pos: i128 = match pos {
+            SeekFrom::Start(offset) => offset as i128,
+            SeekFrom::End(offset) => self.len as i128 + offset as i128,
+            SeekFrom::Current(offset) => self.pos as i128 + offset as i128,
+        };
+        if new_pos < 0 {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, "invalid seek to a negative position"));
+        }
+        self.pos = new_pos as u64;
+        return Ok(self.pos);
+    }
+}
+
+impl Drop for MmapWriter {
+
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+#[cfg(all(test, feature = "read"))]
+mod tests {
+
+    use std::io::{Seek, SeekFrom, Write};
+    use std::path::PathBuf;
+
+    use tempdir::TempDir;
+
+    use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+
+    use super::MmapWriter;
+    use crate::{FileReader, FileWriter, DataSet, DataVector, Version};
+
+    const TMP_DIR_PREFIX: &str = "netcdf3_tests_";
+
+    #[test]
+    fn test_mmap_writer_write_seek() {
+        let tmp_dir: TempDir = TempDir::new(TMP_DIR_PREFIX).unwrap();
+        let file_path: PathBuf = tmp_dir.path().join("mmap_writer.bin");
+
+        let mut mmap_writer = MmapWriter::create(&file_path, 4).unwrap();
+        assert_eq!(4,                           mmap_writer.capacity());
+        assert_eq!(4,                           std::fs::metadata(&file_path).unwrap().len());
+        assert_eq!(true,                        mmap_writer.is_empty());
+
+        mmap_writer.write_all(&[1, 2, 3]).unwrap();
+        assert_eq!(3,                           mmap_writer.len());
+        // the capacity is doubled
+        mmap_writer.seek(SeekFrom::Start(6)).unwrap();
+        mmap_writer.write_all(&[7]).unwrap();
+        assert_eq!(8,                           mmap_writer.capacity());
+        assert_eq!(7,                           mmap_writer.len());
+        // or grown to the end of the write
+        mmap_writer.seek(SeekFrom::End(1)).unwrap();
+        mmap_writer.write_all(&[9; 12]).unwrap();
+        assert_eq!(20,                          mmap_writer.capacity());
+        assert_eq!(20,                          mmap_writer.len());
+        mmap_writer.seek(SeekFrom::Current(-20)).unwrap();
+        mmap_writer.write_all(&[0]).unwrap();
+        assert_eq!(true,                        mmap_writer.seek(SeekFrom::Current(-2)).is_err());
+
+        // the file is truncated to the written bytes
+        mmap_writer.reserve(100).unwrap();
+        assert_eq!(100,                         std::fs::metadata(&file_path).unwrap().len());
+        mmap_writer.flush().unwrap();
+        let mut expected_bytes: Vec<u8> = vec![0, 2, 3, 0, 0, 0, 7, 0];
+        expected_bytes.extend_from_slice(&[9; 12]);
+        assert_eq!(expected_bytes,              std::fs::read(&file_path).unwrap());
+
+        // and when the writer is dropped
+        mmap_writer.reserve(100).unwrap();
+        drop(mmap_writer);
+        assert_eq!(expected_bytes,              std::fs::read(&file_path).unwrap());
+
+        tmp_dir.close().unwrap();
+    }
+
+    #[test]
+    fn test_file_writer_create_mmap() {
+        let tmp_dir: TempDir = TempDir::new(TMP_DIR_PREFIX).unwrap();
+        let file_path: PathBuf = tmp_dir.path().join("mmap_writer.nc");
+
+        let mut input_reader = FileReader::from_reader(std::io::Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        let vars_data: Vec<(String, DataVector)> = input_reader.data_set().get_var_names().into_iter().map(|var_name: String| {
+            let data: DataVector = input_reader.read_var(&var_name).unwrap();
+            (var_name, data)
+        }).collect();
+        let (data_set, _version): (DataSet, Version) = input_reader.close();
+
+        let mut file_writer: FileWriter<MmapWriter> = FileWriter::create_mmap(&file_path, &data_set, Version::Classic, 0).unwrap();
+        assert_eq!(Some(file_path.as_path()),                   file_writer.file_path());
+        assert_eq!(true,                                        file_writer.header_is_defined());
+        // the file is pre-sized
+        assert_eq!(NC3_CLASSIC_FILE_BYTES.len() as u64,         std::fs::metadata(&file_path).unwrap().len());
+        for (var_name, data) in vars_data.iter() {
+            match data {
+                DataVector::I8(data) => file_writer.write_var_i8(var_name, data),
+                DataVector::U8(data) => file_writer.write_var_u8(var_name, data),
+                DataVector::I16(data) => file_writer.write_var_i16(var_name, data),
+                DataVector::I32(data) => file_writer.write_var_i32(var_name, data),
+                DataVector::F32(data) => file_writer.write_var_f32(var_name, data),
+                DataVector::F64(data) => file_writer.write_var_f64(var_name, data),
+            }.unwrap();
+        }
+        file_writer.close().unwrap();
+        assert_eq!(NC3_CLASSIC_FILE_BYTES,                      &std::fs::read(&file_path).unwrap()[..]);
+
+        tmp_dir.close().unwrap();
+    }
+}
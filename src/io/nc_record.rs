@@ -0,0 +1,95 @@
+use std::io::{Read, Seek, Write};
+
+use crate::{DataSet, DataType, FileReader, FileWriter, InvalidDataSet};
+use crate::error::{ReadError, WriteError};
+
+/// Struct whose fields are stored in record variables, one value of each field per record.
+///
+/// The trait is implemented by the derive macro `#[derive(NcRecord)]` (requires the feature `derive`),
+/// each field being mapped to the record variable having its name (or the name given by `#[nc(rename = "...")]`).
+/// The type of each field must implement [`NcValue`](trait.NcValue.html).
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # {
+/// use std::io::Cursor;
+/// use netcdf3::{DataSet, FileReader, FileWriter, NcRecord, Version};
+///
+/// #[derive(Debug, PartialEq, NcRecord)]
+/// struct Telemetry {
+///     time: f64,
+///     #[nc(rename = "battery_voltage")]
+///     voltage: f32,
+///     status: i8,
+/// }
+///
+/// let records = vec![
+///     Telemetry{time: 0.0, voltage: 12.5, status: 0},
+///     Telemetry{time: 1.0, voltage: 12.4, status: 1},
+/// ];
+///
+/// let mut data_set = DataSet::new();
+/// data_set.set_unlimited_dim("time", records.len()).unwrap();
+/// Telemetry::add_vars(&mut data_set, "time").unwrap();
+/// assert_eq!(vec!["time", "battery_voltage", "status"],     data_set.get_var_names());
+///
+/// let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+/// file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+/// for (record_index, record) in records.iter().enumerate() {
+///     record.write_record(&mut file_writer, record_index).unwrap();
+/// }
+///
+/// let mut file_reader = FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+/// assert_eq!(vec![12.5, 12.4],                            file_reader.read_var_f32("battery_voltage").unwrap());
+/// assert_eq!(records[1],                                  Telemetry::read_record(&mut file_reader, 1).unwrap());
+/// # }
+/// ```
+pub trait NcRecord: Sized {
+    /// Adds to `data_set` the record variables of the fields, defined over the *unlimited-size* dimension `unlim_dim_name`.
+    fn add_vars(data_set: &mut DataSet, unlim_dim_name: &str) -> Result<(), InvalidDataSet>;
+
+    /// Writes the fields into the record `record_index` of their variables.
+    fn write_record<W: Write + Seek>(&self, file_writer: &mut FileWriter<'_, W>, record_index: usize) -> Result<(), WriteError>;
+
+    /// Reads the fields from the record `record_index` of their variables.
+    fn read_record<R: Read + Seek>(file_reader: &mut FileReader<R>, record_index: usize) -> Result<Self, ReadError>;
+}
+
+/// Value of a field of a [`NcRecord`](trait.NcRecord.html), stored as one element of each record of a variable.
+pub trait NcValue: Sized {
+    /// Data type of the record variable.
+    const DATA_TYPE: DataType;
+
+    /// Writes the value into the record `record_index` of the variable.
+    fn write_record<W: Write + Seek>(&self, file_writer: &mut FileWriter<'_, W>, var_name: &str, record_index: usize) -> Result<(), WriteError>;
+
+    /// Reads the value from the record `record_index` of the variable.
+    fn read_record<R: Read + Seek>(file_reader: &mut FileReader<R>, var_name: &str, record_index: usize) -> Result<Self, ReadError>;
+}
+
+macro_rules! impl_nc_value {
+    ($prim_type:ty, $data_type:path, $write_record:ident, $read_record:ident) => {
+        impl NcValue for $prim_type {
+            const DATA_TYPE: DataType = $data_type;
+
+            fn write_record<W: Write + Seek>(&self, file_writer: &mut FileWriter<'_, W>, var_name: &str, record_index: usize) -> Result<(), WriteError> {
+                return file_writer.$write_record(var_name, record_index, &[*self]);
+            }
+
+            fn read_record<R: Read + Seek>(file_reader: &mut FileReader<R>, var_name: &str, record_index: usize) -> Result<Self, ReadError> {
+                let record: Vec<$prim_type> = file_reader.$read_record(var_name, record_index)?;
+                // the record of a variable defined only over the unlimited-size dimension has one element
+                return record.first().copied().ok_or(ReadError::Unexpected);
+            }
+        }
+    };
+}
+
+impl_nc_value!(i8, DataType::I8, write_record_i8, read_record_i8);
+impl_nc_value!(u8, DataType::U8, write_record_u8, read_record_u8);
+impl_nc_value!(i16, DataType::I16, write_record_i16, read_record_i16);
+impl_nc_value!(i32, DataType::I32, write_record_i32, read_record_i32);
+impl_nc_value!(f32, DataType::F32, write_record_f32, read_record_f32);
+impl_nc_value!(f64, DataType::F64, write_record_f64, read_record_f64);
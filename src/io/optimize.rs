@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+use std::io::{Read, Seek, Write};
+#[cfg(feature = "fs")]
+use std::path::{Path, PathBuf};
+
+use crate::{FileReader, Variable, Version};
+use crate::error::CopyError;
+use crate::io::copy::{copy_data_set, select_vars};
+
+/// Options of the function [`optimize_nc3`](fn.optimize_nc3.html).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct OptimizeOptions {
+    /// Names of the *fixed-size* variables the most often read, their data are stored first and in this order.
+    ///
+    /// The other *fixed-size* variables follow in the order of the input file, the record variables are not reordered.
+    pub var_priority: Vec<String>,
+    /// Alignment (number of bytes) of the beginning of the data part of the output file, no alignment if `0`.
+    pub data_alignment: usize,
+    /// Version of the output file, the version of the input file is kept if `None`.
+    pub version: Option<Version>,
+}
+
+/// Rewrites a NetCDF-3 file into `output` with a layout tuned for reading.
+///
+/// The data of the *fixed-size* variables listed in [`OptimizeOptions::var_priority`](struct.OptimizeOptions.html#structfield.var_priority)
+/// are stored first, the beginning of the data part is aligned, and the unused bytes reserved after the header are removed.
+/// The data are streamed like [`copy_nc3`](fn.copy_nc3.html) does, the underlying output is returned once the rewrite is done.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{FileReader, optimize_nc3, OptimizeOptions};
+/// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+///
+/// let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+/// assert_eq!(vec!["latitude", "longitude", "time", "temperature_i8"],     file_reader.data_set().get_var_names()[0..4].to_vec());
+///
+/// let options = OptimizeOptions{
+///     var_priority: vec![String::from("longitude")],
+///     data_alignment: 4096,
+///     ..OptimizeOptions::default()
+/// };
+/// let output: Cursor<Vec<u8>> = optimize_nc3(&mut file_reader, Cursor::new(vec![]), &options).unwrap();
+///
+/// let mut optimized_reader = FileReader::from_reader(output).unwrap();
+/// assert_eq!(vec!["longitude", "latitude", "time", "temperature_i8"],     optimized_reader.data_set().get_var_names()[0..4].to_vec());
+/// assert_eq!(Some(vec![(4096, 20)]),                                      optimized_reader.var_byte_ranges("longitude"));
+/// assert_eq!(Some(vec![(4116, 12)]),                                      optimized_reader.var_byte_ranges("latitude"));
+/// assert_eq!(file_reader.read_all_vars().unwrap(),                        optimized_reader.read_all_vars().unwrap());
+/// ```
+pub fn optimize_nc3<R: Read + Seek, W: Write + Seek>(file_reader: &mut FileReader<R>, output: W, options: &OptimizeOptions) -> Result<W, CopyError> {
+    let input_var_names: Vec<String> = file_reader.data_set().get_var_names();
+    let mut var_names: Vec<String> = Vec::with_capacity(input_var_names.len());
+    for var_name in options.var_priority.iter() {
+        let var: &Variable = file_reader.data_set().get_var(var_name).ok_or_else(|| CopyError::VariableNotDefined(var_name.to_string()))?;
+        if !var.is_record_var() && !var_names.contains(var_name) {
+            var_names.push(var_name.to_string());
+        }
+    }
+    // the other *fixed-size* variables, then the record variables
+    for is_record_var in [false, true].iter() {
+        for var in file_reader.data_set().vars().iter().filter(|var: &&Variable| var.is_record_var() == *is_record_var) {
+            if !var_names.iter().any(|var_name: &String| var_name == var.name()) {
+                var_names.push(var.name().to_string());
+            }
+        }
+    }
+
+    let dim_ranges: HashMap<String, (usize, usize)> = HashMap::new();
+    let data_set = select_vars(file_reader.data_set(), &var_names, &dim_ranges)?;
+    let version: Version = options.version.unwrap_or_else(|| file_reader.version());
+    return copy_data_set(file_reader, output, &data_set, version, 0, options.data_alignment, &dim_ranges);
+}
+
+/// Rewrites a NetCDF-3 file in place with a layout tuned for reading (see [`optimize_nc3`](fn.optimize_nc3.html)).
+///
+/// The file is rewritten into a temporary file of the same directory (named after the file, with the suffix `.optimize.tmp`),
+/// which replaces the original file once complete.
+#[cfg(feature = "fs")]
+pub fn optimize_nc3_file<P: AsRef<Path>>(file_path: P, options: &OptimizeOptions) -> Result<(), CopyError> {
+    let file_path: &Path = file_path.as_ref();
+    let tmp_file_path: PathBuf = {
+        let mut file_name = file_path.file_name().unwrap_or_default().to_os_string();
+        file_name.push(".optimize.tmp");
+        file_path.with_file_name(file_name)
+    };
+    let result: Result<(), CopyError> = (|| -> Result<(), CopyError> {
+        let mut file_reader = FileReader::open(file_path)?;
+        let tmp_file = std::io::BufWriter::new(std::fs::File::create(&tmp_file_path).map_err(crate::error::WriteError::from)?);
+        let tmp_file: std::fs::File = optimize_nc3(&mut file_reader, tmp_file, options)?
+            .into_inner()
+            .map_err(|err: std::io::IntoInnerError<_>| crate::error::WriteError::from(err.into_error()))?;
+        tmp_file.sync_all().map_err(crate::error::WriteError::from)?;
+        // the input file is closed before being replaced
+        drop(file_reader);
+        std::fs::rename(&tmp_file_path, file_path).map_err(crate::error::WriteError::from)?;
+        return Ok(());
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp_file_path);
+    }
+    return result;
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Cursor;
+
+    use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+
+    use super::{optimize_nc3, OptimizeOptions};
+    use crate::FileReader;
+    use crate::error::CopyError;
+
+    #[test]
+    fn test_optimize_nc3_header_padding() {
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        // reserve bytes after the header
+        let padded_bytes: Vec<u8> = crate::copy_nc3(&mut file_reader, Cursor::new(vec![]), &crate::CopyOptions{header_min_size: 10_000, ..Default::default()}).unwrap().into_inner();
+        let mut padded_reader = FileReader::from_reader(Cursor::new(&padded_bytes[..])).unwrap();
+        assert_eq!(Some(vec![(10_000, 12)]),        padded_reader.var_byte_ranges("latitude"));
+
+        // the reserved bytes are removed, and the file is identical to the original one
+        let output: Vec<u8> = optimize_nc3(&mut padded_reader, Cursor::new(vec![]), &OptimizeOptions::default()).unwrap().into_inner();
+        assert_eq!(NC3_CLASSIC_FILE_BYTES.to_vec(),     output);
+    }
+
+    #[test]
+    fn test_optimize_nc3_var_priority() {
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        // the record variables and the duplicated names are ignored
+        let options = OptimizeOptions{
+            var_priority: vec![String::from("temperature_f32"), String::from("longitude"), String::from("longitude")],
+            ..OptimizeOptions::default()
+        };
+        let output = optimize_nc3(&mut file_reader, Cursor::new(vec![]), &options).unwrap();
+
+        let mut optimized_reader = FileReader::from_reader(output).unwrap();
+        let var_names: Vec<String> = optimized_reader.data_set().get_var_names();
+        assert_eq!(vec!["longitude", "latitude", "time"],       var_names[0..3].to_vec());
+        assert_eq!(file_reader.data_set().num_vars(),           var_names.len());
+        assert_eq!(file_reader.read_all_vars().unwrap(),        optimized_reader.read_all_vars().unwrap());
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_optimize_nc3_file() {
+        use copy_to_tmp_file::{copy_bytes_to_tmp_file, NC3_CLASSIC_FILE_NAME};
+        use super::optimize_nc3_file;
+
+        let (tmp_dir, file_path) = copy_bytes_to_tmp_file(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+        let options = OptimizeOptions{var_priority: vec![String::from("longitude")], ..OptimizeOptions::default()};
+        optimize_nc3_file(&file_path, &options).unwrap();
+
+        let mut file_reader = FileReader::open(&file_path).unwrap();
+        let mut expected_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        assert_eq!(vec!["longitude", "latitude"],               file_reader.data_set().get_var_names()[0..2].to_vec());
+        assert_eq!(expected_reader.read_all_vars().unwrap(),    file_reader.read_all_vars().unwrap());
+        // the temporary file has been renamed
+        assert_eq!(1,                                           std::fs::read_dir(tmp_dir.path()).unwrap().count());
+    }
+
+    #[test]
+    fn test_optimize_nc3_undefined_var() {
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        let options = OptimizeOptions{var_priority: vec![String::from("undef_var")], ..OptimizeOptions::default()};
+        assert_eq!(
+            CopyError::VariableNotDefined(String::from("undef_var")),
+            optimize_nc3(&mut file_reader, Cursor::new(vec![]), &options).unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use std::io::Write;
+
+use crate::{DataSet, DataVector, Dimension, Variable};
+use crate::io::json::{json_array, json_attrs, json_data_type, json_string};
+
+/// Writes the elements of `data` contiguously, in the native byte order and without any header.
+///
+/// The written bytes can be loaded directly into a typed buffer (GPU buffers, `numpy.fromfile`, etc.)
+/// using the informations of the sidecar (see [`raw_sidecar_json`](fn.raw_sidecar_json.html)).
+pub fn write_raw_data<W: Write>(data: &DataVector, output: &mut W) -> std::io::Result<()> {
+    macro_rules! write_values {
+        ($data:expr) => {
+            for value in $data.iter() {
+                output.write_all(&value.to_ne_bytes())?;
+            }
+        };
+    }
+    match data {
+        DataVector::I8(data) => write_values!(data),
+        DataVector::U8(data) => output.write_all(data)?,
+        DataVector::I16(data) => write_values!(data),
+        DataVector::I32(data) => write_values!(data),
+        DataVector::F32(data) => write_values!(data),
+        DataVector::F64(data) => write_values!(data),
+    }
+    return Ok(());
+}
+
+/// Returns the JSON sidecar describing the raw data of a variable (see [`write_raw_data`](fn.write_raw_data.html)).
+///
+/// Returns `None` if the variable is not defined.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{DataSet, raw_sidecar_json};
+///
+/// let mut data_set = DataSet::new();
+/// data_set.set_unlimited_dim("time", 2).unwrap();
+/// data_set.add_fixed_dim("lat", 3).unwrap();
+/// data_set.add_var_f32("temperature", &["time", "lat"]).unwrap();
+/// data_set.add_var_attr_string("temperature", "units", "K").unwrap();
+///
+/// let sidecar: String = raw_sidecar_json(&data_set, "temperature").unwrap();
+/// let byte_order: &str = if cfg!(target_endian = "little") { "little" } else { "big" };
+/// assert_eq!(
+///     format!(concat!(
+///         "{{\n",
+///         "  \"name\": \"temperature\",\n",
+///         "  \"data_type\": \"f32\",\n",
+///         "  \"byte_order\": \"{}\",\n",
+///         "  \"shape\": [2, 3],\n",
+///         "  \"dim_names\": [\"time\", \"lat\"],\n",
+///         "  \"data_file\": \"temperature.bin\",\n",
+///         "  \"attributes\": {{\n",
+///         "    \"units\": \"K\"\n",
+///         "  }}\n",
+///         "}}\n",
+///     ), byte_order),
+///     sidecar
+/// );
+/// ```
+pub fn raw_sidecar_json(data_set: &DataSet, var_name: &str) -> Option<String> {
+    let var: &Variable = data_set.get_var(var_name)?;
+    let shape: Vec<usize> = var.dims().iter().map(|dim: &std::rc::Rc<Dimension>| dim.size()).collect();
+    let byte_order: &str = if cfg!(target_endian = "little") { "little" } else { "big" };
+    let members: Vec<String> = vec![
+        format!("\"name\": {}", json_string(var.name())),
+        format!("\"data_type\": {}", json_data_type(var.data_type())),
+        format!("\"byte_order\": {}", json_string(byte_order)),
+        format!("\"shape\": {}", json_array(&shape, |size: &usize| size.to_string())),
+        format!("\"dim_names\": {}", json_array(&var.dim_names(), |dim_name: &String| json_string(dim_name))),
+        format!("\"data_file\": {}", json_string(&raw_data_file_name(var_name))),
+        format!("\"attributes\": {}", json_attrs(&var.get_attrs(), "  ")),
+    ];
+    return Some(format!("{{\n  {}\n}}\n", members.join(",\n  ")));
+}
+
+/// Name of the JSON file containing the global attributes.
+///
+/// The `%` of the escaped variable names is always followed by 2 hexadecimal digits, this name cannot collide with the files of the variables.
+#[cfg(feature = "fs")]
+#[cfg(feature = "read")]
+pub(crate) const GLOBAL_ATTRS_FILE_NAME: &str = "%global_attributes.json";
+
+/// Escapes a variable name into a file name.
+///
+/// The bytes which are not allowed or special in the file names (`/`, `\`, `%`, the control characters, the characters reserved on Windows)
+/// and a leading `.` (`.`, `..`, hidden files) are replaced by `%XX`, `XX` being the hexadecimal value of the byte.
+pub(crate) fn escape_file_name(var_name: &str) -> String {
+    let mut file_name = String::with_capacity(var_name.len());
+    for (i, c) in var_name.char_indices() {
+        let escaped: bool = c.is_control() || "/\\%:*?\"<>|".contains(c) || (i == 0 && c == '.');
+        if escaped {
+            let mut buffer: [u8; 4] = [0; 4];
+            for byte in c.encode_utf8(&mut buffer).bytes() {
+                file_name.push_str(&format!("%{:02X}", byte));
+            }
+        } else {
+            file_name.push(c);
+        }
+    }
+    return file_name;
+}
+
+/// Name of the raw data file of a variable.
+pub(crate) fn raw_data_file_name(var_name: &str) -> String {
+    return format!("{}.bin", escape_file_name(var_name));
+}
+
+/// Name of the JSON sidecar of a variable.
+#[cfg(feature = "fs")]
+#[cfg(feature = "read")]
+pub(crate) fn raw_sidecar_file_name(var_name: &str) -> String {
+    return format!("{}.json", escape_file_name(var_name));
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{escape_file_name, write_raw_data};
+    use crate::DataVector;
+
+    #[test]
+    fn test_write_raw_data() {
+        let mut bytes: Vec<u8> = vec![];
+        write_raw_data(&DataVector::I16(vec![1, -2]), &mut bytes).unwrap();
+        let mut expected_bytes: Vec<u8> = 1_i16.to_ne_bytes().to_vec();
+        expected_bytes.extend_from_slice(&(-2_i16).to_ne_bytes());
+        assert_eq!(expected_bytes,                          bytes);
+
+        let mut bytes: Vec<u8> = vec![];
+        write_raw_data(&DataVector::U8(b"abc".to_vec()), &mut bytes).unwrap();
+        assert_eq!(b"abc".to_vec(),                         bytes);
+
+        let mut bytes: Vec<u8> = vec![];
+        write_raw_data(&DataVector::F64(vec![]), &mut bytes).unwrap();
+        assert_eq!(0,                                       bytes.len());
+    }
+
+    #[test]
+    fn test_escape_file_name() {
+        assert_eq!("temperature",                           escape_file_name("temperature"));
+        assert_eq!("global_attributes",                     escape_file_name("global_attributes"));
+        assert_eq!("air.temp-2m_max",                       escape_file_name("air.temp-2m_max"));
+        assert_eq!("température",                           escape_file_name("température"));
+        assert_eq!("a%2Fb",                                 escape_file_name("a/b"));
+        assert_eq!("%2E.%2Fetc%2Fpasswd",                   escape_file_name("../etc/passwd"));
+        assert_eq!("%2E.",                                  escape_file_name(".."));
+        assert_eq!("a%5Cb%3A%25",                           escape_file_name("a\\b:%"));
+        assert_eq!("line%0Abreak%7F",                       escape_file_name("line\nbreak\u{7f}"));
+    }
+}
@@ -0,0 +1,444 @@
+use std::io::{Read, Seek, SeekFrom, Write};
+#[cfg(feature = "fs")]
+use std::path::Path;
+
+use crate::{Attribute, DataSet, DataType, DataVector, FileReader, ReadOptions, Variable};
+use crate::{NC_FILL_I8, NC_FILL_U8, NC_FILL_I16, NC_FILL_I32, NC_FILL_F32, NC_FILL_F64};
+use crate::error::RepairError;
+
+/// Name of the attribute storing the fill value of a variable.
+const FILL_VALUE_ATTR_NAME: &str = "_FillValue";
+/// Offset of the number of records in the header.
+const NUM_RECORDS_OFFSET: u64 = 4;
+/// Value of the number of records in the header when it is not determined (*streaming* files).
+const INDETERMINATE_NUM_RECORDS: u32 = u32::MAX;
+
+/// Summary of the repair of a file (see [`repair_nc3`](fn.repair_nc3.html)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepairReport {
+    /// Number of records stored in the header before the repair (`None` if it was not determined).
+    pub header_num_records: Option<usize>,
+    /// Number of records stored in the header after the repair.
+    pub num_records: usize,
+    /// Number of bytes appended to complete the last record.
+    pub num_filled_bytes: usize,
+}
+
+impl RepairReport {
+
+    /// Returns `true` if the file has been modified.
+    pub fn is_modified(&self) -> bool {
+        return self.header_num_records != Some(self.num_records) || self.num_filled_bytes > 0;
+    }
+}
+
+/// Repairs a NetCDF-3 file truncated in the middle of the record data (an interrupted acquisition for example).
+///
+/// The number of records is recomputed from the size of the file, the unwritten variables of the partial trailing record are completed
+/// with their fill values (the attribute `_FillValue` of each variable, or the default fill value of its data type),
+/// and the number of records is rewritten in the header. The other bytes of the file are not modified.
+///
+/// The *fixed-size* variables data have to be complete, otherwise the error
+/// [`RepairError::FixedSizeDataTruncated`](enum.RepairError.html#variant.FixedSizeDataTruncated) is returned.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{FileReader, repair_nc3, RepairReport};
+/// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+///
+/// // the second record (308 bytes) has been partially written
+/// let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+/// bytes.truncate(bytes.len() - 100);
+/// let mut file = Cursor::new(bytes);
+///
+/// let report: RepairReport = repair_nc3(&mut file).unwrap();
+/// assert_eq!(Some(2),         report.header_num_records);
+/// assert_eq!(2,               report.num_records);
+/// assert_eq!(100,             report.num_filled_bytes);
+///
+/// let mut file_reader = FileReader::from_reader(file).unwrap();
+/// assert_eq!(vec![438300.0, 438324.0],        file_reader.read_var_f32("time").unwrap());
+/// ```
+pub fn repair_nc3<F: Read + Write + Seek>(file: &mut F) -> Result<RepairReport, RepairError> {
+    return RepairPlan::new(file)?.apply(file);
+}
+
+/// Repair of a file computed from its header and its size, without modifying it (see [`repair_nc3`](fn.repair_nc3.html)).
+#[derive(Debug)]
+pub(crate) struct RepairPlan {
+    /// Number of records stored in the header (`None` if it is not determined)
+    header_num_records: Option<usize>,
+    /// Number of records computed from the size of the file
+    num_records: usize,
+    /// Bytes to append to complete the last record
+    filled_bytes: Vec<u8>,
+}
+
+impl RepairPlan {
+
+    /// Computes the repair of the file, which is only read.
+    pub(crate) fn new<F: Read + Seek>(file: &mut F) -> Result<RepairPlan, RepairError> {
+        let file_size: u64 = file.seek(SeekFrom::End(0))?;
+        let header_num_records: Option<usize> = {
+            let mut bytes: [u8; 4] = [0; 4];
+            file.seek(SeekFrom::Start(NUM_RECORDS_OFFSET))?;
+            file.read_exact(&mut bytes)?;
+            match u32::from_be_bytes(bytes) {
+                INDETERMINATE_NUM_RECORDS => None,
+                num_records => Some(num_records as usize),
+            }
+        };
+
+        // Parse the header ignoring its number of records, and compute the number of records from the size of the file
+        let (num_records, filled_bytes): (usize, Vec<u8>) = {
+            let file_reader = FileReader::parse_from_reader(&mut *file, Some(0), &ReadOptions::default())?;
+            let data_set: &DataSet = file_reader.data_set();
+            let fixed_size_data_end: u64 = data_set.vars().iter()
+                .filter(|var: &&Variable| !var.is_record_var())
+                .filter_map(|var: &Variable| file_reader.var_byte_ranges(var.name()))
+                .flatten()
+                .map(|(offset, length): (u64, usize)| offset + length as u64)
+                .max()
+                .unwrap_or(0);
+            let records_begin: Option<u64> = data_set.vars().iter()
+                .filter(|var: &&Variable| var.is_record_var())
+                .filter_map(|var: &Variable| file_reader.var_begin_offset(var.name()))
+                .min();
+            let required_size: u64 = std::cmp::max(fixed_size_data_end, records_begin.unwrap_or(0));
+            if file_size < required_size {
+                return Err(RepairError::FixedSizeDataTruncated{required_size: required_size, file_size: file_size});
+            }
+            match (records_begin, data_set.record_size()) {
+                (Some(records_begin), Some(record_size)) if record_size > 0 => {
+                    let records_size: u64 = file_size - records_begin;
+                    let record_size: u64 = record_size as u64;
+                    let num_records: u64 = records_size.div_ceil(record_size);
+                    let records_end: u64 = records_begin + num_records * record_size;
+                    let mut filled_bytes: Vec<u8> = vec![0; (records_end - file_size) as usize];
+                    if !filled_bytes.is_empty() {
+                        // Fill the missing bytes of each record variable in the last record
+                        let last_record_offset: u64 = (num_records - 1) * record_size;
+                        for var in data_set.vars().iter().filter(|var: &&Variable| var.is_record_var()) {
+                            let var_begin: u64 = match file_reader.var_begin_offset(var.name()) {
+                                None => continue,
+                                Some(var_begin) => var_begin + last_record_offset,
+                            };
+                            let chunk_size: u64 = if data_set.records_are_packed() { record_size } else { var.chunk_size() as u64 };
+                            let fill_bytes: Vec<u8> = var_fill_bytes(var);
+                            for position in std::cmp::max(var_begin, file_size)..std::cmp::min(var_begin + chunk_size, records_end) {
+                                filled_bytes[(position - file_size) as usize] = fill_bytes[((position - var_begin) % fill_bytes.len() as u64) as usize];
+                            }
+                        }
+                    }
+                    (num_records as usize, filled_bytes)
+                },
+                // no record variable, the number of records is kept
+                _ => (header_num_records.unwrap_or(0), vec![]),
+            }
+        };
+        if num_records > (i32::MAX as usize) {
+            return Err(RepairError::NumberOfRecordsExceeded(num_records));
+        }
+        return Ok(RepairPlan{
+            header_num_records: header_num_records,
+            num_records: num_records,
+            filled_bytes: filled_bytes,
+        });
+    }
+
+    /// Returns the number of records of the file once repaired.
+    #[cfg(all(feature = "fs", feature = "write"))]
+    pub(crate) fn num_records(&self) -> usize {
+        return self.num_records;
+    }
+
+    /// Rewrites the number of records of the header and completes the last record.
+    pub(crate) fn apply<F: Write + Seek>(self, file: &mut F) -> Result<RepairReport, RepairError> {
+        if self.header_num_records != Some(self.num_records) {
+            file.seek(SeekFrom::Start(NUM_RECORDS_OFFSET))?;
+            file.write_all(&(self.num_records as u32).to_be_bytes())?;
+        }
+        if !self.filled_bytes.is_empty() {
+            file.seek(SeekFrom::End(0))?;
+            file.write_all(&self.filled_bytes)?;
+        }
+        file.flush()?;
+        return Ok(RepairReport{
+            header_num_records: self.header_num_records,
+            num_records: self.num_records,
+            num_filled_bytes: self.filled_bytes.len(),
+        });
+    }
+}
+
+/// Repairs a truncated NetCDF-3 file in place (see [`repair_nc3`](fn.repair_nc3.html)).
+#[cfg(feature = "fs")]
+pub fn repair_nc3_file<P: AsRef<Path>>(file_path: P) -> Result<RepairReport, RepairError> {
+    let mut file = std::fs::OpenOptions::new().read(true).write(true).open(file_path)?;
+    return repair_nc3(&mut file);
+}
+
+/// Summary of the records dropped from a file (see [`truncate_records_nc3`](fn.truncate_records_nc3.html)).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TruncateReport {
+    /// Number of records of the file before dropping the last ones.
+    pub header_num_records: usize,
+    /// Number of records kept, stored in the header.
+    pub num_records: usize,
+    /// New size of the file (the number of bytes), the end of the last record kept.
+    pub file_size: u64,
+}
+
+/// Drops the last `num_dropped_records` records of a NetCDF-3 file (bad data appended to an archive file for example).
+///
+/// The number of records is rewritten in the header, the other bytes of the file are not modified.
+/// The stream cannot be shortened through the traits `Read + Write + Seek`, so the caller has to truncate it
+/// to the returned [`TruncateReport::file_size`](struct.TruncateReport.html#structfield.file_size)
+/// (see [`truncate_records_nc3_file`](fn.truncate_records_nc3_file.html) for the files of the file system).
+///
+/// The error [`RepairError::NotEnoughRecords`](enum.RepairError.html#variant.NotEnoughRecords) is returned
+/// if the file contains less than `num_dropped_records` records.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{FileReader, truncate_records_nc3, TruncateReport};
+/// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+///
+/// let mut file = Cursor::new(NC3_CLASSIC_FILE_BYTES.to_vec());
+/// let report: TruncateReport = truncate_records_nc3(&mut file, 1).unwrap();
+/// assert_eq!(2,               report.header_num_records);
+/// assert_eq!(1,               report.num_records);
+/// file.get_mut().truncate(report.file_size as usize);
+///
+/// let mut file_reader = FileReader::from_reader(file).unwrap();
+/// assert_eq!(Some(1),                 file_reader.data_set().num_records());
+/// assert_eq!(vec![438300.0],          file_reader.read_var_f32("time").unwrap());
+/// ```
+pub fn truncate_records_nc3<F: Read + Write + Seek>(file: &mut F, num_dropped_records: usize) -> Result<TruncateReport, RepairError> {
+    let file_size: u64 = file.seek(SeekFrom::End(0))?;
+    file.seek(SeekFrom::Start(0))?;
+    let (header_num_records, records_begin, record_size): (usize, Option<u64>, Option<usize>) = {
+        let file_reader = FileReader::parse_from_reader(&mut *file, None, &ReadOptions::default())?;
+        let data_set: &DataSet = file_reader.data_set();
+        let records_begin: Option<u64> = data_set.vars().iter()
+            .filter(|var: &&Variable| var.is_record_var())
+            .filter_map(|var: &Variable| file_reader.var_begin_offset(var.name()))
+            .min();
+        (data_set.num_records().unwrap_or(0), records_begin, data_set.record_size())
+    };
+    if num_dropped_records > header_num_records {
+        return Err(RepairError::NotEnoughRecords{num_records: header_num_records, num_dropped_records: num_dropped_records});
+    }
+    let num_records: usize = header_num_records - num_dropped_records;
+    let new_file_size: u64 = match (records_begin, record_size) {
+        (Some(records_begin), Some(record_size)) => std::cmp::min(file_size, records_begin + (num_records as u64) * (record_size as u64)),
+        // no record variable, only the number of records is rewritten
+        _ => file_size,
+    };
+
+    file.seek(SeekFrom::Start(NUM_RECORDS_OFFSET))?;
+    file.write_all(&(num_records as u32).to_be_bytes())?;
+    file.flush()?;
+    return Ok(TruncateReport{
+        header_num_records: header_num_records,
+        num_records: num_records,
+        file_size: new_file_size,
+    });
+}
+
+/// Drops the last `num_dropped_records` records of a NetCDF-3 file in place, and truncates the file
+/// (see [`truncate_records_nc3`](fn.truncate_records_nc3.html)).
+#[cfg(feature = "fs")]
+pub fn truncate_records_nc3_file<P: AsRef<Path>>(file_path: P, num_dropped_records: usize) -> Result<TruncateReport, RepairError> {
+    let mut file = std::fs::OpenOptions::new().read(true).write(true).open(file_path)?;
+    let report: TruncateReport = truncate_records_nc3(&mut file, num_dropped_records)?;
+    file.set_len(report.file_size)?;
+    return Ok(report);
+}
+
+/// Returns the big-endian bytes of the fill value of the variable, its attribute `_FillValue` if it holds a single value
+/// of the data type of the variable, or the default fill value of the data type otherwise.
+fn var_fill_bytes(var: &Variable) -> Vec<u8> {
+    let fill_value: Option<&DataVector> = var.get_attr(FILL_VALUE_ATTR_NAME)
+        .filter(|attr: &&Attribute| attr.data_type() == var.data_type() && attr.len() == 1)
+        .map(|attr: &Attribute| attr.data());
+    return match fill_value {
+        Some(DataVector::I8(values)) => values[0].to_be_bytes().to_vec(),
+        Some(DataVector::U8(values)) => values.to_vec(),
+        Some(DataVector::I16(values)) => values[0].to_be_bytes().to_vec(),
+        Some(DataVector::I32(values)) => values[0].to_be_bytes().to_vec(),
+        Some(DataVector::F32(values)) => values[0].to_be_bytes().to_vec(),
+        Some(DataVector::F64(values)) => values[0].to_be_bytes().to_vec(),
+        None => nc_fill_bytes(var.data_type()),
+    };
+}
+
+/// Returns the big-endian bytes of the default fill value of the data type.
+fn nc_fill_bytes(data_type: DataType) -> Vec<u8> {
+    return match data_type {
+        DataType::I8 => NC_FILL_I8.to_be_bytes().to_vec(),
+        DataType::U8 => NC_FILL_U8.to_be_bytes().to_vec(),
+        DataType::I16 => NC_FILL_I16.to_be_bytes().to_vec(),
+        DataType::I32 => NC_FILL_I32.to_be_bytes().to_vec(),
+        DataType::F32 => NC_FILL_F32.to_be_bytes().to_vec(),
+        DataType::F64 => NC_FILL_F64.to_be_bytes().to_vec(),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::io::Cursor;
+
+    use copy_to_tmp_file::{NC3_CLASSIC_FILE_BYTES, NC3_64BIT_OFFSET_FILE_BYTES, NC3_FILL_VALUES_FILE_BYTES};
+
+    use super::{repair_nc3, RepairReport, truncate_records_nc3, TruncateReport};
+    use crate::{DataSet, FileReader, FileWriter, DataVector, Version, NC_FILL_I8, NC_FILL_U8, NC_FILL_I16, NC_FILL_I32, NC_FILL_F32, NC_FILL_F64};
+    use crate::error::RepairError;
+
+    /// Size of the header and of the fixed-size data of the file `NC3_CLASSIC_FILE_BYTES`
+    const RECORDS_BEGIN: usize = 1716;
+    const RECORD_SIZE: usize = 308;
+
+    #[test]
+    fn test_repair_nc3_complete_file() {
+        for input_bytes in [NC3_CLASSIC_FILE_BYTES, NC3_64BIT_OFFSET_FILE_BYTES, NC3_FILL_VALUES_FILE_BYTES].iter() {
+            let mut file = Cursor::new(input_bytes.to_vec());
+            let report: RepairReport = repair_nc3(&mut file).unwrap();
+            assert_eq!(false,                       report.is_modified());
+            assert_eq!(input_bytes.to_vec(),        file.into_inner());
+        }
+    }
+
+    #[test]
+    fn test_repair_nc3_truncated_records() {
+        let mut expected_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        assert_eq!(RECORDS_BEGIN + 2 * RECORD_SIZE,     NC3_CLASSIC_FILE_BYTES.len());
+
+        // only the first record has been written, and the number of records has not been updated
+        let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES[0..(RECORDS_BEGIN + RECORD_SIZE)].to_vec();
+        bytes[4..8].copy_from_slice(&0_u32.to_be_bytes());
+        let mut file = Cursor::new(bytes);
+        assert_eq!(
+            RepairReport{header_num_records: Some(0), num_records: 1, num_filled_bytes: 0},
+            repair_nc3(&mut file).unwrap()
+        );
+        let mut file_reader = FileReader::from_reader(file).unwrap();
+        assert_eq!(Some(1),                                     file_reader.data_set().num_records());
+        assert_eq!(expected_reader.read_record("temperature_f64", 0).unwrap(),     file_reader.read_record("temperature_f64", 0).unwrap());
+
+        // the second record is partial and the number of records is indeterminate
+        let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES[0..(RECORDS_BEGIN + RECORD_SIZE + 10)].to_vec();
+        bytes[4..8].copy_from_slice(&u32::MAX.to_be_bytes());
+        let mut file = Cursor::new(bytes);
+        assert_eq!(
+            RepairReport{header_num_records: None, num_records: 2, num_filled_bytes: RECORD_SIZE - 10},
+            repair_nc3(&mut file).unwrap()
+        );
+        assert_eq!(RECORDS_BEGIN + 2 * RECORD_SIZE,             file.get_ref().len());
+        let mut file_reader = FileReader::from_reader(file).unwrap();
+        assert_eq!(Some(2),                                     file_reader.data_set().num_records());
+        // the record variables `time` (4 bytes) and `temperature_i8` (16 bytes) are the first ones of the record
+        assert_eq!(DataVector::F32(vec![438324.0]),             file_reader.read_record("time", 1).unwrap());
+        // the unwritten values are filled with the default fill values
+        assert_eq!(DataVector::I8(vec![15, 16, 17, 18, 19, 20, NC_FILL_I8, NC_FILL_I8, NC_FILL_I8, NC_FILL_I8, NC_FILL_I8, NC_FILL_I8, NC_FILL_I8, NC_FILL_I8, NC_FILL_I8]),
+                                                                file_reader.read_record("temperature_i8", 1).unwrap());
+        assert_eq!(DataVector::U8(vec![NC_FILL_U8; 15]),        file_reader.read_record("temperature_u8", 1).unwrap());
+        assert_eq!(DataVector::I16(vec![NC_FILL_I16; 15]),      file_reader.read_record("temperature_i16", 1).unwrap());
+        assert_eq!(DataVector::I32(vec![NC_FILL_I32; 15]),      file_reader.read_record("temperature_i32", 1).unwrap());
+        assert_eq!(DataVector::F64(vec![NC_FILL_F64; 15]),      file_reader.read_record("temperature_f64", 1).unwrap());
+    }
+
+    #[test]
+    fn test_repair_nc3_truncated_records_fill_value_attr() {
+        let mut data_set = DataSet::new();
+        data_set.set_unlimited_dim("time", 2).unwrap();
+        data_set.add_fixed_dim("cell", 2).unwrap();
+        data_set.add_var_i16("var_1", &["time", "cell"]).unwrap();
+        data_set.add_var_attr_i16("var_1", "_FillValue", vec![-1]).unwrap();
+        data_set.add_var_f32("var_2", &["time", "cell"]).unwrap();
+        data_set.add_var_attr_f32("var_2", "_FillValue", vec![-1.0, -2.0]).unwrap();
+        data_set.add_var_i32("var_3", &["time", "cell"]).unwrap();
+        data_set.add_var_attr_i32("var_3", "_FillValue", vec![-3]).unwrap();
+        let bytes: Vec<u8> = {
+            let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+            file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+            for record_index in 0..2 {
+                file_writer.write_record_i16("var_1", record_index, &[1, 2]).unwrap();
+                file_writer.write_record_f32("var_2", record_index, &[1.0, 2.0]).unwrap();
+                file_writer.write_record_i32("var_3", record_index, &[1, 2]).unwrap();
+            }
+            file_writer.close_into_inner().unwrap().into_inner()
+        };
+
+        // only the first value of `var_1` has been written in the second record (4 + 8 + 8 bytes)
+        let mut file = Cursor::new(bytes[0..(bytes.len() - 18)].to_vec());
+        assert_eq!(
+            RepairReport{header_num_records: Some(2), num_records: 2, num_filled_bytes: 18},
+            repair_nc3(&mut file).unwrap()
+        );
+        let mut file_reader = FileReader::from_reader(file).unwrap();
+        // the attribute `_FillValue` is used if it holds a single value of the data type of the variable
+        assert_eq!(DataVector::I16(vec![1, -1]),                file_reader.read_record("var_1", 1).unwrap());
+        assert_eq!(DataVector::F32(vec![NC_FILL_F32; 2]),       file_reader.read_record("var_2", 1).unwrap());
+        assert_eq!(DataVector::I32(vec![-3, -3]),               file_reader.read_record("var_3", 1).unwrap());
+    }
+
+    #[test]
+    fn test_truncate_records_nc3() {
+        let mut expected_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+
+        // nothing dropped
+        let mut file = Cursor::new(NC3_CLASSIC_FILE_BYTES.to_vec());
+        assert_eq!(
+            TruncateReport{header_num_records: 2, num_records: 2, file_size: (RECORDS_BEGIN + 2 * RECORD_SIZE) as u64},
+            truncate_records_nc3(&mut file, 0).unwrap()
+        );
+        assert_eq!(NC3_CLASSIC_FILE_BYTES.to_vec(),             file.into_inner());
+
+        // drop the last record
+        let mut file = Cursor::new(NC3_CLASSIC_FILE_BYTES.to_vec());
+        assert_eq!(
+            TruncateReport{header_num_records: 2, num_records: 1, file_size: (RECORDS_BEGIN + RECORD_SIZE) as u64},
+            truncate_records_nc3(&mut file, 1).unwrap()
+        );
+        file.get_mut().truncate(RECORDS_BEGIN + RECORD_SIZE);
+        let mut file_reader = FileReader::from_reader(file).unwrap();
+        assert_eq!(Some(1),                                     file_reader.data_set().num_records());
+        assert_eq!(expected_reader.read_record("temperature_f64", 0).unwrap(),     file_reader.read_record("temperature_f64", 0).unwrap());
+        assert_eq!(expected_reader.read_var("latitude").unwrap(),                  file_reader.read_var("latitude").unwrap());
+
+        // drop all the records
+        let mut file = Cursor::new(NC3_CLASSIC_FILE_BYTES.to_vec());
+        assert_eq!(
+            TruncateReport{header_num_records: 2, num_records: 0, file_size: RECORDS_BEGIN as u64},
+            truncate_records_nc3(&mut file, 2).unwrap()
+        );
+        file.get_mut().truncate(RECORDS_BEGIN);
+        let file_reader = FileReader::from_reader(file).unwrap();
+        assert_eq!(Some(0),                                     file_reader.data_set().num_records());
+    }
+
+    #[test]
+    fn test_truncate_records_nc3_error_not_enough_records() {
+        let mut file = Cursor::new(NC3_CLASSIC_FILE_BYTES.to_vec());
+        assert_eq!(
+            RepairError::NotEnoughRecords{num_records: 2, num_dropped_records: 3},
+            truncate_records_nc3(&mut file, 3).unwrap_err()
+        );
+        assert_eq!(NC3_CLASSIC_FILE_BYTES.to_vec(),             file.into_inner());
+    }
+
+    #[test]
+    fn test_repair_nc3_truncated_fixed_size_data() {
+        let mut file = Cursor::new(NC3_CLASSIC_FILE_BYTES[0..(RECORDS_BEGIN - 1)].to_vec());
+        assert_eq!(
+            RepairError::FixedSizeDataTruncated{required_size: RECORDS_BEGIN as u64, file_size: (RECORDS_BEGIN - 1) as u64},
+            repair_nc3(&mut file).unwrap_err()
+        );
+    }
+}
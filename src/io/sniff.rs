@@ -0,0 +1,128 @@
+#[cfg(feature = "fs")]
+use std::io::Read;
+#[cfg(feature = "fs")]
+use std::path::Path;
+
+use crate::Version;
+
+/// Magic bytes of the NetCDF classic formats, followed by the version byte.
+const CDF_MAGIC: [u8; 3] = *b"CDF";
+/// Version byte of the CDF-5 format (64-bit data), not supported by this crate.
+const CDF5_VERSION_BYTE: u8 = 5;
+/// Signature of the HDF5 files, used by the NetCDF-4 format.
+const HDF5_SIGNATURE: [u8; 8] = [0x89, b'H', b'D', b'F', b'\r', b'\n', 0x1a, b'\n'];
+/// Maximum number of bytes required to determine the kind of a file.
+pub const SNIFF_NUM_BYTES: usize = HDF5_SIGNATURE.len();
+
+/// Kind of a file, determined from its magic bytes (see [`sniff`](fn.sniff.html)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    /// NetCDF-3 classic format (`CDF\x01`)
+    Classic,
+    /// NetCDF-3 64-bit offset format (`CDF\x02`)
+    Offset64Bit,
+    /// CDF-5 format (`CDF\x05`), not supported by this crate
+    Cdf5,
+    /// NetCDF-4 format, stored as an HDF5 file, not supported by this crate
+    NetCdf4Hdf5,
+    /// Any other file
+    Unknown,
+}
+
+impl FileKind {
+
+    /// Returns the NetCDF-3 version of the file, `None` if the file cannot be read by this crate.
+    pub fn version(&self) -> Option<Version> {
+        return match self {
+            FileKind::Classic => Some(Version::Classic),
+            FileKind::Offset64Bit => Some(Version::Offset64Bit),
+            FileKind::Cdf5 | FileKind::NetCdf4Hdf5 | FileKind::Unknown => None,
+        };
+    }
+}
+
+/// Determines the kind of a file from its first bytes, without parsing the header.
+///
+/// Only the first [`SNIFF_NUM_BYTES`](constant.SNIFF_NUM_BYTES.html) bytes are used.
+/// The HDF5 files starting with a user block are reported as `FileKind::Unknown`.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{sniff, FileKind, Version};
+/// # use copy_to_tmp_file::{NC3_CLASSIC_FILE_BYTES, NC3_64BIT_OFFSET_FILE_BYTES};
+///
+/// assert_eq!(FileKind::Classic,           sniff(NC3_CLASSIC_FILE_BYTES));
+/// assert_eq!(FileKind::Offset64Bit,       sniff(NC3_64BIT_OFFSET_FILE_BYTES));
+/// assert_eq!(FileKind::Cdf5,              sniff(b"CDF\x05"));
+/// assert_eq!(FileKind::NetCdf4Hdf5,       sniff(b"\x89HDF\r\n\x1a\n"));
+/// assert_eq!(FileKind::Unknown,           sniff(b"CDF"));
+///
+/// assert_eq!(Some(Version::Classic),      sniff(NC3_CLASSIC_FILE_BYTES).version());
+/// ```
+pub fn sniff(bytes: &[u8]) -> FileKind {
+    if bytes.starts_with(&HDF5_SIGNATURE) {
+        return FileKind::NetCdf4Hdf5;
+    }
+    if bytes.len() < CDF_MAGIC.len() + 1 || bytes[0..CDF_MAGIC.len()] != CDF_MAGIC {
+        return FileKind::Unknown;
+    }
+    return match bytes[CDF_MAGIC.len()] {
+        version_byte if version_byte == Version::Classic as u8 => FileKind::Classic,
+        version_byte if version_byte == Version::Offset64Bit as u8 => FileKind::Offset64Bit,
+        CDF5_VERSION_BYTE => FileKind::Cdf5,
+        _ => FileKind::Unknown,
+    };
+}
+
+/// Determines the kind of a file, reading only its first bytes (see [`sniff`](fn.sniff.html)).
+#[cfg(feature = "fs")]
+pub fn sniff_file<P: AsRef<Path>>(file_path: P) -> Result<FileKind, std::io::Error> {
+    let file = std::fs::File::open(file_path)?;
+    let mut bytes: Vec<u8> = Vec::with_capacity(SNIFF_NUM_BYTES);
+    let _num_bytes: usize = file.take(SNIFF_NUM_BYTES as u64).read_to_end(&mut bytes)?;
+    return Ok(sniff(&bytes));
+}
+
+#[cfg(test)]
+mod tests {
+
+    use copy_to_tmp_file::{NC3_CLASSIC_FILE_BYTES, NC3_64BIT_OFFSET_FILE_BYTES};
+
+    use super::{sniff, FileKind};
+
+    #[test]
+    fn test_sniff() {
+        assert_eq!(FileKind::Classic,           sniff(NC3_CLASSIC_FILE_BYTES));
+        assert_eq!(FileKind::Offset64Bit,       sniff(NC3_64BIT_OFFSET_FILE_BYTES));
+        assert_eq!(FileKind::Classic,           sniff(b"CDF\x01"));
+        assert_eq!(FileKind::Offset64Bit,       sniff(b"CDF\x02"));
+        assert_eq!(FileKind::Cdf5,              sniff(b"CDF\x05\x00\x00\x00\x00"));
+        assert_eq!(FileKind::NetCdf4Hdf5,       sniff(b"\x89HDF\r\n\x1a\n\x00\x00"));
+
+        // truncated or unknown magic bytes
+        assert_eq!(FileKind::Unknown,           sniff(b""));
+        assert_eq!(FileKind::Unknown,           sniff(b"CDF"));
+        assert_eq!(FileKind::Unknown,           sniff(b"CDF\x03"));
+        assert_eq!(FileKind::Unknown,           sniff(b"\x89HDF\r\n\x1a"));
+        assert_eq!(FileKind::Unknown,           sniff(b"\x0e\x03\x13\x01"));
+    }
+
+    #[cfg(feature = "fs")]
+    #[test]
+    fn test_sniff_file() {
+        use copy_to_tmp_file::{copy_bytes_to_tmp_file, NC3_CLASSIC_FILE_NAME};
+
+        use super::sniff_file;
+
+        let (tmp_dir, input_file_path) = copy_bytes_to_tmp_file(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+        assert_eq!(FileKind::Classic,           sniff_file(&input_file_path).unwrap());
+
+        let empty_file_path = tmp_dir.path().join("empty.nc");
+        std::fs::write(&empty_file_path, b"").unwrap();
+        assert_eq!(FileKind::Unknown,           sniff_file(&empty_file_path).unwrap());
+
+        assert_eq!(true,                        sniff_file(tmp_dir.path().join("not_existing.nc")).is_err());
+        tmp_dir.close().unwrap();
+    }
+}
@@ -12,3 +12,27 @@ fn test_compute_padding_size()
     assert_eq!(0, compute_padding_size(4));
     assert_eq!(3, compute_padding_size(5));
 }
+
+#[test]
+fn test_encode_decode_header()
+{
+    use crate::{decode_header, encode_header, DataSet, ReadOptions, Version};
+
+    let data_set: DataSet = {
+        let mut data_set = DataSet::new();
+        data_set.add_fixed_dim("latitude", 3).unwrap();
+        data_set.set_unlimited_dim("time", 2).unwrap();
+        data_set.add_var_f32("temperature", &["time", "latitude"]).unwrap();
+        data_set.add_global_attr_string("title", "example").unwrap();
+        data_set
+    };
+
+    let bytes: Vec<u8> = encode_header(&data_set, Version::Offset64Bit, 0).unwrap();
+    let (decoded, version): (DataSet, Version) = decode_header(&bytes, &ReadOptions::default()).unwrap();
+
+    assert_eq!(Version::Offset64Bit,                version);
+    assert_eq!(data_set.dim_names(),                decoded.dim_names());
+    assert_eq!(data_set.get_var_names(),            decoded.get_var_names());
+    assert_eq!(Some(2),                             decoded.num_records());
+    assert_eq!(data_set.get_global_attr_as_string("title"), decoded.get_global_attr_as_string("title"));
+}
@@ -0,0 +1,271 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use crate::{DataSet, FileReader, ReadOptions};
+use crate::error::ReadError;
+use crate::io::LayoutViolation;
+
+/// Size of the buffer used to check the zero padding of the header.
+const PADDING_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Checks the consistency of the data part of a NetCDF-3 file with its header, and returns the violations found.
+///
+/// The header is parsed first, an error is returned if it is not valid (the dimensions, the attributes and the variables
+/// are checked by the [`DataSet`](struct.DataSet.html) API). Then the following checks are done :
+///
+/// - the begin offsets of the variables are increasing, and the data of the variables do not overlap,
+/// - the chunk sizes stored in the header match the dimensions of the variables,
+/// - the size of the file matches the size of the data,
+/// - the space reserved after the header is filled with zero bytes.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{validate_nc3, LayoutViolation};
+/// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+///
+/// let violations: Vec<LayoutViolation> = validate_nc3(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+/// assert_eq!(true,    violations.is_empty());
+///
+/// let truncated_bytes: &[u8] = &NC3_CLASSIC_FILE_BYTES[0..2000];
+/// let violations: Vec<LayoutViolation> = validate_nc3(Cursor::new(truncated_bytes)).unwrap();
+/// assert_eq!(
+///     vec![LayoutViolation::FileTooShort{required_size: 2332, file_size: 2000}],
+///     violations
+/// );
+/// ```
+pub fn validate_nc3<R: Read + Seek>(mut input: R) -> Result<Vec<LayoutViolation>, ReadError> {
+    let file_size: u64 = input.seek(SeekFrom::End(0))?;
+    let (data_set, vars_layout, header_used_size): (DataSet, Vec<VarLayout>, u64) = {
+        let options = ReadOptions{allow_overlapping_vars: true, ..ReadOptions::default()};
+        let file_reader = FileReader::from_reader_with_options(&mut input, &options)?;
+        let vars_layout: Vec<VarLayout> = file_reader.vars_layout()?;
+        // end of the parsed header
+        let header_used_size: u64 = file_reader.header_used_size() as u64;
+        let (data_set, _version) = file_reader.close();
+        (data_set, vars_layout, header_used_size)
+    };
+    let mut violations: Vec<LayoutViolation> = check_layout(&data_set, &vars_layout, header_used_size, file_size);
+
+    // Zero padding of the header
+    let header_size: u64 = vars_layout.iter().map(|var: &VarLayout| var.begin_offset).min().unwrap_or(file_size).min(file_size);
+    if let Some(offset) = find_non_zero_byte(&mut input, header_used_size, header_size)? {
+        violations.push(LayoutViolation::NonZeroPadding{offset: offset});
+    }
+    return Ok(violations);
+}
+
+/// Layout of the data of a variable.
+pub(crate) struct VarLayout {
+    pub(crate) name: String,
+    pub(crate) is_record_var: bool,
+    pub(crate) begin_offset: u64,
+    pub(crate) chunk_size: usize,
+    /// Chunk size stored in the header, `None` if it is too large to be stored.
+    pub(crate) header_chunk_size: Option<usize>,
+}
+
+impl VarLayout {
+
+    /// Returns the offset of the end of the data of the variable (of its first record for the record variables).
+    pub(crate) fn end_offset(&self) -> u64 {
+        return self.begin_offset.saturating_add(self.chunk_size as u64);
+    }
+}
+
+/// Checks the layout of the data of the variables, except the zero padding of the header.
+pub(crate) fn check_layout(data_set: &DataSet, vars_layout: &[VarLayout], header_used_size: u64, file_size: u64) -> Vec<LayoutViolation> {
+    let mut violations: Vec<LayoutViolation> = vec![];
+
+    // Chunk sizes and begin offsets of the variables
+    for var in vars_layout.iter() {
+        if let Some(header_chunk_size) = var.header_chunk_size {
+            if header_chunk_size != var.chunk_size {
+                violations.push(LayoutViolation::ChunkSizeMismatch{
+                    var_name: var.name.clone(),
+                    header_chunk_size: header_chunk_size,
+                    chunk_size: var.chunk_size,
+                });
+            }
+        }
+        if var.begin_offset < header_used_size {
+            violations.push(LayoutViolation::DataOverlapsHeader{
+                var_name: var.name.clone(),
+                begin_offset: var.begin_offset,
+                header_size: header_used_size,
+            });
+        }
+    }
+    let (record_vars, fixed_size_vars): (Vec<&VarLayout>, Vec<&VarLayout>) = vars_layout.iter().partition(|var: &&VarLayout| var.is_record_var);
+    let ordered_vars: Vec<&VarLayout> = order_vars_layout(vars_layout);
+    for (prev_var, var) in ordered_vars.iter().zip(ordered_vars.iter().skip(1)) {
+        if var.begin_offset < prev_var.begin_offset {
+            violations.push(LayoutViolation::OffsetsNotIncreasing{var_name: var.name.clone(), prev_var_name: prev_var.name.clone()});
+        }
+        else if var.begin_offset < prev_var.end_offset() {
+            violations.push(LayoutViolation::DataOverlap{var_name: var.name.clone(), prev_var_name: prev_var.name.clone()});
+        }
+    }
+    let record_size: u64 = data_set.record_size().unwrap_or(0) as u64;
+    let records_begin: Option<u64> = record_vars.iter().map(|var: &&VarLayout| var.begin_offset).min();
+    // the records of the only record variable are not padded
+    if let (Some(records_begin), false) = (records_begin, data_set.records_are_packed()) {
+        for var in record_vars.iter() {
+            if var.end_offset() > records_begin.saturating_add(record_size) {
+                violations.push(LayoutViolation::RecordOverflow{var_name: var.name.clone()});
+            }
+        }
+    }
+
+    // Size of the file
+    let fixed_size_data_end: Option<u64> = fixed_size_vars.iter().map(|var: &&VarLayout| var.end_offset()).max();
+    let records_end: Option<u64> = records_begin.map(|records_begin: u64| {
+        records_begin.saturating_add((data_set.num_records().unwrap_or(0) as u64).saturating_mul(record_size))
+    });
+    let data_end: Option<u64> = fixed_size_data_end.into_iter().chain(records_end).max();
+    if let Some(data_end) = data_end {
+        if file_size < data_end {
+            violations.push(LayoutViolation::FileTooShort{required_size: data_end, file_size: file_size});
+        }
+        else if file_size > data_end {
+            violations.push(LayoutViolation::TrailingBytes{data_end: data_end, file_size: file_size});
+        }
+    }
+    return violations;
+}
+
+/// Returns the variables in the order of their data, the *fixed-size* variables first and then the record variables,
+/// both in the order of the header.
+fn order_vars_layout(vars_layout: &[VarLayout]) -> Vec<&VarLayout> {
+    let (record_vars, fixed_size_vars): (Vec<&VarLayout>, Vec<&VarLayout>) = vars_layout.iter().partition(|var: &&VarLayout| var.is_record_var);
+    return fixed_size_vars.into_iter().chain(record_vars).collect();
+}
+
+/// Returns the first variable whose data begin before the end of the data of the previous variable, and this previous variable.
+pub(crate) fn find_overlapping_vars(vars_layout: &[VarLayout]) -> Option<(&VarLayout, &VarLayout)> {
+    let ordered_vars: Vec<&VarLayout> = order_vars_layout(vars_layout);
+    return ordered_vars.iter().zip(ordered_vars.iter().skip(1))
+        .find(|(prev_var, var): &(&&VarLayout, &&VarLayout)| var.begin_offset < prev_var.end_offset())
+        .map(|(prev_var, var): (&&VarLayout, &&VarLayout)| (*var, *prev_var));
+}
+
+/// Returns the offset of the first non-zero byte in `start..end`.
+fn find_non_zero_byte<R: Read + Seek>(input: &mut R, start: u64, end: u64) -> Result<Option<u64>, ReadError> {
+    let mut buffer: Vec<u8> = vec![0_u8; PADDING_BUFFER_SIZE];
+    let mut chunk_start: u64 = start;
+    input.seek(SeekFrom::Start(start))?;
+    while chunk_start < end {
+        let chunk_len: usize = std::cmp::min(PADDING_BUFFER_SIZE as u64, end - chunk_start) as usize;
+        let chunk: &mut [u8] = &mut buffer[0..chunk_len];
+        input.read_exact(chunk)?;
+        if let Some(position) = chunk.iter().position(|byte: &u8| *byte != 0) {
+            return Ok(Some(chunk_start + position as u64));
+        }
+        chunk_start += chunk_len as u64;
+    }
+    return Ok(None);
+}
+
+#[cfg(all(test, feature = "write"))]
+mod tests {
+
+    use std::io::Cursor;
+
+    use copy_to_tmp_file::{
+        NC3_CLASSIC_FILE_BYTES,
+        NC3_64BIT_OFFSET_FILE_BYTES,
+        SCALAR_VARIABLES_FILE_BYTES,
+        EMPTY_DATA_SET_FILE_BYTES,
+        NC3_ZERO_SIZED_UNLIMITED_DIM_FILE_BYTES,
+    };
+
+    use super::{validate_nc3, LayoutViolation};
+    use crate::{copy_nc3, CopyOptions, FileReader};
+
+    /// Returns the position of the last occurence of `begin_offset` in the header of the classic test file.
+    fn find_begin_offset_position(bytes: &[u8], begin_offset: u32) -> usize {
+        const HEADER_SIZE: usize = 1684;
+        let pattern: [u8; 4] = begin_offset.to_be_bytes();
+        return bytes[0..HEADER_SIZE.min(bytes.len())].windows(4).rposition(|window: &[u8]| window == pattern).unwrap();
+    }
+
+    #[test]
+    fn test_validate_nc3_valid_files() {
+        for bytes in [
+            NC3_CLASSIC_FILE_BYTES,
+            NC3_64BIT_OFFSET_FILE_BYTES,
+            SCALAR_VARIABLES_FILE_BYTES,
+            EMPTY_DATA_SET_FILE_BYTES,
+            NC3_ZERO_SIZED_UNLIMITED_DIM_FILE_BYTES,
+        ].iter() {
+            assert_eq!(Vec::<LayoutViolation>::new(),   validate_nc3(Cursor::new(bytes)).unwrap());
+        }
+
+        // with a reserved space after the header
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        let options = CopyOptions{header_min_size: 4096, ..CopyOptions::default()};
+        let bytes: Vec<u8> = copy_nc3(&mut file_reader, Cursor::new(vec![]), &options).unwrap().into_inner();
+        assert_eq!(Vec::<LayoutViolation>::new(),       validate_nc3(Cursor::new(&bytes)).unwrap());
+    }
+
+    #[test]
+    fn test_validate_nc3_file_size() {
+        let bytes: &[u8] = &NC3_CLASSIC_FILE_BYTES[0..2000];
+        assert_eq!(
+            vec![LayoutViolation::FileTooShort{required_size: 2332, file_size: 2000}],
+            validate_nc3(Cursor::new(bytes)).unwrap()
+        );
+
+        let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+        bytes.extend_from_slice(&[0_u8; 8]);
+        assert_eq!(
+            vec![LayoutViolation::TrailingBytes{data_end: 2332, file_size: 2340}],
+            validate_nc3(Cursor::new(&bytes)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_validate_nc3_offsets() {
+        // move the data of `longitude` (begin offset 1696) on the data of `latitude` (begin offset 1684)
+        let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+        let position: usize = find_begin_offset_position(&bytes, 1696);
+        bytes[position..(position + 4)].copy_from_slice(&1688_u32.to_be_bytes());
+        assert_eq!(
+            vec![LayoutViolation::DataOverlap{var_name: String::from("longitude"), prev_var_name: String::from("latitude")}],
+            validate_nc3(Cursor::new(&bytes)).unwrap()
+        );
+
+        // move the data of `latitude` (begin offset 1684) into the header
+        let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+        let position: usize = find_begin_offset_position(&bytes, 1684);
+        bytes[position..(position + 4)].copy_from_slice(&1680_u32.to_be_bytes());
+        assert_eq!(
+            vec![LayoutViolation::DataOverlapsHeader{var_name: String::from("latitude"), begin_offset: 1680, header_size: 1684}],
+            validate_nc3(Cursor::new(&bytes)).unwrap()
+        );
+
+        // swap the data of `latitude` and `longitude`
+        let mut bytes: Vec<u8> = NC3_CLASSIC_FILE_BYTES.to_vec();
+        let position: usize = find_begin_offset_position(&bytes, 1696);
+        bytes[position..(position + 4)].copy_from_slice(&1684_u32.to_be_bytes());
+        let position: usize = find_begin_offset_position(&bytes[0..position], 1684);
+        bytes[position..(position + 4)].copy_from_slice(&1704_u32.to_be_bytes());
+        let violations: Vec<LayoutViolation> = validate_nc3(Cursor::new(&bytes)).unwrap();
+        assert_eq!(
+            Some(&LayoutViolation::OffsetsNotIncreasing{var_name: String::from("longitude"), prev_var_name: String::from("latitude")}),
+            violations.first()
+        );
+    }
+
+    #[test]
+    fn test_validate_nc3_padding() {
+        let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+        let options = CopyOptions{header_min_size: 4096, ..CopyOptions::default()};
+        let mut bytes: Vec<u8> = copy_nc3(&mut file_reader, Cursor::new(vec![]), &options).unwrap().into_inner();
+        bytes[4000] = 1;
+        assert_eq!(
+            vec![LayoutViolation::NonZeroPadding{offset: 4000}],
+            validate_nc3(Cursor::new(&bytes)).unwrap()
+        );
+    }
+}
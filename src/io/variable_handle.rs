@@ -0,0 +1,224 @@
+#[cfg(feature = "read")]
+use std::io::Read;
+#[cfg(feature = "write")]
+use std::io::Write;
+use std::io::Seek;
+use std::rc::Rc;
+
+use crate::{Attribute, DataType, DataVector, Dimension, Variable};
+#[cfg(feature = "read")]
+use crate::FileReader;
+#[cfg(feature = "read")]
+use crate::error::ReadError;
+#[cfg(feature = "write")]
+use crate::{DataSet, DataSlice, FileWriter};
+#[cfg(feature = "write")]
+use crate::error::WriteError;
+
+/// Handle on a variable of a [`FileReader`](struct.FileReader.html), returned by
+/// [`FileReader::variable`](struct.FileReader.html#method.variable).
+///
+/// The name of the variable is resolved once, when the handle is created.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{FileReader, DataType, ReadError};
+/// # use copy_to_tmp_file::NC3_CLASSIC_FILE_BYTES;
+///
+/// let mut file_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+/// assert_eq!(
+///     ReadError::VariableNotDefined(String::from("undef_var")),
+///     file_reader.variable("undef_var").unwrap_err()
+/// );
+///
+/// let mut temperature = file_reader.variable("temperature_f32").unwrap();
+/// assert_eq!(DataType::F32,                               temperature.data_type());
+/// assert_eq!(vec![2, 3, 5],                               temperature.shape());
+/// assert_eq!(Some(String::from("Celsius")),               temperature.attr("units").and_then(|attr| attr.as_string()));
+/// assert_eq!(30,                                          temperature.read().unwrap().len());
+/// assert_eq!(Some(&[18.0, 19.0][..]),                     temperature.read_slice(&[1, 0, 3], &[1, 1, 2]).unwrap().get_f32());
+/// ```
+#[cfg(feature = "read")]
+#[derive(Debug)]
+pub struct VariableReader<'f, R: Read + Seek> {
+    file_reader: &'f mut FileReader<R>,
+    var_name: String,
+}
+
+#[cfg(feature = "read")]
+impl<'f, R: Read + Seek> VariableReader<'f, R> {
+    pub(crate) fn new(file_reader: &'f mut FileReader<R>, var_name: &str) -> Result<VariableReader<'f, R>, ReadError> {
+        if !file_reader.data_set().has_var(var_name) {
+            return Err(ReadError::VariableNotDefined(String::from(var_name)));
+        }
+        return Ok(VariableReader {
+            file_reader: file_reader,
+            var_name: String::from(var_name),
+        });
+    }
+
+    /// Returns the name of the variable.
+    pub fn name(&self) -> &str {
+        return &self.var_name;
+    }
+
+    /// Returns the definition of the variable.
+    pub fn variable(&self) -> &Variable {
+        // the variable is defined, it has been checked when the handle has been created
+        return self.file_reader.data_set().get_var(&self.var_name).unwrap();
+    }
+
+    /// Returns the data type of the variable.
+    pub fn data_type(&self) -> DataType {
+        return self.variable().data_type();
+    }
+
+    /// Returns the sizes of the dimensions of the variable (the number of records along the *unlimited-size* dimension).
+    pub fn shape(&self) -> Vec<usize> {
+        return self.variable().dims().iter().map(|dim: &Rc<Dimension>| dim.size()).collect();
+    }
+
+    /// Returns the attributes of the variable.
+    pub fn attrs(&self) -> &[Attribute] {
+        return self.variable().attrs();
+    }
+
+    /// Returns the attribute of the variable, or `None`.
+    pub fn attr(&self, attr_name: &str) -> Option<&Attribute> {
+        return self.variable().get_attr(attr_name);
+    }
+
+    /// Reads all the values of the variable (see [`FileReader::read_var`](struct.FileReader.html#method.read_var)).
+    pub fn read(&mut self) -> Result<DataVector, ReadError> {
+        return self.file_reader.read_var(&self.var_name);
+    }
+
+    /// Reads all the values of the variable into `data_vec` (see [`FileReader::read_var_into`](struct.FileReader.html#method.read_var_into)).
+    pub fn read_into(&mut self, data_vec: &mut DataVector) -> Result<(), ReadError> {
+        return self.file_reader.read_var_into(&self.var_name, data_vec);
+    }
+
+    /// Reads one record of the variable (see [`FileReader::read_record`](struct.FileReader.html#method.read_record)).
+    pub fn read_record(&mut self, record_index: usize) -> Result<DataVector, ReadError> {
+        return self.file_reader.read_record(&self.var_name, record_index);
+    }
+
+    /// Reads a hyperslab of the variable (see [`FileReader::read_var_slice`](struct.FileReader.html#method.read_var_slice)).
+    pub fn read_slice(&mut self, start: &[usize], count: &[usize]) -> Result<DataVector, ReadError> {
+        return self.file_reader.read_var_slice(&self.var_name, start, count);
+    }
+}
+
+/// Handle on a variable of a [`FileWriter`](struct.FileWriter.html), returned by
+/// [`FileWriter::variable`](struct.FileWriter.html#method.variable).
+///
+/// The name of the variable is resolved once, when the handle is created.
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{FileReader, FileWriter, DataSet, DataSlice, DataVector, Version};
+///
+/// let mut data_set = DataSet::new();
+/// data_set.set_unlimited_dim("time", 2).unwrap();
+/// data_set.add_fixed_dim("cell", 3).unwrap();
+/// data_set.add_var_f64("time", &["time"]).unwrap();
+/// data_set.add_var_i32("count", &["time", "cell"]).unwrap();
+///
+/// let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+/// file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+///
+/// let mut time = file_writer.variable("time").unwrap();
+/// assert_eq!(vec![2],                             time.shape());
+/// time.write(DataSlice::F64(&[0.0, 1.0])).unwrap();
+///
+/// let mut count = file_writer.variable("count").unwrap();
+/// count.write_record(0, DataSlice::I32(&[1, 2, 3])).unwrap();
+/// count.write_record(1, DataSlice::I32(&[4, 5, 6])).unwrap();
+///
+/// let mut file_reader = FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+/// assert_eq!(vec![0.0, 1.0],                      file_reader.read_var_f64("time").unwrap());
+/// assert_eq!(vec![1, 2, 3, 4, 5, 6],              file_reader.read_var_i32("count").unwrap());
+/// ```
+#[cfg(feature = "write")]
+#[derive(Debug)]
+pub struct VariableWriter<'f, 'a, W: Write + Seek> {
+    file_writer: &'f mut FileWriter<'a, W>,
+    var: &'a Variable,
+}
+
+#[cfg(feature = "write")]
+impl<'f, 'a, W: Write + Seek> VariableWriter<'f, 'a, W> {
+    pub(crate) fn new(file_writer: &'f mut FileWriter<'a, W>, var_name: &str) -> Result<VariableWriter<'f, 'a, W>, WriteError> {
+        let data_set: &'a DataSet = file_writer.data_set().ok_or(WriteError::HeaderNotDefined)?;
+        let var: &'a Variable = data_set.get_var(var_name).ok_or_else(|| WriteError::VariableNotDefined(String::from(var_name)))?;
+        return Ok(VariableWriter {
+            file_writer: file_writer,
+            var: var,
+        });
+    }
+
+    /// Returns the name of the variable.
+    pub fn name(&self) -> &'a str {
+        return self.var.name();
+    }
+
+    /// Returns the definition of the variable.
+    pub fn variable(&self) -> &'a Variable {
+        return self.var;
+    }
+
+    /// Returns the data type of the variable.
+    pub fn data_type(&self) -> DataType {
+        return self.var.data_type();
+    }
+
+    /// Returns the sizes of the dimensions of the variable (the number of records along the *unlimited-size* dimension).
+    pub fn shape(&self) -> Vec<usize> {
+        return self.var.dims().iter().map(|dim: &Rc<Dimension>| dim.size()).collect();
+    }
+
+    /// Returns the attributes of the variable.
+    pub fn attrs(&self) -> &'a [Attribute] {
+        return self.var.attrs();
+    }
+
+    /// Returns the attribute of the variable, or `None`.
+    pub fn attr(&self, attr_name: &str) -> Option<&'a Attribute> {
+        return self.var.get_attr(attr_name);
+    }
+
+    /// Writes all the values of the variable (see [`FileWriter::write_var_i8`](struct.FileWriter.html#method.write_var_i8), etc.).
+    pub fn write(&mut self, data: DataSlice) -> Result<(), WriteError> {
+        let var_name: &'a str = self.var.name();
+        return match data {
+            DataSlice::I8(data) => self.file_writer.write_var_i8(var_name, data),
+            DataSlice::U8(data) => self.file_writer.write_var_u8(var_name, data),
+            DataSlice::I16(data) => self.file_writer.write_var_i16(var_name, data),
+            DataSlice::I32(data) => self.file_writer.write_var_i32(var_name, data),
+            DataSlice::F32(data) => self.file_writer.write_var_f32(var_name, data),
+            DataSlice::F64(data) => self.file_writer.write_var_f64(var_name, data),
+        };
+    }
+
+    /// Writes all the values of the variable, consuming `data` (see [`FileWriter::write_var_owned`](struct.FileWriter.html#method.write_var_owned)).
+    pub fn write_owned(&mut self, data: DataVector) -> Result<(), WriteError> {
+        return self.file_writer.write_var_owned(self.var.name(), data);
+    }
+
+    /// Writes one record of the variable (see [`FileWriter::write_record_i8`](struct.FileWriter.html#method.write_record_i8), etc.).
+    pub fn write_record(&mut self, record_index: usize, record: DataSlice) -> Result<(), WriteError> {
+        let var_name: &'a str = self.var.name();
+        return match record {
+            DataSlice::I8(record) => self.file_writer.write_record_i8(var_name, record_index, record),
+            DataSlice::U8(record) => self.file_writer.write_record_u8(var_name, record_index, record),
+            DataSlice::I16(record) => self.file_writer.write_record_i16(var_name, record_index, record),
+            DataSlice::I32(record) => self.file_writer.write_record_i32(var_name, record_index, record),
+            DataSlice::F32(record) => self.file_writer.write_record_f32(var_name, record_index, record),
+            DataSlice::F64(record) => self.file_writer.write_record_f64(var_name, record_index, record),
+        };
+    }
+}
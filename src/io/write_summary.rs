@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+/// Summary of the writing of a file, returned by [`FileWriter::close`](struct.FileWriter.html#method.close).
+///
+/// # Example
+///
+/// ```
+/// use std::io::Cursor;
+/// use netcdf3::{FileWriter, DataSet, Version, WriteSummary};
+///
+/// let mut data_set = DataSet::new();
+/// data_set.set_unlimited_dim("time", 3).unwrap();
+/// data_set.add_var_f64("time", &["time"]).unwrap();
+/// data_set.add_var_i32("count", &["time"]).unwrap();
+///
+/// let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+/// file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+/// file_writer.write_var_f64("time", &[0.0, 1.0, 2.0]).unwrap();
+/// file_writer.write_record_i32("count", 0, &[10]).unwrap();
+///
+/// // the records 1 and 2 of `count` are filled with the default fill value
+/// let summary: WriteSummary = file_writer.close().unwrap();
+/// assert_eq!(vec!["time", "count"],   summary.vars_written);
+/// assert_eq!(vec!["count"],           summary.vars_filled);
+/// assert_eq!(3,                       summary.num_records);
+/// assert_eq!(156,                     summary.bytes_written);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WriteSummary {
+    /// Number of bytes written into the output (header, padding and fill values included),
+    /// counted like [`IoMetrics::bytes_written`](struct.IoMetrics.html#structfield.bytes_written).
+    pub bytes_written: u64,
+    /// Names of the variables of which at least one chunk has been written with the values given by the user.
+    pub vars_written: Vec<String>,
+    /// Names of the variables of which at least one chunk has been filled with the fill values when the writer was closed.
+    pub vars_filled: Vec<String>,
+    /// Number of records of the data set (`0` if no *unlimited-size* dimension is defined).
+    pub num_records: usize,
+    /// Time elapsed since the creation of the writer.
+    pub elapsed: Duration,
+}
+
+/// Report of the verification of the written data, returned by [`FileWriter::close_verified`](struct.FileWriter.html#method.close_verified).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VerifyReport {
+    /// Summary of the writing.
+    pub summary: WriteSummary,
+    /// Number of chunks read back and compared with the written values.
+    pub num_verified_chunks: usize,
+    /// Variable name and index of the chunks (the whole data of a *fixed-size* variable, or one of its records)
+    /// read back with other values than the written ones.
+    pub mismatched_chunks: Vec<(String, usize)>,
+}
+
+impl VerifyReport {
+
+    /// Returns `true` if all the verified chunks have been read back with the written values.
+    pub fn is_verified(&self) -> bool {
+        return self.mismatched_chunks.is_empty();
+    }
+}
@@ -11,34 +11,86 @@
 //!     - Define (create, get, rename and remove) variable attributes (examples [here](struct.Attribute.html#variable-attributes));
 //! - Read the NetCDF-3 files using the struct [`FileReader`](struct.FileReader.html).
 //! - Write the NetCDF-3 files using the struct [`FileWriter`](struct.FileWriter.html).
+//! - Import the commonly used items at once with `use netcdf3::prelude::*;` (see the module [`prelude`](prelude/index.html)).
+//!
+//! # Cargo features
+//!
+//! - `std` (enabled by default through `fs`, `read` and `write`): without it the crate is `no_std` and only requires `alloc`.
+//!   The data model ([`DataSet`](struct.DataSet.html), [`DataType`](enum.DataType.html), [`DataVector`](enum.DataVector.html)
+//!   and [`InvalidDataSet`](error/enum.InvalidDataSet.html)) is still available, and so are the header parser and serializer
+//!   (the functions `decode_header`, `encode_header` and `encode_chunk` working on byte buffers).
+//!   The structs `FileReader` and `FileWriter` and the modules `cf` and `mapping` require `std`.
+//! - `read` (enabled by default): the struct [`FileReader`](struct.FileReader.html), and the functions `repair_nc3`, `truncate_records_nc3`, `validate_nc3` and `compare_nc3`.
+//! - `write` (enabled by default): the struct [`FileWriter`](struct.FileWriter.html).
+//!   The functions `copy_nc3`, `concat_nc3`, `optimize_nc3` and `edit_nc3`, the trait `NcRecord` and the module `testing` require both `read` and `write`.
+//!   A program which only writes (or only reads) the files can disable the default features and enable only one of them.
+//! - `fs` (enabled by default): opens the files of the file system with the methods `FileReader::open`, `FileWriter::open` and `FileWriter::create_new`.
+//!   Without it the crate also builds for the `wasm32-unknown-unknown` target, and reads/writes any `Read + Seek`/`Write + Seek` source
+//!   (see the methods `FileReader::from_reader` and `FileWriter::from_writer`).
+//! - `units`: the module `cf::units` (parsing of the CF unit strings).
+//! - `chrono`: the functions `cf::decode_time` (decoding of the CF time coordinates) and `cf::select_records_by_time`, also enables `units`.
+//! - `dap`: the struct [`DapReader`](struct.DapReader.html) reading the data sets served by the OPeNDAP (DAP2) servers.
+//! - `http`: the method `FileReader::open_url` reading the remote files through HTTP(S) range requests sent by `reqwest` (see [`HttpRangeReader`](struct.HttpRangeReader.html), the HTTP client can be replaced by implementing [`RangeBackend`](trait.RangeBackend.html)).
+//! - `http-ureq`: the range backend [`UreqBackend`](struct.UreqBackend.html), sending the requests with the lighter client `ureq`.
+//! - `mmap`: the method `FileWriter::create_mmap` writing the files through a memory map (see [`MmapWriter`](struct.MmapWriter.html)).
+//! - `parallel`: the method `FileReader::read_var_parallel` reading the records of a variable with several threads.
+//! - `derive`: the derive macro `#[derive(NcRecord)]` mapping the fields of a struct to record variables (see [`NcRecord`](trait.NcRecord.html)).
+//! - `fixtures`: the module `fixtures` providing the bytes of sample NetCDF-3 files (see [`fixtures`](fixtures/index.html)).
+//! - `checksums`: the functions `compute_checksums` and `verify_checksums` (SHA-256 checksums of the variable data), also enables `read`.
+//! - `normalization` (enabled by default): the function `normalize_name`, and the check of the normalization (NFC) of the multibyte UTF-8
+//!   names by the strict rules (see [`NameValidation::Strict`](enum.NameValidation.html#variant.Strict)). Compatible with `no_std`.
 //!
 //! # Notes
 //!
 //! - If the number of records `numrecs` is greater than `std::i32::MAX` then this value is considered as indeterminate and the actually written value is `numrecs = 2^32 - 1`(see the [File Format Specifications][File_Format_Specs]).
 //! - If the chunk size of a given variable `vsize` is greater the `std::i32::MAX` then its value is considered as indeterminate and the actually written value is `vsize = 2^32 - 1` (see the [File Format Specifications][File_Format_Specs]).
 //! - To validate the implementation of the NetCDF-3 files writing, binary comparisons between the crate outcomes and files produced by the Python library [netCDF4](https://github.com/Unidata/netcdf4-python) are done while the test suite (see the Python script `pyscripts/create_test_nc3_files.py` and the Rust test file `tests/tests_write_nc3_files.rs`).
+//! - The header of an existing NetCDF-3 file is rewritten by [`edit_nc3`](fn.edit_nc3.html) and [`edit_nc3_file`](fn.edit_nc3_file.html), in place when the edited header fits in the space reserved for it.
 //!
 //! ## Known limitations
 //!
-//! - Cannot read/write a subset of a variable data yet.
-//! - Cannot rewrite a NetCDF-3 file.
+//! - Cannot write a subset of a variable data yet, except whole records (see `FileWriter::write_records`). The subsets are read by `FileReader::read_var_slice`.
 //!
 //! [File_Format_Specs]: https://www.unidata.ucar.edu/software/netcdf/docs/file_format_specifications.html
+#![cfg_attr(not(feature = "std"), no_std)]
+// The collections (`Vec`, `String`, ...) are imported from `alloc` when the feature `std` is disabled (see `alloc_prelude`)
+extern crate alloc;
+
+/// Items of the `std` prelude, imported from `alloc` when the feature `std` is disabled.
+#[cfg(not(feature = "std"))]
+mod alloc_prelude {
+    pub(crate) use alloc::{format, string::{String, ToString}, vec, vec::Vec};
+}
+
+/// Items used by the expansion of the exported macros.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::vec;
+}
+
+mod macros;
+
 pub mod error;
-pub use error::{ReadError, WriteError, InvalidDataSet};
+pub use error::{InvalidDataSet, ReadError, WriteError};
+#[cfg(feature = "std")]
+pub use error::{CopyError, RepairError, ConcatError, EditError};
+#[cfg(feature = "dap")]
+pub use error::DapError;
 
 mod name_string;
-pub use name_string::is_valid_name;
+pub use name_string::{is_valid_name, is_valid_name_with, validate_name, validate_name_with, NameError, NameValidation};
+#[cfg(feature = "normalization")]
+pub use name_string::normalize_name;
 pub use name_string::NC_MAX_NAME_SIZE;
 
 mod data_type;
 pub use data_type::DataType;
 
 mod data_vector;
-pub use data_vector::DataVector;
+pub use data_vector::{DataVector, DataSlice, VariableView};
 
 mod data_set;
-pub use data_set::{Attribute, DataSet, Dimension, DimensionType, Variable};
+pub use data_set::{Attribute, AttrValue, DataSet, DimId, DimKey, Dimension, DimensionType, DimRef, Variable, VarRef};
 pub use data_set::NC_FILL_I8;
 pub use data_set::NC_FILL_U8;
 pub use data_set::NC_FILL_I16;
@@ -49,7 +101,80 @@ pub use data_set::NC_MAX_DIM_SIZE;
 pub use data_set::NC_MAX_VAR_DIMS;
 
 mod io;
-pub use io::{FileReader, FileWriter};
+pub use io::{decode_header, encode_chunk, encode_header};
+pub use io::{DuplicateAttrPolicy, DuplicateDimPolicy, LongNamePolicy, NameDecoding, PaddingCheck, ReadOptions};
+#[cfg(feature = "read")]
+pub use io::FileReader;
+#[cfg(feature = "write")]
+pub use io::{FileWriter, WriteOptions};
+#[cfg(feature = "write")]
+pub use io::recommended_header_min_size;
+#[cfg(feature = "dap")]
+pub use io::DapReader;
+#[cfg(feature = "http")]
+pub use io::{HttpRangeReader, RangeBackend, ReqwestBackend};
+#[cfg(feature = "http-ureq")]
+pub use io::UreqBackend;
+#[cfg(feature = "mmap")]
+pub use io::MmapWriter;
+#[cfg(feature = "std")]
+pub use io::{raw_sidecar_json, write_raw_data};
+#[cfg(all(feature = "read", feature = "write"))]
+pub use io::{copy_nc3, copy_var, CopyOptions, DimSlice};
+#[cfg(feature = "read")]
+pub use io::{repair_nc3, RepairReport, truncate_records_nc3, TruncateReport};
+#[cfg(all(feature = "fs", feature = "read"))]
+pub use io::{repair_nc3_file, truncate_records_nc3_file};
+#[cfg(all(feature = "read", feature = "write"))]
+pub use io::concat_nc3;
+#[cfg(all(feature = "read", feature = "write"))]
+pub use io::{optimize_nc3, OptimizeOptions};
+#[cfg(all(feature = "fs", feature = "read", feature = "write"))]
+pub use io::optimize_nc3_file;
+#[cfg(feature = "checksums")]
+pub use io::{compute_checksums, read_checksums, verify_checksums, write_checksums, ChecksumMismatch};
+#[cfg(all(feature = "read", feature = "write"))]
+pub use io::{edit_nc3, EditMode};
+#[cfg(all(feature = "fs", feature = "read", feature = "write"))]
+pub use io::edit_nc3_file;
+#[cfg(feature = "read")]
+pub use io::validate_nc3;
+#[cfg(feature = "read")]
+pub use io::{compare_nc3, Divergence, Tolerance};
+#[cfg(all(feature = "fs", feature = "read"))]
+pub use io::{files_equal, first_divergence};
+pub use io::{Diagnostic, Diagnostics, LayoutViolation};
+#[cfg(feature = "read")]
+pub use io::VariableReader;
+#[cfg(feature = "write")]
+pub use io::VariableWriter;
+#[cfg(all(feature = "read", feature = "write"))]
+pub use io::{NcRecord, NcValue};
+#[cfg(any(feature = "read", feature = "write"))]
+pub use io::IoMetrics;
+#[cfg(feature = "write")]
+pub use io::{VerifyReport, WriteSummary};
+#[cfg(feature = "derive")]
+pub use netcdf3_derive::NcRecord;
+pub use io::{sniff, FileKind, SNIFF_NUM_BYTES};
+#[cfg(feature = "fs")]
+pub use io::sniff_file;
 
 mod version;
-pub use version::Version;
\ No newline at end of file
+pub use version::Version;
+
+pub mod index;
+
+#[cfg(feature = "std")]
+pub mod mapping;
+
+pub mod prelude;
+
+#[cfg(feature = "std")]
+pub mod cf;
+
+#[cfg(all(feature = "read", feature = "write", any(test, feature = "testing")))]
+pub mod testing;
+
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
\ No newline at end of file
@@ -0,0 +1,248 @@
+/// Defines a [`DataSet`](struct.DataSet.html) with a syntax close to the CDL notation (the text output of `ncdump`).
+///
+/// The macro expands to the calls of the methods `DataSet::set_unlimited_dim`, `DataSet::add_fixed_dim`, `DataSet::add_var`,
+/// `DataSet::add_var_attr_*` and `DataSet::add_global_attr_*`, and evaluates to a `Result<DataSet, InvalidDataSet>`.
+///
+/// The sections `dims`, `attrs` (global attributes) and `vars` are optional, but must be given in this order.
+///
+/// - A dimension is defined by `name = size;` (*fixed-size*) or `name = unlimited(num_records);` (*unlimited-size*).
+/// - A variable is defined by `data_type name(dim_1, dim_2, ...);`, or by `data_type name;` for a scalar variable.
+///   Its attributes can be given between braces instead of the final `;`.
+/// - An attribute is defined by `name = "text";` (any expression implementing `AsRef<str>` can be used),
+///   or by `name = data_type(value_1, value_2, ...);` for the numeric attributes.
+///
+/// The data types are `i8`, `u8`, `i16`, `i32`, `f32` and `f64`. The names are Rust identifiers.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{netcdf3, DataSet, DataType};
+///
+/// let num_latitudes: usize = 180;
+/// let data_set: DataSet = netcdf3!{
+///     dims {
+///         lat = num_latitudes;
+///         time = unlimited(0);
+///     }
+///     attrs {
+///         title = "Example";
+///         version = i32(2);
+///     }
+///     vars {
+///         f64 time(time) {
+///             units = "hours since 1970-01-01 00:00:00";
+///         }
+///         f32 temp(time, lat) {
+///             units = "K";
+///             valid_range = f32(150.0, 350.0);
+///         }
+///         i32 station_id;
+///     }
+/// }.unwrap();
+///
+/// assert_eq!(Some(180),                           data_set.dim_size("lat"));
+/// assert_eq!(true,                                data_set.has_unlimited_dim());
+/// assert_eq!(Some(String::from("Example")),       data_set.get_global_attr_as_string("title"));
+/// assert_eq!(Some(&[2][..]),                      data_set.get_global_attr_i32("version"));
+/// assert_eq!(vec!["time", "lat"],                 data_set["temp"].dim_names());
+/// assert_eq!(DataType::F32,                       data_set["temp"].data_type());
+/// assert_eq!(Some(&[150.0, 350.0][..]),           data_set["temp"]["valid_range"].get_f32());
+/// assert_eq!(0,                                   data_set["station_id"].num_dims());
+/// ```
+///
+/// The errors of the definition are returned, like the methods of `DataSet` do.
+///
+/// ```
+/// use netcdf3::{netcdf3, InvalidDataSet};
+///
+/// assert_eq!(
+///     InvalidDataSet::DimensionsNotDefined{var_name: String::from("temp"), undef_dim_names: vec![String::from("lon")]},
+///     netcdf3!{
+///         dims { lat = 3; }
+///         vars { f32 temp(lat, lon); }
+///     }.unwrap_err()
+/// );
+/// ```
+#[macro_export]
+macro_rules! netcdf3 {
+    (
+        $(dims { $($dims:tt)* })?
+        $(attrs { $($global_attrs:tt)* })?
+        $(vars { $($vars:tt)* })?
+    ) => {
+        (|| -> ::core::result::Result<$crate::DataSet, $crate::InvalidDataSet> {
+            // the data set is not modified by an empty definition
+            #[allow(unused_mut)]
+            let mut data_set = $crate::DataSet::new();
+            $($crate::netcdf3!(@dims data_set; $($dims)*);)?
+            $($crate::netcdf3!(@global_attrs data_set; $($global_attrs)*);)?
+            $($crate::netcdf3!(@vars data_set; $($vars)*);)?
+            return ::core::result::Result::Ok(data_set);
+        })()
+    };
+
+    // Dimensions
+    (@dims $data_set:ident; ) => {};
+    (@dims $data_set:ident; $dim_name:ident = unlimited($num_records:expr); $($rest:tt)*) => {
+        $data_set.set_unlimited_dim(stringify!($dim_name), $num_records)?;
+        $crate::netcdf3!(@dims $data_set; $($rest)*);
+    };
+    (@dims $data_set:ident; $dim_name:ident = $dim_size:expr; $($rest:tt)*) => {
+        $data_set.add_fixed_dim(stringify!($dim_name), $dim_size)?;
+        $crate::netcdf3!(@dims $data_set; $($rest)*);
+    };
+
+    // Global attributes
+    (@global_attrs $data_set:ident; ) => {};
+    (@global_attrs $data_set:ident; $attr_name:ident = $data_type:ident($($value:expr),* $(,)?); $($rest:tt)*) => {
+        $crate::netcdf3!(@add_global_attr $data_set, stringify!($attr_name), $data_type, $crate::__private::vec![$($value),*]);
+        $crate::netcdf3!(@global_attrs $data_set; $($rest)*);
+    };
+    (@global_attrs $data_set:ident; $attr_name:ident = $text:expr; $($rest:tt)*) => {
+        $data_set.add_global_attr_string(stringify!($attr_name), $text)?;
+        $crate::netcdf3!(@global_attrs $data_set; $($rest)*);
+    };
+    (@add_global_attr $data_set:ident, $attr_name:expr, i8, $values:expr) => { $data_set.add_global_attr_i8($attr_name, $values)?; };
+    (@add_global_attr $data_set:ident, $attr_name:expr, u8, $values:expr) => { $data_set.add_global_attr_u8($attr_name, $values)?; };
+    (@add_global_attr $data_set:ident, $attr_name:expr, i16, $values:expr) => { $data_set.add_global_attr_i16($attr_name, $values)?; };
+    (@add_global_attr $data_set:ident, $attr_name:expr, i32, $values:expr) => { $data_set.add_global_attr_i32($attr_name, $values)?; };
+    (@add_global_attr $data_set:ident, $attr_name:expr, f32, $values:expr) => { $data_set.add_global_attr_f32($attr_name, $values)?; };
+    (@add_global_attr $data_set:ident, $attr_name:expr, f64, $values:expr) => { $data_set.add_global_attr_f64($attr_name, $values)?; };
+
+    // Variables
+    (@vars $data_set:ident; ) => {};
+    (@vars $data_set:ident; $data_type:ident $var_name:ident($($dim_name:ident),* $(,)?) { $($attrs:tt)* } $($rest:tt)*) => {
+        $crate::netcdf3!(@add_var $data_set, $data_type, $var_name, [$($dim_name),*]);
+        $crate::netcdf3!(@var_attrs $data_set, stringify!($var_name); $($attrs)*);
+        $crate::netcdf3!(@vars $data_set; $($rest)*);
+    };
+    (@vars $data_set:ident; $data_type:ident $var_name:ident($($dim_name:ident),* $(,)?); $($rest:tt)*) => {
+        $crate::netcdf3!(@add_var $data_set, $data_type, $var_name, [$($dim_name),*]);
+        $crate::netcdf3!(@vars $data_set; $($rest)*);
+    };
+    (@vars $data_set:ident; $data_type:ident $var_name:ident { $($attrs:tt)* } $($rest:tt)*) => {
+        $crate::netcdf3!(@add_var $data_set, $data_type, $var_name, []);
+        $crate::netcdf3!(@var_attrs $data_set, stringify!($var_name); $($attrs)*);
+        $crate::netcdf3!(@vars $data_set; $($rest)*);
+    };
+    (@vars $data_set:ident; $data_type:ident $var_name:ident; $($rest:tt)*) => {
+        $crate::netcdf3!(@add_var $data_set, $data_type, $var_name, []);
+        $crate::netcdf3!(@vars $data_set; $($rest)*);
+    };
+    (@add_var $data_set:ident, $data_type:ident, $var_name:ident, [$($dim_name:ident),*]) => {
+        let dim_names: &[&str] = &[$(stringify!($dim_name)),*];
+        $data_set.add_var(stringify!($var_name), dim_names, $crate::netcdf3!(@data_type $data_type))?;
+    };
+    (@data_type i8) => { $crate::DataType::I8 };
+    (@data_type u8) => { $crate::DataType::U8 };
+    (@data_type i16) => { $crate::DataType::I16 };
+    (@data_type i32) => { $crate::DataType::I32 };
+    (@data_type f32) => { $crate::DataType::F32 };
+    (@data_type f64) => { $crate::DataType::F64 };
+
+    // Variable attributes
+    (@var_attrs $data_set:ident, $var_name:expr; ) => {};
+    (@var_attrs $data_set:ident, $var_name:expr; $attr_name:ident = $data_type:ident($($value:expr),* $(,)?); $($rest:tt)*) => {
+        $crate::netcdf3!(@add_var_attr $data_set, $var_name, stringify!($attr_name), $data_type, $crate::__private::vec![$($value),*]);
+        $crate::netcdf3!(@var_attrs $data_set, $var_name; $($rest)*);
+    };
+    (@var_attrs $data_set:ident, $var_name:expr; $attr_name:ident = $text:expr; $($rest:tt)*) => {
+        $data_set.add_var_attr_string($var_name, stringify!($attr_name), $text)?;
+        $crate::netcdf3!(@var_attrs $data_set, $var_name; $($rest)*);
+    };
+    (@add_var_attr $data_set:ident, $var_name:expr, $attr_name:expr, i8, $values:expr) => { $data_set.add_var_attr_i8($var_name, $attr_name, $values)?; };
+    (@add_var_attr $data_set:ident, $var_name:expr, $attr_name:expr, u8, $values:expr) => { $data_set.add_var_attr_u8($var_name, $attr_name, $values)?; };
+    (@add_var_attr $data_set:ident, $var_name:expr, $attr_name:expr, i16, $values:expr) => { $data_set.add_var_attr_i16($var_name, $attr_name, $values)?; };
+    (@add_var_attr $data_set:ident, $var_name:expr, $attr_name:expr, i32, $values:expr) => { $data_set.add_var_attr_i32($var_name, $attr_name, $values)?; };
+    (@add_var_attr $data_set:ident, $var_name:expr, $attr_name:expr, f32, $values:expr) => { $data_set.add_var_attr_f32($var_name, $attr_name, $values)?; };
+    (@add_var_attr $data_set:ident, $var_name:expr, $attr_name:expr, f64, $values:expr) => { $data_set.add_var_attr_f64($var_name, $attr_name, $values)?; };
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(feature = "std"))]
+    use crate::alloc_prelude::*;
+    use crate::{DataSet, DataType, DimensionType, InvalidDataSet};
+
+    #[test]
+    fn test_netcdf3_macro() {
+        let data_set: DataSet = netcdf3!{
+            dims {
+                time = unlimited(2);
+                lat = 3;
+                lon = 2 * 2;
+            }
+            attrs {
+                title = String::from("Example");
+                flags = i8(1, 2, 3,);
+            }
+            vars {
+                f64 time(time) { units = "hours since 1970-01-01 00:00:00"; }
+                u8 name(lat, lon);
+                i16 temp(time, lat, lon) {
+                    units = "K";
+                    scale_factor = f32(0.01);
+                    add_offset = f64(273.15);
+                    _FillValue = i16(-1);
+                    valid_range = i32(0, 1000);
+                    code = u8(65);
+                }
+                f32 scalar_1;
+                i8 scalar_2 { comment = "scalar"; }
+            }
+        }.unwrap();
+
+        // dimensions
+        assert_eq!(vec!["time", "lat", "lon"],          data_set.dim_names());
+        assert_eq!(Some(DimensionType::UnlimitedSize),  data_set.dim_type("time"));
+        assert_eq!(Some(2),                             data_set.dim_size("time"));
+        assert_eq!(Some(DimensionType::FixedSize),      data_set.dim_type("lon"));
+        assert_eq!(Some(4),                             data_set.dim_size("lon"));
+
+        // global attributes
+        assert_eq!(Some(String::from("Example")),       data_set.get_global_attr_as_string("title"));
+        assert_eq!(Some(&[1, 2, 3][..]),                data_set.get_global_attr_i8("flags"));
+
+        // variables
+        assert_eq!(vec!["time", "name", "temp", "scalar_1", "scalar_2"],    data_set.get_var_names());
+        assert_eq!(DataType::U8,                        data_set["name"].data_type());
+        assert_eq!(vec!["lat", "lon"],                  data_set["name"].dim_names());
+        assert_eq!(0,                                   data_set["name"].num_attrs());
+        assert_eq!(vec!["time", "lat", "lon"],          data_set["temp"].dim_names());
+        assert_eq!(DataType::F32,                       data_set["scalar_1"].data_type());
+        assert_eq!(0,                                   data_set["scalar_1"].num_dims());
+        assert_eq!(Some(String::from("scalar")),        data_set["scalar_2"]["comment"].as_string());
+
+        // variable attributes
+        let temp = &data_set["temp"];
+        assert_eq!(vec!["units", "scale_factor", "add_offset", "_FillValue", "valid_range", "code"],    temp.get_attr_names());
+        assert_eq!(Some(String::from("K")),             temp["units"].as_string());
+        assert_eq!(Some(&[0.01][..]),                   temp["scale_factor"].get_f32());
+        assert_eq!(Some(&[273.15][..]),                 temp["add_offset"].get_f64());
+        assert_eq!(Some(&[-1][..]),                     temp["_FillValue"].get_i16());
+        assert_eq!(Some(&[0, 1000][..]),                temp["valid_range"].get_i32());
+        assert_eq!(Some(&[65][..]),                     temp["code"].get_u8());
+    }
+
+    #[test]
+    fn test_netcdf3_macro_empty() {
+        assert_eq!(DataSet::new(),                      netcdf3!{}.unwrap());
+        assert_eq!(DataSet::new(),                      netcdf3!{ dims {} attrs {} vars {} }.unwrap());
+    }
+
+    #[test]
+    fn test_netcdf3_macro_errors() {
+        assert_eq!(
+            InvalidDataSet::UnlimitedDimensionAlreadyExists(String::from("time")),
+            netcdf3!{ dims { time = unlimited(0); records = unlimited(0); } }.unwrap_err()
+        );
+        assert_eq!(
+            InvalidDataSet::VariableAlreadyExists(String::from("var_1")),
+            netcdf3!{ vars { i8 var_1; i16 var_1; } }.unwrap_err()
+        );
+        assert_eq!(
+            InvalidDataSet::VariableAttributeAlreadyExists{var_name: String::from("var_1"), attr_name: String::from("units")},
+            netcdf3!{ vars { i8 var_1 { units = "m"; units = "s"; } } }.unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,362 @@
+//! Declarative mappings normalizing the data sets to a common schema.
+//!
+//! A [`SchemaMapping`](struct.SchemaMapping.html) lists the variables to remove, the names to change, the data types to change
+//! and the attributes to set, so that the normalization of the files of a provider can be stored and shared as data.
+//! It is applied to a data set and to the values loaded in its variables with [`apply`](struct.SchemaMapping.html#method.apply),
+//! and to the values of the variables read separately with [`apply_data`](struct.SchemaMapping.html#method.apply_data).
+//!
+//! # Example
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use netcdf3::{AttrValue, DataSet, DataType, DataVector};
+//! use netcdf3::mapping::SchemaMapping;
+//!
+//! // the file of a provider
+//! let mut data_set = DataSet::new();
+//! data_set.add_fixed_dim("lat", 2).unwrap();
+//! data_set.add_var_f64("lat", &["lat"]).unwrap();
+//! data_set.add_var_f64("t2m", &["lat"]).unwrap();
+//! data_set.add_var_attr_f64("t2m", "_FillValue", vec![-999.0]).unwrap();
+//! data_set.add_var_i32("quality", &["lat"]).unwrap();
+//! let mut data: HashMap<String, DataVector> = HashMap::new();
+//! data.insert(String::from("lat"), DataVector::F64(vec![0.0, 0.5]));
+//! data.insert(String::from("t2m"), DataVector::F64(vec![273.15, -999.0]));
+//! data.insert(String::from("quality"), DataVector::I32(vec![1, 0]));
+//!
+//! // the common schema
+//! let mapping = SchemaMapping::new()
+//!     .drop_var("quality")
+//!     .rename("lat", "latitude")
+//!     .rename("t2m", "temperature")
+//!     .cast("temperature", DataType::F32)
+//!     .set_var_attr("temperature", "units", AttrValue::Str("K"))
+//!     .set_global_attr("Conventions", AttrValue::Str("CF-1.8"));
+//!
+//! mapping.apply(&mut data_set).unwrap();
+//! mapping.apply_data(&mut data);
+//!
+//! assert_eq!(vec!["latitude"],                            data_set.dim_names());
+//! assert_eq!(vec!["latitude", "temperature"],             data_set.get_var_names());
+//! assert_eq!(Some(DataType::F32),                         data_set.var_data_type("temperature"));
+//! assert_eq!(Some(&[-999.0_f32][..]),                     data_set.get_var_attr_f32("temperature", "_FillValue"));
+//! assert_eq!(Some(String::from("K")),                     data_set.get_var_attr_as_string("temperature", "units"));
+//! assert_eq!(Some(String::from("CF-1.8")),                data_set.get_global_attr_as_string("Conventions"));
+//!
+//! assert_eq!(2,                                           data.len());
+//! assert_eq!(Some(&DataVector::F32(vec![273.15, -999.0])),    data.get("temperature"));
+//! ```
+use std::collections::HashMap;
+
+use crate::{Attribute, AttrValue, DataSet, DataType, DataVector, InvalidDataSet};
+use crate::cf::pack;
+use crate::data_set::SAME_TYPE_ATTR_NAMES;
+
+/// Attribute set by a [`SchemaMapping`](struct.SchemaMapping.html).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MappedAttr {
+    /// Name of the variable of the attribute, `None` for a global attribute.
+    pub var_name: Option<String>,
+    /// Name of the attribute.
+    pub attr_name: String,
+    /// Values of the attribute.
+    pub value: DataVector,
+}
+
+/// Declarative mapping of a data set to a common schema (see the module [`mapping`](index.html)).
+///
+/// The steps are applied in this order, the names used by each step being the names resulting from the previous steps :
+///
+/// 1. the variables `drop_vars` are removed,
+/// 2. the dimensions, the variables and the attributes are renamed by the pairs `renames`
+///    (see [`DataSet::rename_many`](../struct.DataSet.html#method.rename_many)),
+/// 3. the data types of the variables `casts` are changed,
+/// 4. the attributes `attrs` are set, replacing the attributes already defined.
+///
+/// The variables which are not defined are ignored by each step, so that the same mapping can normalize files missing some variables.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SchemaMapping {
+    /// Names of the removed variables.
+    pub drop_vars: Vec<String>,
+    /// Pairs `(old_name, new_name)` renaming the dimensions, the variables and the attributes.
+    pub renames: Vec<(String, String)>,
+    /// Pairs `(var_name, data_type)` changing the data types of the variables.
+    pub casts: Vec<(String, DataType)>,
+    /// Attributes set in the variables or in the global attributes.
+    pub attrs: Vec<MappedAttr>,
+}
+
+impl SchemaMapping {
+    /// Creates an empty mapping, leaving the data sets unchanged.
+    pub fn new() -> SchemaMapping {
+        return SchemaMapping::default();
+    }
+
+    /// Removes the variable `var_name`.
+    pub fn drop_var(mut self, var_name: &str) -> SchemaMapping {
+        self.drop_vars.push(var_name.to_string());
+        return self;
+    }
+
+    /// Renames the dimensions, the variables and the attributes named `old_name`.
+    pub fn rename(mut self, old_name: &str, new_name: &str) -> SchemaMapping {
+        self.renames.push((old_name.to_string(), new_name.to_string()));
+        return self;
+    }
+
+    /// Changes the data type of the variable `var_name` (its name after the renames).
+    pub fn cast(mut self, var_name: &str, data_type: DataType) -> SchemaMapping {
+        self.casts.push((var_name.to_string(), data_type));
+        return self;
+    }
+
+    /// Sets the attribute `attr_name` of the variable `var_name` (its name after the renames).
+    pub fn set_var_attr(mut self, var_name: &str, attr_name: &str, value: AttrValue) -> SchemaMapping {
+        self.attrs.push(MappedAttr{
+            var_name: Some(var_name.to_string()),
+            attr_name: attr_name.to_string(),
+            value: value.to_data_vector(),
+        });
+        return self;
+    }
+
+    /// Sets the global attribute `attr_name`.
+    pub fn set_global_attr(mut self, attr_name: &str, value: AttrValue) -> SchemaMapping {
+        self.attrs.push(MappedAttr{
+            var_name: None,
+            attr_name: attr_name.to_string(),
+            value: value.to_data_vector(),
+        });
+        return self;
+    }
+
+    /// Applies the mapping to the definition of the data set.
+    ///
+    /// The loaded values of the variables (see [`Variable::data`](../struct.Variable.html#method.data)) are removed, renamed and casted
+    /// with their variables, like by [`apply_data`](#method.apply_data). The attributes `_FillValue` and `missing_value` of the casted variables
+    /// are converted to their new data type (the integer values being rounded and saturated).
+    ///
+    /// The mapping is applied to a copy of the data set, which replaces the data set only if all the steps succeed :
+    /// the data set is not modified if an error is returned.
+    pub fn apply(&self, data_set: &mut DataSet) -> Result<(), InvalidDataSet> {
+        let mut mapped_data_set: DataSet = data_set.deep_clone();
+        self.apply_steps(&mut mapped_data_set)?;
+        *data_set = mapped_data_set;
+        return Ok(());
+    }
+
+    /// Applies the steps of the mapping to the data set, stopping at the first error.
+    fn apply_steps(&self, data_set: &mut DataSet) -> Result<(), InvalidDataSet> {
+        for var_name in self.drop_vars.iter() {
+            if data_set.has_var(var_name) {
+                data_set.remove_var(var_name)?;
+            }
+        }
+
+        let renames: Vec<(&str, &str)> = self.renames.iter().map(|(old_name, new_name): &(String, String)| (old_name.as_str(), new_name.as_str())).collect();
+        data_set.rename_many(&renames)?;
+
+        for (var_name, data_type) in self.casts.iter() {
+            let (var_attrs, var_data): (Vec<Attribute>, Option<DataVector>) = match data_set.get_var(var_name) {
+                None => continue,
+                Some(var) => (
+                    var.get_attrs().into_iter().filter(|attr: &&Attribute| SAME_TYPE_ATTR_NAMES.contains(&attr.name())).cloned().collect(),
+                    var.data().filter(|values: &&DataVector| values.data_type() != *data_type).map(|values: &DataVector| cast_values(values, *data_type)),
+                ),
+            };
+            data_set.set_var_data_type(var_name, *data_type)?;
+            if let Some(var_data) = var_data {
+                if let Some(var) = data_set.get_var_mut(var_name) {
+                    var.data = Some(var_data);
+                }
+            }
+            for attr in var_attrs.into_iter() {
+                let casted_attr = Attribute::from_data(attr.name().to_string(), cast_values(attr.data(), *data_type));
+                data_set.set_var_attr_from(var_name, casted_attr)?;
+            }
+        }
+
+        for mapped_attr in self.attrs.iter() {
+            let attr = Attribute::from_data(mapped_attr.attr_name.clone(), mapped_attr.value.clone());
+            match &mapped_attr.var_name {
+                None => data_set.set_global_attr_from(attr)?,
+                Some(var_name) => if data_set.has_var(var_name) {
+                    data_set.set_var_attr_from(var_name, attr)?;
+                },
+            }
+        }
+        return Ok(());
+    }
+
+    /// Applies the mapping to the values of the variables indexed by their names
+    /// (see [`FileReader::read_all_vars`](../struct.FileReader.html#method.read_all_vars)).
+    ///
+    /// The values are removed, renamed and casted like their variables by [`apply`](#method.apply),
+    /// the integer values being rounded and saturated and the NaN values becoming the default fill values.
+    pub fn apply_data(&self, data: &mut HashMap<String, DataVector>) {
+        for var_name in self.drop_vars.iter() {
+            data.remove(var_name);
+        }
+
+        let renamed_names: Vec<(String, String)> = data.keys().filter_map(|var_name: &String| {
+            // the same rename as the one of the variable (see `DataSet::rename_many`)
+            self.renames.iter()
+                .find(|(old_name, new_name): &&(String, String)| old_name == var_name && new_name != var_name)
+                .cloned()
+        }).collect();
+        // the values are renamed simultaneously
+        let renamed_values: Vec<(String, DataVector)> = renamed_names.into_iter().filter_map(|(old_name, new_name): (String, String)| {
+            data.remove(&old_name).map(|values: DataVector| (new_name, values))
+        }).collect();
+        data.extend(renamed_values);
+
+        for (var_name, data_type) in self.casts.iter() {
+            if let Some(values) = data.get_mut(var_name) {
+                if values.data_type() != *data_type {
+                    *values = cast_values(values, *data_type);
+                }
+            }
+        }
+    }
+}
+
+/// Converts the values to the data type `data_type`.
+///
+/// The NaN values are kept by the floating-point data types, and become the default fill values of the integer data types.
+fn cast_values(values: &DataVector, data_type: DataType) -> DataVector {
+    let values: Vec<f64> = values.get_as_f64_vec();
+    return match data_type {
+        DataType::F32 => DataVector::F32(values.iter().map(|value: &f64| *value as f32).collect()),
+        DataType::F64 => DataVector::F64(values),
+        _ => pack(&values, 1.0, 0.0, data_type),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    use super::{MappedAttr, SchemaMapping};
+    use crate::{AttrValue, DataSet, DataType, DataVector, Dimension, InvalidDataSet};
+    use crate::{NC_FILL_I16, NC_FILL_I32};
+
+    #[test]
+    fn test_schema_mapping_builder() {
+        let mapping = SchemaMapping::new()
+            .drop_var("var_1")
+            .rename("var_2", "var_3")
+            .cast("var_3", DataType::I16)
+            .set_var_attr("var_3", "units", AttrValue::Str("K"))
+            .set_global_attr("title", AttrValue::Str("title"));
+        assert_eq!(
+            SchemaMapping{
+                drop_vars: vec![String::from("var_1")],
+                renames: vec![(String::from("var_2"), String::from("var_3"))],
+                casts: vec![(String::from("var_3"), DataType::I16)],
+                attrs: vec![
+                    MappedAttr{var_name: Some(String::from("var_3")), attr_name: String::from("units"), value: DataVector::U8(b"K".to_vec())},
+                    MappedAttr{var_name: None, attr_name: String::from("title"), value: DataVector::U8(b"title".to_vec())},
+                ],
+            },
+            mapping
+        );
+        assert_eq!(SchemaMapping::default(),        SchemaMapping::new());
+    }
+
+    #[test]
+    fn test_schema_mapping_apply() {
+        let mut data_set = DataSet::new();
+        data_set.set_unlimited_dim("time", 2).unwrap();
+        data_set.add_var_f64("time", &["time"]).unwrap();
+        data_set.add_var_f64("sst", &["time"]).unwrap();
+        data_set.add_var_attr_f64("sst", "missing_value", vec![f64::NAN, 1e10]).unwrap();
+        data_set.add_var_attr_string("sst", "units", "degC").unwrap();
+        data_set.add_global_attr_string("title", "vendor file").unwrap();
+        data_set.get_var_mut("time").unwrap().data = Some(DataVector::F64(vec![0.0, 1.0]));
+        data_set.get_var_mut("sst").unwrap().data = Some(DataVector::F64(vec![14.6, f64::NAN]));
+
+        let mapping = SchemaMapping::new()
+            // not defined
+            .drop_var("quality")
+            .rename("sst", "sea_surface_temperature")
+            .cast("sea_surface_temperature", DataType::I16)
+            .cast("salinity", DataType::F32)
+            .set_var_attr("sea_surface_temperature", "units", AttrValue::Str("K"))
+            .set_var_attr("salinity", "units", AttrValue::Str("1e-3"))
+            .set_global_attr("title", AttrValue::Str("common schema"));
+        mapping.apply(&mut data_set).unwrap();
+
+        assert_eq!(vec!["time", "sea_surface_temperature"],             data_set.get_var_names());
+        assert_eq!(Some(DataType::I16),                                 data_set.var_data_type("sea_surface_temperature"));
+        assert_eq!(Some(&[NC_FILL_I16, i16::MAX][..]),                  data_set.get_var_attr_i16("sea_surface_temperature", "missing_value"));
+        // the attributes are replaced in place
+        assert_eq!(
+            vec!["missing_value", "units"],
+            data_set.get_var("sea_surface_temperature").unwrap().get_attr_names()
+        );
+        assert_eq!(Some(String::from("K")),                             data_set.get_var_attr_as_string("sea_surface_temperature", "units"));
+        assert_eq!(Some(String::from("common schema")),                 data_set.get_global_attr_as_string("title"));
+        // 8 bytes of `time` and 2 bytes of `sea_surface_temperature` padded to 4 bytes
+        assert_eq!(Some(12),                                            data_set.record_size());
+        // the loaded values are renamed and casted with their variables
+        assert_eq!(Some(&DataVector::F64(vec![0.0, 1.0])),              data_set["time"].data());
+        assert_eq!(Some(&DataVector::I16(vec![15, NC_FILL_I16])),       data_set["sea_surface_temperature"].data());
+
+        // the data type of `_FillValue` must be the data type of the variable
+        let mapping = SchemaMapping::new()
+            .rename("time", "t")
+            .drop_var("sea_surface_temperature")
+            .set_var_attr("t", "_FillValue", AttrValue::I32(0));
+        assert_eq!(
+            Err(InvalidDataSet::VariableAttributeMismatchDataType{
+                var_name: String::from("t"),
+                attr_name: String::from("_FillValue"),
+                req: DataType::F64,
+                get: DataType::I32,
+            }),
+            mapping.apply(&mut data_set)
+        );
+        // the data set is not modified by the previous steps
+        assert_eq!(vec!["time", "sea_surface_temperature"],             data_set.get_var_names());
+        assert_eq!(vec!["time"],                                        data_set.dim_names());
+        assert_eq!(Some(String::from("time")),                          data_set.get_unlimited_dim().map(|dim: Rc<Dimension>| dim.name()));
+        assert_eq!(vec![String::from("time")],                          data_set.get_var("time").unwrap().dim_names());
+        assert_eq!(Some(&DataVector::I16(vec![15, NC_FILL_I16])),       data_set["sea_surface_temperature"].data());
+    }
+
+    #[test]
+    fn test_schema_mapping_apply_data() {
+        let mut data: HashMap<String, DataVector> = HashMap::new();
+        data.insert(String::from("a"), DataVector::F64(vec![1.4, f64::NAN]));
+        data.insert(String::from("b"), DataVector::I8(vec![1, 2]));
+        data.insert(String::from("c"), DataVector::F32(vec![f32::NAN]));
+
+        // the names `a` and `b` are swapped
+        let mapping = SchemaMapping::new()
+            .drop_var("c")
+            .rename("a", "b")
+            .rename("b", "a")
+            .rename("a", "c")
+            .cast("b", DataType::I32)
+            .cast("a", DataType::F64);
+        mapping.apply_data(&mut data);
+
+        assert_eq!(2,                                               data.len());
+        assert_eq!(Some(&DataVector::I32(vec![1, NC_FILL_I32])),    data.get("b"));
+        assert_eq!(Some(&DataVector::F64(vec![1.0, 2.0])),          data.get("a"));
+
+        // the renames to the same name are skipped like by `DataSet::rename_many`
+        let mut data: HashMap<String, DataVector> = HashMap::new();
+        data.insert(String::from("a"), DataVector::I8(vec![1]));
+        SchemaMapping::new().rename("a", "a").rename("a", "b").apply_data(&mut data);
+        assert_eq!(vec![String::from("b")],                         data.keys().cloned().collect::<Vec<String>>());
+
+        // the NaN values are kept by the floating-point data types
+        let mut data: HashMap<String, DataVector> = HashMap::new();
+        data.insert(String::from("a"), DataVector::F64(vec![f64::NAN]));
+        SchemaMapping::new().cast("a", DataType::F32).apply_data(&mut data);
+        assert_eq!(true,                                            data["a"].get_f32().unwrap()[0].is_nan());
+    }
+}
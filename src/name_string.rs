@@ -1,8 +1,42 @@
+#[cfg(feature = "normalization")]
+use alloc::string::String;
+
+#[cfg(feature = "normalization")]
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+
 /// Maximum size (number of bytes) allowed for the NetCDF names.
 ///
 pub const NC_MAX_NAME_SIZE: usize = 256;
 
-/// Checks that `name` follows the NetCDF-3 naming convention.
+/// Set of rules applied to check the NetCDF-3 names (see [`is_valid_name_with`](fn.is_valid_name_with.html)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameValidation {
+    /// Full grammar of the NetCDF classic names, as checked by the NetCDF C library when the names are defined (default).
+    ///
+    /// ``` text
+    /// name         = (idchar | utf8) (idchar | utf8 | special1 | special2)*
+    /// idchar       = [a-zA-Z0-9_]
+    /// ```
+    ///
+    /// The trailing spaces are not allowed, and the multibyte UTF-8 names must be normalized (NFC, see
+    /// [`normalize_name`](fn.normalize_name.html)). The normalization is only checked with the feature `normalization`
+    /// (enabled by default).
+    Strict,
+    /// Rules of the names which can be stored in a file and read back: the first character may be a special character,
+    /// the trailing spaces are allowed and the names are not required to be normalized.
+    ///
+    /// The empty names, the names exceeding [`NC_MAX_NAME_SIZE`](constant.NC_MAX_NAME_SIZE.html) bytes and the names containing
+    /// control characters or `'/'` are still rejected.
+    Lenient,
+}
+
+impl Default for NameValidation {
+    fn default() -> Self {
+        return NameValidation::Strict;
+    }
+}
+
+/// Checks that `name` follows the NetCDF-3 naming convention (see [`NameValidation::Strict`](enum.NameValidation.html#variant.Strict)).
 ///
 /// # Examples
 ///
@@ -14,38 +48,178 @@ pub const NC_MAX_NAME_SIZE: usize = 256;
 /// assert_eq!(true,    is_valid_name("_FillValue"));
 /// assert_eq!(true,    is_valid_name("café"));  // the UTF-8 encoded characters are supported
 /// assert_eq!(true,    is_valid_name("A"));
+/// assert_eq!(true,    is_valid_name("2m_temperature"));
 ///
 /// assert_eq!(false,   is_valid_name(""));
 /// assert_eq!(false,   is_valid_name("!invalid_name"));
+/// assert_eq!(false,   is_valid_name("trailing_space "));
+/// assert_eq!(false,   is_valid_name("cafe\u{301}"));  // not normalized (NFC)
 /// ```
 pub fn is_valid_name(name: &str) -> bool {
+    return is_valid_name_with(name, NameValidation::Strict);
+}
+
+/// Checks that `name` follows the rules `name_validation`.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{is_valid_name_with, NameValidation};
+///
+/// assert_eq!(false,   is_valid_name_with(".hidden", NameValidation::Strict));
+/// assert_eq!(true,    is_valid_name_with(".hidden", NameValidation::Lenient));
+/// assert_eq!(false,   is_valid_name_with("trailing_space ", NameValidation::Strict));
+/// assert_eq!(true,    is_valid_name_with("trailing_space ", NameValidation::Lenient));
+///
+/// assert_eq!(false,   is_valid_name_with("path/name", NameValidation::Lenient));
+/// assert_eq!(false,   is_valid_name_with("tab\tname", NameValidation::Lenient));
+/// ```
+pub fn is_valid_name_with(name: &str, name_validation: NameValidation) -> bool {
+    return validate_name_with(name, name_validation).is_ok();
+}
+
+/// Reason why a name does not follow the NetCDF-3 naming convention (see [`validate_name`](fn.validate_name.html)).
+///
+/// The indices are byte indices of the name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NameError {
+    /// The name is empty.
+    Empty,
+    /// The name exceeds [`NC_MAX_NAME_SIZE`](constant.NC_MAX_NAME_SIZE.html) bytes.
+    TooLong{max: usize, get: usize},
+    /// The character is never allowed in the names (a control character or `'/'`).
+    CharNotAllowed{chr: char, index: usize},
+    /// The name begins with an ASCII character other than a letter, a digit or `'_'` (strict rules only).
+    FirstCharNotAllowed{chr: char},
+    /// The name ends with a space (strict rules only), `index` being the index of the first trailing space.
+    TrailingSpace{index: usize},
+    /// The name is not normalized (NFC, strict rules only, see [`normalize_name`](fn.normalize_name.html)).
+    NotNormalized,
+}
+
+impl core::fmt::Display for NameError {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        return match self {
+            NameError::Empty => write!(f, "the name is empty"),
+            NameError::TooLong{max, get} => write!(f, "the name has {} bytes, {} bytes at most are allowed", get, max),
+            NameError::CharNotAllowed{chr, index} => write!(f, "the character {:?} at the byte {} is not allowed", chr, index),
+            NameError::FirstCharNotAllowed{chr} => write!(f, "the name cannot begin with the character {:?}", chr),
+            NameError::TrailingSpace{index} => write!(f, "the name ends with spaces from the byte {}", index),
+            NameError::NotNormalized => write!(f, "the name is not normalized (NFC)"),
+        };
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NameError {}
+
+/// Checks that `name` follows the NetCDF-3 naming convention (see [`NameValidation::Strict`](enum.NameValidation.html#variant.Strict)),
+/// and returns the first rule broken otherwise.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{validate_name, NameError, NC_MAX_NAME_SIZE};
+///
+/// assert_eq!(Ok(()),                                                  validate_name("temperature"));
+///
+/// assert_eq!(Err(NameError::Empty),                                   validate_name(""));
+/// assert_eq!(Err(NameError::TooLong{max: NC_MAX_NAME_SIZE, get: 257}),  validate_name(&"a".repeat(257)));
+/// assert_eq!(Err(NameError::CharNotAllowed{chr: '/', index: 4}),      validate_name("path/name"));
+/// assert_eq!(Err(NameError::FirstCharNotAllowed{chr: '!'}),           validate_name("!invalid_name"));
+/// assert_eq!(Err(NameError::TrailingSpace{index: 14}),                validate_name("trailing_space  "));
+/// assert_eq!(Err(NameError::NotNormalized),                           validate_name("cafe\u{301}"));
+/// ```
+pub fn validate_name(name: &str) -> Result<(), NameError> {
+    return validate_name_with(name, NameValidation::Strict);
+}
+
+/// Checks that `name` follows the rules `name_validation`, and returns the first rule broken otherwise.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{validate_name_with, NameError, NameValidation};
+///
+/// assert_eq!(Err(NameError::FirstCharNotAllowed{chr: '.'}),       validate_name_with(".hidden", NameValidation::Strict));
+/// assert_eq!(Ok(()),                                              validate_name_with(".hidden", NameValidation::Lenient));
+/// assert_eq!(Err(NameError::CharNotAllowed{chr: '\t', index: 3}), validate_name_with("tab\tname", NameValidation::Lenient));
+/// ```
+pub fn validate_name_with(name: &str, name_validation: NameValidation) -> Result<(), NameError> {
+    if name.is_empty() {
+        return Err(NameError::Empty);
+    }
+    if name.len() > NC_MAX_NAME_SIZE {
+        return Err(NameError::TooLong{max: NC_MAX_NAME_SIZE, get: name.len()});
+    }
+    return validate_name_chars(name, name_validation);
+}
+
+/// Checks `name` with the rules `name_validation`, the names exceeding [`NC_MAX_NAME_SIZE`](constant.NC_MAX_NAME_SIZE.html) bytes
+/// being accepted if `allow_long_names` (see [`LongNamePolicy::Preserve`](enum.LongNamePolicy.html#variant.Preserve)).
+pub(crate) fn check_name(name: &str, name_validation: NameValidation, allow_long_names: bool) -> Result<(), NameError> {
+    return match validate_name_with(name, name_validation) {
+        Err(NameError::TooLong{..}) if allow_long_names => validate_name_chars(name, name_validation),
+        result => result,
+    };
+}
+
+/// Checks the characters of the non-empty `name` with the rules `name_validation`, its size is not checked.
+fn validate_name_chars(name: &str, name_validation: NameValidation) -> Result<(), NameError> {
+    if let Some((index, chr)) = name.char_indices().find(|(_index, chr): &(usize, char)| !is_name_char(*chr)) {
+        return Err(NameError::CharNotAllowed{chr: chr, index: index});
+    }
+    if name_validation == NameValidation::Lenient {
+        return Ok(());
+    }
     // check the first character
-    match name.chars().nth(0) {
-        None => {
-            // then the name string is empty
-            return false;
-        }
-        Some(c) => {
-            if c.is_ascii() {
-                if !(c.is_alphanumeric() || c == '_') {
-                    return false;
-                }
-            }
-        }
+    let first_char: char = name.chars().next().unwrap_or('_');
+    if first_char.is_ascii() && !(first_char.is_ascii_alphanumeric() || first_char == '_') {
+        return Err(NameError::FirstCharNotAllowed{chr: first_char});
     }
-    if name.as_bytes().len() > NC_MAX_NAME_SIZE {
-        return false;
+    // check the last characters
+    if name.ends_with(' ') {
+        return Err(NameError::TrailingSpace{index: name.trim_end_matches(' ').len()});
     }
-    for c in name.chars().skip(1) {
-        if !(c.is_alphanumeric()) {
-            if c.is_ascii() {
-                if !(is_special_1(c) || is_special_2(c)) {
-                    return false;
-                }
-            }
-        }
+    #[cfg(feature = "normalization")]
+    if !name.is_ascii() && !is_nfc(name) {
+        return Err(NameError::NotNormalized);
     }
-    return true;
+    return Ok(());
+}
+
+/// Returns the normalized form (NFC) of the name, as stored by the NetCDF C library.
+///
+/// Requires the feature `normalization` (enabled by default).
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{is_valid_name, normalize_name};
+///
+/// let name: &str = "cafe\u{301}";  // decomposed form of `café`
+/// assert_eq!(false,               is_valid_name(name));
+/// assert_eq!("caf\u{e9}",         normalize_name(name));
+/// assert_eq!(true,                is_valid_name(&normalize_name(name)));
+/// ```
+#[cfg(feature = "normalization")]
+pub fn normalize_name(name: &str) -> String {
+    return name.nfc().collect();
+}
+
+/// Returns the longest prefix of `name` not exceeding `max_size` bytes (usually [`NC_MAX_NAME_SIZE`](constant.NC_MAX_NAME_SIZE.html)),
+/// without splitting a multibyte character.
+pub(crate) fn truncate_name(name: &str, max_size: usize) -> &str {
+    let end: usize = (0..=core::cmp::min(name.len(), max_size)).rev()
+        .find(|index: &usize| name.is_char_boundary(*index))
+        .unwrap_or(0);
+    return &name[0..end];
+}
+
+/// Returns `true` if the `char` is allowed in the NetCDF-3 names : alphanumeric, special1 or special2 ASCII characters,
+/// and the non-ASCII characters (multibyte UTF-8).
+fn is_name_char(chr: char) -> bool {
+    return !chr.is_ascii() || chr.is_ascii_alphanumeric() || is_special_1(chr) || is_special_2(chr);
 }
 
 /// Returns `true` if the `char` is a NetCDF-3 special1 characters.
@@ -98,7 +272,9 @@ fn is_special_2(chr: char) -> bool {
 #[cfg(test)]
 mod tests {
 
-    use super::{is_special_1, is_special_2, is_valid_name, NC_MAX_NAME_SIZE};
+    #[cfg(feature = "normalization")]
+    use super::normalize_name;
+    use super::{check_name, is_special_1, is_special_2, is_valid_name, is_valid_name_with, truncate_name, validate_name, validate_name_with, NameError, NameValidation, NC_MAX_NAME_SIZE};
 
     #[test]
     fn test_some_valid_name_strings() {
@@ -126,6 +302,75 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_name_validation() {
+        // names accepted by both validations
+        for name in ["foo", "2foo", "_foo", "foo bar", "foo.bar@baz+qux-quux", "àfoo", "foo_€"].iter() {
+            assert_eq!(true,        is_valid_name_with(name, NameValidation::Strict),   "{:?}", name);
+            assert_eq!(true,        is_valid_name_with(name, NameValidation::Lenient),  "{:?}", name);
+        }
+        // names accepted only by the lenient validation
+        for name in [".foo", "-foo", " foo", "foo ", "!foo"].iter() {
+            assert_eq!(false,       is_valid_name_with(name, NameValidation::Strict),   "{:?}", name);
+            assert_eq!(true,        is_valid_name_with(name, NameValidation::Lenient),  "{:?}", name);
+        }
+        // name not normalized
+        assert_eq!(!cfg!(feature = "normalization"),    is_valid_name_with("e\u{301}foo", NameValidation::Strict));
+        assert_eq!(true,                                is_valid_name_with("e\u{301}foo", NameValidation::Lenient));
+        // names rejected by both validations
+        for name in ["", "foo/bar", "/foo", "foo\0", "foo\n", "\tfoo", "foo\x7f"].iter() {
+            assert_eq!(false,       is_valid_name_with(name, NameValidation::Strict),   "{:?}", name);
+            assert_eq!(false,       is_valid_name_with(name, NameValidation::Lenient),  "{:?}", name);
+        }
+        let too_long_name: String = "a".chars().cycle().take(NC_MAX_NAME_SIZE + 1).collect();
+        assert_eq!(false,           is_valid_name_with(&too_long_name, NameValidation::Lenient));
+    }
+
+    #[test]
+    fn test_validate_name() {
+        assert_eq!(Ok(()),                                              validate_name("foo"));
+        assert_eq!(Err(NameError::CharNotAllowed{chr: '\0', index: 3}), validate_name("foo\0"));
+        // the indices are byte indices
+        assert_eq!(Err(NameError::CharNotAllowed{chr: '/', index: 3}),  validate_name("\u{e9}f/oo"));
+        assert_eq!(Err(NameError::TrailingSpace{index: 4}),             validate_name("\u{e9}fo  "));
+        // the characters never allowed are reported before the strict rules
+        assert_eq!(Err(NameError::CharNotAllowed{chr: '\n', index: 4}), validate_name(".foo\n"));
+        assert_eq!(Err(NameError::FirstCharNotAllowed{chr: ' '}),       validate_name(" foo "));
+
+        let too_long_name: String = "\u{e9}".repeat(NC_MAX_NAME_SIZE / 2 + 1);
+        assert_eq!(Err(NameError::TooLong{max: NC_MAX_NAME_SIZE, get: NC_MAX_NAME_SIZE + 2}),  validate_name_with(&too_long_name, NameValidation::Lenient));
+        assert_eq!(Ok(()),                                              validate_name_with("e\u{301}foo ", NameValidation::Lenient));
+    }
+
+    #[test]
+    fn test_check_name() {
+        let long_name: String = "a".repeat(NC_MAX_NAME_SIZE + 1);
+        assert_eq!(Err(NameError::TooLong{max: NC_MAX_NAME_SIZE, get: NC_MAX_NAME_SIZE + 1}),  check_name(&long_name, NameValidation::Strict, false));
+        assert_eq!(Ok(()),                                              check_name(&long_name, NameValidation::Strict, true));
+        // the other rules are still checked
+        assert_eq!(Err(NameError::TrailingSpace{index: NC_MAX_NAME_SIZE + 1}), check_name(&(long_name + " "), NameValidation::Strict, true));
+        assert_eq!(Err(NameError::Empty),                               check_name("", NameValidation::Lenient, true));
+    }
+
+    #[test]
+    fn test_truncate_name() {
+        assert_eq!("foo",                                               truncate_name("foo", NC_MAX_NAME_SIZE));
+        assert_eq!("fo",                                                truncate_name("foo", 2));
+        let long_name: String = "a".repeat(NC_MAX_NAME_SIZE + 10);
+        assert_eq!(&long_name[0..NC_MAX_NAME_SIZE],                     truncate_name(&long_name, NC_MAX_NAME_SIZE));
+        // the 2-byte character crossing the limit is removed
+        let long_utf8_name: String = String::from("a") + &"\u{e9}".repeat(NC_MAX_NAME_SIZE / 2);
+        assert_eq!(NC_MAX_NAME_SIZE - 1,                                truncate_name(&long_utf8_name, NC_MAX_NAME_SIZE).len());
+    }
+
+    #[test]
+    #[cfg(feature = "normalization")]
+    fn test_normalize_name() {
+        assert_eq!("foo",           normalize_name("foo"));
+        assert_eq!("\u{e9}foo",     normalize_name("e\u{301}foo"));
+        assert_eq!("\u{e9}foo",     normalize_name("\u{e9}foo"));
+    }
+
     #[test]
     fn test_is_special_1() {
         // test all special 1 characters
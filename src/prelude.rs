@@ -0,0 +1,31 @@
+//! Commonly used items, imported at once with `use netcdf3::prelude::*;`.
+//!
+//! # Example
+//!
+//! ```
+//! use std::io::Cursor;
+//! use netcdf3::prelude::*;
+//!
+//! let mut data_set = DataSet::new();
+//! let time: DimId = data_set.set_unlimited_dim("time", 2).unwrap();
+//! data_set.add_var("time", &[time], DataType::F64).unwrap();
+//!
+//! let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+//! file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+//! file_writer.write_var_f64("time", &[0.0, 1.0]).unwrap();
+//!
+//! let mut file_reader = FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+//! let time: &Variable = &file_reader.data_set()["time"];
+//! assert_eq!(DataType::F64,                       time.data_type());
+//! assert_eq!(Ok(DataVector::F64(vec![0.0, 1.0])), file_reader.read_var("time"));
+//! ```
+
+pub use crate::{Attribute, AttrValue, DataSet, DataType, DataSlice, DataVector, DimId, Dimension, InvalidDataSet, Variable, Version};
+#[cfg(feature = "std")]
+pub use crate::{ReadError, WriteError};
+#[cfg(feature = "read")]
+pub use crate::FileReader;
+#[cfg(feature = "write")]
+pub use crate::FileWriter;
+#[cfg(all(feature = "read", feature = "write"))]
+pub use crate::{NcRecord, NcValue};
@@ -0,0 +1,349 @@
+//! Property-based testing utilities (requires the feature `testing`).
+//!
+//! Generates random valid data sets (dimensions, variables, attributes and data) from a seed,
+//! and checks that they are written and read back unchanged. The crates extending this library
+//! can run the same checks on their own changes.
+//!
+//! # Example
+//!
+//! ```
+//! use netcdf3::Version;
+//! use netcdf3::testing::{arbitrary_data_set, arbitrary_vars_data, assert_round_trip_with_data, TestRng};
+//!
+//! let mut rng = TestRng::new(42);
+//! for _ in 0..20 {
+//!     let data_set = arbitrary_data_set(&mut rng);
+//!     let vars_data = arbitrary_vars_data(&data_set, &mut rng);
+//!     assert_round_trip_with_data(&data_set, &vars_data, Version::Classic);
+//!     assert_round_trip_with_data(&data_set, &vars_data, Version::Offset64Bit);
+//! }
+//! ```
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::rc::Rc;
+
+use crate::{DataSet, DataType, DataVector, Dimension, FileReader, FileWriter, Version};
+use crate::{NC_FILL_I8, NC_FILL_U8, NC_FILL_I16, NC_FILL_I32, NC_FILL_F32, NC_FILL_F64};
+use crate::io::write_var_data;
+
+const DATA_TYPES: [DataType; 6] = [DataType::I8, DataType::U8, DataType::I16, DataType::I32, DataType::F32, DataType::F64];
+const NAME_FIRST_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const NAME_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789_";
+const MAX_NAME_LEN: usize = 8;
+
+/// Deterministic pseudo-random number generator (SplitMix64), the same seed generating the same data sets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestRng {
+    state: u64,
+}
+
+impl TestRng {
+
+    /// Creates a generator from a seed.
+    pub fn new(seed: u64) -> TestRng {
+        return TestRng{state: seed};
+    }
+
+    /// Returns the next random `u64`.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z: u64 = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        return z ^ (z >> 31);
+    }
+
+    /// Returns a random number in `0..bound` (`bound` must be positive).
+    pub fn below(&mut self, bound: usize) -> usize {
+        return (self.next_u64() % (bound as u64)) as usize;
+    }
+
+    /// Returns a random number in `min..=max`.
+    pub fn in_range(&mut self, min: usize, max: usize) -> usize {
+        return min + self.below(max - min + 1);
+    }
+
+    /// Returns a random boolean.
+    pub fn gen_bool(&mut self) -> bool {
+        return self.next_u64() & 1 == 1;
+    }
+}
+
+/// Limits of the data sets generated by [`arbitrary_data_set_with`](fn.arbitrary_data_set_with.html).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataSetLimits {
+    /// Maximum number of dimensions, the *unlimited-size* dimension included.
+    pub max_dims: usize,
+    /// Maximum size of the *fixed-size* dimensions.
+    pub max_dim_size: usize,
+    /// Maximum number of records.
+    pub max_records: usize,
+    /// Maximum number of variables.
+    pub max_vars: usize,
+    /// Maximum number of dimensions of each variable.
+    pub max_var_dims: usize,
+    /// Maximum number of attributes, globally and for each variable.
+    pub max_attrs: usize,
+    /// Maximum number of elements of each attribute.
+    pub max_attr_len: usize,
+}
+
+impl Default for DataSetLimits {
+    fn default() -> Self {
+        return DataSetLimits{
+            max_dims: 4,
+            max_dim_size: 5,
+            max_records: 3,
+            max_vars: 5,
+            max_var_dims: 3,
+            max_attrs: 3,
+            max_attr_len: 5,
+        };
+    }
+}
+
+/// Generates a random valid data set with the default limits (see [`DataSetLimits`](struct.DataSetLimits.html)).
+pub fn arbitrary_data_set(rng: &mut TestRng) -> DataSet {
+    return arbitrary_data_set_with(rng, &DataSetLimits::default());
+}
+
+/// Generates a random valid data set : dimensions (optionally the *unlimited-size* one), variables of all the data types,
+/// global and variable attributes.
+pub fn arbitrary_data_set_with(rng: &mut TestRng, limits: &DataSetLimits) -> DataSet {
+    let mut data_set = DataSet::new();
+
+    // Dimensions
+    let num_dims: usize = rng.in_range(0, limits.max_dims);
+    for i in 0..num_dims {
+        let dim_name: String = arbitrary_name(rng, |name: &str| data_set.has_dim(name));
+        if i == 0 && rng.gen_bool() {
+            data_set.set_unlimited_dim(&dim_name, rng.in_range(0, limits.max_records)).unwrap();
+        }
+        else {
+            data_set.add_fixed_dim(&dim_name, rng.in_range(1, limits.max_dim_size)).unwrap();
+        }
+    }
+
+    // Global attributes
+    for _ in 0..rng.in_range(0, limits.max_attrs) {
+        let attr_name: String = arbitrary_name(rng, |name: &str| data_set.has_global_attr(name));
+        let attr_len: usize = rng.in_range(1, limits.max_attr_len);
+        let attr_data_type: DataType = arbitrary_data_type(rng);
+        match arbitrary_data_vector(rng, attr_data_type, attr_len) {
+            DataVector::I8(data) => data_set.add_global_attr_i8(&attr_name, data),
+            DataVector::U8(data) => data_set.add_global_attr_u8(&attr_name, data),
+            DataVector::I16(data) => data_set.add_global_attr_i16(&attr_name, data),
+            DataVector::I32(data) => data_set.add_global_attr_i32(&attr_name, data),
+            DataVector::F32(data) => data_set.add_global_attr_f32(&attr_name, data),
+            DataVector::F64(data) => data_set.add_global_attr_f64(&attr_name, data),
+        }.unwrap();
+    }
+
+    // Variables and their attributes
+    for _ in 0..rng.in_range(0, limits.max_vars) {
+        let var_name: String = arbitrary_name(rng, |name: &str| data_set.has_var(name));
+        let var_dim_names: Vec<String> = {
+            let dims: Vec<Rc<Dimension>> = data_set.get_dims();
+            let (unlimited_dims, mut fixed_size_dims): (Vec<Rc<Dimension>>, Vec<Rc<Dimension>>) = dims.into_iter().partition(|dim: &Rc<Dimension>| dim.is_unlimited());
+            let mut var_dim_names: Vec<String> = vec![];
+            if let Some(unlimited_dim) = unlimited_dims.first() {
+                if rng.gen_bool() {
+                    var_dim_names.push(unlimited_dim.name());
+                }
+            }
+            let num_fixed_size_dims: usize = rng.in_range(0, std::cmp::min(limits.max_var_dims.saturating_sub(var_dim_names.len()), fixed_size_dims.len()));
+            for _ in 0..num_fixed_size_dims {
+                let dim: Rc<Dimension> = fixed_size_dims.remove(rng.below(fixed_size_dims.len()));
+                var_dim_names.push(dim.name());
+            }
+            var_dim_names
+        };
+        let var_data_type: DataType = arbitrary_data_type(rng);
+        data_set.add_var(&var_name, &var_dim_names, var_data_type).unwrap();
+
+        for _ in 0..rng.in_range(0, limits.max_attrs) {
+            let attr_name: String = arbitrary_name(rng, |name: &str| data_set.has_var_attr(&var_name, name) == Some(true));
+            let attr_len: usize = rng.in_range(1, limits.max_attr_len);
+            let attr_data_type: DataType = arbitrary_data_type(rng);
+            match arbitrary_data_vector(rng, attr_data_type, attr_len) {
+                DataVector::I8(data) => data_set.add_var_attr_i8(&var_name, &attr_name, data),
+                DataVector::U8(data) => data_set.add_var_attr_u8(&var_name, &attr_name, data),
+                DataVector::I16(data) => data_set.add_var_attr_i16(&var_name, &attr_name, data),
+                DataVector::I32(data) => data_set.add_var_attr_i32(&var_name, &attr_name, data),
+                DataVector::F32(data) => data_set.add_var_attr_f32(&var_name, &attr_name, data),
+                DataVector::F64(data) => data_set.add_var_attr_f64(&var_name, &attr_name, data),
+            }.unwrap();
+        }
+    }
+    return data_set;
+}
+
+/// Generates random data for each variable of the data set.
+pub fn arbitrary_vars_data(data_set: &DataSet, rng: &mut TestRng) -> HashMap<String, DataVector> {
+    return data_set.vars().iter().map(|var| {
+        (var.name().to_string(), arbitrary_data_vector(rng, var.data_type(), var.len()))
+    }).collect();
+}
+
+/// Generates `len` random finite values of the data type.
+pub fn arbitrary_data_vector(rng: &mut TestRng, data_type: DataType, len: usize) -> DataVector {
+    return match data_type {
+        DataType::I8 => DataVector::I8((0..len).map(|_| rng.next_u64() as i8).collect()),
+        DataType::U8 => DataVector::U8((0..len).map(|_| rng.next_u64() as u8).collect()),
+        DataType::I16 => DataVector::I16((0..len).map(|_| rng.next_u64() as i16).collect()),
+        DataType::I32 => DataVector::I32((0..len).map(|_| rng.next_u64() as i32).collect()),
+        DataType::F32 => DataVector::F32((0..len).map(|_| (rng.next_u64() as i32 as f32) / 1024.0).collect()),
+        DataType::F64 => DataVector::F64((0..len).map(|_| (rng.next_u64() as i64 as f64) / 1024.0).collect()),
+    };
+}
+
+/// Writes the data set without data into memory, reads it back and checks that the header is unchanged
+/// and that the variables contain the default fill values.
+///
+/// Panics if the round trip fails.
+pub fn assert_round_trip(data_set: &DataSet, version: Version) {
+    assert_round_trip_with_data(data_set, &HashMap::new(), version);
+}
+
+/// Writes the data set and the data of its variables into memory, reads them back and checks that they are unchanged.
+///
+/// The variables missing in `vars_data` must contain the default fill values.
+///
+/// Panics if the round trip fails.
+pub fn assert_round_trip_with_data(data_set: &DataSet, vars_data: &HashMap<String, DataVector>, version: Version) {
+    let bytes: Vec<u8> = write_to_bytes(data_set, vars_data, version);
+    let _ = read_back_checked(bytes, data_set, vars_data, version);
+}
+
+/// Writes the data set without data into memory, reads it back and checks that the header is unchanged
+/// and that the variables contain the default fill values, like [`assert_round_trip`](fn.assert_round_trip.html).
+///
+/// The data set and the data read back are then written again, and the bytes of both files are checked to be identical.
+/// Returns the bytes of the file.
+///
+/// Panics if the round trip fails.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::{DataSet, Version};
+/// use netcdf3::testing::assert_round_trip_bytes;
+///
+/// let mut data_set = DataSet::new();
+/// data_set.set_unlimited_dim("time", 2).unwrap();
+/// data_set.add_var_f64("time", &["time"]).unwrap();
+///
+/// let bytes: Vec<u8> = assert_round_trip_bytes(&data_set, Version::Classic);
+/// assert_eq!(b"CDF\x01",             &bytes[0..4]);
+/// ```
+pub fn assert_round_trip_bytes(data_set: &DataSet, version: Version) -> Vec<u8> {
+    let bytes: Vec<u8> = write_to_bytes(data_set, &HashMap::new(), version);
+    let (read_data_set, read_vars_data): (DataSet, HashMap<String, DataVector>) = read_back_checked(bytes.clone(), data_set, &HashMap::new(), version);
+    let rewritten_bytes: Vec<u8> = write_to_bytes(&read_data_set, &read_vars_data, version);
+    assert!(bytes == rewritten_bytes, "the rewritten bytes differ from the written bytes of {:#?}", data_set);
+    return bytes;
+}
+
+/// Writes the data set and the data of its variables into memory.
+fn write_to_bytes(data_set: &DataSet, vars_data: &HashMap<String, DataVector>, version: Version) -> Vec<u8> {
+    let mut file_writer = FileWriter::from_writer(Cursor::new(vec![]));
+    file_writer.set_def(data_set, version, 0).unwrap();
+    for var in data_set.vars().iter() {
+        if let Some(data) = vars_data.get(var.name()) {
+            write_var_data(&mut file_writer, var.name(), data).unwrap();
+        }
+    }
+    return file_writer.close_into_inner().unwrap().into_inner();
+}
+
+/// Reads back the bytes, checks the data set and the data of the variables, and returns them.
+fn read_back_checked(bytes: Vec<u8>, data_set: &DataSet, vars_data: &HashMap<String, DataVector>, version: Version) -> (DataSet, HashMap<String, DataVector>) {
+    let mut file_reader = FileReader::from_reader(Cursor::new(bytes)).unwrap();
+    assert_eq!(version,                         file_reader.version());
+    assert_eq!(data_set,                        file_reader.data_set());
+    let mut read_vars_data: HashMap<String, DataVector> = HashMap::with_capacity(data_set.num_vars());
+    for var in data_set.vars().iter() {
+        let expected_data: DataVector = match vars_data.get(var.name()) {
+            Some(data) => data.clone(),
+            None => fill_values(var.data_type(), var.len()),
+        };
+        let read_data: DataVector = file_reader.read_var(var.name()).unwrap();
+        assert_eq!(expected_data,               read_data, "variable `{}` of {:#?}", var.name(), data_set);
+        read_vars_data.insert(var.name().to_string(), read_data);
+    }
+    return (file_reader.close().0, read_vars_data);
+}
+
+/// Returns `len` default fill values of the data type.
+fn fill_values(data_type: DataType, len: usize) -> DataVector {
+    return match data_type {
+        DataType::I8 => DataVector::I8(vec![NC_FILL_I8; len]),
+        DataType::U8 => DataVector::U8(vec![NC_FILL_U8; len]),
+        DataType::I16 => DataVector::I16(vec![NC_FILL_I16; len]),
+        DataType::I32 => DataVector::I32(vec![NC_FILL_I32; len]),
+        DataType::F32 => DataVector::F32(vec![NC_FILL_F32; len]),
+        DataType::F64 => DataVector::F64(vec![NC_FILL_F64; len]),
+    };
+}
+
+fn arbitrary_data_type(rng: &mut TestRng) -> DataType {
+    return DATA_TYPES[rng.below(DATA_TYPES.len())];
+}
+
+/// Generates a random valid name, not used yet.
+fn arbitrary_name(rng: &mut TestRng, is_used: impl Fn(&str) -> bool) -> String {
+    loop {
+        let len: usize = rng.in_range(1, MAX_NAME_LEN);
+        let name: String = (0..len).map(|i: usize| {
+            let chars: &[u8] = if i == 0 { NAME_FIRST_CHARS } else { NAME_CHARS };
+            chars[rng.below(chars.len())] as char
+        }).collect();
+        if !is_used(&name) {
+            return name;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::HashMap;
+
+    use super::{arbitrary_data_set, arbitrary_data_set_with, arbitrary_vars_data, assert_round_trip, assert_round_trip_bytes, assert_round_trip_with_data, DataSetLimits, TestRng};
+    use crate::{DataSet, DataVector, Version};
+
+    #[test]
+    fn test_test_rng() {
+        let mut rng_1 = TestRng::new(7);
+        let mut rng_2 = TestRng::new(7);
+        let values_1: Vec<u64> = (0..10).map(|_| rng_1.next_u64()).collect();
+        let values_2: Vec<u64> = (0..10).map(|_| rng_2.next_u64()).collect();
+        assert_eq!(values_1,                    values_2);
+        assert_eq!(true,                        (0..100).all(|_| rng_1.in_range(2, 4) >= 2 && rng_1.in_range(2, 4) <= 4));
+    }
+
+    #[test]
+    fn test_round_trips() {
+        let mut rng = TestRng::new(0);
+        for _ in 0..100 {
+            let data_set: DataSet = arbitrary_data_set(&mut rng);
+            let vars_data: HashMap<String, DataVector> = arbitrary_vars_data(&data_set, &mut rng);
+            assert_round_trip(&data_set, Version::Classic);
+            let bytes: Vec<u8> = assert_round_trip_bytes(&data_set, Version::Offset64Bit);
+            assert_eq!(&b"CDF\x02"[..],            &bytes[0..4]);
+            assert_round_trip_with_data(&data_set, &vars_data, Version::Classic);
+            assert_round_trip_with_data(&data_set, &vars_data, Version::Offset64Bit);
+        }
+    }
+
+    #[test]
+    fn test_round_trips_with_limits() {
+        let limits = DataSetLimits{max_dims: 6, max_dim_size: 3, max_records: 5, max_vars: 10, max_var_dims: 6, max_attrs: 5, max_attr_len: 9};
+        let mut rng = TestRng::new(1);
+        for _ in 0..50 {
+            let data_set: DataSet = arbitrary_data_set_with(&mut rng, &limits);
+            let vars_data: HashMap<String, DataVector> = arbitrary_vars_data(&data_set, &mut rng);
+            assert_round_trip_with_data(&data_set, &vars_data, Version::Offset64Bit);
+        }
+    }
+}
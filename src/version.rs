@@ -1,5 +1,5 @@
 #[repr(u8)]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /// NetCDF-3 file versions (classic or 64-bit offset)
 pub enum Version {
     /// Classic format (use `i32` for the begin offsets)
@@ -8,7 +8,46 @@ pub enum Version {
     Offset64Bit = 2,
 }
 
-impl std::convert::TryFrom<u8> for Version {
+impl Version {
+
+    /// Returns the maximum chunk size (number of bytes, zero padding included) of the variables, except the last ones
+    /// (see [`DataSet::check_format_limits`](struct.DataSet.html#method.check_format_limits)).
+    pub fn max_chunk_size(&self) -> usize {
+        return match self {
+            Version::Classic => (core::i32::MAX - 3) as usize,
+            Version::Offset64Bit => (core::u32::MAX - 3) as usize,
+        };
+    }
+
+    /// Returns the maximum begin offset of the variables.
+    pub fn max_begin_offset(&self) -> u64 {
+        return match self {
+            Version::Classic => core::i32::MAX as u64,
+            Version::Offset64Bit => core::i64::MAX as u64,
+        };
+    }
+}
+
+/// Formats the name of the version printed by `ncdump -k`.
+///
+/// # Example
+///
+/// ```
+/// use netcdf3::Version;
+///
+/// assert_eq!("classic",           Version::Classic.to_string());
+/// assert_eq!("64-bit offset",     Version::Offset64Bit.to_string());
+/// ```
+impl core::fmt::Display for Version {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        return f.write_str(match self {
+            Version::Classic => "classic",
+            Version::Offset64Bit => "64-bit offset",
+        });
+    }
+}
+
+impl core::convert::TryFrom<u8> for Version {
 
     type Error = &'static str;
 
@@ -24,7 +63,7 @@ impl std::convert::TryFrom<u8> for Version {
 #[cfg(test)]
 mod tests
 {
-    use std::convert::TryFrom;
+    use core::convert::TryFrom;
     use super::Version;
 
     #[test]
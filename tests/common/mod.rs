@@ -0,0 +1,51 @@
+//! Helpers shared by the test modules running the command line tools.
+// each test module uses only a part of these items
+#![allow(dead_code)]
+
+use std::ffi::OsStr;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+use tempdir::TempDir;
+
+use copy_to_tmp_file::copy_bytes_to_tmp_file;
+
+/// Copy of a test file in a temporary directory, removed when the fixture is dropped.
+pub struct Fixture {
+    pub tmp_dir: TempDir,
+    pub input_file_path: PathBuf,
+}
+
+impl Fixture {
+
+    /// Copies the bytes of a test file into the temporary file `file_name`.
+    pub fn new(bytes: &[u8], file_name: &str) -> Fixture {
+        let (tmp_dir, input_file_path): (TempDir, PathBuf) = copy_bytes_to_tmp_file(bytes, file_name);
+        return Fixture{
+            tmp_dir: tmp_dir,
+            input_file_path: input_file_path,
+        };
+    }
+
+    /// Returns the path of the file `file_name` in the temporary directory.
+    pub fn path(&self, file_name: &str) -> PathBuf {
+        return self.tmp_dir.path().join(file_name);
+    }
+}
+
+/// Runs the command line tool located at `tool_path` (see `env!("CARGO_BIN_EXE_<name>")`).
+pub fn run_tool(tool_path: &str, args: &[&dyn AsRef<OsStr>]) -> Output {
+    return Command::new(tool_path).args(args.iter().map(|arg: &&dyn AsRef<OsStr>| arg.as_ref())).output().unwrap();
+}
+
+/// Returns the standard output of the tool, which must have succeeded.
+pub fn stdout(output: &Output) -> String {
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    return String::from_utf8(output.stdout.clone()).unwrap();
+}
+
+/// Returns the standard error of the tool, which must have failed with the exit code `code`.
+pub fn stderr(output: &Output, code: i32) -> String {
+    assert_eq!(Some(code),      output.status.code());
+    return String::from_utf8(output.stderr.clone()).unwrap();
+}
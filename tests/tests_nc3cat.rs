@@ -0,0 +1,42 @@
+#![cfg(all(feature = "fs", feature = "read", feature = "write"))]
+//! This test module runs the command line tool `nc3cat`.
+mod common;
+
+use std::path::PathBuf;
+use std::process::Output;
+
+use netcdf3::{FileReader, Version};
+
+use copy_to_tmp_file::{
+    NC3_CLASSIC_FILE_NAME, NC3_CLASSIC_FILE_BYTES,
+    SCALAR_VARIABLES_FILE_NAME, SCALAR_VARIABLES_FILE_BYTES,
+};
+
+use common::{run_tool, stderr, stdout, Fixture};
+
+const NC3CAT: &str = env!("CARGO_BIN_EXE_nc3cat");
+
+#[test]
+fn test_nc3cat() {
+    let fixture = Fixture::new(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    let input_file_path: &PathBuf = &fixture.input_file_path;
+    let output_file_path: PathBuf = fixture.path("concat.nc");
+
+    let output: Output = run_tool(NC3CAT, &[input_file_path, input_file_path, input_file_path, &output_file_path]);
+    stdout(&output);
+
+    let mut file_reader = FileReader::open(&output_file_path).unwrap();
+    assert_eq!(Version::Classic,                    file_reader.version());
+    assert_eq!(Some(6),                             file_reader.data_set().num_records());
+    assert_eq!(vec![438300.0, 438324.0, 438300.0, 438324.0, 438300.0, 438324.0],    file_reader.read_var_f32("time").unwrap());
+}
+
+#[test]
+fn test_nc3cat_incompatible_files() {
+    let fixture_1 = Fixture::new(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    let fixture_2 = Fixture::new(SCALAR_VARIABLES_FILE_BYTES, SCALAR_VARIABLES_FILE_NAME);
+    let output_file_path: PathBuf = fixture_1.path("concat.nc");
+
+    let output: Output = run_tool(NC3CAT, &[&fixture_1.input_file_path, &fixture_2.input_file_path, &output_file_path]);
+    assert!(stderr(&output, 1).contains("the dimension `latitude`"));
+}
@@ -0,0 +1,57 @@
+#![cfg(all(feature = "fs", feature = "read", feature = "write"))]
+//! This test module runs the command line tool `nc3copy`.
+mod common;
+
+use std::path::PathBuf;
+use std::process::Output;
+
+use netcdf3::{FileReader, Version};
+
+use copy_to_tmp_file::{
+    NC3_CLASSIC_FILE_NAME, NC3_CLASSIC_FILE_BYTES,
+};
+
+use common::{run_tool, stderr, stdout, Fixture};
+
+const NC3COPY: &str = env!("CARGO_BIN_EXE_nc3copy");
+
+#[test]
+fn test_nc3copy_identity() {
+    let fixture = Fixture::new(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    let output_file_path: PathBuf = fixture.path("copy.nc");
+
+    stdout(&run_tool(NC3COPY, &[&fixture.input_file_path, &output_file_path]));
+    assert_eq!(NC3_CLASSIC_FILE_BYTES.to_vec(),         std::fs::read(&output_file_path).unwrap());
+}
+
+#[test]
+fn test_nc3copy_options() {
+    let fixture = Fixture::new(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    let output_file_path: PathBuf = fixture.path("copy.nc");
+
+    stdout(&run_tool(NC3COPY, &[
+        &"--kind", &"64-bit-offset", &"--header-reserve", &"2000", &"--vars", &"latitude,temperature_f32", &"--align", &"4096",
+        &fixture.input_file_path, &output_file_path,
+    ]));
+
+    let mut input_reader = FileReader::open(&fixture.input_file_path).unwrap();
+    let mut copy_reader = FileReader::open(&output_file_path).unwrap();
+    assert_eq!(Version::Offset64Bit,                                copy_reader.version());
+    assert_eq!(vec!["latitude", "temperature_f32"],                 copy_reader.data_set().get_var_names());
+    assert_eq!(Some(vec![(4096, 12)]),                              copy_reader.var_byte_ranges("latitude"));
+    assert_eq!(input_reader.read_var("temperature_f32").unwrap(),   copy_reader.read_var("temperature_f32").unwrap());
+}
+
+#[test]
+fn test_nc3copy_errors() {
+    let fixture = Fixture::new(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    let input_file_path: PathBuf = fixture.path("undef_file.nc");
+    let output_file_path: PathBuf = fixture.path("copy.nc");
+
+    // invalid command line
+    stderr(&run_tool(NC3COPY, &[&input_file_path]), 2);
+
+    // missing input file
+    let output: Output = run_tool(NC3COPY, &[&input_file_path, &output_file_path]);
+    assert!(!stderr(&output, 1).is_empty());
+}
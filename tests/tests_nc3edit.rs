@@ -0,0 +1,63 @@
+#![cfg(all(feature = "fs", feature = "read", feature = "write"))]
+//! This test module runs the command line tool `nc3edit`.
+mod common;
+
+use std::io::Cursor;
+use std::path::PathBuf;
+
+use netcdf3::FileReader;
+
+use copy_to_tmp_file::{
+    NC3_CLASSIC_FILE_NAME, NC3_CLASSIC_FILE_BYTES,
+};
+
+use common::{run_tool, stderr, stdout, Fixture};
+
+const NC3EDIT: &str = env!("CARGO_BIN_EXE_nc3edit");
+
+#[test]
+fn test_nc3edit() {
+    let fixture = Fixture::new(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+
+    stdout(&run_tool(NC3EDIT, &[
+        &"-v", &"temperature_f32,tas", &"-d", &"latitude,lat", &"-r", &"tas,units,unit",
+        &"-a", &"long_name,tas,o,c,Air temperature", &"-a", &"valid_range,tas,c,f,-50,50", &"-a", &"title,global,d",
+        &fixture.input_file_path,
+    ]));
+
+    let mut file_reader = FileReader::open(&fixture.input_file_path).unwrap();
+    let mut ref_reader = FileReader::from_reader(Cursor::new(NC3_CLASSIC_FILE_BYTES)).unwrap();
+    assert_eq!(false,                                           file_reader.data_set().has_var("temperature_f32"));
+    assert_eq!(vec!["lat", "longitude", "time"],                file_reader.data_set().dim_names());
+    assert_eq!(Some(String::from("Air temperature")),           file_reader.data_set().get_var_attr_as_string("tas", "long_name"));
+    assert_eq!(Some(&[-50.0, 50.0][..]),                        file_reader.data_set().get_var_attr_f32("tas", "valid_range"));
+    assert_eq!(false,                                           file_reader.data_set().has_global_attr("title"));
+    assert_eq!(ref_reader.read_var("temperature_f32").unwrap(), file_reader.read_var("tas").unwrap());
+    assert_eq!(ref_reader.read_var("time").unwrap(),            file_reader.read_var("time").unwrap());
+    // the temporary file has been renamed over the edited file
+    assert_eq!(false,                                           fixture.path(&format!("{}.edit.tmp", NC3_CLASSIC_FILE_NAME)).exists());
+}
+
+#[test]
+fn test_nc3edit_output() {
+    let fixture = Fixture::new(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    let output_file_path: PathBuf = fixture.path("edited.nc");
+
+    stdout(&run_tool(NC3EDIT, &[&"-a", &"history,global,o,c,edited with nc3edit", &fixture.input_file_path, &"-o", &output_file_path]));
+    assert_eq!(NC3_CLASSIC_FILE_BYTES.to_vec(),                 std::fs::read(&fixture.input_file_path).unwrap());
+
+    let file_reader = FileReader::open(&output_file_path).unwrap();
+    assert_eq!(Some(String::from("edited with nc3edit")),       file_reader.data_set().get_global_attr_as_string("history"));
+}
+
+#[test]
+fn test_nc3edit_errors() {
+    let fixture = Fixture::new(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+
+    // invalid command line
+    stderr(&run_tool(NC3EDIT, &[&fixture.input_file_path]), 2);
+
+    // undefined variable, the file is not modified
+    stderr(&run_tool(NC3EDIT, &[&"-v", &"undef_var,var", &fixture.input_file_path]), 1);
+    assert_eq!(NC3_CLASSIC_FILE_BYTES.to_vec(),                 std::fs::read(&fixture.input_file_path).unwrap());
+}
@@ -0,0 +1,38 @@
+#![cfg(all(feature = "fs", feature = "read"))]
+//! This test module runs the command line tool `nc3repair`.
+mod common;
+
+use std::path::PathBuf;
+
+use netcdf3::FileReader;
+
+use copy_to_tmp_file::{
+    NC3_CLASSIC_FILE_NAME, NC3_CLASSIC_FILE_BYTES,
+};
+
+use common::{run_tool, stdout, Fixture};
+
+const NC3REPAIR: &str = env!("CARGO_BIN_EXE_nc3repair");
+
+#[test]
+fn test_nc3repair() {
+    // the last record has been partially written
+    let truncated_bytes: &[u8] = &NC3_CLASSIC_FILE_BYTES[0..(NC3_CLASSIC_FILE_BYTES.len() - 100)];
+    let fixture = Fixture::new(truncated_bytes, NC3_CLASSIC_FILE_NAME);
+    let input_file_path: &PathBuf = &fixture.input_file_path;
+    let output_file_path: PathBuf = fixture.path("repaired.nc");
+
+    // into another file
+    stdout(&run_tool(NC3REPAIR, &[input_file_path, &"--output", &output_file_path]));
+    assert_eq!(truncated_bytes.to_vec(),                std::fs::read(input_file_path).unwrap());
+    assert_eq!(NC3_CLASSIC_FILE_BYTES.len(),            std::fs::read(&output_file_path).unwrap().len());
+    let mut file_reader = FileReader::open(&output_file_path).unwrap();
+    assert_eq!(vec![438300.0, 438324.0],                file_reader.read_var_f32("time").unwrap());
+
+    // in place
+    stdout(&run_tool(NC3REPAIR, &[input_file_path]));
+    assert_eq!(std::fs::read(&output_file_path).unwrap(),      std::fs::read(input_file_path).unwrap());
+
+    // nothing more to repair
+    assert!(stdout(&run_tool(NC3REPAIR, &[input_file_path])).contains("nothing to repair"));
+}
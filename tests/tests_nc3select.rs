@@ -0,0 +1,42 @@
+#![cfg(all(feature = "fs", feature = "read", feature = "write"))]
+//! This test module runs the command line tool `nc3select`.
+mod common;
+
+use std::path::PathBuf;
+
+use netcdf3::FileReader;
+
+use copy_to_tmp_file::{
+    NC3_CLASSIC_FILE_NAME, NC3_CLASSIC_FILE_BYTES,
+};
+
+use common::{run_tool, stderr, stdout, Fixture};
+
+const NC3SELECT: &str = env!("CARGO_BIN_EXE_nc3select");
+
+#[test]
+fn test_nc3select() {
+    let fixture = Fixture::new(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    let output_file_path: PathBuf = fixture.path("subset.nc");
+
+    stdout(&run_tool(NC3SELECT, &[
+        &"-v", &"time,temperature_i32", &"-d", &"time,1,1", &"-d", &"longitude,1,3",
+        &fixture.input_file_path, &output_file_path,
+    ]));
+
+    let mut file_reader = FileReader::open(&output_file_path).unwrap();
+    assert_eq!(vec!["time", "temperature_i32"],             file_reader.data_set().get_var_names());
+    assert_eq!(vec!["latitude", "longitude", "time"],       file_reader.data_set().dim_names());
+    assert_eq!(Some(3),                                     file_reader.data_set().dim_size("longitude"));
+    assert_eq!(vec![438324.0],                              file_reader.read_var_f32("time").unwrap());
+    assert_eq!(vec![16, 17, 18, 21, 22, 23, 26, 27, 28],    file_reader.read_var_i32("temperature_i32").unwrap());
+}
+
+#[test]
+fn test_nc3select_invalid_dim_slice() {
+    let fixture = Fixture::new(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    let output_file_path: PathBuf = fixture.path("subset.nc");
+
+    let message: String = stderr(&run_tool(NC3SELECT, &[&"-d", &"latitude,2,5", &fixture.input_file_path, &output_file_path]), 1);
+    assert!(message.contains("invalid range `latitude,2,5` of the dimension `latitude` of size 3"));
+}
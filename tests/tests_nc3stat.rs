@@ -0,0 +1,53 @@
+#![cfg(all(feature = "fs", feature = "read"))]
+//! This test module runs the command line tool `nc3stat`.
+mod common;
+
+use copy_to_tmp_file::{
+    NC3_CLASSIC_FILE_NAME, NC3_CLASSIC_FILE_BYTES,
+    NC3_FILL_VALUES_FILE_NAME, NC3_FILL_VALUES_FILE_BYTES,
+    EMPTY_DATA_SET_FILE_NAME, EMPTY_DATA_SET_FILE_BYTES,
+};
+
+use common::{run_tool, stderr, stdout, Fixture};
+
+const NC3STAT: &str = env!("CARGO_BIN_EXE_nc3stat");
+
+#[test]
+fn test_nc3stat_layout() {
+    let fixture = Fixture::new(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+
+    let report: String = stdout(&run_tool(NC3STAT, &[&fixture.input_file_path]));
+    assert!(report.contains("version: classic\n"));
+    assert!(report.contains("header size: 1684 bytes\n"));
+    assert!(report.contains("header used size: 1684 bytes\n"));
+    assert!(report.contains("record size: 308 bytes\n"));
+    assert!(report.contains("number of records: 2\n"));
+    assert!(report.contains("\nlatitude         NC_FLOAT   [3]        1684    12    0       1       0.5     0\n"));
+    assert!(report.contains("\ntemperature_f64  NC_DOUBLE  [2, 3, 5]  1904    240   0       29      14.5    0\n"));
+}
+
+#[test]
+fn test_nc3stat_empty_data_set() {
+    let fixture = Fixture::new(EMPTY_DATA_SET_FILE_BYTES, EMPTY_DATA_SET_FILE_NAME);
+
+    let report: String = stdout(&run_tool(NC3STAT, &[&fixture.input_file_path]));
+    // the whole file is the header
+    assert!(report.contains(&format!("header size: {} bytes\n", EMPTY_DATA_SET_FILE_BYTES.len())));
+    assert!(report.contains("header used size: 32 bytes\n"));
+    assert!(report.contains("record size: -\n"));
+    assert!(report.ends_with("variable  type  shape  offset  size  min  max  mean  missing\n"));
+}
+
+#[test]
+fn test_nc3stat_missing_values() {
+    let fixture = Fixture::new(NC3_FILL_VALUES_FILE_BYTES, NC3_FILL_VALUES_FILE_NAME);
+
+    let report: String = stdout(&run_tool(NC3STAT, &[&"--vars", &"nc_fill_value_i8,nc_fill_value_f32", &fixture.input_file_path]));
+    assert!(report.ends_with(concat!(
+        "variable           type      shape  offset  size  min   max   mean  missing\n",
+        "nc_fill_value_i8   NC_BYTE   []     312     1     -127  -127  -127  0\n",
+        "nc_fill_value_f32  NC_FLOAT  []     328     4     -     -     -     1\n",
+    )));
+
+    stderr(&run_tool(NC3STAT, &[&"--vars", &"undef_var", &fixture.input_file_path]), 1);
+}
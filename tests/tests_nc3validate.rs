@@ -0,0 +1,45 @@
+#![cfg(all(feature = "fs", feature = "read"))]
+//! This test module runs the command line tool `nc3validate`.
+mod common;
+
+use std::path::PathBuf;
+
+use copy_to_tmp_file::{
+    NC3_CLASSIC_FILE_NAME, NC3_CLASSIC_FILE_BYTES,
+};
+
+use common::{run_tool, stderr, stdout, Fixture};
+
+const NC3VALIDATE: &str = env!("CARGO_BIN_EXE_nc3validate");
+
+#[test]
+fn test_nc3validate() {
+    let fixture = Fixture::new(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    let input_file_path: &PathBuf = &fixture.input_file_path;
+
+    assert_eq!(format!("{}: ok\n", input_file_path.display()),     stdout(&run_tool(NC3VALIDATE, &[input_file_path])));
+    assert_eq!("",                                                  stdout(&run_tool(NC3VALIDATE, &[&"-q", input_file_path])));
+}
+
+#[test]
+fn test_nc3validate_invalid_files() {
+    let fixture = Fixture::new(NC3_CLASSIC_FILE_BYTES, NC3_CLASSIC_FILE_NAME);
+    let truncated_file_path: PathBuf = fixture.path("truncated.nc");
+    std::fs::write(&truncated_file_path, &NC3_CLASSIC_FILE_BYTES[0..2000]).unwrap();
+    let not_nc3_file_path: PathBuf = fixture.path("not_nc3.nc");
+    std::fs::write(&not_nc3_file_path, b"not a NetCDF-3 file").unwrap();
+
+    let output = run_tool(NC3VALIDATE, &[&"--quiet", &fixture.input_file_path, &truncated_file_path, &not_nc3_file_path]);
+    stderr(&output, 1);
+    let stdout: String = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(2,                                                   lines.len());
+    assert_eq!(
+        format!("{}: the file is truncated, 2000 bytes instead of 2332 bytes", truncated_file_path.display()),
+        lines[0]
+    );
+    assert!(lines[1].starts_with(&format!("{}: invalid header", not_nc3_file_path.display())));
+
+    // invalid command line
+    stderr(&run_tool(NC3VALIDATE, &[]), 2);
+}
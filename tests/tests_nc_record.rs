@@ -0,0 +1,104 @@
+#![cfg(feature = "derive")]
+//! This test module checks the implementations of `NcRecord` generated by `#[derive(NcRecord)]`.
+use std::io::Cursor;
+
+use netcdf3::{DataSet, DataType, FileReader, FileWriter, InvalidDataSet, NcRecord, ReadError, Version, WriteError};
+
+#[derive(Debug, Clone, PartialEq, NcRecord)]
+struct Telemetry {
+    time: f64,
+    altitude: f32,
+    #[nc(rename = "battery_level")]
+    battery: u8,
+    status: i8,
+    temperature: i16,
+    num_satellites: i32,
+    r#type: i8,
+}
+
+fn telemetry(i: usize) -> Telemetry {
+    return Telemetry{
+        time: i as f64,
+        altitude: 100.0 + i as f32,
+        battery: 100 - i as u8,
+        status: -(i as i8),
+        temperature: 20 + i as i16,
+        num_satellites: 8,
+        r#type: 1,
+    };
+}
+
+#[test]
+fn test_nc_record_add_vars() {
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim("record", 0).unwrap();
+    Telemetry::add_vars(&mut data_set, "record").unwrap();
+
+    assert_eq!(
+        vec!["time", "altitude", "battery_level", "status", "temperature", "num_satellites", "type"],
+        data_set.get_var_names()
+    );
+    assert_eq!(Some(DataType::F64),                 data_set.var_data_type("time"));
+    assert_eq!(Some(DataType::U8),                  data_set.var_data_type("battery_level"));
+    assert_eq!(Some(DataType::I32),                 data_set.var_data_type("num_satellites"));
+    assert_eq!(vec!["record"],                      data_set["altitude"].dim_names());
+
+    // the variables are already defined
+    assert_eq!(
+        InvalidDataSet::VariableAlreadyExists(String::from("time")),
+        Telemetry::add_vars(&mut data_set, "record").unwrap_err()
+    );
+}
+
+#[test]
+fn test_nc_record_write_read() {
+    const NUM_RECORDS: usize = 3;
+    let records: Vec<Telemetry> = (0..NUM_RECORDS).map(telemetry).collect();
+
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim("record", NUM_RECORDS).unwrap();
+    Telemetry::add_vars(&mut data_set, "record").unwrap();
+
+    let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    for (record_index, record) in records.iter().enumerate() {
+        record.write_record(&mut file_writer, record_index).unwrap();
+    }
+    assert_eq!(
+        WriteError::RecordIndexExceeded{index: NUM_RECORDS, num_records: NUM_RECORDS},
+        records[0].write_record(&mut file_writer, NUM_RECORDS).unwrap_err()
+    );
+
+    let mut file_reader = FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+    assert_eq!(vec![100.0, 101.0, 102.0],           file_reader.read_var_f32("altitude").unwrap());
+    assert_eq!(vec![100, 99, 98],                   file_reader.read_var_u8("battery_level").unwrap());
+    for (record_index, record) in records.iter().enumerate() {
+        assert_eq!(*record,                         Telemetry::read_record(&mut file_reader, record_index).unwrap());
+    }
+    assert_eq!(
+        ReadError::RecordIndexExceeded{index: NUM_RECORDS, num_records: NUM_RECORDS},
+        Telemetry::read_record(&mut file_reader, NUM_RECORDS).unwrap_err()
+    );
+}
+
+#[test]
+fn test_nc_record_mismatch_data_type() {
+    let mut data_set = DataSet::new();
+    data_set.set_unlimited_dim("record", 1).unwrap();
+    Telemetry::add_vars(&mut data_set, "record").unwrap();
+    data_set.remove_var("status").unwrap();
+    data_set.add_var_i32("status", &["record"]).unwrap();
+
+    let mut file_writer = FileWriter::from_writer(Cursor::new(Vec::<u8>::new()));
+    file_writer.set_def(&data_set, Version::Classic, 0).unwrap();
+    assert_eq!(
+        WriteError::VariableMismatchDataType{var_name: String::from("status"), req: DataType::I32, get: DataType::I8},
+        telemetry(0).write_record(&mut file_writer, 0).unwrap_err()
+    );
+
+    let mut file_reader = FileReader::from_reader(file_writer.close_into_inner().unwrap()).unwrap();
+    assert_eq!(
+        ReadError::VariableMismatchDataType{var_name: String::from("status"), req: DataType::I32, get: DataType::I8},
+        Telemetry::read_record(&mut file_reader, 0).unwrap_err()
+    );
+}
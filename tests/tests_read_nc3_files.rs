@@ -1,4 +1,4 @@
-#![cfg(test)]
+#![cfg(all(feature = "fs", feature = "read"))]
 use std::rc::Rc;
 
 use copy_to_tmp_file::{
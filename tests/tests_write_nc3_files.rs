@@ -1,4 +1,4 @@
-#![cfg(test)]
+#![cfg(all(feature = "fs", feature = "read", feature = "write"))]
 //! This test module checks the binary diff beetween 2 NetCDF-3 files.
 //!
 //! One of theses file has been produced with the Rust crate `netcdf3`